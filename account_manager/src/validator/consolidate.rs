@@ -0,0 +1,296 @@
+use crate::validator::exit::{
+    get_current_epoch, get_geneisis_data, get_validator_data, is_syncing, BEACON_SERVER_FLAG,
+    DEFAULT_BEACON_NODE,
+};
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use environment::Environment;
+use eth2::{
+    types::{ValidatorData, ValidatorStatus},
+    BeaconNodeHttpClient, Timeouts,
+};
+use sensitive_url::SensitiveUrl;
+use serde::Serialize;
+use std::fs::OpenOptions;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use types::{ChainSpec, ConsolidationRequest, EthSpec, PublicKeyBytes};
+
+pub const CMD: &str = "consolidate";
+pub const SOURCE_PUBKEY_FLAG: &str = "source-pubkey";
+pub const TARGET_PUBKEY_FLAG: &str = "target-pubkey";
+pub const OUTPUT_FLAG: &str = "output-path";
+
+pub fn cli_app() -> Command {
+    Command::new(CMD)
+        .about(
+            "Creates an EIP-7251 consolidation request which consolidates the balance of the \
+            source validator into the target validator. The request must be submitted to the \
+            consolidation request contract in a transaction sent from the source validator's \
+            withdrawal address.",
+        )
+        .arg(
+            Arg::new(SOURCE_PUBKEY_FLAG)
+                .long(SOURCE_PUBKEY_FLAG)
+                .value_name("PUBKEY")
+                .help("The voting public key of the validator whose balance is consolidated")
+                .action(ArgAction::Set)
+                .required(true)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(TARGET_PUBKEY_FLAG)
+                .long(TARGET_PUBKEY_FLAG)
+                .value_name("PUBKEY")
+                .help("The voting public key of the validator which receives the balance")
+                .action(ArgAction::Set)
+                .required(true)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(OUTPUT_FLAG)
+                .long(OUTPUT_FLAG)
+                .value_name("PATH")
+                .help("The path at which to write the consolidation request as JSON")
+                .action(ArgAction::Set)
+                .required(true)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(BEACON_SERVER_FLAG)
+                .long(BEACON_SERVER_FLAG)
+                .value_name("NETWORK_ADDRESS")
+                .help("Address to a beacon node HTTP API")
+                .default_value(DEFAULT_BEACON_NODE)
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+}
+
+/// A consolidation request, along with the input data of the transaction which submits it.
+#[derive(Debug, PartialEq, Serialize)]
+struct ConsolidationRequestOutput {
+    #[serde(flatten)]
+    request: ConsolidationRequest,
+    /// The source and target public keys, concatenated as expected by the consolidation request
+    /// contract.
+    #[serde(with = "serde_utils::hex_vec")]
+    calldata: Vec<u8>,
+}
+
+impl From<ConsolidationRequest> for ConsolidationRequestOutput {
+    fn from(request: ConsolidationRequest) -> Self {
+        let mut calldata = request.source_pubkey.serialize().to_vec();
+        calldata.extend_from_slice(&request.target_pubkey.serialize());
+        Self { request, calldata }
+    }
+}
+
+pub fn cli_run<E: EthSpec>(matches: &ArgMatches, env: Environment<E>) -> Result<(), String> {
+    let source_pubkey: PublicKeyBytes = clap_utils::parse_required(matches, SOURCE_PUBKEY_FLAG)?;
+    let target_pubkey: PublicKeyBytes = clap_utils::parse_required(matches, TARGET_PUBKEY_FLAG)?;
+    let output_path: PathBuf = clap_utils::parse_required(matches, OUTPUT_FLAG)?;
+
+    let spec = env.eth2_config().spec.clone();
+    let electra_fork_epoch = spec.electra_fork_epoch.ok_or(
+        "Consolidations require the Electra fork, which is not scheduled for this network",
+    )?;
+
+    if output_path.exists() {
+        return Err(format!("{:?} already exists", output_path));
+    }
+
+    let server_url: String = clap_utils::parse_required(matches, BEACON_SERVER_FLAG)?;
+    let client = BeaconNodeHttpClient::new(
+        SensitiveUrl::parse(&server_url)
+            .map_err(|e| format!("Failed to parse beacon http server: {:?}", e))?,
+        Timeouts::set_all(Duration::from_secs(spec.seconds_per_slot)),
+    );
+
+    let eth2_network_config = env
+        .eth2_network_config
+        .clone()
+        .expect("network should have a valid config");
+    let network_genesis_root = eth2_network_config
+        .genesis_validators_root::<E>()?
+        .ok_or("Genesis state is unknown")?;
+
+    let source_pk = source_pubkey
+        .decompress()
+        .map_err(|e| format!("Invalid source public key: {:?}", e))?;
+    let target_pk = target_pubkey
+        .decompress()
+        .map_err(|e| format!("Invalid target public key: {:?}", e))?;
+
+    let request = env.runtime().block_on(async {
+        let genesis_data = get_geneisis_data(&client).await?;
+        // Verify that the beacon node and validators are on the same network.
+        if genesis_data.genesis_validators_root != network_genesis_root {
+            return Err(
+                "Invalid genesis state. Please ensure that your beacon node is on the same \
+                 network as the validators you are consolidating"
+                    .to_string(),
+            );
+        }
+
+        if is_syncing(&client).await? {
+            return Err("Beacon node is still syncing".to_string());
+        }
+
+        let epoch = get_current_epoch::<E>(genesis_data.genesis_time, &spec)
+            .ok_or("Failed to get current epoch. Please check your system time")?;
+        if epoch < electra_fork_epoch {
+            return Err(format!(
+                "Consolidation requests are not processed until the Electra fork at epoch {}, \
+                 the current epoch is {}",
+                electra_fork_epoch, epoch
+            ));
+        }
+
+        let source = get_validator_data(&client, &source_pk).await?;
+        let target = get_validator_data(&client, &target_pk).await?;
+        consolidation_request(&source, &target, &spec)
+    })?;
+
+    let output = ConsolidationRequestOutput::from(request);
+    write_consolidation_request(&output_path, &output)?;
+
+    eprintln!(
+        "Wrote a request to consolidate validator {:?} into validator {:?} to {:?}. Submit it \
+         to the consolidation request contract from the withdrawal address {:?}, using the \
+         calldata in the file.",
+        output.request.source_pubkey,
+        output.request.target_pubkey,
+        output_path,
+        output.request.source_address,
+    );
+
+    Ok(())
+}
+
+/// Returns the request which consolidates `source` into `target`, after checking that it would
+/// be accepted by `process_consolidation_request`.
+fn consolidation_request(
+    source: &ValidatorData,
+    target: &ValidatorData,
+    spec: &ChainSpec,
+) -> Result<ConsolidationRequest, String> {
+    if source.index == target.index {
+        return Err("The source and target validators must be different".to_string());
+    }
+
+    for (name, data) in [("Source", source), ("Target", target)] {
+        if data.status != ValidatorStatus::ActiveOngoing {
+            return Err(format!(
+                "{} validator {} must be active and not exiting. Validator status: {:?}",
+                name, data.index, data.status
+            ));
+        }
+        if !data.validator.has_execution_withdrawal_credential(spec) {
+            return Err(format!(
+                "{} validator {} does not have execution withdrawal credentials",
+                name, data.index
+            ));
+        }
+    }
+
+    let source_address = source
+        .validator
+        .get_execution_withdrawal_address(spec)
+        .ok_or_else(|| {
+            format!(
+                "Source validator {} does not have an execution withdrawal address",
+                source.index
+            )
+        })?;
+
+    Ok(ConsolidationRequest {
+        source_address,
+        source_pubkey: source.validator.pubkey,
+        target_pubkey: target.validator.pubkey,
+    })
+}
+
+/// Writes `output` to `path` as JSON, refusing to overwrite an existing file.
+fn write_consolidation_request(
+    path: &Path,
+    output: &ConsolidationRequestOutput,
+) -> Result<(), String> {
+    let file = OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)
+        .map_err(|e| format!("Unable to create {:?}: {}", path, e))?;
+    serde_json::to_writer_pretty(file, output)
+        .map_err(|e| format!("Unable to write {:?}: {:?}", path, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::{Address, Epoch, Hash256, Keypair, MainnetEthSpec, Validator};
+
+    fn validator_data(index: u64, withdrawal_credentials: Hash256) -> ValidatorData {
+        ValidatorData {
+            index,
+            balance: 32_000_000_000,
+            status: ValidatorStatus::ActiveOngoing,
+            validator: Validator {
+                pubkey: Keypair::random().pk.compress(),
+                withdrawal_credentials,
+                effective_balance: 32_000_000_000,
+                slashed: false,
+                activation_eligibility_epoch: Epoch::new(0),
+                activation_epoch: Epoch::new(0),
+                exit_epoch: Epoch::new(u64::MAX),
+                withdrawable_epoch: Epoch::new(u64::MAX),
+            },
+        }
+    }
+
+    fn eth1_credentials(address: Address, spec: &ChainSpec) -> Hash256 {
+        let mut credentials = [0; 32];
+        credentials[0] = spec.eth1_address_withdrawal_prefix_byte;
+        credentials[12..].copy_from_slice(address.as_bytes());
+        Hash256::from(credentials)
+    }
+
+    #[test]
+    fn request_is_sent_from_the_source_withdrawal_address() {
+        let spec = MainnetEthSpec::default_spec();
+        let source_address = Address::repeat_byte(1);
+        let source = validator_data(0, eth1_credentials(source_address, &spec));
+        let target = validator_data(1, eth1_credentials(Address::repeat_byte(2), &spec));
+
+        let request = consolidation_request(&source, &target, &spec).unwrap();
+        assert_eq!(
+            request,
+            ConsolidationRequest {
+                source_address,
+                source_pubkey: source.validator.pubkey,
+                target_pubkey: target.validator.pubkey,
+            }
+        );
+
+        let output = ConsolidationRequestOutput::from(request);
+        assert_eq!(output.calldata.len(), 96);
+        assert_eq!(&output.calldata[..48], source.validator.pubkey.serialize());
+        assert_eq!(&output.calldata[48..], target.validator.pubkey.serialize());
+    }
+
+    #[test]
+    fn request_requires_execution_credentials_and_distinct_validators() {
+        let spec = MainnetEthSpec::default_spec();
+        let credentials = eth1_credentials(Address::repeat_byte(1), &spec);
+        let source = validator_data(0, credentials);
+
+        assert!(consolidation_request(&source, &source, &spec).is_err());
+
+        let bls_target = validator_data(1, Hash256::zero());
+        assert!(consolidation_request(&source, &bls_target, &spec).is_err());
+        assert!(consolidation_request(&bls_target, &source, &spec).is_err());
+
+        let mut exiting_target = validator_data(1, credentials);
+        exiting_target.status = ValidatorStatus::ActiveExiting;
+        assert!(consolidation_request(&source, &exiting_target, &spec).is_err());
+    }
+}
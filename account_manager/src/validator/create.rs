@@ -1,6 +1,7 @@
 use crate::common::read_wallet_name_from_cli;
 use crate::wallet::create::STDIN_INPUTS_FLAG;
 use crate::{SECRETS_DIR_FLAG, WALLETS_DIR_FLAG};
+use account_utils::eth2_keystore::keypair_from_secret;
 use account_utils::{
     random_password, read_password_from_user, strip_off_newlines, validator_definitions, PlainText,
 };
@@ -10,12 +11,18 @@ use directory::{
     ensure_dir_exists, parse_path_or_default_with_flag, DEFAULT_SECRET_DIR, DEFAULT_WALLET_DIR,
 };
 use environment::Environment;
+use eth2_wallet::{recover_validator_secret_from_mnemonic, KeyType, Wallet};
 use eth2_wallet_manager::WalletManager;
+use serde::{Deserialize, Serialize};
 use slashing_protection::{SlashingDatabase, SLASHING_PROTECTION_FILENAME};
+use ssz::Encode;
+use ssz_derive::{Decode, Encode};
 use std::ffi::OsStr;
-use std::fs;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::ops::Range;
 use std::path::{Path, PathBuf};
-use types::EthSpec;
+use types::{Address, ChainSpec, EthSpec, Hash256, PublicKeyBytes};
 use validator_dir::Builder as ValidatorDirBuilder;
 
 pub const CMD: &str = "create";
@@ -23,8 +30,12 @@ pub const WALLET_NAME_FLAG: &str = "wallet-name";
 pub const WALLET_PASSWORD_FLAG: &str = "wallet-password";
 pub const DEPOSIT_GWEI_FLAG: &str = "deposit-gwei";
 pub const STORE_WITHDRAW_FLAG: &str = "store-withdrawal-keystore";
+pub const COMPOUNDING_WITHDRAWAL_ADDRESS_FLAG: &str = "compounding-withdrawal-address";
 pub const COUNT_FLAG: &str = "count";
 pub const AT_MOST_FLAG: &str = "at-most";
+pub const DERIVATION_INDEX_RANGE_FLAG: &str = "derivation-index-range";
+pub const DRY_RUN_MANIFEST_FLAG: &str = "dry-run-manifest";
+pub const MANIFEST_FORMAT_FLAG: &str = "manifest-format";
 pub const WALLET_PASSWORD_PROMPT: &str = "Enter your wallet's password:";
 
 pub fn cli_app() -> Command {
@@ -76,7 +87,21 @@ pub fn cli_app() -> Command {
                 .value_name("DEPOSIT_GWEI")
                 .help(
                     "The GWEI value of the deposit amount. Defaults to the minimum amount \
-                    required for an active validator (MAX_EFFECTIVE_BALANCE)",
+                    required for an active validator (MAX_EFFECTIVE_BALANCE). Amounts above \
+                    MAX_EFFECTIVE_BALANCE require --compounding-withdrawal-address.",
+                )
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new(COMPOUNDING_WITHDRAWAL_ADDRESS_FLAG)
+                .long(COMPOUNDING_WITHDRAWAL_ADDRESS_FLAG)
+                .value_name("ETH1_ADDRESS")
+                .help(
+                    "Create deposits with EIP-7251 compounding (0x02) withdrawal credentials for \
+                    this execution address, instead of BLS withdrawal credentials. This permits \
+                    deposits of up to MAX_EFFECTIVE_BALANCE_ELECTRA and requires the Electra fork \
+                    to be scheduled.",
                 )
                 .action(ArgAction::Set)
                 .display_order(0)
@@ -99,6 +124,7 @@ pub fn cli_app() -> Command {
                 .value_name("VALIDATOR_COUNT")
                 .help("The number of validators to create, regardless of how many already exist")
                 .conflicts_with("at-most")
+                .conflicts_with(DERIVATION_INDEX_RANGE_FLAG)
                 .action(ArgAction::Set)
                 .display_order(0)
         )
@@ -111,6 +137,43 @@ pub fn cli_app() -> Command {
                     reach the given count. Never deletes an existing validator.",
                 )
                 .conflicts_with("count")
+                .conflicts_with(DERIVATION_INDEX_RANGE_FLAG)
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new(DERIVATION_INDEX_RANGE_FLAG)
+                .long(DERIVATION_INDEX_RANGE_FLAG)
+                .value_name("START..END")
+                .help(
+                    "Derive the validators at the EIP-2334 indices from START up to, but not \
+                    including, END (e.g. 100..200). When creating validators, START must not be \
+                    less than the wallet's next unused index, which is advanced to END.",
+                )
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new(DRY_RUN_MANIFEST_FLAG)
+                .long(DRY_RUN_MANIFEST_FLAG)
+                .value_name("MANIFEST_PATH")
+                .help(
+                    "Write a manifest of the voting and withdrawal public keys for the indices in \
+                    --derivation-index-range to this path, instead of creating validators. No \
+                    keystores are written and the wallet is not modified.",
+                )
+                .requires(DERIVATION_INDEX_RANGE_FLAG)
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new(MANIFEST_FORMAT_FLAG)
+                .long(MANIFEST_FORMAT_FLAG)
+                .value_name("FORMAT")
+                .help("The encoding of the manifest written by --dry-run-manifest.")
+                .value_parser(["json", "ssz"])
+                .default_value("json")
+                .requires(DRY_RUN_MANIFEST_FLAG)
                 .action(ArgAction::Set)
                 .display_order(0)
         )
@@ -151,8 +214,23 @@ pub fn cli_run<E: EthSpec>(
 
     let deposit_gwei = clap_utils::parse_optional(matches, DEPOSIT_GWEI_FLAG)?
         .unwrap_or(spec.max_effective_balance);
+    let compounding_withdrawal_address: Option<Address> =
+        clap_utils::parse_optional(matches, COMPOUNDING_WITHDRAWAL_ADDRESS_FLAG)?;
+    verify_deposit_amount(
+        deposit_gwei,
+        compounding_withdrawal_address.is_some(),
+        &spec,
+    )?;
     let count: Option<usize> = clap_utils::parse_optional(matches, COUNT_FLAG)?;
     let at_most: Option<usize> = clap_utils::parse_optional(matches, AT_MOST_FLAG)?;
+    let index_range = matches
+        .get_one::<String>(DERIVATION_INDEX_RANGE_FLAG)
+        .map(|range| parse_derivation_index_range(range))
+        .transpose()?;
+    let dry_run_manifest: Option<PathBuf> =
+        clap_utils::parse_optional(matches, DRY_RUN_MANIFEST_FLAG)?;
+    let wallet_password_path: Option<PathBuf> =
+        clap_utils::parse_optional(matches, WALLET_PASSWORD_FLAG)?;
 
     // The command will always fail if the wallet dir does not exist.
     if !wallet_base_dir.exists() {
@@ -166,6 +244,38 @@ pub fn cli_run<E: EthSpec>(
         ));
     }
 
+    if let (Some(manifest_path), Some(index_range)) = (dry_run_manifest, &index_range) {
+        let wallet_name = read_wallet_name_from_cli(name, stdin_inputs)?;
+        let wallet_password = read_wallet_password_from_cli(wallet_password_path, stdin_inputs)?;
+
+        let mgr = WalletManager::open(&wallet_base_dir)
+            .map_err(|e| format!("Unable to open --{}: {:?}", WALLETS_DIR_FLAG, e))?;
+        let wallet = mgr
+            .wallet_by_name(&wallet_name)
+            .map_err(|e| format!("Unable to open wallet: {:?}", e))?;
+
+        let manifest = derive_manifest(wallet.wallet(), &wallet_password, index_range.clone())?;
+        let bytes = match matches
+            .get_one::<String>(MANIFEST_FORMAT_FLAG)
+            .map(String::as_str)
+        {
+            Some("ssz") => manifest.as_ssz_bytes(),
+            _ => serde_json::to_vec_pretty(&manifest)
+                .map_err(|e| format!("Unable to encode manifest: {:?}", e))?,
+        };
+        write_manifest(&manifest_path, &bytes)?;
+
+        for entry in &manifest {
+            println!("{}\t{}", entry.index, entry.voting_pubkey.as_hex_string());
+        }
+        eprintln!(
+            "Wrote a manifest of {} validators to {:?}",
+            manifest.len(),
+            manifest_path
+        );
+        return Ok(());
+    }
+
     ensure_dir_exists(&validator_dir)?;
     ensure_dir_exists(&secrets_dir)?;
 
@@ -174,17 +284,18 @@ pub fn cli_run<E: EthSpec>(
 
     let starting_validator_count = existing_validator_count(&validator_dir)?;
 
-    let n = match (count, at_most) {
-        (Some(_), Some(_)) => Err(format!(
+    let n = match (count, at_most, &index_range) {
+        (Some(_), Some(_), _) => Err(format!(
             "Cannot supply --{} and --{}",
             COUNT_FLAG, AT_MOST_FLAG
         )),
-        (None, None) => Err(format!(
-            "Must supply either --{} or --{}",
-            COUNT_FLAG, AT_MOST_FLAG
+        (None, None, None) => Err(format!(
+            "Must supply either --{}, --{} or --{}",
+            COUNT_FLAG, AT_MOST_FLAG, DERIVATION_INDEX_RANGE_FLAG
         )),
-        (Some(count), None) => Ok(count),
-        (None, Some(at_most)) => Ok(at_most.saturating_sub(starting_validator_count)),
+        (Some(count), None, None) => Ok(count),
+        (None, Some(at_most), None) => Ok(at_most.saturating_sub(starting_validator_count)),
+        (_, _, Some(index_range)) => Ok(index_range.len()),
     }?;
 
     if n == 0 {
@@ -195,9 +306,6 @@ pub fn cli_run<E: EthSpec>(
         return Ok(());
     }
 
-    let wallet_password_path: Option<PathBuf> =
-        clap_utils::parse_optional(matches, WALLET_PASSWORD_FLAG)?;
-
     let wallet_name = read_wallet_name_from_cli(name, stdin_inputs)?;
     let wallet_password = read_wallet_password_from_cli(wallet_password_path, stdin_inputs)?;
 
@@ -226,6 +334,21 @@ pub fn cli_run<E: EthSpec>(
         )
     })?;
 
+    if let Some(index_range) = &index_range {
+        let nextaccount = wallet.wallet().nextaccount();
+        if index_range.start < nextaccount {
+            return Err(format!(
+                "Cannot create validators from index {} since the wallet has already created \
+                 validators up to index {}. Use --{} to derive the public keys of existing \
+                 validators.",
+                index_range.start, nextaccount, DRY_RUN_MANIFEST_FLAG
+            ));
+        }
+        wallet
+            .set_nextaccount(index_range.start)
+            .map_err(|e| format!("Unable to update wallet: {:?}", e))?;
+    }
+
     for i in 0..n {
         let voting_password = random_password();
         let withdrawal_password = random_password();
@@ -255,12 +378,17 @@ pub fn cli_run<E: EthSpec>(
                 )
             })?;
 
-        ValidatorDirBuilder::new(validator_dir.clone())
+        let mut builder = ValidatorDirBuilder::new(validator_dir.clone())
             .password_dir(secrets_dir.clone())
             .voting_keystore(keystores.voting, voting_password.as_bytes())
             .withdrawal_keystore(keystores.withdrawal, withdrawal_password.as_bytes())
             .create_eth1_tx_data(deposit_gwei, &spec)
-            .store_withdrawal_keystore(matches.get_flag(STORE_WITHDRAW_FLAG))
+            .store_withdrawal_keystore(matches.get_flag(STORE_WITHDRAW_FLAG));
+        if let Some(address) = compounding_withdrawal_address {
+            builder =
+                builder.withdrawal_credentials(compounding_withdrawal_credentials(address, &spec));
+        }
+        builder
             .build()
             .map_err(|e| format!("Unable to build validator directory: {:?}", e))?;
 
@@ -270,6 +398,137 @@ pub fn cli_run<E: EthSpec>(
     Ok(())
 }
 
+/// Checks that a deposit of `deposit_gwei` is valid under `spec` for a validator with compounding
+/// withdrawal credentials if `compounding` is `true`, or BLS withdrawal credentials otherwise.
+///
+/// Any amount above the maximum effective balance would not contribute to the validator's
+/// effective balance, so it is rejected rather than locked up in the deposit.
+fn verify_deposit_amount(
+    deposit_gwei: u64,
+    compounding: bool,
+    spec: &ChainSpec,
+) -> Result<(), String> {
+    if compounding && spec.electra_fork_epoch.is_none() {
+        return Err(format!(
+            "Cannot use --{} since the Electra fork is not scheduled for this network",
+            COMPOUNDING_WITHDRAWAL_ADDRESS_FLAG
+        ));
+    }
+
+    if deposit_gwei < spec.min_deposit_amount {
+        return Err(format!(
+            "Deposit of {} gwei is less than the minimum deposit amount of {} gwei",
+            deposit_gwei, spec.min_deposit_amount
+        ));
+    }
+
+    if compounding && deposit_gwei > spec.max_effective_balance_electra {
+        return Err(format!(
+            "Deposit of {} gwei exceeds the maximum effective balance of {} gwei for a \
+             validator with compounding withdrawal credentials",
+            deposit_gwei, spec.max_effective_balance_electra
+        ));
+    } else if !compounding && deposit_gwei > spec.max_effective_balance {
+        return Err(format!(
+            "Deposit of {} gwei exceeds the maximum effective balance of {} gwei. Use --{} to \
+             create validators with compounding withdrawal credentials, which permit a maximum \
+             effective balance of {} gwei",
+            deposit_gwei,
+            spec.max_effective_balance,
+            COMPOUNDING_WITHDRAWAL_ADDRESS_FLAG,
+            spec.max_effective_balance_electra
+        ));
+    }
+
+    Ok(())
+}
+
+/// Returns the EIP-7251 compounding withdrawal credentials for `address`.
+fn compounding_withdrawal_credentials(address: Address, spec: &ChainSpec) -> Hash256 {
+    let mut withdrawal_credentials = [0; 32];
+    withdrawal_credentials[0] = spec.compounding_withdrawal_prefix_byte;
+    withdrawal_credentials[12..].copy_from_slice(address.as_bytes());
+    Hash256::from(withdrawal_credentials)
+}
+
+/// An entry in the manifest written by `--dry-run-manifest`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Encode, Decode)]
+pub struct DerivedKeyManifestEntry {
+    #[serde(with = "serde_utils::quoted_u32")]
+    pub index: u32,
+    pub voting_pubkey: PublicKeyBytes,
+    pub withdrawal_pubkey: PublicKeyBytes,
+}
+
+/// Parses a range of the form `START..END`, where `END` is exclusive.
+fn parse_derivation_index_range(range: &str) -> Result<Range<u32>, String> {
+    let (start, end) = range.split_once("..").ok_or_else(|| {
+        format!(
+            "--{} must be of the form START..END",
+            DERIVATION_INDEX_RANGE_FLAG
+        )
+    })?;
+    let parse = |index: &str| {
+        index.parse::<u32>().map_err(|e| {
+            format!(
+                "Invalid index {:?} in --{}: {}",
+                index, DERIVATION_INDEX_RANGE_FLAG, e
+            )
+        })
+    };
+    let (start, end) = (parse(start)?, parse(end)?);
+
+    if start >= end {
+        return Err(format!(
+            "--{} must contain at least one index, {}..{} is empty",
+            DERIVATION_INDEX_RANGE_FLAG, start, end
+        ));
+    }
+    Ok(start..end)
+}
+
+/// Derives the voting and withdrawal public keys for each index in `index_range`.
+///
+/// The seed is only decrypted once, rather than for each key.
+fn derive_manifest(
+    wallet: &Wallet,
+    wallet_password: &PlainText,
+    index_range: Range<u32>,
+) -> Result<Vec<DerivedKeyManifestEntry>, String> {
+    let seed = wallet
+        .decrypt_seed(wallet_password.as_bytes())
+        .map_err(|e| format!("Unable to decrypt wallet seed: {:?}", e))?;
+
+    let derive_pubkey = |index: u32, key_type: KeyType| {
+        let (secret, _) = recover_validator_secret_from_mnemonic(seed.as_bytes(), index, key_type)
+            .map_err(|e| format!("Unable to derive key at index {}: {:?}", index, e))?;
+        keypair_from_secret(secret.as_bytes())
+            .map(|keypair| keypair.pk.compress())
+            .map_err(|e| format!("Invalid secret key at index {}: {:?}", index, e))
+    };
+
+    index_range
+        .map(|index| {
+            Ok(DerivedKeyManifestEntry {
+                index,
+                voting_pubkey: derive_pubkey(index, KeyType::Voting)?,
+                withdrawal_pubkey: derive_pubkey(index, KeyType::Withdrawal)?,
+            })
+        })
+        .collect()
+}
+
+/// Writes the manifest to `path`, refusing to overwrite an existing file.
+fn write_manifest(path: &Path, bytes: &[u8]) -> Result<(), String> {
+    let mut file = OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)
+        .map_err(|e| format!("Unable to create manifest at {:?}: {}", path, e))?;
+    file.write_all(bytes)
+        .map_err(|e| format!("Unable to write manifest to {:?}: {}", path, e))
+}
+
 /// Returns the number of validators that exist in the given `validator_dir`.
 ///
 /// This function just assumes all files and directories, excluding the validator definitions YAML
@@ -283,7 +283,7 @@ async fn get_validator_index_for_exit(
 }
 
 /// Returns the validator data by querying the beacon node client.
-async fn get_validator_data(
+pub(crate) async fn get_validator_data(
     client: &BeaconNodeHttpClient,
     validator_pubkey: &PublicKey,
 ) -> Result<ValidatorData, String> {
@@ -305,7 +305,9 @@ async fn get_validator_data(
 }
 
 /// Get genesis data by querying the beacon node client.
-async fn get_geneisis_data(client: &BeaconNodeHttpClient) -> Result<GenesisData, String> {
+pub(crate) async fn get_geneisis_data(
+    client: &BeaconNodeHttpClient,
+) -> Result<GenesisData, String> {
     Ok(client
         .get_beacon_genesis()
         .await
@@ -314,7 +316,7 @@ async fn get_geneisis_data(client: &BeaconNodeHttpClient) -> Result<GenesisData,
 }
 
 /// Gets syncing status from beacon node client and returns true if syncing and false otherwise.
-async fn is_syncing(client: &BeaconNodeHttpClient) -> Result<bool, String> {
+pub(crate) async fn is_syncing(client: &BeaconNodeHttpClient) -> Result<bool, String> {
     Ok(client
         .get_node_syncing()
         .await
@@ -324,7 +326,7 @@ async fn is_syncing(client: &BeaconNodeHttpClient) -> Result<bool, String> {
 }
 
 /// Calculates the current epoch from the genesis time and current time.
-fn get_current_epoch<E: EthSpec>(genesis_time: u64, spec: &ChainSpec) -> Option<Epoch> {
+pub(crate) fn get_current_epoch<E: EthSpec>(genesis_time: u64, spec: &ChainSpec) -> Option<Epoch> {
     let slot_clock = SystemTimeSlotClock::new(
         spec.genesis_slot,
         Duration::from_secs(genesis_time),
@@ -1,8 +1,9 @@
 use clap::{Arg, ArgAction, ArgMatches, Command};
+use clap_utils::FLAG_HEADER;
 use environment::Environment;
 use slashing_protection::{
     interchange::Interchange, InterchangeError, InterchangeImportOutcome, SlashingDatabase,
-    SLASHING_PROTECTION_FILENAME,
+    DEFAULT_INTERCHANGE_IMPORT_BATCH_SIZE, SLASHING_PROTECTION_FILENAME,
 };
 use std::fs::File;
 use std::path::PathBuf;
@@ -17,6 +18,7 @@ pub const IMPORT_FILE_ARG: &str = "IMPORT-FILE";
 pub const EXPORT_FILE_ARG: &str = "EXPORT-FILE";
 
 pub const PUBKEYS_FLAG: &str = "pubkeys";
+pub const STREAMING_FLAG: &str = "streaming";
 
 pub fn cli_app() -> Command {
     Command::new(CMD)
@@ -32,6 +34,19 @@ pub fn cli_app() -> Command {
                          .display_order(0)
                         .help("The slashing protection interchange file to import (.json)"),
                 )
+                .arg(
+                    Arg::new(STREAMING_FLAG)
+                        .long(STREAMING_FLAG)
+                        .action(ArgAction::SetTrue)
+                        .help_heading(FLAG_HEADER)
+                        .help(
+                            "Read the file one record at a time instead of loading it into memory, \
+                             and import the records in batches. Recommended for very large files. \
+                             Unlike a regular import, records from batches that are imported \
+                             before an error remain in the database.",
+                        )
+                        .display_order(0)
+                )
         )
         .subcommand(
             Command::new(EXPORT_CMD)
@@ -82,10 +97,7 @@ pub fn cli_run<E: EthSpec>(
                 )
             })?;
 
-            eprint!("Loading JSON file into memory & deserializing");
-            let interchange = Interchange::from_json_reader(&import_file)
-                .map_err(|e| format!("Error parsing file for import: {:?}", e))?;
-            eprintln!(" [done].");
+            let streaming = matches.get_flag(STREAMING_FLAG);
 
             let slashing_protection_database =
                 SlashingDatabase::open_or_create(&slashing_protection_db_path).map_err(|e| {
@@ -109,9 +121,31 @@ pub fn cli_run<E: EthSpec>(
                 }
             };
 
-            match slashing_protection_database
-                .import_interchange_info(interchange, genesis_validators_root)
-            {
+            let import_result = if streaming {
+                let result = slashing_protection_database.import_interchange_stream(
+                    &import_file,
+                    genesis_validators_root,
+                    DEFAULT_INTERCHANGE_IMPORT_BATCH_SIZE,
+                    |progress| {
+                        eprint!(
+                            "\rImported {} records ({} failed)",
+                            progress.records_imported, progress.records_failed
+                        )
+                    },
+                );
+                eprintln!();
+                result
+            } else {
+                eprint!("Loading JSON file into memory & deserializing");
+                let interchange = Interchange::from_json_reader(&import_file)
+                    .map_err(|e| format!("Error parsing file for import: {:?}", e))?;
+                eprintln!(" [done].");
+
+                slashing_protection_database
+                    .import_interchange_info(interchange, genesis_validators_root)
+            };
+
+            match import_result {
                 Ok(outcomes) => {
                     eprintln!("All records imported successfully:");
                     for outcome in &outcomes {
@@ -155,6 +189,21 @@ pub fn cli_run<E: EthSpec>(
                                 slashing protection data for them."
                         .to_string());
                 }
+                Err(InterchangeError::PartialImport(outcomes)) => {
+                    eprintln!("ERROR: some records could not be imported");
+                    for outcome in &outcomes {
+                        if let InterchangeImportOutcome::Failure { pubkey, error } = outcome {
+                            eprintln!("- {:?}", pubkey);
+                            eprintln!("    - error: {:?}", error);
+                        }
+                    }
+                    return Err("ERROR: import incomplete due to errors, see above.\n\
+                                All other records have been imported.\n\
+                                It is NOT SAFE to start validating with the validator keys\n\
+                                listed above, as your database lacks slashing protection\n\
+                                data for them."
+                        .to_string());
+                }
                 Err(e) => {
                     return Err(format!(
                         "Fatal error during import: {:?}\n\
@@ -397,7 +397,7 @@ fn process_slash_info<T: BeaconChainTypes>(
 ) -> Error {
     use AttestationSlashInfo::*;
 
-    if let Some(slasher) = chain.slasher.as_ref() {
+    if chain.has_slasher() {
         let (indexed_attestation, check_signature, err) = match slash_info {
             SignatureNotChecked(attestation, err) => {
                 if let Error::UnknownHeadBlock { .. } = err {
@@ -435,7 +435,7 @@ fn process_slash_info<T: BeaconChainTypes>(
         }
 
         // Supply to slasher.
-        slasher.accept_attestation(indexed_attestation);
+        chain.slasher_accept_attestation(indexed_attestation);
 
         err
     } else {
@@ -457,8 +457,9 @@ impl<'a, T: BeaconChainTypes> IndexedAggregatedAttestation<'a, T> {
     ) -> Result<Self, Error> {
         Self::verify_slashable(signed_aggregate, chain)
             .map(|verified_aggregate| {
-                if let Some(slasher) = chain.slasher.as_ref() {
-                    slasher.accept_attestation(verified_aggregate.indexed_attestation.clone());
+                if chain.has_slasher() {
+                    chain
+                        .slasher_accept_attestation(verified_aggregate.indexed_attestation.clone());
                 }
                 verified_aggregate
             })
@@ -724,8 +725,8 @@ impl<'a, T: BeaconChainTypes> VerifiedAggregatedAttestation<'a, T> {
     }
 
     fn apply_to_slasher(self, chain: &BeaconChain<T>) -> Self {
-        if let Some(slasher) = chain.slasher.as_ref() {
-            slasher.accept_attestation(self.indexed_attestation.clone());
+        if chain.has_slasher() {
+            chain.slasher_accept_attestation(self.indexed_attestation.clone());
         }
         self
     }
@@ -893,8 +894,10 @@ impl<'a, T: BeaconChainTypes> IndexedUnaggregatedAttestation<'a, T> {
     ) -> Result<Self, Error> {
         Self::verify_slashable(attestation.to_ref(), subnet_id, chain)
             .map(|verified_unaggregated| {
-                if let Some(slasher) = chain.slasher.as_ref() {
-                    slasher.accept_attestation(verified_unaggregated.indexed_attestation.clone());
+                if chain.has_slasher() {
+                    chain.slasher_accept_attestation(
+                        verified_unaggregated.indexed_attestation.clone(),
+                    );
                 }
                 verified_unaggregated
             })
@@ -999,8 +1002,8 @@ impl<'a, T: BeaconChainTypes> VerifiedUnaggregatedAttestation<'a, T> {
     }
 
     fn apply_to_slasher(self, chain: &BeaconChain<T>) -> Self {
-        if let Some(slasher) = chain.slasher.as_ref() {
-            slasher.accept_attestation(self.indexed_attestation.clone());
+        if chain.has_slasher() {
+            chain.slasher_accept_attestation(self.indexed_attestation.clone());
         }
         self
     }
@@ -0,0 +1,120 @@
+//! Provides a cache of the validator balances of the head and finalized states.
+//!
+//! Balances are one of the most frequently requested parts of the state (e.g., by staking
+//! dashboards) and serving them for the finalized state would otherwise require reading an entire
+//! state from the database.
+//!
+//! The cached balances are shared with the states they came from rather than copied: cloning a
+//! `List` only clones the `Arc` at the root of its tree, so updating the cache is cheap even with
+//! a large validator registry.
+use crate::metrics;
+use parking_lot::RwLock;
+use types::{BeaconState, EthSpec, Hash256, List};
+
+/// The balances of every validator in a state.
+pub type Balances<E> = List<u64, <E as EthSpec>::ValidatorRegistryLimit>;
+
+struct CacheItem<E: EthSpec> {
+    state_root: Hash256,
+    balances: Balances<E>,
+}
+
+impl<E: EthSpec> CacheItem<E> {
+    fn new(state_root: Hash256, state: &BeaconState<E>) -> Self {
+        Self {
+            state_root,
+            balances: state.balances().clone(),
+        }
+    }
+}
+
+/// Holds the balances of at most two states: the head and the finalized state.
+///
+/// The head balances are replaced each time the head changes, whilst the finalized balances are
+/// populated on the first request for a new finalized state.
+pub struct BalancesCache<E: EthSpec> {
+    head: RwLock<Option<CacheItem<E>>>,
+    finalized: RwLock<Option<CacheItem<E>>>,
+}
+
+impl<E: EthSpec> Default for BalancesCache<E> {
+    fn default() -> Self {
+        Self {
+            head: RwLock::new(None),
+            finalized: RwLock::new(None),
+        }
+    }
+}
+
+impl<E: EthSpec> BalancesCache<E> {
+    /// Returns the balances of the state with `state_root`, if it is cached.
+    pub fn get(&self, state_root: Hash256) -> Option<Balances<E>> {
+        let balances = [&self.head, &self.finalized].into_iter().find_map(|item| {
+            item.read()
+                .as_ref()
+                .filter(|item| item.state_root == state_root)
+                .map(|item| item.balances.clone())
+        });
+
+        if balances.is_some() {
+            metrics::inc_counter(&metrics::VALIDATOR_BALANCES_CACHE_HITS);
+        } else {
+            metrics::inc_counter(&metrics::VALIDATOR_BALANCES_CACHE_MISSES);
+        }
+
+        balances
+    }
+
+    /// Replace the head balances with those of `state`.
+    pub fn update_head(&self, state_root: Hash256, state: &BeaconState<E>) {
+        *self.head.write() = Some(CacheItem::new(state_root, state));
+    }
+
+    /// Replace the finalized balances with those of `state`, returning the new balances.
+    pub fn update_finalized(&self, state_root: Hash256, state: &BeaconState<E>) -> Balances<E> {
+        let item = CacheItem::new(state_root, state);
+        let balances = item.balances.clone();
+        *self.finalized.write() = Some(item);
+        balances
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use types::{BeaconState, ChainSpec, Eth1Data, MinimalEthSpec};
+
+    type E = MinimalEthSpec;
+
+    fn state_with_balances(balances: &[u64]) -> BeaconState<E> {
+        let spec = ChainSpec::minimal();
+        let mut state = BeaconState::<E>::new(0, Eth1Data::default(), &spec);
+        for balance in balances {
+            state.balances_mut().push(*balance).unwrap();
+        }
+        state
+    }
+
+    #[test]
+    fn head_and_finalized() {
+        let cache = BalancesCache::<E>::default();
+        let head_root = Hash256::repeat_byte(1);
+        let finalized_root = Hash256::repeat_byte(2);
+
+        assert!(cache.get(head_root).is_none());
+
+        cache.update_head(head_root, &state_with_balances(&[1, 2, 3]));
+        assert_eq!(cache.get(head_root).unwrap().to_vec(), vec![1, 2, 3]);
+        assert!(cache.get(finalized_root).is_none());
+
+        cache.update_finalized(finalized_root, &state_with_balances(&[4, 5]));
+        assert_eq!(cache.get(finalized_root).unwrap().to_vec(), vec![4, 5]);
+        assert_eq!(cache.get(head_root).unwrap().to_vec(), vec![1, 2, 3]);
+
+        // A new head replaces the previous head.
+        let new_head_root = Hash256::repeat_byte(3);
+        cache.update_head(new_head_root, &state_with_balances(&[6]));
+        assert!(cache.get(head_root).is_none());
+        assert_eq!(cache.get(new_head_root).unwrap().to_vec(), vec![6]);
+    }
+}
@@ -1,9 +1,11 @@
+use crate::attestation_rewards::AttestationRewardsCache;
 use crate::attestation_verification::{
     batch_verify_aggregated_attestations, batch_verify_unaggregated_attestations,
     Error as AttestationError, VerifiedAggregatedAttestation, VerifiedAttestation,
     VerifiedUnaggregatedAttestation,
 };
 use crate::attester_cache::{AttesterCache, AttesterCacheKey};
+use crate::balances_cache::BalancesCache;
 use crate::beacon_block_streamer::{BeaconBlockStreamer, CheckCaches};
 use crate::beacon_proposer_cache::compute_proposer_duties_from_head;
 use crate::beacon_proposer_cache::BeaconProposerCache;
@@ -20,6 +22,7 @@ use crate::block_verification_types::{
 };
 pub use crate::canonical_head::CanonicalHead;
 use crate::chain_config::ChainConfig;
+use crate::clock_sanity::ClockSanity;
 use crate::data_availability_checker::{
     Availability, AvailabilityCheckError, AvailableBlock, DataAvailabilityChecker,
 };
@@ -30,6 +33,7 @@ use crate::eth1_chain::{Eth1Chain, Eth1ChainBackend};
 use crate::eth1_finalization_cache::{Eth1FinalizationCache, Eth1FinalizationData};
 use crate::events::ServerSentEventHandler;
 use crate::execution_payload::{get_execution_payload, NotifyExecutionLayer, PreparePayloadHandle};
+use crate::finality_watchdog::FinalityWatchdog;
 use crate::fork_choice_signal::{ForkChoiceSignalRx, ForkChoiceSignalTx, ForkChoiceWaitResult};
 use crate::graffiti_calculator::GraffitiCalculator;
 use crate::head_tracker::{HeadTracker, HeadTrackerReader, SszHeadTracker};
@@ -54,11 +58,14 @@ use crate::observed_attesters::{
 };
 use crate::observed_blob_sidecars::ObservedBlobSidecars;
 use crate::observed_block_producers::ObservedBlockProducers;
+use crate::observed_equivocations::ObservedEquivocations;
 use crate::observed_operations::{ObservationOutcome, ObservedOperations};
 use crate::observed_slashable::ObservedSlashable;
+use crate::partial_block_body::{BlockOperations, PendingPartialBlockBody};
 use crate::persisted_beacon_chain::{PersistedBeaconChain, DUMMY_CANONICAL_HEAD_BLOCK_ROOT};
 use crate::persisted_fork_choice::PersistedForkChoice;
 use crate::pre_finalization_cache::PreFinalizationBlockCache;
+use crate::remote_slasher::RemoteSlasherQueue;
 use crate::shuffling_cache::{BlockShufflingIds, ShufflingCache};
 use crate::sync_committee_verification::{
     Error as SyncCommitteeError, VerifiedSyncCommitteeMessage, VerifiedSyncContribution,
@@ -72,6 +79,7 @@ use crate::{
     kzg_utils, metrics, AvailabilityPendingExecutedBlock, BeaconChainError, BeaconForkChoiceStore,
     BeaconSnapshot, CachedHead,
 };
+use eth2::lighthouse::CacheMemory;
 use eth2::types::{EventKind, SseBlobSidecar, SseBlock, SseExtendedPayloadAttributes};
 use execution_layer::{
     BlockProposalContents, BlockProposalContentsType, BuilderParams, ChainHealth, ExecutionLayer,
@@ -113,14 +121,17 @@ use std::collections::HashMap;
 use std::collections::HashSet;
 use std::io::prelude::*;
 use std::marker::PhantomData;
+use std::sync::atomic::{AtomicBool, Ordering as AtomicOrdering};
 use std::sync::Arc;
 use std::time::Duration;
 use store::iter::{BlockRootsIterator, ParentRootBlockIterator, StateRootsIterator};
 use store::{
     DatabaseBlock, Error as DBError, HotColdDB, KeyValueStore, KeyValueStoreOp, StoreItem, StoreOp,
+    WeakSubjectivityViolation,
 };
 use task_executor::{ShutdownReason, TaskExecutor};
 use tokio_stream::Stream;
+use tracing::{info_span, instrument};
 use tree_hash::TreeHash;
 use types::blob_sidecar::FixedBlobSidecarList;
 use types::payload::BlockProductionVersion;
@@ -368,6 +379,9 @@ type ReqRespPreImportCache<E> = HashMap<Hash256, Arc<SignedBeaconBlock<E>>>;
 /// operations and chooses a canonical head.
 pub struct BeaconChain<T: BeaconChainTypes> {
     pub spec: ChainSpec,
+    /// The spec of the p2p network, which determines fork digests and gossip topics. This is the
+    /// same as `spec` unless running a shadow fork.
+    pub network_spec: ChainSpec,
     /// Configuration for `BeaconChain` runtime behaviour.
     pub config: ChainConfig,
     /// Persistent storage for blocks, states, etc. Typically an on-disk store, such as LevelDB.
@@ -418,6 +432,8 @@ pub struct BeaconChain<T: BeaconChainTypes> {
     pub observed_blob_sidecars: RwLock<ObservedBlobSidecars<T::EthSpec>>,
     /// Maintains a record of slashable message seen over the gossip network or RPC.
     pub observed_slashable: RwLock<ObservedSlashable<T::EthSpec>>,
+    /// Maintains evidence of block proposers which have signed conflicting blocks.
+    pub observed_equivocations: RwLock<ObservedEquivocations<T::EthSpec>>,
     /// Maintains a record of which validators have submitted voluntary exits.
     pub observed_voluntary_exits: Mutex<ObservedOperations<SignedVoluntaryExit, T::EthSpec>>,
     /// Maintains a record of which validators we've seen proposer slashings for.
@@ -428,6 +444,8 @@ pub struct BeaconChain<T: BeaconChainTypes> {
     /// Maintains a record of which validators we've seen BLS to execution changes for.
     pub observed_bls_to_execution_changes:
         Mutex<ObservedOperations<SignedBlsToExecutionChange, T::EthSpec>>,
+    /// Operations submitted via the HTTP API for inclusion in the next block we propose.
+    pub(crate) partial_block_body: Mutex<Option<PendingPartialBlockBody<T::EthSpec>>>,
     /// Provides information from the Ethereum 1 (PoW) chain.
     pub eth1_chain: Option<Eth1Chain<T::Eth1Chain, T::EthSpec>>,
     /// Interfaces with the execution client.
@@ -464,6 +482,8 @@ pub struct BeaconChain<T: BeaconChainTypes> {
     pub(crate) attester_cache: Arc<AttesterCache>,
     /// A cache used when producing attestations whilst the head block is still being imported.
     pub early_attester_cache: EarlyAttesterCache<T::EthSpec>,
+    /// A cache of the validator balances of the head and finalized states.
+    pub balances_cache: BalancesCache<T::EthSpec>,
     /// Cache gossip verified blocks to serve over ReqResp before they are imported
     pub reqresp_pre_import_cache: Arc<RwLock<ReqRespPreImportCache<T::EthSpec>>>,
     /// A cache used to keep track of various block timings.
@@ -483,6 +503,8 @@ pub struct BeaconChain<T: BeaconChainTypes> {
     pub(crate) graffiti_calculator: GraffitiCalculator<T>,
     /// Optional slasher.
     pub slasher: Option<Arc<Slasher<T::EthSpec>>>,
+    /// Optional queue of attestations and blocks for a slasher running in another process.
+    pub remote_slasher: Option<Arc<RemoteSlasherQueue<T::EthSpec>>>,
     /// Provides monitoring of a set of explicitly defined validators.
     pub validator_monitor: RwLock<ValidatorMonitor<T::EthSpec>>,
     /// The slot at which blocks are downloaded back to.
@@ -492,6 +514,20 @@ pub struct BeaconChain<T: BeaconChainTypes> {
     pub data_availability_checker: Arc<DataAvailabilityChecker<T>>,
     /// The KZG trusted setup used by this chain.
     pub kzg: Option<Arc<Kzg>>,
+    /// Set if a block which finalizes a chain conflicting with the configured weak subjectivity
+    /// checkpoint has been rejected, either since startup or as recorded in the database.
+    pub(crate) weak_subjectivity_violated: AtomicBool,
+    /// Set whilst historic blocks are being imported from era files.
+    pub(crate) era_import_in_progress: AtomicBool,
+    /// Whether a shadow fork had diverged from the network when last checked, so that the
+    /// divergence is only reported when it changes.
+    pub(crate) shadow_fork_diverged: AtomicBool,
+    /// Caches the attestation rewards of all validators for recent epochs.
+    pub(crate) attestation_rewards_cache: Mutex<AttestationRewardsCache>,
+    /// Detects periods of non-finality and places the node into safe mode.
+    pub finality_watchdog: FinalityWatchdog,
+    /// Estimates the skew of the local clock against peers and NTP.
+    pub clock_sanity: ClockSanity,
 }
 
 pub enum BeaconBlockResponseWrapper<E: EthSpec> {
@@ -894,6 +930,28 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         })
     }
 
+    /// Returns the canonical `(state_root, slot)` pairs for `start_slot..=end_slot`, ordered by
+    /// slot.
+    ///
+    /// The roots are loaded in a single forwards iteration, which reads finalized roots from the
+    /// freezer's chunked storage rather than looking up each slot individually. Use this instead
+    /// of repeated calls to `state_root_at_slot` when a range of roots is required.
+    ///
+    /// Slots later than the current slot are omitted.
+    pub fn state_roots_in_range(
+        &self,
+        start_slot: Slot,
+        end_slot: Slot,
+    ) -> Result<Vec<(Hash256, Slot)>, Error> {
+        let end_slot = std::cmp::min(end_slot, self.slot()?);
+        if start_slot > end_slot {
+            return Ok(vec![]);
+        }
+
+        self.forwards_iter_state_roots_until(start_slot, end_slot)?
+            .collect()
+    }
+
     /// Returns the block at the given slot, if any. Only returns blocks in the canonical chain.
     ///
     /// Use the `skips` parameter to define the behaviour when `request_slot` is a skipped slot.
@@ -2317,6 +2375,31 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         Ok(())
     }
 
+    /// Iterate through the naive aggregation pool and ensure all the attestations from there
+    /// are included in `op_pool`.
+    ///
+    /// Errors are logged rather than returned, so that they don't prevent block production.
+    pub(crate) fn import_naive_aggregation_pool(
+        &self,
+        op_pool: &OperationPool<T::EthSpec>,
+        state: &BeaconState<T::EthSpec>,
+    ) {
+        for attestation in self.naive_aggregation_pool.read().iter() {
+            let import = |attestation: &Attestation<T::EthSpec>| {
+                let attesting_indices =
+                    get_attesting_indices_from_state(state, attestation.to_ref())?;
+                op_pool.insert_attestation(attestation.clone(), attesting_indices)
+            };
+            if let Err(e) = import(attestation) {
+                error!(
+                    self.log,
+                    "Attestation did not transfer to op pool";
+                    "reason" => ?e
+                );
+            }
+        }
+    }
+
     /// Filter an attestation from the op pool for shuffling compatibility.
     ///
     /// Use the provided `filter_cache` map to memoize results.
@@ -2875,6 +2958,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
     /// ## Errors
     ///
     /// Returns an `Err` if the given block was invalid, or an error was encountered during
+    #[instrument(skip_all, fields(slot = %block.slot()))]
     pub async fn verify_block_for_gossip(
         self: &Arc<Self>,
         block: Arc<SignedBeaconBlock<T::EthSpec>>,
@@ -3086,6 +3170,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
     ///
     /// Returns an `Err` if the given block was invalid, or an error was encountered during
     /// verification.
+    #[instrument(skip_all, fields(?block_root, source = %block_source))]
     pub async fn process_block<B: IntoExecutionPendingBlock<T>>(
         self: &Arc<Self>,
         block_root: Hash256,
@@ -3211,6 +3296,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
     /// get a fully `ExecutedBlock`.
     ///
     /// An error is returned if the verification handle couldn't be awaited.
+    #[instrument(skip_all)]
     pub async fn into_executed_block(
         self: Arc<Self>,
         execution_pending_block: ExecutionPendingBlock<T>,
@@ -3284,8 +3370,8 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         blob: GossipVerifiedBlob<T>,
     ) -> Result<AvailabilityProcessingStatus, BlockError<T::EthSpec>> {
         let slot = blob.slot();
-        if let Some(slasher) = self.slasher.as_ref() {
-            slasher.accept_block_header(blob.signed_block_header());
+        if self.has_slasher() {
+            self.slasher_accept_block_header(blob.signed_block_header());
         }
         let availability = self.data_availability_checker.put_gossip_blob(blob)?;
 
@@ -3298,9 +3384,9 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         self: &Arc<Self>,
         data_columns: Vec<GossipVerifiedDataColumn<T>>,
     ) -> Result<AvailabilityProcessingStatus, BlockError<T::EthSpec>> {
-        if let Some(slasher) = self.slasher.as_ref() {
+        if self.has_slasher() {
             for data_colum in &data_columns {
-                slasher.accept_block_header(data_colum.signed_block_header());
+                self.slasher_accept_block_header(data_colum.signed_block_header());
             }
         }
 
@@ -3342,9 +3428,8 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                             block_root,
                         )
                         .map_err(|e| BlockError::BeaconChainError(e.into()))?;
-                    if let Some(slasher) = self.slasher.as_ref() {
-                        slasher.accept_block_header(header);
-                    }
+                    self.observe_block_header_for_equivocation(&header);
+                    self.slasher_accept_block_header(header);
                 }
             }
         }
@@ -3376,6 +3461,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         }
     }
 
+    #[instrument(skip_all)]
     pub async fn import_available_block(
         self: &Arc<Self>,
         block: Box<AvailableExecutedBlock<T::EthSpec>>,
@@ -3446,6 +3532,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
     /// An error is returned if the block was unable to be imported. It may be partially imported
     /// (i.e., this function is not atomic).
     #[allow(clippy::too_many_arguments)]
+    #[instrument(skip_all, fields(?block_root))]
     fn import_block(
         &self,
         signed_block: AvailableBlock<T::EthSpec>,
@@ -3516,6 +3603,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         {
             let _fork_choice_block_timer =
                 metrics::start_timer(&metrics::FORK_CHOICE_PROCESS_BLOCK_TIMES);
+            let _fork_choice_block_span = info_span!("fork_choice_on_block").entered();
             let block_delay = self
                 .slot_clock
                 .seconds_from_current_slot_start()
@@ -3621,6 +3709,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         self.import_block_update_slasher(block, &state, &mut consensus_context);
 
         let db_write_timer = metrics::start_timer(&metrics::BLOCK_PROCESSING_DB_WRITE);
+        let db_write_span = info_span!("db_write").entered();
 
         // Store the block and its state, and execute the confirmation batch for the intermediate
         // states, which will delete their temporary flags.
@@ -3722,6 +3811,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             .register_block(block_root, parent_root, slot);
 
         metrics::stop_timer(db_write_timer);
+        drop(db_write_span);
 
         metrics::inc_counter(&metrics::BLOCK_PROCESSING_SUCCESSES);
 
@@ -3740,6 +3830,9 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         self.pre_finalization_block_cache
             .block_processed(block_root);
 
+        // Clear any submitted partial block body which this block included.
+        self.partial_block_body_imported(block_root);
+
         self.import_block_update_metrics_and_events(
             block,
             block_root,
@@ -3781,6 +3874,19 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                 self.verify_weak_subjectivity_checkpoint(wss_checkpoint, block_root, state)
             {
                 let mut shutdown_sender = self.shutdown_sender();
+                self.weak_subjectivity_violated
+                    .store(true, AtomicOrdering::Relaxed);
+                // Persist the violation so that it is still reported after the shutdown.
+                if let Err(e) = self
+                    .store
+                    .store_weak_subjectivity_violation(WeakSubjectivityViolation { block_root })
+                {
+                    error!(
+                        self.log,
+                        "Failed to persist weak subjectivity violation";
+                        "error" => ?e
+                    );
+                }
                 crit!(
                     self.log,
                     "Weak subjectivity checkpoint verification failed while importing block!";
@@ -3869,6 +3975,12 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                     );
                 }
             }
+
+            validator_monitor.register_sync_aggregate_subnets(
+                block.slot(),
+                block.parent_root(),
+                &sync_aggregate.sync_committee_bits,
+            );
         }
 
         // Attestations.
@@ -3973,6 +4085,77 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         }
     }
 
+    /// Returns `true` if a slasher is configured, either in this process or remotely.
+    pub fn has_slasher(&self) -> bool {
+        self.slasher.is_some() || self.remote_slasher.is_some()
+    }
+
+    /// Provide a verified attestation to the slasher, if one is configured.
+    pub fn slasher_accept_attestation(&self, attestation: IndexedAttestation<T::EthSpec>) {
+        if let Some(slasher) = self.slasher.as_ref() {
+            slasher.accept_attestation(attestation);
+        } else if let Some(remote_slasher) = self.remote_slasher.as_ref() {
+            remote_slasher.accept_attestation(attestation);
+        }
+    }
+
+    /// Provide a block header with a verified signature to the slasher, if one is configured.
+    pub fn slasher_accept_block_header(&self, block_header: SignedBeaconBlockHeader) {
+        if let Some(slasher) = self.slasher.as_ref() {
+            slasher.accept_block_header(block_header);
+        } else if let Some(remote_slasher) = self.remote_slasher.as_ref() {
+            remote_slasher.accept_block_header(block_header);
+        }
+    }
+
+    /// Check a block header with a verified signature for an equivocation by its proposer.
+    ///
+    /// If the header conflicts with a header previously seen for the same slot and proposer, the
+    /// equivocation is recorded and the resulting proposer slashing is added to the op pool.
+    pub fn observe_block_header_for_equivocation(&self, block_header: &SignedBeaconBlockHeader) {
+        let proposer_slashing = match self
+            .observed_equivocations
+            .write()
+            .observe_header(block_header)
+        {
+            Ok(Some(proposer_slashing)) => proposer_slashing,
+            Ok(None) => return,
+            Err(e) => {
+                debug!(
+                    self.log,
+                    "Unable to check block header for equivocation";
+                    "error" => ?e,
+                    "slot" => block_header.message.slot,
+                );
+                return;
+            }
+        };
+
+        warn!(
+            self.log,
+            "Block proposer equivocation detected";
+            "slot" => block_header.message.slot,
+            "proposer_index" => block_header.message.proposer_index,
+            "block_root_1" => ?proposer_slashing.signed_header_1.message.canonical_root(),
+            "block_root_2" => ?proposer_slashing.signed_header_2.message.canonical_root(),
+        );
+
+        match self.verify_proposer_slashing_for_gossip(proposer_slashing) {
+            Ok(ObservationOutcome::New(proposer_slashing)) => {
+                self.import_proposer_slashing(proposer_slashing)
+            }
+            Ok(ObservationOutcome::AlreadyKnown) => {}
+            Err(e) => {
+                debug!(
+                    self.log,
+                    "Unable to verify proposer slashing for equivocation";
+                    "error" => ?e,
+                    "slot" => block_header.message.slot,
+                );
+            }
+        }
+    }
+
     /// If a slasher is configured, provide the attestations from the block.
     fn import_block_update_slasher(
         &self,
@@ -3980,7 +4163,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         state: &BeaconState<T::EthSpec>,
         ctxt: &mut ConsensusContext<T::EthSpec>,
     ) {
-        if let Some(slasher) = self.slasher.as_ref() {
+        if self.has_slasher() {
             for attestation in block.body().attestations() {
                 let indexed_attestation = match ctxt.get_indexed_attestation(state, attestation) {
                     Ok(indexed) => indexed,
@@ -3995,7 +4178,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                         continue;
                     }
                 };
-                slasher.accept_attestation(indexed_attestation.clone_as_indexed_attestation());
+                self.slasher_accept_attestation(indexed_attestation.clone_as_indexed_attestation());
             }
         }
     }
@@ -4221,6 +4404,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         Ok(())
     }
 
+    #[instrument(skip_all, fields(%slot))]
     pub async fn produce_block_with_verification(
         self: &Arc<Self>,
         randao_reveal: Signature,
@@ -4807,6 +4991,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
     /// equal to the root of `state`. Providing this value will serve as an optimization to avoid
     /// performing a tree hash in some scenarios.
     #[allow(clippy::too_many_arguments)]
+    #[instrument(skip_all, fields(%produce_at_slot))]
     pub async fn produce_block_on_state(
         self: &Arc<Self>,
         state: BeaconState<T::EthSpec>,
@@ -4927,6 +5112,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
     }
 
     #[allow(clippy::too_many_arguments)]
+    #[instrument(skip_all, fields(%produce_at_slot))]
     fn produce_partial_beacon_block(
         self: &Arc<Self>,
         mut state: BeaconState<T::EthSpec>,
@@ -5007,7 +5193,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             }
         };
 
-        let (mut proposer_slashings, mut attester_slashings, mut voluntary_exits) =
+        let (proposer_slashings, attester_slashings, voluntary_exits) =
             self.op_pool.get_slashings_and_exits(&state, &self.spec);
 
         let eth1_data = eth1_chain.eth1_data_for_block_production(&state, &self.spec)?;
@@ -5018,26 +5204,9 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             .op_pool
             .get_bls_to_execution_changes(&state, &self.spec);
 
-        // Iterate through the naive aggregation pool and ensure all the attestations from there
-        // are included in the operation pool.
         let unagg_import_timer =
             metrics::start_timer(&metrics::BLOCK_PRODUCTION_UNAGGREGATED_TIMES);
-        for attestation in self.naive_aggregation_pool.read().iter() {
-            let import = |attestation: &Attestation<T::EthSpec>| {
-                let attesting_indices =
-                    get_attesting_indices_from_state(&state, attestation.to_ref())?;
-                self.op_pool
-                    .insert_attestation(attestation.clone(), attesting_indices)
-            };
-            if let Err(e) = import(attestation) {
-                // Don't stop block production if there's an error, just create a log.
-                error!(
-                    self.log,
-                    "Attestation did not transfer to op pool";
-                    "reason" => ?e
-                );
-            }
-        }
+        self.import_naive_aggregation_pool(&self.op_pool, &state);
         drop(unagg_import_timer);
 
         let attestation_packing_timer =
@@ -5056,7 +5225,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             self.filter_op_pool_attestation(&mut curr_filter_cache, att, &state)
         };
 
-        let mut attestations = self
+        let attestations = self
             .op_pool
             .get_attestations(
                 &state,
@@ -5067,6 +5236,23 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             .map_err(BlockProductionError::OpPoolError)?;
         drop(attestation_packing_timer);
 
+        // Include any operations submitted via the HTTP API ahead of those from the op pool.
+        let mut operations = BlockOperations {
+            proposer_slashings,
+            attester_slashings,
+            attestations,
+            voluntary_exits,
+            bls_to_execution_changes,
+        };
+        self.merge_partial_block_body(&state, &mut operations);
+        let BlockOperations {
+            mut proposer_slashings,
+            mut attester_slashings,
+            mut attestations,
+            mut voluntary_exits,
+            bls_to_execution_changes,
+        } = operations;
+
         // If paranoid mode is enabled re-check the signatures of every included message.
         // This will be a lot slower but guards against bugs in block production and can be
         // quickly rolled out without a release.
@@ -5179,6 +5365,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         })
     }
 
+    #[instrument(skip_all)]
     fn complete_partial_beacon_block<Payload: AbstractExecPayload<T::EthSpec>>(
         &self,
         partial_beacon_block: PartialBeaconBlock<T::EthSpec>,
@@ -5404,8 +5591,6 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                             bls_to_execution_changes: bls_to_execution_changes.into(),
                             blob_kzg_commitments: kzg_commitments
                                 .ok_or(BlockProductionError::InvalidPayloadFork)?,
-                            // TODO(electra): finish consolidations when they're more spec'd out
-                            consolidations: Vec::new().into(),
                         },
                     }),
                     maybe_blobs_and_proofs,
@@ -5443,7 +5628,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         let mut ctxt = ConsensusContext::new(block.slot());
 
         let consensus_block_value = self
-            .compute_beacon_block_reward(block.message(), Hash256::zero(), &mut state)
+            .compute_beacon_block_reward(block.message(), &mut state)
             .map(|reward| reward.total)
             .unwrap_or(0);
 
@@ -5505,6 +5690,8 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
 
         drop(blobs_verification_timer);
 
+        self.partial_block_body_produced(&block);
+
         metrics::inc_counter(&metrics::BLOCK_PRODUCTION_SUCCESSES);
 
         trace!(
@@ -6232,6 +6419,16 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         Ok(())
     }
 
+    /// Returns `true` if a block conflicting with the configured weak subjectivity checkpoint has
+    /// been rejected.
+    ///
+    /// The violation is persisted to the database, so this flag is only cleared by purging the
+    /// database and resyncing from a trusted source.
+    pub fn is_weak_subjectivity_violated(&self) -> bool {
+        self.weak_subjectivity_violated
+            .load(AtomicOrdering::Relaxed)
+    }
+
     /// Called by the timer on every slot.
     ///
     /// Note: this function **MUST** be called from a non-async context since
@@ -6250,6 +6447,9 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             self.naive_aggregation_pool.write().prune(slot);
             self.block_times_cache.write().prune(slot);
 
+            self.check_clock_skew();
+            self.check_shadow_fork_divergence();
+
             // Don't run heavy-weight tasks during sync.
             if self.best_slot() + MAX_PER_SLOT_FORK_CHOICE_DISTANCE < slot {
                 return;
@@ -6258,6 +6458,8 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             // Run fork choice and signal to any waiting task that it has completed.
             self.recompute_head_at_current_slot().await;
 
+            self.check_finality_stall(slot);
+
             // Send the notification regardless of fork choice success, this is a "best effort"
             // notification and we don't want block production to hit the timeout in case of error.
             // Use a blocking task to avoid blocking the core executor whilst waiting for locks
@@ -6337,9 +6539,23 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             head_block_epoch: head_block.slot.epoch(T::EthSpec::slots_per_epoch()),
         })?;
 
+        // If the block's state will be so far ahead of `shuffling_epoch` that even its previous
+        // epoch committee cache will be too new, then error. Callers of this function shouldn't be
+        // requesting such old shufflings for this `head_block_root`.
+        //
+        // This check must happen before a promise is created, otherwise the promise would be
+        // dropped and every other caller waiting on it would fail.
+        let head_block_epoch = head_block.slot.epoch(T::EthSpec::slots_per_epoch());
+        if head_block_epoch > shuffling_epoch + 1 {
+            return Err(Error::InvalidStateForShuffling {
+                state_epoch: head_block_epoch,
+                shuffling_epoch,
+            });
+        }
+
         // Obtain the shuffling cache, timing how long we wait.
         let mut shuffling_cache = {
-            let _ =
+            let _timer =
                 metrics::start_timer(&metrics::ATTESTATION_PROCESSING_SHUFFLING_CACHE_WAIT_TIMES);
             self.shuffling_cache.write()
         };
@@ -6349,6 +6565,11 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             // access.
             drop(shuffling_cache);
 
+            // If another caller is already computing this shuffling, wait for it rather than
+            // loading a state ourselves.
+            let _timer = cache_item
+                .is_promise()
+                .then(|| metrics::start_timer(&metrics::SHUFFLING_CACHE_PROMISE_WAIT_TIMES));
             let committee_cache = cache_item.wait()?;
             map_fn(&committee_cache, shuffling_id.shuffling_decision_block)
         } else {
@@ -6371,17 +6592,6 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                 "head_block_root" => head_block_root.to_string(),
             );
 
-            // If the block's state will be so far ahead of `shuffling_epoch` that even its
-            // previous epoch committee cache will be too new, then error. Callers of this function
-            // shouldn't be requesting such old shufflings for this `head_block_root`.
-            let head_block_epoch = head_block.slot.epoch(T::EthSpec::slots_per_epoch());
-            if head_block_epoch > shuffling_epoch + 1 {
-                return Err(Error::InvalidStateForShuffling {
-                    state_epoch: head_block_epoch,
-                    shuffling_epoch,
-                });
-            }
-
             let state_read_timer =
                 metrics::start_timer(&metrics::ATTESTATION_PROCESSING_STATE_READ_TIMES);
 
@@ -6528,10 +6738,64 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         // therefore use the genesis slot.
         let slot = self.slot().unwrap_or(self.spec.genesis_slot);
 
-        self.spec
+        self.network_spec
             .enr_fork_id::<T::EthSpec>(slot, self.genesis_validators_root)
     }
 
+    /// Returns `true` if running a shadow fork whose fork schedule has diverged from the
+    /// network's at the current epoch.
+    ///
+    /// Once diverged, the node only follows its own chain, built from blocks produced by its own
+    /// validators:
+    ///
+    /// - Objects signed or produced after the divergence are invalid on the network, so they are
+    ///   not published.
+    /// - Blocks for the network's fork can't be imported, so they are ignored without penalizing
+    ///   the peers that send them, and no peer is considered to be ahead of us for range sync.
+    pub fn is_shadow_fork_diverged(&self) -> bool {
+        let slot = self.slot().unwrap_or(self.spec.genesis_slot);
+        let epoch = slot.epoch(T::EthSpec::slots_per_epoch());
+        self.spec.fork_at_epoch(epoch) != self.network_spec.fork_at_epoch(epoch)
+    }
+
+    /// Reports a change in whether a shadow fork has diverged from the network.
+    ///
+    /// This is cheap to call when there is no change, and is called once per slot.
+    pub fn check_shadow_fork_divergence(&self) {
+        let diverged = self.is_shadow_fork_diverged();
+        if self
+            .shadow_fork_diverged
+            .swap(diverged, AtomicOrdering::Relaxed)
+            == diverged
+        {
+            return;
+        }
+        metrics::set_gauge(&metrics::BEACON_SHADOW_FORK_DIVERGED, diverged as i64);
+
+        let epoch = self
+            .slot()
+            .unwrap_or(self.spec.genesis_slot)
+            .epoch(T::EthSpec::slots_per_epoch());
+        if diverged {
+            warn!(
+                self.log,
+                "Shadow fork has diverged from the network";
+                "info" => "following blocks from local validators only, network blocks are \
+                           ignored and nothing is published",
+                "fork" => %self.spec.fork_name_at_epoch(epoch),
+                "network_fork" => %self.network_spec.fork_name_at_epoch(epoch),
+                "epoch" => epoch,
+            );
+        } else {
+            info!(
+                self.log,
+                "Shadow fork has rejoined the network's fork schedule";
+                "fork" => %self.spec.fork_name_at_epoch(epoch),
+                "epoch" => epoch,
+            );
+        }
+    }
+
     /// Calculates the `Duration` to the next fork if it exists and returns it
     /// with it's corresponding `ForkName`.
     pub fn duration_to_next_fork(&self) -> Option<(ForkName, Duration)> {
@@ -6539,7 +6803,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         // therefore use the genesis slot.
         let slot = self.slot().unwrap_or(self.spec.genesis_slot);
 
-        let (fork_name, epoch) = self.spec.next_fork_epoch::<T::EthSpec>(slot)?;
+        let (fork_name, epoch) = self.network_spec.next_fork_epoch::<T::EthSpec>(slot)?;
         self.slot_clock
             .duration_to_slot(epoch.start_slot(T::EthSpec::slots_per_epoch()))
             .map(|duration| (fork_name, duration))
@@ -6802,6 +7066,126 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             reqresp_pre_import_cache_len: self.reqresp_pre_import_cache.read().len(),
         }
     }
+
+    /// Returns the sizes of the largest in-memory caches.
+    ///
+    /// Byte counts are rough estimates. None is given for the state cache, because cached states
+    /// share most of their memory with each other.
+    pub fn cache_memory(&self) -> Vec<CacheMemory> {
+        let cache = |name: &str, entries, capacity, estimated_bytes| CacheMemory {
+            name: name.to_string(),
+            entries,
+            capacity,
+            estimated_bytes: Some(estimated_bytes),
+        };
+        let op_pool = &self.op_pool;
+        let op_pool_entries = op_pool.num_attestations()
+            + op_pool.num_sync_contributions()
+            + op_pool.num_attester_slashings()
+            + op_pool.num_proposer_slashings()
+            + op_pool.num_voluntary_exits()
+            + op_pool.num_bls_to_execution_changes();
+
+        vec![
+            CacheMemory {
+                name: "state_cache".to_string(),
+                entries: self.store.state_cache_len(),
+                capacity: Some(self.store.state_cache_capacity()),
+                estimated_bytes: None,
+            },
+            {
+                let shuffling_cache = self.shuffling_cache.read();
+                cache(
+                    "shuffling_cache",
+                    shuffling_cache.len(),
+                    Some(shuffling_cache.capacity()),
+                    shuffling_cache.estimated_bytes(),
+                )
+            },
+            cache("op_pool", op_pool_entries, None, op_pool.estimated_bytes()),
+            {
+                let observed = self.observed_attestations.read();
+                cache(
+                    "observed_attestations",
+                    observed.num_observed(),
+                    None,
+                    observed.estimated_bytes(),
+                )
+            },
+            {
+                let observed = self.observed_sync_contributions.read();
+                cache(
+                    "observed_sync_contributions",
+                    observed.num_observed(),
+                    None,
+                    observed.estimated_bytes(),
+                )
+            },
+            {
+                let observed = self.observed_gossip_attesters.read();
+                cache(
+                    "observed_gossip_attesters",
+                    observed.total_validator_count(),
+                    None,
+                    observed.estimated_bytes(),
+                )
+            },
+            {
+                let observed = self.observed_block_attesters.read();
+                cache(
+                    "observed_block_attesters",
+                    observed.total_validator_count(),
+                    None,
+                    observed.estimated_bytes(),
+                )
+            },
+            {
+                let observed = self.observed_sync_contributors.read();
+                cache(
+                    "observed_sync_contributors",
+                    observed.total_validator_count(),
+                    None,
+                    observed.estimated_bytes(),
+                )
+            },
+            {
+                let observed = self.observed_aggregators.read();
+                cache(
+                    "observed_aggregators",
+                    observed.total_validator_count(),
+                    None,
+                    observed.estimated_bytes(),
+                )
+            },
+            {
+                let observed = self.observed_sync_aggregators.read();
+                cache(
+                    "observed_sync_aggregators",
+                    observed.total_validator_count(),
+                    None,
+                    observed.estimated_bytes(),
+                )
+            },
+            {
+                let observed = self.observed_block_producers.read();
+                cache(
+                    "observed_block_producers",
+                    observed.num_observed(),
+                    None,
+                    observed.estimated_bytes(),
+                )
+            },
+            {
+                let observed = self.observed_blob_sidecars.read();
+                cache(
+                    "observed_blob_sidecars",
+                    observed.num_observed(),
+                    None,
+                    observed.estimated_bytes(),
+                )
+            },
+        ]
+    }
 }
 
 impl<T: BeaconChainTypes> Drop for BeaconChain<T> {
@@ -573,6 +573,7 @@ pub fn validate_blob_sidecar_for_gossip<T: BeaconChainTypes>(
     let kzg_verified_blob = KzgVerifiedBlob::new(blob_sidecar.clone(), kzg, seen_timestamp)
         .map_err(GossipBlobError::KzgError)?;
 
+    chain.observe_block_header_for_equivocation(&blob_sidecar.signed_block_header);
     chain
         .observed_slashable
         .write()
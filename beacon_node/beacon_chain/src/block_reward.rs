@@ -1,11 +1,16 @@
 use crate::{BeaconChain, BeaconChainError, BeaconChainTypes};
-use eth2::lighthouse::{AttestationRewards, BlockReward, BlockRewardMeta};
-use operation_pool::{AttMaxCover, MaxCover, RewardCache, SplitAttestation};
+use eth2::lighthouse::{AttestationRewards, BlockReward, BlockRewardMeta, PredictedBlockReward};
+use operation_pool::{AttMaxCover, CompactAttestationRef, MaxCover, RewardCache, SplitAttestation};
 use state_processing::{
-    common::get_attesting_indices_from_state,
+    common::get_attesting_indices_from_state, epoch_cache::initialize_epoch_cache,
     per_block_processing::altair::sync_committee::compute_sync_aggregate_rewards,
+    state_advance::complete_state_advance,
+};
+use std::collections::HashMap;
+use types::{
+    AbstractExecPayload, Attestation, AttestationRef, BeaconBlockRef, BeaconState, ChainSpec,
+    EthSpec, Hash256, RelativeEpoch, Slot,
 };
-use types::{AbstractExecPayload, BeaconBlockRef, BeaconState, EthSpec, Hash256};
 
 impl<T: BeaconChainTypes> BeaconChain<T> {
     pub fn compute_block_reward<Payload: AbstractExecPayload<T::EthSpec>>(
@@ -20,68 +25,13 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             return Err(BeaconChainError::BlockRewardSlotError);
         }
 
-        reward_cache.update(state)?;
-
-        let total_active_balance = state.get_total_active_balance()?;
-
-        let split_attestations = block
-            .body()
-            .attestations()
-            .map(|att| {
-                let attesting_indices = get_attesting_indices_from_state(state, att)?;
-                Ok(SplitAttestation::new(
-                    att.clone_as_attestation(),
-                    attesting_indices,
-                ))
-            })
-            .collect::<Result<Vec<_>, BeaconChainError>>()?;
-
-        let mut per_attestation_rewards = split_attestations
-            .iter()
-            .map(|att| {
-                AttMaxCover::new(
-                    att.as_ref(),
-                    state,
-                    reward_cache,
-                    total_active_balance,
-                    &self.spec,
-                )
-                .ok_or(BeaconChainError::BlockRewardAttestationError)
-            })
-            .collect::<Result<Vec<_>, _>>()?;
-
-        // Update the attestation rewards for each previous attestation included.
-        // This is O(n^2) in the number of attestations n.
-        for i in 0..per_attestation_rewards.len() {
-            let (updated, to_update) = per_attestation_rewards.split_at_mut(i + 1);
-            let latest_att = &updated[i];
-
-            for att in to_update {
-                att.update_covering_set(latest_att.intermediate(), latest_att.covering_set());
-            }
-        }
-
-        let mut prev_epoch_total = 0;
-        let mut curr_epoch_total = 0;
-
-        for cover in &per_attestation_rewards {
-            for &reward in cover.fresh_validators_rewards.values() {
-                if cover.att.data.slot.epoch(T::EthSpec::slots_per_epoch()) == state.current_epoch()
-                {
-                    curr_epoch_total += reward;
-                } else {
-                    prev_epoch_total += reward;
-                }
-            }
-        }
-
-        let attestation_total = prev_epoch_total + curr_epoch_total;
-
-        // Drop the covers.
-        let per_attestation_rewards = per_attestation_rewards
-            .into_iter()
-            .map(|cover| cover.fresh_validators_rewards)
-            .collect();
+        let attestation_rewards = compute_attestation_packing_rewards(
+            block.body().attestations(),
+            state,
+            reward_cache,
+            &self.spec,
+        )?;
+        let attestation_total = attestation_rewards.total;
 
         // Add the attestation data if desired.
         let attestations = if include_attestations {
@@ -95,11 +45,8 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         };
 
         let attestation_rewards = AttestationRewards {
-            total: attestation_total,
-            prev_epoch_total,
-            curr_epoch_total,
-            per_attestation_rewards,
             attestations,
+            ..attestation_rewards
         };
 
         // Sync committee rewards.
@@ -129,4 +76,143 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             sync_committee_rewards,
         })
     }
+
+    /// Predict the attestation rewards for a block proposed at `slot` on top of the current head,
+    /// using the attestations that the op pool would pack into it right now.
+    ///
+    /// The prediction does not modify the op pool, the attestations are packed from a copy.
+    pub fn predict_block_reward(
+        &self,
+        slot: Slot,
+        include_attestations: bool,
+    ) -> Result<PredictedBlockReward, BeaconChainError> {
+        let (head_block_root, head_state_root) = {
+            let head = self.canonical_head.cached_head();
+            (head.head_block_root(), head.head_state_root())
+        };
+
+        // Start from the head state advanced by the state advance timer, if it is cached.
+        let (state_root, mut state) = self
+            .store
+            .get_advanced_hot_state(head_block_root, slot, head_state_root)?
+            .ok_or(BeaconChainError::MissingBeaconState(head_state_root))?;
+        complete_state_advance(&mut state, Some(state_root), slot, &self.spec)?;
+        state.build_committee_cache(RelativeEpoch::Previous, &self.spec)?;
+        state.build_committee_cache(RelativeEpoch::Current, &self.spec)?;
+        state.build_total_active_balance_cache(&self.spec)?;
+        initialize_epoch_cache(&mut state, &self.spec)?;
+
+        // Pack attestations in the same way as block production.
+        let op_pool = self.op_pool.clone_attestations();
+        self.import_naive_aggregation_pool(&op_pool, &state);
+        let mut prev_filter_cache = HashMap::new();
+        let mut curr_filter_cache = HashMap::new();
+        let prev_attestation_filter = |att: &CompactAttestationRef<T::EthSpec>| {
+            self.filter_op_pool_attestation(&mut prev_filter_cache, att, &state)
+        };
+        let curr_attestation_filter = |att: &CompactAttestationRef<T::EthSpec>| {
+            self.filter_op_pool_attestation(&mut curr_filter_cache, att, &state)
+        };
+        let attestations = op_pool.get_attestations(
+            &state,
+            prev_attestation_filter,
+            curr_attestation_filter,
+            &self.spec,
+        )?;
+
+        let mut attestation_rewards = compute_attestation_packing_rewards(
+            attestations.iter().map(Attestation::to_ref),
+            &state,
+            &mut RewardCache::default(),
+            &self.spec,
+        )?;
+        if include_attestations {
+            attestation_rewards.attestations =
+                attestations.iter().map(|a| a.data().clone()).collect();
+        }
+
+        Ok(PredictedBlockReward {
+            slot,
+            parent_root: head_block_root,
+            proposer_index: state.get_beacon_proposer_index(slot, &self.spec)? as u64,
+            attestation_rewards,
+        })
+    }
+}
+
+/// Compute the rewards for including `attestations` in a block at the slot of `state`.
+///
+/// Each attestation is only credited with the rewards for validators which weren't covered by
+/// an earlier attestation. The `attestations` field of the result is left empty.
+pub(crate) fn compute_attestation_packing_rewards<'a, E: EthSpec>(
+    attestations: impl Iterator<Item = AttestationRef<'a, E>>,
+    state: &BeaconState<E>,
+    reward_cache: &mut RewardCache,
+    spec: &ChainSpec,
+) -> Result<AttestationRewards, BeaconChainError> {
+    reward_cache.update(state)?;
+
+    let total_active_balance = state.get_total_active_balance()?;
+
+    let split_attestations = attestations
+        .map(|att| {
+            let attesting_indices = get_attesting_indices_from_state(state, att)?;
+            Ok(SplitAttestation::new(
+                att.clone_as_attestation(),
+                attesting_indices,
+            ))
+        })
+        .collect::<Result<Vec<_>, BeaconChainError>>()?;
+
+    let mut per_attestation_rewards = split_attestations
+        .iter()
+        .map(|att| {
+            AttMaxCover::new(
+                att.as_ref(),
+                state,
+                reward_cache,
+                total_active_balance,
+                spec,
+            )
+            .ok_or(BeaconChainError::BlockRewardAttestationError)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // Update the attestation rewards for each previous attestation included.
+    // This is O(n^2) in the number of attestations n.
+    for i in 0..per_attestation_rewards.len() {
+        let (updated, to_update) = per_attestation_rewards.split_at_mut(i + 1);
+        let latest_att = &updated[i];
+
+        for att in to_update {
+            att.update_covering_set(latest_att.intermediate(), latest_att.covering_set());
+        }
+    }
+
+    let mut prev_epoch_total = 0;
+    let mut curr_epoch_total = 0;
+
+    for cover in &per_attestation_rewards {
+        for &reward in cover.fresh_validators_rewards.values() {
+            if cover.att.data.slot.epoch(E::slots_per_epoch()) == state.current_epoch() {
+                curr_epoch_total += reward;
+            } else {
+                prev_epoch_total += reward;
+            }
+        }
+    }
+
+    // Drop the covers.
+    let per_attestation_rewards = per_attestation_rewards
+        .into_iter()
+        .map(|cover| cover.fresh_validators_rewards)
+        .collect();
+
+    Ok(AttestationRewards {
+        total: prev_epoch_total + curr_epoch_total,
+        prev_epoch_total,
+        curr_epoch_total,
+        per_attestation_rewards,
+        attestations: vec![],
+    })
 }
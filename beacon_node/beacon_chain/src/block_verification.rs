@@ -94,6 +94,7 @@ use std::io::Write;
 use std::sync::Arc;
 use store::{Error as DBError, HotStateSummary, KeyValueStore, StoreOp};
 use task_executor::JoinHandle;
+use tracing::{info_span, instrument};
 use types::{
     BeaconBlockRef, BeaconState, BeaconStateError, ChainSpec, Epoch, EthSpec, ExecutionBlockHash,
     Hash256, InconsistentFork, PublicKey, PublicKeyBytes, RelativeEpoch, SignedBeaconBlock,
@@ -554,7 +555,7 @@ pub(crate) fn process_block_slash_info<T: BeaconChainTypes, TErr: BlockBlobError
     chain: &BeaconChain<T>,
     slash_info: BlockSlashInfo<TErr>,
 ) -> TErr {
-    if let Some(slasher) = chain.slasher.as_ref() {
+    if chain.has_slasher() {
         let (verified_header, error) = match slash_info {
             BlockSlashInfo::SignatureNotChecked(header, e) => {
                 if verify_header_signature::<_, TErr>(chain, &header).is_ok() {
@@ -567,7 +568,7 @@ pub(crate) fn process_block_slash_info<T: BeaconChainTypes, TErr: BlockBlobError
             BlockSlashInfo::SignatureValid(header, e) => (header, e),
         };
 
-        slasher.accept_block_header(verified_header);
+        chain.slasher_accept_block_header(verified_header);
         error
     } else {
         match slash_info {
@@ -763,8 +764,9 @@ pub trait IntoExecutionPendingBlock<T: BeaconChainTypes>: Sized {
         self.into_execution_pending_block_slashable(block_root, chain, notify_execution_layer)
             .map(|execution_pending| {
                 // Supply valid block to slasher.
-                if let Some(slasher) = chain.slasher.as_ref() {
-                    slasher.accept_block_header(execution_pending.block.signed_block_header());
+                if chain.has_slasher() {
+                    chain
+                        .slasher_accept_block_header(execution_pending.block.signed_block_header());
                 }
                 execution_pending
             })
@@ -965,6 +967,7 @@ impl<T: BeaconChainTypes> GossipVerifiedBlock<T> {
             return Err(BlockError::ProposalSignatureInvalid);
         }
 
+        chain.observe_block_header_for_equivocation(&block.signed_block_header());
         chain
             .observed_slashable
             .write()
@@ -1283,6 +1286,7 @@ impl<T: BeaconChainTypes> ExecutionPendingBlock<T> {
     /// verification must be done upstream (e.g., via a `SignatureVerifiedBlock`
     ///
     /// Returns an error if the block is invalid, or if the block was unable to be verified.
+    #[instrument(skip_all, fields(slot = %block.slot(), ?block_root))]
     pub fn from_signature_verified_components(
         block: MaybeAvailableBlock<T::EthSpec>,
         block_root: Hash256,
@@ -1291,6 +1295,7 @@ impl<T: BeaconChainTypes> ExecutionPendingBlock<T> {
         chain: &Arc<BeaconChain<T>>,
         notify_execution_layer: NotifyExecutionLayer,
     ) -> Result<Self, BlockError<T::EthSpec>> {
+        chain.observe_block_header_for_equivocation(&block.signed_block_header());
         chain
             .observed_slashable
             .write()
@@ -1420,6 +1425,7 @@ impl<T: BeaconChainTypes> ExecutionPendingBlock<T> {
          */
 
         let catchup_timer = metrics::start_timer(&metrics::BLOCK_PROCESSING_CATCHUP_STATE);
+        let catchup_span = info_span!("catch_up_state").entered();
 
         // Stage a batch of operations to be completed atomically if this block is imported
         // successfully. If there is a skipped slot, we include the state root of the pre-state,
@@ -1532,6 +1538,7 @@ impl<T: BeaconChainTypes> ExecutionPendingBlock<T> {
             }
         }
         metrics::stop_timer(catchup_timer);
+        drop(catchup_span);
 
         let block_slot = block.slot();
         let state_current_epoch = state.current_epoch();
@@ -1603,6 +1610,7 @@ impl<T: BeaconChainTypes> ExecutionPendingBlock<T> {
         write_block(block.as_block(), block_root, &chain.log);
 
         let core_timer = metrics::start_timer(&metrics::BLOCK_PROCESSING_CORE);
+        let core_span = info_span!("per_block_processing").entered();
 
         if let Err(err) = per_block_processing(
             &mut state,
@@ -1622,16 +1630,19 @@ impl<T: BeaconChainTypes> ExecutionPendingBlock<T> {
         };
 
         metrics::stop_timer(core_timer);
+        drop(core_span);
 
         /*
          * Calculate the state root of the newly modified state
          */
 
         let state_root_timer = metrics::start_timer(&metrics::BLOCK_PROCESSING_STATE_ROOT);
+        let state_root_span = info_span!("state_root").entered();
 
         let state_root = state.update_tree_hash_cache()?;
 
         metrics::stop_timer(state_root_timer);
+        drop(state_root_span);
 
         write_state(
             &format!("state_post_block_{}", block_root),
@@ -2,9 +2,11 @@ use crate::beacon_chain::{
     CanonicalHead, LightClientProducerEvent, BEACON_CHAIN_DB_KEY, ETH1_CACHE_DB_KEY, OP_POOL_DB_KEY,
 };
 use crate::beacon_proposer_cache::BeaconProposerCache;
+use crate::clock_sanity::ClockSanity;
 use crate::data_availability_checker::DataAvailabilityChecker;
 use crate::eth1_chain::{CachingEth1Backend, SszEth1};
 use crate::eth1_finalization_cache::Eth1FinalizationCache;
+use crate::finality_watchdog::FinalityWatchdog;
 use crate::fork_choice_signal::ForkChoiceSignalTx;
 use crate::fork_revert::{reset_fork_choice_to_finalization, revert_to_fork_boundary};
 use crate::graffiti_calculator::{GraffitiCalculator, GraffitiOrigin};
@@ -12,6 +14,7 @@ use crate::head_tracker::HeadTracker;
 use crate::light_client_server_cache::LightClientServerCache;
 use crate::migrate::{BackgroundMigrator, MigratorConfig};
 use crate::persisted_beacon_chain::PersistedBeaconChain;
+use crate::remote_slasher::RemoteSlasherQueue;
 use crate::shuffling_cache::{BlockShufflingIds, ShufflingCache};
 use crate::validator_monitor::{ValidatorMonitor, ValidatorMonitorConfig};
 use crate::validator_pubkey_cache::ValidatorPubkeyCache;
@@ -33,6 +36,7 @@ use slog::{crit, debug, error, info, o, Logger};
 use slot_clock::{SlotClock, TestingSlotClock};
 use state_processing::{per_slot_processing, AllCaches};
 use std::marker::PhantomData;
+use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 use std::time::Duration;
 use store::{Error as StoreError, HotColdDB, ItemStore, KeyValueStoreOp};
@@ -93,10 +97,12 @@ pub struct BeaconChainBuilder<T: BeaconChainTypes> {
     head_tracker: Option<HeadTracker>,
     validator_pubkey_cache: Option<ValidatorPubkeyCache<T>>,
     spec: ChainSpec,
+    network_spec: Option<ChainSpec>,
     chain_config: ChainConfig,
     log: Option<Logger>,
     beacon_graffiti: GraffitiOrigin,
     slasher: Option<Arc<Slasher<T::EthSpec>>>,
+    remote_slasher: Option<Arc<RemoteSlasherQueue<T::EthSpec>>>,
     // Pending I/O batch that is constructed during building and should be executed atomically
     // alongside `PersistedBeaconChain` storage when `BeaconChainBuilder::build` is called.
     pending_io_batch: Vec<KeyValueStoreOp>,
@@ -136,10 +142,12 @@ where
             head_tracker: None,
             validator_pubkey_cache: None,
             spec: E::default_spec(),
+            network_spec: None,
             chain_config: ChainConfig::default(),
             log: None,
             beacon_graffiti: GraffitiOrigin::default(),
             slasher: None,
+            remote_slasher: None,
             pending_io_batch: vec![],
             kzg: None,
             task_executor: None,
@@ -156,6 +164,15 @@ where
         self
     }
 
+    /// Set the spec of the p2p network, when it differs from the spec used to process blocks.
+    ///
+    /// This is used to run a shadow fork which follows the network's gossip whilst applying a
+    /// locally overridden fork schedule. Defaults to the spec set by `Self::custom_spec`.
+    pub fn network_spec(mut self, network_spec: ChainSpec) -> Self {
+        self.network_spec = Some(network_spec);
+        self
+    }
+
     /// Get a reference to the builder's spec.
     pub fn get_spec(&self) -> &ChainSpec {
         &self.spec
@@ -214,6 +231,12 @@ where
         self
     }
 
+    /// Sets the queue of attestations and blocks for a remote slasher.
+    pub fn remote_slasher(mut self, remote_slasher: Arc<RemoteSlasherQueue<E>>) -> Self {
+        self.remote_slasher = Some(remote_slasher);
+        self
+    }
+
     /// Sets the logger.
     ///
     /// Should generally be called early in the build chain.
@@ -696,6 +719,9 @@ where
             .slot_clock
             .ok_or("Cannot build without a slot_clock.")?;
         let store = self.store.clone().ok_or("Cannot build without a store.")?;
+        let weak_subjectivity_violation = store
+            .load_weak_subjectivity_violation()
+            .map_err(|e| format!("Unable to load weak subjectivity violation: {:?}", e))?;
         let mut fork_choice = self
             .fork_choice
             .ok_or("Cannot build without fork choice.")?;
@@ -864,30 +890,11 @@ where
         let genesis_backfill_slot = if self.chain_config.genesis_backfill {
             Slot::new(0)
         } else {
-            let backfill_epoch_range = if cfg!(feature = "test_backfill") {
-                3
-            } else {
-                (self.spec.min_validator_withdrawability_delay + self.spec.churn_limit_quotient)
-                    .as_u64()
-                    / 2
-            };
-
-            match slot_clock.now() {
-                Some(current_slot) => {
-                    let genesis_backfill_epoch = current_slot
-                        .epoch(E::slots_per_epoch())
-                        .saturating_sub(backfill_epoch_range);
-                    genesis_backfill_epoch.start_slot(E::slots_per_epoch())
-                }
-                None => {
-                    // The slot clock cannot derive the current slot. We therefore assume we are
-                    // at or prior to genesis and backfill should sync all the way to genesis.
-                    Slot::new(0)
-                }
-            }
+            weak_subjectivity_backfill_slot::<E>(slot_clock.now(), &self.spec)
         };
 
         let beacon_chain = BeaconChain {
+            network_spec: self.network_spec.unwrap_or_else(|| self.spec.clone()),
             spec: self.spec.clone(),
             config: self.chain_config,
             store: store.clone(),
@@ -919,10 +926,12 @@ where
             observed_block_producers: <_>::default(),
             observed_blob_sidecars: <_>::default(),
             observed_slashable: <_>::default(),
+            observed_equivocations: <_>::default(),
             observed_voluntary_exits: <_>::default(),
             observed_proposer_slashings: <_>::default(),
             observed_attester_slashings: <_>::default(),
             observed_bls_to_execution_changes: <_>::default(),
+            partial_block_body: <_>::default(),
             eth1_chain: self.eth1_chain,
             execution_layer: self.execution_layer.clone(),
             genesis_validators_root,
@@ -946,6 +955,7 @@ where
             validator_pubkey_cache: RwLock::new(validator_pubkey_cache),
             attester_cache: <_>::default(),
             early_attester_cache: <_>::default(),
+            balances_cache: <_>::default(),
             reqresp_pre_import_cache: <_>::default(),
             light_client_server_cache: LightClientServerCache::new(),
             light_client_server_tx: self.light_client_server_tx,
@@ -960,6 +970,7 @@ where
                 log.clone(),
             ),
             slasher: self.slasher.clone(),
+            remote_slasher: self.remote_slasher.clone(),
             validator_monitor: RwLock::new(validator_monitor),
             genesis_backfill_slot,
             data_availability_checker: Arc::new(
@@ -967,6 +978,12 @@ where
                     .map_err(|e| format!("Error initializing DataAvailabiltyChecker: {:?}", e))?,
             ),
             kzg: self.kzg.clone(),
+            weak_subjectivity_violated: AtomicBool::new(weak_subjectivity_violation.is_some()),
+            era_import_in_progress: AtomicBool::new(false),
+            shadow_fork_diverged: AtomicBool::new(false),
+            attestation_rewards_cache: <_>::default(),
+            finality_watchdog: FinalityWatchdog::default(),
+            clock_sanity: ClockSanity::default(),
         };
 
         let head = beacon_chain.head_snapshot();
@@ -1002,6 +1019,16 @@ where
             }
         }
 
+        // Keep reporting a violation detected before the restart, until the database is purged.
+        if let Some(violation) = weak_subjectivity_violation {
+            crit!(
+                log,
+                "Database contains a chain which conflicts with the weak subjectivity checkpoint";
+                "rejected_block_root" => ?violation.block_root,
+            );
+            crit!(log, "You must use the `--purge-db` flag to clear the database and restart sync. You may be on a hostile network.");
+        }
+
         info!(
             log,
             "Beacon chain initialized";
@@ -1095,6 +1122,33 @@ where
     }
 }
 
+/// Returns the slot that backfill sync downloads blocks back to, unless `--genesis-backfill` is
+/// set: the start of the weak subjectivity period prior to `current_slot`.
+pub fn weak_subjectivity_backfill_slot<E: EthSpec>(
+    current_slot: Option<Slot>,
+    spec: &ChainSpec,
+) -> Slot {
+    let backfill_epoch_range = if cfg!(feature = "test_backfill") {
+        3
+    } else {
+        (spec.min_validator_withdrawability_delay + spec.churn_limit_quotient).as_u64() / 2
+    };
+
+    match current_slot {
+        Some(current_slot) => {
+            let genesis_backfill_epoch = current_slot
+                .epoch(E::slots_per_epoch())
+                .saturating_sub(backfill_epoch_range);
+            genesis_backfill_epoch.start_slot(E::slots_per_epoch())
+        }
+        None => {
+            // The slot clock cannot derive the current slot. We therefore assume we are
+            // at or prior to genesis and backfill should sync all the way to genesis.
+            Slot::new(0)
+        }
+    }
+}
+
 fn genesis_block<E: EthSpec>(
     genesis_state: &mut BeaconState<E>,
     spec: &ChainSpec,
@@ -1142,7 +1196,7 @@ mod test {
     use store::config::StoreConfig;
     use store::{HotColdDB, MemoryStore};
     use task_executor::test_utils::TestRuntime;
-    use types::{EthSpec, MinimalEthSpec, Slot};
+    use types::{EthSpec, MainnetEthSpec, MinimalEthSpec, Slot};
 
     type TestEthSpec = MinimalEthSpec;
     type Builder = BeaconChainBuilder<EphemeralHarnessType<TestEthSpec>>;
@@ -1290,4 +1344,31 @@ mod test {
             "validator count should be correct"
         );
     }
+
+    #[test]
+    fn backfill_slot_is_start_of_weak_subjectivity_period() {
+        let spec = ChainSpec::mainnet();
+        let slots_per_epoch = MainnetEthSpec::slots_per_epoch();
+        let backfill_epochs = if cfg!(feature = "test_backfill") {
+            3
+        } else {
+            32_896
+        };
+        let backfill_slot = |current_slot: Option<u64>| -> u64 {
+            weak_subjectivity_backfill_slot::<MainnetEthSpec>(current_slot.map(Slot::new), &spec)
+                .as_u64()
+        };
+
+        // Before genesis and during the first weak subjectivity period, backfill reaches genesis.
+        assert_eq!(backfill_slot(None), 0);
+        assert_eq!(
+            backfill_slot(Some(backfill_epochs * slots_per_epoch - 1)),
+            0
+        );
+        // Afterwards it stops at the start of an epoch.
+        assert_eq!(
+            backfill_slot(Some((backfill_epochs + 10) * slots_per_epoch + 5)),
+            10 * slots_per_epoch
+        );
+    }
 }
@@ -55,8 +55,18 @@ use std::sync::Arc;
 use std::time::Duration;
 use store::{iter::StateRootsIterator, KeyValueStoreOp, StoreItem};
 use task_executor::{JoinHandle, ShutdownReason};
+use tracing::instrument;
 use types::*;
 
+/// Blocks set as head more than this many slots after the start of their slot are assumed to come
+/// from sync rather than gossip, and are left out of the head delay metrics.
+///
+/// Blocks from gossip, including those whose parents had to be looked up first, normally become
+/// head within a slot or two. Blocks from range sync are older by however far the node is behind,
+/// so recording their delays would swamp the metrics with values describing sync progress rather
+/// than block propagation.
+const MAX_GOSSIP_HEAD_DELAY_SLOTS: u32 = 4;
+
 /// Simple wrapper around `RwLock` that uses private visibility to prevent any other modules from
 /// accessing the contained lock without it being explicitly noted in this module.
 pub struct CanonicalHeadRwLock<T>(RwLock<T>);
@@ -492,6 +502,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
     /// such a case it's critical that the `BeaconChain` keeps importing blocks so that the
     /// situation can be rectified. We avoid returning an error here so that calling functions
     /// can't abort block import because an error is returned here.
+    #[instrument(skip_all, fields(%current_slot))]
     pub async fn recompute_head_at_slot(self: &Arc<Self>, current_slot: Slot) {
         metrics::inc_counter(&metrics::FORK_CHOICE_REQUESTS);
         let _timer = metrics::start_timer(&metrics::FORK_CHOICE_TIMES);
@@ -830,26 +841,9 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             }
         }
 
-        observe_head_block_delays(
-            &mut self.block_times_cache.write(),
-            &new_head_proto_block,
-            new_snapshot.beacon_block.message().proposer_index(),
-            new_snapshot
-                .beacon_block
-                .message()
-                .body()
-                .graffiti()
-                .as_utf8_lossy(),
-            &self.slot_clock,
-            self.event_handler.as_ref(),
-            &self.log,
-        );
-
-        if is_epoch_transition || reorg_distance.is_some() {
-            self.persist_head_and_fork_choice()?;
-            self.op_pool.prune_attestations(self.epoch()?);
-        }
-
+        // Publish the new head to validators and event subscribers before performing any
+        // non-critical processing, since they are waiting on it to produce attestations.
+        //
         // Register server-sent-events for a new head.
         if let Some(event_handler) = self
             .event_handler
@@ -898,6 +892,42 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             }
         }
 
+        // Observe the delay between the start of the head block's slot and the head being
+        // published, ignoring blocks from sync.
+        let head_block_slot = new_head_proto_block.slot;
+        let head_event_delay =
+            get_slot_delay_ms(timestamp_now(), head_block_slot, &self.slot_clock);
+        if !is_block_from_sync(head_event_delay, self.slot_clock.slot_duration()) {
+            metrics::observe_duration(
+                &metrics::FORK_CHOICE_HEAD_EVENT_SLOT_START_DELAY_TIMES,
+                head_event_delay,
+            );
+        }
+
+        // Non-critical post-processing.
+        self.balances_cache
+            .update_head(state_root, &new_snapshot.beacon_state);
+
+        observe_head_block_delays(
+            &mut self.block_times_cache.write(),
+            &new_head_proto_block,
+            new_snapshot.beacon_block.message().proposer_index(),
+            new_snapshot
+                .beacon_block
+                .message()
+                .body()
+                .graffiti()
+                .as_utf8_lossy(),
+            &self.slot_clock,
+            self.event_handler.as_ref(),
+            &self.log,
+        );
+
+        if is_epoch_transition || reorg_distance.is_some() {
+            self.persist_head_and_fork_choice()?;
+            self.op_pool.prune_attestations(self.epoch()?);
+        }
+
         Ok(())
     }
 
@@ -946,6 +976,13 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                 .start_slot(T::EthSpec::slots_per_epoch()),
         );
 
+        self.observed_equivocations.write().prune(
+            new_view
+                .finalized_checkpoint
+                .epoch
+                .start_slot(T::EthSpec::slots_per_epoch()),
+        );
+
         self.attester_cache
             .prune_below(new_view.finalized_checkpoint.epoch);
 
@@ -1296,6 +1333,12 @@ pub fn find_reorg_slot<E: EthSpec>(
         .start_slot(E::slots_per_epoch()))
 }
 
+/// Returns `true` if a block set as head `block_delay` after the start of its slot most likely came
+/// from sync. See `MAX_GOSSIP_HEAD_DELAY_SLOTS`.
+fn is_block_from_sync(block_delay: Duration, slot_duration: Duration) -> bool {
+    block_delay > slot_duration * MAX_GOSSIP_HEAD_DELAY_SLOTS
+}
+
 fn observe_head_block_delays<E: EthSpec, S: SlotClock>(
     block_times_cache: &mut BlockTimesCache,
     head_block: &ProtoBlock,
@@ -1323,15 +1366,13 @@ fn observe_head_block_delays<E: EthSpec, S: SlotClock>(
         );
     }
 
-    // If a block comes in from over 4 slots ago, it is most likely a block from sync.
-    let block_from_sync = block_delay_total > slot_clock.slot_duration() * 4;
+    let block_from_sync = is_block_from_sync(block_delay_total, slot_clock.slot_duration());
 
     // Determine whether the block has been set as head too late for proper attestation
     // production.
     let late_head = block_delay_total >= slot_clock.unagg_attestation_production_delay();
 
-    // Do not store metrics if the block was > 4 slots old, this helps prevent noise during
-    // sync.
+    // Do not store metrics for blocks from sync, this helps prevent noise during sync.
     if !block_from_sync {
         // Observe the delay between when we imported the block and when we set the block as
         // head.
@@ -1496,3 +1537,23 @@ fn observe_head_block_delays<E: EthSpec, S: SlotClock>(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn block_from_sync_threshold() {
+        let slot_duration = Duration::from_secs(12);
+        let threshold = slot_duration * MAX_GOSSIP_HEAD_DELAY_SLOTS;
+
+        assert!(!is_block_from_sync(Duration::ZERO, slot_duration));
+        assert!(!is_block_from_sync(slot_duration, slot_duration));
+        assert!(!is_block_from_sync(threshold, slot_duration));
+        assert!(is_block_from_sync(
+            threshold + Duration::from_millis(1),
+            slot_duration
+        ));
+        assert!(is_block_from_sync(slot_duration * 64, slot_duration));
+    }
+}
@@ -1,7 +1,8 @@
 pub use proto_array::{DisallowedReOrgOffsets, ReOrgThreshold};
 use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
 use std::time::Duration;
-use types::{Checkpoint, Epoch};
+use types::{ChainSpec, Checkpoint, Epoch};
 
 pub const DEFAULT_RE_ORG_HEAD_THRESHOLD: ReOrgThreshold = ReOrgThreshold(20);
 pub const DEFAULT_RE_ORG_PARENT_THRESHOLD: ReOrgThreshold = ReOrgThreshold(160);
@@ -13,6 +14,14 @@ pub const DEFAULT_FORK_CHOICE_BEFORE_PROPOSAL_TIMEOUT: u64 = 250;
 /// Default fraction of a slot lookahead for payload preparation (12/3 = 4 seconds on mainnet).
 pub const DEFAULT_PREPARE_PAYLOAD_LOOKAHEAD_FACTOR: u32 = 3;
 
+/// Default number of epochs without finalization before the node enters safe mode.
+///
+/// This is approximately the point at which the inactivity leak begins.
+pub const DEFAULT_FINALITY_STALL_EPOCHS: u64 = 5;
+
+/// Default skew of the local clock, in milliseconds, above which a warning is raised.
+pub const DEFAULT_CLOCK_SKEW_THRESHOLD_MS: u64 = 2000;
+
 /// Fraction of a slot lookahead for fork choice in the state advance timer (500ms on mainnet).
 pub const FORK_CHOICE_LOOKAHEAD_FACTOR: u32 = 24;
 
@@ -43,6 +52,11 @@ pub struct ChainConfig {
     /// By default this list is empty, but it can be useful for reacting to network conditions, e.g.
     /// slow gossip of re-org blocks at slot 1 in the epoch.
     pub re_org_disallowed_offsets: DisallowedReOrgOffsets,
+    /// Replaces `PROPOSER_SCORE_BOOST` from the chain spec, only for use on devnets.
+    pub proposer_boost_percentage: Option<u64>,
+    /// Replaces the committee weight used to compute the proposer boost score, only for use on
+    /// devnets.
+    pub committee_weight_override: Option<u64>,
     /// Number of milliseconds to wait for fork choice before proposing a block.
     ///
     /// If set to 0 then block proposal will not wait for fork choice at all.
@@ -84,6 +98,16 @@ pub struct ChainConfig {
     pub epochs_per_migration: u64,
     /// When set to true Light client server computes and caches state proofs for serving updates
     pub enable_light_client_server: bool,
+    /// Number of epochs without finalization after which the chain is considered stalled.
+    pub finality_stall_epochs: u64,
+    /// Whether to widen the state cache and defer blob pruning whilst the chain is stalled.
+    pub finality_stall_safe_mode: bool,
+    /// Directory of era files from which to read historic blocks during backfill sync.
+    pub import_era_dir: Option<PathBuf>,
+    /// Skew of the local clock above which a warning is raised.
+    pub clock_skew_threshold: Duration,
+    /// NTP server against which to check the local clock.
+    pub ntp_server: Option<String>,
 }
 
 impl Default for ChainConfig {
@@ -98,6 +122,8 @@ impl Default for ChainConfig {
             re_org_max_epochs_since_finalization: DEFAULT_RE_ORG_MAX_EPOCHS_SINCE_FINALIZATION,
             re_org_cutoff_millis: None,
             re_org_disallowed_offsets: DisallowedReOrgOffsets::default(),
+            proposer_boost_percentage: None,
+            committee_weight_override: None,
             fork_choice_before_proposal_timeout_ms: DEFAULT_FORK_CHOICE_BEFORE_PROPOSAL_TIMEOUT,
             // Builder fallback configs that are set in `clap` will override these.
             builder_fallback_skips: 3,
@@ -115,6 +141,11 @@ impl Default for ChainConfig {
             always_prepare_payload: false,
             epochs_per_migration: crate::migrate::DEFAULT_EPOCHS_PER_MIGRATION,
             enable_light_client_server: false,
+            finality_stall_epochs: DEFAULT_FINALITY_STALL_EPOCHS,
+            finality_stall_safe_mode: false,
+            import_era_dir: None,
+            clock_skew_threshold: Duration::from_millis(DEFAULT_CLOCK_SKEW_THRESHOLD_MS),
+            ntp_server: None,
         }
     }
 }
@@ -128,4 +159,19 @@ impl ChainConfig {
                 Duration::from_secs(seconds_per_slot) / DEFAULT_RE_ORG_CUTOFF_DENOMINATOR
             })
     }
+
+    /// Whether any fork choice parameters deviate from the chain spec.
+    pub fn unsafe_tuning(&self) -> bool {
+        self.proposer_boost_percentage.is_some() || self.committee_weight_override.is_some()
+    }
+
+    /// Apply the fork choice parameter overrides to `spec`.
+    pub fn apply_unsafe_tuning(&self, spec: &mut ChainSpec) {
+        if let Some(proposer_boost_percentage) = self.proposer_boost_percentage {
+            spec.proposer_score_boost = Some(proposer_boost_percentage);
+        }
+        if let Some(committee_weight) = self.committee_weight_override {
+            spec.committee_weight_override = Some(committee_weight);
+        }
+    }
 }
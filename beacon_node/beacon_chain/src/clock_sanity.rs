@@ -0,0 +1,452 @@
+//! Detects skew between the local clock and the rest of the network.
+//!
+//! The slot clock is driven by the system clock, so a skewed system clock causes the node to judge
+//! blocks and attestations as early or late, and its validators to attest at the wrong time. The
+//! skew is estimated in two ways:
+//!
+//! 1. From peers. No honest block or attestation for a slot is produced before the start of that
+//!    slot, and in most slots the first of them arrives shortly afterwards. A low percentile of the
+//!    earliest arrival time of a verified gossip message in each recent slot therefore estimates
+//!    the skew of the local clock. This estimate is biased slightly late by propagation delays.
+//!    Gossip messages which arrive more than `MAXIMUM_GOSSIP_CLOCK_DISPARITY` early are ignored,
+//!    so a local clock which is running behind can only be partially detected this way.
+//! 2. From an NTP server, if one is configured with `--ntp-server`.
+//!
+//! Either estimate exceeding the configured threshold is logged, reflected in the
+//! `beacon_clock_skew_detected` metric and reported by the `/lighthouse/health` API.
+use crate::{metrics, BeaconChain, BeaconChainTypes};
+use parking_lot::Mutex;
+use slog::{debug, info, warn, Logger};
+use slot_clock::SlotClock;
+use std::net::{SocketAddr, ToSocketAddrs, UdpSocket};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use task_executor::TaskExecutor;
+use tokio::time::sleep;
+use types::Slot;
+
+/// The number of recent slots for which the timing of gossip messages is kept.
+pub const PEER_SAMPLE_SLOTS: usize = 64;
+/// The minimum number of slots with gossip messages required to estimate the skew from peers.
+const MIN_PEER_SAMPLE_SLOTS: usize = 16;
+/// The percentile of the per-slot arrival times used as the estimate. Slots in which every message
+/// was delayed say nothing about the local clock, so a low percentile is used.
+const PEER_SAMPLE_PERCENTILE: usize = 10;
+
+/// The interval between queries to the NTP server.
+pub const NTP_POLL_INTERVAL: Duration = Duration::from_secs(300);
+const NTP_TIMEOUT: Duration = Duration::from_secs(5);
+const NTP_DEFAULT_PORT: u16 = 123;
+const NTP_PACKET_LENGTH: usize = 48;
+/// Seconds between the NTP epoch (1900) and the Unix epoch (1970).
+const NTP_UNIX_EPOCH_OFFSET_SECS: i64 = 2_208_988_800;
+
+/// Tracks the estimated skew of the local clock.
+pub struct ClockSanity {
+    /// The arrival time of the earliest verified gossip message for each recent slot, in
+    /// milliseconds relative to the start of the slot according to the local clock.
+    ///
+    /// This is updated for every verified gossip message, so it's lock-free. Each slot is stored at
+    /// the index `slot % PEER_SAMPLE_SLOTS`, packed into a single word by `EarliestArrival`.
+    earliest_arrivals: [AtomicU64; PEER_SAMPLE_SLOTS],
+    /// The skew measured by the most recent successful NTP query.
+    ntp_skew_ms: Mutex<Option<i64>>,
+    /// Set whilst either estimate of the skew exceeds the threshold.
+    skew_detected: AtomicBool,
+}
+
+impl Default for ClockSanity {
+    fn default() -> Self {
+        Self {
+            earliest_arrivals: std::array::from_fn(|_| AtomicU64::new(EarliestArrival::EMPTY)),
+            ntp_skew_ms: Mutex::new(None),
+            skew_detected: AtomicBool::new(false),
+        }
+    }
+}
+
+/// The earliest arrival time of a gossip message for a slot, packed into a `u64` so that it can be
+/// updated atomically.
+///
+/// The upper 32 bits hold the slot plus one, so that zero represents an empty entry, and the lower
+/// 32 bits hold the arrival time in milliseconds.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct EarliestArrival {
+    slot: Slot,
+    arrival_ms: i32,
+}
+
+impl EarliestArrival {
+    const EMPTY: u64 = 0;
+
+    /// Returns `None` if the slot is too large to be packed.
+    fn pack(self) -> Option<u64> {
+        let slot = u32::try_from(self.slot.as_u64().checked_add(1)?).ok()?;
+        Some(((slot as u64) << 32) | self.arrival_ms as u32 as u64)
+    }
+
+    fn unpack(packed: u64) -> Option<Self> {
+        if packed == Self::EMPTY {
+            return None;
+        }
+        Some(Self {
+            slot: Slot::new((packed >> 32) - 1),
+            arrival_ms: packed as u32 as i32,
+        })
+    }
+}
+
+impl ClockSanity {
+    /// Record the arrival of a verified block or attestation for `slot` at `seen_timestamp`.
+    ///
+    /// Only the first message for a slot, or one which arrived earlier than any before it, results
+    /// in a write. Any other message only requires a single atomic load.
+    pub fn observe_gossip_message<S: SlotClock>(
+        &self,
+        slot: Slot,
+        seen_timestamp: Duration,
+        slot_clock: &S,
+    ) {
+        let Some(slot_start) = slot_clock.start_of(slot) else {
+            return;
+        };
+        let arrival_ms = seen_timestamp.as_millis() as i64 - slot_start.as_millis() as i64;
+        let observed = EarliestArrival {
+            slot,
+            arrival_ms: arrival_ms.clamp(i32::MIN as i64, i32::MAX as i64) as i32,
+        };
+        let Some(packed) = observed.pack() else {
+            return;
+        };
+
+        let entry = &self.earliest_arrivals[slot.as_usize() % PEER_SAMPLE_SLOTS];
+        let mut current = entry.load(Ordering::Relaxed);
+        loop {
+            if let Some(earliest) = EarliestArrival::unpack(current) {
+                // Keep the entry if it has been reused for a later slot, or if an earlier message
+                // has already been observed for this slot.
+                if earliest.slot > slot
+                    || (earliest.slot == slot && earliest.arrival_ms <= observed.arrival_ms)
+                {
+                    return;
+                }
+            }
+            match entry.compare_exchange_weak(current, packed, Ordering::Relaxed, Ordering::Relaxed)
+            {
+                Ok(_) => return,
+                Err(actual) => current = actual,
+            }
+        }
+    }
+
+    /// Returns the skew of the local clock estimated from the timing of gossip messages, in
+    /// milliseconds. A positive value indicates that the local clock is ahead.
+    pub fn peer_skew_ms(&self) -> Option<i64> {
+        let mut arrivals = self
+            .earliest_arrivals
+            .iter()
+            .filter_map(|entry| EarliestArrival::unpack(entry.load(Ordering::Relaxed)))
+            .map(|earliest| earliest.arrival_ms as i64)
+            .collect::<Vec<_>>();
+        if arrivals.len() < MIN_PEER_SAMPLE_SLOTS {
+            return None;
+        }
+        arrivals.sort_unstable();
+        arrivals
+            .get(arrivals.len() * PEER_SAMPLE_PERCENTILE / 100)
+            .copied()
+    }
+
+    /// Returns the skew of the local clock measured by the most recent successful NTP query, in
+    /// milliseconds. A positive value indicates that the local clock is ahead.
+    pub fn ntp_skew_ms(&self) -> Option<i64> {
+        *self.ntp_skew_ms.lock()
+    }
+
+    /// Returns `true` whilst the skew of the local clock exceeds the configured threshold.
+    pub fn is_skew_detected(&self) -> bool {
+        self.skew_detected.load(Ordering::Relaxed)
+    }
+}
+
+impl<T: BeaconChainTypes> BeaconChain<T> {
+    /// Compares the estimated skew of the local clock against the configured threshold, updating
+    /// metrics and logging any change.
+    ///
+    /// This is cheap to call, and is called once per slot.
+    pub fn check_clock_skew(&self) {
+        let peer_skew_ms = self.clock_sanity.peer_skew_ms();
+        let ntp_skew_ms = self.clock_sanity.ntp_skew_ms();
+        if let Some(skew_ms) = peer_skew_ms {
+            metrics::set_gauge(&metrics::BEACON_CLOCK_SKEW_PEERS_MILLISECONDS, skew_ms);
+        }
+        if let Some(skew_ms) = ntp_skew_ms {
+            metrics::set_gauge(&metrics::BEACON_CLOCK_SKEW_NTP_MILLISECONDS, skew_ms);
+        }
+
+        let threshold_ms = self.config.clock_skew_threshold.as_millis() as i64;
+        let skew_detected = peer_skew_ms
+            .into_iter()
+            .chain(ntp_skew_ms)
+            .any(|skew_ms| skew_ms.abs() > threshold_ms);
+        metrics::set_gauge(&metrics::BEACON_CLOCK_SKEW_DETECTED, skew_detected as i64);
+
+        let prev_skew_detected = self
+            .clock_sanity
+            .skew_detected
+            .swap(skew_detected, Ordering::Relaxed);
+        if skew_detected && !prev_skew_detected {
+            warn!(
+                self.log,
+                "System clock appears to be skewed";
+                "info" => "attestations may be missed, ensure the system clock is synchronized",
+                "peer_skew_ms" => ?peer_skew_ms,
+                "ntp_skew_ms" => ?ntp_skew_ms,
+                "threshold_ms" => threshold_ms,
+            );
+        } else if !skew_detected && prev_skew_detected {
+            info!(
+                self.log,
+                "System clock skew is within threshold";
+                "peer_skew_ms" => ?peer_skew_ms,
+                "ntp_skew_ms" => ?ntp_skew_ms,
+                "threshold_ms" => threshold_ms,
+            );
+        }
+    }
+}
+
+/// Spawns a task which periodically measures the skew of the local clock against `ntp_server`.
+pub fn spawn_ntp_clock_check<T: BeaconChainTypes>(
+    executor: TaskExecutor,
+    beacon_chain: Arc<BeaconChain<T>>,
+    ntp_server: String,
+    log: Logger,
+) {
+    executor.spawn(
+        ntp_clock_check(executor.clone(), beacon_chain, ntp_server, log),
+        "ntp_clock_check",
+    );
+}
+
+async fn ntp_clock_check<T: BeaconChainTypes>(
+    executor: TaskExecutor,
+    beacon_chain: Arc<BeaconChain<T>>,
+    ntp_server: String,
+    log: Logger,
+) {
+    loop {
+        let server = ntp_server.clone();
+        let Some(handle) =
+            executor.spawn_blocking_handle(move || query_ntp_skew_ms(&server), "ntp_query")
+        else {
+            return;
+        };
+
+        match handle.await {
+            Ok(Ok(skew_ms)) => {
+                debug!(
+                    log,
+                    "Measured clock skew against NTP server";
+                    "server" => &ntp_server,
+                    "skew_ms" => skew_ms,
+                );
+                *beacon_chain.clock_sanity.ntp_skew_ms.lock() = Some(skew_ms);
+            }
+            Ok(Err(e)) => {
+                warn!(
+                    log,
+                    "Failed to query NTP server";
+                    "server" => &ntp_server,
+                    "error" => e,
+                );
+            }
+            Err(_) => return,
+        }
+
+        sleep(NTP_POLL_INTERVAL).await;
+    }
+}
+
+/// Measures the skew of the local clock against `server` using SNTP, in milliseconds. A positive
+/// value indicates that the local clock is ahead.
+///
+/// The port defaults to 123 if `server` does not include one.
+pub fn query_ntp_skew_ms(server: &str) -> Result<i64, String> {
+    let addr = resolve_ntp_server(server)?;
+    let bind_addr = if addr.is_ipv4() {
+        "0.0.0.0:0"
+    } else {
+        "[::]:0"
+    };
+    let socket = UdpSocket::bind(bind_addr).map_err(|e| format!("Unable to bind socket: {e}"))?;
+    socket
+        .set_read_timeout(Some(NTP_TIMEOUT))
+        .map_err(|e| format!("Unable to set timeout: {e}"))?;
+
+    let mut request = [0; NTP_PACKET_LENGTH];
+    // Leap indicator 0, version 4, client mode.
+    request[0] = 0b00_100_011;
+
+    let originate_ms = unix_time_ms()?;
+    socket
+        .send_to(&request, addr)
+        .map_err(|e| format!("Unable to send request to {addr}: {e}"))?;
+    let mut response = [0; NTP_PACKET_LENGTH];
+    let (len, _) = socket
+        .recv_from(&mut response)
+        .map_err(|e| format!("No response from {addr}: {e}"))?;
+    let destination_ms = unix_time_ms()?;
+
+    parse_ntp_skew_ms(&response[..len], originate_ms, destination_ms)
+}
+
+fn resolve_ntp_server(server: &str) -> Result<SocketAddr, String> {
+    server
+        .to_socket_addrs()
+        .or_else(|_| (server, NTP_DEFAULT_PORT).to_socket_addrs())
+        .map_err(|e| format!("Unable to resolve {server}: {e}"))?
+        .next()
+        .ok_or_else(|| format!("Unable to resolve {server}"))
+}
+
+fn unix_time_ms() -> Result<i64, String> {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as i64)
+        .map_err(|e| format!("System clock is before the Unix epoch: {e}"))
+}
+
+/// Computes the skew of the local clock from an SNTP `response`, given the local times at which
+/// the request was sent and the response was received.
+fn parse_ntp_skew_ms(
+    response: &[u8],
+    originate_ms: i64,
+    destination_ms: i64,
+) -> Result<i64, String> {
+    if response.len() < NTP_PACKET_LENGTH {
+        return Err(format!("Response too short: {} bytes", response.len()));
+    }
+    let mode = response[0] & 0b111;
+    if mode != 4 {
+        return Err(format!("Unexpected response mode: {mode}"));
+    }
+    let stratum = response[1];
+    if stratum == 0 {
+        return Err("Server sent a kiss-of-death response".to_string());
+    }
+
+    let receive_ms = ntp_timestamp_ms(&response[32..40]);
+    let transmit_ms = ntp_timestamp_ms(&response[40..48]);
+
+    // The standard SNTP offset calculation gives the offset of the server clock from the local
+    // clock, which is negated to give the skew of the local clock.
+    let offset_ms = ((receive_ms - originate_ms) + (transmit_ms - destination_ms)) / 2;
+    Ok(-offset_ms)
+}
+
+/// Converts a 64-bit NTP timestamp to milliseconds since the Unix epoch.
+fn ntp_timestamp_ms(bytes: &[u8]) -> i64 {
+    let seconds = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as i64;
+    let fraction = u32::from_be_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]) as i64;
+    (seconds - NTP_UNIX_EPOCH_OFFSET_SECS) * 1000 + ((fraction * 1000) >> 32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use slot_clock::ManualSlotClock;
+
+    fn ntp_response(receive_ms: i64, transmit_ms: i64) -> [u8; NTP_PACKET_LENGTH] {
+        let mut response = [0; NTP_PACKET_LENGTH];
+        response[0] = 0b00_100_100;
+        response[1] = 1;
+        for (offset, time_ms) in [(32, receive_ms), (40, transmit_ms)] {
+            let seconds = (time_ms / 1000 + NTP_UNIX_EPOCH_OFFSET_SECS) as u32;
+            let fraction = (((time_ms % 1000) << 32) / 1000) as u32;
+            response[offset..offset + 4].copy_from_slice(&seconds.to_be_bytes());
+            response[offset + 4..offset + 8].copy_from_slice(&fraction.to_be_bytes());
+        }
+        response
+    }
+
+    #[test]
+    fn ntp_skew() {
+        // The local clock is 1.5s ahead, with a 100ms round trip.
+        let originate_ms = 1_700_000_001_500;
+        let response = ntp_response(1_700_000_000_050, 1_700_000_000_050);
+        let skew_ms = parse_ntp_skew_ms(&response, originate_ms, originate_ms + 100).unwrap();
+        assert!((skew_ms - 1_500).abs() <= 1, "skew {skew_ms}");
+
+        let mut kiss_of_death = response;
+        kiss_of_death[1] = 0;
+        assert!(parse_ntp_skew_ms(&kiss_of_death, originate_ms, originate_ms + 100).is_err());
+        assert!(parse_ntp_skew_ms(&response[..40], originate_ms, originate_ms + 100).is_err());
+    }
+
+    #[test]
+    fn peer_skew() {
+        let slot_duration = Duration::from_secs(12);
+        let slot_clock = ManualSlotClock::new(Slot::new(0), Duration::ZERO, slot_duration);
+        let clock_sanity = ClockSanity::default();
+
+        let observe = |slot: u64, arrival_ms: u64| {
+            let slot_start = slot_duration * slot as u32;
+            clock_sanity.observe_gossip_message(
+                Slot::new(slot),
+                slot_start + Duration::from_millis(arrival_ms),
+                &slot_clock,
+            );
+        };
+
+        // Too few slots to make an estimate.
+        for slot in 1..MIN_PEER_SAMPLE_SLOTS as u64 {
+            observe(slot, 3_000);
+        }
+        assert_eq!(clock_sanity.peer_skew_ms(), None);
+
+        // Only the earliest message in each slot is considered.
+        for slot in 1..=PEER_SAMPLE_SLOTS as u64 {
+            observe(slot, 2_000 + slot);
+            observe(slot, 5_000);
+        }
+        assert_eq!(clock_sanity.peer_skew_ms(), Some(2_000 + 7));
+
+        // Old slots are pruned.
+        for slot in PEER_SAMPLE_SLOTS as u64 + 1..=2 * PEER_SAMPLE_SLOTS as u64 {
+            observe(slot, 500);
+        }
+        assert_eq!(clock_sanity.peer_skew_ms(), Some(500));
+
+        // Messages for pruned slots are ignored.
+        for slot in 1..=PEER_SAMPLE_SLOTS as u64 {
+            observe(slot, 0);
+        }
+        assert_eq!(clock_sanity.peer_skew_ms(), Some(500));
+    }
+
+    #[test]
+    fn earliest_arrival_packing() {
+        for (slot, arrival_ms) in [
+            (0, 0),
+            (1, -1_500),
+            (7, i32::MIN),
+            (u32::MAX as u64 - 1, i32::MAX),
+        ] {
+            let earliest = EarliestArrival {
+                slot: Slot::new(slot),
+                arrival_ms,
+            };
+            let packed = earliest.pack().unwrap();
+            assert_ne!(packed, EarliestArrival::EMPTY);
+            assert_eq!(EarliestArrival::unpack(packed), Some(earliest));
+        }
+
+        let too_late = EarliestArrival {
+            slot: Slot::new(u32::MAX as u64),
+            arrival_ms: 0,
+        };
+        assert_eq!(too_late.pack(), None);
+    }
+}
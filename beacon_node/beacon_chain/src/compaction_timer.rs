@@ -0,0 +1,78 @@
+//! Provides a timer which runs database compactions whilst the node is idle.
+//!
+//! Compacting the database after pruning can involve a large amount of disk I/O. Rather than
+//! compacting immediately after each finalization migration, the migrator marks a compaction as
+//! due and this timer runs it part-way through a slot in which:
+//!
+//! 1. The node is synced, and
+//! 2. The block for the current slot has already been imported.
+//!
+//! This avoids competing for disk I/O with block import, and with the range sync which would
+//! otherwise have the node compacting repeatedly.
+use crate::{BeaconChain, BeaconChainTypes};
+use slog::{debug, error, Logger};
+use slot_clock::SlotClock;
+use std::sync::Arc;
+use task_executor::TaskExecutor;
+use tokio::time::sleep;
+
+/// Spawns the timer described in the module-level documentation.
+pub fn spawn_compaction_timer<T: BeaconChainTypes>(
+    executor: TaskExecutor,
+    beacon_chain: Arc<BeaconChain<T>>,
+    log: Logger,
+) {
+    executor.spawn(
+        compaction_timer(executor.clone(), beacon_chain, log),
+        "compaction_timer",
+    );
+}
+
+/// Provides the timer described in the module-level documentation.
+async fn compaction_timer<T: BeaconChainTypes>(
+    executor: TaskExecutor,
+    beacon_chain: Arc<BeaconChain<T>>,
+    log: Logger,
+) {
+    let slot_duration = beacon_chain.slot_clock.slot_duration();
+
+    loop {
+        let Some(duration_to_next_slot) = beacon_chain.slot_clock.duration_to_next_slot() else {
+            error!(log, "Failed to read slot clock");
+            // If we can't read the slot clock, just wait another slot.
+            sleep(slot_duration).await;
+            continue;
+        };
+
+        // Check for idleness half-way through the slot (6s on mainnet), by which time the block
+        // for the slot has usually been imported and attested to.
+        sleep(duration_to_next_slot + slot_duration / 2).await;
+
+        if !beacon_chain.store_migrator.is_compaction_due() {
+            continue;
+        }
+
+        let Some(current_slot) = beacon_chain.slot_clock.now() else {
+            continue;
+        };
+        let head_slot = beacon_chain.canonical_head.cached_head().head_slot();
+        if head_slot != current_slot {
+            debug!(
+                log,
+                "Deferring database compaction";
+                "reason" => "head is not at the current slot",
+                "head_slot" => head_slot,
+                "current_slot" => current_slot,
+            );
+            continue;
+        }
+
+        // The migrator runs the compaction on its own thread, but may run it on the calling
+        // thread if it isn't configured to run in the background.
+        let chain = beacon_chain.clone();
+        executor.spawn_blocking(
+            move || chain.store_migrator.process_compaction_if_due(),
+            "compaction_timer_check",
+        );
+    }
+}
@@ -0,0 +1,309 @@
+//! Imports historic blocks from local era files during backfill sync.
+//!
+//! After a checkpoint sync, backfill sync downloads every block prior to the checkpoint from
+//! peers. If an era directory is configured, blocks are instead read from the era files it
+//! contains, which is much faster and avoids using the network.
+//!
+//! Era files do not contain blobs, so blocks within the data availability window are still
+//! downloaded from peers. Once backfill sync has passed the data availability boundary, the era
+//! import takes over, pausing backfill sync until it runs out of era files. Backfill sync then
+//! resumes from wherever the era import finished.
+//!
+//! Blocks from era files are checked in exactly the same way as those downloaded from peers: each
+//! block root must match the parent root of its child, back to the checkpoint block, and every
+//! proposer signature is verified.
+//!
+//! If an era file can't be imported, e.g. because it's corrupt or from another chain, any other
+//! files for the same era are tried. If none of them can be imported, backfill sync downloads the
+//! rest of the blocks from peers.
+use crate::block_verification_types::RpcBlock;
+use crate::data_availability_checker::MaybeAvailableBlock;
+use crate::{BeaconChain, BeaconChainTypes};
+use slog::{debug, error, info, warn, Logger};
+use slot_clock::SlotClock;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use store::era::{parse_era_file_name, EraReader};
+use task_executor::TaskExecutor;
+use tokio::time::sleep;
+use types::{EthSpec, SignedBeaconBlock};
+
+/// The number of blocks verified and stored at a time.
+const ERA_IMPORT_BATCH_SIZE: usize = 256;
+
+/// How the import of an era file finished.
+enum EraFileImport {
+    /// Every block prior to the oldest block was imported, so the import can continue with the
+    /// previous era.
+    Complete,
+    /// A block which requires blobs was reached, so the remaining blocks must be downloaded from
+    /// peers.
+    BlobsRequired,
+}
+
+/// Spawns a task which waits until backfill sync has passed the data availability boundary, and
+/// then imports blocks from the era files in `era_dir`.
+pub fn spawn_era_import<T: BeaconChainTypes>(
+    executor: TaskExecutor,
+    beacon_chain: Arc<BeaconChain<T>>,
+    era_dir: PathBuf,
+    log: Logger,
+) {
+    executor.spawn(
+        era_import(executor.clone(), beacon_chain, era_dir, log),
+        "era_import",
+    );
+}
+
+async fn era_import<T: BeaconChainTypes>(
+    executor: TaskExecutor,
+    beacon_chain: Arc<BeaconChain<T>>,
+    era_dir: PathBuf,
+    log: Logger,
+) {
+    let slot_duration = beacon_chain.slot_clock.slot_duration();
+
+    loop {
+        let Some(anchor) = beacon_chain.store.get_anchor_info() else {
+            return;
+        };
+        if anchor.block_backfill_complete(beacon_chain.genesis_backfill_slot) {
+            return;
+        }
+
+        // Blocks within the data availability window must be imported along with their blobs,
+        // which are not included in era files.
+        let ready = beacon_chain
+            .data_availability_boundary()
+            .map_or(true, |boundary| {
+                anchor.oldest_block_slot <= boundary.start_slot(T::EthSpec::slots_per_epoch())
+            });
+        if ready {
+            break;
+        }
+        sleep(slot_duration).await;
+    }
+
+    executor.spawn_blocking(
+        move || match beacon_chain.import_era_files(&era_dir) {
+            Ok(imported) => {
+                info!(log, "Finished importing era files"; "imported_blocks" => imported);
+            }
+            Err(e) => {
+                error!(
+                    log,
+                    "Failed to import era files";
+                    "error" => e,
+                    "info" => "blocks will be downloaded from peers instead",
+                );
+            }
+        },
+        "era_import_blocking",
+    );
+}
+
+impl<T: BeaconChainTypes> BeaconChain<T> {
+    /// Returns `true` whilst blocks are being imported from era files, during which backfill sync
+    /// should not download blocks.
+    pub fn is_era_import_in_progress(&self) -> bool {
+        self.era_import_in_progress.load(Ordering::Relaxed)
+    }
+
+    /// Import historic blocks from the era files in `era_dir`, working backwards from the oldest
+    /// block in the database until an era file is missing or backfill is complete.
+    ///
+    /// Returns the number of blocks imported.
+    pub fn import_era_files(&self, era_dir: &Path) -> Result<usize, String> {
+        self.era_import_in_progress.store(true, Ordering::Relaxed);
+        let result = self.import_era_files_inner(era_dir);
+        self.era_import_in_progress.store(false, Ordering::Relaxed);
+        result
+    }
+
+    fn import_era_files_inner(&self, era_dir: &Path) -> Result<usize, String> {
+        let era_files = self.find_era_files(era_dir)?;
+        let slots_per_era = T::EthSpec::slots_per_historical_root() as u64;
+
+        info!(
+            self.log,
+            "Importing blocks from era files";
+            "era_files" => era_files.values().map(Vec::len).sum::<usize>(),
+            "dir" => era_dir.display(),
+        );
+
+        let mut imported = 0;
+        let mut next_era = None;
+        loop {
+            let anchor = self
+                .store
+                .get_anchor_info()
+                .ok_or("Anchor info is missing")?;
+            if anchor.block_backfill_complete(self.genesis_backfill_slot) {
+                return Ok(imported);
+            }
+
+            // The era containing the parent of the oldest block, which is always after genesis,
+            // unless the previous era had no blocks prior to the oldest block.
+            let anchor_era = (anchor.oldest_block_slot.as_u64() - 1) / slots_per_era + 1;
+            let era = next_era.map_or(anchor_era, |next_era: u64| next_era.min(anchor_era));
+            if era == 0 {
+                return Ok(imported);
+            }
+            let Some(paths) = era_files.get(&era) else {
+                info!(
+                    self.log,
+                    "Era file not found, resuming backfill sync";
+                    "era" => era,
+                    "oldest_block_slot" => anchor.oldest_block_slot,
+                );
+                return Ok(imported);
+            };
+
+            // There may be several files for an era, e.g. from different sources, so try each of
+            // them until one can be imported.
+            let mut era_import = None;
+            for path in paths {
+                let mut file_imported = 0;
+                let result = self.import_era_file(path, &mut file_imported);
+                imported += file_imported;
+                match result {
+                    Ok(status) => {
+                        debug!(
+                            self.log,
+                            "Imported era file";
+                            "era" => era,
+                            "blocks" => file_imported,
+                            "path" => path.display(),
+                        );
+                        era_import = Some(status);
+                        break;
+                    }
+                    Err(e) => {
+                        warn!(
+                            self.log,
+                            "Failed to import era file";
+                            "error" => e,
+                            "era" => era,
+                            "blocks" => file_imported,
+                            "path" => path.display(),
+                        );
+                    }
+                }
+            }
+
+            match era_import {
+                Some(EraFileImport::Complete) => next_era = Some(era - 1),
+                Some(EraFileImport::BlobsRequired) => return Ok(imported),
+                None => {
+                    // Blocks must be imported in order, so older eras can't be imported either.
+                    warn!(
+                        self.log,
+                        "Unable to import era, resuming backfill sync";
+                        "era" => era,
+                        "files" => paths.len(),
+                    );
+                    return Ok(imported);
+                }
+            }
+        }
+    }
+
+    /// Returns the era files in `era_dir` for this network, keyed by era number.
+    ///
+    /// The files for each era are sorted by path.
+    fn find_era_files(&self, era_dir: &Path) -> Result<BTreeMap<u64, Vec<PathBuf>>, String> {
+        let entries = std::fs::read_dir(era_dir)
+            .map_err(|e| format!("Unable to read {}: {e:?}", era_dir.display()))?;
+
+        let mut era_files = BTreeMap::<_, Vec<_>>::new();
+        for entry in entries {
+            let path = entry
+                .map_err(|e| format!("Unable to read {}: {e:?}", era_dir.display()))?
+                .path();
+            let Some((config_name, era)) = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(parse_era_file_name)
+            else {
+                continue;
+            };
+            if self
+                .spec
+                .config_name
+                .as_deref()
+                .map_or(true, |name| name == config_name)
+            {
+                era_files.entry(era).or_default().push(path);
+            }
+        }
+        for paths in era_files.values_mut() {
+            paths.sort();
+        }
+        Ok(era_files)
+    }
+
+    /// Import the blocks prior to the oldest block from the era file at `path`, in batches working
+    /// backwards from the end of the era.
+    ///
+    /// The number of blocks imported is added to `imported`, including when an error occurs after
+    /// some batches have been imported.
+    fn import_era_file(&self, path: &Path, imported: &mut usize) -> Result<EraFileImport, String> {
+        let oldest_block_slot = self
+            .store
+            .get_anchor_info()
+            .ok_or("Anchor info is missing")?
+            .oldest_block_slot;
+        let mut reader = EraReader::open(path)
+            .map_err(|e| format!("Unable to open {}: {e:?}", path.display()))?;
+        let slots = reader
+            .block_slots()
+            .filter(|slot| *slot < oldest_block_slot)
+            .collect::<Vec<_>>();
+
+        for batch_slots in slots.rchunks(ERA_IMPORT_BATCH_SIZE) {
+            let mut blocks = Vec::with_capacity(batch_slots.len());
+            for slot in batch_slots {
+                let ssz_bytes = reader
+                    .read_block(*slot)
+                    .map_err(|e| format!("Unable to read {}: {e:?}", path.display()))?
+                    .ok_or_else(|| format!("Block at slot {slot} missing from era file"))?;
+                let block = SignedBeaconBlock::from_ssz_bytes(&ssz_bytes, &self.spec)
+                    .map_err(|e| format!("Invalid block at slot {slot}: {e:?}"))?;
+                if block.slot() != *slot {
+                    return Err(format!(
+                        "Block at slot {} is indexed at slot {slot} in {}",
+                        block.slot(),
+                        path.display()
+                    ));
+                }
+                blocks.push(RpcBlock::new_without_blobs(None, Arc::new(block)));
+            }
+
+            let mut available_blocks = Vec::with_capacity(blocks.len());
+            for block in self
+                .data_availability_checker
+                .verify_kzg_for_rpc_blocks(blocks)
+                .map_err(|e| format!("Failed to check block availability: {e:?}"))?
+            {
+                match block {
+                    MaybeAvailableBlock::Available(block) => available_blocks.push(block),
+                    MaybeAvailableBlock::AvailabilityPending { block_root, .. } => {
+                        warn!(
+                            self.log,
+                            "Era import reached a block which requires blobs";
+                            "block_root" => ?block_root,
+                        );
+                        return Ok(EraFileImport::BlobsRequired);
+                    }
+                }
+            }
+
+            *imported += self
+                .import_historical_block_batch(available_blocks)
+                .map_err(|e| format!("Failed to import blocks from {}: {e:?}", path.display()))?;
+        }
+        Ok(EraFileImport::Complete)
+    }
+}
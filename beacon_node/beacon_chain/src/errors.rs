@@ -48,6 +48,8 @@ pub enum BeaconChainError {
     InsufficientValidators,
     UnableToReadSlot,
     UnableToComputeTimeAtSlot,
+    /// The task was cancelled because its result is no longer required.
+    Cancelled,
     RevertedFinalizedEpoch {
         old: Checkpoint,
         new: Checkpoint,
@@ -17,6 +17,7 @@ pub struct ServerSentEventHandler<E: EthSpec> {
     contribution_tx: Sender<EventKind<E>>,
     payload_attributes_tx: Sender<EventKind<E>>,
     late_head: Sender<EventKind<E>>,
+    finality_stall_tx: Sender<EventKind<E>>,
     light_client_finality_update_tx: Sender<EventKind<E>>,
     light_client_optimistic_update_tx: Sender<EventKind<E>>,
     block_reward_tx: Sender<EventKind<E>>,
@@ -46,6 +47,7 @@ impl<E: EthSpec> ServerSentEventHandler<E> {
         let (contribution_tx, _) = broadcast::channel(capacity);
         let (payload_attributes_tx, _) = broadcast::channel(capacity);
         let (late_head, _) = broadcast::channel(capacity);
+        let (finality_stall_tx, _) = broadcast::channel(capacity);
         let (light_client_finality_update_tx, _) = broadcast::channel(capacity);
         let (light_client_optimistic_update_tx, _) = broadcast::channel(capacity);
         let (block_reward_tx, _) = broadcast::channel(capacity);
@@ -65,6 +67,7 @@ impl<E: EthSpec> ServerSentEventHandler<E> {
             contribution_tx,
             payload_attributes_tx,
             late_head,
+            finality_stall_tx,
             light_client_finality_update_tx,
             light_client_optimistic_update_tx,
             block_reward_tx,
@@ -126,6 +129,10 @@ impl<E: EthSpec> ServerSentEventHandler<E> {
                 .late_head
                 .send(kind)
                 .map(|count| log_count("late head", count)),
+            EventKind::FinalityStall(_) => self
+                .finality_stall_tx
+                .send(kind)
+                .map(|count| log_count("finality stall", count)),
             EventKind::LightClientFinalityUpdate(_) => self
                 .light_client_finality_update_tx
                 .send(kind)
@@ -200,6 +207,10 @@ impl<E: EthSpec> ServerSentEventHandler<E> {
         self.late_head.subscribe()
     }
 
+    pub fn subscribe_finality_stall(&self) -> Receiver<EventKind<E>> {
+        self.finality_stall_tx.subscribe()
+    }
+
     pub fn subscribe_light_client_finality_update(&self) -> Receiver<EventKind<E>> {
         self.light_client_finality_update_tx.subscribe()
     }
@@ -268,6 +279,10 @@ impl<E: EthSpec> ServerSentEventHandler<E> {
         self.late_head.receiver_count() > 0
     }
 
+    pub fn has_finality_stall_subscribers(&self) -> bool {
+        self.finality_stall_tx.receiver_count() > 0
+    }
+
     pub fn has_block_reward_subscribers(&self) -> bool {
         self.block_reward_tx.receiver_count() > 0
     }
@@ -0,0 +1,150 @@
+//! Detects when the chain stops finalizing and places the node into a "safe mode" until it
+//! finalizes again.
+//!
+//! The stall is measured from the finalized checkpoint of the head to the wall-clock epoch, and
+//! is only evaluated whilst the node is synced. Whilst syncing (including just after checkpoint
+//! sync) the finalized checkpoint of the head lags the network's, which would otherwise look like
+//! a stall.
+//!
+//! During a long period of non-finality nothing can be migrated to the freezer or pruned, so the
+//! hot database, fork choice and the caches of unfinalized states all grow until the chain
+//! finalizes again. When enabled with `ChainConfig::finality_stall_safe_mode`, the node:
+//!
+//! - Widens the state cache by `SAFE_MODE_STATE_CACHE_MULTIPLIER`, so that switching between the
+//!   competing forks of a non-finalizing chain does not repeatedly reload states from disk.
+//! - Defers blob pruning. Pruning is triggered by finalization, which may still advance slowly
+//!   during a stall, and any prune requested whilst stalled is held back and run once the chain
+//!   is finalizing again.
+//!
+//! Both are reverted upon leaving safe mode.
+//!
+//! Entering and leaving safe mode is logged, emitted as a `finality_stall` server-sent event and
+//! reflected in the `beacon_finality_stall_active` metric, so that operators are alerted before
+//! they notice degraded behaviour.
+use crate::events::EventKind;
+use crate::{metrics, BeaconChain, BeaconChainTypes};
+use eth2::types::SseFinalityStall;
+use parking_lot::Mutex;
+use slog::{info, warn};
+use std::num::NonZeroUsize;
+use types::{EthSpec, Slot};
+
+/// The factor by which the capacity of the state cache is multiplied whilst in safe mode.
+pub const SAFE_MODE_STATE_CACHE_MULTIPLIER: usize = 2;
+
+/// The node is only considered synced, and finality stalls are only detected, whilst the head is
+/// at most this many slots behind the wall-clock slot. A few skipped or late blocks are tolerated.
+pub const FINALITY_STALL_MAX_HEAD_DISTANCE: u64 = 4;
+
+/// Tracks whether the node is currently in safe mode.
+#[derive(Default)]
+pub struct FinalityWatchdog {
+    stall: Mutex<Option<FinalityStall>>,
+}
+
+/// The changes made upon entering safe mode, which must be reverted upon leaving it.
+struct FinalityStall {
+    /// The capacity of the state cache prior to it being widened, if it was widened.
+    prev_state_cache_capacity: Option<NonZeroUsize>,
+}
+
+impl FinalityWatchdog {
+    /// Returns `true` if the chain is currently failing to finalize.
+    pub fn is_stalled(&self) -> bool {
+        self.stall.lock().is_some()
+    }
+}
+
+impl<T: BeaconChainTypes> BeaconChain<T> {
+    /// Checks for a finality stall at `current_slot`, entering or leaving safe mode as required.
+    ///
+    /// Nothing changes whilst the node is syncing. This is cheap to call when there is no change,
+    /// and is called once per slot.
+    pub fn check_finality_stall(&self, current_slot: Slot) {
+        let cached_head = self.canonical_head.cached_head();
+        if cached_head.head_slot() + FINALITY_STALL_MAX_HEAD_DISTANCE < current_slot {
+            return;
+        }
+
+        let current_epoch = current_slot.epoch(T::EthSpec::slots_per_epoch());
+        let finalized_epoch = cached_head.finalized_checkpoint().epoch;
+        let epochs_since_finalization = current_epoch.saturating_sub(finalized_epoch).as_u64();
+        metrics::set_gauge(
+            &metrics::BEACON_EPOCHS_SINCE_FINALIZATION,
+            epochs_since_finalization as i64,
+        );
+
+        let is_stalled = epochs_since_finalization >= self.config.finality_stall_epochs;
+        let mut stall = self.finality_watchdog.stall.lock();
+
+        match (stall.take(), is_stalled) {
+            (None, true) => {
+                let prev_state_cache_capacity = if self.config.finality_stall_safe_mode {
+                    self.store_migrator.defer_blob_pruning();
+                    self.widen_state_cache()
+                } else {
+                    None
+                };
+                *stall = Some(FinalityStall {
+                    prev_state_cache_capacity,
+                });
+                metrics::inc_counter(&metrics::BEACON_FINALITY_STALLS_TOTAL);
+
+                warn!(
+                    self.log,
+                    "Chain is failing to finalize";
+                    "info" => "entering safe mode until the chain finalizes",
+                    "safe_mode_enabled" => self.config.finality_stall_safe_mode,
+                    "epochs_since_finalization" => epochs_since_finalization,
+                    "finalized_epoch" => finalized_epoch,
+                    "current_epoch" => current_epoch,
+                );
+            }
+            (Some(prev_stall), false) => {
+                if let Some(capacity) = prev_stall.prev_state_cache_capacity {
+                    self.store.set_state_cache_capacity(capacity);
+                }
+                // Run any prune held back during the stall, including the one requested by the
+                // finalization which ended it.
+                self.store_migrator.resume_blob_pruning();
+
+                info!(
+                    self.log,
+                    "Chain is finalizing again";
+                    "info" => "leaving safe mode",
+                    "epochs_since_finalization" => epochs_since_finalization,
+                    "finalized_epoch" => finalized_epoch,
+                    "current_epoch" => current_epoch,
+                );
+            }
+            (prev_stall, _) => {
+                // No change.
+                *stall = prev_stall;
+                return;
+            }
+        }
+
+        metrics::set_gauge(&metrics::BEACON_FINALITY_STALL_ACTIVE, is_stalled as i64);
+
+        if let Some(event_handler) = self.event_handler.as_ref() {
+            if event_handler.has_finality_stall_subscribers() {
+                event_handler.register(EventKind::FinalityStall(SseFinalityStall {
+                    stalled: is_stalled,
+                    current_epoch,
+                    finalized_epoch,
+                    epochs_since_finalization,
+                }));
+            }
+        }
+    }
+
+    /// Multiplies the capacity of the state cache by `SAFE_MODE_STATE_CACHE_MULTIPLIER`, returning
+    /// the previous capacity.
+    fn widen_state_cache(&self) -> Option<NonZeroUsize> {
+        let prev_capacity = NonZeroUsize::new(self.store.state_cache_capacity())?;
+        let capacity =
+            prev_capacity.saturating_mul(NonZeroUsize::new(SAFE_MODE_STATE_CACHE_MULTIPLIER)?);
+        self.store.set_state_cache_capacity(capacity);
+        Some(prev_capacity)
+    }
+}
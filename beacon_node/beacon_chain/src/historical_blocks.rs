@@ -3,14 +3,23 @@ use crate::{errors::BeaconChainError as Error, metrics, BeaconChain, BeaconChain
 use itertools::Itertools;
 use slog::debug;
 use state_processing::{
-    per_block_processing::ParallelSignatureSets,
+    common::{attesting_indices_base, attesting_indices_electra},
+    per_block_processing::{
+        errors::{AttestationInvalid, BlockOperationError},
+        ParallelSignatureSets,
+    },
     signature_sets::{block_proposal_signature_set_from_parts, Error as SignatureSetError},
 };
 use std::borrow::Cow;
+use std::collections::{hash_map::Entry, HashMap};
 use std::iter;
+use std::sync::Arc;
 use std::time::Duration;
 use store::{chunked_vector::BlockRoots, AnchorInfo, BlobInfo, ChunkWriter, KeyValueStore};
-use types::{Hash256, Slot};
+use types::{
+    AttestationRef, BeaconStateError, CommitteeCache, EthSpec, Hash256, IndexedAttestation,
+    SignedBeaconBlock, Slot,
+};
 
 /// Use a longer timeout on the pubkey cache.
 ///
@@ -243,6 +252,112 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             self.store_migrator.process_reconstruction();
         }
 
+        self.import_historical_blocks_update_slasher(&signed_blocks);
+
         Ok(num_relevant)
     }
+
+    /// If a slasher is configured to process backfilled blocks, provide it with the proposals and
+    /// attestations from `blocks`.
+    ///
+    /// The blocks are an ancestor chain of the finalized anchor, so their attestations were
+    /// verified when the blocks were first imported by the network and aren't re-verified here.
+    fn import_historical_blocks_update_slasher(
+        &self,
+        blocks: &[Arc<SignedBeaconBlock<T::EthSpec>>],
+    ) {
+        let Some(slasher) = self
+            .slasher
+            .as_ref()
+            .filter(|slasher| slasher.config().backfill)
+        else {
+            return;
+        };
+        let Ok(current_epoch) = self.epoch() else {
+            return;
+        };
+        let history_length = slasher.config().history_length as u64;
+
+        // Committees for historic epochs can be computed from the head state, which retains the
+        // RANDAO mixes and validator activity required to shuffle them.
+        let head = self.canonical_head.cached_head();
+        let state = &head.snapshot.beacon_state;
+        let mut committee_caches = HashMap::new();
+        let mut num_attestations = 0;
+
+        for block in blocks {
+            // The slasher would drop anything older than its history length.
+            if block.message().epoch() + history_length <= current_epoch {
+                continue;
+            }
+            slasher.accept_block_header(block.signed_block_header());
+
+            for attestation in block.message().body().attestations() {
+                let epoch = attestation.data().target.epoch;
+                let committee_cache = match committee_caches.entry(epoch) {
+                    Entry::Occupied(occupied) => occupied.into_mut(),
+                    Entry::Vacant(vacant) => {
+                        match CommitteeCache::initialized(state, epoch, &self.spec) {
+                            Ok(committee_cache) => vacant.insert(committee_cache),
+                            Err(e) => {
+                                debug!(
+                                    self.log,
+                                    "Failed to build committee cache";
+                                    "purpose" => "slasher backfill",
+                                    "epoch" => epoch,
+                                    "error" => ?e,
+                                );
+                                continue;
+                            }
+                        }
+                    }
+                };
+
+                match historical_indexed_attestation(committee_cache, attestation) {
+                    Ok(indexed_attestation) => {
+                        slasher.accept_attestation(indexed_attestation);
+                        num_attestations += 1;
+                    }
+                    Err(e) => {
+                        debug!(
+                            self.log,
+                            "Failed to get indexed attestation";
+                            "purpose" => "slasher backfill",
+                            "attestation_slot" => attestation.data().slot,
+                            "error" => ?e,
+                        );
+                    }
+                }
+            }
+        }
+
+        debug!(
+            self.log,
+            "Provided backfilled blocks to slasher";
+            "num_attestations" => num_attestations,
+        );
+    }
+}
+
+/// Convert `attestation` to indexed form using the committees in `committee_cache`.
+fn historical_indexed_attestation<E: EthSpec>(
+    committee_cache: &CommitteeCache,
+    attestation: AttestationRef<E>,
+) -> Result<IndexedAttestation<E>, BlockOperationError<AttestationInvalid>> {
+    match attestation {
+        AttestationRef::Base(attestation) => {
+            let committee = committee_cache
+                .get_beacon_committee(attestation.data.slot, attestation.data.index)
+                .ok_or(BeaconStateError::NoCommittee {
+                    slot: attestation.data.slot,
+                    index: attestation.data.index,
+                })?;
+            attesting_indices_base::get_indexed_attestation(committee.committee, attestation)
+        }
+        AttestationRef::Electra(attestation) => {
+            let committees =
+                committee_cache.get_beacon_committees_at_slot(attestation.data.slot)?;
+            attesting_indices_electra::get_indexed_attestation(&committees, attestation)
+        }
+    }
 }
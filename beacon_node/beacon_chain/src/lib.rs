@@ -2,6 +2,7 @@ pub mod attestation_rewards;
 pub mod attestation_simulator;
 pub mod attestation_verification;
 mod attester_cache;
+pub mod balances_cache;
 pub mod beacon_block_reward;
 mod beacon_block_streamer;
 mod beacon_chain;
@@ -18,16 +19,20 @@ pub mod builder;
 pub mod canonical_head;
 pub mod capella_readiness;
 pub mod chain_config;
+pub mod clock_sanity;
+pub mod compaction_timer;
 pub mod data_availability_checker;
 pub mod data_column_verification;
 pub mod deneb_readiness;
 mod early_attester_cache;
 pub mod electra_readiness;
+pub mod era_import;
 mod errors;
 pub mod eth1_chain;
 mod eth1_finalization_cache;
 pub mod events;
 pub mod execution_payload;
+pub mod finality_watchdog;
 pub mod fork_choice_signal;
 pub mod fork_revert;
 pub mod graffiti_calculator;
@@ -44,13 +49,16 @@ pub mod observed_aggregates;
 mod observed_attesters;
 mod observed_blob_sidecars;
 pub mod observed_block_producers;
+pub mod observed_equivocations;
 pub mod observed_operations;
 mod observed_slashable;
 pub mod otb_verification_service;
+pub mod partial_block_body;
 mod persisted_beacon_chain;
 mod persisted_fork_choice;
 mod pre_finalization_cache;
 pub mod proposer_prep_service;
+pub mod remote_slasher;
 pub mod schema_change;
 pub mod shuffling_cache;
 pub mod state_advance_timer;
@@ -283,11 +283,26 @@ lazy_static! {
     pub static ref SHUFFLING_CACHE_HITS: Result<IntCounter> =
         try_create_int_counter("beacon_shuffling_cache_hits_total", "Count of times shuffling cache fulfils request");
     pub static ref SHUFFLING_CACHE_MISSES: Result<IntCounter> =
-        try_create_int_counter("beacon_shuffling_cache_misses_total", "Count of times shuffling cache fulfils request");
+        try_create_int_counter("beacon_shuffling_cache_misses_total", "Count of times shuffling cache fails to fulfil request");
     pub static ref SHUFFLING_CACHE_PROMISE_HITS: Result<IntCounter> =
         try_create_int_counter("beacon_shuffling_cache_promise_hits_total", "Count of times shuffling cache returns a promise to future shuffling");
     pub static ref SHUFFLING_CACHE_PROMISE_FAILS: Result<IntCounter> =
         try_create_int_counter("beacon_shuffling_cache_promise_fails_total", "Count of times shuffling cache detects a failed promise");
+    pub static ref SHUFFLING_CACHE_PROMISES_CREATED: Result<IntCounter> =
+        try_create_int_counter("beacon_shuffling_cache_promises_created_total", "Count of times a shuffling is promised to be computed after a cache miss");
+    pub static ref SHUFFLING_CACHE_PROMISE_WAIT_TIMES: Result<Histogram> = try_create_histogram_with_buckets(
+        "beacon_shuffling_cache_promise_wait_seconds",
+        "Time spent waiting for another task to compute a promised shuffling",
+        exponential_buckets(1e-3, 2.0, 12)
+    );
+
+    /*
+     * Validator balances cache
+     */
+    pub static ref VALIDATOR_BALANCES_CACHE_HITS: Result<IntCounter> =
+        try_create_int_counter("beacon_validator_balances_cache_hits_total", "Count of times the validator balances cache fulfils request");
+    pub static ref VALIDATOR_BALANCES_CACHE_MISSES: Result<IntCounter> =
+        try_create_int_counter("beacon_validator_balances_cache_misses_total", "Count of times the validator balances cache fails to fulfil request");
 
     /*
      * Early attester cache
@@ -305,6 +320,54 @@ lazy_static! {
         "beacon_reqresp_pre_import_cache_hits",
         "Count of times the reqresp pre import cache returns an item"
     );
+
+    /*
+     * Database compaction
+     */
+    pub static ref STORE_COMPACTION_DEBT_EPOCHS: Result<IntGauge> = try_create_int_gauge(
+        "store_compaction_debt_epochs",
+        "Number of finalized epochs pruned from the database since it was last compacted"
+    );
+
+    /*
+     * Finality watchdog
+     */
+    pub static ref BEACON_EPOCHS_SINCE_FINALIZATION: Result<IntGauge> = try_create_int_gauge(
+        "beacon_epochs_since_finalization",
+        "Number of epochs between the current epoch and the finalized epoch"
+    );
+    pub static ref BEACON_FINALITY_STALL_ACTIVE: Result<IntGauge> = try_create_int_gauge(
+        "beacon_finality_stall_active",
+        "Set to 1 whilst the chain is failing to finalize and the node is in safe mode"
+    );
+    pub static ref BEACON_FINALITY_STALLS_TOTAL: Result<IntCounter> = try_create_int_counter(
+        "beacon_finality_stalls_total",
+        "Count of finality stalls detected since startup"
+    );
+
+    /*
+     * Clock sanity
+     */
+    pub static ref BEACON_CLOCK_SKEW_PEERS_MILLISECONDS: Result<IntGauge> = try_create_int_gauge(
+        "beacon_clock_skew_peers_milliseconds",
+        "Skew of the local clock estimated from the arrival times of gossip messages"
+    );
+    pub static ref BEACON_CLOCK_SKEW_NTP_MILLISECONDS: Result<IntGauge> = try_create_int_gauge(
+        "beacon_clock_skew_ntp_milliseconds",
+        "Skew of the local clock measured against the configured NTP server"
+    );
+    pub static ref BEACON_CLOCK_SKEW_DETECTED: Result<IntGauge> = try_create_int_gauge(
+        "beacon_clock_skew_detected",
+        "Set to 1 whilst the skew of the local clock exceeds the configured threshold"
+    );
+
+    /*
+     * Shadow fork
+     */
+    pub static ref BEACON_SHADOW_FORK_DIVERGED: Result<IntGauge> = try_create_int_gauge(
+        "beacon_shadow_fork_diverged",
+        "Set to 1 whilst running a shadow fork whose fork schedule has diverged from the network's"
+    );
 }
 
 // Second lazy-static block is used to account for macro recursion limit.
@@ -377,6 +440,12 @@ lazy_static! {
         "Time taken to run `after_finalization`",
         exponential_buckets(1e-3, 2.0, 10)
     );
+    pub static ref FORK_CHOICE_HEAD_EVENT_SLOT_START_DELAY_TIMES: Result<Histogram> = try_create_histogram_with_buckets(
+        "beacon_fork_choice_head_event_slot_start_delay_seconds",
+        "Duration between the start of the head block's slot and the head being published to \
+        validators and server-sent event subscribers",
+        exponential_buckets(0.05, 2.0, 10)
+    );
     pub static ref FORK_CHOICE_PROCESS_BLOCK_TIMES: Result<Histogram> = try_create_histogram(
         "beacon_fork_choice_process_block_seconds",
         "Time taken to add a block and all attestations to fork choice"
@@ -808,6 +877,31 @@ lazy_static! {
         "Number of times a validator's sync committee message has been seen in a sync aggregate",
         &["src", "validator"]
     );
+    pub static ref VALIDATOR_MONITOR_SYNC_AGGREGATION_CONTRIBUTIONS_TOTAL: Result<IntCounterVec> = try_create_int_counter_vec(
+        "validator_monitor_sync_aggregation_contributions_total",
+        "Number of sync contributions published by monitored aggregators",
+        &["subnet"]
+    );
+    pub static ref VALIDATOR_MONITOR_SYNC_AGGREGATION_CONTRIBUTION_PARTICIPANTS_TOTAL: Result<IntCounterVec> = try_create_int_counter_vec(
+        "validator_monitor_sync_aggregation_contribution_participants_total",
+        "Number of sync signatures aggregated into contributions published by monitored aggregators",
+        &["subnet"]
+    );
+    pub static ref VALIDATOR_MONITOR_SYNC_AGGREGATION_BLOCK_PARTICIPANTS_TOTAL: Result<IntCounterVec> = try_create_int_counter_vec(
+        "validator_monitor_sync_aggregation_block_participants_total",
+        "Number of sync signatures included in blocks for subnets and slots at which monitored aggregators published a contribution",
+        &["subnet"]
+    );
+    pub static ref VALIDATOR_MONITOR_SYNC_AGGREGATION_BLOCK_INCLUSIONS_TOTAL: Result<IntCounterVec> = try_create_int_counter_vec(
+        "validator_monitor_sync_aggregation_block_inclusions_total",
+        "Number of sync signatures from contributions published by monitored aggregators which were included in blocks",
+        &["subnet"]
+    );
+    pub static ref VALIDATOR_MONITOR_SYNC_AGGREGATION_EFFICIENCY: Result<GaugeVec> = try_create_float_gauge_vec(
+        "validator_monitor_sync_aggregation_efficiency",
+        "Fraction of the sync signatures included in the latest block which were present in the contribution published by monitored aggregators",
+        &["subnet"]
+    );
     pub static ref VALIDATOR_MONITOR_ATTESTATION_IN_BLOCK_DELAY_SLOTS: Result<IntGaugeVec> = try_create_int_gauge_vec(
         "validator_monitor_attestation_in_block_delay_slots",
         "The excess slots (beyond the minimum delay) between the attestation slot and the block slot.",
@@ -1195,6 +1289,18 @@ lazy_static! {
         "beacon_light_client_server_cache_prev_block_cache_miss",
         "Count of prev block cache misses",
     );
+
+    /*
+    * Remote slasher metrics
+    */
+    pub static ref REMOTE_SLASHER_QUEUED_ATTESTATIONS: Result<IntGauge> = try_create_int_gauge(
+        "beacon_remote_slasher_queued_attestations",
+        "Number of attestations waiting to be taken by the remote slasher",
+    );
+    pub static ref REMOTE_SLASHER_DROPPED_ATTESTATIONS: Result<IntCounter> = try_create_int_counter(
+        "beacon_remote_slasher_dropped_attestations_total",
+        "Number of attestations dropped because the remote slasher queue was full",
+    );
 }
 
 /// Scrape the `beacon_chain` for metrics that are not constantly updated (e.g., the present slot,
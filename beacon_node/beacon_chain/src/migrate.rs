@@ -1,11 +1,13 @@
 use crate::beacon_chain::BEACON_CHAIN_DB_KEY;
 use crate::errors::BeaconChainError;
 use crate::head_tracker::{HeadTracker, SszHeadTracker};
+use crate::metrics;
 use crate::persisted_beacon_chain::{PersistedBeaconChain, DUMMY_CANONICAL_HEAD_BLOCK_ROOT};
 use parking_lot::Mutex;
 use slog::{debug, error, info, warn, Logger};
 use std::collections::{HashMap, HashSet};
 use std::mem;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{mpsc, Arc};
 use std::thread;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
@@ -34,6 +36,10 @@ pub struct BackgroundMigrator<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>
     db: Arc<HotColdDB<E, Hot, Cold>>,
     /// Record of when the last migration ran, for enforcing `epochs_per_migration`.
     prev_migration: Arc<Mutex<PrevMigration>>,
+    /// Pruning which has not yet been followed by a compaction.
+    compaction_debt: Arc<CompactionDebt>,
+    /// Whether blob pruning is deferred (see `finality_watchdog`).
+    blob_pruning: Mutex<BlobPruning>,
     #[allow(clippy::type_complexity)]
     tx_thread: Option<Mutex<(mpsc::Sender<Notification>, thread::JoinHandle<()>)>>,
     /// Genesis block root, for persisting the `PersistedBeaconChain`.
@@ -79,6 +85,64 @@ pub struct PrevMigration {
     epochs_per_migration: u64,
 }
 
+/// Tracks the pruning which has occurred since the database was last compacted.
+///
+/// Compactions are not run immediately after pruning. Instead they are marked as due, and run
+/// once the node is idle (see `compaction_timer`).
+#[derive(Default)]
+pub struct CompactionDebt {
+    /// The number of finalized epochs pruned since the last compaction.
+    epochs: AtomicU64,
+    /// Whether a compaction should be run at the next idle period.
+    due: AtomicBool,
+}
+
+impl CompactionDebt {
+    fn add_epochs(&self, epochs: u64) -> u64 {
+        let total = self.epochs.fetch_add(epochs, Ordering::Relaxed) + epochs;
+        metrics::set_gauge(&metrics::STORE_COMPACTION_DEBT_EPOCHS, total as i64);
+        total
+    }
+
+    /// Take the scheduled compaction, if any, so that it is only run once.
+    fn take_due(&self) -> bool {
+        self.due.swap(false, Ordering::Relaxed)
+    }
+
+    /// Schedule a compaction whose run failed or was skipped for the next idle period, keeping
+    /// the debt which it would have paid off.
+    fn reschedule(&self) {
+        self.due.store(true, Ordering::Relaxed);
+    }
+
+    fn clear(&self) {
+        self.epochs.store(0, Ordering::Relaxed);
+        metrics::set_gauge(&metrics::STORE_COMPACTION_DEBT_EPOCHS, 0);
+    }
+}
+
+/// Return `true` if a compaction should be scheduled, given the number of epochs by which
+/// finalization just advanced and the time which has passed since the last compaction.
+///
+/// Only a single large finality gap triggers an early compaction. The steady advance of a healthy
+/// chain waits for `MAX_COMPACTION_PERIOD_SECONDS`, even though its debt passes
+/// `COMPACTION_FINALITY_DISTANCE` every few days.
+fn compaction_is_due(finality_gap_epochs: u64, seconds_since_last_compaction: u64) -> bool {
+    seconds_since_last_compaction > MAX_COMPACTION_PERIOD_SECONDS
+        || (finality_gap_epochs > COMPACTION_FINALITY_DISTANCE
+            && seconds_since_last_compaction > MIN_COMPACTION_PERIOD_SECONDS)
+}
+
+/// Return how long to pause after a compaction step which took `step_duration`, so that
+/// compaction occupies at most `io_limit_percent` of the time.
+fn compaction_pause(step_duration: Duration, io_limit_percent: u32) -> Duration {
+    if io_limit_percent == 0 || io_limit_percent >= 100 {
+        Duration::ZERO
+    } else {
+        step_duration * (100 - io_limit_percent) / io_limit_percent
+    }
+}
+
 /// Pruning can be successful, or in rare cases deferred to a later point.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PruningOutcome {
@@ -113,11 +177,21 @@ pub enum PruningError {
     UnexpectedUnequalStateRoots,
 }
 
+/// Whether requests to prune blobs are run or held back.
+enum BlobPruning {
+    Enabled,
+    /// Requests are held back. Only the latest is kept, as it covers all earlier ones.
+    Deferred {
+        latest_request: Option<Epoch>,
+    },
+}
+
 /// Message sent to the migration thread containing the information it needs to run.
 pub enum Notification {
     Finalization(FinalizationNotification),
     Reconstruction,
     PruneBlobs(Epoch),
+    Compaction(Arc<CompactionDebt>),
 }
 
 pub struct FinalizationNotification {
@@ -125,6 +199,7 @@ pub struct FinalizationNotification {
     finalized_checkpoint: Checkpoint,
     head_tracker: Arc<HeadTracker>,
     prev_migration: Arc<Mutex<PrevMigration>>,
+    compaction_debt: Arc<CompactionDebt>,
     genesis_block_root: Hash256,
 }
 
@@ -150,6 +225,8 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> BackgroundMigrator<E, Ho
             db,
             tx_thread,
             prev_migration,
+            compaction_debt: Arc::new(CompactionDebt::default()),
+            blob_pruning: Mutex::new(BlobPruning::Enabled),
             genesis_block_root,
             log,
         }
@@ -171,6 +248,7 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> BackgroundMigrator<E, Ho
             finalized_checkpoint,
             head_tracker,
             prev_migration: self.prev_migration.clone(),
+            compaction_debt: self.compaction_debt.clone(),
             genesis_block_root: self.genesis_block_root,
         };
 
@@ -193,6 +271,15 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> BackgroundMigrator<E, Ho
     }
 
     pub fn process_prune_blobs(&self, data_availability_boundary: Epoch) {
+        if let BlobPruning::Deferred { latest_request } = &mut *self.blob_pruning.lock() {
+            debug!(
+                self.log,
+                "Deferring blob pruning";
+                "data_availability_boundary" => data_availability_boundary,
+            );
+            *latest_request = Some(data_availability_boundary);
+            return;
+        }
         if let Some(Notification::PruneBlobs(data_availability_boundary)) =
             self.send_background_notification(Notification::PruneBlobs(data_availability_boundary))
         {
@@ -200,6 +287,52 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> BackgroundMigrator<E, Ho
         }
     }
 
+    /// Hold back requests to prune blobs until `resume_blob_pruning` is called.
+    pub fn defer_blob_pruning(&self) {
+        let mut blob_pruning = self.blob_pruning.lock();
+        if let BlobPruning::Enabled = *blob_pruning {
+            *blob_pruning = BlobPruning::Deferred {
+                latest_request: None,
+            };
+        }
+    }
+
+    /// Stop deferring blob pruning, running the latest request received whilst it was deferred.
+    pub fn resume_blob_pruning(&self) {
+        let prev = mem::replace(&mut *self.blob_pruning.lock(), BlobPruning::Enabled);
+        if let BlobPruning::Deferred {
+            latest_request: Some(data_availability_boundary),
+        } = prev
+        {
+            self.process_prune_blobs(data_availability_boundary);
+        }
+    }
+
+    /// Return `true` if blob pruning is currently deferred.
+    pub fn is_blob_pruning_deferred(&self) -> bool {
+        matches!(*self.blob_pruning.lock(), BlobPruning::Deferred { .. })
+    }
+
+    /// Return `true` if enough pruning has occurred that a compaction should be run.
+    pub fn is_compaction_due(&self) -> bool {
+        self.compaction_debt.due.load(Ordering::Relaxed)
+    }
+
+    /// Run a compaction if one is due.
+    ///
+    /// This should only be called whilst the node is idle.
+    pub fn process_compaction_if_due(&self) {
+        if !self.compaction_debt.take_due() {
+            return;
+        }
+
+        if let Some(Notification::Compaction(compaction_debt)) = self
+            .send_background_notification(Notification::Compaction(self.compaction_debt.clone()))
+        {
+            Self::run_compaction(self.db.clone(), &compaction_debt, &self.log);
+        }
+    }
+
     pub fn run_reconstruction(db: Arc<HotColdDB<E, Hot, Cold>>, log: &Logger) {
         if let Err(e) = db.reconstruct_historic_states() {
             error!(
@@ -367,14 +500,15 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> BackgroundMigrator<E, Ho
             }
         };
 
-        // Finally, compact the database so that new free space is properly reclaimed.
-        if let Err(e) = Self::run_compaction(
+        // Finally, schedule a compaction so that new free space is properly reclaimed.
+        if let Err(e) = Self::schedule_compaction(
             db,
+            &notif.compaction_debt,
             old_finalized_checkpoint.epoch,
             notif.finalized_checkpoint.epoch,
             log,
         ) {
-            warn!(log, "Database compaction failed"; "error" => format!("{:?}", e));
+            warn!(log, "Database compaction scheduling failed"; "error" => format!("{:?}", e));
         }
 
         debug!(log, "Database consolidation complete");
@@ -393,10 +527,12 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> BackgroundMigrator<E, Ho
                 let mut reconstruction_notif = None;
                 let mut finalization_notif = None;
                 let mut prune_blobs_notif = None;
+                let mut compaction_notif = None;
                 match notif {
                     Notification::Reconstruction => reconstruction_notif = Some(notif),
                     Notification::Finalization(fin) => finalization_notif = Some(fin),
                     Notification::PruneBlobs(dab) => prune_blobs_notif = Some(dab),
+                    Notification::Compaction(debt) => compaction_notif = Some(debt),
                 }
                 // Read the rest of the messages in the channel, taking the best of each type.
                 for notif in rx.try_iter() {
@@ -416,11 +552,15 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> BackgroundMigrator<E, Ho
                         Notification::PruneBlobs(dab) => {
                             prune_blobs_notif = std::cmp::max(prune_blobs_notif, Some(dab));
                         }
+                        Notification::Compaction(debt) => compaction_notif = Some(debt),
                     }
                 }
                 // If reconstruction is on-going, ignore finalization migration and blob pruning.
                 if reconstruction_notif.is_some() {
                     Self::run_reconstruction(db.clone(), &log);
+                    if let Some(debt) = compaction_notif {
+                        debt.reschedule();
+                    }
                 } else {
                     if let Some(fin) = finalization_notif {
                         Self::run_migration(db.clone(), fin, &log);
@@ -428,6 +568,9 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> BackgroundMigrator<E, Ho
                     if let Some(dab) = prune_blobs_notif {
                         Self::run_prune_blobs(db.clone(), dab, &log);
                     }
+                    if let Some(debt) = compaction_notif {
+                        Self::run_compaction(db.clone(), &debt, &log);
+                    }
                 }
             }
         });
@@ -715,10 +858,12 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> BackgroundMigrator<E, Ho
         })
     }
 
-    /// Compact the database if it has been more than `COMPACTION_PERIOD_SECONDS` since it
-    /// was last compacted.
-    pub fn run_compaction(
+    /// Record the pruning of the epochs between `old_finalized_epoch` and `new_finalized_epoch`,
+    /// and mark a compaction as due if the gap between them is large or the last compaction was
+    /// long ago.
+    fn schedule_compaction(
         db: Arc<HotColdDB<E, Hot, Cold>>,
+        compaction_debt: &CompactionDebt,
         old_finalized_epoch: Epoch,
         new_finalized_epoch: Epoch,
         log: &Logger,
@@ -727,36 +872,152 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> BackgroundMigrator<E, Ho
             return Ok(());
         }
 
+        let finality_gap_epochs = new_finalized_epoch
+            .saturating_sub(old_finalized_epoch)
+            .as_u64();
+        let debt_epochs = compaction_debt.add_epochs(finality_gap_epochs);
+
         let last_compaction_timestamp = db
             .load_compaction_timestamp()?
             .unwrap_or_else(|| Duration::from_secs(0));
-        let start_time = SystemTime::now()
+        let seconds_since_last_compaction = SystemTime::now()
             .duration_since(UNIX_EPOCH)
-            .unwrap_or(last_compaction_timestamp);
-        let seconds_since_last_compaction = start_time
-            .checked_sub(last_compaction_timestamp)
+            .ok()
+            .and_then(|now| now.checked_sub(last_compaction_timestamp))
             .as_ref()
             .map_or(0, Duration::as_secs);
 
-        if seconds_since_last_compaction > MAX_COMPACTION_PERIOD_SECONDS
-            || (new_finalized_epoch - old_finalized_epoch > COMPACTION_FINALITY_DISTANCE
-                && seconds_since_last_compaction > MIN_COMPACTION_PERIOD_SECONDS)
-        {
-            info!(
+        if compaction_is_due(finality_gap_epochs, seconds_since_last_compaction) {
+            debug!(
                 log,
-                "Starting database compaction";
-                "old_finalized_epoch" => old_finalized_epoch,
+                "Database compaction scheduled";
+                "finality_gap_epochs" => finality_gap_epochs,
+                "debt_epochs" => debt_epochs,
                 "new_finalized_epoch" => new_finalized_epoch,
             );
-            db.compact()?;
+            compaction_debt.reschedule();
+        }
+        Ok(())
+    }
+
+    /// Compact the database, pausing between steps to respect the configured I/O limit.
+    pub fn run_compaction(
+        db: Arc<HotColdDB<E, Hot, Cold>>,
+        compaction_debt: &CompactionDebt,
+        log: &Logger,
+    ) {
+        let io_limit_percent = u32::from(db.get_config().compaction_io_limit_percent);
+
+        info!(
+            log,
+            "Starting database compaction";
+            "debt_epochs" => compaction_debt.epochs.load(Ordering::Relaxed),
+            "io_limit_percent" => io_limit_percent,
+        );
+        let start_time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+
+        let result = db
+            .compact_in_steps(|step_duration| {
+                let pause = compaction_pause(step_duration, io_limit_percent);
+                if !pause.is_zero() {
+                    thread::sleep(pause);
+                }
+            })
+            .and_then(|()| {
+                let finish_time = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or(start_time);
+                db.store_compaction_timestamp(finish_time)
+            });
 
-            let finish_time = SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .unwrap_or(start_time);
-            db.store_compaction_timestamp(finish_time)?;
+        match result {
+            Ok(()) => {
+                compaction_debt.clear();
+                info!(log, "Database compaction complete");
+            }
+            Err(e) => {
+                // Keep the debt and try again at the next idle period.
+                compaction_debt.reschedule();
+                warn!(log, "Database compaction failed"; "error" => ?e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compaction_debt_accumulates_until_cleared() {
+        let debt = CompactionDebt::default();
+        assert_eq!(debt.add_epochs(3), 3);
+        assert_eq!(debt.add_epochs(5), 8);
+        debt.clear();
+        assert_eq!(debt.add_epochs(2), 2);
+    }
 
-            info!(log, "Database compaction complete");
+    #[test]
+    fn failed_compaction_keeps_debt_scheduled() {
+        let debt = CompactionDebt::default();
+        debt.add_epochs(COMPACTION_FINALITY_DISTANCE + 1);
+        debt.reschedule();
+
+        // The compaction is only handed out once.
+        assert!(debt.take_due());
+        assert!(!debt.take_due());
+
+        // A failed compaction leaves both the debt and the schedule in place.
+        debt.reschedule();
+        assert!(debt.take_due());
+        assert_eq!(
+            debt.epochs.load(Ordering::Relaxed),
+            COMPACTION_FINALITY_DISTANCE + 1
+        );
+    }
+
+    #[test]
+    fn compaction_scheduling() {
+        // Always compact once the maximum period has passed, regardless of the gap.
+        assert!(compaction_is_due(1, MAX_COMPACTION_PERIOD_SECONDS + 1));
+        assert!(!compaction_is_due(1, MAX_COMPACTION_PERIOD_SECONDS));
+
+        // Compact after a large finality gap, but no more often than the minimum period.
+        let large_gap = COMPACTION_FINALITY_DISTANCE + 1;
+        assert!(compaction_is_due(
+            large_gap,
+            MIN_COMPACTION_PERIOD_SECONDS + 1
+        ));
+        assert!(!compaction_is_due(large_gap, MIN_COMPACTION_PERIOD_SECONDS));
+        assert!(!compaction_is_due(
+            COMPACTION_FINALITY_DISTANCE,
+            MIN_COMPACTION_PERIOD_SECONDS + 1
+        ));
+    }
+
+    #[test]
+    fn steady_finalization_waits_for_max_period() {
+        // A chain finalizing every epoch accumulates more debt than `COMPACTION_FINALITY_DISTANCE`
+        // well before the maximum period, without triggering an early compaction.
+        let debt = CompactionDebt::default();
+        let mut seconds_since_last_compaction = 0;
+        while seconds_since_last_compaction <= MAX_COMPACTION_PERIOD_SECONDS {
+            debt.add_epochs(1);
+            assert!(!compaction_is_due(1, seconds_since_last_compaction));
+            seconds_since_last_compaction += 384;
         }
-        Ok(())
+        assert!(debt.epochs.load(Ordering::Relaxed) > COMPACTION_FINALITY_DISTANCE);
+        assert!(compaction_is_due(1, seconds_since_last_compaction));
+    }
+
+    #[test]
+    fn compaction_pacing() {
+        let step = Duration::from_millis(100);
+        assert_eq!(compaction_pause(step, 100), Duration::ZERO);
+        assert_eq!(compaction_pause(step, 50), step);
+        assert_eq!(compaction_pause(step, 25), step * 3);
+        assert_eq!(compaction_pause(step, 10), step * 9);
     }
 }
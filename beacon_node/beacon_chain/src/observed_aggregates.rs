@@ -300,6 +300,14 @@ impl<I> SlotHashSet<I> {
     pub fn len(&self) -> usize {
         self.map.len()
     }
+
+    /// Estimates the memory allocated by `self`, in bytes.
+    pub fn estimated_bytes(&self) -> usize {
+        self.map
+            .values()
+            .map(|items| std::mem::size_of::<Hash256>() + std::mem::size_of_val(items.as_slice()))
+            .sum()
+    }
 }
 
 /// Trait for observable items that can be observed from their reference type.
@@ -390,6 +398,16 @@ where
             .and_then(|set| set.is_known_subset(item, root))
     }
 
+    /// The number of observed items across all slots in `self`.
+    pub fn num_observed(&self) -> usize {
+        self.sets.iter().map(SlotHashSet::len).sum()
+    }
+
+    /// Estimates the memory allocated by the items in `self`, in bytes.
+    pub fn estimated_bytes(&self) -> usize {
+        self.sets.iter().map(SlotHashSet::estimated_bytes).sum()
+    }
+
     /// The maximum number of slots that items are stored for.
     fn max_capacity(&self) -> u64 {
         // We add `2` in order to account for one slot either side of the range due to
@@ -73,6 +73,9 @@ pub trait Item<T> {
     /// Returns the number of validators that have been observed by `self`.
     fn validator_count(&self) -> usize;
 
+    /// Estimates the memory allocated by `self`, in bytes.
+    fn estimated_bytes(&self) -> usize;
+
     /// Store `validator_index` and `value` in `self`.
     fn insert(&mut self, validator_index: usize, value: T) -> bool;
 
@@ -106,6 +109,10 @@ impl Item<()> for EpochBitfield {
         self.bitfield.iter().filter(|bit| **bit).count()
     }
 
+    fn estimated_bytes(&self) -> usize {
+        self.bitfield.capacity() / 8
+    }
+
     fn insert(&mut self, validator_index: usize, _value: ()) -> bool {
         self.bitfield
             .get_mut(validator_index)
@@ -162,6 +169,10 @@ impl Item<()> for EpochHashSet {
         self.set.len()
     }
 
+    fn estimated_bytes(&self) -> usize {
+        self.set.capacity() * std::mem::size_of::<usize>()
+    }
+
     /// Inserts the `validator_index` in the set. Returns `true` if the `validator_index` was
     /// already in the set.
     fn insert(&mut self, validator_index: usize, _value: ()) -> bool {
@@ -202,6 +213,10 @@ impl<E: EthSpec> Item<Hash256> for SyncContributorSlotHashSet<E> {
         self.map.len()
     }
 
+    fn estimated_bytes(&self) -> usize {
+        self.map.capacity() * std::mem::size_of::<(usize, Hash256)>()
+    }
+
     /// Inserts the `validator_index` in the set. Returns `true` if the `validator_index` was
     /// already in the set.
     fn insert(&mut self, validator_index: usize, beacon_block_root: Hash256) -> bool {
@@ -242,6 +257,10 @@ impl Item<()> for SyncAggregatorSlotHashSet {
         self.set.len()
     }
 
+    fn estimated_bytes(&self) -> usize {
+        self.set.capacity() * std::mem::size_of::<usize>()
+    }
+
     /// Inserts the `validator_index` in the set. Returns `true` if the `validator_index` was
     /// already in the set.
     fn insert(&mut self, validator_index: usize, _value: ()) -> bool {
@@ -347,6 +366,16 @@ impl<T: Item<()>, E: EthSpec> AutoPruningEpochContainer<T, E> {
         self.items.get(&epoch).map(|item| item.validator_count())
     }
 
+    /// Returns the number of validators that have been observed across all epochs in `self`.
+    pub fn total_validator_count(&self) -> usize {
+        self.items.values().map(|item| item.validator_count()).sum()
+    }
+
+    /// Estimates the memory allocated by the items in `self`, in bytes.
+    pub fn estimated_bytes(&self) -> usize {
+        self.items.values().map(|item| item.estimated_bytes()).sum()
+    }
+
     fn sanitize_request(&self, epoch: Epoch, validator_index: usize) -> Result<(), Error> {
         if validator_index > E::ValidatorRegistryLimit::to_usize() {
             return Err(Error::ValidatorIndexTooHigh(validator_index));
@@ -547,6 +576,16 @@ impl<K: SlotData + Eq + Hash + Copy, S, V: Item<S>, E: EthSpec>
         self.items.get(&key).map(|item| item.validator_count())
     }
 
+    /// Returns the number of validators that have been observed across all slots in `self`.
+    pub fn total_validator_count(&self) -> usize {
+        self.items.values().map(|item| item.validator_count()).sum()
+    }
+
+    /// Estimates the memory allocated by the items in `self`, in bytes.
+    pub fn estimated_bytes(&self) -> usize {
+        self.items.values().map(|item| item.estimated_bytes()).sum()
+    }
+
     fn sanitize_request(&self, slot: Slot, validator_index: usize) -> Result<(), Error> {
         if validator_index > E::ValidatorRegistryLimit::to_usize() {
             return Err(Error::ValidatorIndexTooHigh(validator_index));
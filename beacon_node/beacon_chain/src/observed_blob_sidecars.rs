@@ -104,6 +104,21 @@ impl<E: EthSpec> ObservedBlobSidecars<E> {
         self.finalized_slot = finalized_slot;
         self.items.retain(|k, _| k.slot > finalized_slot);
     }
+
+    /// Returns the number of distinct blob sidecars observed.
+    pub fn num_observed(&self) -> usize {
+        self.items.values().map(HashSet::len).sum()
+    }
+
+    /// Estimates the memory allocated by `self`, in bytes.
+    pub fn estimated_bytes(&self) -> usize {
+        self.items
+            .values()
+            .map(|indices| {
+                std::mem::size_of::<ProposalKey>() + indices.capacity() * std::mem::size_of::<u64>()
+            })
+            .sum()
+    }
 }
 
 #[cfg(test)]
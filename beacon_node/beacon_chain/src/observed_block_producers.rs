@@ -192,6 +192,22 @@ impl<E: EthSpec> ObservedBlockProducers<E> {
         self.items.retain(|key, _| key.slot > finalized_slot);
     }
 
+    /// Returns the number of distinct blocks observed.
+    pub fn num_observed(&self) -> usize {
+        self.items.values().map(HashSet::len).sum()
+    }
+
+    /// Estimates the memory allocated by `self`, in bytes.
+    pub fn estimated_bytes(&self) -> usize {
+        self.items
+            .values()
+            .map(|block_roots| {
+                std::mem::size_of::<ProposalKey>()
+                    + block_roots.capacity() * std::mem::size_of::<Hash256>()
+            })
+            .sum()
+    }
+
     /// Returns `true` if the given `validator_index` has been stored in `self` at `epoch`.
     ///
     /// This is useful for doppelganger detection.
@@ -0,0 +1,200 @@
+//! Provides the `ObservedEquivocations` struct which records evidence of block proposers signing
+//! two different blocks for the same slot.
+//!
+//! Unlike the slasher, this only detects equivocations between blocks (or blob sidecars) which
+//! this node has seen, but it is always enabled.
+
+use crate::observed_block_producers::{Error, ProposalKey};
+use std::collections::{BTreeMap, HashMap};
+use std::marker::PhantomData;
+use types::{EthSpec, ProposerSlashing, SignedBeaconBlockHeader, Slot, Unsigned};
+
+/// The maximum number of equivocations to retain, dropping those with the lowest slots first.
+///
+/// Equivocations are retained after finalization so that they remain available to the HTTP API.
+pub const MAX_EQUIVOCATIONS: usize = 1_024;
+
+/// Maintains the first signed header seen for each `(slot, proposer)` since finalization, along
+/// with a `ProposerSlashing` for each proposal which has been equivocated.
+///
+/// The supplied headers **MUST** have a valid signature, otherwise an invalid header could be
+/// stored in place of a valid one and then reported as an equivocation.
+pub struct ObservedEquivocations<E: EthSpec> {
+    finalized_slot: Slot,
+    headers: HashMap<ProposalKey, SignedBeaconBlockHeader>,
+    equivocations: BTreeMap<(Slot, u64), ProposerSlashing>,
+    _phantom: PhantomData<E>,
+}
+
+impl<E: EthSpec> Default for ObservedEquivocations<E> {
+    /// Instantiates `Self` with `finalized_slot == 0`.
+    fn default() -> Self {
+        Self {
+            finalized_slot: Slot::new(0),
+            headers: HashMap::new(),
+            equivocations: BTreeMap::new(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<E: EthSpec> ObservedEquivocations<E> {
+    /// Observe a `header` with a valid signature.
+    ///
+    /// Returns a `ProposerSlashing` if `header` conflicts with a header previously observed for
+    /// the same slot and proposer, and no equivocation has been recorded for that proposal yet.
+    ///
+    /// ## Errors
+    ///
+    /// - `header.message.proposer_index` is greater than `VALIDATOR_REGISTRY_LIMIT`.
+    /// - `header.message.slot` is equal to or less than the latest pruned `finalized_slot`.
+    pub fn observe_header(
+        &mut self,
+        header: &SignedBeaconBlockHeader,
+    ) -> Result<Option<ProposerSlashing>, Error> {
+        let slot = header.message.slot;
+        let proposer = header.message.proposer_index;
+        self.sanitize_header(slot, proposer)?;
+
+        let first_header = self
+            .headers
+            .entry(ProposalKey::new(proposer, slot))
+            .or_insert_with(|| header.clone());
+        if first_header.message == header.message
+            || self.equivocations.contains_key(&(slot, proposer))
+        {
+            return Ok(None);
+        }
+
+        let proposer_slashing = ProposerSlashing {
+            signed_header_1: first_header.clone(),
+            signed_header_2: header.clone(),
+        };
+        self.equivocations
+            .insert((slot, proposer), proposer_slashing.clone());
+        while self.equivocations.len() > MAX_EQUIVOCATIONS {
+            self.equivocations.pop_first();
+        }
+
+        Ok(Some(proposer_slashing))
+    }
+
+    /// Returns the recorded equivocations, ordered by slot and then proposer index.
+    pub fn equivocations(&self) -> impl Iterator<Item = &ProposerSlashing> {
+        self.equivocations.values()
+    }
+
+    /// Returns `Ok(())` if the given `slot` and `proposer_index` are sane.
+    fn sanitize_header(&self, slot: Slot, proposer_index: u64) -> Result<(), Error> {
+        if proposer_index >= E::ValidatorRegistryLimit::to_u64() {
+            return Err(Error::ValidatorIndexTooHigh(proposer_index));
+        }
+
+        let finalized_slot = self.finalized_slot;
+        if finalized_slot > 0 && slot <= finalized_slot {
+            return Err(Error::FinalizedBlock {
+                slot,
+                finalized_slot,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Removes all headers with slots equal to or earlier than `finalized_slot`.
+    ///
+    /// Equivocations are not removed, since they remain slashable after finalization.
+    ///
+    /// No-op if `finalized_slot == 0`.
+    pub fn prune(&mut self, finalized_slot: Slot) {
+        if finalized_slot == 0 {
+            return;
+        }
+
+        self.finalized_slot = finalized_slot;
+        self.headers.retain(|key, _| key.slot > finalized_slot);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::{BeaconBlockHeader, Hash256, MainnetEthSpec, Signature};
+
+    type E = MainnetEthSpec;
+
+    fn get_header(slot: u64, proposer: u64, body: u64) -> SignedBeaconBlockHeader {
+        SignedBeaconBlockHeader {
+            message: BeaconBlockHeader {
+                slot: Slot::new(slot),
+                proposer_index: proposer,
+                parent_root: Hash256::zero(),
+                state_root: Hash256::zero(),
+                body_root: Hash256::from_low_u64_be(body),
+            },
+            signature: Signature::empty(),
+        }
+    }
+
+    #[test]
+    fn detects_equivocation_once() {
+        let mut cache = ObservedEquivocations::<E>::default();
+        let header_a = get_header(1, 0, 1);
+        let header_b = get_header(1, 0, 2);
+        let header_c = get_header(1, 0, 3);
+
+        assert_eq!(cache.observe_header(&header_a), Ok(None));
+        assert_eq!(
+            cache.observe_header(&header_a),
+            Ok(None),
+            "the same header is not an equivocation"
+        );
+        assert_eq!(
+            cache.observe_header(&get_header(1, 1, 2)),
+            Ok(None),
+            "a different proposer is not an equivocation"
+        );
+        assert_eq!(
+            cache.observe_header(&get_header(2, 0, 2)),
+            Ok(None),
+            "a different slot is not an equivocation"
+        );
+
+        let slashing = cache
+            .observe_header(&header_b)
+            .unwrap()
+            .expect("should detect equivocation");
+        assert_eq!(slashing.signed_header_1, header_a);
+        assert_eq!(slashing.signed_header_2, header_b);
+
+        assert_eq!(
+            cache.observe_header(&header_c),
+            Ok(None),
+            "an equivocation is only reported once"
+        );
+        assert_eq!(cache.equivocations().collect::<Vec<_>>(), vec![&slashing]);
+    }
+
+    #[test]
+    fn pruning_keeps_equivocations() {
+        let mut cache = ObservedEquivocations::<E>::default();
+        cache.observe_header(&get_header(1, 0, 1)).unwrap();
+        cache
+            .observe_header(&get_header(1, 0, 2))
+            .unwrap()
+            .expect("should detect equivocation");
+        cache.observe_header(&get_header(3, 0, 1)).unwrap();
+
+        cache.prune(Slot::new(2));
+        assert_eq!(cache.headers.len(), 1, "only slot 3 should remain");
+        assert_eq!(cache.equivocations().count(), 1);
+        assert_eq!(
+            cache.observe_header(&get_header(2, 0, 1)),
+            Err(Error::FinalizedBlock {
+                slot: Slot::new(2),
+                finalized_slot: Slot::new(2),
+            }),
+            "cannot observe a finalized header"
+        );
+    }
+}
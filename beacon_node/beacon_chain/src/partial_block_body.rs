@@ -0,0 +1,424 @@
+//! Operations submitted by a trusted local process (e.g. for research or recovery) for inclusion in
+//! the next block proposed by this node.
+//!
+//! Submitted operations are verified against the wall clock state when they are submitted, and
+//! again against the state of the block when it is produced. They are included ahead of the
+//! operations from the op pool, which fill any remaining space.
+//!
+//! The submitted operations are kept until a block produced with them is imported, so that they
+//! are not lost if that block is never published or fails to import.
+use crate::{BeaconChain, BeaconChainError, BeaconChainTypes};
+use eth2::lighthouse::PartialBlockBody;
+use slog::{debug, warn, Logger};
+use state_processing::{
+    per_block_processing::{
+        get_slashable_indices, verify_attestation_for_block_inclusion,
+        verify_attestation_for_state, VerifySignatures,
+    },
+    ConsensusContext, VerifyOperation,
+};
+use std::collections::HashSet;
+use std::fmt::Debug;
+use std::hash::Hash;
+use tree_hash::TreeHash;
+use types::{
+    AbstractExecPayload, Attestation, AttesterSlashing, BeaconBlock, BeaconState, EthSpec,
+    ForkName, Hash256, ProposerSlashing, SignedBlsToExecutionChange, SignedVoluntaryExit, Slot,
+    Unsigned,
+};
+
+#[derive(Debug)]
+pub enum Error {
+    BeaconChain(BeaconChainError),
+    /// More operations of `kind` were submitted than fit in a block.
+    TooManyOperations {
+        kind: &'static str,
+        count: usize,
+        max: usize,
+    },
+    /// The operation of `kind` at `index` is invalid.
+    InvalidOperation {
+        kind: &'static str,
+        index: usize,
+        reason: String,
+    },
+}
+
+/// A submitted body, along with the blocks which have been produced with it.
+pub(crate) struct PendingPartialBlockBody<E: EthSpec> {
+    body: PartialBlockBody<E>,
+    /// The slots of the blocks being produced with the body.
+    production_slots: HashSet<Slot>,
+    /// The roots of the blocks produced with the body.
+    block_roots: HashSet<Hash256>,
+}
+
+impl<E: EthSpec> PendingPartialBlockBody<E> {
+    fn new(body: PartialBlockBody<E>) -> Self {
+        Self {
+            body,
+            production_slots: HashSet::new(),
+            block_roots: HashSet::new(),
+        }
+    }
+}
+
+/// Operations selected for inclusion in a block.
+pub(crate) struct BlockOperations<E: EthSpec> {
+    pub proposer_slashings: Vec<ProposerSlashing>,
+    pub attester_slashings: Vec<AttesterSlashing<E>>,
+    pub attestations: Vec<Attestation<E>>,
+    pub voluntary_exits: Vec<SignedVoluntaryExit>,
+    pub bls_to_execution_changes: Vec<SignedBlsToExecutionChange>,
+}
+
+/// The maximum number of each kind of operation in a block.
+struct OperationLimits {
+    proposer_slashings: usize,
+    attester_slashings: usize,
+    attestations: usize,
+    voluntary_exits: usize,
+    bls_to_execution_changes: usize,
+}
+
+impl OperationLimits {
+    fn at_fork<E: EthSpec>(fork_name: ForkName) -> Self {
+        let (attester_slashings, attestations) = if fork_name.electra_enabled() {
+            (
+                E::max_attester_slashings_electra(),
+                E::max_attestations_electra(),
+            )
+        } else {
+            (
+                E::MaxAttesterSlashings::to_usize(),
+                E::MaxAttestations::to_usize(),
+            )
+        };
+        let bls_to_execution_changes = if fork_name.capella_enabled() {
+            E::MaxBlsToExecutionChanges::to_usize()
+        } else {
+            0
+        };
+        Self {
+            proposer_slashings: E::MaxProposerSlashings::to_usize(),
+            attester_slashings,
+            attestations,
+            voluntary_exits: E::MaxVoluntaryExits::to_usize(),
+            bls_to_execution_changes,
+        }
+    }
+}
+
+fn check_limit(kind: &'static str, count: usize, max: usize) -> Result<(), Error> {
+    if count > max {
+        Err(Error::TooManyOperations { kind, count, max })
+    } else {
+        Ok(())
+    }
+}
+
+/// Place `submitted` ahead of `selected`, keeping the first `max` operations which satisfy
+/// `is_includable`.
+///
+/// `is_includable` is only called for operations up to the limit, so it may record the operations
+/// it accepts.
+fn prepend_operations<T>(
+    submitted: Vec<T>,
+    selected: &mut Vec<T>,
+    max: usize,
+    is_includable: impl FnMut(&T) -> bool,
+) {
+    *selected = submitted
+        .into_iter()
+        .chain(std::mem::take(selected))
+        .filter(is_includable)
+        .take(max)
+        .collect();
+}
+
+/// Keep the submitted `operations` which pass `validate`, logging those which do not.
+fn retain_valid<T, V, E: Debug>(
+    log: &Logger,
+    block_slot: Slot,
+    kind: &'static str,
+    operations: Vec<T>,
+    validate: impl Fn(&T) -> Result<V, E>,
+) -> Vec<T> {
+    operations
+        .into_iter()
+        .filter(|operation| match validate(operation) {
+            Ok(_) => true,
+            Err(e) => {
+                warn!(
+                    log,
+                    "Dropping invalid submitted operation";
+                    "error" => ?e,
+                    "kind" => kind,
+                    "block_slot" => block_slot,
+                );
+                false
+            }
+        })
+        .collect()
+}
+
+/// Returns a function which accepts each key once.
+fn unique_by<T, K: Eq + Hash>(key: impl Fn(&T) -> K) -> impl FnMut(&T) -> bool {
+    let mut seen = HashSet::new();
+    move |operation| seen.insert(key(operation))
+}
+
+impl<T: BeaconChainTypes> BeaconChain<T> {
+    /// Verify `partial_block_body` against the wall clock state and store it for inclusion in the
+    /// next block proposed by this node, replacing any body submitted previously.
+    ///
+    /// An empty body clears the submitted body.
+    pub fn submit_partial_block_body(
+        &self,
+        partial_block_body: PartialBlockBody<T::EthSpec>,
+    ) -> Result<(), Error> {
+        if partial_block_body.is_empty() {
+            *self.partial_block_body.lock() = None;
+            return Ok(());
+        }
+
+        let mut state = self.wall_clock_state().map_err(Error::BeaconChain)?;
+        state
+            .build_all_committee_caches(&self.spec)
+            .map_err(|e| Error::BeaconChain(e.into()))?;
+
+        let limits = OperationLimits::at_fork::<T::EthSpec>(state.fork_name_unchecked());
+        check_limit(
+            "proposer_slashing",
+            partial_block_body.proposer_slashings.len(),
+            limits.proposer_slashings,
+        )?;
+        check_limit(
+            "attester_slashing",
+            partial_block_body.attester_slashings.len(),
+            limits.attester_slashings,
+        )?;
+        check_limit(
+            "attestation",
+            partial_block_body.attestations.len(),
+            limits.attestations,
+        )?;
+        check_limit(
+            "voluntary_exit",
+            partial_block_body.voluntary_exits.len(),
+            limits.voluntary_exits,
+        )?;
+        check_limit(
+            "bls_to_execution_change",
+            partial_block_body.bls_to_execution_changes.len(),
+            limits.bls_to_execution_changes,
+        )?;
+
+        let invalid = |kind, index, reason| Error::InvalidOperation {
+            kind,
+            index,
+            reason,
+        };
+        for (index, slashing) in partial_block_body.proposer_slashings.iter().enumerate() {
+            VerifyOperation::<T::EthSpec>::validate(slashing.clone(), &state, &self.spec)
+                .map_err(|e| invalid("proposer_slashing", index, format!("{:?}", e)))?;
+        }
+        for (index, slashing) in partial_block_body.attester_slashings.iter().enumerate() {
+            slashing
+                .clone()
+                .validate(&state, &self.spec)
+                .map_err(|e| invalid("attester_slashing", index, format!("{:?}", e)))?;
+        }
+        for (index, attestation) in partial_block_body.attestations.iter().enumerate() {
+            // The attestation may be included in a block at a later slot, so only check that it
+            // is valid for the current chain, not that it is includable now.
+            let mut ctxt = ConsensusContext::new(state.slot());
+            verify_attestation_for_state(
+                &state,
+                attestation.to_ref(),
+                &mut ctxt,
+                VerifySignatures::True,
+                &self.spec,
+            )
+            .map_err(|e| invalid("attestation", index, format!("{:?}", e)))?;
+        }
+        for (index, exit) in partial_block_body.voluntary_exits.iter().enumerate() {
+            VerifyOperation::<T::EthSpec>::validate(exit.clone(), &state, &self.spec)
+                .map_err(|e| invalid("voluntary_exit", index, format!("{:?}", e)))?;
+        }
+        for (index, change) in partial_block_body
+            .bls_to_execution_changes
+            .iter()
+            .enumerate()
+        {
+            VerifyOperation::<T::EthSpec>::validate(change.clone(), &state, &self.spec)
+                .map_err(|e| invalid("bls_to_execution_change", index, format!("{:?}", e)))?;
+        }
+
+        *self.partial_block_body.lock() = Some(PendingPartialBlockBody::new(partial_block_body));
+        Ok(())
+    }
+
+    /// Returns the body awaiting inclusion in an imported block proposed by this node, if any.
+    pub fn pending_partial_block_body(&self) -> Option<PartialBlockBody<T::EthSpec>> {
+        self.partial_block_body
+            .lock()
+            .as_ref()
+            .map(|pending| pending.body.clone())
+    }
+
+    /// Merge the submitted body (if any) into the `operations` selected from the op pool for a
+    /// block applied to `state`.
+    ///
+    /// Submitted operations which are no longer valid are dropped from the block, as are
+    /// operations from the op pool which conflict with the submitted operations. The submitted
+    /// body is kept until the block is imported, see `partial_block_body_imported`.
+    pub(crate) fn merge_partial_block_body(
+        &self,
+        state: &BeaconState<T::EthSpec>,
+        operations: &mut BlockOperations<T::EthSpec>,
+    ) {
+        let Some(partial_block_body) = self.partial_block_body.lock().as_mut().map(|pending| {
+            pending.production_slots.insert(state.slot());
+            pending.body.clone()
+        }) else {
+            return;
+        };
+        let PartialBlockBody {
+            proposer_slashings,
+            attester_slashings,
+            attestations,
+            voluntary_exits,
+            bls_to_execution_changes,
+        } = partial_block_body;
+
+        let log = &self.log;
+        let slot = state.slot();
+        let proposer_slashings = retain_valid(
+            log,
+            slot,
+            "proposer_slashing",
+            proposer_slashings,
+            |slashing| VerifyOperation::<T::EthSpec>::validate(slashing.clone(), state, &self.spec),
+        );
+        let attester_slashings = retain_valid(
+            log,
+            slot,
+            "attester_slashing",
+            attester_slashings,
+            |slashing| slashing.clone().validate(state, &self.spec),
+        );
+        let attestations = retain_valid(log, slot, "attestation", attestations, |attestation| {
+            let mut ctxt = ConsensusContext::new(slot);
+            verify_attestation_for_block_inclusion(
+                state,
+                attestation.to_ref(),
+                &mut ctxt,
+                VerifySignatures::True,
+                &self.spec,
+            )
+            .map(drop)
+        });
+        let voluntary_exits = retain_valid(log, slot, "voluntary_exit", voluntary_exits, |exit| {
+            VerifyOperation::<T::EthSpec>::validate(exit.clone(), state, &self.spec)
+        });
+        let bls_to_execution_changes = retain_valid(
+            log,
+            slot,
+            "bls_to_execution_change",
+            bls_to_execution_changes,
+            |change| VerifyOperation::<T::EthSpec>::validate(change.clone(), state, &self.spec),
+        );
+
+        let limits = OperationLimits::at_fork::<T::EthSpec>(state.fork_name_unchecked());
+
+        // Track the validators slashed by the block, so that no slashing or exit is included for a
+        // validator which has already been slashed by an earlier operation.
+        let mut to_be_slashed = HashSet::new();
+        prepend_operations(
+            proposer_slashings,
+            &mut operations.proposer_slashings,
+            limits.proposer_slashings,
+            |slashing| to_be_slashed.insert(slashing.proposer_index()),
+        );
+        prepend_operations(
+            attester_slashings,
+            &mut operations.attester_slashings,
+            limits.attester_slashings,
+            |slashing| {
+                let newly_slashed = get_slashable_indices(state, slashing.to_ref())
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter(|index| !to_be_slashed.contains(index))
+                    .collect::<Vec<_>>();
+                to_be_slashed.extend(&newly_slashed);
+                !newly_slashed.is_empty()
+            },
+        );
+        prepend_operations(
+            attestations,
+            &mut operations.attestations,
+            limits.attestations,
+            unique_by(|attestation: &Attestation<T::EthSpec>| attestation.tree_hash_root()),
+        );
+        let mut is_unique_exit =
+            unique_by(|exit: &SignedVoluntaryExit| exit.message.validator_index);
+        prepend_operations(
+            voluntary_exits,
+            &mut operations.voluntary_exits,
+            limits.voluntary_exits,
+            |exit| !to_be_slashed.contains(&exit.message.validator_index) && is_unique_exit(exit),
+        );
+        prepend_operations(
+            bls_to_execution_changes,
+            &mut operations.bls_to_execution_changes,
+            limits.bls_to_execution_changes,
+            unique_by(|change: &SignedBlsToExecutionChange| change.message.validator_index),
+        );
+    }
+
+    /// Record the root of `block`, if it was produced with the submitted body.
+    pub(crate) fn partial_block_body_produced<Payload: AbstractExecPayload<T::EthSpec>>(
+        &self,
+        block: &BeaconBlock<T::EthSpec, Payload>,
+    ) {
+        let is_production_slot = |pending: &PendingPartialBlockBody<T::EthSpec>| {
+            pending.production_slots.contains(&block.slot())
+        };
+        if !self
+            .partial_block_body
+            .lock()
+            .as_ref()
+            .map_or(false, is_production_slot)
+        {
+            return;
+        }
+
+        // The root of a blinded block is the same as the root of the full block.
+        let block_root = block.tree_hash_root();
+        if let Some(pending) = self
+            .partial_block_body
+            .lock()
+            .as_mut()
+            .filter(|pending| is_production_slot(pending))
+        {
+            pending.block_roots.insert(block_root);
+        }
+    }
+
+    /// Clear the submitted body once a block produced with it has been imported.
+    pub(crate) fn partial_block_body_imported(&self, block_root: Hash256) {
+        let mut partial_block_body = self.partial_block_body.lock();
+        if partial_block_body
+            .as_ref()
+            .map_or(false, |pending| pending.block_roots.contains(&block_root))
+        {
+            *partial_block_body = None;
+            debug!(
+                self.log,
+                "Submitted partial block body included";
+                "block_root" => ?block_root,
+            );
+        }
+    }
+}
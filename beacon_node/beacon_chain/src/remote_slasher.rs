@@ -0,0 +1,144 @@
+//! Provides the `RemoteSlasherQueue`, which buffers verified attestations and block headers until
+//! they are taken by a slasher running in another process.
+//!
+//! This allows the memory and disk hungry slasher to run on separate hardware. The remote slasher
+//! periodically takes the queued batch via the HTTP API and publishes any slashings it finds
+//! through the operation pool endpoints.
+//!
+//! Each batch is kept until the remote slasher acknowledges it by its `batch_id` when taking the
+//! next batch. Until then, the same batch is handed out again, so that a batch is not lost if the
+//! response is never received or the remote slasher stops before processing it.
+use crate::metrics;
+use eth2::lighthouse::SlasherBatch;
+use parking_lot::Mutex;
+use slasher::{AttestationQueue, BlockQueue};
+use types::{EthSpec, IndexedAttestation, SignedBeaconBlockHeader};
+
+/// The maximum number of attestations to hold for the remote slasher, excluding those in the
+/// unacknowledged batch.
+///
+/// If the remote slasher stops taking batches then further attestations are dropped, rather than
+/// being allowed to consume an unbounded amount of memory.
+pub const MAX_QUEUED_ATTESTATIONS: usize = 131_072;
+
+#[derive(Debug)]
+pub struct RemoteSlasherQueue<E: EthSpec> {
+    attestation_queue: AttestationQueue<E>,
+    block_queue: BlockQueue,
+    in_flight: Mutex<InFlight<E>>,
+}
+
+/// The batch handed out to the remote slasher which is yet to be acknowledged.
+#[derive(Debug)]
+struct InFlight<E: EthSpec> {
+    /// The ID of the next batch. This starts at a random value, so that acknowledgements sent
+    /// before the beacon node restarted do not acknowledge a new batch.
+    next_batch_id: u64,
+    batch: Option<SlasherBatch<E>>,
+}
+
+impl<E: EthSpec> Default for RemoteSlasherQueue<E> {
+    fn default() -> Self {
+        Self {
+            attestation_queue: AttestationQueue::default(),
+            block_queue: BlockQueue::default(),
+            in_flight: Mutex::new(InFlight {
+                next_batch_id: rand::random(),
+                batch: None,
+            }),
+        }
+    }
+}
+
+impl<E: EthSpec> RemoteSlasherQueue<E> {
+    /// Queue a verified attestation for the remote slasher.
+    pub fn accept_attestation(&self, attestation: IndexedAttestation<E>) {
+        let queued = self.attestation_queue.len();
+        if queued >= MAX_QUEUED_ATTESTATIONS {
+            metrics::inc_counter(&metrics::REMOTE_SLASHER_DROPPED_ATTESTATIONS);
+            return;
+        }
+        self.attestation_queue.queue(attestation);
+        metrics::set_gauge(
+            &metrics::REMOTE_SLASHER_QUEUED_ATTESTATIONS,
+            queued as i64 + 1,
+        );
+    }
+
+    /// Queue a verified block header for the remote slasher.
+    pub fn accept_block_header(&self, block_header: SignedBeaconBlockHeader) {
+        self.block_queue.queue(block_header);
+    }
+
+    /// Return the batch for the remote slasher.
+    ///
+    /// If `ack` is the `batch_id` of the unacknowledged batch, that batch is discarded and a new
+    /// batch is taken from the queue. Otherwise the unacknowledged batch is returned again.
+    pub fn dequeue(&self, ack: Option<u64>) -> SlasherBatch<E> {
+        let mut in_flight = self.in_flight.lock();
+        if in_flight
+            .batch
+            .as_ref()
+            .map_or(false, |batch| Some(batch.batch_id) == ack)
+        {
+            in_flight.batch = None;
+        }
+        if let Some(batch) = &in_flight.batch {
+            return batch.clone();
+        }
+
+        let mut batch = SlasherBatch {
+            batch_id: in_flight.next_batch_id,
+            ..SlasherBatch::default()
+        };
+        in_flight.next_batch_id = in_flight.next_batch_id.wrapping_add(1);
+        for record in self.attestation_queue.dequeue() {
+            batch.push_attestation(record.indexed.clone());
+        }
+        batch.block_headers = self.block_queue.dequeue().into_iter().collect();
+        metrics::set_gauge(&metrics::REMOTE_SLASHER_QUEUED_ATTESTATIONS, 0);
+
+        in_flight.batch = Some(batch.clone());
+        batch
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use slasher::test_utils::{indexed_att, E};
+
+    #[test]
+    fn dequeue_takes_everything() {
+        let queue = RemoteSlasherQueue::<E>::default();
+        queue.accept_attestation(indexed_att([0, 1], 0, 1, 0));
+        queue.accept_attestation(indexed_att([2], 0, 1, 0));
+
+        let batch = queue.dequeue(None);
+        assert_eq!(batch.attestations_base.len(), 2);
+        assert!(batch.attestations_electra.is_empty());
+        assert!(queue
+            .dequeue(Some(batch.batch_id))
+            .attestations_base
+            .is_empty());
+    }
+
+    #[test]
+    fn dequeue_repeats_batch_until_acknowledged() {
+        let queue = RemoteSlasherQueue::<E>::default();
+        queue.accept_attestation(indexed_att([0], 0, 1, 0));
+        let batch = queue.dequeue(None);
+
+        // Attestations queued after the batch was taken are held for the next batch.
+        queue.accept_attestation(indexed_att([1], 0, 1, 0));
+        assert_eq!(queue.dequeue(None), batch);
+        assert_eq!(queue.dequeue(Some(batch.batch_id.wrapping_sub(1))), batch);
+
+        let next_batch = queue.dequeue(Some(batch.batch_id));
+        assert_eq!(next_batch.batch_id, batch.batch_id.wrapping_add(1));
+        assert_eq!(
+            next_batch.into_attestations().collect::<Vec<_>>(),
+            vec![indexed_att([1], 0, 1, 0)]
+        );
+    }
+}
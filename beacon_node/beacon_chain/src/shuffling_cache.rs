@@ -128,6 +128,33 @@ impl ShufflingCache {
         self.cache.contains_key(key)
     }
 
+    /// The number of committee caches and promises in the cache.
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.cache_size
+    }
+
+    /// Estimate the memory used by the committee caches, which is dominated by the shuffling and
+    /// the position of each validator within it.
+    pub fn estimated_bytes(&self) -> usize {
+        self.cache
+            .values()
+            .map(|item| match item {
+                CacheItem::Committee(committee_cache) => {
+                    2 * std::mem::size_of_val(committee_cache.shuffling())
+                }
+                CacheItem::Promise(_) => 0,
+            })
+            .sum()
+    }
+
     pub fn insert_committee_cache<C: ToArcCommitteeCache>(
         &mut self,
         key: AttestationShufflingId,
@@ -202,6 +229,7 @@ impl ShufflingCache {
             return Err(BeaconChainError::MaxCommitteePromises(num_active_promises));
         }
 
+        metrics::inc_counter(&metrics::SHUFFLING_CACHE_PROMISES_CREATED);
         let (sender, receiver) = oneshot();
         self.insert_cache_item(key, CacheItem::Promise(receiver));
         Ok(sender)
@@ -4,6 +4,7 @@
 
 use crate::beacon_proposer_cache::{BeaconProposerCache, TYPICAL_SLOTS_PER_EPOCH};
 use crate::metrics;
+use eth2::lighthouse::SyncAggregationSummary;
 use itertools::Itertools;
 use parking_lot::{Mutex, RwLock};
 use serde::{Deserialize, Serialize};
@@ -14,7 +15,7 @@ use state_processing::common::get_attestation_participation_flag_indices;
 use state_processing::per_epoch_processing::{
     errors::EpochProcessingError, EpochProcessingSummary,
 };
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::io;
 use std::marker::PhantomData;
 use std::str::Utf8Error;
@@ -22,11 +23,12 @@ use std::sync::Arc;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use store::AbstractExecPayload;
 use types::consts::altair::{
-    TIMELY_HEAD_FLAG_INDEX, TIMELY_SOURCE_FLAG_INDEX, TIMELY_TARGET_FLAG_INDEX,
+    SYNC_COMMITTEE_SUBNET_COUNT, TIMELY_HEAD_FLAG_INDEX, TIMELY_SOURCE_FLAG_INDEX,
+    TIMELY_TARGET_FLAG_INDEX,
 };
 use types::{
-    Attestation, AttestationData, AttesterSlashingRef, BeaconBlockRef, BeaconState,
-    BeaconStateError, ChainSpec, Epoch, EthSpec, Hash256, IndexedAttestation,
+    Address, Attestation, AttestationData, AttesterSlashingRef, BeaconBlockRef, BeaconState,
+    BeaconStateError, BitVector, ChainSpec, Epoch, EthSpec, Hash256, IndexedAttestation,
     IndexedAttestationRef, ProposerSlashing, PublicKeyBytes, SignedAggregateAndProof,
     SignedContributionAndProof, Slot, SyncCommitteeMessage, VoluntaryExit,
 };
@@ -60,6 +62,8 @@ pub const MISSED_BLOCK_LOOKBACK_EPOCHS: u64 = 1;
 pub struct ValidatorMonitorConfig {
     pub auto_register: bool,
     pub validators: Vec<PublicKeyBytes>,
+    /// Monitor all validators which withdraw to one of these execution addresses.
+    pub withdrawal_addresses: Vec<Address>,
     pub individual_tracking_threshold: usize,
 }
 
@@ -68,6 +72,7 @@ impl Default for ValidatorMonitorConfig {
         Self {
             auto_register: false,
             validators: vec![],
+            withdrawal_addresses: vec![],
             individual_tracking_threshold: DEFAULT_INDIVIDUAL_TRACKING_THRESHOLD,
         }
     }
@@ -82,6 +87,10 @@ pub const UNAGGREGATED_ATTESTATION_LAG_SLOTS: usize = 16;
 /// from the current and previous epoch.
 pub const MAX_UNAGGREGATED_ATTESTATION_HASHMAP_LENGTH: usize = 64;
 
+/// Sync committee contributions published by monitored validators are kept for this many slots
+/// whilst waiting for the block which should include them.
+pub const SYNC_CONTRIBUTION_INCLUSION_LAG_SLOTS: u64 = 32;
+
 #[derive(Debug)]
 pub enum Error {
     InvalidPubkey(String),
@@ -368,6 +377,13 @@ impl MonitoredValidator {
     }
 }
 
+/// The union of the sync committee contributions published by monitored validators for a single
+/// slot and subnet.
+struct PublishedSyncContribution<E: EthSpec> {
+    beacon_block_root: Hash256,
+    aggregation_bits: BitVector<E::SyncSubcommitteeSize>,
+}
+
 #[derive(PartialEq, Hash, Eq)]
 struct MissedBlock {
     slot: Slot,
@@ -390,6 +406,11 @@ pub struct ValidatorMonitor<E: EthSpec> {
     indices: HashMap<u64, PublicKeyBytes>,
     /// If true, allow the automatic registration of validators.
     auto_register: bool,
+    /// Validators which withdraw to any of these execution addresses are registered automatically.
+    withdrawal_addresses: HashSet<Address>,
+    /// The epoch at which the withdrawal credentials of all validators were last checked against
+    /// `withdrawal_addresses`.
+    withdrawal_addresses_checked_epoch: Option<Epoch>,
     /// Once the number of monitored validators goes above this threshold, we
     /// will stop tracking metrics/logs on a per-validator basis. This prevents
     /// large validator counts causing infeasibly high cardinailty for
@@ -401,6 +422,12 @@ pub struct ValidatorMonitor<E: EthSpec> {
     beacon_proposer_cache: Arc<Mutex<BeaconProposerCache>>,
     // Unaggregated attestations generated by the committee index at each slot.
     unaggregated_attestations: HashMap<Slot, Attestation<E>>,
+    /// Sync committee contributions published by monitored validators, keyed by slot and subnet,
+    /// which are yet to be compared against the sync aggregate of a block.
+    published_sync_contributions: Mutex<HashMap<(Slot, u64), PublishedSyncContribution<E>>>,
+    /// Per-epoch and per-subnet summaries of the sync committee contributions published by
+    /// monitored validators.
+    sync_aggregation_summaries: Mutex<BTreeMap<Epoch, BTreeMap<u64, SyncAggregationSummary>>>,
     log: Logger,
     _phantom: PhantomData<E>,
 }
@@ -414,6 +441,7 @@ impl<E: EthSpec> ValidatorMonitor<E> {
         let ValidatorMonitorConfig {
             auto_register,
             validators,
+            withdrawal_addresses,
             individual_tracking_threshold,
         } = config;
 
@@ -421,10 +449,14 @@ impl<E: EthSpec> ValidatorMonitor<E> {
             validators: <_>::default(),
             indices: <_>::default(),
             auto_register,
+            withdrawal_addresses: withdrawal_addresses.into_iter().collect(),
+            withdrawal_addresses_checked_epoch: None,
             individual_tracking_threshold,
             missed_blocks: <_>::default(),
             beacon_proposer_cache,
             unaggregated_attestations: <_>::default(),
+            published_sync_contributions: <_>::default(),
+            sync_aggregation_summaries: <_>::default(),
             log,
             _phantom: PhantomData,
         };
@@ -487,6 +519,7 @@ impl<E: EthSpec> ValidatorMonitor<E> {
         spec: &ChainSpec,
     ) {
         // Add any new validator indices.
+        let first_new_index = self.indices.len();
         state
             .validators()
             .iter()
@@ -499,6 +532,7 @@ impl<E: EthSpec> ValidatorMonitor<E> {
                 }
                 self.indices.insert(i, validator.pubkey);
             });
+        self.add_withdrawal_address_validators(first_new_index, current_epoch, state, spec);
 
         // Add missed non-finalized blocks for the monitored validators
         self.add_validators_missed_blocks(state);
@@ -591,6 +625,54 @@ impl<E: EthSpec> ValidatorMonitor<E> {
         });
     }
 
+    /// Start monitoring the validators in `state` which withdraw to one of
+    /// `self.withdrawal_addresses`.
+    ///
+    /// Validators from `first_new_index` onwards are checked for every state, whilst all
+    /// validators are checked once per epoch to find those which have changed their withdrawal
+    /// credentials.
+    fn add_withdrawal_address_validators(
+        &mut self,
+        first_new_index: usize,
+        current_epoch: Epoch,
+        state: &BeaconState<E>,
+        spec: &ChainSpec,
+    ) {
+        if self.withdrawal_addresses.is_empty() {
+            return;
+        }
+
+        let first_index = if self.withdrawal_addresses_checked_epoch == Some(current_epoch) {
+            first_new_index
+        } else {
+            self.withdrawal_addresses_checked_epoch = Some(current_epoch);
+            0
+        };
+
+        for (i, validator) in state.validators().iter().enumerate().skip(first_index) {
+            let Some(address) = validator.get_execution_withdrawal_address(spec) else {
+                continue;
+            };
+            if !self.withdrawal_addresses.contains(&address)
+                || self.validators.contains_key(&validator.pubkey)
+            {
+                continue;
+            }
+
+            info!(
+                self.log,
+                "Started monitoring validator";
+                "pubkey" => %validator.pubkey,
+                "validator" => i,
+                "withdrawal_address" => ?address,
+            );
+            self.validators.insert(
+                validator.pubkey,
+                MonitoredValidator::new(validator.pubkey, Some(i as u64)),
+            );
+        }
+    }
+
     /// Add missed non-finalized blocks for the monitored validators
     fn add_validators_missed_blocks(&mut self, state: &BeaconState<E>) {
         // Define range variables
@@ -1676,6 +1758,8 @@ impl<E: EthSpec> ValidatorMonitor<E> {
             validator.with_epoch_summary(epoch, |summary| {
                 summary.register_sync_committee_contribution(delay)
             });
+
+            self.register_published_sync_contribution(sync_contribution);
         }
 
         for validator_pubkey in participant_pubkeys.iter() {
@@ -1709,6 +1793,181 @@ impl<E: EthSpec> ValidatorMonitor<E> {
         }
     }
 
+    /// Record a sync committee contribution from a monitored aggregator, so that its signatures
+    /// can be compared against those included in the next block.
+    fn register_published_sync_contribution(
+        &self,
+        sync_contribution: &SignedContributionAndProof<E>,
+    ) {
+        let contribution = &sync_contribution.message.contribution;
+        let slot = contribution.slot;
+        let subnet_id = contribution.subcommittee_index;
+
+        let mut published = self.published_sync_contributions.lock();
+        published.retain(|(published_slot, _), _| {
+            *published_slot + SYNC_CONTRIBUTION_INCLUSION_LAG_SLOTS >= slot
+        });
+
+        // Several monitored validators may aggregate the same subnet, in which case only the
+        // signatures that are new to this contribution are counted.
+        let new_participants = match published.get_mut(&(slot, subnet_id)) {
+            Some(existing) if existing.beacon_block_root == contribution.beacon_block_root => {
+                let union = existing
+                    .aggregation_bits
+                    .union(&contribution.aggregation_bits);
+                let new_participants =
+                    union.num_set_bits() - existing.aggregation_bits.num_set_bits();
+                existing.aggregation_bits = union;
+                new_participants
+            }
+            // A contribution for a different head cannot be included alongside the first.
+            Some(_) => 0,
+            None => {
+                published.insert(
+                    (slot, subnet_id),
+                    PublishedSyncContribution {
+                        beacon_block_root: contribution.beacon_block_root,
+                        aggregation_bits: contribution.aggregation_bits.clone(),
+                    },
+                );
+                contribution.aggregation_bits.num_set_bits()
+            }
+        };
+        drop(published);
+
+        let subnet_label = subnet_id.to_string();
+        metrics::inc_counter_vec(
+            &metrics::VALIDATOR_MONITOR_SYNC_AGGREGATION_CONTRIBUTIONS_TOTAL,
+            &[&subnet_label],
+        );
+        metrics::inc_counter_vec_by(
+            &metrics::VALIDATOR_MONITOR_SYNC_AGGREGATION_CONTRIBUTION_PARTICIPANTS_TOTAL,
+            &[&subnet_label],
+            new_participants as u64,
+        );
+
+        self.with_sync_aggregation_summary(
+            slot.epoch(E::slots_per_epoch()),
+            subnet_id,
+            |summary| {
+                summary.contributions += 1;
+                summary.contribution_participants += new_participants as u64;
+            },
+        );
+    }
+
+    /// Compare the sync aggregate included in the block at `block_slot` against the sync committee
+    /// contributions published by monitored validators for the previous slot.
+    pub fn register_sync_aggregate_subnets(
+        &self,
+        block_slot: Slot,
+        parent_root: Hash256,
+        sync_committee_bits: &BitVector<E::SyncCommitteeSize>,
+    ) {
+        let Some(slot) = block_slot.as_u64().checked_sub(1).map(Slot::new) else {
+            return;
+        };
+        let subcommittee_size = E::sync_subcommittee_size();
+
+        for subnet_id in 0..SYNC_COMMITTEE_SUBNET_COUNT {
+            let Some(contribution) = self
+                .published_sync_contributions
+                .lock()
+                .remove(&(slot, subnet_id))
+            else {
+                continue;
+            };
+
+            // The sync aggregate signs the block root at `slot`, which is the parent of the block.
+            let head_matches = contribution.beacon_block_root == parent_root;
+            let offset = subnet_id as usize * subcommittee_size;
+            let mut block_participants = 0;
+            let mut block_inclusions = 0;
+            for i in 0..subcommittee_size {
+                if sync_committee_bits.get(offset + i).unwrap_or(false) {
+                    block_participants += 1;
+                    if head_matches && contribution.aggregation_bits.get(i).unwrap_or(false) {
+                        block_inclusions += 1;
+                    }
+                }
+            }
+
+            let subnet_label = subnet_id.to_string();
+            metrics::inc_counter_vec_by(
+                &metrics::VALIDATOR_MONITOR_SYNC_AGGREGATION_BLOCK_PARTICIPANTS_TOTAL,
+                &[&subnet_label],
+                block_participants,
+            );
+            metrics::inc_counter_vec_by(
+                &metrics::VALIDATOR_MONITOR_SYNC_AGGREGATION_BLOCK_INCLUSIONS_TOTAL,
+                &[&subnet_label],
+                block_inclusions,
+            );
+            if block_participants > 0 {
+                metrics::set_float_gauge_vec(
+                    &metrics::VALIDATOR_MONITOR_SYNC_AGGREGATION_EFFICIENCY,
+                    &[&subnet_label],
+                    block_inclusions as f64 / block_participants as f64,
+                );
+            }
+
+            if block_inclusions < block_participants {
+                debug!(
+                    self.log,
+                    "Sync signatures missing from published contribution";
+                    "slot" => %slot,
+                    "subnet_id" => subnet_id,
+                    "block_participants" => block_participants,
+                    "block_inclusions" => block_inclusions,
+                    "head_matches" => head_matches,
+                );
+            }
+
+            self.with_sync_aggregation_summary(
+                slot.epoch(E::slots_per_epoch()),
+                subnet_id,
+                |summary| {
+                    summary.block_participants += block_participants;
+                    summary.block_inclusions += block_inclusions;
+                },
+            );
+        }
+    }
+
+    /// Maps `func` across the sync aggregation summary for `epoch` and `subnet_id`, creating it if
+    /// necessary and pruning summaries older than `HISTORIC_EPOCHS`.
+    fn with_sync_aggregation_summary<F>(&self, epoch: Epoch, subnet_id: u64, func: F)
+    where
+        F: FnOnce(&mut SyncAggregationSummary),
+    {
+        let mut summaries = self.sync_aggregation_summaries.lock();
+
+        func(
+            summaries
+                .entry(epoch)
+                .or_default()
+                .entry(subnet_id)
+                .or_insert_with(|| SyncAggregationSummary {
+                    epoch,
+                    subnet_id,
+                    ..<_>::default()
+                }),
+        );
+
+        while summaries.len() > HISTORIC_EPOCHS {
+            summaries.pop_first();
+        }
+    }
+
+    /// Returns the sync aggregation summaries for recent epochs, ordered by epoch and subnet.
+    pub fn get_sync_aggregation_summaries(&self) -> Vec<SyncAggregationSummary> {
+        self.sync_aggregation_summaries
+            .lock()
+            .values()
+            .flat_map(|subnets| subnets.values().cloned())
+            .collect()
+    }
+
     /// Register that the `sync_aggregate` was included in a *valid* `BeaconBlock`.
     pub fn register_sync_aggregate_in_block(
         &self,
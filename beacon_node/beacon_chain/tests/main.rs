@@ -6,6 +6,7 @@ mod capella;
 mod events;
 mod op_verification;
 mod payload_invalidation;
+mod reward_snapshots;
 mod rewards;
 mod store_tests;
 mod sync_committee_verification;
@@ -0,0 +1,429 @@
+#![cfg(not(debug_assertions))]
+
+//! Snapshot tests for the rewards APIs.
+//!
+//! Each directory in `tests/reward_snapshots` holds a segment of a recorded chain, which is
+//! replayed through block import before the block, sync committee and attestation rewards are
+//! compared byte-for-byte against the reference JSON in its `expected` directory. See the
+//! `README.md` in that directory for the fixture layout and how to record new fixtures.
+//!
+//! No fixtures have been committed yet, so `recorded_reward_snapshots` is ignored until they are.
+//! `record_local_reward_snapshot` records a case from a local chain using the minimal preset. When
+//! recording a case from another network, set `UPDATE_REWARD_SNAPSHOTS=1` to write the computed
+//! rewards to its `expected` directory rather than comparing against them.
+
+use beacon_chain::block_verification_types::RpcBlock;
+use beacon_chain::test_utils::{
+    generate_deterministic_keypairs, AttestationStrategy, BeaconChainHarness, BlockStrategy,
+    EphemeralHarnessType,
+};
+use beacon_chain::{BeaconChain, BeaconChainTypes, NotifyExecutionLayer, WhenSlotSkipped};
+use lazy_static::lazy_static;
+use serde::Serialize;
+use ssz::{Decode, Encode};
+use state_processing::BlockReplayer;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use types::{
+    BeaconState, BlobSidecarList, BlockImportSource, ChainSpec, Config, Epoch, EthSpec, Hash256,
+    Keypair, MainnetEthSpec, MinimalEthSpec, SignedBeaconBlock, Slot,
+};
+
+const UPDATE_ENV_VAR: &str = "UPDATE_REWARD_SNAPSHOTS";
+/// The case written by `record_local_reward_snapshot`.
+const LOCAL_CASE: &str = "minimal_altair_fork";
+const VALIDATOR_COUNT: usize = 32;
+
+lazy_static! {
+    static ref KEYPAIRS: Vec<Keypair> = generate_deterministic_keypairs(VALIDATOR_COUNT);
+}
+
+/// A segment of a chain, starting from an epoch-aligned anchor block and state.
+struct Fixture<E: EthSpec> {
+    spec: ChainSpec,
+    genesis_state: BeaconState<E>,
+    anchor_state: BeaconState<E>,
+    anchor_block: SignedBeaconBlock<E>,
+    /// The blocks following the anchor in slot order, with their blobs.
+    blocks: Vec<(SignedBeaconBlock<E>, Option<BlobSidecarList<E>>)>,
+}
+
+impl<E: EthSpec> Fixture<E> {
+    /// Load a fixture from `dir`, using the `config.yaml` in it to modify `base_spec` if present.
+    fn load(dir: &Path, base_spec: &ChainSpec) -> Self {
+        let config_path = dir.join("config.yaml");
+        let spec = if config_path.exists() {
+            Config::from_file(&config_path)
+                .unwrap()
+                .apply_to_chain_spec::<E>(base_spec)
+                .expect("config should match the preset")
+        } else {
+            base_spec.clone()
+        };
+
+        let read = |path: PathBuf| {
+            fs::read(&path).unwrap_or_else(|e| panic!("unable to read {}: {e:?}", path.display()))
+        };
+        let state = |name: &str| BeaconState::from_ssz_bytes(&read(dir.join(name)), &spec).unwrap();
+        let block = |path: PathBuf| SignedBeaconBlock::from_ssz_bytes(&read(path), &spec).unwrap();
+
+        let mut block_paths = fs::read_dir(dir.join("blocks"))
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect::<Vec<_>>();
+        block_paths.sort();
+        let blocks = block_paths
+            .into_iter()
+            .map(|path| {
+                let blobs_path = dir.join("blobs").join(path.file_name().unwrap());
+                let blobs = blobs_path
+                    .exists()
+                    .then(|| BlobSidecarList::from_ssz_bytes(&read(blobs_path)).unwrap());
+                (block(path), blobs)
+            })
+            .collect();
+
+        Self {
+            genesis_state: state("genesis_state.ssz"),
+            anchor_state: state("anchor_state.ssz"),
+            anchor_block: block(dir.join("anchor_block.ssz")),
+            blocks,
+            spec,
+        }
+    }
+
+    fn write(&self, dir: &Path) {
+        fs::create_dir_all(dir.join("blocks")).unwrap();
+        fs::write(
+            dir.join("config.yaml"),
+            serde_yaml::to_string(&Config::from_chain_spec::<E>(&self.spec)).unwrap(),
+        )
+        .unwrap();
+        fs::write(
+            dir.join("genesis_state.ssz"),
+            self.genesis_state.as_ssz_bytes(),
+        )
+        .unwrap();
+        fs::write(
+            dir.join("anchor_state.ssz"),
+            self.anchor_state.as_ssz_bytes(),
+        )
+        .unwrap();
+        fs::write(
+            dir.join("anchor_block.ssz"),
+            self.anchor_block.as_ssz_bytes(),
+        )
+        .unwrap();
+        for (block, blobs) in &self.blocks {
+            let file_name = format!("{:08}.ssz", block.slot().as_u64());
+            fs::write(dir.join("blocks").join(&file_name), block.as_ssz_bytes()).unwrap();
+            if let Some(blobs) = blobs {
+                fs::create_dir_all(dir.join("blobs")).unwrap();
+                fs::write(dir.join("blobs").join(&file_name), blobs.as_ssz_bytes()).unwrap();
+            }
+        }
+    }
+
+    /// Start a new chain from the anchor and import each of the blocks, returning the rewards
+    /// computed along the way.
+    async fn replay(&self) -> BTreeMap<String, String> {
+        let anchor_state = self.anchor_state.clone();
+        let anchor_block = self.anchor_block.clone();
+        let genesis_state = self.genesis_state.clone();
+        let harness = BeaconChainHarness::<EphemeralHarnessType<E>>::builder(E::default())
+            .spec(self.spec.clone())
+            .keypairs(vec![])
+            .fresh_ephemeral_store()
+            .override_store_mutator(Box::new(move |builder| {
+                builder
+                    .weak_subjectivity_state(anchor_state, anchor_block, None, genesis_state)
+                    .expect("should start from the anchor")
+            }))
+            .mock_execution_layer()
+            .mock_execution_layer_all_payloads_valid()
+            .build();
+        let chain = &harness.chain;
+
+        let mut snapshots = BTreeMap::new();
+        let mut next_epoch = self.anchor_state.current_epoch();
+        for (block, blobs) in &self.blocks {
+            let block_root = block.canonical_root();
+            harness.set_current_slot(block.slot());
+            chain
+                .process_block(
+                    block_root,
+                    RpcBlock::new(Some(block_root), Arc::new(block.clone()), blobs.clone())
+                        .unwrap(),
+                    NotifyExecutionLayer::Yes,
+                    BlockImportSource::Lookup,
+                    || Ok(()),
+                )
+                .await
+                .unwrap_or_else(|e| panic!("unable to import block at {}: {e:?}", block.slot()));
+            chain.recompute_head_at_current_slot().await;
+
+            // Compute the rewards whilst the states they require are still in the hot database.
+            record_block_rewards(chain, block_root, &mut snapshots);
+            while (next_epoch + 1).end_slot(E::slots_per_epoch()) <= block.slot() {
+                record_attestation_rewards(chain, next_epoch, &mut snapshots);
+                next_epoch += 1;
+            }
+        }
+        snapshots
+    }
+}
+
+/// Load the state prior to the application of the block at `block_root`, as per the rewards API.
+fn state_before_block<T: BeaconChainTypes>(
+    chain: &BeaconChain<T>,
+    block_root: Hash256,
+) -> BeaconState<T::EthSpec> {
+    let block = chain.get_blinded_block(&block_root).unwrap().unwrap();
+    let parent_block = chain
+        .get_blinded_block(&block.parent_root())
+        .unwrap()
+        .unwrap();
+    let parent_state = chain
+        .get_state(&parent_block.state_root(), Some(parent_block.slot()))
+        .unwrap()
+        .unwrap();
+
+    BlockReplayer::new(parent_state, &chain.spec)
+        .no_signature_verification()
+        .state_root_iter([Ok((parent_block.state_root(), parent_block.slot()))].into_iter())
+        .minimal_block_root_verification()
+        .apply_blocks(vec![], Some(block.slot()))
+        .unwrap()
+        .into_state()
+}
+
+fn record_block_rewards<T: BeaconChainTypes>(
+    chain: &BeaconChain<T>,
+    block_root: Hash256,
+    snapshots: &mut BTreeMap<String, String>,
+) {
+    let block = chain.get_blinded_block(&block_root).unwrap().unwrap();
+    let slot = block.slot();
+
+    let mut state = state_before_block(chain, block_root);
+    let block_rewards = chain
+        .compute_beacon_block_reward(block.message(), &mut state)
+        .unwrap();
+    snapshots.insert(
+        format!("block_rewards_{slot}.json"),
+        to_json(&block_rewards),
+    );
+
+    if block.message().body().sync_aggregate().is_ok() {
+        let mut state = state_before_block(chain, block_root);
+        let sync_committee_rewards = chain
+            .compute_sync_committee_rewards(block.message(), &mut state)
+            .unwrap();
+        snapshots.insert(
+            format!("sync_committee_rewards_{slot}.json"),
+            to_json(&sync_committee_rewards),
+        );
+    }
+}
+
+fn record_attestation_rewards<T: BeaconChainTypes>(
+    chain: &BeaconChain<T>,
+    epoch: Epoch,
+    snapshots: &mut BTreeMap<String, String>,
+) {
+    let attestation_rewards = chain.compute_attestation_rewards(epoch, vec![]).unwrap();
+    snapshots.insert(
+        format!("attestation_rewards_{epoch}.json"),
+        to_json(&attestation_rewards),
+    );
+}
+
+fn to_json<T: Serialize>(value: &T) -> String {
+    let mut json = serde_json::to_string_pretty(value).unwrap();
+    json.push('\n');
+    json
+}
+
+/// Compare the `snapshots` against the files in `dir`, or overwrite them if `update` is set.
+fn check_snapshots(dir: &Path, snapshots: &BTreeMap<String, String>, update: bool) {
+    if update {
+        fs::create_dir_all(dir).unwrap();
+        for (name, json) in snapshots {
+            fs::write(dir.join(name), json).unwrap();
+        }
+        return;
+    }
+
+    let mut expected_names = fs::read_dir(dir)
+        .unwrap_or_else(|e| panic!("unable to read {}: {e:?}", dir.display()))
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .collect::<Vec<_>>();
+    expected_names.sort();
+    assert_eq!(
+        expected_names,
+        snapshots.keys().cloned().collect::<Vec<_>>(),
+        "{} should contain exactly the computed rewards",
+        dir.display()
+    );
+
+    for (name, json) in snapshots {
+        let path = dir.join(name);
+        let expected = fs::read_to_string(&path)
+            .unwrap_or_else(|e| panic!("unable to read {}: {e:?}", path.display()));
+        assert!(
+            expected == *json,
+            "{} does not match the computed rewards:\n{json}",
+            path.display()
+        );
+    }
+}
+
+fn fixtures_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/reward_snapshots")
+}
+
+/// Return the recorded fixtures, which must be present in the source tree.
+fn fixture_dirs() -> Vec<PathBuf> {
+    let root = fixtures_root();
+    let mut dirs = fs::read_dir(&root)
+        .unwrap_or_else(|e| panic!("unable to read {}: {e:?}", root.display()))
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.is_dir())
+        .collect::<Vec<_>>();
+    dirs.sort();
+    assert!(
+        !dirs.is_empty(),
+        "no fixtures in {}, see the README.md there",
+        root.display()
+    );
+    dirs
+}
+
+/// Replay the fixture in `dir` and check its rewards against its `expected` directory.
+async fn check_fixture<E: EthSpec>(dir: &Path, base_spec: &ChainSpec, update: bool) {
+    let fixture = Fixture::<E>::load(dir, base_spec);
+    let snapshots = fixture.replay().await;
+    assert!(!snapshots.is_empty(), "{} has no blocks", dir.display());
+    check_snapshots(&dir.join("expected"), &snapshots, update);
+}
+
+/// Replay each recorded fixture and check its rewards.
+#[tokio::test]
+#[ignore = "no fixtures have been committed to tests/reward_snapshots yet, see its README.md"]
+async fn recorded_reward_snapshots() {
+    let update = std::env::var(UPDATE_ENV_VAR).is_ok();
+
+    // Fixtures use the mainnet preset unless their config says otherwise.
+    for dir in fixture_dirs() {
+        let config_path = dir.join("config.yaml");
+        let preset_base = config_path
+            .exists()
+            .then(|| Config::from_file(&config_path).unwrap().preset_base);
+        match preset_base.as_deref() {
+            Some("minimal") => {
+                check_fixture::<MinimalEthSpec>(&dir, &ChainSpec::minimal(), update).await
+            }
+            _ => check_fixture::<MainnetEthSpec>(&dir, &ChainSpec::mainnet(), update).await,
+        }
+    }
+}
+
+/// Build a local chain spanning the Altair fork, returning a fixture of it and the rewards
+/// computed by the original chain.
+async fn record_local_chain() -> (Fixture<MinimalEthSpec>, BTreeMap<String, String>) {
+    type E = MinimalEthSpec;
+    let slots_per_epoch = E::slots_per_epoch();
+
+    let mut spec = E::default_spec();
+    spec.altair_fork_epoch = Some(Epoch::new(1));
+    let harness = BeaconChainHarness::builder(E::default())
+        .spec(spec.clone())
+        .keypairs(KEYPAIRS.to_vec())
+        .fresh_ephemeral_store()
+        .mock_execution_layer()
+        .build();
+    let genesis_state = harness.get_current_state();
+
+    // Only some of the validators attest so that the chain doesn't finalize and every state
+    // remains available, and so that the rewards include penalties.
+    harness.advance_slot();
+    harness
+        .extend_chain(
+            (slots_per_epoch * 5) as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::SomeValidators((0..VALIDATOR_COUNT / 2).collect()),
+        )
+        .await;
+
+    // Anchor the fixture at the start of the first Altair epoch, so that it spans the fork.
+    let chain = &harness.chain;
+    let anchor_slot = Slot::new(slots_per_epoch);
+    let anchor_block_root = chain
+        .block_root_at_slot(anchor_slot, WhenSlotSkipped::None)
+        .unwrap()
+        .unwrap();
+    let anchor_block = chain.get_block(&anchor_block_root).await.unwrap().unwrap();
+    let anchor_state = chain
+        .get_state(&anchor_block.state_root(), Some(anchor_slot))
+        .unwrap()
+        .unwrap();
+
+    let mut blocks = vec![];
+    let mut expected = BTreeMap::new();
+    for snapshot in chain.chain_dump().unwrap() {
+        if snapshot.beacon_block.slot() <= anchor_slot {
+            continue;
+        }
+        let block_root = snapshot.beacon_block_root;
+        blocks.push((chain.get_block(&block_root).await.unwrap().unwrap(), None));
+        record_block_rewards(chain, block_root, &mut expected);
+    }
+    let head_slot = harness.head_slot();
+    let mut epoch = anchor_slot.epoch(slots_per_epoch);
+    while (epoch + 1).end_slot(slots_per_epoch) <= head_slot {
+        record_attestation_rewards(chain, epoch, &mut expected);
+        epoch += 1;
+    }
+
+    let fixture = Fixture {
+        spec,
+        genesis_state,
+        anchor_state,
+        anchor_block,
+        blocks,
+    };
+    (fixture, expected)
+}
+
+/// Record a fixture from a local chain and check that replaying it reproduces the rewards of the
+/// original chain.
+#[tokio::test]
+async fn replayed_rewards_match_recorded_chain() {
+    let (fixture, expected) = record_local_chain().await;
+    let dir = tempfile::tempdir().unwrap();
+    fixture.write(dir.path());
+    check_snapshots(&dir.path().join("expected"), &expected, true);
+
+    let fixture = Fixture::<MinimalEthSpec>::load(dir.path(), &ChainSpec::minimal());
+    let snapshots = fixture.replay().await;
+    assert_eq!(
+        snapshots.keys().collect::<Vec<_>>(),
+        expected.keys().collect::<Vec<_>>()
+    );
+    check_snapshots(&dir.path().join("expected"), &snapshots, false);
+}
+
+/// Write the fixture of a local chain to `tests/reward_snapshots`, for committing.
+#[tokio::test]
+#[ignore = "records a fixture into the source tree"]
+async fn record_local_reward_snapshot() {
+    let (fixture, expected) = record_local_chain().await;
+    let dir = fixtures_root().join(LOCAL_CASE);
+    if dir.exists() {
+        fs::remove_dir_all(&dir).unwrap();
+    }
+    fixture.write(&dir);
+    check_snapshots(&dir.join("expected"), &expected, true);
+}
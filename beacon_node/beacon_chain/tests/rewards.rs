@@ -12,9 +12,16 @@ use beacon_chain::{
 use eth2::lighthouse::attestation_rewards::TotalAttestationRewards;
 use eth2::lighthouse::StandardAttestationRewards;
 use eth2::types::ValidatorId;
+use genesis::interop_genesis_state;
 use lazy_static::lazy_static;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use state_processing::per_slot_processing;
 use types::beacon_state::Error as BeaconStateError;
-use types::{BeaconState, ChainSpec, ForkName, Slot};
+use types::consts::altair::{
+    NUM_FLAG_INDICES, TIMELY_HEAD_FLAG_INDEX, TIMELY_SOURCE_FLAG_INDEX, TIMELY_TARGET_FLAG_INDEX,
+};
+use types::{BeaconState, ChainSpec, ForkName, Hash256, Slot};
 
 pub const VALIDATOR_COUNT: usize = 64;
 
@@ -159,6 +166,15 @@ async fn test_verify_attestation_rewards_base() {
     assert!(ideal_rewards.iter().all(|reward| reward.inactivity == 0));
     assert!(total_rewards.iter().all(|reward| reward.inactivity == 0));
 
+    // assert the ideal rewards are computed from the partial participation of epoch N
+    let total_active_balance = VALIDATOR_COUNT as u64 * E::default_spec().max_effective_balance;
+    assert!(ideal_rewards.iter().all(|reward| {
+        !reward.is_in_inactivity_leak
+            && reward.total_active_balance == total_active_balance
+            && reward.source_participating_balance > 0
+            && reward.source_participating_balance <= total_active_balance * 2 / 3
+    }));
+
     // apply attestation rewards to initial balances
     let expected_balances = apply_attestation_rewards(&initial_balances, total_rewards);
 
@@ -312,11 +328,7 @@ async fn test_verify_attestation_rewards_altair() {
             harness.make_block_return_pre_state(state, slot).await;
         let beacon_block_reward = harness
             .chain
-            .compute_beacon_block_reward(
-                signed_block.message(),
-                signed_block.canonical_root(),
-                &mut state,
-            )
+            .compute_beacon_block_reward(signed_block.message(), &mut state)
             .unwrap();
 
         let total_proposer_reward = proposal_rewards_map
@@ -400,11 +412,7 @@ async fn test_verify_attestation_rewards_altair_inactivity_leak() {
             harness.make_block_return_pre_state(state, slot).await;
         let beacon_block_reward = harness
             .chain
-            .compute_beacon_block_reward(
-                signed_block.message(),
-                signed_block.canonical_root(),
-                &mut state,
-            )
+            .compute_beacon_block_reward(signed_block.message(), &mut state)
             .unwrap();
 
         let total_proposer_reward = proposal_rewards_map
@@ -447,6 +455,16 @@ async fn test_verify_attestation_rewards_altair_inactivity_leak() {
     assert!(total_rewards[..half]
         .iter()
         .all(|reward| reward.inactivity == 0));
+
+    // assert the ideal rewards reflect the inactivity leak and the participation of half of the
+    // validators
+    assert!(ideal_rewards.iter().all(|reward| {
+        reward.is_in_inactivity_leak
+            && reward.head == 0
+            && reward.target == 0
+            && reward.source == 0
+            && reward.target_participating_balance == half as u64 * spec.max_effective_balance
+    }));
     assert!(total_rewards[half..]
         .iter()
         .all(|reward| reward.inactivity < 0));
@@ -506,11 +524,7 @@ async fn test_verify_attestation_rewards_altair_inactivity_leak_justification_ep
             harness.make_block_return_pre_state(state, slot).await;
         let beacon_block_reward = harness
             .chain
-            .compute_beacon_block_reward(
-                signed_block.message(),
-                signed_block.canonical_root(),
-                &mut state,
-            )
+            .compute_beacon_block_reward(signed_block.message(), &mut state)
             .unwrap();
 
         let total_proposer_reward = proposal_rewards_map
@@ -618,6 +632,280 @@ async fn test_verify_attestation_rewards_base_subset_only() {
     assert_eq!(expected_balances, balances);
 }
 
+#[tokio::test]
+async fn test_verify_attestation_rewards_base_historic() {
+    let harness = get_harness(E::default_spec());
+
+    // epoch 0 (N), only two thirds of validators vote.
+    let two_thirds = (VALIDATOR_COUNT / 3) * 2;
+    let two_thirds_validators: Vec<usize> = (0..two_thirds).collect();
+    harness
+        .extend_chain(
+            E::slots_per_epoch() as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::SomeValidators(two_thirds_validators),
+        )
+        .await;
+
+    // extend slots to beginning of epoch N + 2
+    harness.extend_slots(E::slots_per_epoch() as usize).await;
+
+    // compute reward deltas for all validators in epoch N, from the hot database
+    let hot_rewards = harness
+        .chain
+        .compute_attestation_rewards(Epoch::new(0), vec![])
+        .unwrap();
+
+    // finalize the chain until the rewards state for epoch N has been migrated to the freezer
+    harness
+        .extend_slots(5 * E::slots_per_epoch() as usize)
+        .await;
+    let rewards_state_slot = Epoch::new(1).end_slot(E::slots_per_epoch());
+    assert!(harness.chain.store.get_split_slot() > rewards_state_slot);
+
+    // the rewards computed from the freezer match those computed from the hot database
+    let historic_rewards = harness
+        .chain
+        .compute_attestation_rewards(Epoch::new(0), vec![])
+        .unwrap();
+    assert_eq!(historic_rewards, hot_rewards);
+}
+
+#[tokio::test]
+async fn test_attestation_rewards_partial_participation_base() {
+    let harness = get_harness(E::default_spec());
+    extend_chain_with_partial_participation(&harness).await;
+    assert_rewards_match_epoch_processing(&harness, Epoch::new(1));
+}
+
+#[tokio::test]
+async fn test_attestation_rewards_partial_participation_altair() {
+    let spec = ForkName::Altair.make_genesis_spec(E::default_spec());
+    let harness = get_harness(spec);
+    extend_chain_with_partial_participation(&harness).await;
+    assert_rewards_match_epoch_processing(&harness, Epoch::new(1));
+}
+
+/// Check the attestation rewards computed from randomly generated Altair states against the balance
+/// changes made by epoch processing.
+#[tokio::test]
+async fn test_attestation_rewards_random_states_altair() {
+    let spec = ForkName::Altair.make_genesis_spec(E::default_spec());
+    let harness = get_harness(spec.clone());
+    let mut rng = StdRng::seed_from_u64(0);
+
+    for case in 0..32 {
+        let pre_state = random_altair_state(&mut rng, &spec);
+        let rewards_by_index: HashMap<usize, TotalAttestationRewards> = harness
+            .chain
+            .compute_attestation_rewards_from_state(pre_state.clone(), vec![])
+            .unwrap()
+            .total_rewards
+            .into_iter()
+            .map(|rewards| (rewards.validator_index as usize, rewards))
+            .collect();
+
+        let mut post_state = pre_state.clone();
+        per_slot_processing(&mut post_state, None, &spec).unwrap();
+
+        let mut total_rewards = 0;
+        let mut total_delta = 0;
+        for (index, (pre_balance, post_balance)) in pre_state
+            .balances()
+            .iter()
+            .zip(post_state.balances().iter())
+            .enumerate()
+        {
+            let rewards = rewards_by_index.get(&index).map_or(0, |rewards| {
+                rewards.head + rewards.source + rewards.target + rewards.inactivity
+            });
+            let delta = *post_balance as i64 - *pre_balance as i64;
+            assert_eq!(delta, rewards, "case {case}, validator {index}");
+            total_rewards += rewards;
+            total_delta += delta;
+        }
+        assert_eq!(total_delta, total_rewards, "case {case}");
+    }
+}
+
+/// Check the network context of the ideal rewards computed from randomly generated Altair states,
+/// which must exclude slashed validators from the participating balances.
+#[tokio::test]
+async fn test_ideal_attestation_rewards_context_random_states_altair() {
+    let spec = ForkName::Altair.make_genesis_spec(E::default_spec());
+    let harness = get_harness(spec.clone());
+    let mut rng = StdRng::seed_from_u64(1);
+
+    for case in 0..8 {
+        let state = random_altair_state(&mut rng, &spec);
+        let previous_epoch = state.previous_epoch();
+        let current_epoch = state.current_epoch();
+
+        let total_active_balance = state
+            .validators()
+            .iter()
+            .filter(|validator| validator.is_active_at(current_epoch))
+            .map(|validator| validator.effective_balance)
+            .sum::<u64>();
+        let participating_balance = |flag_index| {
+            state
+                .validators()
+                .iter()
+                .zip(state.previous_epoch_participation().unwrap().iter())
+                .filter(|(validator, participation)| {
+                    validator.is_active_at(previous_epoch)
+                        && !validator.slashed
+                        && participation.has_flag(flag_index).unwrap()
+                })
+                .map(|(validator, _)| validator.effective_balance)
+                .sum::<u64>()
+        };
+
+        let ideal_rewards = harness
+            .chain
+            .compute_attestation_rewards_from_state(state.clone(), vec![])
+            .unwrap()
+            .ideal_rewards;
+
+        assert!(!ideal_rewards.is_empty());
+        for reward in &ideal_rewards {
+            assert_eq!(
+                reward.total_active_balance, total_active_balance,
+                "case {case}"
+            );
+            assert_eq!(
+                reward.head_participating_balance,
+                participating_balance(TIMELY_HEAD_FLAG_INDEX),
+                "case {case}"
+            );
+            assert_eq!(
+                reward.target_participating_balance,
+                participating_balance(TIMELY_TARGET_FLAG_INDEX),
+                "case {case}"
+            );
+            assert_eq!(
+                reward.source_participating_balance,
+                participating_balance(TIMELY_SOURCE_FLAG_INDEX),
+                "case {case}"
+            );
+            // No head, target or source rewards are paid during an inactivity leak.
+            assert_eq!(
+                reward.is_in_inactivity_leak,
+                reward.head == 0 && reward.target == 0 && reward.source == 0,
+                "case {case}"
+            );
+        }
+    }
+}
+
+/// Generate an Altair state at the last slot of an epoch between 2 and 8, with random balances,
+/// slashings, inactivity scores and participation in the previous epoch.
+///
+/// Nothing is finalized after genesis, so states from epoch 6 onwards are in an inactivity leak.
+fn random_altair_state(rng: &mut StdRng, spec: &ChainSpec) -> BeaconState<E> {
+    let validator_count = rng.gen_range(16..=VALIDATOR_COUNT);
+    let mut state =
+        interop_genesis_state::<E>(&KEYPAIRS[..validator_count], 0, Hash256::zero(), None, spec)
+            .unwrap();
+
+    let epoch = Epoch::new(rng.gen_range(2..=8));
+    while state.slot() < epoch.end_slot(E::slots_per_epoch()) {
+        per_slot_processing(&mut state, None, spec).unwrap();
+    }
+
+    let previous_epoch = state.previous_epoch();
+    let current_epoch = state.current_epoch();
+    for index in 0..validator_count {
+        // Keep effective balances above the ejection balance, and balances large enough that no
+        // penalty is capped, so that epoch processing applies each reward and penalty in full.
+        let effective_balance = rng.gen_range(17..=32) * spec.effective_balance_increment;
+        let validator = state.get_validator_mut(index).unwrap();
+        validator.effective_balance = effective_balance;
+        validator.slashed = rng.gen_bool(0.1);
+        *state.get_balance_mut(index).unwrap() =
+            effective_balance + rng.gen_range(0..spec.effective_balance_increment);
+        *state.get_inactivity_score_mut(index).unwrap() = rng.gen_range(0..64);
+
+        let participation = state
+            .get_epoch_participation_mut(previous_epoch, previous_epoch, current_epoch)
+            .unwrap()
+            .get_mut(index)
+            .unwrap();
+        for flag_index in 0..NUM_FLAG_INDICES {
+            if rng.gen_bool(0.7) {
+                participation.add_flag(flag_index).unwrap();
+            }
+        }
+    }
+    state.drop_all_caches().unwrap();
+    state
+}
+
+/// Extend the chain to the start of epoch 3, with only every other validator attesting during
+/// epoch 1.
+async fn extend_chain_with_partial_participation(
+    harness: &BeaconChainHarness<EphemeralHarnessType<E>>,
+) {
+    let half_validators: Vec<usize> = (0..VALIDATOR_COUNT).step_by(2).collect();
+    harness.extend_slots(E::slots_per_epoch() as usize).await;
+    harness
+        .extend_slots_some_validators(E::slots_per_epoch() as usize, half_validators)
+        .await;
+    harness.extend_slots(E::slots_per_epoch() as usize).await;
+}
+
+/// Check the attestation rewards of every validator for `epoch` against the balance changes made
+/// by epoch processing at the end of `epoch + 1`.
+fn assert_rewards_match_epoch_processing(
+    harness: &BeaconChainHarness<EphemeralHarnessType<E>>,
+    epoch: Epoch,
+) {
+    let chain = &harness.chain;
+    let state_slot = (epoch + 1).end_slot(E::slots_per_epoch());
+    let state_root = chain.state_root_at_slot(state_slot).unwrap().unwrap();
+    let pre_state = chain
+        .get_state(&state_root, Some(state_slot))
+        .unwrap()
+        .unwrap();
+    let mut post_state = pre_state.clone();
+    per_slot_processing(&mut post_state, Some(state_root), &chain.spec).unwrap();
+
+    let rewards_by_index: HashMap<usize, TotalAttestationRewards> = chain
+        .compute_attestation_rewards(epoch, vec![])
+        .unwrap()
+        .total_rewards
+        .into_iter()
+        .map(|rewards| (rewards.validator_index as usize, rewards))
+        .collect();
+
+    // Both participating and non-participating validators must be checked.
+    let participating = rewards_by_index
+        .values()
+        .filter(|rewards| rewards.target > 0)
+        .count();
+    assert!(participating > 0 && participating < VALIDATOR_COUNT);
+
+    for (index, (pre_balance, post_balance)) in pre_state
+        .balances()
+        .iter()
+        .zip(post_state.balances().iter())
+        .enumerate()
+    {
+        let expected_delta = rewards_by_index.get(&index).map_or(0, |rewards| {
+            rewards.head
+                + rewards.source
+                + rewards.target
+                + rewards.inclusion_delay.map_or(0, |q| q.value as i64)
+                + rewards.inactivity
+        });
+        assert_eq!(
+            *post_balance as i64 - *pre_balance as i64,
+            expected_delta,
+            "validator {index}"
+        );
+    }
+}
+
 /// Apply a vec of `TotalAttestationRewards` to initial balances, and return
 fn apply_attestation_rewards(
     initial_balances: &[u64],
@@ -19,17 +19,23 @@ use logging::test_logger;
 use maplit::hashset;
 use rand::Rng;
 use slot_clock::{SlotClock, TestingSlotClock};
+use ssz::Encode;
 use state_processing::{state_advance::complete_state_advance, BlockReplayer};
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::convert::TryInto;
+use std::path::Path;
 use std::sync::Arc;
 use std::time::Duration;
 use store::chunked_vector::Chunk;
+use store::era::{
+    era_file_name, E2StoreWriter, COMPRESSED_BEACON_STATE, COMPRESSED_SIGNED_BEACON_BLOCK, VERSION,
+};
 use store::metadata::{SchemaVersion, CURRENT_SCHEMA_VERSION, STATE_UPPER_LIMIT_NO_RETAIN};
 use store::{
     chunked_vector::{chunk_key, Field},
     get_key_for_col,
+    hot_cold_store::HotColdDBError,
     iter::{BlockRootsIterator, StateRootsIterator},
     BlobInfo, DBColumn, HotColdDB, KeyValueStore, KeyValueStoreOp, LevelDB, StoreConfig,
 };
@@ -705,6 +711,14 @@ async fn forwards_iter_block_and_state_roots_until() {
             let state_root = state_roots[slot.as_usize()];
             assert_eq!(state_root_iter.next().unwrap().unwrap(), (state_root, slot));
         }
+
+        let expected_state_roots = (start_slot.as_u64()..=end_slot.as_u64())
+            .map(|slot| (state_roots[slot as usize], Slot::new(slot)))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            chain.state_roots_in_range(start_slot, end_slot).unwrap(),
+            expected_state_roots
+        );
     };
 
     let split_slot = store.get_split_slot();
@@ -2617,6 +2631,181 @@ async fn weak_subjectivity_sync_test(slots: Vec<Slot>, checkpoint_slot: Slot) {
     assert_eq!(store.get_anchor_slot(), None);
 }
 
+/// Write an era file for each of the eras `1..=end_era` of `harness`'s chain to `era_dir`, in the
+/// same format as `database_manager`'s era export.
+async fn write_era_files(harness: &TestHarness, end_era: u64, era_dir: &Path) {
+    let slots_per_era = E::slots_per_historical_root() as u64;
+    let config_name = harness.spec.config_name.as_deref().unwrap_or("minimal");
+
+    for era in 1..=end_era {
+        let state_slot = Slot::new(era * slots_per_era);
+        let start_slot = state_slot - slots_per_era;
+
+        let mut writer = E2StoreWriter::new(vec![]);
+        writer.write_record(VERSION, &[]).unwrap();
+
+        // The genesis block is not included, as it is unsigned.
+        let mut block_offsets = vec![];
+        for slot in (start_slot.as_u64()..state_slot.as_u64()).map(Slot::new) {
+            let block_root = harness
+                .chain
+                .block_root_at_slot(slot, WhenSlotSkipped::None)
+                .unwrap()
+                .filter(|_| slot != 0);
+            let offset = match block_root {
+                Some(block_root) => {
+                    let block = harness.chain.get_block(&block_root).await.unwrap().unwrap();
+                    Some(
+                        writer
+                            .write_compressed(COMPRESSED_SIGNED_BEACON_BLOCK, &block.as_ssz_bytes())
+                            .unwrap(),
+                    )
+                }
+                None => None,
+            };
+            block_offsets.push(offset);
+        }
+
+        let state_root = harness
+            .chain
+            .state_root_at_slot(state_slot)
+            .unwrap()
+            .unwrap();
+        let state = harness
+            .chain
+            .get_state(&state_root, Some(state_slot))
+            .unwrap()
+            .unwrap();
+        let state_offset = writer
+            .write_compressed(COMPRESSED_BEACON_STATE, &state.as_ssz_bytes())
+            .unwrap();
+        writer.write_slot_index(start_slot, &block_offsets).unwrap();
+        writer
+            .write_slot_index(state_slot, &[Some(state_offset)])
+            .unwrap();
+
+        let short_root = hex::encode(&state_root.as_bytes()[..4]);
+        let path = era_dir.join(era_file_name(config_name, era, &short_root));
+        std::fs::write(path, writer.into_inner()).unwrap();
+    }
+}
+
+/// Test that backfill after a checkpoint sync can import the blocks prior to the checkpoint from
+/// era files, skipping era files which can't be imported.
+#[tokio::test]
+async fn era_import_after_checkpoint_sync() {
+    let spec = test_spec::<E>();
+    if spec.deneb_fork_epoch.is_some() {
+        // Era files don't contain blobs, so blocks within the data availability window can't be
+        // imported from them after Deneb.
+        return;
+    }
+
+    // Build a chain with full history, with the checkpoint at the end of the second era.
+    let slots_per_era = E::slots_per_historical_root() as u64;
+    let checkpoint_slot = Slot::new(2 * slots_per_era);
+    let num_initial_slots = checkpoint_slot.as_u64() + E::slots_per_epoch() * 2;
+
+    let temp1 = tempdir().unwrap();
+    let full_store = get_store(&temp1);
+    let harness = get_harness(full_store.clone(), LOW_VALIDATOR_COUNT);
+    let all_validators = (0..LOW_VALIDATOR_COUNT).collect::<Vec<_>>();
+
+    let (genesis_state, genesis_state_root) = harness.get_current_state_and_root();
+    let slots = (1..num_initial_slots).map(Slot::new).collect::<Vec<_>>();
+    harness
+        .add_attested_blocks_at_slots(
+            genesis_state.clone(),
+            genesis_state_root,
+            &slots,
+            &all_validators,
+        )
+        .await;
+
+    let era_dir = tempdir().unwrap();
+    write_era_files(&harness, 2, era_dir.path()).await;
+
+    // A corrupt file for the second era, which should be skipped in favour of the valid one.
+    let config_name = harness.spec.config_name.as_deref().unwrap_or("minimal");
+    std::fs::write(
+        era_dir
+            .path()
+            .join(era_file_name(config_name, 2, "00000000")),
+        [0; 64],
+    )
+    .unwrap();
+
+    let wss_block_root = harness
+        .chain
+        .block_root_at_slot(checkpoint_slot, WhenSlotSkipped::Prev)
+        .unwrap()
+        .unwrap();
+    let wss_state_root = harness
+        .chain
+        .state_root_at_slot(checkpoint_slot)
+        .unwrap()
+        .unwrap();
+    let wss_block = full_store.get_full_block(&wss_block_root).unwrap().unwrap();
+    let wss_state = full_store
+        .get_state(&wss_state_root, Some(checkpoint_slot))
+        .unwrap()
+        .unwrap();
+
+    // Initialise a new beacon chain from the checkpoint.
+    let (shutdown_tx, _shutdown_rx) = futures::channel::mpsc::channel(1);
+    let log = test_logger();
+    let temp2 = tempdir().unwrap();
+    let store = get_store(&temp2);
+    let mock =
+        mock_execution_layer_from_parts(&harness.spec, harness.runtime.task_executor.clone());
+
+    let slot_clock = TestingSlotClock::new(
+        Slot::new(0),
+        Duration::from_secs(harness.chain.genesis_time),
+        Duration::from_secs(spec.seconds_per_slot),
+    );
+    slot_clock.set_slot(harness.get_current_slot().as_u64());
+
+    let beacon_chain = BeaconChainBuilder::<DiskHarnessType<E>>::new(MinimalEthSpec)
+        .store(store.clone())
+        .custom_spec(spec)
+        .task_executor(harness.chain.task_executor.clone())
+        .logger(log.clone())
+        .weak_subjectivity_state(wss_state, wss_block, None, genesis_state)
+        .unwrap()
+        .store_migrator_config(MigratorConfig::default().blocking())
+        .dummy_eth1_backend()
+        .expect("should build dummy backend")
+        .slot_clock(slot_clock)
+        .shutdown_sender(shutdown_tx)
+        .chain_config(ChainConfig::default())
+        .event_handler(Some(ServerSentEventHandler::new_with_capacity(log, 1)))
+        .execution_layer(Some(mock.el))
+        .build()
+        .expect("should build");
+
+    assert_eq!(store.get_oldest_block_slot(), checkpoint_slot);
+
+    let imported = beacon_chain.import_era_files(era_dir.path()).unwrap();
+    let expected_imported = slots.iter().filter(|slot| **slot < checkpoint_slot).count();
+    assert_eq!(imported, expected_imported);
+    assert_eq!(store.get_oldest_block_slot(), 0);
+
+    // The backfilled chain matches the original chain.
+    let forwards = beacon_chain
+        .forwards_iter_block_roots_until(Slot::new(0), checkpoint_slot)
+        .unwrap()
+        .map(Result::unwrap)
+        .collect::<Vec<_>>();
+    let expected = harness
+        .chain
+        .forwards_iter_block_roots_until(Slot::new(0), checkpoint_slot)
+        .unwrap()
+        .map(Result::unwrap)
+        .collect::<Vec<_>>();
+    assert_eq!(forwards, expected);
+}
+
 /// Test that blocks and attestations that refer to states around an unaligned split state are
 /// processed correctly.
 #[tokio::test]
@@ -3392,6 +3581,73 @@ async fn change_to_separate_blobs_db_before_deneb() {
     assert_eq!(store.get_blob_info(), init_blob_info);
 }
 
+/// Check that a Deneb fork which is announced or rescheduled between restarts updates the blob info.
+#[tokio::test]
+async fn deneb_fork_rescheduled_at_restart() {
+    let db_path = tempdir().unwrap();
+    let mut spec = test_spec::<E>();
+    spec.deneb_fork_epoch = None;
+    spec.electra_fork_epoch = None;
+
+    let store = get_store_generic(&db_path, StoreConfig::default(), spec.clone());
+    assert_eq!(store.get_blob_info().oldest_blob_slot, None);
+    drop(store);
+
+    // Announce the Deneb fork.
+    spec.deneb_fork_epoch = Some(Epoch::new(100));
+    let store = get_store_generic(&db_path, StoreConfig::default(), spec.clone());
+    assert_eq!(
+        store.get_blob_info().oldest_blob_slot,
+        Some(Epoch::new(100).start_slot(E::slots_per_epoch()))
+    );
+    drop(store);
+
+    // Bring the Deneb fork forward.
+    spec.deneb_fork_epoch = Some(Epoch::new(50));
+    let store = get_store_generic(&db_path, StoreConfig::default(), spec.clone());
+    assert_eq!(
+        store.get_blob_info().oldest_blob_slot,
+        Some(Epoch::new(50).start_slot(E::slots_per_epoch()))
+    );
+    drop(store);
+
+    // Cancel the Deneb fork.
+    spec.deneb_fork_epoch = None;
+    let store = get_store_generic(&db_path, StoreConfig::default(), spec);
+    assert_eq!(store.get_blob_info().oldest_blob_slot, None);
+}
+
+/// Check that a fork can't be rescheduled to before the split.
+#[tokio::test]
+async fn fork_rescheduled_before_split_at_restart() {
+    let db_path = tempdir().unwrap();
+    let mut spec = test_spec::<E>();
+    spec.electra_fork_epoch = None;
+
+    let store = get_store_generic(&db_path, StoreConfig::default(), spec.clone());
+    drop(store);
+
+    // The split is at genesis, so scheduling a fork at genesis is not allowed.
+    spec.electra_fork_epoch = Some(Epoch::new(0));
+    let err = HotColdDB::open(
+        &db_path.path().join("hot_db"),
+        &db_path.path().join("cold_db"),
+        &db_path.path().join("blobs_db"),
+        |_, _, _| Ok(()),
+        StoreConfig::default(),
+        spec,
+        test_logger(),
+    )
+    .unwrap_err();
+    assert!(matches!(
+        err,
+        store::Error::HotColdDBError(HotColdDBError::ForkScheduleChangedBeforeSplit {
+            fork_name: ForkName::Electra,
+            ..
+        })
+    ));
+}
+
 /// Check that there are blob sidecars (or not) at every slot in the range.
 fn check_blob_existence(
     harness: &TestHarness,
@@ -3490,6 +3746,118 @@ async fn prune_historic_states() {
     check_split_slot(&harness, store);
 }
 
+#[tokio::test]
+async fn prune_history_before() {
+    let num_blocks_produced = E::slots_per_epoch() * 5;
+    let db_path = tempdir().unwrap();
+    let store = get_store(&db_path);
+    let harness = get_harness(store.clone(), LOW_VALIDATOR_COUNT);
+    let genesis_state_root = harness.chain.genesis_state_root;
+    let genesis_state = harness
+        .chain
+        .get_state(&genesis_state_root, None)
+        .unwrap()
+        .unwrap();
+
+    harness
+        .extend_chain(
+            num_blocks_produced as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+
+    let prune_slot = Slot::new(E::slots_per_epoch() * 2 + 1);
+    assert!(prune_slot < store.get_split_slot());
+    let block_roots = harness
+        .chain
+        .forwards_iter_block_roots(Slot::new(0))
+        .unwrap()
+        .map(Result::unwrap)
+        .take_while(|(_, slot)| *slot <= prune_slot)
+        .collect::<Vec<_>>();
+    let (genesis_block_root, _) = block_roots[0];
+    let (new_oldest_block_root, _) = block_roots[prune_slot.as_usize()];
+
+    // Blocks can't be pruned while historic states are stored.
+    assert!(matches!(
+        store.prune_history_before(prune_slot),
+        Err(store::Error::HotColdDBError(
+            HotColdDBError::HistoricStatesNotPruned
+        ))
+    ));
+
+    store
+        .prune_historic_states(genesis_state_root, &genesis_state)
+        .unwrap();
+    assert_eq!(
+        store.prune_history_before(prune_slot).unwrap(),
+        prune_slot.as_usize() - 1
+    );
+
+    // Check that anchor info is updated.
+    let anchor_info = store.get_anchor_info().unwrap();
+    let new_oldest_block = store
+        .get_blinded_block(&new_oldest_block_root)
+        .unwrap()
+        .unwrap();
+    assert_eq!(anchor_info.oldest_block_slot, prune_slot);
+    assert_eq!(
+        anchor_info.oldest_block_parent,
+        new_oldest_block.parent_root()
+    );
+
+    // Older blocks should be deleted, except for the genesis block.
+    for (block_root, slot) in &block_roots[1..prune_slot.as_usize()] {
+        assert!(
+            store.get_blinded_block(block_root).unwrap().is_none(),
+            "block at slot {slot} should be pruned"
+        );
+    }
+    assert!(store
+        .get_blinded_block(&genesis_block_root)
+        .unwrap()
+        .is_some());
+
+    // Pruning again is a no-op.
+    assert_eq!(store.prune_history_before(prune_slot).unwrap(), 0);
+
+    // The oldest block slot can be raised without deleting blocks, and lowered again.
+    let later_slot = prune_slot + 2;
+    let anchor_info = store.set_oldest_block_slot(later_slot).unwrap();
+    assert_eq!(anchor_info.oldest_block_slot, later_slot);
+    assert!(store
+        .get_blinded_block(&new_oldest_block_root)
+        .unwrap()
+        .is_some());
+    let anchor_info = store.set_oldest_block_slot(prune_slot).unwrap();
+    assert_eq!(anchor_info.oldest_block_slot, prune_slot);
+    assert_eq!(
+        anchor_info.oldest_block_parent,
+        new_oldest_block.parent_root()
+    );
+
+    // It can't be lowered past the pruned blocks.
+    assert!(matches!(
+        store.set_oldest_block_slot(Slot::new(1)),
+        Err(store::Error::HotColdDBError(
+            HotColdDBError::MissingHistoricBlock {
+                oldest_available_slot,
+                ..
+            }
+        )) if oldest_available_slot == prune_slot
+    ));
+
+    // Run for another two epochs.
+    let additional_blocks_produced = 2 * E::slots_per_epoch();
+    harness
+        .extend_slots(additional_blocks_produced as usize)
+        .await;
+
+    check_finalization(&harness, num_blocks_produced + additional_blocks_produced);
+    check_split_slot(&harness, store);
+}
+
 /// Checks that two chains are the same, for the purpose of these tests.
 ///
 /// Several fields that are hard/impossible to check are ignored (e.g., the store).
@@ -2,18 +2,23 @@
 
 use beacon_chain::{
     attestation_verification::Error as AttnError,
+    block_verification_types::RpcBlock,
+    finality_watchdog::{FINALITY_STALL_MAX_HEAD_DISTANCE, SAFE_MODE_STATE_CACHE_MULTIPLIER},
     test_utils::{
         AttestationStrategy, BeaconChainHarness, BlockStrategy, EphemeralHarnessType,
         OP_POOL_DB_KEY,
     },
-    BeaconChain, ChainConfig, NotifyExecutionLayer, StateSkipConfig, WhenSlotSkipped,
+    BeaconChain, BlockError, ChainConfig, NotifyExecutionLayer, StateSkipConfig, WhenSlotSkipped,
 };
 use lazy_static::lazy_static;
 use operation_pool::PersistedOperationPool;
 use state_processing::{per_slot_processing, per_slot_processing::Error as SlotProcessingError};
+use std::sync::Arc;
+use store::WeakSubjectivityViolation;
+use task_executor::ShutdownReason;
 use types::{
-    BeaconState, BeaconStateError, BlockImportSource, EthSpec, Hash256, Keypair, MinimalEthSpec,
-    RelativeEpoch, Slot,
+    BeaconState, BeaconStateError, BlockImportSource, Checkpoint, Epoch, EthSpec, Hash256, Keypair,
+    MinimalEthSpec, RelativeEpoch, Slot,
 };
 
 // Should ideally be divisible by 3.
@@ -871,3 +876,211 @@ async fn block_roots_skip_slot_behaviour() {
         "WhenSlotSkipped::Prev should return None on a future slot"
     );
 }
+
+#[tokio::test]
+async fn rejects_chain_conflicting_with_weak_subjectivity_checkpoint() {
+    let num_blocks_produced = MinimalEthSpec::slots_per_epoch() * 5;
+
+    let harness = get_harness(VALIDATOR_COUNT);
+    harness
+        .extend_chain(
+            num_blocks_produced as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+    assert!(
+        harness.finalized_checkpoint().epoch >= 2,
+        "test precondition: chain should finalize past the checkpoint"
+    );
+
+    // A node configured with a checkpoint which is not part of the chain above.
+    let wss_checkpoint = Checkpoint {
+        epoch: Epoch::new(2),
+        root: Hash256::repeat_byte(0x42),
+    };
+    let node = BeaconChainHarness::builder(MinimalEthSpec)
+        .default_spec()
+        .chain_config(ChainConfig {
+            weak_subjectivity_checkpoint: Some(wss_checkpoint),
+            ..ChainConfig::default()
+        })
+        .keypairs(KEYPAIRS[0..VALIDATOR_COUNT].to_vec())
+        .fresh_ephemeral_store()
+        .mock_execution_layer()
+        .build();
+
+    let mut rejected_block = None;
+    for snapshot in harness.chain.chain_dump().unwrap().into_iter().skip(1) {
+        let block_root = snapshot.beacon_block_root;
+        let block = harness.chain.get_block(&block_root).await.unwrap().unwrap();
+        let blobs = harness.chain.get_blobs(&block_root).unwrap();
+
+        node.set_current_slot(block.slot());
+        let result = node
+            .chain
+            .process_block(
+                block_root,
+                RpcBlock::new(Some(block_root), Arc::new(block), Some(blobs)).unwrap(),
+                NotifyExecutionLayer::Yes,
+                BlockImportSource::RangeSync,
+                || Ok(()),
+            )
+            .await;
+        node.chain.recompute_head_at_current_slot().await;
+
+        match result {
+            Ok(_) => assert!(!node.chain.is_weak_subjectivity_violated()),
+            Err(BlockError::WeakSubjectivityConflict) => {
+                rejected_block = Some(block_root);
+                break;
+            }
+            Err(e) => panic!("unexpected error: {:?}", e),
+        }
+    }
+
+    let rejected_block = rejected_block.expect("a block should conflict with the checkpoint");
+    assert!(node.chain.is_weak_subjectivity_violated());
+    assert!(
+        matches!(
+            node.shutdown_reasons().as_slice(),
+            [ShutdownReason::Failure(_)]
+        ),
+        "the node should shut down"
+    );
+    assert!(
+        node.finalized_checkpoint().epoch < wss_checkpoint.epoch,
+        "the node should not finalize the conflicting chain"
+    );
+    assert_ne!(node.head_block_root(), rejected_block);
+
+    // The violation is persisted, and still reported after a restart.
+    assert_eq!(
+        node.chain.store.load_weak_subjectivity_violation().unwrap(),
+        Some(WeakSubjectivityViolation {
+            block_root: rejected_block
+        })
+    );
+    node.chain.persist_head_and_fork_choice().unwrap();
+    let resumed = BeaconChainHarness::builder(MinimalEthSpec)
+        .default_spec()
+        .chain_config(ChainConfig {
+            weak_subjectivity_checkpoint: Some(wss_checkpoint),
+            ..ChainConfig::default()
+        })
+        .keypairs(KEYPAIRS[0..VALIDATOR_COUNT].to_vec())
+        .resumed_ephemeral_store(node.chain.store.clone())
+        .mock_execution_layer()
+        .testing_slot_clock(node.chain.slot_clock.clone())
+        .build();
+    assert!(resumed.chain.is_weak_subjectivity_violated());
+}
+
+#[tokio::test]
+async fn finality_stall_enters_and_leaves_safe_mode() {
+    let slots_per_epoch = MinimalEthSpec::slots_per_epoch();
+    let harness = BeaconChainHarness::builder(MinimalEthSpec)
+        .default_spec()
+        .chain_config(ChainConfig {
+            finality_stall_safe_mode: true,
+            ..ChainConfig::default()
+        })
+        .keypairs(KEYPAIRS[0..VALIDATOR_COUNT].to_vec())
+        .fresh_ephemeral_store()
+        .mock_execution_layer()
+        .build();
+    harness.advance_slot();
+    let chain = &harness.chain;
+    let stall_epochs = chain.config.finality_stall_epochs;
+    let state_cache_capacity = chain.store.state_cache_capacity();
+
+    // Produce blocks without any attestations so that the chain cannot finalize.
+    harness
+        .extend_chain(
+            (slots_per_epoch * stall_epochs) as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::SomeValidators(vec![]),
+        )
+        .await;
+    chain.check_finality_stall(chain.slot().unwrap());
+
+    assert!(chain.finality_watchdog.is_stalled());
+    assert!(chain.store_migrator.is_blob_pruning_deferred());
+    assert_eq!(
+        chain.store.state_cache_capacity(),
+        state_cache_capacity * SAFE_MODE_STATE_CACHE_MULTIPLIER
+    );
+
+    // Resume attesting until the chain finalizes again.
+    harness
+        .extend_chain(
+            (slots_per_epoch * 5) as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+    let current_epoch = chain.epoch().unwrap();
+    assert!(
+        harness.finalized_checkpoint().epoch + stall_epochs > current_epoch,
+        "test precondition: chain should have finalized"
+    );
+    chain.check_finality_stall(chain.slot().unwrap());
+
+    assert!(!chain.finality_watchdog.is_stalled());
+    assert!(!chain.store_migrator.is_blob_pruning_deferred());
+    assert_eq!(chain.store.state_cache_capacity(), state_cache_capacity);
+}
+
+#[tokio::test]
+async fn finality_stall_without_safe_mode() {
+    let slots_per_epoch = MinimalEthSpec::slots_per_epoch();
+    let harness = get_harness(VALIDATOR_COUNT);
+    let chain = &harness.chain;
+    assert!(!chain.config.finality_stall_safe_mode);
+    let state_cache_capacity = chain.store.state_cache_capacity();
+
+    harness
+        .extend_chain(
+            (slots_per_epoch * chain.config.finality_stall_epochs) as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::SomeValidators(vec![]),
+        )
+        .await;
+    chain.check_finality_stall(chain.slot().unwrap());
+
+    // The stall is still detected, but the cache and pruning are left alone.
+    assert!(chain.finality_watchdog.is_stalled());
+    assert!(!chain.store_migrator.is_blob_pruning_deferred());
+    assert_eq!(chain.store.state_cache_capacity(), state_cache_capacity);
+}
+
+#[tokio::test]
+async fn finality_stall_ignored_whilst_syncing() {
+    let slots_per_epoch = MinimalEthSpec::slots_per_epoch();
+    let harness = get_harness(VALIDATOR_COUNT);
+    let chain = &harness.chain;
+    let stall_epochs = chain.config.finality_stall_epochs;
+
+    // Advance the clock without importing any blocks, as if the node were still syncing the
+    // chain, e.g. just after checkpoint sync.
+    for _ in 0..slots_per_epoch * (stall_epochs + 1) {
+        harness.advance_slot();
+    }
+    chain.check_finality_stall(chain.slot().unwrap());
+    assert!(!chain.finality_watchdog.is_stalled());
+
+    // Once the head catches up with the wall clock, the chain is measured as stalled.
+    harness
+        .extend_chain(
+            1,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::SomeValidators(vec![]),
+        )
+        .await;
+    assert!(
+        chain.head_snapshot().beacon_block.slot() + FINALITY_STALL_MAX_HEAD_DISTANCE
+            >= chain.slot().unwrap()
+    );
+    chain.check_finality_stall(chain.slot().unwrap());
+    assert!(chain.finality_watchdog.is_stalled());
+}
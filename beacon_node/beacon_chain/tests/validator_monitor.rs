@@ -1,10 +1,16 @@
 use beacon_chain::test_utils::{
     AttestationStrategy, BeaconChainHarness, BlockStrategy, EphemeralHarnessType,
 };
-use beacon_chain::validator_monitor::{ValidatorMonitorConfig, MISSED_BLOCK_LAG_SLOTS};
+use beacon_chain::validator_monitor::{
+    timestamp_now, ValidatorMonitorConfig, MISSED_BLOCK_LAG_SLOTS,
+};
 use lazy_static::lazy_static;
 use logging::test_logger;
-use types::{Epoch, EthSpec, Keypair, MainnetEthSpec, PublicKeyBytes, Slot};
+use types::{
+    Address, AggregateSignature, BitVector, ContributionAndProof, Epoch, EthSpec, Keypair,
+    MainnetEthSpec, PublicKeyBytes, Signature, SignedContributionAndProof, Slot,
+    SyncCommitteeContribution,
+};
 
 // Should ideally be divisible by 3.
 pub const VALIDATOR_COUNT: usize = 48;
@@ -387,3 +393,123 @@ async fn produces_missed_blocks() {
         );
     }
 }
+
+#[tokio::test]
+async fn sync_aggregation_summaries() {
+    let aggregator_index = 0;
+    let subnet_id = 1;
+    let harness = get_harness(VALIDATOR_COUNT, vec![aggregator_index as usize]);
+    let head_root = harness.head_block_root();
+    let contribution_slot = Slot::new(1);
+
+    harness.chain.validator_monitor.write().process_valid_state(
+        Epoch::new(0),
+        &harness.get_current_state(),
+        &harness.chain.spec,
+    );
+
+    // The contribution contains signatures from the first 3 members of the subcommittee.
+    let mut aggregation_bits = BitVector::new();
+    for i in 0..3 {
+        aggregation_bits.set(i, true).unwrap();
+    }
+    let contribution = SignedContributionAndProof {
+        message: ContributionAndProof {
+            aggregator_index,
+            contribution: SyncCommitteeContribution {
+                slot: contribution_slot,
+                beacon_block_root: head_root,
+                subcommittee_index: subnet_id,
+                aggregation_bits,
+                signature: AggregateSignature::empty(),
+            },
+            selection_proof: Signature::empty(),
+        },
+        signature: Signature::empty(),
+    };
+
+    // The block includes signatures from the 2nd, 3rd and 4th members of the subcommittee.
+    let mut sync_committee_bits = BitVector::new();
+    let offset = subnet_id as usize * E::sync_subcommittee_size();
+    for i in 1..4 {
+        sync_committee_bits.set(offset + i, true).unwrap();
+    }
+
+    let validator_monitor = harness.chain.validator_monitor.read();
+    validator_monitor.register_api_sync_committee_contribution(
+        timestamp_now(),
+        &contribution,
+        &[],
+        &harness.chain.slot_clock,
+    );
+    validator_monitor.register_sync_aggregate_subnets(
+        contribution_slot + 1,
+        head_root,
+        &sync_committee_bits,
+    );
+    // The contribution has already been compared against a block and is not counted again.
+    validator_monitor.register_sync_aggregate_subnets(
+        contribution_slot + 1,
+        head_root,
+        &sync_committee_bits,
+    );
+
+    let summaries = validator_monitor.get_sync_aggregation_summaries();
+    assert_eq!(summaries.len(), 1);
+    let summary = &summaries[0];
+    assert_eq!(summary.epoch, Epoch::new(0));
+    assert_eq!(summary.subnet_id, subnet_id);
+    assert_eq!(summary.contributions, 1);
+    assert_eq!(summary.contribution_participants, 3);
+    assert_eq!(summary.block_participants, 3);
+    assert_eq!(summary.block_inclusions, 2);
+}
+
+#[tokio::test]
+async fn monitor_validators_by_withdrawal_address() {
+    let address = Address::repeat_byte(0x42);
+    let validator_count = VALIDATOR_COUNT - 1;
+    let harness = BeaconChainHarness::builder(MainnetEthSpec)
+        .default_spec()
+        .keypairs(KEYPAIRS[0..validator_count].to_vec())
+        .logger(test_logger())
+        .fresh_ephemeral_store()
+        .mock_execution_layer()
+        .validator_monitor_config(ValidatorMonitorConfig {
+            withdrawal_addresses: vec![address],
+            ..<_>::default()
+        })
+        .build();
+    let spec = &harness.chain.spec;
+    let mut state = harness.get_current_state();
+
+    let mut validator_monitor = harness.chain.validator_monitor.write();
+    validator_monitor.process_valid_state(Epoch::new(0), &state, spec);
+    assert_eq!(validator_monitor.num_validators(), 0);
+
+    // A new validator which withdraws to the address is monitored as soon as it appears.
+    let mut new_validator = state.get_validator(0).unwrap().clone();
+    new_validator.pubkey = KEYPAIRS[validator_count].pk.compress();
+    new_validator.change_withdrawal_credentials(&address, spec);
+    state.validators_mut().push(new_validator).unwrap();
+    state
+        .balances_mut()
+        .push(spec.max_effective_balance)
+        .unwrap();
+    validator_monitor.process_valid_state(Epoch::new(0), &state, spec);
+    assert!(validator_monitor
+        .get_monitored_validator(validator_count as u64)
+        .is_some());
+    assert_eq!(validator_monitor.num_validators(), 1);
+
+    // An existing validator which changes its withdrawal credentials to the address is monitored
+    // once the credentials are checked in the next epoch.
+    state
+        .get_validator_mut(1)
+        .unwrap()
+        .change_withdrawal_credentials(&address, spec);
+    validator_monitor.process_valid_state(Epoch::new(1), &state, spec);
+    assert!(validator_monitor.get_monitored_validator(1).is_some());
+    assert!(validator_monitor.get_monitored_validator(2).is_none());
+    assert_eq!(validator_monitor.num_validators(), 2);
+}
@@ -38,6 +38,7 @@
 //! checks the queues to see if there are more parcels of work that can be spawned in a new worker
 //! task.
 
+use crate::queue_stats::QueueCounters;
 use crate::work_reprocessing_queue::{
     QueuedBackfillBatch, QueuedGossipBlock, ReprocessQueueMessage,
 };
@@ -71,8 +72,11 @@ use work_reprocessing_queue::{
 };
 
 mod metrics;
+mod queue_stats;
 pub mod work_reprocessing_queue;
 
+pub use queue_stats::{BeaconProcessorQueueStats, QueueStats};
+
 /// The maximum size of the channel for work events to the `BeaconProcessor`.
 ///
 /// Setting this too low will cause consensus messages to be dropped.
@@ -297,14 +301,20 @@ impl<E: EthSpec> Default for BeaconProcessorChannels<E> {
 struct FifoQueue<T> {
     queue: VecDeque<T>,
     max_length: usize,
+    counters: Arc<QueueCounters>,
 }
 
 impl<T> FifoQueue<T> {
-    /// Create a new, empty queue with the given length.
-    pub fn new(max_length: usize) -> Self {
+    /// Create a new, empty queue with the given length, reporting to `queue_stats` as `name`.
+    pub fn new(
+        name: &'static str,
+        max_length: usize,
+        queue_stats: &BeaconProcessorQueueStats,
+    ) -> Self {
         Self {
             queue: VecDeque::default(),
             max_length,
+            counters: queue_stats.register(name, max_length),
         }
     }
 
@@ -313,6 +323,7 @@ impl<T> FifoQueue<T> {
     /// Drops `item` if the queue is full.
     pub fn push(&mut self, item: T, item_desc: &str, log: &Logger) {
         if self.queue.len() == self.max_length {
+            self.counters.inc_dropped();
             error!(
                 log,
                 "Work queue is full";
@@ -322,12 +333,15 @@ impl<T> FifoQueue<T> {
             )
         } else {
             self.queue.push_back(item);
+            self.counters.set_length(self.queue.len());
         }
     }
 
     /// Remove the next item from the queue.
     pub fn pop(&mut self) -> Option<T> {
-        self.queue.pop_front()
+        let item = self.queue.pop_front();
+        self.counters.set_length(self.queue.len());
+        item
     }
 
     /// Returns the current length of the queue.
@@ -340,14 +354,20 @@ impl<T> FifoQueue<T> {
 struct LifoQueue<T> {
     queue: VecDeque<T>,
     max_length: usize,
+    counters: Arc<QueueCounters>,
 }
 
 impl<T> LifoQueue<T> {
-    /// Create a new, empty queue with the given length.
-    pub fn new(max_length: usize) -> Self {
+    /// Create a new, empty queue with the given length, reporting to `queue_stats` as `name`.
+    pub fn new(
+        name: &'static str,
+        max_length: usize,
+        queue_stats: &BeaconProcessorQueueStats,
+    ) -> Self {
         Self {
             queue: VecDeque::default(),
             max_length,
+            counters: queue_stats.register(name, max_length),
         }
     }
 
@@ -357,13 +377,17 @@ impl<T> LifoQueue<T> {
     pub fn push(&mut self, item: T) {
         if self.queue.len() == self.max_length {
             self.queue.pop_back();
+            self.counters.inc_dropped();
         }
         self.queue.push_front(item);
+        self.counters.set_length(self.queue.len());
     }
 
     /// Remove the next item from the queue.
     pub fn pop(&mut self) -> Option<T> {
-        self.queue.pop_front()
+        let item = self.queue.pop_front();
+        self.counters.set_length(self.queue.len());
+        item
     }
 
     /// Returns `true` if the queue is full.
@@ -739,6 +763,7 @@ pub struct BeaconProcessor<E: EthSpec> {
     pub executor: TaskExecutor,
     pub current_workers: usize,
     pub config: BeaconProcessorConfig,
+    pub queue_stats: BeaconProcessorQueueStats,
     pub log: Logger,
 }
 
@@ -771,62 +796,172 @@ impl<E: EthSpec> BeaconProcessor<E> {
         // Using LIFO queues for attestations since validator profits rely upon getting fresh
         // attestations into blocks. Additionally, later attestations contain more information than
         // earlier ones, so we consider them more valuable.
-        let mut aggregate_queue = LifoQueue::new(queue_lengths.aggregate_queue);
+        let mut aggregate_queue = LifoQueue::new(
+            GOSSIP_AGGREGATE,
+            queue_lengths.aggregate_queue,
+            &self.queue_stats,
+        );
         let mut aggregate_debounce = TimeLatch::default();
-        let mut attestation_queue = LifoQueue::new(queue_lengths.attestation_queue);
+        let mut attestation_queue = LifoQueue::new(
+            GOSSIP_ATTESTATION,
+            queue_lengths.attestation_queue,
+            &self.queue_stats,
+        );
         let mut attestation_debounce = TimeLatch::default();
-        let mut unknown_block_aggregate_queue =
-            LifoQueue::new(queue_lengths.unknown_block_aggregate_queue);
-        let mut unknown_block_attestation_queue =
-            LifoQueue::new(queue_lengths.unknown_block_attestation_queue);
+        let mut unknown_block_aggregate_queue = LifoQueue::new(
+            UNKNOWN_BLOCK_AGGREGATE,
+            queue_lengths.unknown_block_aggregate_queue,
+            &self.queue_stats,
+        );
+        let mut unknown_block_attestation_queue = LifoQueue::new(
+            UNKNOWN_BLOCK_ATTESTATION,
+            queue_lengths.unknown_block_attestation_queue,
+            &self.queue_stats,
+        );
 
-        let mut sync_message_queue = LifoQueue::new(queue_lengths.sync_message_queue);
-        let mut sync_contribution_queue = LifoQueue::new(queue_lengths.sync_contribution_queue);
+        let mut sync_message_queue = LifoQueue::new(
+            GOSSIP_SYNC_SIGNATURE,
+            queue_lengths.sync_message_queue,
+            &self.queue_stats,
+        );
+        let mut sync_contribution_queue = LifoQueue::new(
+            GOSSIP_SYNC_CONTRIBUTION,
+            queue_lengths.sync_contribution_queue,
+            &self.queue_stats,
+        );
 
         // Using a FIFO queue for voluntary exits since it prevents exit censoring. I don't have
         // a strong feeling about queue type for exits.
-        let mut gossip_voluntary_exit_queue =
-            FifoQueue::new(queue_lengths.gossip_voluntary_exit_queue);
+        let mut gossip_voluntary_exit_queue = FifoQueue::new(
+            GOSSIP_VOLUNTARY_EXIT,
+            queue_lengths.gossip_voluntary_exit_queue,
+            &self.queue_stats,
+        );
 
         // Using a FIFO queue for slashing to prevent people from flushing their slashings from the
         // queues with lots of junk messages.
-        let mut gossip_proposer_slashing_queue =
-            FifoQueue::new(queue_lengths.gossip_proposer_slashing_queue);
-        let mut gossip_attester_slashing_queue =
-            FifoQueue::new(queue_lengths.gossip_attester_slashing_queue);
+        let mut gossip_proposer_slashing_queue = FifoQueue::new(
+            GOSSIP_PROPOSER_SLASHING,
+            queue_lengths.gossip_proposer_slashing_queue,
+            &self.queue_stats,
+        );
+        let mut gossip_attester_slashing_queue = FifoQueue::new(
+            GOSSIP_ATTESTER_SLASHING,
+            queue_lengths.gossip_attester_slashing_queue,
+            &self.queue_stats,
+        );
 
         // Using a FIFO queue for light client updates to maintain sequence order.
-        let mut finality_update_queue = FifoQueue::new(queue_lengths.finality_update_queue);
-        let mut optimistic_update_queue = FifoQueue::new(queue_lengths.optimistic_update_queue);
-        let mut unknown_light_client_update_queue =
-            FifoQueue::new(queue_lengths.unknown_light_client_update_queue);
+        let mut finality_update_queue = FifoQueue::new(
+            GOSSIP_LIGHT_CLIENT_FINALITY_UPDATE,
+            queue_lengths.finality_update_queue,
+            &self.queue_stats,
+        );
+        let mut optimistic_update_queue = FifoQueue::new(
+            GOSSIP_LIGHT_CLIENT_OPTIMISTIC_UPDATE,
+            queue_lengths.optimistic_update_queue,
+            &self.queue_stats,
+        );
+        let mut unknown_light_client_update_queue = FifoQueue::new(
+            UNKNOWN_LIGHT_CLIENT_UPDATE,
+            queue_lengths.unknown_light_client_update_queue,
+            &self.queue_stats,
+        );
 
         // Using a FIFO queue since blocks need to be imported sequentially.
-        let mut rpc_block_queue = FifoQueue::new(queue_lengths.rpc_block_queue);
-        let mut rpc_blob_queue = FifoQueue::new(queue_lengths.rpc_blob_queue);
-        let mut chain_segment_queue = FifoQueue::new(queue_lengths.chain_segment_queue);
-        let mut backfill_chain_segment = FifoQueue::new(queue_lengths.backfill_chain_segment);
-        let mut gossip_block_queue = FifoQueue::new(queue_lengths.gossip_block_queue);
-        let mut gossip_blob_queue = FifoQueue::new(queue_lengths.gossip_blob_queue);
-        let mut gossip_data_column_queue = FifoQueue::new(queue_lengths.gossip_data_column_queue);
-        let mut delayed_block_queue = FifoQueue::new(queue_lengths.delayed_block_queue);
-
-        let mut status_queue = FifoQueue::new(queue_lengths.status_queue);
-        let mut bbrange_queue = FifoQueue::new(queue_lengths.bbrange_queue);
-        let mut bbroots_queue = FifoQueue::new(queue_lengths.bbroots_queue);
-        let mut blbroots_queue = FifoQueue::new(queue_lengths.blbroots_queue);
-        let mut blbrange_queue = FifoQueue::new(queue_lengths.blbrange_queue);
-
-        let mut gossip_bls_to_execution_change_queue =
-            FifoQueue::new(queue_lengths.gossip_bls_to_execution_change_queue);
-
-        let mut lc_bootstrap_queue = FifoQueue::new(queue_lengths.lc_bootstrap_queue);
-        let mut lc_optimistic_update_queue =
-            FifoQueue::new(queue_lengths.lc_optimistic_update_queue);
-        let mut lc_finality_update_queue = FifoQueue::new(queue_lengths.lc_finality_update_queue);
-
-        let mut api_request_p0_queue = FifoQueue::new(queue_lengths.api_request_p0_queue);
-        let mut api_request_p1_queue = FifoQueue::new(queue_lengths.api_request_p1_queue);
+        let mut rpc_block_queue =
+            FifoQueue::new(RPC_BLOCK, queue_lengths.rpc_block_queue, &self.queue_stats);
+        let mut rpc_blob_queue =
+            FifoQueue::new(RPC_BLOBS, queue_lengths.rpc_blob_queue, &self.queue_stats);
+        let mut chain_segment_queue = FifoQueue::new(
+            CHAIN_SEGMENT,
+            queue_lengths.chain_segment_queue,
+            &self.queue_stats,
+        );
+        let mut backfill_chain_segment = FifoQueue::new(
+            CHAIN_SEGMENT_BACKFILL,
+            queue_lengths.backfill_chain_segment,
+            &self.queue_stats,
+        );
+        let mut gossip_block_queue = FifoQueue::new(
+            GOSSIP_BLOCK,
+            queue_lengths.gossip_block_queue,
+            &self.queue_stats,
+        );
+        let mut gossip_blob_queue = FifoQueue::new(
+            GOSSIP_BLOBS_SIDECAR,
+            queue_lengths.gossip_blob_queue,
+            &self.queue_stats,
+        );
+        let mut gossip_data_column_queue = FifoQueue::new(
+            GOSSIP_BLOBS_COLUMN_SIDECAR,
+            queue_lengths.gossip_data_column_queue,
+            &self.queue_stats,
+        );
+        let mut delayed_block_queue = FifoQueue::new(
+            DELAYED_IMPORT_BLOCK,
+            queue_lengths.delayed_block_queue,
+            &self.queue_stats,
+        );
+
+        let mut status_queue = FifoQueue::new(
+            STATUS_PROCESSING,
+            queue_lengths.status_queue,
+            &self.queue_stats,
+        );
+        let mut bbrange_queue = FifoQueue::new(
+            BLOCKS_BY_RANGE_REQUEST,
+            queue_lengths.bbrange_queue,
+            &self.queue_stats,
+        );
+        let mut bbroots_queue = FifoQueue::new(
+            BLOCKS_BY_ROOTS_REQUEST,
+            queue_lengths.bbroots_queue,
+            &self.queue_stats,
+        );
+        let mut blbroots_queue = FifoQueue::new(
+            BLOBS_BY_ROOTS_REQUEST,
+            queue_lengths.blbroots_queue,
+            &self.queue_stats,
+        );
+        let mut blbrange_queue = FifoQueue::new(
+            BLOBS_BY_RANGE_REQUEST,
+            queue_lengths.blbrange_queue,
+            &self.queue_stats,
+        );
+
+        let mut gossip_bls_to_execution_change_queue = FifoQueue::new(
+            GOSSIP_BLS_TO_EXECUTION_CHANGE,
+            queue_lengths.gossip_bls_to_execution_change_queue,
+            &self.queue_stats,
+        );
+
+        let mut lc_bootstrap_queue = FifoQueue::new(
+            LIGHT_CLIENT_BOOTSTRAP_REQUEST,
+            queue_lengths.lc_bootstrap_queue,
+            &self.queue_stats,
+        );
+        let mut lc_optimistic_update_queue = FifoQueue::new(
+            LIGHT_CLIENT_OPTIMISTIC_UPDATE_REQUEST,
+            queue_lengths.lc_optimistic_update_queue,
+            &self.queue_stats,
+        );
+        let mut lc_finality_update_queue = FifoQueue::new(
+            LIGHT_CLIENT_FINALITY_UPDATE_REQUEST,
+            queue_lengths.lc_finality_update_queue,
+            &self.queue_stats,
+        );
+
+        let mut api_request_p0_queue = FifoQueue::new(
+            API_REQUEST_P0,
+            queue_lengths.api_request_p0_queue,
+            &self.queue_stats,
+        );
+        let mut api_request_p1_queue = FifoQueue::new(
+            API_REQUEST_P1,
+            queue_lengths.api_request_p1_queue,
+            &self.queue_stats,
+        );
 
         // Channels for sending work to the re-process scheduler (`work_reprocessing_tx`) and to
         // receive them back once they are ready (`ready_work_rx`).
@@ -0,0 +1,136 @@
+//! Provides visibility into the `BeaconProcessor` work queues from outside the manager task.
+//!
+//! The manager owns the queues, so each queue records its depth and the number of items it has
+//! dropped into a set of shared atomic counters. These counters are cheap to update on the hot
+//! path and can be read (or reset) at any time, e.g. by the HTTP API.
+use parking_lot::RwLock;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// A handle to the statistics of every work queue.
+///
+/// Cloning the handle is cheap and all clones share the same statistics.
+#[derive(Clone, Default)]
+pub struct BeaconProcessorQueueStats {
+    queues: Arc<RwLock<Vec<(&'static str, Arc<QueueCounters>)>>>,
+}
+
+/// A point-in-time snapshot of a single work queue.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueueStats {
+    pub name: &'static str,
+    /// The number of items currently in the queue.
+    pub length: usize,
+    /// The capacity of the queue.
+    pub max_length: usize,
+    /// The greatest number of items in the queue since it was created or last reset.
+    pub max_depth: usize,
+    /// The number of items dropped due to the queue being full since it was created or last reset.
+    pub dropped: u64,
+}
+
+impl BeaconProcessorQueueStats {
+    /// Registers a queue called `name`, returning the counters it should update.
+    ///
+    /// Registering a queue with an existing name replaces its counters.
+    pub(crate) fn register(&self, name: &'static str, max_length: usize) -> Arc<QueueCounters> {
+        let counters = Arc::new(QueueCounters::new(max_length));
+        let mut queues = self.queues.write();
+        match queues.iter_mut().find(|(existing, _)| *existing == name) {
+            Some((_, existing)) => *existing = counters.clone(),
+            None => queues.push((name, counters.clone())),
+        }
+        counters
+    }
+
+    /// Returns a snapshot of every registered queue, in the order in which they were registered.
+    pub fn queues(&self) -> Vec<QueueStats> {
+        self.queues
+            .read()
+            .iter()
+            .map(|(name, counters)| QueueStats {
+                name: *name,
+                length: counters.length.load(Ordering::Relaxed),
+                max_length: counters.max_length,
+                max_depth: counters.max_depth.load(Ordering::Relaxed),
+                dropped: counters.dropped.load(Ordering::Relaxed),
+            })
+            .collect()
+    }
+
+    /// Resets the maximum depth and drop count of every queue.
+    ///
+    /// The maximum depth is reset to the current length of each queue.
+    pub fn reset(&self) {
+        for (_, counters) in self.queues.read().iter() {
+            counters
+                .max_depth
+                .store(counters.length.load(Ordering::Relaxed), Ordering::Relaxed);
+            counters.dropped.store(0, Ordering::Relaxed);
+        }
+    }
+}
+
+/// The counters updated by a single work queue.
+pub(crate) struct QueueCounters {
+    max_length: usize,
+    length: AtomicUsize,
+    max_depth: AtomicUsize,
+    dropped: AtomicU64,
+}
+
+impl QueueCounters {
+    fn new(max_length: usize) -> Self {
+        Self {
+            max_length,
+            length: AtomicUsize::new(0),
+            max_depth: AtomicUsize::new(0),
+            dropped: AtomicU64::new(0),
+        }
+    }
+
+    /// Records that the queue now contains `length` items.
+    pub(crate) fn set_length(&self, length: usize) {
+        self.length.store(length, Ordering::Relaxed);
+        self.max_depth.fetch_max(length, Ordering::Relaxed);
+    }
+
+    /// Records that the queue dropped an item.
+    pub(crate) fn inc_dropped(&self) {
+        self.dropped.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reset_clears_drops_and_max_depth() {
+        let stats = BeaconProcessorQueueStats::default();
+        let counters = stats.register("gossip_block", 4);
+
+        counters.set_length(3);
+        counters.set_length(1);
+        counters.inc_dropped();
+
+        let expected = QueueStats {
+            name: "gossip_block",
+            length: 1,
+            max_length: 4,
+            max_depth: 3,
+            dropped: 1,
+        };
+        assert_eq!(stats.queues(), vec![expected.clone()]);
+
+        stats.reset();
+        assert_eq!(
+            stats.queues(),
+            vec![QueueStats {
+                max_depth: 1,
+                dropped: 0,
+                ..expected
+            }]
+        );
+    }
+}
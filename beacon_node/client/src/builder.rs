@@ -13,13 +13,17 @@ use beacon_chain::schema_change::migrate_schema;
 use beacon_chain::LightClientProducerEvent;
 use beacon_chain::{
     builder::{BeaconChainBuilder, Witness},
+    clock_sanity::spawn_ntp_clock_check,
+    compaction_timer::spawn_compaction_timer,
+    era_import::spawn_era_import,
     eth1_chain::{CachingEth1Backend, Eth1Chain},
+    remote_slasher::RemoteSlasherQueue,
     slot_clock::{SlotClock, SystemTimeSlotClock},
     state_advance_timer::spawn_state_advance_timer,
     store::{HotColdDB, ItemStore, LevelDB, StoreConfig},
     BeaconChain, BeaconChainTypes, Eth1ChainBackend, MigratorConfig, ServerSentEventHandler,
 };
-use beacon_processor::{BeaconProcessor, BeaconProcessorChannels};
+use beacon_processor::{BeaconProcessor, BeaconProcessorChannels, BeaconProcessorQueueStats};
 use beacon_processor::{BeaconProcessorConfig, BeaconProcessorQueueLengths};
 use environment::RuntimeContext;
 use eth1::{Config as Eth1Config, Service as Eth1Service};
@@ -77,6 +81,7 @@ pub struct ClientBuilder<T: BeaconChainTypes> {
     store: Option<Arc<HotColdDB<T::EthSpec, T::HotStore, T::ColdStore>>>,
     runtime_context: Option<RuntimeContext<T::EthSpec>>,
     chain_spec: Option<ChainSpec>,
+    network_spec: Option<ChainSpec>,
     beacon_chain_builder: Option<BeaconChainBuilder<T>>,
     beacon_chain: Option<Arc<BeaconChain<T>>>,
     eth1_service: Option<Eth1Service>,
@@ -112,6 +117,7 @@ where
             store: None,
             runtime_context: None,
             chain_spec: None,
+            network_spec: None,
             beacon_chain_builder: None,
             beacon_chain: None,
             eth1_service: None,
@@ -142,6 +148,13 @@ where
         self
     }
 
+    /// Specifies the `ChainSpec` of the p2p network, if it differs from the `chain_spec` (i.e.
+    /// when running a shadow fork).
+    pub fn network_spec(mut self, spec: ChainSpec) -> Self {
+        self.network_spec = Some(spec);
+        self
+    }
+
     pub fn beacon_processor(mut self, config: BeaconProcessorConfig) -> Self {
         self.beacon_processor_channels = Some(BeaconProcessorChannels::new(&config));
         self.beacon_processor_config = Some(config);
@@ -209,8 +222,16 @@ where
             .execution_layer(execution_layer)
             .validator_monitor_config(config.validator_monitor.clone());
 
+        let builder = if let Some(network_spec) = self.network_spec.clone() {
+            builder.network_spec(network_spec)
+        } else {
+            builder
+        };
+
         let builder = if let Some(slasher) = self.slasher.clone() {
             builder.slasher(slasher)
+        } else if config.remote_slasher {
+            builder.remote_slasher(Arc::new(RemoteSlasherQueue::default()))
         } else {
             builder
         };
@@ -563,9 +584,11 @@ where
                         network_globals: None,
                         beacon_processor_send: None,
                         beacon_processor_reprocess_send: None,
+                        beacon_processor_queue_stats: None,
                         eth1_service: Some(genesis_service.eth1_service.clone()),
                         log: context.log().clone(),
                         sse_logging_components: runtime_context.sse_logging_components.clone(),
+                        module_levels: runtime_context.module_levels.clone(),
                     });
 
                     // Discard the error from the oneshot.
@@ -800,6 +823,7 @@ where
             .take()
             .ok_or("build requires a beacon_processor_config")?;
         let log = runtime_context.log().clone();
+        let beacon_processor_queue_stats = BeaconProcessorQueueStats::default();
 
         let http_api_listen_addr = if self.http_api_config.enabled {
             let ctx = Arc::new(http_api::Context {
@@ -812,7 +836,9 @@ where
                 beacon_processor_reprocess_send: Some(
                     beacon_processor_channels.work_reprocessing_tx.clone(),
                 ),
+                beacon_processor_queue_stats: Some(beacon_processor_queue_stats.clone()),
                 sse_logging_components: runtime_context.sse_logging_components.clone(),
+                module_levels: runtime_context.module_levels.clone(),
                 log: log.clone(),
             });
 
@@ -875,6 +901,7 @@ where
                     executor: beacon_processor_context.executor.clone(),
                     current_workers: 0,
                     config: beacon_processor_config,
+                    queue_stats: beacon_processor_queue_stats,
                     log: beacon_processor_context.log().clone(),
                 }
                 .spawn_manager(
@@ -903,6 +930,36 @@ where
                 state_advance_log,
             );
 
+            let compaction_context = runtime_context.service_context("compaction".into());
+            let compaction_log = compaction_context.log().clone();
+            spawn_compaction_timer(
+                compaction_context.executor,
+                beacon_chain.clone(),
+                compaction_log,
+            );
+
+            if let Some(era_dir) = beacon_chain.config.import_era_dir.clone() {
+                let era_import_context = runtime_context.service_context("era_import".into());
+                let era_import_log = era_import_context.log().clone();
+                spawn_era_import(
+                    era_import_context.executor,
+                    beacon_chain.clone(),
+                    era_dir,
+                    era_import_log,
+                );
+            }
+
+            if let Some(ntp_server) = beacon_chain.config.ntp_server.clone() {
+                let ntp_context = runtime_context.service_context("ntp_clock_check".into());
+                let ntp_log = ntp_context.log().clone();
+                spawn_ntp_clock_check(
+                    ntp_context.executor,
+                    beacon_chain.clone(),
+                    ntp_server,
+                    ntp_log,
+                );
+            }
+
             if let Some(execution_layer) = beacon_chain.execution_layer.as_ref() {
                 // Only send a head update *after* genesis.
                 if let Ok(current_slot) = beacon_chain.slot() {
@@ -943,6 +1000,9 @@ where
                     // Spawn a routine that tracks the status of the execution engines.
                     execution_layer.spawn_watchdog_routine(beacon_chain.slot_clock.clone());
 
+                    // Spawn a routine that reloads the JWT secret when it changes.
+                    execution_layer.spawn_jwt_secret_reload_routine();
+
                     // Spawn a routine that removes expired proposer preparations.
                     execution_layer.spawn_clean_proposer_caches_routine::<TSlotClock>(
                         beacon_chain.slot_clock.clone(),
@@ -80,11 +80,16 @@ pub struct Config {
     pub http_metrics: http_metrics::Config,
     pub monitoring_api: Option<monitoring_api::Config>,
     pub slasher: Option<slasher::Config>,
+    /// Queue attestations and blocks for a slasher running in another process.
+    pub remote_slasher: bool,
     pub logger_config: LoggerConfig,
     pub beacon_processor: BeaconProcessorConfig,
     pub genesis_state_url: Option<String>,
     pub genesis_state_url_timeout: Duration,
     pub allow_insecure_genesis_sync: bool,
+    /// A config whose fork schedule and execution settings override the network's for block
+    /// processing, whilst the network's config is still used on the p2p network.
+    pub shadow_fork: Option<types::Config>,
 }
 
 impl Default for Config {
@@ -109,6 +114,7 @@ impl Default for Config {
             http_metrics: <_>::default(),
             monitoring_api: None,
             slasher: None,
+            remote_slasher: false,
             validator_monitor: <_>::default(),
             logger_config: LoggerConfig::default(),
             beacon_processor: <_>::default(),
@@ -116,6 +122,7 @@ impl Default for Config {
             // This default value should always be overwritten by the CLI default value.
             genesis_state_url_timeout: Duration::from_secs(60),
             allow_insecure_genesis_sync: false,
+            shadow_fork: None,
         }
     }
 }
@@ -1,4 +1,4 @@
-use crate::service::endpoint_from_config;
+use crate::service::{endpoint_from_config, fallback_endpoints_from_config};
 use crate::Config;
 use crate::{
     block_cache::{BlockCache, Eth1Block},
@@ -47,6 +47,7 @@ pub struct Inner {
     pub block_cache: RwLock<BlockCache>,
     pub deposit_cache: RwLock<DepositUpdater>,
     pub endpoint: HttpJsonRpc,
+    pub fallback_endpoints: Vec<HttpJsonRpc>,
     // this gets set to Some(Eth1Data) when the deposit finalization conditions are met
     pub to_finalize: RwLock<Option<Eth1Data>>,
     pub config: RwLock<Config>,
@@ -121,6 +122,8 @@ impl SszEth1Cache {
             }),
             endpoint: endpoint_from_config(&config)
                 .map_err(|e| format!("Failed to create endpoint: {:?}", e))?,
+            fallback_endpoints: fallback_endpoints_from_config(&config)
+                .map_err(|e| format!("Failed to create fallback endpoints: {:?}", e))?,
             to_finalize: RwLock::new(None),
             // Set the remote head_block zero when creating a new instance. We only care about
             // present and future eth1 nodes.
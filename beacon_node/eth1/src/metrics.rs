@@ -18,6 +18,16 @@ lazy_static! {
         try_create_int_gauge("eth1_deposit_cache_len", "Number of deposits in the eth1 cache");
     pub static ref HIGHEST_PROCESSED_DEPOSIT_BLOCK: Result<IntGauge> =
         try_create_int_gauge("eth1_highest_processed_deposit_block", "Number of the last block checked for deposits");
+    pub static ref DEPOSIT_LOG_REQUESTS: Result<IntCounterVec> = try_create_int_counter_vec(
+        "eth1_deposit_log_requests_total",
+        "Count of deposit log requests sent to each eth1 endpoint",
+        &["endpoint"]
+    );
+    pub static ref DEPOSIT_LOG_REQUEST_ERRORS: Result<IntCounterVec> = try_create_int_counter_vec(
+        "eth1_deposit_log_request_errors_total",
+        "Count of failed deposit log requests for each eth1 endpoint",
+        &["endpoint"]
+    );
 
     /*
      * Eth1 rpc connection
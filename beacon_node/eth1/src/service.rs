@@ -7,7 +7,7 @@ use crate::{
 use execution_layer::auth::Auth;
 use execution_layer::http::{
     deposit_methods::{BlockQuery, Eth1Id},
-    HttpJsonRpc,
+    HttpJsonRpc, Log,
 };
 use futures::future::TryFutureExt;
 use parking_lot::{RwLock, RwLockReadGuard};
@@ -264,6 +264,10 @@ impl Eth1Endpoint {
 pub struct Config {
     /// An Eth1 node (e.g., Geth) running a HTTP JSON-RPC endpoint.
     pub endpoint: Eth1Endpoint,
+    /// Non-authenticated HTTP JSON-RPC endpoints which are used, in order, to download deposit
+    /// logs when `endpoint` fails to provide them (e.g., because it has pruned historical logs).
+    #[serde(default)]
+    pub fallback_endpoints: Vec<SensitiveUrl>,
     /// The address the `BlockCache` and `DepositCache` should assume is the canonical deposit contract.
     pub deposit_contract_address: String,
     /// The eth1 chain id where the deposit contract is deployed (Holesky/Mainnet).
@@ -345,6 +349,7 @@ impl Default for Config {
                 SensitiveUrl::parse(DEFAULT_ETH1_ENDPOINT)
                     .expect("The default Eth1 endpoint must always be a valid URL."),
             ),
+            fallback_endpoints: vec![],
             deposit_contract_address: "0x0000000000000000000000000000000000000000".into(),
             chain_id: DEFAULT_CHAIN_ID,
             deposit_contract_deploy_block: 1,
@@ -383,6 +388,17 @@ pub fn endpoint_from_config(config: &Config) -> Result<HttpJsonRpc, String> {
     }
 }
 
+pub fn fallback_endpoints_from_config(config: &Config) -> Result<Vec<HttpJsonRpc>, String> {
+    config
+        .fallback_endpoints
+        .iter()
+        .map(|endpoint| {
+            HttpJsonRpc::new(endpoint.clone(), Some(config.execution_timeout_multiplier))
+                .map_err(|e| format!("Failed to create eth1 fallback json rpc client: {:?}", e))
+        })
+        .collect()
+}
+
 /// Provides a set of Eth1 caches and async functions to update them.
 ///
 /// Stores the following caches:
@@ -405,6 +421,7 @@ impl Service {
                     config.deposit_contract_deploy_block,
                 )),
                 endpoint: endpoint_from_config(&config)?,
+                fallback_endpoints: fallback_endpoints_from_config(&config)?,
                 to_finalize: RwLock::new(None),
                 remote_head_block: RwLock::new(None),
                 config: RwLock::new(config),
@@ -435,6 +452,8 @@ impl Service {
                 deposit_cache: RwLock::new(deposit_cache),
                 endpoint: endpoint_from_config(&config)
                     .map_err(Error::FailedToInitializeFromSnapshot)?,
+                fallback_endpoints: fallback_endpoints_from_config(&config)
+                    .map_err(Error::FailedToInitializeFromSnapshot)?,
                 to_finalize: RwLock::new(None),
                 remote_head_block: RwLock::new(None),
                 config: RwLock::new(config),
@@ -620,6 +639,20 @@ impl Service {
         let chain_id = self.config().chain_id.clone();
         let node_far_behind_seconds = self.inner.config.read().node_far_behind_seconds;
 
+        // Pick up a rotated JWT secret without requiring a restart.
+        if let Some(auth) = client.auth() {
+            match auth.reload_if_modified() {
+                Ok(true) => info!(log, "Reloaded JWT secret for eth1 endpoint"),
+                Ok(false) => {}
+                Err(e) => warn!(
+                    log,
+                    "Failed to reload JWT secret for eth1 endpoint";
+                    "info" => "continuing to use the previous secret",
+                    "error" => ?e,
+                ),
+            }
+        }
+
         match endpoint_state(client, &chain_id, &log).await {
             Ok(()) => crate::metrics::set_gauge(&metrics::ETH1_CONNECTED, 1),
             Err(e) => {
@@ -894,15 +927,9 @@ impl Service {
             /*
              * Step 1. Download logs.
              */
-            let block_range_ref = &block_range;
-            let logs = client
-                .get_deposit_logs_in_range(
-                    deposit_contract_address_ref,
-                    block_range_ref.clone(),
-                    Duration::from_millis(GET_DEPOSIT_LOG_TIMEOUT_MILLIS),
-                )
-                .await
-                .map_err(Error::GetDepositLogsFailed)?;
+            let logs = self
+                .get_deposit_logs_in_range(deposit_contract_address_ref, block_range.clone())
+                .await?;
 
             /*
              * Step 2. Import logs to cache.
@@ -976,6 +1003,78 @@ impl Service {
         Ok(DepositCacheUpdateOutcome { logs_imported })
     }
 
+    /// Downloads the deposit logs in `block_range`, trying each of the fallback endpoints in turn
+    /// if the primary endpoint fails.
+    ///
+    /// A fallback endpoint is only used if it is on the configured chain, since an endpoint on
+    /// another chain would return no logs (or the wrong ones) rather than an error.
+    ///
+    /// Returns the error from the last endpoint tried if every endpoint fails.
+    async fn get_deposit_logs_in_range(
+        &self,
+        deposit_contract_address: &str,
+        block_range: Range<u64>,
+    ) -> Result<Vec<Log>, Error> {
+        let chain_id = self.config().chain_id.clone();
+        let mut last_error = None;
+
+        for (i, endpoint) in std::iter::once(self.client())
+            .chain(&self.inner.fallback_endpoints)
+            .enumerate()
+        {
+            let endpoint_label = endpoint.url.to_string();
+            metrics::inc_counter_vec(&metrics::DEPOSIT_LOG_REQUESTS, &[&endpoint_label]);
+
+            let logs = async {
+                // The chain ID of the primary endpoint is checked at the start of each update.
+                if i > 0 {
+                    endpoint_state(endpoint, &chain_id, &self.log)
+                        .await
+                        .map_err(|e| format!("Invalid endpoint state: {:?}", e))?;
+                }
+                endpoint
+                    .get_deposit_logs_in_range(
+                        deposit_contract_address,
+                        block_range.clone(),
+                        Duration::from_millis(GET_DEPOSIT_LOG_TIMEOUT_MILLIS),
+                    )
+                    .await
+            };
+
+            match logs.await {
+                Ok(logs) => {
+                    if last_error.is_some() {
+                        debug!(
+                            self.log,
+                            "Downloaded deposit logs from fallback endpoint";
+                            "endpoint" => %endpoint,
+                            "block_range" => ?block_range,
+                        );
+                    }
+                    return Ok(logs);
+                }
+                Err(e) => {
+                    metrics::inc_counter_vec(
+                        &metrics::DEPOSIT_LOG_REQUEST_ERRORS,
+                        &[&endpoint_label],
+                    );
+                    if !self.inner.fallback_endpoints.is_empty() {
+                        warn!(
+                            self.log,
+                            "Failed to download deposit logs";
+                            "endpoint" => %endpoint,
+                            "block_range" => ?block_range,
+                            "error" => &e,
+                        );
+                    }
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        Err(Error::GetDepositLogsFailed(last_error.unwrap_or_default()))
+    }
+
     /// Contacts the remote eth1 node and attempts to import all blocks up to the configured
     /// follow-distance block.
     ///
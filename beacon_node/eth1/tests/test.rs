@@ -577,6 +577,70 @@ mod deposit_tree {
         }
         .await;
     }
+
+    #[tokio::test]
+    async fn fallback_endpoint() {
+        async {
+            let log = null_logger();
+
+            let n = 4;
+
+            let eth1 = new_anvil_instance()
+                .await
+                .expect("should start eth1 environment");
+            let deposit_contract = &eth1.deposit_contract;
+            let anvil_client = eth1.json_rpc_client();
+
+            // An endpoint on another chain, which has no deposit logs.
+            let wrong_chain_eth1 = AnvilEth1Instance::new(u64::from(DEFAULT_CHAIN_ID) + 1)
+                .await
+                .expect("should start eth1 environment");
+
+            let start_block = get_block_number(&anvil_client).await;
+
+            // The primary endpoint is unreachable, so all logs must be downloaded from the
+            // fallback endpoint which is on the configured chain.
+            let service = Service::new(
+                Config {
+                    endpoint: Eth1Endpoint::NoAuth(
+                        SensitiveUrl::parse("http://127.0.0.1:1").unwrap(),
+                    ),
+                    fallback_endpoints: vec![
+                        SensitiveUrl::parse(wrong_chain_eth1.endpoint().as_str()).unwrap(),
+                        SensitiveUrl::parse(eth1.endpoint().as_str()).unwrap(),
+                    ],
+                    deposit_contract_address: deposit_contract.address(),
+                    deposit_contract_deploy_block: start_block,
+                    follow_distance: 0,
+                    ..Config::default()
+                },
+                log,
+                MainnetEthSpec::default_spec(),
+            )
+            .unwrap();
+
+            let deposits: Vec<_> = (0..n).map(|_| random_deposit_data()).collect();
+            for deposit in &deposits {
+                deposit_contract
+                    .deposit(deposit.clone())
+                    .await
+                    .expect("should perform a deposit");
+            }
+
+            let end_block = get_block_number(&anvil_client).await;
+            service
+                .update_deposit_cache(Some(Some(start_block..=end_block)))
+                .await
+                .expect("should download logs from the fallback endpoint");
+
+            assert_eq!(
+                service.deposit_cache_len(),
+                n,
+                "should import all deposits from the fallback endpoint"
+            );
+        }
+        .await;
+    }
 }
 
 /// Tests for the base HTTP requests and response handlers.
@@ -20,7 +20,7 @@ use reqwest::StatusCode;
 use serde::{Deserialize, Serialize};
 use strum::IntoStaticStr;
 use superstruct::superstruct;
-use types::execution_payload::{DepositRequests, WithdrawalRequests};
+use types::execution_payload::{ConsolidationRequests, DepositRequests, WithdrawalRequests};
 pub use types::{
     Address, BeaconBlockRef, EthSpec, ExecutionBlockHash, ExecutionPayload, ExecutionPayloadHeader,
     ExecutionPayloadRef, FixedVector, ForkName, Hash256, Transactions, Uint256, VariableList,
@@ -36,13 +36,14 @@ pub mod auth;
 pub mod http;
 pub mod json_structures;
 mod new_payload_request;
+pub mod recorder;
 
 pub use new_payload_request::{
     NewPayloadRequest, NewPayloadRequestBellatrix, NewPayloadRequestCapella,
     NewPayloadRequestDeneb, NewPayloadRequestElectra,
 };
 
-use self::json_structures::{JsonDepositRequest, JsonWithdrawalRequest};
+use self::json_structures::{JsonConsolidationRequest, JsonDepositRequest, JsonWithdrawalRequest};
 
 pub const LATEST_TAG: &str = "latest";
 
@@ -68,6 +69,7 @@ pub enum Error {
     DeserializeWithdrawals(ssz_types::Error),
     DeserializeDepositRequests(ssz_types::Error),
     DeserializeWithdrawalRequests(ssz_types::Error),
+    DeserializeConsolidationRequests(ssz_types::Error),
     BuilderApi(builder_client::Error),
     IncorrectStateVariant,
     RequiredMethodUnsupported(&'static str),
@@ -206,6 +208,8 @@ pub struct ExecutionBlockWithTransactions<E: EthSpec> {
     pub deposit_requests: Vec<JsonDepositRequest>,
     #[superstruct(only(Electra))]
     pub withdrawal_requests: Vec<JsonWithdrawalRequest>,
+    #[superstruct(only(Electra))]
+    pub consolidation_requests: Vec<JsonConsolidationRequest>,
 }
 
 impl<E: EthSpec> TryFrom<ExecutionPayload<E>> for ExecutionBlockWithTransactions<E> {
@@ -323,6 +327,11 @@ impl<E: EthSpec> TryFrom<ExecutionPayload<E>> for ExecutionBlockWithTransactions
                         .into_iter()
                         .map(|withdrawal| withdrawal.into())
                         .collect(),
+                    consolidation_requests: block
+                        .consolidation_requests
+                        .into_iter()
+                        .map(|consolidation| consolidation.into())
+                        .collect(),
                 })
             }
         };
@@ -547,6 +556,7 @@ pub struct ExecutionPayloadBodyV1<E: EthSpec> {
     pub withdrawals: Option<Withdrawals<E>>,
     pub deposit_requests: Option<DepositRequests<E>>,
     pub withdrawal_requests: Option<WithdrawalRequests<E>>,
+    pub consolidation_requests: Option<ConsolidationRequests<E>>,
 }
 
 impl<E: EthSpec> ExecutionPayloadBodyV1<E> {
@@ -637,10 +647,17 @@ impl<E: EthSpec> ExecutionPayloadBodyV1<E> {
                 let withdrawals_exist = self.withdrawals.is_some();
                 let deposit_requests_exist = self.deposit_requests.is_some();
                 let withdrawal_requests_exist = self.withdrawal_requests.is_some();
-                if let (Some(withdrawals), Some(deposit_requests), Some(withdrawal_requests)) = (
+                let consolidation_requests_exist = self.consolidation_requests.is_some();
+                if let (
+                    Some(withdrawals),
+                    Some(deposit_requests),
+                    Some(withdrawal_requests),
+                    Some(consolidation_requests),
+                ) = (
                     self.withdrawals,
                     self.deposit_requests,
                     self.withdrawal_requests,
+                    self.consolidation_requests,
                 ) {
                     Ok(ExecutionPayload::Electra(ExecutionPayloadElectra {
                         parent_hash: header.parent_hash,
@@ -662,12 +679,13 @@ impl<E: EthSpec> ExecutionPayloadBodyV1<E> {
                         excess_blob_gas: header.excess_blob_gas,
                         deposit_requests,
                         withdrawal_requests,
+                        consolidation_requests,
                     }))
                 } else {
                     Err(format!(
-                        "block {} is post-electra but payload body doesn't have withdrawals/deposit_requests/withdrawal_requests \
-                        withdrawals: {}, deposit_requests: {}, withdrawal_requests: {}",
-                        header.block_hash, withdrawals_exist, deposit_requests_exist, withdrawal_requests_exist
+                        "block {} is post-electra but payload body doesn't have withdrawals/deposit_requests/withdrawal_requests/consolidation_requests \
+                        withdrawals: {}, deposit_requests: {}, withdrawal_requests: {}, consolidation_requests: {}",
+                        header.block_hash, withdrawals_exist, deposit_requests_exist, withdrawal_requests_exist, consolidation_requests_exist
                     ))
                 }
             }
@@ -1,6 +1,8 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 
 use jsonwebtoken::{encode, get_current_timestamp, Algorithm, EncodingKey, Header};
+use parking_lot::RwLock;
 use rand::Rng;
 use serde::{Deserialize, Serialize};
 use zeroize::Zeroize;
@@ -58,6 +60,15 @@ impl JwtKey {
     pub fn hex_string(&self) -> String {
         hex::encode(self.0)
     }
+
+    /// Read a hex encoded secret from the file at `path`.
+    pub fn from_file(path: &Path) -> Result<Self, String> {
+        let s = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read JWT secret file. Error: {:?}", e))?;
+        let secret_bytes = hex::decode(strip_prefix(s.trim_end()))
+            .map_err(|e| format!("Invalid hex string: {:?}", e))?;
+        Self::from_slice(&secret_bytes)
+    }
 }
 
 pub fn strip_prefix(s: &str) -> &str {
@@ -68,9 +79,31 @@ pub fn strip_prefix(s: &str) -> &str {
     }
 }
 
+/// The key used to sign tokens, along with when and from where it was loaded.
+struct SigningKey {
+    key: EncodingKey,
+    loaded_at: SystemTime,
+    /// The modification time of the secret file when the key was read from it.
+    file_modified: Option<SystemTime>,
+}
+
+impl SigningKey {
+    fn new(secret: &JwtKey, file_modified: Option<SystemTime>) -> Self {
+        Self {
+            key: EncodingKey::from_secret(secret.as_bytes()),
+            loaded_at: SystemTime::now(),
+            file_modified,
+        }
+    }
+}
+
 /// Contains the JWT secret and claims parameters.
+///
+/// If the secret was read from a file, it can be reloaded using `Self::reload_if_modified` so that
+/// the secret can be rotated without restarting.
 pub struct Auth {
-    key: EncodingKey,
+    signing_key: RwLock<SigningKey>,
+    secret_file: Option<PathBuf>,
     id: Option<String>,
     clv: Option<String>,
 }
@@ -78,12 +111,49 @@ pub struct Auth {
 impl Auth {
     pub fn new(secret: JwtKey, id: Option<String>, clv: Option<String>) -> Self {
         Self {
-            key: EncodingKey::from_secret(secret.as_bytes()),
+            signing_key: RwLock::new(SigningKey::new(&secret, None)),
+            secret_file: None,
             id,
             clv,
         }
     }
 
+    /// Record that the current secret was read from `path`, so that it may be reloaded if the
+    /// file changes.
+    pub fn with_secret_file(mut self, path: PathBuf) -> Self {
+        self.signing_key.get_mut().file_modified = modified_time(&path);
+        self.secret_file = Some(path);
+        self
+    }
+
+    /// Reload the secret if the secret file has been modified since it was last read, returning
+    /// `true` if the secret was reloaded.
+    ///
+    /// The current secret is retained if the file does not contain a valid secret.
+    pub fn reload_if_modified(&self) -> Result<bool, Error> {
+        let Some(path) = &self.secret_file else {
+            return Ok(false);
+        };
+        let modified = modified_time(path);
+        if modified.is_none() || modified == self.signing_key.read().file_modified {
+            return Ok(false);
+        }
+
+        let secret = JwtKey::from_file(path).map_err(Error::InvalidKey)?;
+        *self.signing_key.write() = SigningKey::new(&secret, modified);
+        Ok(true)
+    }
+
+    /// The file from which the secret was read, if any.
+    pub fn secret_file(&self) -> Option<&Path> {
+        self.secret_file.as_deref()
+    }
+
+    /// The time at which the current secret was loaded.
+    pub fn secret_loaded_at(&self) -> SystemTime {
+        self.signing_key.read().loaded_at
+    }
+
     /// Create a new `Auth` struct given the path to the file containing the hex
     /// encoded jwt key.
     pub fn new_with_path(
@@ -91,19 +161,9 @@ impl Auth {
         id: Option<String>,
         clv: Option<String>,
     ) -> Result<Self, Error> {
-        std::fs::read_to_string(&jwt_path)
-            .map_err(|e| {
-                Error::InvalidKey(format!(
-                    "Failed to read JWT secret file {:?}, error: {:?}",
-                    jwt_path, e
-                ))
-            })
-            .and_then(|ref s| {
-                let secret_bytes = hex::decode(strip_prefix(s.trim_end()))
-                    .map_err(|e| Error::InvalidKey(format!("Invalid hex string: {:?}", e)))?;
-                let secret = JwtKey::from_slice(&secret_bytes).map_err(Error::InvalidKey)?;
-                Ok(Self::new(secret, id, clv))
-            })
+        let secret = JwtKey::from_file(&jwt_path)
+            .map_err(|e| Error::InvalidKey(format!("{} (path: {:?})", e, jwt_path)))?;
+        Ok(Self::new(secret, id, clv).with_secret_file(jwt_path))
     }
 
     /// Generate a JWT token with `claims.iat` set to current time.
@@ -115,7 +175,7 @@ impl Auth {
     /// Generate a JWT token with the given claims.
     fn generate_token_with_claims(&self, claims: &Claims) -> Result<String, Error> {
         let header = Header::new(DEFAULT_ALGORITHM);
-        Ok(encode(&header, claims, &self.key)?)
+        Ok(encode(&header, claims, &self.signing_key.read().key)?)
     }
 
     /// Generate a `Claims` struct with `iat` set to current time
@@ -145,6 +205,12 @@ impl Auth {
     }
 }
 
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
 /// Claims struct as defined in https://github.com/ethereum/execution-apis/blob/main/src/engine/authentication.md#jwt-claims
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
 pub struct Claims {
@@ -160,6 +226,7 @@ pub struct Claims {
 mod tests {
     use super::*;
     use crate::test_utils::DEFAULT_JWT_SECRET;
+    use std::time::Duration;
 
     #[test]
     fn test_roundtrip() {
@@ -178,4 +245,39 @@ mod tests {
             claims
         );
     }
+
+    #[test]
+    fn test_reload_if_modified() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("jwt.hex");
+        let write_secret = |contents: &str, modified: SystemTime| {
+            std::fs::write(&path, contents).unwrap();
+            std::fs::File::options()
+                .write(true)
+                .open(&path)
+                .unwrap()
+                .set_modified(modified)
+                .unwrap();
+        };
+        let old_secret = JwtKey::from_slice(&DEFAULT_JWT_SECRET).unwrap();
+        let new_secret = JwtKey::random();
+        let start = SystemTime::now() - Duration::from_secs(60);
+
+        write_secret(&old_secret.hex_string(), start);
+        let auth = Auth::new_with_path(path.clone(), None, None).unwrap();
+        assert!(!auth.reload_if_modified().unwrap());
+
+        // An invalid secret is rejected, retaining the old secret.
+        write_secret("not a secret", start + Duration::from_secs(1));
+        assert!(auth.reload_if_modified().is_err());
+        let token = auth.generate_token().unwrap();
+        assert!(Auth::validate_token(&token, &old_secret).is_ok());
+
+        write_secret(&new_secret.hex_string(), start + Duration::from_secs(2));
+        assert!(auth.reload_if_modified().unwrap());
+        assert!(!auth.reload_if_modified().unwrap());
+        let token = auth.generate_token().unwrap();
+        assert!(Auth::validate_token(&token, &new_secret).is_ok());
+        assert!(Auth::validate_token(&token, &old_secret).is_err());
+    }
 }
@@ -3,6 +3,7 @@
 use super::*;
 use crate::auth::Auth;
 use crate::json_structures::*;
+use crate::recorder::EngineRecorder;
 use lazy_static::lazy_static;
 use lighthouse_version::{COMMIT_PREFIX, VERSION};
 use reqwest::header::CONTENT_TYPE;
@@ -12,7 +13,7 @@ use serde_json::json;
 use std::collections::HashSet;
 use tokio::sync::Mutex;
 
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime};
 
 pub use deposit_log::{DepositLog, Log};
 pub use reqwest::Client;
@@ -600,6 +601,7 @@ pub struct HttpJsonRpc {
     pub engine_capabilities_cache: Mutex<Option<CachedResponse<EngineCapabilities>>>,
     pub engine_version_cache: Mutex<Option<CachedResponse<Vec<ClientVersionV1>>>>,
     auth: Option<Auth>,
+    recorder: Option<EngineRecorder>,
 }
 
 impl HttpJsonRpc {
@@ -614,6 +616,7 @@ impl HttpJsonRpc {
             engine_capabilities_cache: Mutex::new(None),
             engine_version_cache: Mutex::new(None),
             auth: None,
+            recorder: None,
         })
     }
 
@@ -629,9 +632,21 @@ impl HttpJsonRpc {
             engine_capabilities_cache: Mutex::new(None),
             engine_version_cache: Mutex::new(None),
             auth: Some(auth),
+            recorder: None,
         })
     }
 
+    /// The credentials used to authenticate with the server, if any.
+    pub fn auth(&self) -> Option<&Auth> {
+        self.auth.as_ref()
+    }
+
+    /// Record every request and response made by this client using `recorder`.
+    pub fn with_recorder(mut self, recorder: EngineRecorder) -> Self {
+        self.recorder = Some(recorder);
+        self
+    }
+
     pub async fn rpc_request<D: DeserializeOwned>(
         &self,
         method: &str,
@@ -645,19 +660,13 @@ impl HttpJsonRpc {
             id: json!(STATIC_ID),
         };
 
-        let mut request = self
-            .client
-            .post(self.url.full.clone())
-            .timeout(timeout)
-            .header(CONTENT_TYPE, "application/json")
-            .json(&body);
-
-        // Generate and add a jwt token to the header if auth is defined.
-        if let Some(auth) = &self.auth {
-            request = request.bearer_auth(auth.generate_token()?);
-        };
-
-        let body: JsonResponseBody = request.send().await?.error_for_status()?.json().await?;
+        let sent_at = SystemTime::now();
+        let start = Instant::now();
+        let response = self.send_rpc_request(&body, timeout).await;
+        if let Some(recorder) = &self.recorder {
+            recorder.record(&body, response.as_ref(), sent_at, start.elapsed());
+        }
+        let body = response?;
 
         match (body.result, body.error) {
             (result, None) => serde_json::from_value(result).map_err(Into::into),
@@ -673,6 +682,26 @@ impl HttpJsonRpc {
             }
         }
     }
+
+    async fn send_rpc_request(
+        &self,
+        body: &JsonRequestBody<'_>,
+        timeout: Duration,
+    ) -> Result<JsonResponseBody, Error> {
+        let mut request = self
+            .client
+            .post(self.url.full.clone())
+            .timeout(timeout)
+            .header(CONTENT_TYPE, "application/json")
+            .json(body);
+
+        // Generate and add a jwt token to the header if auth is defined.
+        if let Some(auth) = &self.auth {
+            request = request.bearer_auth(auth.generate_token()?);
+        };
+
+        Ok(request.send().await?.error_for_status()?.json().await?)
+    }
 }
 
 impl std::fmt::Display for HttpJsonRpc {
@@ -0,0 +1,352 @@
+//! Records engine API requests and responses to disk for debugging.
+//!
+//! Each exchange with the execution engine is written as a single line of JSON to
+//! `engine_api.jsonl` in the record directory. Once that file reaches the maximum size it is
+//! rotated to `engine_api.1.jsonl`, `engine_api.2.jsonl`, and so on, and the oldest file is deleted
+//! so that the recording covers a bounded window of recent requests.
+//!
+//! Only the JSON-RPC request and response bodies are recorded. HTTP headers, including the JWT
+//! used to authenticate with the execution engine, are never written.
+//!
+//! Records are serialized by the caller and sent to a dedicated writer thread, so that requests to
+//! the execution engine never wait on disk I/O. If the writer falls behind, new records are dropped.
+use super::json_structures::{JsonRequestBody, JsonResponseBody};
+use super::Error as ApiError;
+use serde::{Deserialize, Serialize};
+use slog::{warn, Logger};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, SyncSender, TrySendError};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// The name of the file which records are currently written to.
+pub const RECORD_FILE_NAME: &str = "engine_api.jsonl";
+
+/// The maximum number of records waiting to be written before new records are dropped.
+const RECORD_QUEUE_LEN: usize = 1_024;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EngineRecordConfig {
+    /// The directory in which to store record files.
+    pub dir: PathBuf,
+    /// The size (in bytes) at which the current record file is rotated.
+    pub max_file_size: u64,
+    /// The maximum number of record files to keep, including the current file.
+    pub max_files: usize,
+}
+
+#[derive(Serialize)]
+struct Record<'a> {
+    /// Milliseconds since the UNIX epoch at which the request was sent.
+    timestamp_ms: u128,
+    duration_ms: u128,
+    request: &'a JsonRequestBody<'a>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response: Option<&'a JsonResponseBody>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+struct RecordFile {
+    file: File,
+    size: u64,
+}
+
+pub struct EngineRecorder {
+    sender: SyncSender<Vec<u8>>,
+    log: Logger,
+}
+
+impl EngineRecorder {
+    /// Create the record directory and start the writer thread.
+    pub fn new(config: EngineRecordConfig, log: Logger) -> Result<Self, String> {
+        let writer = RecordWriter::new(config, log.clone())?;
+        let (sender, receiver) = mpsc::sync_channel(RECORD_QUEUE_LEN);
+        thread::Builder::new()
+            .name("engine_api_recorder".to_string())
+            .spawn(move || writer.run(receiver))
+            .map_err(|e| format!("Unable to spawn engine API recorder thread: {:?}", e))?;
+
+        Ok(Self { sender, log })
+    }
+
+    /// Record a request sent at `sent_at` along with its response, or the error encountered whilst
+    /// making the request.
+    ///
+    /// The record is written by the writer thread. Failures to record are logged rather than
+    /// returned, so that recording never interferes with communication with the execution engine.
+    pub fn record(
+        &self,
+        request: &JsonRequestBody,
+        response: Result<&JsonResponseBody, &ApiError>,
+        sent_at: SystemTime,
+        duration: Duration,
+    ) {
+        let record = Record {
+            timestamp_ms: sent_at
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis(),
+            duration_ms: duration.as_millis(),
+            request,
+            response: response.ok(),
+            error: response.err().map(|e| format!("{:?}", e)),
+        };
+
+        let error = match serde_json::to_vec(&record) {
+            Ok(mut line) => {
+                line.push(b'\n');
+                match self.sender.try_send(line) {
+                    Ok(()) => return,
+                    Err(TrySendError::Full(_)) => "record queue is full".to_string(),
+                    Err(TrySendError::Disconnected(_)) => "writer has stopped".to_string(),
+                }
+            }
+            Err(e) => format!("{:?}", e),
+        };
+        warn!(
+            self.log,
+            "Failed to record engine API request";
+            "method" => request.method,
+            "error" => error,
+        );
+    }
+}
+
+/// Writes serialized records to the record files, on the writer thread.
+struct RecordWriter {
+    config: EngineRecordConfig,
+    current: Option<RecordFile>,
+    log: Logger,
+}
+
+impl RecordWriter {
+    fn new(config: EngineRecordConfig, log: Logger) -> Result<Self, String> {
+        if config.max_file_size == 0 || config.max_files == 0 {
+            return Err("Engine API record file size and count must be non-zero".to_string());
+        }
+        fs::create_dir_all(&config.dir).map_err(|e| {
+            format!(
+                "Unable to create engine API record directory {}: {:?}",
+                config.dir.display(),
+                e
+            )
+        })?;
+
+        Ok(Self {
+            config,
+            current: None,
+            log,
+        })
+    }
+
+    /// Write records until the `EngineRecorder` is dropped.
+    fn run(mut self, receiver: Receiver<Vec<u8>>) {
+        while let Ok(line) = receiver.recv() {
+            if let Err(e) = self.write_line(&line) {
+                warn!(
+                    self.log,
+                    "Failed to write engine API record";
+                    "dir" => %self.config.dir.display(),
+                    "error" => ?e,
+                );
+            }
+        }
+    }
+
+    fn write_line(&mut self, line: &[u8]) -> io::Result<()> {
+        if self.current.as_ref().map_or(false, |current| {
+            current.size > 0 && current.size + line.len() as u64 > self.config.max_file_size
+        }) {
+            self.current = None;
+            self.rotate()?;
+        }
+
+        let record_file = match self.current.as_mut() {
+            Some(record_file) => record_file,
+            None => {
+                let file = OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(file_path(&self.config.dir, 0))?;
+                let size = file.metadata()?.len();
+                self.current.insert(RecordFile { file, size })
+            }
+        };
+
+        record_file.file.write_all(line)?;
+        record_file.size += line.len() as u64;
+        Ok(())
+    }
+
+    /// Shift each record file along by one, deleting the oldest.
+    fn rotate(&self) -> io::Result<()> {
+        let dir = &self.config.dir;
+        remove_if_exists(&file_path(dir, self.config.max_files - 1))?;
+        for index in (0..self.config.max_files - 1).rev() {
+            let path = file_path(dir, index);
+            if path.exists() {
+                fs::rename(path, file_path(dir, index + 1))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The path of the record file at `index` in `dir`, where `0` is the current file and higher
+/// indices are older.
+fn file_path(dir: &Path, index: usize) -> PathBuf {
+    if index == 0 {
+        dir.join(RECORD_FILE_NAME)
+    } else {
+        dir.join(format!("engine_api.{}.jsonl", index))
+    }
+}
+
+fn remove_if_exists(path: &Path) -> io::Result<()> {
+    match fs::remove_file(path) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use environment::null_logger;
+    use serde_json::json;
+    use tempfile::tempdir;
+
+    fn request(method: &str) -> JsonRequestBody {
+        JsonRequestBody {
+            jsonrpc: "2.0",
+            method,
+            params: json!([]),
+            id: json!(1),
+        }
+    }
+
+    fn response() -> JsonResponseBody {
+        JsonResponseBody {
+            jsonrpc: "2.0".to_string(),
+            error: None,
+            result: json!(false),
+            id: json!(1),
+        }
+    }
+
+    /// Make records with `record`, then write them on the current thread rather than a writer
+    /// thread so that they have all been written on return.
+    fn record_all(
+        config: EngineRecordConfig,
+        queue_len: usize,
+        record: impl FnOnce(&EngineRecorder),
+    ) {
+        let log = null_logger().unwrap();
+        let writer = RecordWriter::new(config, log.clone()).unwrap();
+        let (sender, receiver) = mpsc::sync_channel(queue_len);
+        record(&EngineRecorder { sender, log });
+        // The recorder has been dropped, so the writer stops once the queue is empty.
+        writer.run(receiver);
+    }
+
+    fn read_records(path: &Path) -> Vec<serde_json::Value> {
+        fs::read_to_string(path)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn records_requests_and_errors() {
+        let dir = tempdir().unwrap();
+        let config = EngineRecordConfig {
+            dir: dir.path().join("records"),
+            max_file_size: 1024 * 1024,
+            max_files: 2,
+        };
+
+        record_all(config, RECORD_QUEUE_LEN, |recorder| {
+            recorder.record(
+                &request("eth_syncing"),
+                Ok(&response()),
+                SystemTime::now(),
+                Duration::from_millis(3),
+            );
+            recorder.record(
+                &request("engine_newPayloadV3"),
+                Err(&ApiError::IsSyncing),
+                SystemTime::now(),
+                Duration::from_millis(5),
+            );
+        });
+
+        let records = read_records(&dir.path().join("records").join(RECORD_FILE_NAME));
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0]["request"]["method"], "eth_syncing");
+        assert_eq!(records[0]["response"]["result"], false);
+        assert_eq!(records[0]["duration_ms"], 3);
+        assert!(records[0].get("error").is_none());
+        assert_eq!(records[1]["request"]["method"], "engine_newPayloadV3");
+        assert_eq!(records[1]["error"], "IsSyncing");
+        assert!(records[1].get("response").is_none());
+    }
+
+    #[test]
+    fn rotates_and_bounds_files() {
+        let dir = tempdir().unwrap();
+        let max_files = 3;
+        let config = EngineRecordConfig {
+            dir: dir.path().to_path_buf(),
+            // Small enough that every record is written to a new file.
+            max_file_size: 1,
+            max_files,
+        };
+
+        record_all(config, RECORD_QUEUE_LEN, |recorder| {
+            for i in 0..5 {
+                recorder.record(
+                    &request(&format!("method_{i}")),
+                    Ok(&response()),
+                    SystemTime::now(),
+                    Duration::ZERO,
+                );
+            }
+        });
+
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), max_files);
+        for (index, method) in ["method_4", "method_3", "method_2"].iter().enumerate() {
+            let records = read_records(&file_path(dir.path(), index));
+            assert_eq!(records.len(), 1);
+            assert_eq!(records[0]["request"]["method"], *method);
+        }
+    }
+
+    #[test]
+    fn drops_records_when_queue_is_full() {
+        let dir = tempdir().unwrap();
+        let config = EngineRecordConfig {
+            dir: dir.path().to_path_buf(),
+            max_file_size: 1024 * 1024,
+            max_files: 2,
+        };
+
+        record_all(config, 1, |recorder| {
+            for method in ["method_0", "method_1"] {
+                recorder.record(
+                    &request(method),
+                    Ok(&response()),
+                    SystemTime::now(),
+                    Duration::ZERO,
+                );
+            }
+        });
+
+        let records = read_records(&file_path(dir.path(), 0));
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0]["request"]["method"], "method_0");
+    }
+}
@@ -0,0 +1,452 @@
+//! Parsing of execution layer genesis files, as used by Geth and other execution clients.
+//!
+//! This allows the execution payload header of a post-merge consensus genesis state to be derived
+//! directly from the genesis of the execution chain.
+use crate::block_hash::rlp_encode_block_header;
+use crate::keccak::{keccak256, KeccakHasher};
+use alloy_rlp::Encodable;
+use keccak_hash::{KECCAK_EMPTY, KECCAK_EMPTY_LIST_RLP, KECCAK_NULL_RLP};
+use serde::{Deserialize, Deserializer};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::str::FromStr;
+use triehash::sec_trie_root;
+use types::{
+    Address, EthSpec, ExecutionBlockHash, ExecutionBlockHeader, ExecutionPayload,
+    ExecutionPayloadHeader, ForkName, Hash256, Hash64, Uint256, VariableList,
+};
+
+/// The base fee of the first London block, used when the genesis doesn't specify one.
+const INITIAL_BASE_FEE: u64 = 1_000_000_000;
+
+/// The gas limit used when the genesis doesn't specify one.
+const DEFAULT_GAS_LIMIT: u64 = 4_712_388;
+
+/// The difficulty used when the genesis specifies neither a difficulty nor a mix hash.
+const DEFAULT_DIFFICULTY: u64 = 131_072;
+
+/// An execution layer `genesis.json`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutionGenesis {
+    pub config: ExecutionGenesisConfig,
+    #[serde(default, deserialize_with = "deserialize_u64")]
+    pub nonce: u64,
+    #[serde(default, deserialize_with = "deserialize_u64")]
+    pub timestamp: u64,
+    #[serde(default, deserialize_with = "deserialize_bytes")]
+    pub extra_data: Vec<u8>,
+    #[serde(default, deserialize_with = "deserialize_u64")]
+    pub gas_limit: u64,
+    #[serde(default, deserialize_with = "deserialize_optional_u256")]
+    pub difficulty: Option<Uint256>,
+    #[serde(default)]
+    pub mix_hash: Hash256,
+    #[serde(default)]
+    pub coinbase: Address,
+    #[serde(default)]
+    pub alloc: BTreeMap<String, GenesisAccount>,
+    #[serde(default, deserialize_with = "deserialize_u64")]
+    pub number: u64,
+    #[serde(default, deserialize_with = "deserialize_u64")]
+    pub gas_used: u64,
+    #[serde(default)]
+    pub parent_hash: Hash256,
+    #[serde(default, deserialize_with = "deserialize_optional_u256")]
+    pub base_fee_per_gas: Option<Uint256>,
+    #[serde(default, deserialize_with = "deserialize_optional_u64")]
+    pub excess_blob_gas: Option<u64>,
+    #[serde(default, deserialize_with = "deserialize_optional_u64")]
+    pub blob_gas_used: Option<u64>,
+}
+
+/// The subset of the chain config in an execution layer genesis which is relevant to the genesis
+/// block.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutionGenesisConfig {
+    #[serde(deserialize_with = "deserialize_u64")]
+    pub chain_id: u64,
+    #[serde(default, deserialize_with = "deserialize_optional_u64")]
+    pub london_block: Option<u64>,
+    #[serde(default, deserialize_with = "deserialize_optional_u64")]
+    pub shanghai_time: Option<u64>,
+    #[serde(default, deserialize_with = "deserialize_optional_u64")]
+    pub cancun_time: Option<u64>,
+    #[serde(default, deserialize_with = "deserialize_optional_u64")]
+    pub prague_time: Option<u64>,
+    /// The address of the deposit contract, which some execution clients require in the genesis
+    /// of networks that process deposits from the execution layer.
+    #[serde(default)]
+    pub deposit_contract_address: Option<Address>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct GenesisAccount {
+    #[serde(default, deserialize_with = "deserialize_u256")]
+    pub balance: Uint256,
+    #[serde(default, deserialize_with = "deserialize_u64")]
+    pub nonce: u64,
+    #[serde(default, deserialize_with = "deserialize_bytes")]
+    pub code: Vec<u8>,
+    #[serde(default)]
+    pub storage: BTreeMap<Hash256, Hash256>,
+}
+
+impl ExecutionGenesis {
+    pub fn from_file(path: &Path) -> Result<Self, String> {
+        let file = std::fs::File::open(path)
+            .map_err(|e| format!("Unable to open {}: {:?}", path.display(), e))?;
+        serde_json::from_reader(file)
+            .map_err(|e| format!("Unable to parse {}: {:?}", path.display(), e))
+    }
+
+    /// Returns `true` if the fork activated at `fork_time` is active in the genesis block.
+    fn is_active_at_genesis(&self, fork_time: Option<u64>) -> bool {
+        fork_time.map_or(false, |time| time <= self.timestamp)
+    }
+
+    /// Returns the latest consensus fork whose execution counterpart is active in the genesis
+    /// block.
+    pub fn fork_name(&self) -> Result<ForkName, String> {
+        if self.config.london_block != Some(0) {
+            return Err("London must be active at genesis".to_string());
+        }
+        if self.is_active_at_genesis(self.config.prague_time) {
+            Err("Prague at genesis is not supported".to_string())
+        } else if self.is_active_at_genesis(self.config.cancun_time) {
+            Ok(ForkName::Deneb)
+        } else if self.is_active_at_genesis(self.config.shanghai_time) {
+            Ok(ForkName::Capella)
+        } else {
+            Ok(ForkName::Bellatrix)
+        }
+    }
+
+    /// Computes the root of the state trie of the genesis block.
+    pub fn state_root(&self) -> Result<Hash256, String> {
+        let accounts = self
+            .alloc
+            .iter()
+            .map(|(address, account)| {
+                let address = Address::from_str(address)
+                    .map_err(|e| format!("Invalid address {}: {:?}", address, e))?;
+                Ok((address, rlp_encode_account(account)))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        Ok(sec_trie_root::<KeccakHasher, _, _, _>(accounts))
+    }
+
+    /// Computes the execution payload header of the genesis block, to be included in the genesis
+    /// state of the consensus chain.
+    pub fn execution_payload_header<E: EthSpec>(
+        &self,
+    ) -> Result<ExecutionPayloadHeader<E>, String> {
+        let fork_name = self.fork_name()?;
+        let mut payload = match fork_name {
+            ForkName::Bellatrix => ExecutionPayload::Bellatrix(<_>::default()),
+            ForkName::Capella => ExecutionPayload::Capella(<_>::default()),
+            ForkName::Deneb => {
+                let mut payload = ExecutionPayload::Deneb(<_>::default());
+                *payload
+                    .blob_gas_used_mut()
+                    .map_err(|_| "Missing blob gas")? = self.blob_gas_used.unwrap_or_default();
+                *payload
+                    .excess_blob_gas_mut()
+                    .map_err(|_| "Missing blob gas")? = self.excess_blob_gas.unwrap_or_default();
+                payload
+            }
+            other => return Err(format!("Unsupported genesis fork {}", other)),
+        };
+
+        *payload.parent_hash_mut() = ExecutionBlockHash::from_root(self.parent_hash);
+        *payload.fee_recipient_mut() = self.coinbase;
+        *payload.state_root_mut() = self.state_root()?;
+        *payload.receipts_root_mut() = empty_trie_root();
+        *payload.prev_randao_mut() = self.mix_hash;
+        *payload.block_number_mut() = self.number;
+        *payload.gas_limit_mut() = if self.gas_limit == 0 {
+            DEFAULT_GAS_LIMIT
+        } else {
+            self.gas_limit
+        };
+        *payload.gas_used_mut() = self.gas_used;
+        *payload.timestamp_mut() = self.timestamp;
+        *payload.extra_data_mut() = VariableList::new(self.extra_data.clone())
+            .map_err(|e| format!("Extra data is too long: {:?}", e))?;
+        *payload.base_fee_per_gas_mut() = self
+            .base_fee_per_gas
+            .unwrap_or_else(|| Uint256::from(INITIAL_BASE_FEE));
+
+        // The genesis block may have a non-zero difficulty and nonce, which can't be represented
+        // in a payload but must be included in its block hash.
+        let mut header = ExecutionBlockHeader::from_payload(
+            payload.to_ref(),
+            KECCAK_EMPTY_LIST_RLP.as_fixed_bytes().into(),
+            empty_trie_root(),
+            fork_name.capella_enabled().then(empty_trie_root),
+            payload.blob_gas_used().ok(),
+            payload.excess_blob_gas().ok(),
+            fork_name.deneb_enabled().then(Hash256::zero),
+        );
+        header.difficulty = self.difficulty.unwrap_or_else(|| {
+            if self.mix_hash.is_zero() {
+                Uint256::from(DEFAULT_DIFFICULTY)
+            } else {
+                Uint256::zero()
+            }
+        });
+        header.nonce = Hash64::from_slice(&self.nonce.to_be_bytes());
+        *payload.block_hash_mut() =
+            ExecutionBlockHash::from_root(keccak256(&rlp_encode_block_header(&header)));
+
+        Ok(ExecutionPayloadHeader::from(payload.to_ref()))
+    }
+}
+
+/// The root of an empty Merkle Patricia trie.
+fn empty_trie_root() -> Hash256 {
+    KECCAK_NULL_RLP.as_fixed_bytes().into()
+}
+
+/// RLP encode an account as it is stored in the state trie.
+fn rlp_encode_account(account: &GenesisAccount) -> Vec<u8> {
+    let storage = account
+        .storage
+        .iter()
+        .filter(|(_, value)| !value.is_zero())
+        .map(|(key, value)| {
+            let mut out = vec![];
+            alloy_primitives::U256::from_be_slice(value.as_bytes()).encode(&mut out);
+            (*key, out)
+        })
+        .collect::<Vec<_>>();
+    let storage_root = if storage.is_empty() {
+        empty_trie_root()
+    } else {
+        sec_trie_root::<KeccakHasher, _, _, _>(storage)
+    };
+    let code_hash = if account.code.is_empty() {
+        KECCAK_EMPTY.as_fixed_bytes().into()
+    } else {
+        keccak256(&account.code)
+    };
+
+    let mut balance = [0; 32];
+    account.balance.to_big_endian(&mut balance);
+    let balance = alloy_primitives::U256::from_be_bytes(balance);
+
+    let payload_length = account.nonce.length()
+        + balance.length()
+        + storage_root.as_bytes().length()
+        + code_hash.as_bytes().length();
+    let mut out = vec![];
+    alloy_rlp::Header {
+        list: true,
+        payload_length,
+    }
+    .encode(&mut out);
+    account.nonce.encode(&mut out);
+    balance.encode(&mut out);
+    storage_root.as_bytes().encode(&mut out);
+    code_hash.as_bytes().encode(&mut out);
+    out
+}
+
+/// A quantity which may be given as a JSON number or as a hex or decimal string.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Quantity {
+    Number(u64),
+    String(String),
+}
+
+impl Quantity {
+    fn into_u256(self) -> Result<Uint256, String> {
+        match self {
+            Quantity::Number(n) => Ok(Uint256::from(n)),
+            Quantity::String(s) => match s.strip_prefix("0x") {
+                Some(hex) if hex.is_empty() => Ok(Uint256::zero()),
+                Some(hex) => Uint256::from_str_radix(hex, 16).map_err(|e| format!("{:?}", e)),
+                None => Uint256::from_dec_str(&s).map_err(|e| format!("{:?}", e)),
+            },
+        }
+    }
+
+    fn into_u64(self) -> Result<u64, String> {
+        let value = self.into_u256()?;
+        if value > Uint256::from(u64::MAX) {
+            return Err(format!("{} is too large", value));
+        }
+        Ok(value.as_u64())
+    }
+}
+
+fn deserialize_u64<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+    Quantity::deserialize(deserializer)?
+        .into_u64()
+        .map_err(serde::de::Error::custom)
+}
+
+fn deserialize_optional_u64<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<u64>, D::Error> {
+    Option::<Quantity>::deserialize(deserializer)?
+        .map(Quantity::into_u64)
+        .transpose()
+        .map_err(serde::de::Error::custom)
+}
+
+fn deserialize_u256<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Uint256, D::Error> {
+    Quantity::deserialize(deserializer)?
+        .into_u256()
+        .map_err(serde::de::Error::custom)
+}
+
+fn deserialize_optional_u256<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<Uint256>, D::Error> {
+    Option::<Quantity>::deserialize(deserializer)?
+        .map(Quantity::into_u256)
+        .transpose()
+        .map_err(serde::de::Error::custom)
+}
+
+fn deserialize_bytes<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+    let s = String::deserialize(deserializer)?;
+    hex::decode(s.strip_prefix("0x").unwrap_or(&s)).map_err(serde::de::Error::custom)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use tree_hash::TreeHash;
+    use types::{MainnetEthSpec, Transactions, Withdrawals};
+
+    fn genesis(shanghai_time: u64, cancun_time: u64) -> ExecutionGenesis {
+        serde_json::from_value(serde_json::json!({
+            "config": {
+                "chainId": 1337,
+                "londonBlock": 0,
+                "shanghaiTime": shanghai_time,
+                "cancunTime": cancun_time,
+                "terminalTotalDifficulty": 0,
+                "depositContractAddress": "0x4242424242424242424242424242424242424242"
+            },
+            "nonce": "0x1234",
+            "timestamp": "1700000000",
+            "extraData": "0x",
+            "gasLimit": "0x1c9c380",
+            "difficulty": "0x01",
+            "alloc": {
+                "0x4242424242424242424242424242424242424242": {
+                    "balance": "1000000000000000000000000000",
+                },
+                "0000000000000000000000000000000000000001": {
+                    "balance": "0x1",
+                    "code": "0x6000",
+                    "storage": {
+                        "0x0000000000000000000000000000000000000000000000000000000000000000":
+                            "0x0000000000000000000000000000000000000000000000000000000000000001"
+                    }
+                }
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn deposit_contract_address() {
+        let mut genesis = genesis(0, 0);
+        assert_eq!(
+            genesis.config.deposit_contract_address,
+            Some(Address::repeat_byte(0x42))
+        );
+
+        genesis = serde_json::from_value(serde_json::json!({
+            "config": { "chainId": 1337, "londonBlock": 0 }
+        }))
+        .unwrap();
+        assert_eq!(genesis.config.deposit_contract_address, None);
+    }
+
+    #[test]
+    fn empty_state_root() {
+        let mut genesis = genesis(0, 0);
+        genesis.alloc.clear();
+        assert_eq!(genesis.state_root().unwrap(), empty_trie_root());
+    }
+
+    #[test]
+    fn fork_at_genesis() {
+        let timestamp = 1_700_000_000;
+        assert_eq!(genesis(0, 0).fork_name(), Ok(ForkName::Deneb));
+        assert_eq!(
+            genesis(timestamp, timestamp + 1).fork_name(),
+            Ok(ForkName::Capella)
+        );
+        assert_eq!(
+            genesis(timestamp + 1, timestamp + 1).fork_name(),
+            Ok(ForkName::Bellatrix)
+        );
+
+        let mut pre_london = genesis(0, 0);
+        pre_london.config.london_block = None;
+        assert!(pre_london.fork_name().is_err());
+    }
+
+    #[test]
+    fn payload_header() {
+        let genesis = genesis(0, 0);
+        let header = genesis
+            .execution_payload_header::<MainnetEthSpec>()
+            .unwrap();
+
+        assert!(matches!(header, ExecutionPayloadHeader::Deneb(_)));
+        assert_eq!(header.timestamp(), 1_700_000_000);
+        assert_eq!(header.gas_limit(), 30_000_000);
+        assert_eq!(header.state_root(), genesis.state_root().unwrap());
+        assert_eq!(
+            header.transactions_root(),
+            Transactions::<MainnetEthSpec>::empty().tree_hash_root()
+        );
+        assert_eq!(
+            header.withdrawals_root().unwrap(),
+            Withdrawals::<MainnetEthSpec>::empty().tree_hash_root()
+        );
+        assert_eq!(header.base_fee_per_gas(), Uint256::from(INITIAL_BASE_FEE));
+
+        // The nonce isn't part of the payload, but is part of the block hash.
+        let mut other_nonce = genesis.clone();
+        other_nonce.nonce = 0;
+        let other_header = other_nonce
+            .execution_payload_header::<MainnetEthSpec>()
+            .unwrap();
+        assert_ne!(header.block_hash(), other_header.block_hash());
+        assert_eq!(header.state_root(), other_header.state_root());
+    }
+
+    /// The state root and block hash of `genesis(0, 0)`, computed with a separate implementation of
+    /// keccak256, RLP and the Merkle Patricia trie (checked against the `puppy` vector of the
+    /// Ethereum trie tests) following the genesis rules of Geth.
+    #[test]
+    fn genesis_hash_vector() {
+        let genesis = genesis(0, 0);
+        let header = genesis
+            .execution_payload_header::<MainnetEthSpec>()
+            .unwrap();
+
+        assert_eq!(
+            header.state_root(),
+            Hash256::from_str("0x16151cf06257a2553f5160cf228d1180dd561b98a8b6fe1acdd355dcc0727b9d")
+                .unwrap()
+        );
+        assert_eq!(
+            header.block_hash(),
+            ExecutionBlockHash::from_str(
+                "0x7b17e6255772b9c509ee1afb3a4e01cdec3c18ce4ef2462e5703ed0ce2b853aa"
+            )
+            .unwrap()
+        );
+    }
+}
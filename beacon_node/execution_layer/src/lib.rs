@@ -6,9 +6,10 @@
 
 use crate::payload_cache::PayloadCache;
 use arc_swap::ArcSwapOption;
-use auth::{strip_prefix, Auth, JwtKey};
+use auth::{Auth, JwtKey};
 pub use block_hash::calculate_execution_block_hash;
 use builder_client::BuilderHttpClient;
+pub use engine_api::recorder::{EngineRecordConfig, EngineRecorder};
 pub use engine_api::EngineCapabilities;
 use engine_api::Error as ApiError;
 pub use engine_api::*;
@@ -58,6 +59,7 @@ use types::{
 mod block_hash;
 mod engine_api;
 pub mod engines;
+pub mod genesis;
 mod keccak;
 mod metrics;
 pub mod payload_cache;
@@ -71,6 +73,9 @@ pub const DEFAULT_EXECUTION_ENDPOINT: &str = "http://localhost:8551/";
 /// Name for the default file used for the jwt secret.
 pub const DEFAULT_JWT_FILE: &str = "jwt.hex";
 
+/// How often to check the JWT secret file for changes.
+const JWT_SECRET_RELOAD_INTERVAL: Duration = Duration::from_secs(10);
+
 /// Each time the `ExecutionLayer` retrieves a block from an execution node, it stores that block
 /// in an LRU cache to avoid redundant lookups. This is the size of that cache.
 const EXECUTION_BLOCKS_LRU_CACHE_SIZE: NonZeroUsize = new_non_zero_usize(128);
@@ -144,6 +149,7 @@ pub enum Error {
         transactions_root: Hash256,
     },
     InvalidJWTSecret(String),
+    InvalidEngineRecordConfig(String),
     InvalidForkForPayload,
     InvalidPayloadBody(String),
     InvalidPayloadConversion,
@@ -387,6 +393,8 @@ pub struct Config {
     /// Default directory for the jwt secret if not provided through cli.
     pub default_datadir: PathBuf,
     pub execution_timeout_multiplier: Option<u32>,
+    /// Record engine API requests and responses to files, for debugging.
+    pub engine_record: Option<EngineRecordConfig>,
 }
 
 /// Provides access to one execution engine and provides a neat interface for consumption by the
@@ -410,6 +418,7 @@ impl<E: EthSpec> ExecutionLayer<E> {
             jwt_version,
             default_datadir,
             execution_timeout_multiplier,
+            engine_record,
         } = config;
 
         let execution_url = url.ok_or(Error::NoEngine)?;
@@ -419,16 +428,7 @@ impl<E: EthSpec> ExecutionLayer<E> {
 
         let jwt_key = if secret_file.exists() {
             // Read secret from file if it already exists
-            std::fs::read_to_string(&secret_file)
-                .map_err(|e| format!("Failed to read JWT secret file. Error: {:?}", e))
-                .and_then(|ref s| {
-                    let secret = JwtKey::from_slice(
-                        &hex::decode(strip_prefix(s.trim_end()))
-                            .map_err(|e| format!("Invalid hex string: {:?}", e))?,
-                    )?;
-                    Ok(secret)
-                })
-                .map_err(Error::InvalidJWTSecret)
+            JwtKey::from_file(&secret_file).map_err(Error::InvalidJWTSecret)
         } else {
             // Create a new file and write a randomly generated secret to it if file does not exist
             warn!(log, "No JWT found on disk. Generating"; "path" => %secret_file.display());
@@ -447,10 +447,22 @@ impl<E: EthSpec> ExecutionLayer<E> {
         }?;
 
         let engine: Engine = {
-            let auth = Auth::new(jwt_key, jwt_id, jwt_version);
+            let auth =
+                Auth::new(jwt_key, jwt_id, jwt_version).with_secret_file(secret_file.clone());
             debug!(log, "Loaded execution endpoint"; "endpoint" => %execution_url, "jwt_path" => ?secret_file.as_path());
-            let api = HttpJsonRpc::new_with_auth(execution_url, auth, execution_timeout_multiplier)
-                .map_err(Error::ApiError)?;
+            let mut api =
+                HttpJsonRpc::new_with_auth(execution_url, auth, execution_timeout_multiplier)
+                    .map_err(Error::ApiError)?;
+            if let Some(record_config) = engine_record {
+                info!(
+                    log,
+                    "Recording engine API requests";
+                    "dir" => %record_config.dir.display(),
+                );
+                let recorder = EngineRecorder::new(record_config, log.clone())
+                    .map_err(Error::InvalidEngineRecordConfig)?;
+                api = api.with_recorder(recorder);
+            }
             Engine::new(api, executor.clone(), &log)
         };
 
@@ -619,6 +631,60 @@ impl<E: EthSpec> ExecutionLayer<E> {
         self.engine().upcheck().await;
     }
 
+    /// Spawns a routine which reloads the JWT secret whenever the secret file is modified, so that
+    /// the secret can be rotated without a restart.
+    pub fn spawn_jwt_secret_reload_routine(&self) {
+        let reloader = |el: ExecutionLayer<E>| async move {
+            loop {
+                sleep(JWT_SECRET_RELOAD_INTERVAL).await;
+
+                if let Err(e) = el.reload_jwt_secret().await {
+                    warn!(
+                        el.log(),
+                        "Failed to reload JWT secret";
+                        "info" => "continuing to use the previous secret",
+                        "error" => ?e,
+                    );
+                }
+            }
+        };
+
+        self.spawn(reloader, "exec_jwt_secret_reload");
+    }
+
+    /// Reload the JWT secret if the secret file has been modified, returning `true` if the secret
+    /// was reloaded.
+    ///
+    /// The current secret is retained if the file does not contain a valid secret.
+    pub async fn reload_jwt_secret(&self) -> Result<bool, Error> {
+        let Some(auth) = self.engine().api.auth() else {
+            return Ok(false);
+        };
+        if !auth.reload_if_modified().map_err(ApiError::Auth)? {
+            return Ok(false);
+        }
+        info!(self.log(), "Reloaded JWT secret");
+
+        // Check the new secret immediately rather than waiting for the watchdog.
+        self.engine().upcheck().await;
+        Ok(true)
+    }
+
+    /// The time at which the current JWT secret was loaded.
+    pub fn jwt_secret_loaded_at(&self) -> Option<SystemTime> {
+        self.engine().api.auth().map(Auth::secret_loaded_at)
+    }
+
+    /// Check that the execution engine accepts requests signed with the current JWT secret.
+    ///
+    /// A syncing execution engine is considered to have accepted the request.
+    pub async fn check_auth(&self) -> Result<(), Error> {
+        match self.engine().api.upcheck().await {
+            Ok(()) | Err(ApiError::IsSyncing) => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
     /// Spawns a routine which cleans the cached proposer data periodically.
     pub fn spawn_clean_proposer_caches_routine<S: SlotClock + 'static>(&self, slot_clock: S) {
         let preparation_cleaner = |el: ExecutionLayer<E>| async move {
@@ -2010,6 +2076,14 @@ impl<E: EthSpec> ExecutionLayer<E> {
                         .collect(),
                 )
                 .map_err(ApiError::DeserializeWithdrawalRequests)?;
+                let consolidation_requests = VariableList::new(
+                    electra_block
+                        .consolidation_requests
+                        .into_iter()
+                        .map(Into::into)
+                        .collect(),
+                )
+                .map_err(ApiError::DeserializeConsolidationRequests)?;
                 ExecutionPayload::Electra(ExecutionPayloadElectra {
                     parent_hash: electra_block.parent_hash,
                     fee_recipient: electra_block.fee_recipient,
@@ -2030,6 +2104,7 @@ impl<E: EthSpec> ExecutionLayer<E> {
                     excess_blob_gas: electra_block.excess_blob_gas,
                     deposit_requests,
                     withdrawal_requests,
+                    consolidation_requests,
                 })
             }
         };
@@ -2293,6 +2368,32 @@ mod test {
             .await;
     }
 
+    #[tokio::test]
+    async fn reload_jwt_secret() {
+        let runtime = TestRuntime::default();
+        let mock = MockExecutionLayer::default_params(runtime.task_executor.clone());
+        let el = &mock.el;
+        let secret_file = el
+            .engine()
+            .api
+            .auth()
+            .and_then(Auth::secret_file)
+            .unwrap()
+            .to_path_buf();
+        el.check_auth().await.unwrap();
+        assert!(!el.reload_jwt_secret().await.unwrap());
+
+        // A secret unknown to the execution engine is used as soon as it is loaded.
+        std::fs::write(&secret_file, JwtKey::random().hex_string()).unwrap();
+        assert!(el.reload_jwt_secret().await.unwrap());
+        assert!(matches!(
+            el.check_auth().await,
+            Err(Error::ApiError(ApiError::Auth(_)))
+        ));
+
+        std::fs::remove_file(&secret_file).unwrap();
+    }
+
     #[tokio::test]
     async fn test_forked_terminal_block() {
         let runtime = TestRuntime::default();
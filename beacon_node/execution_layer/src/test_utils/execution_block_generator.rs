@@ -661,6 +661,7 @@ impl<E: EthSpec> ExecutionBlockGenerator<E> {
                     excess_blob_gas: 0,
                     deposit_requests: vec![].into(),
                     withdrawal_requests: vec![].into(),
+                    consolidation_requests: vec![].into(),
                 }),
                 _ => unreachable!(),
             },
@@ -597,6 +597,11 @@ pub async fn handle_rpc<E: EthSpec>(
                                     VariableList::from(withdrawal_requests.clone())
                                 },
                             ),
+                            consolidation_requests: block.consolidation_requests().ok().map(
+                                |consolidation_requests| {
+                                    VariableList::from(consolidation_requests.clone())
+                                },
+                            ),
                         }));
                     }
                     None => response.push(None),
@@ -0,0 +1,52 @@
+//! Contains the handler for the `POST lighthouse/duties/all` endpoint.
+
+use crate::{attester_duties, proposer_duties, sync_committees};
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use eth2::lighthouse::AllDuties;
+use eth2::types::GenericResponse;
+use slog::Logger;
+use std::collections::HashSet;
+use types::Epoch;
+
+/// Returns the proposer, attester and sync committee duties of the validators in
+/// `request_indices` at `request_epoch`.
+///
+/// The same epoch restrictions apply as to the standard duties endpoints, so the request fails if
+/// any of the three kinds of duties cannot be served.
+pub fn all_duties<T: BeaconChainTypes>(
+    request_epoch: Epoch,
+    request_indices: &[u64],
+    chain: &BeaconChain<T>,
+    log: &Logger,
+) -> Result<GenericResponse<AllDuties>, warp::reject::Rejection> {
+    let proposer = proposer_duties::proposer_duties(request_epoch, chain, log)?;
+    let attester = attester_duties::attester_duties(request_epoch, request_indices, chain)?;
+    let sync = sync_committees::sync_committee_duties(request_epoch, request_indices, chain)?;
+
+    // Proposer duties are computed for every validator, so only return the requested ones.
+    let requested = request_indices.iter().collect::<HashSet<_>>();
+    let proposer_duties = proposer
+        .data
+        .into_iter()
+        .filter(|duty| requested.contains(&duty.validator_index))
+        .collect();
+
+    let execution_optimistic = [
+        proposer.execution_optimistic,
+        attester.execution_optimistic,
+        sync.execution_optimistic,
+    ]
+    .into_iter()
+    .flatten()
+    .reduce(|a, b| a || b);
+
+    Ok(GenericResponse::from(AllDuties {
+        epoch: request_epoch,
+        execution_optimistic,
+        proposer_dependent_root: proposer.dependent_root,
+        attester_dependent_root: attester.dependent_root,
+        proposer_duties,
+        attester_duties: attester.data,
+        sync_duties: sync.data,
+    }))
+}
@@ -1,3 +1,4 @@
+use crate::task_spawner::check_cancelled;
 use beacon_chain::{BeaconChain, BeaconChainError, BeaconChainTypes};
 use eth2::lighthouse::{
     AttestationPerformance, AttestationPerformanceQuery, AttestationPerformanceStatistics,
@@ -131,6 +132,12 @@ pub fn get_attestation_performance<T: BeaconChainTypes>(
         .and_then(|maybe_state| maybe_state.ok_or(BeaconChainError::MissingBeaconState(state_root)))
         .map_err(beacon_chain_error)?;
 
+    // Load the state roots of the whole range at once, so that they need not be computed during
+    // replay.
+    let state_roots = chain
+        .state_roots_in_range(prior_slot, end_slot)
+        .map_err(beacon_chain_error)?;
+
     // Allocate an AttestationPerformance vector for each validator in the range.
     let mut perfs: Vec<AttestationPerformance> =
         AttestationPerformance::initialize(index_range.clone());
@@ -182,13 +189,19 @@ pub fn get_attestation_performance<T: BeaconChainTypes>(
 
     // Initialize block replayer
     let mut replayer = BlockReplayer::new(state, spec)
-        .no_state_root_iter()
+        .state_root_iter(
+            state_roots
+                .into_iter()
+                .map(Ok::<_, AttestationPerformanceError>),
+        )
         .no_signature_verification()
         .minimal_block_root_verification()
         .post_slot_hook(Box::new(post_slot_hook));
 
     // Iterate through block roots in chunks to reduce load on memory.
     for block_root_chunks in block_roots.chunks(BLOCK_ROOT_CHUNK_SIZE) {
+        check_cancelled()?;
+
         // Load blocks from the block root chunks.
         let blocks = block_root_chunks
             .iter()
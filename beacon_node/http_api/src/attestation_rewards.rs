@@ -0,0 +1,85 @@
+use beacon_chain::{BeaconChain, BeaconChainError, BeaconChainTypes};
+use eth2::lighthouse::PagedAttestationRewards;
+use eth2::types::{AttestationRewardsQuery, ValidatorId};
+use std::sync::Arc;
+use types::{BeaconStateError, Epoch};
+use warp_utils::reject::{custom_bad_request, custom_not_found, custom_server_error};
+
+/// The number of validators per page if a `page_token` is provided without a `page_size`.
+const DEFAULT_PAGE_SIZE: usize = 16_384;
+
+/// Compute the attestation rewards for `epoch`, returning a single page if requested by `query`.
+///
+/// Pages of the rewards for all validators are served from the chain's attestation rewards cache,
+/// so that the rewards are only computed once for a client fetching every page. The page token is
+/// the position of the first validator in the page within the full list of rewards.
+pub fn compute_attestation_rewards<T: BeaconChainTypes>(
+    chain: Arc<BeaconChain<T>>,
+    epoch: Epoch,
+    validators: Vec<ValidatorId>,
+    query: AttestationRewardsQuery,
+) -> Result<PagedAttestationRewards, warp::Rejection> {
+    if query.page_size.is_none() && query.page_token.is_none() {
+        let rewards = chain
+            .compute_attestation_rewards(epoch, validators)
+            .map_err(rewards_error)?;
+        return Ok(PagedAttestationRewards {
+            ideal_rewards: rewards.ideal_rewards,
+            total_rewards: rewards.total_rewards,
+            next_page_token: None,
+        });
+    }
+
+    let page_size = query.page_size.unwrap_or(DEFAULT_PAGE_SIZE);
+    if page_size == 0 {
+        return Err(custom_bad_request(
+            "page_size must be at least 1".to_string(),
+        ));
+    }
+    let start = query
+        .page_token
+        .map(|token| token.parse::<usize>())
+        .transpose()
+        .map_err(|_| custom_bad_request("invalid page_token".to_string()))?
+        .unwrap_or(0);
+
+    let rewards = if validators.is_empty() {
+        chain.compute_all_attestation_rewards_cached(epoch)
+    } else {
+        chain
+            .compute_attestation_rewards(epoch, validators)
+            .map(Arc::new)
+    }
+    .map_err(rewards_error)?;
+
+    let total = rewards.total_rewards.len();
+    if start > total {
+        return Err(custom_bad_request("invalid page_token".to_string()));
+    }
+    let end = start.saturating_add(page_size).min(total);
+
+    Ok(PagedAttestationRewards {
+        ideal_rewards: rewards.ideal_rewards.clone(),
+        total_rewards: rewards.total_rewards[start..end].to_vec(),
+        next_page_token: (end < total).then(|| end.to_string()),
+    })
+}
+
+fn rewards_error(e: BeaconChainError) -> warp::Rejection {
+    match e {
+        BeaconChainError::Cancelled => custom_server_error("the request was cancelled".to_string()),
+        BeaconChainError::MissingBeaconState(root) => {
+            custom_not_found(format!("missing state {root:?}"))
+        }
+        BeaconChainError::NoStateForSlot(slot) => {
+            custom_not_found(format!("missing state at slot {slot}"))
+        }
+        BeaconChainError::BeaconStateError(BeaconStateError::UnknownValidator(validator_index)) => {
+            custom_bad_request(format!("validator is unknown: {validator_index}"))
+        }
+        BeaconChainError::ValidatorPubkeyUnknown(pubkey) => {
+            custom_bad_request(format!("validator pubkey is unknown: {pubkey:?}"))
+        }
+        e => custom_server_error(format!("unexpected error: {:?}", e)),
+    }
+}
@@ -1,12 +1,17 @@
+use crate::task_spawner::check_cancelled;
 use beacon_chain::{BeaconChain, BeaconChainError, BeaconChainTypes, WhenSlotSkipped};
-use eth2::lighthouse::{BlockReward, BlockRewardsQuery};
+use eth2::lighthouse::{
+    BlockReward, BlockRewardsQuery, PredictedBlockReward, PredictedBlockRewardQuery,
+};
 use lru::LruCache;
 use slog::{debug, warn, Logger};
 use state_processing::BlockReplayer;
 use std::num::NonZeroUsize;
 use std::sync::Arc;
+use task_executor::cancellation;
 use types::beacon_block::BlindedBeaconBlock;
 use types::non_zero_usize::new_non_zero_usize;
+use types::EthSpec;
 use warp_utils::reject::{beacon_chain_error, beacon_state_error, custom_bad_request};
 
 const STATE_CACHE_SIZE: NonZeroUsize = new_non_zero_usize(2);
@@ -38,9 +43,14 @@ pub fn get_block_rewards<T: BeaconChainTypes>(
         .load_blocks_to_replay(start_slot, end_slot, end_block_root)
         .map_err(|e| beacon_chain_error(e.into()))?;
 
-    let state_root = chain
-        .state_root_at_slot(prior_slot)
-        .map_err(beacon_chain_error)?
+    // Load the state roots of the whole range at once, rather than looking up each slot.
+    let state_roots = chain
+        .state_roots_in_range(prior_slot, end_slot)
+        .map_err(beacon_chain_error)?;
+    let state_root = state_roots
+        .first()
+        .filter(|(_, slot)| *slot == prior_slot)
+        .map(|(state_root, _)| *state_root)
         .ok_or_else(|| custom_bad_request(format!("prior state at slot {} unknown", prior_slot)))?;
 
     let mut state = chain
@@ -57,6 +67,9 @@ pub fn get_block_rewards<T: BeaconChainTypes>(
 
     let block_replayer = BlockReplayer::new(state, &chain.spec)
         .pre_block_hook(Box::new(|state, block| {
+            if cancellation::is_cancelled() {
+                return Err(BeaconChainError::Cancelled);
+            }
             state.build_all_committee_caches(&chain.spec)?;
 
             // Compute block reward.
@@ -70,11 +83,7 @@ pub fn get_block_rewards<T: BeaconChainTypes>(
             block_rewards.push(block_reward);
             Ok(())
         }))
-        .state_root_iter(
-            chain
-                .forwards_iter_state_roots_until(prior_slot, end_slot)
-                .map_err(beacon_chain_error)?,
-        )
+        .state_root_iter(state_roots.into_iter().map(Ok::<_, BeaconChainError>))
         .no_signature_verification()
         .minimal_block_root_verification()
         .apply_blocks(blocks, None)
@@ -105,6 +114,7 @@ pub fn compute_block_rewards<T: BeaconChainTypes>(
     let mut reward_cache = Default::default();
 
     for block in blocks {
+        check_cancelled()?;
         let parent_root = block.parent_root();
 
         // Check LRU cache for a constructed state from a previous iteration.
@@ -182,3 +192,27 @@ pub fn compute_block_rewards<T: BeaconChainTypes>(
 
     Ok(block_rewards)
 }
+
+/// Predict the attestation rewards for a block proposed at `query.slot` on top of the current head.
+///
+/// The slot must be after the head, and no more than an epoch in the future so that the op pool
+/// contents remain relevant.
+pub fn get_predicted_block_reward<T: BeaconChainTypes>(
+    query: PredictedBlockRewardQuery,
+    chain: Arc<BeaconChain<T>>,
+) -> Result<PredictedBlockReward, warp::Rejection> {
+    let head_slot = chain.canonical_head.cached_head().head_slot();
+    let current_slot = chain.slot().map_err(beacon_chain_error)?;
+    let max_slot = current_slot + T::EthSpec::slots_per_epoch();
+
+    if query.slot <= head_slot || query.slot > max_slot {
+        return Err(custom_bad_request(format!(
+            "slot {} must be after the head slot {} and no later than {}",
+            query.slot, head_slot, max_slot
+        )));
+    }
+
+    chain
+        .predict_block_reward(query.slot, query.include_attestations)
+        .map_err(beacon_chain_error)
+}
@@ -0,0 +1,107 @@
+//! Contains the handler for the `GET lighthouse/analysis/churn` endpoint.
+
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use eth2::lighthouse::ChurnAnalysis;
+use eth2::types::GenericResponse;
+use safe_arith::SafeArith;
+use std::cmp;
+use types::{BeaconState, BeaconStateError, ChainSpec, EthSpec, RelativeEpoch};
+
+/// Analyses the activation and exit queues of the head state.
+pub fn churn_analysis<T: BeaconChainTypes>(
+    chain: &BeaconChain<T>,
+) -> Result<GenericResponse<ChurnAnalysis>, warp::Rejection> {
+    // Computing the projected exit epoch mutates the state's churn, so use a copy of it.
+    let mut state = chain.head_snapshot().beacon_state.clone();
+    compute_churn_analysis(&mut state, &chain.spec)
+        .map(GenericResponse::from)
+        .map_err(warp_utils::reject::beacon_state_error)
+}
+
+fn compute_churn_analysis<E: EthSpec>(
+    state: &mut BeaconState<E>,
+    spec: &ChainSpec,
+) -> Result<ChurnAnalysis, BeaconStateError> {
+    state.build_committee_cache(RelativeEpoch::Current, spec)?;
+    state.build_exit_cache(spec)?;
+    state.build_total_active_balance_cache(spec)?;
+
+    let epoch = state.current_epoch();
+    let far_future_epoch = spec.far_future_epoch;
+    let activation_queue_length = state
+        .validators()
+        .iter()
+        .filter(|validator| {
+            validator.activation_eligibility_epoch != far_future_epoch
+                && validator.activation_epoch == far_future_epoch
+        })
+        .count() as u64;
+    let exit_queue_length = state
+        .validators()
+        .iter()
+        .filter(|validator| {
+            validator.exit_epoch != far_future_epoch && validator.exit_epoch > epoch
+        })
+        .count() as u64;
+    let delayed_epoch = state.compute_activation_exit_epoch(epoch, spec)?;
+
+    if state.fork_name_unchecked().electra_enabled() {
+        let churn_limit = state.get_activation_exit_churn_limit(spec)?;
+        let pending_deposit_balance = state
+            .pending_balance_deposits()?
+            .iter()
+            .try_fold(0_u64, |total, deposit| total.safe_add(deposit.amount))?;
+
+        // Deposits are applied in order, up to the churn limit each epoch, after which the
+        // validator becomes eligible for activation in the following epoch.
+        let balance_ahead = pending_deposit_balance
+            .safe_add(spec.min_activation_balance)?
+            .saturating_sub(state.deposit_balance_to_consume()?);
+        let deposit_epoch = epoch.safe_add(balance_ahead.div_ceil(cmp::max(churn_limit, 1)))?;
+        let projected_activation_epoch =
+            state.compute_activation_exit_epoch(deposit_epoch.safe_add(1)?, spec)?;
+        let projected_exit_epoch =
+            state.compute_exit_epoch_and_update_churn(spec.min_activation_balance, spec)?;
+
+        Ok(ChurnAnalysis {
+            epoch,
+            activation_queue_length,
+            exit_queue_length,
+            pending_deposit_balance: Some(pending_deposit_balance),
+            activation_churn_limit: churn_limit,
+            exit_churn_limit: churn_limit,
+            projected_activation_epoch,
+            projected_exit_epoch,
+        })
+    } else {
+        let activation_churn_limit = state.get_activation_churn_limit(spec)?;
+        let exit_churn_limit = state.get_validator_churn_limit(spec)?;
+
+        // The queue is dequeued at the activation churn limit, starting from the first epoch at
+        // which an activation triggered now could take effect.
+        let projected_activation_epoch = delayed_epoch
+            .safe_add(activation_queue_length.safe_div(cmp::max(activation_churn_limit, 1))?)?;
+
+        // As per `initiate_validator_exit`.
+        let mut projected_exit_epoch = state
+            .exit_cache()
+            .max_epoch()?
+            .map_or(delayed_epoch, |max_epoch| {
+                cmp::max(max_epoch, delayed_epoch)
+            });
+        if state.exit_cache().get_churn_at(projected_exit_epoch)? >= exit_churn_limit {
+            projected_exit_epoch.safe_add_assign(1)?;
+        }
+
+        Ok(ChurnAnalysis {
+            epoch,
+            activation_queue_length,
+            exit_queue_length,
+            pending_deposit_balance: None,
+            activation_churn_limit,
+            exit_churn_limit,
+            projected_activation_epoch,
+            projected_exit_epoch,
+        })
+    }
+}
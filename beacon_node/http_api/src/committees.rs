@@ -0,0 +1,245 @@
+use crate::state_id::StateId;
+use beacon_chain::{BeaconChain, BeaconChainTypes, WhenSlotSkipped};
+use eth2::types::{
+    self as api_types, CommitteeData, CommitteesQuery, ExecutionOptimisticFinalizedResponse,
+    StateId as CoreStateId,
+};
+use std::sync::Arc;
+use std::time::Duration;
+use types::{
+    AttestationShufflingId, BeaconStateError, CommitteeCache, Epoch, EthSpec, RelativeEpoch, Slot,
+};
+
+/// The maximum time to wait for a lock on the shuffling cache.
+const SHUFFLING_CACHE_LOCK_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Returns the committees of the state identified by `state_id`, as per
+/// `GET beacon/states/{state_id}/committees`.
+///
+/// Committees for canonical states are served from the shuffling cache where possible, which
+/// avoids loading the state from the database.
+pub fn get_beacon_state_committees<T: BeaconChainTypes>(
+    state_id: StateId,
+    chain: Arc<BeaconChain<T>>,
+    query: CommitteesQuery,
+) -> Result<ExecutionOptimisticFinalizedResponse<Vec<CommitteeData>>, warp::Rejection> {
+    if let Some(response) = committees_from_shuffling_cache(&state_id, &chain, &query)? {
+        return Ok(response);
+    }
+
+    let (data, execution_optimistic, finalized) = state_id
+        .map_state_and_execution_optimistic_and_finalized(
+            &chain,
+            |state, execution_optimistic, finalized| {
+                let current_epoch = state.current_epoch();
+                let epoch = query.epoch.unwrap_or(current_epoch);
+
+                // Attempt to obtain the committee_cache from the beacon chain. Find the decision
+                // block and skip to another method on any kind of failure.
+                let shuffling_id = canonical_shuffling_id(&chain, epoch);
+
+                // Attempt to read from the chain cache if there exists a shuffling_id
+                let maybe_cached_shuffling = shuffling_id
+                    .as_ref()
+                    .and_then(|shuffling_id| cached_committee_cache(&chain, shuffling_id));
+
+                let committee_cache = if let Some(shuffling) = maybe_cached_shuffling {
+                    shuffling
+                } else {
+                    let possibly_built_cache =
+                        match RelativeEpoch::from_epoch(current_epoch, epoch) {
+                            Ok(relative_epoch)
+                                if state.committee_cache_is_initialized(relative_epoch) =>
+                            {
+                                state.committee_cache(relative_epoch).cloned()
+                            }
+                            _ => CommitteeCache::initialized(state, epoch, &chain.spec),
+                        }
+                        .map_err(|e| match e {
+                            BeaconStateError::EpochOutOfBounds => {
+                                let max_sprp = T::EthSpec::slots_per_historical_root() as u64;
+                                let first_subsequent_restore_point_slot =
+                                    ((epoch.start_slot(T::EthSpec::slots_per_epoch()) / max_sprp)
+                                        + 1)
+                                        * max_sprp;
+                                if epoch < current_epoch {
+                                    warp_utils::reject::custom_bad_request(format!(
+                                        "epoch out of bounds, try state at slot {}",
+                                        first_subsequent_restore_point_slot,
+                                    ))
+                                } else {
+                                    warp_utils::reject::custom_bad_request(
+                                        "epoch out of bounds, too far in future".into(),
+                                    )
+                                }
+                            }
+                            _ => warp_utils::reject::beacon_chain_error(e.into()),
+                        })?;
+
+                    // Attempt to write to the beacon cache (only if the cache size is not the
+                    // default value).
+                    if chain.config.shuffling_cache_size
+                        != beacon_chain::shuffling_cache::DEFAULT_CACHE_SIZE
+                    {
+                        if let Some(shuffling_id) = shuffling_id {
+                            if let Some(mut cache_write) = chain
+                                .shuffling_cache
+                                .try_write_for(SHUFFLING_CACHE_LOCK_TIMEOUT)
+                            {
+                                cache_write
+                                    .insert_committee_cache(shuffling_id, &possibly_built_cache);
+                            }
+                        }
+                    }
+                    possibly_built_cache
+                };
+
+                let response = committees_response::<T::EthSpec>(&committee_cache, epoch, &query)?;
+
+                Ok((response, execution_optimistic, finalized))
+            },
+        )?;
+
+    Ok(ExecutionOptimisticFinalizedResponse {
+        data,
+        execution_optimistic: Some(execution_optimistic),
+        finalized: Some(finalized),
+    })
+}
+
+/// Attempt to serve the committees of a canonical state from the shuffling cache, without loading
+/// the state.
+///
+/// Returns `Ok(None)` if the committees must be computed from the state.
+fn committees_from_shuffling_cache<T: BeaconChainTypes>(
+    state_id: &StateId,
+    chain: &BeaconChain<T>,
+    query: &CommitteesQuery,
+) -> Result<Option<ExecutionOptimisticFinalizedResponse<Vec<CommitteeData>>>, warp::Rejection> {
+    let slots_per_epoch = T::EthSpec::slots_per_epoch();
+    let cached_head = chain.canonical_head.cached_head();
+
+    // Only consider states which are known to be on the canonical chain without loading them.
+    // The head state is excluded since it is already in memory.
+    let state_slot = match &state_id.0 {
+        CoreStateId::Finalized => cached_head
+            .finalized_checkpoint()
+            .epoch
+            .start_slot(slots_per_epoch),
+        CoreStateId::Justified => cached_head
+            .justified_checkpoint()
+            .epoch
+            .start_slot(slots_per_epoch),
+        CoreStateId::Slot(slot) if *slot <= cached_head.head_slot() => *slot,
+        _ => return Ok(None),
+    };
+
+    // The state can only serve committees up to its next epoch.
+    let state_epoch = state_slot.epoch(slots_per_epoch);
+    let epoch = query.epoch.unwrap_or(state_epoch);
+    if epoch > state_epoch + 1 {
+        return Ok(None);
+    }
+
+    let Some(committee_cache) = canonical_shuffling_id(chain, epoch)
+        .and_then(|shuffling_id| cached_committee_cache(chain, &shuffling_id))
+    else {
+        return Ok(None);
+    };
+
+    let (_, execution_optimistic, finalized) = state_id.root(chain)?;
+    let data = committees_response::<T::EthSpec>(&committee_cache, epoch, query)?;
+
+    Ok(Some(ExecutionOptimisticFinalizedResponse {
+        data,
+        execution_optimistic: Some(execution_optimistic),
+        finalized: Some(finalized),
+    }))
+}
+
+/// Returns the shuffling id of the attester shuffling for `epoch` on the canonical chain, if it
+/// can be determined.
+fn canonical_shuffling_id<T: BeaconChainTypes>(
+    chain: &BeaconChain<T>,
+    epoch: Epoch,
+) -> Option<AttestationShufflingId> {
+    let decision_slot = epoch
+        .saturating_sub(2u64)
+        .end_slot(T::EthSpec::slots_per_epoch());
+    let shuffling_decision_block = if epoch < 2 {
+        chain.genesis_block_root
+    } else {
+        chain
+            .block_root_at_slot(decision_slot, WhenSlotSkipped::Prev)
+            .ok()
+            .flatten()?
+    };
+    Some(AttestationShufflingId {
+        shuffling_epoch: epoch,
+        shuffling_decision_block,
+    })
+}
+
+/// Returns the committee cache for `shuffling_id` from the shuffling cache, waiting for it to be
+/// computed if another task has promised to do so.
+fn cached_committee_cache<T: BeaconChainTypes>(
+    chain: &BeaconChain<T>,
+    shuffling_id: &AttestationShufflingId,
+) -> Option<Arc<CommitteeCache>> {
+    chain
+        .shuffling_cache
+        .try_write_for(SHUFFLING_CACHE_LOCK_TIMEOUT)
+        .and_then(|mut cache_write| cache_write.get(shuffling_id))
+        .and_then(|cache_item| cache_item.wait().ok())
+}
+
+/// Build the response for `query` from the `committee_cache` for `epoch`.
+fn committees_response<E: EthSpec>(
+    committee_cache: &CommitteeCache,
+    epoch: Epoch,
+    query: &CommitteesQuery,
+) -> Result<Vec<CommitteeData>, warp::Rejection> {
+    // Use either the supplied slot or all slots in the epoch.
+    let slots: Vec<Slot> = query
+        .slot
+        .map(|slot| vec![slot])
+        .unwrap_or_else(|| epoch.slot_iter(E::slots_per_epoch()).collect());
+
+    // Use either the supplied committee index or all available indices.
+    let indices = query
+        .index
+        .map(|index| vec![index])
+        .unwrap_or_else(|| (0..committee_cache.committees_per_slot()).collect());
+
+    let mut response = Vec::with_capacity(slots.len() * indices.len());
+
+    for slot in slots {
+        // It is not acceptable to query with a slot that is not within the
+        // specified epoch.
+        if slot.epoch(E::slots_per_epoch()) != epoch {
+            return Err(warp_utils::reject::custom_bad_request(format!(
+                "{} is not in epoch {}",
+                slot, epoch
+            )));
+        }
+
+        for &index in &indices {
+            let committee = committee_cache
+                .get_beacon_committee(slot, index)
+                .ok_or_else(|| {
+                    warp_utils::reject::custom_bad_request(format!(
+                        "committee index {} does not exist in epoch {}",
+                        index, epoch
+                    ))
+                })?;
+
+            response.push(api_types::CommitteeData {
+                index,
+                slot,
+                validators: committee.committee.iter().map(|i| *i as u64).collect(),
+            });
+        }
+    }
+
+    Ok(response)
+}
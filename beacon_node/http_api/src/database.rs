@@ -1,16 +1,30 @@
 use beacon_chain::store::metadata::CURRENT_SCHEMA_VERSION;
 use beacon_chain::{BeaconChain, BeaconChainTypes};
-use eth2::lighthouse::DatabaseInfo;
+use eth2::lighthouse::{DatabaseInfo, DatabaseInfoQuery};
 use std::sync::Arc;
 
 pub fn info<T: BeaconChainTypes>(
     chain: Arc<BeaconChain<T>>,
+    query: DatabaseInfoQuery,
 ) -> Result<DatabaseInfo, warp::Rejection> {
     let store = &chain.store;
     let split = store.get_split_info();
     let config = store.get_config().clone();
     let anchor = store.get_anchor_info();
     let blob_info = store.get_blob_info();
+    // Blobs are retained for `blob_prune_margin_epochs` beyond the data availability boundary.
+    let blob_retention_boundary = chain
+        .data_availability_boundary()
+        .filter(|_| config.prune_blobs)
+        .map(|boundary| boundary.saturating_sub(config.blob_prune_margin_epochs));
+    let sizes = if query.sizes {
+        let sizes = store.column_sizes().map_err(|e| {
+            warp_utils::reject::custom_server_error(format!("unable to read database: {:?}", e))
+        })?;
+        Some(sizes.into())
+    } else {
+        None
+    };
 
     Ok(DatabaseInfo {
         schema_version: CURRENT_SCHEMA_VERSION.as_u64(),
@@ -18,5 +32,7 @@ pub fn info<T: BeaconChainTypes>(
         split,
         anchor,
         blob_info,
+        blob_retention_boundary,
+        sizes,
     })
 }
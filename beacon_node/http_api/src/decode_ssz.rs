@@ -0,0 +1,53 @@
+//! Contains the handler for the `POST lighthouse/debug/decode_ssz` endpoint.
+
+use eth2::lighthouse::{DecodeSszQuery, SszObjectType};
+use eth2::types::GenericResponse;
+use serde::Serialize;
+use ssz::{Decode, DecodeError};
+use types::{
+    Attestation, AttestationBase, AttestationElectra, BeaconState, BlobSidecar, EthSpec,
+    SignedBeaconBlock,
+};
+use warp_utils::reject::{custom_bad_request, custom_server_error};
+
+/// Decodes `bytes` as the SSZ object described by `query`, returning its JSON representation.
+pub fn decode_ssz<E: EthSpec>(
+    query: &DecodeSszQuery,
+    bytes: &[u8],
+) -> Result<GenericResponse<serde_json::Value>, warp::Rejection> {
+    let fork = query.fork;
+    let value = match query.object_type {
+        SszObjectType::Block => {
+            to_json_value(SignedBeaconBlock::<E>::from_ssz_bytes_for_fork(bytes, fork))
+        }
+        SszObjectType::State => {
+            to_json_value(BeaconState::<E>::from_ssz_bytes_for_fork(bytes, fork))
+        }
+        SszObjectType::Attestation => {
+            let attestation = if fork.electra_enabled() {
+                AttestationElectra::from_ssz_bytes(bytes).map(Attestation::<E>::Electra)
+            } else {
+                AttestationBase::from_ssz_bytes(bytes).map(Attestation::<E>::Base)
+            };
+            to_json_value(attestation)
+        }
+        SszObjectType::Blob => {
+            if !fork.deneb_enabled() {
+                return Err(custom_bad_request(format!(
+                    "blobs are not supported at the {fork} fork"
+                )));
+            }
+            to_json_value(BlobSidecar::<E>::from_ssz_bytes(bytes))
+        }
+    }?;
+
+    Ok(GenericResponse::from(value))
+}
+
+fn to_json_value<T: Serialize>(
+    decoded: Result<T, DecodeError>,
+) -> Result<serde_json::Value, warp::Rejection> {
+    let decoded = decoded.map_err(|e| custom_bad_request(format!("invalid SSZ: {e:?}")))?;
+    serde_json::to_value(decoded)
+        .map_err(|e| custom_server_error(format!("unable to serialize JSON: {e:?}")))
+}
@@ -0,0 +1,135 @@
+//! Contains the handler for the `GET lighthouse/deposits/{pubkey}/status` endpoint.
+
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use eth2::lighthouse::{DepositStatus, ObservedDeposit};
+use eth2::types::GenericResponse;
+use std::cmp;
+use std::sync::Arc;
+use types::{BeaconState, BeaconStateError, ChainSpec, Epoch, EthSpec, PublicKeyBytes, Unsigned};
+
+/// Reports the progress of the deposits for `pubkey`, combining the deposit cache of the eth1
+/// service with the deposit and activation queues of the head state.
+pub fn deposit_status<T: BeaconChainTypes>(
+    pubkey: PublicKeyBytes,
+    chain: Arc<BeaconChain<T>>,
+    eth1_service: eth1::Service,
+) -> Result<GenericResponse<DepositStatus>, warp::Rejection> {
+    let eth1_head_number = eth1_service.head_block().map(|block| block.number);
+    let deposits = eth1_service
+        .deposits()
+        .read()
+        .cache
+        .iter()
+        .filter(|log| log.deposit_data.pubkey == pubkey)
+        .map(|log| ObservedDeposit {
+            index: log.index,
+            block_number: log.block_number,
+            amount: log.deposit_data.amount,
+            signature_is_valid: log.signature_is_valid,
+            confirmations: eth1_head_number.map(|head| head.saturating_sub(log.block_number)),
+        })
+        .collect::<Vec<_>>();
+
+    let head = chain.head_snapshot();
+    let state = &head.beacon_state;
+    let spec = &chain.spec;
+    let far_future_epoch = spec.far_future_epoch;
+
+    // The pubkey cache may contain validators which aren't in the head state, so confirm that the
+    // validator exists in it.
+    let validator_index = chain
+        .validator_index(&pubkey)
+        .map_err(warp_utils::reject::beacon_chain_error)?
+        .filter(|&index| {
+            state
+                .validators()
+                .get(index)
+                .map_or(false, |validator| validator.pubkey == pubkey)
+        });
+
+    let (deposits_ahead, estimated_inclusion_slot) = match (validator_index, deposits.first()) {
+        (None, Some(first_deposit)) => {
+            let deposits_ahead = first_deposit
+                .index
+                .saturating_sub(state.eth1_deposit_index());
+            // Deposits are only processed once the `eth1_data` which includes them has been voted
+            // in, after which blocks must include up to `MAX_DEPOSITS` each.
+            let inclusion_slot =
+                (first_deposit.index < state.eth1_data().deposit_count).then(|| {
+                    state.slot()
+                        + deposits_ahead / <T::EthSpec as EthSpec>::MaxDeposits::to_u64()
+                        + 1
+                });
+            (Some(deposits_ahead), inclusion_slot)
+        }
+        _ => (None, None),
+    };
+
+    let validator = validator_index.and_then(|index| state.validators().get(index));
+    let activation_eligibility_epoch = validator
+        .map(|validator| validator.activation_eligibility_epoch)
+        .filter(|epoch| *epoch != far_future_epoch);
+    let expected_activation_epoch = match (validator_index, validator) {
+        (Some(index), Some(validator)) if validator.activation_epoch == far_future_epoch => {
+            if activation_eligibility_epoch.is_some() {
+                Some(estimate_activation_epoch(state, index, spec).map_err(|e| {
+                    warp_utils::reject::custom_server_error(format!(
+                        "unable to estimate activation epoch: {e:?}"
+                    ))
+                })?)
+            } else {
+                None
+            }
+        }
+        (_, validator) => validator.map(|validator| validator.activation_epoch),
+    };
+
+    Ok(GenericResponse::from(DepositStatus {
+        pubkey,
+        deposits,
+        validator_index: validator_index.map(|index| index as u64),
+        deposits_ahead,
+        estimated_inclusion_slot,
+        activation_eligibility_epoch,
+        expected_activation_epoch,
+    }))
+}
+
+/// Estimates the epoch at which the validator at `validator_index`, which is eligible for
+/// activation but not yet activated, will be activated.
+///
+/// Validators are dequeued in order of eligibility epoch and then index. Before Electra at most
+/// the activation churn limit are dequeued per epoch, whereas from Electra onwards all eligible
+/// validators are dequeued at once. The estimate assumes the eligibility epoch is finalized
+/// promptly and that the churn limit remains constant.
+fn estimate_activation_epoch<E: EthSpec>(
+    state: &BeaconState<E>,
+    validator_index: usize,
+    spec: &ChainSpec,
+) -> Result<Epoch, BeaconStateError> {
+    let validator = state.get_validator(validator_index)?;
+    let queue_start = cmp::max(
+        state.current_epoch(),
+        validator.activation_eligibility_epoch,
+    );
+    let first_activation_epoch = state.compute_activation_exit_epoch(queue_start, spec)?;
+
+    if state.fork_name_unchecked().electra_enabled() {
+        return Ok(first_activation_epoch);
+    }
+
+    let queue_key = (validator.activation_eligibility_epoch, validator_index);
+    let position = state
+        .validators()
+        .iter()
+        .enumerate()
+        .filter(|(index, other)| {
+            other.activation_epoch == spec.far_future_epoch
+                && other.activation_eligibility_epoch != spec.far_future_epoch
+                && (other.activation_eligibility_epoch, *index) < queue_key
+        })
+        .count() as u64;
+    let churn_limit = state.get_activation_churn_limit(spec)?;
+
+    Ok(first_activation_epoch + position / cmp::max(churn_limit, 1))
+}
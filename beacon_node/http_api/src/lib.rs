@@ -5,20 +5,31 @@
 //! There are also some additional, non-standard endpoints behind the `/lighthouse/` path which are
 //! used for development.
 
+mod all_duties;
 mod attestation_performance;
+mod attestation_rewards;
 mod attester_duties;
 mod block_id;
 mod block_packing_efficiency;
 mod block_rewards;
 mod build_block_contents;
 mod builder_states;
+mod churn;
+mod committees;
 mod database;
+mod decode_ssz;
+mod deposit_status;
 mod metrics;
+mod peers;
 mod produce_block;
+mod profile;
+mod proofs;
 mod proposer_duties;
 mod publish_attestations;
 mod publish_blocks;
+mod reward_units;
 mod standard_block_rewards;
+mod state_diff;
 mod state_id;
 mod sync_committee_rewards;
 mod sync_committees;
@@ -27,6 +38,7 @@ pub mod test_utils;
 mod ui;
 mod validator;
 mod validator_inclusion;
+mod validator_set_changes;
 mod validators;
 mod version;
 
@@ -34,10 +46,13 @@ use crate::produce_block::{produce_blinded_block_v2, produce_block_v2, produce_b
 use crate::version::fork_versioned_response;
 use beacon_chain::{
     attestation_verification::VerifiedAttestation, observed_operations::ObservationOutcome,
-    validator_monitor::timestamp_now, AttestationError as AttnError, BeaconChain, BeaconChainError,
-    BeaconChainTypes, WhenSlotSkipped,
+    partial_block_body::Error as PartialBlockBodyError, validator_monitor::timestamp_now,
+    AttestationError as AttnError, BeaconChain, BeaconChainError, BeaconChainTypes,
+    WhenSlotSkipped,
+};
+use beacon_processor::{
+    work_reprocessing_queue::ReprocessQueueMessage, BeaconProcessorQueueStats, BeaconProcessorSend,
 };
-use beacon_processor::{work_reprocessing_queue::ReprocessQueueMessage, BeaconProcessorSend};
 pub use block_id::BlockId;
 use builder_states::get_next_withdrawals;
 use bytes::Bytes;
@@ -50,7 +65,7 @@ use eth2::types::{
 use eth2::{CONSENSUS_VERSION_HEADER, CONTENT_TYPE_HEADER, SSZ_CONTENT_TYPE_HEADER};
 use lighthouse_network::{types::SyncState, EnrExt, NetworkGlobals, PeerId, PubsubMessage};
 use lighthouse_version::version_with_platform;
-use logging::SSELoggingComponents;
+use logging::{ModuleLevels, SSELoggingComponents};
 use network::{NetworkMessage, NetworkSenders, ValidatorSubscriptionMessage};
 use operation_pool::ReceivedPreCapella;
 use parking_lot::RwLock;
@@ -62,11 +77,13 @@ use slog::{crit, debug, error, info, warn, Logger};
 use slot_clock::SlotClock;
 use ssz::Encode;
 pub use state_id::StateId;
+use std::cmp::Ordering;
 use std::future::Future;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 use std::path::PathBuf;
 use std::pin::Pin;
 use std::sync::Arc;
+use std::time::{Duration, UNIX_EPOCH};
 use sysinfo::{System, SystemExt};
 use system_health::{observe_nat, observe_system_health_bn};
 use task_spawner::{Priority, TaskSpawner};
@@ -79,12 +96,12 @@ use tokio_stream::{
     StreamExt,
 };
 use types::{
-    fork_versioned_response::EmptyMetadata, Attestation, AttestationData, AttestationShufflingId,
-    AttesterSlashing, BeaconStateError, CommitteeCache, ConfigAndPreset, Epoch, EthSpec, ForkName,
-    ForkVersionedResponse, Hash256, ProposerPreparationData, ProposerSlashing, RelativeEpoch,
-    SignedAggregateAndProof, SignedBlindedBeaconBlock, SignedBlsToExecutionChange,
-    SignedContributionAndProof, SignedValidatorRegistrationData, SignedVoluntaryExit, Slot,
-    SyncCommitteeMessage, SyncContributionData,
+    fork_versioned_response::EmptyMetadata, Attestation, AttestationData, AttesterSlashing,
+    BeaconStateError, ConfigAndPreset, Epoch, EthSpec, ForkName, ForkVersionedResponse, Hash256,
+    ProposerPreparationData, ProposerSlashing, PublicKeyBytes, SignedAggregateAndProof,
+    SignedBlindedBeaconBlock, SignedBlsToExecutionChange, SignedContributionAndProof,
+    SignedValidatorRegistrationData, SignedVoluntaryExit, Slot, SyncCommitteeMessage,
+    SyncContributionData,
 };
 use validator::pubkey_to_validator_index;
 use version::{
@@ -131,8 +148,10 @@ pub struct Context<T: BeaconChainTypes> {
     pub network_globals: Option<Arc<NetworkGlobals<T::EthSpec>>>,
     pub beacon_processor_send: Option<BeaconProcessorSend<T::EthSpec>>,
     pub beacon_processor_reprocess_send: Option<Sender<ReprocessQueueMessage>>,
+    pub beacon_processor_queue_stats: Option<BeaconProcessorQueueStats>,
     pub eth1_service: Option<eth1::Service>,
     pub sse_logging_components: Option<SSELoggingComponents>,
+    pub module_levels: Option<ModuleLevels>,
     pub log: Logger,
 }
 
@@ -151,6 +170,10 @@ pub struct Config {
     #[serde(with = "eth2::types::serde_status_code")]
     pub duplicate_block_status_code: StatusCode,
     pub enable_light_client_server: bool,
+    /// Serve `GET lighthouse/debug/profile`, which captures CPU profiles of the process.
+    pub enable_profiling: bool,
+    /// Timeouts for requests to paths starting with each route, see `Config::route_timeout`.
+    pub route_timeouts: Vec<(String, Duration)>,
 }
 
 impl Default for Config {
@@ -167,10 +190,24 @@ impl Default for Config {
             enable_beacon_processor: true,
             duplicate_block_status_code: StatusCode::ACCEPTED,
             enable_light_client_server: false,
+            enable_profiling: false,
+            route_timeouts: vec![],
         }
     }
 }
 
+impl Config {
+    /// Returns the timeout for requests to `path`, from the longest route in `route_timeouts`
+    /// which is a prefix of `path`.
+    pub fn route_timeout(&self, path: &str) -> Option<Duration> {
+        self.route_timeouts
+            .iter()
+            .filter(|(route, _)| path.starts_with(route.as_str()))
+            .max_by_key(|(route, _)| route.len())
+            .map(|(_, timeout)| *timeout)
+    }
+}
+
 #[derive(Debug)]
 pub enum Error {
     Warp(warp::Error),
@@ -525,19 +562,48 @@ pub fn serve<T: BeaconChainTypes>(
     let app_start = std::time::Instant::now();
     let app_start_filter = warp::any().map(move || app_start);
 
+    // Create a `warp` filter that provides access to the tree hashes of recently proven states.
+    let state_tree_cache = Arc::new(proofs::StateTreeCache::default());
+    let state_tree_cache_filter = warp::any().map(move || state_tree_cache.clone());
+
     // Create a `warp` filter that provides access to the `TaskSpawner`.
     let beacon_processor_send = ctx
         .beacon_processor_send
         .clone()
         .filter(|_| config.enable_beacon_processor);
-    let task_spawner_filter =
-        warp::any().map(move || TaskSpawner::new(beacon_processor_send.clone()));
+    let route_timeouts_config = config.clone();
+    let task_spawner_filter = warp::method().and(warp::path::full()).map(
+        move |method: warp::http::Method, path: warp::path::FullPath| {
+            let task_spawner = TaskSpawner::new(beacon_processor_send.clone())
+                .with_timeout(route_timeouts_config.route_timeout(path.as_str()));
+            // Only `GET` requests are cancelled by default, as they never modify any state. Other
+            // read-only routes opt in with `TaskSpawner::cancellable`.
+            if method == warp::http::Method::GET {
+                task_spawner.cancellable()
+            } else {
+                task_spawner
+            }
+        },
+    );
     let beacon_processor_reprocess_send = ctx
         .beacon_processor_reprocess_send
         .clone()
         .filter(|_| config.enable_beacon_processor);
     let reprocess_send_filter = warp::any().map(move || beacon_processor_reprocess_send.clone());
 
+    // Create a `warp` filter that provides access to the beacon processor queue statistics.
+    let beacon_processor_queue_stats = ctx.beacon_processor_queue_stats.clone();
+    let beacon_processor_queue_stats_filter = warp::any()
+        .map(move || beacon_processor_queue_stats.clone())
+        .and_then(|queue_stats| async move {
+            match queue_stats {
+                Some(queue_stats) => Ok(queue_stats),
+                None => Err(warp_utils::reject::custom_not_found(
+                    "The beacon processor is not started.".to_string(),
+                )),
+            }
+        });
+
     let duplicate_block_status_code = ctx.config.duplicate_block_status_code;
 
     /*
@@ -691,6 +757,7 @@ pub fn serve<T: BeaconChainTypes>(
              task_spawner: TaskSpawner<T::EthSpec>,
              chain: Arc<BeaconChain<T>>,
              query: ValidatorBalancesRequestBody| {
+                let task_spawner = task_spawner.cancellable();
                 task_spawner.blocking_json_task(Priority::P1, move || {
                     crate::validators::get_beacon_state_validator_balances(
                         state_id,
@@ -701,7 +768,7 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
-    // GET beacon/states/{state_id}/validators?id,status
+    // GET beacon/states/{state_id}/validators?id,status,offset,limit
     let get_beacon_state_validators = beacon_states_path
         .clone()
         .and(warp::path("validators"))
@@ -719,6 +786,8 @@ pub fn serve<T: BeaconChainTypes>(
                         chain,
                         &query.id,
                         &query.status,
+                        query.offset.unwrap_or(0),
+                        query.limit,
                     )
                 })
             },
@@ -735,12 +804,15 @@ pub fn serve<T: BeaconChainTypes>(
              task_spawner: TaskSpawner<T::EthSpec>,
              chain: Arc<BeaconChain<T>>,
              query: ValidatorsRequestBody| {
+                let task_spawner = task_spawner.cancellable();
                 task_spawner.blocking_json_task(Priority::P1, move || {
                     crate::validators::get_beacon_state_validators(
                         state_id,
                         chain,
                         &query.ids,
                         &query.statuses,
+                        0,
+                        None,
                     )
                 })
             },
@@ -830,170 +902,7 @@ pub fn serve<T: BeaconChainTypes>(
              chain: Arc<BeaconChain<T>>,
              query: api_types::CommitteesQuery| {
                 task_spawner.blocking_json_task(Priority::P1, move || {
-                    let (data, execution_optimistic, finalized) = state_id
-                        .map_state_and_execution_optimistic_and_finalized(
-                            &chain,
-                            |state, execution_optimistic, finalized| {
-                                let current_epoch = state.current_epoch();
-                                let epoch = query.epoch.unwrap_or(current_epoch);
-
-                                // Attempt to obtain the committee_cache from the beacon chain
-                                let decision_slot = (epoch.saturating_sub(2u64))
-                                    .end_slot(T::EthSpec::slots_per_epoch());
-                                // Find the decision block and skip to another method on any kind
-                                // of failure
-                                let shuffling_id = if let Ok(Some(shuffling_decision_block)) =
-                                    chain.block_root_at_slot(decision_slot, WhenSlotSkipped::Prev)
-                                {
-                                    Some(AttestationShufflingId {
-                                        shuffling_epoch: epoch,
-                                        shuffling_decision_block,
-                                    })
-                                } else {
-                                    None
-                                };
-
-                                // Attempt to read from the chain cache if there exists a
-                                // shuffling_id
-                                let maybe_cached_shuffling = if let Some(shuffling_id) =
-                                    shuffling_id.as_ref()
-                                {
-                                    chain
-                                        .shuffling_cache
-                                        .try_write_for(std::time::Duration::from_secs(1))
-                                        .and_then(|mut cache_write| cache_write.get(shuffling_id))
-                                        .and_then(|cache_item| cache_item.wait().ok())
-                                } else {
-                                    None
-                                };
-
-                                let committee_cache = if let Some(shuffling) =
-                                    maybe_cached_shuffling
-                                {
-                                    shuffling
-                                } else {
-                                    let possibly_built_cache =
-                                        match RelativeEpoch::from_epoch(current_epoch, epoch) {
-                                            Ok(relative_epoch)
-                                                if state.committee_cache_is_initialized(
-                                                    relative_epoch,
-                                                ) =>
-                                            {
-                                                state.committee_cache(relative_epoch).cloned()
-                                            }
-                                            _ => CommitteeCache::initialized(
-                                                state,
-                                                epoch,
-                                                &chain.spec,
-                                            ),
-                                        }
-                                        .map_err(|e| {
-                                            match e {
-                                                BeaconStateError::EpochOutOfBounds => {
-                                                    let max_sprp =
-                                                        T::EthSpec::slots_per_historical_root()
-                                                            as u64;
-                                                    let first_subsequent_restore_point_slot =
-                                                        ((epoch.start_slot(
-                                                            T::EthSpec::slots_per_epoch(),
-                                                        ) / max_sprp)
-                                                            + 1)
-                                                            * max_sprp;
-                                                    if epoch < current_epoch {
-                                                        warp_utils::reject::custom_bad_request(
-                                                            format!(
-                                                                "epoch out of bounds, \
-                                                                 try state at slot {}",
-                                                                first_subsequent_restore_point_slot,
-                                                            ),
-                                                        )
-                                                    } else {
-                                                        warp_utils::reject::custom_bad_request(
-                                                            "epoch out of bounds, \
-                                                             too far in future"
-                                                                .into(),
-                                                        )
-                                                    }
-                                                }
-                                                _ => {
-                                                    warp_utils::reject::beacon_chain_error(e.into())
-                                                }
-                                            }
-                                        })?;
-
-                                    // Attempt to write to the beacon cache (only if the cache
-                                    // size is not the default value).
-                                    if chain.config.shuffling_cache_size
-                                        != beacon_chain::shuffling_cache::DEFAULT_CACHE_SIZE
-                                    {
-                                        if let Some(shuffling_id) = shuffling_id {
-                                            if let Some(mut cache_write) = chain
-                                                .shuffling_cache
-                                                .try_write_for(std::time::Duration::from_secs(1))
-                                            {
-                                                cache_write.insert_committee_cache(
-                                                    shuffling_id,
-                                                    &possibly_built_cache,
-                                                );
-                                            }
-                                        }
-                                    }
-                                    possibly_built_cache
-                                };
-
-                                // Use either the supplied slot or all slots in the epoch.
-                                let slots =
-                                    query.slot.map(|slot| vec![slot]).unwrap_or_else(|| {
-                                        epoch.slot_iter(T::EthSpec::slots_per_epoch()).collect()
-                                    });
-
-                                // Use either the supplied committee index or all available indices.
-                                let indices =
-                                    query.index.map(|index| vec![index]).unwrap_or_else(|| {
-                                        (0..committee_cache.committees_per_slot()).collect()
-                                    });
-
-                                let mut response = Vec::with_capacity(slots.len() * indices.len());
-
-                                for slot in slots {
-                                    // It is not acceptable to query with a slot that is not within the
-                                    // specified epoch.
-                                    if slot.epoch(T::EthSpec::slots_per_epoch()) != epoch {
-                                        return Err(warp_utils::reject::custom_bad_request(
-                                            format!("{} is not in epoch {}", slot, epoch),
-                                        ));
-                                    }
-
-                                    for &index in &indices {
-                                        let committee = committee_cache
-                                            .get_beacon_committee(slot, index)
-                                            .ok_or_else(|| {
-                                                warp_utils::reject::custom_bad_request(format!(
-                                                    "committee index {} does not exist in epoch {}",
-                                                    index, epoch
-                                                ))
-                                            })?;
-
-                                        response.push(api_types::CommitteeData {
-                                            index,
-                                            slot,
-                                            validators: committee
-                                                .committee
-                                                .iter()
-                                                .map(|i| *i as u64)
-                                                .collect(),
-                                        });
-                                    }
-                                }
-
-                                Ok((response, execution_optimistic, finalized))
-                            },
-                        )?;
-                    Ok(api_types::ExecutionOptimisticFinalizedResponse {
-                        data,
-                        execution_optimistic: Some(execution_optimistic),
-                        finalized: Some(finalized),
-                    })
+                    crate::committees::get_beacon_state_committees(state_id, chain, query)
                 })
             },
         );
@@ -1256,6 +1165,7 @@ pub fn serve<T: BeaconChainTypes>(
     let post_beacon_blocks = eth_v1
         .and(warp::path("beacon"))
         .and(warp::path("blocks"))
+        .and(warp::query::<api_types::BroadcastValidationQuery>())
         .and(warp::path::end())
         .and(warp_utils::json::json())
         .and(task_spawner_filter.clone())
@@ -1263,7 +1173,8 @@ pub fn serve<T: BeaconChainTypes>(
         .and(network_tx_filter.clone())
         .and(log_filter.clone())
         .then(
-            move |block_contents: PublishBlockRequest<T::EthSpec>,
+            move |validation_level: api_types::BroadcastValidationQuery,
+                  block_contents: PublishBlockRequest<T::EthSpec>,
                   task_spawner: TaskSpawner<T::EthSpec>,
                   chain: Arc<BeaconChain<T>>,
                   network_tx: UnboundedSender<NetworkMessage<T::EthSpec>>,
@@ -1275,7 +1186,7 @@ pub fn serve<T: BeaconChainTypes>(
                         chain,
                         &network_tx,
                         log,
-                        BroadcastValidation::default(),
+                        validation_level.broadcast_validation,
                         duplicate_block_status_code,
                     )
                     .await
@@ -1286,6 +1197,7 @@ pub fn serve<T: BeaconChainTypes>(
     let post_beacon_blocks_ssz = eth_v1
         .and(warp::path("beacon"))
         .and(warp::path("blocks"))
+        .and(warp::query::<api_types::BroadcastValidationQuery>())
         .and(warp::path::end())
         .and(warp::body::bytes())
         .and(consensus_version_header_filter)
@@ -1294,7 +1206,8 @@ pub fn serve<T: BeaconChainTypes>(
         .and(network_tx_filter.clone())
         .and(log_filter.clone())
         .then(
-            move |block_bytes: Bytes,
+            move |validation_level: api_types::BroadcastValidationQuery,
+                  block_bytes: Bytes,
                   consensus_version: ForkName,
                   task_spawner: TaskSpawner<T::EthSpec>,
                   chain: Arc<BeaconChain<T>>,
@@ -1314,7 +1227,7 @@ pub fn serve<T: BeaconChainTypes>(
                         chain,
                         &network_tx,
                         log,
-                        BroadcastValidation::default(),
+                        validation_level.broadcast_validation,
                         duplicate_block_status_code,
                     )
                     .await
@@ -1912,45 +1825,52 @@ pub fn serve<T: BeaconChainTypes>(
         );
 
     // GET beacon/pool/attester_slashings
-    let get_beacon_pool_attester_slashings =
-        beacon_pool_path_any
-            .clone()
-            .and(warp::path("attester_slashings"))
-            .and(warp::path::end())
-            .then(
-                |endpoint_version: EndpointVersion,
-                 task_spawner: TaskSpawner<T::EthSpec>,
-                 chain: Arc<BeaconChain<T>>| {
-                    task_spawner.blocking_response_task(Priority::P1, move || {
-                        let slashings = chain.op_pool.get_all_attester_slashings();
-
-                        // Use the current slot to find the fork version, and convert all messages to the
-                        // current fork's format. This is to ensure consistent message types matching
-                        // `Eth-Consensus-Version`.
-                        let current_slot = chain.slot_clock.now().ok_or(
-                            warp_utils::reject::custom_server_error(
+    let get_beacon_pool_attester_slashings = beacon_pool_path_any
+        .clone()
+        .and(warp::path("attester_slashings"))
+        .and(warp::path::end())
+        .and(warp::header::optional::<api_types::Accept>("accept"))
+        .then(
+            |endpoint_version: EndpointVersion,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>,
+             accept_header: Option<api_types::Accept>| {
+                task_spawner.blocking_response_task(Priority::P1, move || {
+                    let slashings = chain.op_pool.get_all_attester_slashings();
+
+                    // Use the current slot to find the fork version, and convert all messages to the
+                    // current fork's format. This is to ensure consistent message types matching
+                    // `Eth-Consensus-Version`.
+                    let current_slot =
+                        chain
+                            .slot_clock
+                            .now()
+                            .ok_or(warp_utils::reject::custom_server_error(
                                 "unable to read slot clock".to_string(),
-                            ),
-                        )?;
-                        let fork_name = chain.spec.fork_name_at_slot::<T::EthSpec>(current_slot);
-                        let slashings = slashings
-                            .into_iter()
-                            .filter(|slashing| {
-                                (fork_name.electra_enabled()
-                                    && matches!(slashing, AttesterSlashing::Electra(_)))
-                                    || (!fork_name.electra_enabled()
-                                        && matches!(slashing, AttesterSlashing::Base(_)))
-                            })
-                            .collect::<Vec<_>>();
+                            ))?;
+                    let fork_name = chain.spec.fork_name_at_slot::<T::EthSpec>(current_slot);
+                    let slashings = slashings
+                        .into_iter()
+                        .filter(|slashing| {
+                            (fork_name.electra_enabled()
+                                && matches!(slashing, AttesterSlashing::Electra(_)))
+                                || (!fork_name.electra_enabled()
+                                    && matches!(slashing, AttesterSlashing::Base(_)))
+                        })
+                        .collect::<Vec<_>>();
 
-                        let res = fork_versioned_response(endpoint_version, fork_name, &slashings)?;
-                        Ok(add_consensus_version_header(
-                            warp::reply::json(&res).into_response(),
-                            fork_name,
-                        ))
-                    })
-                },
-            );
+                    let response = match accept_header {
+                        Some(api_types::Accept::Ssz) => ssz_response(slashings.as_ssz_bytes())?,
+                        _ => {
+                            let res =
+                                fork_versioned_response(endpoint_version, fork_name, &slashings)?;
+                            warp::reply::json(&res).into_response()
+                        }
+                    };
+                    Ok(add_consensus_version_header(response, fork_name))
+                })
+            },
+        );
 
     // POST beacon/pool/proposer_slashings
     let post_beacon_pool_proposer_slashings = beacon_pool_path
@@ -2001,11 +1921,20 @@ pub fn serve<T: BeaconChainTypes>(
         .clone()
         .and(warp::path("proposer_slashings"))
         .and(warp::path::end())
+        .and(warp::header::optional::<api_types::Accept>("accept"))
         .then(
-            |task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
-                task_spawner.blocking_json_task(Priority::P1, move || {
-                    let attestations = chain.op_pool.get_all_proposer_slashings();
-                    Ok(api_types::GenericResponse::from(attestations))
+            |task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>,
+             accept_header: Option<api_types::Accept>| {
+                task_spawner.blocking_response_task(Priority::P1, move || {
+                    let slashings = chain.op_pool.get_all_proposer_slashings();
+                    match accept_header {
+                        Some(api_types::Accept::Ssz) => ssz_response(slashings.as_ssz_bytes()),
+                        _ => Ok(
+                            warp::reply::json(&api_types::GenericResponse::from(slashings))
+                                .into_response(),
+                        ),
+                    }
                 })
             },
         );
@@ -2257,16 +2186,35 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp::path("blocks"))
         .and(block_id_or_err)
         .and(warp::path::end())
+        .and(warp::query::<api_types::RewardsQuery>())
+        .and(warp::header::optional::<api_types::Accept>("accept"))
         .then(
             |task_spawner: TaskSpawner<T::EthSpec>,
              chain: Arc<BeaconChain<T>>,
-             block_id: BlockId| {
-                task_spawner.blocking_json_task(Priority::P1, move || {
+             block_id: BlockId,
+             query: api_types::RewardsQuery,
+             accept_header: Option<api_types::Accept>| {
+                task_spawner.blocking_response_task(Priority::P1, move || {
+                    check_reward_units_accept(query.units, accept_header)?;
                     let (rewards, execution_optimistic, finalized) =
                         standard_block_rewards::compute_beacon_block_rewards(chain, block_id)?;
-                    Ok(api_types::GenericResponse::from(rewards)).map(|resp| {
-                        resp.add_execution_optimistic_finalized(execution_optimistic, finalized)
-                    })
+                    match (accept_header, query.units) {
+                        (Some(api_types::Accept::Ssz), _) => ssz_response(rewards.as_ssz_bytes()),
+                        (_, Some(units)) => reward_units::rewards_in_units_response(
+                            rewards,
+                            execution_optimistic,
+                            Some(finalized),
+                            units,
+                        ),
+                        _ => Ok(warp::reply::json(
+                            &api_types::GenericResponse::from(rewards)
+                                .add_execution_optimistic_finalized(
+                                    execution_optimistic,
+                                    finalized,
+                                ),
+                        )
+                        .into_response()),
+                    }
                 })
             },
         );
@@ -2500,46 +2448,56 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp::path("attestations"))
         .and(warp::path::param::<Epoch>())
         .and(warp::path::end())
+        .and(warp::query::<api_types::AttestationRewardsQuery>())
         .and(warp_utils::json::json())
+        .and(warp::header::optional::<api_types::Accept>("accept"))
         .then(
             |task_spawner: TaskSpawner<T::EthSpec>,
              chain: Arc<BeaconChain<T>>,
              epoch: Epoch,
-             validators: Vec<ValidatorId>| {
-                task_spawner.blocking_json_task(Priority::P1, move || {
-                    let attestation_rewards = chain
-                        .compute_attestation_rewards(epoch, validators)
-                        .map_err(|e| match e {
-                            BeaconChainError::MissingBeaconState(root) => {
-                                warp_utils::reject::custom_not_found(format!(
-                                    "missing state {root:?}",
-                                ))
-                            }
-                            BeaconChainError::NoStateForSlot(slot) => {
-                                warp_utils::reject::custom_not_found(format!(
-                                    "missing state at slot {slot}"
-                                ))
-                            }
-                            BeaconChainError::BeaconStateError(
-                                BeaconStateError::UnknownValidator(validator_index),
-                            ) => warp_utils::reject::custom_bad_request(format!(
-                                "validator is unknown: {validator_index}"
-                            )),
-                            BeaconChainError::ValidatorPubkeyUnknown(pubkey) => {
-                                warp_utils::reject::custom_bad_request(format!(
-                                    "validator pubkey is unknown: {pubkey:?}"
-                                ))
-                            }
-                            e => warp_utils::reject::custom_server_error(format!(
-                                "unexpected error: {:?}",
-                                e
-                            )),
-                        })?;
+             query: api_types::AttestationRewardsQuery,
+             validators: Vec<ValidatorId>,
+             accept_header: Option<api_types::Accept>| {
+                let task_spawner = task_spawner.cancellable();
+                task_spawner.blocking_response_task(Priority::P1, move || {
+                    let paged = query.page_size.is_some() || query.page_token.is_some();
+                    if paged && accept_header == Some(api_types::Accept::Ssz) {
+                        return Err(warp_utils::reject::custom_bad_request(
+                            "pagination is not supported for SSZ responses".to_string(),
+                        ));
+                    }
+                    check_reward_units_accept(query.units, accept_header)?;
+                    let units = query.units;
+
+                    let attestation_rewards = attestation_rewards::compute_attestation_rewards(
+                        chain.clone(),
+                        epoch,
+                        validators,
+                        query,
+                    )?;
                     let execution_optimistic =
                         chain.is_optimistic_or_invalid_head().unwrap_or_default();
 
-                    Ok(api_types::GenericResponse::from(attestation_rewards))
-                        .map(|resp| resp.add_execution_optimistic(execution_optimistic))
+                    match (accept_header, units) {
+                        (Some(api_types::Accept::Ssz), _) => ssz_response(
+                            eth2::lighthouse::StandardAttestationRewards {
+                                ideal_rewards: attestation_rewards.ideal_rewards,
+                                total_rewards: attestation_rewards.total_rewards,
+                            }
+                            .as_ssz_bytes(),
+                        ),
+                        (_, Some(units)) => reward_units::rewards_in_units_response(
+                            attestation_rewards,
+                            execution_optimistic,
+                            None,
+                            units,
+                        ),
+                        _ => Ok(warp::reply::json(
+                            &api_types::GenericResponse::from(attestation_rewards)
+                                .add_execution_optimistic(execution_optimistic),
+                        )
+                        .into_response()),
+                    }
                 })
             },
         );
@@ -2550,23 +2508,43 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp::path("sync_committee"))
         .and(block_id_or_err)
         .and(warp::path::end())
+        .and(warp::query::<api_types::RewardsQuery>())
         .and(warp_utils::json::json())
         .and(log_filter.clone())
+        .and(warp::header::optional::<api_types::Accept>("accept"))
         .then(
             |task_spawner: TaskSpawner<T::EthSpec>,
              chain: Arc<BeaconChain<T>>,
              block_id: BlockId,
+             query: api_types::RewardsQuery,
              validators: Vec<ValidatorId>,
-             log: Logger| {
-                task_spawner.blocking_json_task(Priority::P1, move || {
+             log: Logger,
+             accept_header: Option<api_types::Accept>| {
+                let task_spawner = task_spawner.cancellable();
+                task_spawner.blocking_response_task(Priority::P1, move || {
+                    check_reward_units_accept(query.units, accept_header)?;
                     let (rewards, execution_optimistic, finalized) =
                         sync_committee_rewards::compute_sync_committee_rewards(
                             chain, block_id, validators, log,
                         )?;
 
-                    Ok(api_types::GenericResponse::from(rewards)).map(|resp| {
-                        resp.add_execution_optimistic_finalized(execution_optimistic, finalized)
-                    })
+                    match (accept_header, query.units) {
+                        (Some(api_types::Accept::Ssz), _) => ssz_response(rewards.as_ssz_bytes()),
+                        (_, Some(units)) => reward_units::rewards_in_units_response(
+                            rewards,
+                            execution_optimistic,
+                            Some(finalized),
+                            units,
+                        ),
+                        _ => Ok(warp::reply::json(
+                            &api_types::GenericResponse::from(rewards)
+                                .add_execution_optimistic_finalized(
+                                    execution_optimistic,
+                                    finalized,
+                                ),
+                        )
+                        .into_response()),
+                    }
                 })
             },
         );
@@ -3020,54 +2998,7 @@ pub fn serve<T: BeaconChainTypes>(
              network_globals: Arc<NetworkGlobals<T::EthSpec>>| {
                 task_spawner.blocking_json_task(Priority::P1, move || {
                     let query = query_res?;
-                    let mut peers: Vec<api_types::PeerData> = Vec::new();
-                    network_globals
-                        .peers
-                        .read()
-                        .peers()
-                        .for_each(|(peer_id, peer_info)| {
-                            let address =
-                                if let Some(multiaddr) = peer_info.seen_multiaddrs().next() {
-                                    multiaddr.to_string()
-                                } else if let Some(addr) = peer_info.listening_addresses().first() {
-                                    addr.to_string()
-                                } else {
-                                    String::new()
-                                };
-
-                            // the eth2 API spec implies only peers we have been connected to at some point should be included.
-                            if let Some(dir) = peer_info.connection_direction() {
-                                let direction =
-                                    api_types::PeerDirection::from_connection_direction(dir);
-                                let state = api_types::PeerState::from_peer_connection_status(
-                                    peer_info.connection_status(),
-                                );
-
-                                let state_matches = query.state.as_ref().map_or(true, |states| {
-                                    states.iter().any(|state_param| *state_param == state)
-                                });
-                                let direction_matches =
-                                    query.direction.as_ref().map_or(true, |directions| {
-                                        directions.iter().any(|dir_param| *dir_param == direction)
-                                    });
-
-                                if state_matches && direction_matches {
-                                    peers.push(api_types::PeerData {
-                                        peer_id: peer_id.to_string(),
-                                        enr: peer_info.enr().map(|enr| enr.to_base64()),
-                                        last_seen_p2p_address: address,
-                                        direction,
-                                        state,
-                                    });
-                                }
-                            }
-                        });
-                    Ok(api_types::PeersData {
-                        meta: api_types::PeersMetaData {
-                            count: peers.len() as u64,
-                        },
-                        data: peers,
-                    })
+                    peers::get_node_peers(query, &network_globals.peers.read())
                 })
             },
         );
@@ -3967,18 +3898,48 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // POST lighthouse/duties/all?epoch
+    let post_lighthouse_duties_all = warp::path("lighthouse")
+        .and(warp::path("duties"))
+        .and(warp::path("all"))
+        .and(warp::path::end())
+        .and(warp::query::<eth2::lighthouse::AllDutiesQuery>())
+        .and(not_while_syncing_filter.clone())
+        .and(warp_utils::json::json())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .and(log_filter.clone())
+        .then(
+            |query: eth2::lighthouse::AllDutiesQuery,
+             not_synced_filter: Result<(), Rejection>,
+             indices: api_types::ValidatorIndexData,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>,
+             log: Logger| {
+                task_spawner.blocking_json_task(Priority::P0, move || {
+                    not_synced_filter?;
+                    all_duties::all_duties(query.epoch, &indices.0, &chain, &log)
+                })
+            },
+        );
+
     // GET lighthouse/health
     let get_lighthouse_health = warp::path("lighthouse")
         .and(warp::path("health"))
         .and(warp::path::end())
         .and(task_spawner_filter.clone())
-        .then(|task_spawner: TaskSpawner<T::EthSpec>| {
-            task_spawner.blocking_json_task(Priority::P0, move || {
-                eth2::lighthouse::Health::observe()
-                    .map(api_types::GenericResponse::from)
-                    .map_err(warp_utils::reject::custom_bad_request)
-            })
-        });
+        .and(chain_filter.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P0, move || {
+                    let mut health = eth2::lighthouse::Health::observe()
+                        .map_err(warp_utils::reject::custom_bad_request)?;
+                    health.weak_subjectivity_violated = chain.is_weak_subjectivity_violated();
+                    health.clock_skew_detected = chain.clock_sanity.is_skew_detected();
+                    Ok(api_types::GenericResponse::from(health))
+                })
+            },
+        );
 
     // GET lighthouse/ui/health
     let get_lighthouse_ui_health = warp::path("lighthouse")
@@ -4061,6 +4022,25 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // GET lighthouse/validator_monitor/sync_aggregation
+    let get_lighthouse_validator_monitor_sync_aggregation = warp::path("lighthouse")
+        .and(warp::path("validator_monitor"))
+        .and(warp::path("sync_aggregation"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    let summaries = chain
+                        .validator_monitor
+                        .read()
+                        .get_sync_aggregation_summaries();
+                    Ok(api_types::GenericResponse::from(summaries))
+                })
+            },
+        );
+
     // GET lighthouse/syncing
     let get_lighthouse_syncing = warp::path("lighthouse")
         .and(warp::path("syncing"))
@@ -4118,7 +4098,7 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp::path("connected"))
         .and(warp::path::end())
         .and(task_spawner_filter.clone())
-        .and(network_globals)
+        .and(network_globals.clone())
         .then(
             |task_spawner: TaskSpawner<T::EthSpec>,
              network_globals: Arc<NetworkGlobals<T::EthSpec>>| {
@@ -4136,26 +4116,128 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
-    // GET lighthouse/proto_array
-    let get_lighthouse_proto_array = warp::path("lighthouse")
-        .and(warp::path("proto_array"))
+    // GET lighthouse/network/propagation
+    let get_lighthouse_network_propagation = warp::path("lighthouse")
+        .and(warp::path("network"))
+        .and(warp::path("propagation"))
         .and(warp::path::end())
         .and(task_spawner_filter.clone())
+        .and(network_globals)
         .and(chain_filter.clone())
         .then(
-            |task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
-                task_spawner.blocking_response_task(Priority::P1, move || {
-                    Ok::<_, warp::Rejection>(warp::reply::json(
-                        &api_types::GenericResponseRef::from(
-                            chain
-                                .canonical_head
-                                .fork_choice_read_lock()
-                                .proto_array()
-                                .core_proto_array(),
-                        ),
-                    ))
-                })
-            },
+            |task_spawner: TaskSpawner<T::EthSpec>,
+             network_globals: Arc<NetworkGlobals<T::EthSpec>>,
+             chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    let slot_duration = chain.slot_clock.slot_duration();
+                    let mut scores = network_globals
+                        .propagation
+                        .read()
+                        .peers()
+                        .map(
+                            |(peer_id, propagation)| eth2::lighthouse::PeerPropagationScore {
+                                peer_id: peer_id.to_string(),
+                                score: propagation.score(slot_duration),
+                                propagation: propagation.clone(),
+                            },
+                        )
+                        .collect::<Vec<_>>();
+                    // Best peers first, with peers that have no score last.
+                    scores.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(Ordering::Equal));
+                    Ok(api_types::GenericResponse::from(scores))
+                })
+            },
+        );
+
+    // GET lighthouse/network/gossip_rejections
+    let get_lighthouse_network_gossip_rejections = warp::path("lighthouse")
+        .and(warp::path("network"))
+        .and(warp::path("gossip_rejections"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(network_globals.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>,
+             network_globals: Arc<NetworkGlobals<T::EthSpec>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    let gossip_rejections = &network_globals.gossip_rejections;
+                    let epochs = gossip_rejections
+                        .epochs()
+                        .into_iter()
+                        .map(|(epoch, rejections)| eth2::lighthouse::EpochRejections {
+                            epoch,
+                            rejections,
+                        })
+                        .collect();
+                    let mut peers = gossip_rejections
+                        .peers()
+                        .into_iter()
+                        .map(|(peer_id, counts)| eth2::lighthouse::PeerRejections {
+                            peer_id: peer_id.to_string(),
+                            client: counts.client.to_string(),
+                            rejections: counts.counts,
+                        })
+                        .collect::<Vec<_>>();
+                    peers.sort_by(|a, b| b.rejections.total.cmp(&a.rejections.total));
+                    Ok(api_types::GenericResponse::from(
+                        eth2::lighthouse::GossipRejections { epochs, peers },
+                    ))
+                })
+            },
+        );
+
+    // GET lighthouse/proto_array
+    let get_lighthouse_proto_array = warp::path("lighthouse")
+        .and(warp::path("proto_array"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_response_task(Priority::P1, move || {
+                    Ok::<_, warp::Rejection>(warp::reply::json(
+                        &api_types::GenericResponseRef::from(
+                            chain
+                                .canonical_head
+                                .fork_choice_read_lock()
+                                .proto_array()
+                                .core_proto_array(),
+                        ),
+                    ))
+                })
+            },
+        );
+
+    // GET lighthouse/fork_choice
+    let get_lighthouse_fork_choice = warp::path("lighthouse")
+        .and(warp::path("fork_choice"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    let fork_choice = chain.canonical_head.fork_choice_read_lock();
+                    Ok(api_types::GenericResponse::from(
+                        eth2::lighthouse::ForkChoiceParameters {
+                            unsafe_tuning: chain.config.unsafe_tuning(),
+                            proposer_boost_percentage: chain.spec.proposer_score_boost,
+                            committee_weight_override: chain.spec.committee_weight_override,
+                            re_org_head_threshold: chain
+                                .config
+                                .re_org_head_threshold
+                                .map(|threshold| threshold.0),
+                            re_org_parent_threshold: chain
+                                .config
+                                .re_org_parent_threshold
+                                .map(|threshold| threshold.0),
+                            proposer_boost_root: fork_choice.proposer_boost_root(),
+                            justified_checkpoint: fork_choice.justified_checkpoint(),
+                            finalized_checkpoint: fork_choice.finalized_checkpoint(),
+                        },
+                    ))
+                })
+            },
         );
 
     // GET lighthouse/validator_inclusion/{epoch}/{validator_id}
@@ -4195,6 +4277,22 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // GET lighthouse/validator_set/changes?since_epoch
+    let get_lighthouse_validator_set_changes = warp::path("lighthouse")
+        .and(warp::path("validator_set"))
+        .and(warp::path("changes"))
+        .and(warp::query::<eth2::lighthouse::ValidatorSetChangesQuery>())
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |query, task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    validator_set_changes::get_validator_set_changes(query, chain)
+                })
+            },
+        );
+
     // GET lighthouse/eth1/syncing
     let get_lighthouse_eth1_syncing = warp::path("lighthouse")
         .and(warp::path("eth1"))
@@ -4256,7 +4354,7 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp::path("deposit_cache"))
         .and(warp::path::end())
         .and(task_spawner_filter.clone())
-        .and(eth1_service_filter)
+        .and(eth1_service_filter.clone())
         .then(
             |task_spawner: TaskSpawner<T::EthSpec>, eth1_service: eth1::Service| {
                 task_spawner.blocking_json_task(Priority::P1, move || {
@@ -4273,6 +4371,30 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // GET lighthouse/deposits/{pubkey}/status
+    let get_lighthouse_deposit_status = warp::path("lighthouse")
+        .and(warp::path("deposits"))
+        .and(warp::path::param::<PublicKeyBytes>().or_else(|_| async {
+            Err(warp_utils::reject::custom_bad_request(
+                "Invalid pubkey".to_string(),
+            ))
+        }))
+        .and(warp::path("status"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .and(eth1_service_filter)
+        .then(
+            |pubkey: PublicKeyBytes,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>,
+             eth1_service: eth1::Service| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    deposit_status::deposit_status(pubkey, chain, eth1_service)
+                })
+            },
+        );
+
     // GET lighthouse/staking
     let get_lighthouse_staking = warp::path("lighthouse")
         .and(warp::path("staking"))
@@ -4295,17 +4417,127 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // GET lighthouse/pool/stats
+    let get_lighthouse_pool_stats = warp::path("lighthouse")
+        .and(warp::path("pool"))
+        .and(warp::path("stats"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    let op_pool = &chain.op_pool;
+                    let times = op_pool.insertion_times();
+                    let stats = |count: usize, last_insertion: Option<Duration>| {
+                        eth2::lighthouse::PoolOperationStats {
+                            count,
+                            last_insertion: last_insertion.map(|time| time.as_secs()),
+                        }
+                    };
+                    Ok(api_types::GenericResponse::from(
+                        eth2::lighthouse::PoolStats {
+                            attestations: stats(op_pool.num_attestations(), times.attestations),
+                            sync_contributions: stats(
+                                op_pool.num_sync_contributions(),
+                                times.sync_contributions,
+                            ),
+                            attester_slashings: stats(
+                                op_pool.num_attester_slashings(),
+                                times.attester_slashings,
+                            ),
+                            proposer_slashings: stats(
+                                op_pool.num_proposer_slashings(),
+                                times.proposer_slashings,
+                            ),
+                            voluntary_exits: stats(
+                                op_pool.num_voluntary_exits(),
+                                times.voluntary_exits,
+                            ),
+                            bls_to_execution_changes: stats(
+                                op_pool.get_all_bls_to_execution_changes().len(),
+                                times.bls_to_execution_changes,
+                            ),
+                        },
+                    ))
+                })
+            },
+        );
+
+    // GET lighthouse/equivocations
+    let get_lighthouse_equivocations = warp::path("lighthouse")
+        .and(warp::path("equivocations"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    let equivocations = chain
+                        .observed_equivocations
+                        .read()
+                        .equivocations()
+                        .cloned()
+                        .collect::<Vec<_>>();
+                    Ok(api_types::GenericResponse::from(equivocations))
+                })
+            },
+        );
+
+    // GET lighthouse/partial_block_body
+    let get_lighthouse_partial_block_body = warp::path("lighthouse")
+        .and(warp::path("partial_block_body"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    Ok(api_types::GenericResponse::from(
+                        chain.pending_partial_block_body(),
+                    ))
+                })
+            },
+        );
+
+    // POST lighthouse/partial_block_body
+    let post_lighthouse_partial_block_body = warp::path("lighthouse")
+        .and(warp::path("partial_block_body"))
+        .and(warp::path::end())
+        .and(warp_utils::json::json())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |partial_block_body: eth2::lighthouse::PartialBlockBody<T::EthSpec>,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P0, move || {
+                    chain
+                        .submit_partial_block_body(partial_block_body)
+                        .map_err(|e| match e {
+                            PartialBlockBodyError::BeaconChain(e) => {
+                                warp_utils::reject::beacon_chain_error(e)
+                            }
+                            e => warp_utils::reject::custom_bad_request(format!("{:?}", e)),
+                        })
+                })
+            },
+        );
+
     let database_path = warp::path("lighthouse").and(warp::path("database"));
 
     // GET lighthouse/database/info
     let get_lighthouse_database_info = database_path
         .and(warp::path("info"))
         .and(warp::path::end())
+        .and(warp::query::<eth2::lighthouse::DatabaseInfoQuery>())
         .and(task_spawner_filter.clone())
         .and(chain_filter.clone())
         .then(
-            |task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
-                task_spawner.blocking_json_task(Priority::P1, move || database::info(chain))
+            |query: eth2::lighthouse::DatabaseInfoQuery,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || database::info(chain, query))
             },
         );
 
@@ -4328,6 +4560,68 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // POST lighthouse/slasher/dequeue
+    let post_lighthouse_slasher_dequeue = warp::path("lighthouse")
+        .and(warp::path("slasher"))
+        .and(warp::path("dequeue"))
+        .and(warp::path::end())
+        .and(warp::query::<eth2::lighthouse::SlasherDequeueQuery>())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |query: eth2::lighthouse::SlasherDequeueQuery,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    let remote_slasher = chain.remote_slasher.as_ref().ok_or_else(|| {
+                        warp_utils::reject::custom_not_found(
+                            "remote slasher is not enabled, see the --slasher-remote CLI flag"
+                                .to_string(),
+                        )
+                    })?;
+                    Ok(api_types::GenericResponse::from(
+                        remote_slasher.dequeue(query.ack),
+                    ))
+                })
+            },
+        );
+
+    let beacon_processor_queues_path = warp::path("lighthouse")
+        .and(warp::path("beacon_processor"))
+        .and(warp::path("queues"));
+
+    // GET lighthouse/beacon_processor/queues
+    //
+    // Served outside of the beacon processor so that the queues can be inspected whilst it is
+    // overloaded.
+    let get_lighthouse_beacon_processor_queues = beacon_processor_queues_path
+        .and(warp::path::end())
+        .and(beacon_processor_queue_stats_filter.clone())
+        .then(|queue_stats: BeaconProcessorQueueStats| async move {
+            let queues = queue_stats
+                .queues()
+                .into_iter()
+                .map(|queue| eth2::lighthouse::BeaconProcessorQueue {
+                    name: queue.name.to_string(),
+                    length: queue.length,
+                    max_length: queue.max_length,
+                    max_depth: queue.max_depth,
+                    dropped: queue.dropped,
+                })
+                .collect::<Vec<_>>();
+            warp::reply::json(&api_types::GenericResponse::from(queues)).into_response()
+        });
+
+    // POST lighthouse/beacon_processor/queues/reset
+    let post_lighthouse_beacon_processor_queues_reset = beacon_processor_queues_path
+        .and(warp::path("reset"))
+        .and(warp::path::end())
+        .and(beacon_processor_queue_stats_filter)
+        .then(|queue_stats: BeaconProcessorQueueStats| async move {
+            queue_stats.reset();
+            warp::reply().into_response()
+        });
+
     // GET lighthouse/analysis/block_rewards
     let get_lighthouse_block_rewards = warp::path("lighthouse")
         .and(warp::path("analysis"))
@@ -4354,12 +4648,117 @@ pub fn serve<T: BeaconChainTypes>(
         .and(log_filter.clone())
         .then(
             |blocks, task_spawner: TaskSpawner<T::EthSpec>, chain, log| {
+                let task_spawner = task_spawner.cancellable();
                 task_spawner.blocking_json_task(Priority::P1, move || {
                     block_rewards::compute_block_rewards(blocks, chain, log)
                 })
             },
         );
 
+    // POST lighthouse/debug/decode_ssz
+    let post_lighthouse_debug_decode_ssz = warp::path("lighthouse")
+        .and(warp::path("debug"))
+        .and(warp::path("decode_ssz"))
+        .and(warp::path::end())
+        .and(warp::query::<eth2::lighthouse::DecodeSszQuery>())
+        .and(warp::body::bytes())
+        .and(task_spawner_filter.clone())
+        .then(
+            |query, body: Bytes, task_spawner: TaskSpawner<T::EthSpec>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    decode_ssz::decode_ssz::<T::EthSpec>(&query, &body)
+                })
+            },
+        );
+
+    // GET lighthouse/debug/fork_choice_snapshot
+    let get_lighthouse_debug_fork_choice_snapshot = warp::path("lighthouse")
+        .and(warp::path("debug"))
+        .and(warp::path("fork_choice_snapshot"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_response_task(Priority::P1, move || {
+                    let snapshot = chain.canonical_head.fork_choice_read_lock().snapshot();
+                    Response::builder()
+                        .status(200)
+                        .body(snapshot.as_ssz_bytes().into())
+                        .map(|res: Response<Body>| add_ssz_content_type_header(res))
+                        .map_err(|e| {
+                            warp_utils::reject::custom_server_error(format!(
+                                "failed to create response: {}",
+                                e
+                            ))
+                        })
+                })
+            },
+        );
+
+    // GET lighthouse/debug/memory
+    let get_lighthouse_debug_memory = warp::path("lighthouse")
+        .and(warp::path("debug"))
+        .and(warp::path("memory"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    let allocator = malloc_utils::allocator_stats().ok().map(|stats| {
+                        eth2::lighthouse::AllocatorInfo {
+                            name: stats.allocator.to_string(),
+                            stats: stats
+                                .stats
+                                .into_iter()
+                                .map(|(name, value)| (name.to_string(), value))
+                                .collect(),
+                        }
+                    });
+                    Ok(api_types::GenericResponse::from(
+                        eth2::lighthouse::MemoryInfo {
+                            allocator,
+                            caches: chain.cache_memory(),
+                        },
+                    ))
+                })
+            },
+        );
+
+    // GET lighthouse/debug/profile
+    //
+    // Profiling runs outside of the beacon processor so that it doesn't occupy a worker for the
+    // whole duration of the profile.
+    let get_lighthouse_debug_profile = warp::path("lighthouse")
+        .and(warp::path("debug"))
+        .and(warp::path("profile"))
+        .and(warp::path::end())
+        .and(warp::query::<eth2::lighthouse::ProfileQuery>())
+        .then(|query: eth2::lighthouse::ProfileQuery| async move {
+            let result = warp_utils::task::blocking_response_task(move || {
+                let duration = profile::parse_duration(&query.duration)
+                    .map_err(warp_utils::reject::custom_bad_request)?;
+                profile::profile(duration, query.format)
+            })
+            .await;
+            convert_rejection(result).await
+        });
+
+    // GET lighthouse/block_rewards/predicted
+    let get_lighthouse_block_rewards_predicted = warp::path("lighthouse")
+        .and(warp::path("block_rewards"))
+        .and(warp::path("predicted"))
+        .and(warp::query::<eth2::lighthouse::PredictedBlockRewardQuery>())
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(|query, task_spawner: TaskSpawner<T::EthSpec>, chain| {
+            task_spawner.blocking_json_task(Priority::P1, move || {
+                block_rewards::get_predicted_block_reward(query, chain)
+            })
+        });
+
     // GET lighthouse/analysis/attestation_performance/{index}
     let get_lighthouse_attestation_performance = warp::path("lighthouse")
         .and(warp::path("analysis"))
@@ -4393,6 +4792,71 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // GET lighthouse/analysis/participation/{epoch}
+    let get_lighthouse_analysis_participation = warp::path("lighthouse")
+        .and(warp::path("analysis"))
+        .and(warp::path("participation"))
+        .and(warp::path::param::<Epoch>())
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |epoch: Epoch, task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    validator_inclusion::epoch_participation(epoch, &chain)
+                })
+            },
+        );
+
+    // GET lighthouse/analysis/churn
+    let get_lighthouse_analysis_churn = warp::path("lighthouse")
+        .and(warp::path("analysis"))
+        .and(warp::path("churn"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || churn::churn_analysis(&chain))
+            },
+        );
+
+    // GET lighthouse/analysis/state_diff
+    let get_lighthouse_state_diff = warp::path("lighthouse")
+        .and(warp::path("analysis"))
+        .and(warp::path("state_diff"))
+        .and(warp::query::<eth2::lighthouse::StateDiffQuery>())
+        .and(warp::path::end())
+        .and(warp::header::optional::<api_types::Accept>("accept"))
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |query,
+             accept_header: Option<api_types::Accept>,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_response_task(Priority::P1, move || {
+                    let state_diff = state_diff::get_state_diff(query, chain)?;
+                    match accept_header {
+                        Some(api_types::Accept::Ssz) => Response::builder()
+                            .status(200)
+                            .body(state_diff.as_ssz_bytes().into())
+                            .map(|res: Response<Body>| add_ssz_content_type_header(res))
+                            .map_err(|e| {
+                                warp_utils::reject::custom_server_error(format!(
+                                    "failed to create response: {}",
+                                    e
+                                ))
+                            }),
+                        _ => Ok(
+                            warp::reply::json(&api_types::GenericResponse::from(state_diff))
+                                .into_response(),
+                        ),
+                    }
+                })
+            },
+        );
+
     // GET lighthouse/merge_readiness
     let get_lighthouse_merge_readiness = warp::path("lighthouse")
         .and(warp::path("merge_readiness"))
@@ -4412,6 +4876,132 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // GET lighthouse/execution/capabilities
+    let get_lighthouse_execution_capabilities = warp::path("lighthouse")
+        .and(warp::path("execution"))
+        .and(warp::path("capabilities"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
+                task_spawner.spawn_async_with_rejection(Priority::P1, async move {
+                    let execution_layer = chain
+                        .execution_layer
+                        .as_ref()
+                        .ok_or(BeaconChainError::ExecutionLayerMissing)
+                        .map_err(warp_utils::reject::beacon_chain_error)?;
+
+                    // Use the cached result of `engine_exchangeCapabilities` if there is one.
+                    let capabilities = execution_layer
+                        .get_engine_capabilities(None)
+                        .await
+                        .map_err(|e| {
+                            warp_utils::reject::custom_server_error(format!(
+                                "unable to exchange capabilities with the execution engine: {e:?}"
+                            ))
+                        })?;
+                    let methods = capabilities
+                        .to_response()
+                        .into_iter()
+                        .map(String::from)
+                        .collect::<Vec<_>>();
+
+                    Ok::<_, warp::reject::Rejection>(
+                        warp::reply::json(&api_types::GenericResponse::from(methods))
+                            .into_response(),
+                    )
+                })
+            },
+        );
+
+    // GET lighthouse/execution/auth
+    let get_lighthouse_execution_auth = warp::path("lighthouse")
+        .and(warp::path("execution"))
+        .and(warp::path("auth"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
+                task_spawner.spawn_async_with_rejection(Priority::P1, async move {
+                    let execution_layer = chain
+                        .execution_layer
+                        .as_ref()
+                        .ok_or(BeaconChainError::ExecutionLayerMissing)
+                        .map_err(warp_utils::reject::beacon_chain_error)?;
+
+                    let secret_loaded_at = execution_layer
+                        .jwt_secret_loaded_at()
+                        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+                        .map_or(0, |duration| duration.as_secs());
+                    let error = execution_layer
+                        .check_auth()
+                        .await
+                        .err()
+                        .map(|e| format!("{e:?}"));
+                    let status = eth2::lighthouse::ExecutionAuthStatus {
+                        authenticated: error.is_none(),
+                        secret_loaded_at,
+                        error,
+                    };
+
+                    Ok::<_, warp::reject::Rejection>(
+                        warp::reply::json(&api_types::GenericResponse::from(status))
+                            .into_response(),
+                    )
+                })
+            },
+        );
+
+    // GET lighthouse/proofs/historical_block_root/{slot}
+    let get_lighthouse_proofs_historical_block_root = warp::path("lighthouse")
+        .and(warp::path("proofs"))
+        .and(warp::path("historical_block_root"))
+        .and(warp::path::param::<Slot>().or_else(|_| async {
+            Err(warp_utils::reject::custom_bad_request(
+                "Invalid slot".to_string(),
+            ))
+        }))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |slot: Slot, task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    proofs::historical_block_root_proof(slot, chain)
+                        .map(api_types::GenericResponse::from)
+                })
+            },
+        );
+
+    // GET lighthouse/proofs/state/{state_id}?gindex
+    let get_lighthouse_proofs_state = warp::path("lighthouse")
+        .and(warp::path("proofs"))
+        .and(warp::path("state"))
+        .and(warp::path::param::<StateId>().or_else(|_| async {
+            Err(warp_utils::reject::custom_bad_request(
+                "Invalid state ID".to_string(),
+            ))
+        }))
+        .and(warp::path::end())
+        .and(multi_key_query::<eth2::lighthouse::StateProofQuery>())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .and(state_tree_cache_filter)
+        .then(
+            |state_id: StateId,
+             query_res: Result<eth2::lighthouse::StateProofQuery, warp::Rejection>,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>,
+             state_tree_cache: Arc<proofs::StateTreeCache>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    let query = query_res?;
+                    proofs::state_multiproof(state_id, query.gindex, chain, state_tree_cache)
+                })
+            },
+        );
+
     let get_events = eth_v1
         .and(warp::path("events"))
         .and(warp::path::end())
@@ -4456,6 +5046,9 @@ pub fn serve<T: BeaconChainTypes>(
                                 api_types::EventTopic::LateHead => {
                                     event_handler.subscribe_late_head()
                                 }
+                                api_types::EventTopic::FinalityStall => {
+                                    event_handler.subscribe_finality_stall()
+                                }
                                 api_types::EventTopic::LightClientFinalityUpdate => {
                                     event_handler.subscribe_light_client_finality_update()
                                 }
@@ -4516,6 +5109,61 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // Create a `warp` filter that provides access to the per-module log levels.
+    let inner_module_levels = ctx.module_levels.clone();
+    let module_levels_filter = warp::any()
+        .map(move || inner_module_levels.clone())
+        .and_then(|module_levels| async move {
+            match module_levels {
+                Some(module_levels) => Ok(module_levels),
+                None => Err(warp_utils::reject::custom_not_found(
+                    "Runtime log level control is not enabled".to_string(),
+                )),
+            }
+        });
+
+    let logging_path = warp::path("lighthouse").and(warp::path("logging"));
+
+    // GET lighthouse/logging
+    let get_lighthouse_logging = logging_path
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(module_levels_filter.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>, module_levels: ModuleLevels| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    Ok(api_types::GenericResponse::from(
+                        eth2::lighthouse::ModuleLogLevels {
+                            levels: module_levels.directives(),
+                        },
+                    ))
+                })
+            },
+        );
+
+    // POST lighthouse/logging
+    let post_lighthouse_logging = logging_path
+        .and(warp::path::end())
+        .and(warp_utils::json::json())
+        .and(task_spawner_filter.clone())
+        .and(module_levels_filter)
+        .then(
+            |request: eth2::lighthouse::ModuleLogLevels,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             module_levels: ModuleLevels| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    module_levels
+                        .set(&request.levels)
+                        .map_err(warp_utils::reject::custom_bad_request)?;
+                    Ok(api_types::GenericResponse::from(
+                        eth2::lighthouse::ModuleLogLevels {
+                            levels: module_levels.directives(),
+                        },
+                    ))
+                })
+            },
+        );
+
     // Subscribe to logs via Server Side Events
     // /lighthouse/logs
     let lighthouse_log_events = warp::path("lighthouse")
@@ -4612,19 +5260,34 @@ pub fn serve<T: BeaconChainTypes>(
                 .uor(get_lighthouse_health)
                 .uor(get_lighthouse_ui_health)
                 .uor(get_lighthouse_ui_validator_count)
+                .uor(get_lighthouse_validator_monitor_sync_aggregation)
                 .uor(get_lighthouse_syncing)
                 .uor(get_lighthouse_nat)
                 .uor(get_lighthouse_peers)
                 .uor(get_lighthouse_peers_connected)
+                .uor(get_lighthouse_network_propagation)
+                .uor(get_lighthouse_network_gossip_rejections)
                 .uor(get_lighthouse_proto_array)
+                .uor(get_lighthouse_fork_choice)
+                .uor(get_lighthouse_debug_fork_choice_snapshot)
                 .uor(get_lighthouse_validator_inclusion_global)
                 .uor(get_lighthouse_validator_inclusion)
+                .uor(get_lighthouse_validator_set_changes)
                 .uor(get_lighthouse_eth1_syncing)
                 .uor(get_lighthouse_eth1_block_cache)
                 .uor(get_lighthouse_eth1_deposit_cache)
+                .uor(get_lighthouse_deposit_status)
                 .uor(get_lighthouse_staking)
+                .uor(get_lighthouse_pool_stats)
+                .uor(get_lighthouse_equivocations)
+                .uor(get_lighthouse_partial_block_body)
                 .uor(get_lighthouse_database_info)
+                .uor(get_lighthouse_beacon_processor_queues)
+                .uor(get_lighthouse_logging)
+                .uor(get_lighthouse_debug_memory)
+                .uor(enable(ctx.config.enable_profiling).and(get_lighthouse_debug_profile))
                 .uor(get_lighthouse_block_rewards)
+                .uor(get_lighthouse_block_rewards_predicted)
                 .uor(get_lighthouse_attestation_performance)
                 .uor(
                     enable(ctx.config.enable_light_client_server)
@@ -4639,7 +5302,14 @@ pub fn serve<T: BeaconChainTypes>(
                         .and(get_beacon_light_client_bootstrap),
                 )
                 .uor(get_lighthouse_block_packing_efficiency)
+                .uor(get_lighthouse_analysis_participation)
+                .uor(get_lighthouse_analysis_churn)
+                .uor(get_lighthouse_state_diff)
                 .uor(get_lighthouse_merge_readiness)
+                .uor(get_lighthouse_execution_capabilities)
+                .uor(get_lighthouse_execution_auth)
+                .uor(get_lighthouse_proofs_historical_block_root)
+                .uor(get_lighthouse_proofs_state)
                 .uor(get_events)
                 .uor(get_expected_withdrawals)
                 .uor(lighthouse_log_events.boxed())
@@ -4680,10 +5350,16 @@ pub fn serve<T: BeaconChainTypes>(
                     .uor(post_validator_register_validator)
                     .uor(post_validator_liveness_epoch)
                     .uor(post_lighthouse_liveness)
+                    .uor(post_lighthouse_duties_all)
                     .uor(post_lighthouse_database_reconstruct)
+                    .uor(post_lighthouse_slasher_dequeue)
+                    .uor(post_lighthouse_beacon_processor_queues_reset)
+                    .uor(post_lighthouse_logging)
                     .uor(post_lighthouse_block_rewards)
+                    .uor(post_lighthouse_debug_decode_ssz)
                     .uor(post_lighthouse_ui_validator_metrics)
                     .uor(post_lighthouse_ui_validator_info)
+                    .uor(post_lighthouse_partial_block_body)
                     .recover(warp_utils::reject::handle_rejection),
             ),
         )
@@ -4728,6 +5404,33 @@ pub fn serve<T: BeaconChainTypes>(
     Ok(http_server)
 }
 
+/// Reject `units` other than gwei for SSZ responses, which can only encode gwei.
+fn check_reward_units_accept(
+    units: Option<api_types::RewardUnits>,
+    accept_header: Option<api_types::Accept>,
+) -> Result<(), warp::Rejection> {
+    match (units, accept_header) {
+        (Some(units), Some(api_types::Accept::Ssz)) if units != api_types::RewardUnits::Gwei => {
+            Err(warp_utils::reject::custom_bad_request(format!(
+                "units {} are not supported for SSZ responses",
+                units
+            )))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Build a `200` response with an SSZ-encoded body.
+fn ssz_response(bytes: Vec<u8>) -> Result<Response<Body>, warp::Rejection> {
+    Response::builder()
+        .status(200)
+        .body(bytes.into())
+        .map(|res: Response<Body>| add_ssz_content_type_header(res))
+        .map_err(|e| {
+            warp_utils::reject::custom_server_error(format!("failed to create response: {}", e))
+        })
+}
+
 /// Publish a message to the libp2p pubsub network.
 fn publish_pubsub_message<E: EthSpec>(
     network_tx: &UnboundedSender<NetworkMessage<E>>,
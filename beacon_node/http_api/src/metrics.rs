@@ -16,6 +16,14 @@ lazy_static::lazy_static! {
         "Duration to process HTTP requests per path",
         &["path"]
     );
+    pub static ref HTTP_API_TIMED_OUT_REQUESTS_TOTAL: Result<IntCounter> = try_create_int_counter(
+        "http_api_timed_out_requests_total",
+        "Count of HTTP requests which exceeded their route's timeout",
+    );
+    pub static ref HTTP_API_CANCELLED_TASKS_TOTAL: Result<IntCounter> = try_create_int_counter(
+        "http_api_cancelled_tasks_total",
+        "Count of tasks stopped early because their HTTP request was dropped",
+    );
 
     pub static ref HTTP_API_BEACON_PROPOSER_CACHE_TIMES: Result<Histogram> = try_create_histogram(
         "http_api_beacon_proposer_cache_build_times",
@@ -44,6 +52,11 @@ lazy_static::lazy_static! {
         "http_api_block_published_late_total",
         "The count of times a block was published beyond more than half way to the attestation deadline"
     );
+    pub static ref HTTP_API_UNREVEALED_BLINDED_BLOCKS_TOTAL: Result<IntCounterVec> = try_create_int_counter_vec(
+        "http_api_unrevealed_blinded_blocks_total",
+        "Count of blinded blocks whose payload the builder failed to reveal, by whether the block was recovered locally",
+        &["result"]
+    );
     pub static ref HTTP_API_BLOCK_PUBLISHED_VERY_LATE_TOTAL: Result<IntCounter> = try_create_int_counter(
         "http_api_block_published_very_late_total",
         "The count of times a block was published beyond the attestation deadline"
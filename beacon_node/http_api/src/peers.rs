@@ -0,0 +1,95 @@
+use eth2::types::{self as api_types, PeerData, PeersData, PeersMetaData, PeersQuery};
+use lighthouse_network::{PeerDB, PeerId};
+use std::str::FromStr;
+use types::EthSpec;
+use warp_utils::reject::custom_bad_request;
+
+/// The number of peers per page if a `page_token` is provided without a `page_size`.
+const DEFAULT_PAGE_SIZE: usize = 100;
+
+/// Returns the peers matching the state and direction filters in `query`, optionally paginated.
+///
+/// Only peers we have been connected to at some point are included, as implied by the eth2 API
+/// spec. Peers are ordered by peer id, and the page token is the id of the last peer in the
+/// previous page, so that pages remain consistent as peers connect and disconnect.
+pub fn get_node_peers<E: EthSpec>(
+    query: PeersQuery,
+    peer_db: &PeerDB<E>,
+) -> Result<PeersData, warp::Rejection> {
+    let states = query.state.map(|states| {
+        states
+            .iter()
+            .map(|state| state.connection_state())
+            .collect::<Vec<_>>()
+    });
+    let directions = query.direction.map(|directions| {
+        directions
+            .iter()
+            .map(|direction| direction.connection_direction())
+            .collect::<Vec<_>>()
+    });
+
+    let page_size = match query.page_size {
+        Some(0) => {
+            return Err(custom_bad_request(
+                "page_size must be at least 1".to_string(),
+            ))
+        }
+        Some(page_size) => page_size,
+        None if query.page_token.is_some() => DEFAULT_PAGE_SIZE,
+        None => usize::MAX,
+    };
+    let after = query
+        .page_token
+        .map(|token| PeerId::from_str(&token))
+        .transpose()
+        .map_err(|_| custom_bad_request("invalid page_token".to_string()))?;
+
+    // Fetch one extra peer to determine whether another page follows.
+    let mut peers = peer_db.query_peers(
+        states.as_deref(),
+        directions.as_deref(),
+        after.as_ref(),
+        page_size.saturating_add(1),
+    );
+    let next_page_token = (peers.len() > page_size).then(|| {
+        peers.truncate(page_size);
+        peers
+            .last()
+            .map(|(peer_id, _)| peer_id.to_string())
+            .unwrap_or_default()
+    });
+
+    let data = peers
+        .into_iter()
+        .filter_map(|(peer_id, peer_info)| {
+            let address = if let Some(multiaddr) = peer_info.seen_multiaddrs().next() {
+                multiaddr.to_string()
+            } else if let Some(addr) = peer_info.listening_addresses().first() {
+                addr.to_string()
+            } else {
+                String::new()
+            };
+
+            Some(PeerData {
+                peer_id: peer_id.to_string(),
+                enr: peer_info.enr().map(|enr| enr.to_base64()),
+                last_seen_p2p_address: address,
+                direction: api_types::PeerDirection::from_connection_direction(
+                    peer_info.connection_direction()?,
+                ),
+                state: api_types::PeerState::from_peer_connection_status(
+                    peer_info.connection_status(),
+                ),
+            })
+        })
+        .collect::<Vec<_>>();
+
+    Ok(PeersData {
+        meta: PeersMetaData {
+            count: peer_db.count_peers(states.as_deref(), directions.as_deref()) as u64,
+            next_page_token,
+        },
+        data,
+    })
+}
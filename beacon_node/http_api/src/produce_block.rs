@@ -14,6 +14,7 @@ use eth2::types::{
 };
 use ssz::Encode;
 use std::sync::Arc;
+use tracing::instrument;
 use types::{payload::BlockProductionVersion, *};
 use warp::{
     hyper::{Body, Response},
@@ -42,6 +43,7 @@ pub fn get_randao_verification(
     Ok(randao_verification)
 }
 
+#[instrument(skip_all, fields(%slot))]
 pub async fn produce_block_v3<T: BeaconChainTypes>(
     accept_header: Option<api_types::Accept>,
     chain: Arc<BeaconChain<T>>,
@@ -128,6 +130,7 @@ pub fn build_response_v3<T: BeaconChainTypes>(
     }
 }
 
+#[instrument(skip_all, fields(%slot))]
 pub async fn produce_blinded_block_v2<T: BeaconChainTypes>(
     endpoint_version: EndpointVersion,
     accept_header: Option<api_types::Accept>,
@@ -158,6 +161,7 @@ pub async fn produce_blinded_block_v2<T: BeaconChainTypes>(
     build_response_v2(chain, block_response_type, endpoint_version, accept_header)
 }
 
+#[instrument(skip_all, fields(%slot))]
 pub async fn produce_block_v2<T: BeaconChainTypes>(
     endpoint_version: EndpointVersion,
     accept_header: Option<api_types::Accept>,
@@ -0,0 +1,113 @@
+//! Captures CPU profiles of the beacon node for `GET lighthouse/debug/profile`.
+use eth2::lighthouse::ProfileFormat;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use warp::http::header::CONTENT_TYPE;
+use warp::http::Response;
+use warp::hyper::Body;
+use warp_utils::reject::{custom_bad_request, custom_server_error};
+
+/// The longest profile which may be requested.
+pub const MAX_PROFILE_DURATION: Duration = Duration::from_secs(300);
+
+/// The number of stack samples taken per second.
+#[cfg(target_os = "linux")]
+const SAMPLING_FREQUENCY: i32 = 99;
+
+/// Set whilst a profile is being captured, since the profiler is process-wide.
+static PROFILING: AtomicBool = AtomicBool::new(false);
+
+/// Clears `PROFILING` when dropped, including if profiling panics.
+struct ProfilingGuard;
+
+impl Drop for ProfilingGuard {
+    fn drop(&mut self) {
+        PROFILING.store(false, Ordering::SeqCst);
+    }
+}
+
+/// Parse a duration such as `10s`, `500ms` or `2m`. A bare number is a number of seconds.
+pub fn parse_duration(duration: &str) -> Result<Duration, String> {
+    let (value, unit) = match duration.find(|c: char| !c.is_ascii_digit()) {
+        Some(index) => duration.split_at(index),
+        None => (duration, "s"),
+    };
+    let value = value
+        .parse::<u64>()
+        .map_err(|e| format!("invalid duration {:?}: {:?}", duration, e))?;
+    match unit {
+        "ms" => Ok(Duration::from_millis(value)),
+        "s" => Ok(Duration::from_secs(value)),
+        "m" => Ok(Duration::from_secs(value.saturating_mul(60))),
+        _ => Err(format!(
+            "invalid duration unit {:?}, expected ms, s or m",
+            unit
+        )),
+    }
+}
+
+/// Profile the whole process for `duration`, blocking the calling thread until complete.
+pub fn profile(
+    duration: Duration,
+    format: ProfileFormat,
+) -> Result<Response<Body>, warp::Rejection> {
+    if duration.is_zero() || duration > MAX_PROFILE_DURATION {
+        return Err(custom_bad_request(format!(
+            "duration must be greater than zero and at most {:?}",
+            MAX_PROFILE_DURATION
+        )));
+    }
+    if PROFILING.swap(true, Ordering::SeqCst) {
+        return Err(custom_bad_request(
+            "a profile is already being captured".to_string(),
+        ));
+    }
+    let guard = ProfilingGuard;
+    let result = capture(duration, format);
+    drop(guard);
+
+    let (content_type, body) = result.map_err(custom_server_error)?;
+    Response::builder()
+        .status(200)
+        .header(CONTENT_TYPE, content_type)
+        .body(body.into())
+        .map_err(|e| custom_server_error(format!("failed to create response: {}", e)))
+}
+
+/// Capture a profile, returning the content type and body of the response.
+#[cfg(target_os = "linux")]
+fn capture(duration: Duration, format: ProfileFormat) -> Result<(&'static str, Vec<u8>), String> {
+    use pprof::protos::Message;
+
+    let profiler = pprof::ProfilerGuardBuilder::default()
+        .frequency(SAMPLING_FREQUENCY)
+        .blocklist(&["libc", "libgcc", "pthread", "vdso"])
+        .build()
+        .map_err(|e| format!("unable to start profiler: {:?}", e))?;
+    std::thread::sleep(duration);
+    let report = profiler
+        .report()
+        .build()
+        .map_err(|e| format!("unable to build profile: {:?}", e))?;
+
+    match format {
+        ProfileFormat::Flamegraph => {
+            let mut body = vec![];
+            report
+                .flamegraph(&mut body)
+                .map_err(|e| format!("unable to render flamegraph: {:?}", e))?;
+            Ok(("image/svg+xml", body))
+        }
+        ProfileFormat::Pprof => {
+            let profile = report
+                .pprof()
+                .map_err(|e| format!("unable to encode profile: {:?}", e))?;
+            Ok(("application/octet-stream", profile.encode_to_vec()))
+        }
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn capture(_: Duration, _: ProfileFormat) -> Result<(&'static str, Vec<u8>), String> {
+    Err("profiling is only supported on Linux".to_string())
+}
@@ -0,0 +1,642 @@
+use crate::state_id::StateId;
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use eth2::lighthouse::{HistoricalBlockRootProof, StateMultiproof};
+use eth2::types::ExecutionOptimisticFinalizedResponse;
+use ethereum_hashing::{hash32_concat, ZERO_HASHES};
+use lru::LruCache;
+use merkle_proof::MerkleTree;
+use parking_lot::Mutex;
+use std::collections::{BTreeSet, HashMap};
+use std::num::NonZeroUsize;
+use std::sync::Arc;
+use tree_hash::{TreeHash, TreeHashType};
+use types::non_zero_usize::new_non_zero_usize;
+use types::{BeaconState, EthSpec, Hash256, Slot, Unsigned};
+use warp_utils::reject::{
+    beacon_chain_error, beacon_state_error, custom_bad_request, custom_not_found,
+    custom_server_error,
+};
+
+/// The number of states whose tree hashes are retained by the `StateTreeCache`.
+const STATE_TREE_CACHE_SIZE: NonZeroUsize = new_non_zero_usize(4);
+
+/// The maximum number of generalized indices which may be proven in a single request.
+const MAX_MULTIPROOF_INDICES: usize = 256;
+
+/*
+ * The positions of the list and vector fields amongst the fields of the `BeaconState`.
+ */
+const BLOCK_ROOTS_FIELD_INDEX: usize = 5;
+const STATE_ROOTS_FIELD_INDEX: usize = 6;
+const HISTORICAL_ROOTS_FIELD_INDEX: usize = 7;
+const ETH1_DATA_VOTES_FIELD_INDEX: usize = 9;
+const VALIDATORS_FIELD_INDEX: usize = 11;
+const BALANCES_FIELD_INDEX: usize = 12;
+const RANDAO_MIXES_FIELD_INDEX: usize = 13;
+const SLASHINGS_FIELD_INDEX: usize = 14;
+const PREVIOUS_EPOCH_PARTICIPATION_FIELD_INDEX: usize = 15;
+const CURRENT_EPOCH_PARTICIPATION_FIELD_INDEX: usize = 16;
+const INACTIVITY_SCORES_FIELD_INDEX: usize = 21;
+const HISTORICAL_SUMMARIES_FIELD_INDEX: usize = 27;
+
+/// Produce a Merkle proof of the canonical block root at `slot` against the head state.
+///
+/// Slots within the last `SLOTS_PER_HISTORICAL_ROOT` slots are proven via `state.block_roots`,
+/// older slots are proven via the `state.historical_summaries` entry for their period.
+pub fn historical_block_root_proof<T: BeaconChainTypes>(
+    slot: Slot,
+    chain: Arc<BeaconChain<T>>,
+) -> Result<HistoricalBlockRootProof, warp::Rejection> {
+    let head = chain.head_snapshot();
+    let state = &head.beacon_state;
+    let state_slot = state.slot();
+    let state_root = head.beacon_state_root();
+
+    if slot >= state_slot {
+        return Err(custom_bad_request(format!(
+            "slot {} is not prior to the head state slot {}",
+            slot, state_slot
+        )));
+    }
+
+    let slots_per_historical_root = T::EthSpec::slots_per_historical_root();
+    let vector_depth = slots_per_historical_root.trailing_zeros() as usize;
+    let vector_index = slot.as_usize() % slots_per_historical_root;
+
+    let (block_root, gindex, proof) =
+        if slot.as_usize() + slots_per_historical_root >= state_slot.as_usize() {
+            let block_root = *state.get_block_root(slot).map_err(beacon_state_error)?;
+            let leaves = state.block_roots().iter().copied().collect::<Vec<_>>();
+            let (_, mut proof) = MerkleTree::create(&leaves, vector_depth)
+                .generate_proof(vector_index, vector_depth)
+                .map_err(|e| custom_server_error(format!("unable to build proof: {:?}", e)))?;
+            proof.extend(
+                state
+                    .compute_field_merkle_proof(BLOCK_ROOTS_FIELD_INDEX)
+                    .map_err(beacon_state_error)?,
+            );
+
+            let field_gindex = (state.num_fields_pow2() + BLOCK_ROOTS_FIELD_INDEX) as u64;
+            let gindex = (field_gindex << vector_depth) | vector_index as u64;
+
+            (block_root, gindex, proof)
+        } else {
+            historical_summary_proof(slot, state, &chain)?
+        };
+
+    let depth = proof.len();
+    let leaf_index = gindex
+        .checked_sub(1 << depth)
+        .ok_or_else(|| custom_server_error("invalid generalized index".to_string()))?;
+    if !merkle_proof::verify_merkle_proof(
+        block_root,
+        &proof,
+        depth,
+        leaf_index as usize,
+        state_root,
+    ) {
+        return Err(custom_server_error(format!(
+            "computed proof for slot {} does not verify against state root {:?}",
+            slot, state_root
+        )));
+    }
+
+    Ok(HistoricalBlockRootProof {
+        slot,
+        block_root,
+        state_slot,
+        state_root,
+        gindex,
+        proof,
+    })
+}
+
+/// Prove the block root at `slot` via the `historical_summaries` of `state`.
+///
+/// Returns the block root, its generalized index within `state` and the proof.
+fn historical_summary_proof<T: BeaconChainTypes>(
+    slot: Slot,
+    state: &BeaconState<T::EthSpec>,
+    chain: &BeaconChain<T>,
+) -> Result<(Hash256, u64, Vec<Hash256>), warp::Rejection> {
+    let slots_per_historical_root = T::EthSpec::slots_per_historical_root();
+    let vector_depth = slots_per_historical_root.trailing_zeros() as usize;
+    let vector_index = slot.as_usize() % slots_per_historical_root;
+
+    let historical_summaries = state.historical_summaries().map_err(|_| {
+        custom_bad_request("head state does not contain historical summaries".to_string())
+    })?;
+
+    // Historical summaries are accumulated from the first period that ends after the Capella
+    // fork, earlier periods are only committed to in `state.historical_roots`.
+    let capella_fork_slot = chain
+        .spec
+        .capella_fork_epoch
+        .ok_or_else(|| custom_server_error("capella fork epoch is not set".to_string()))?
+        .start_slot(T::EthSpec::slots_per_epoch());
+    let first_summary_period = capella_fork_slot.as_usize() / slots_per_historical_root;
+    let period = slot.as_usize() / slots_per_historical_root;
+
+    let summary_index = period.checked_sub(first_summary_period).ok_or_else(|| {
+        custom_bad_request(format!(
+            "slot {} precedes the first historical summary, proofs against \
+             historical_roots are not supported",
+            slot
+        ))
+    })?;
+    let summary = historical_summaries
+        .get(summary_index)
+        .copied()
+        .ok_or_else(|| custom_not_found(format!("historical summary for period {}", period)))?;
+
+    // Reconstruct the `block_roots` vector committed to by the summary.
+    let period_start_slot = Slot::from(period * slots_per_historical_root);
+    let period_end_slot = period_start_slot + slots_per_historical_root as u64 - 1;
+    let block_roots = chain
+        .forwards_iter_block_roots_until(period_start_slot, period_end_slot)
+        .map_err(beacon_chain_error)?
+        .map(|result| result.map(|(root, _)| root))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(beacon_chain_error)?;
+    if block_roots.len() != slots_per_historical_root {
+        return Err(custom_server_error(format!(
+            "expected {} block roots for period {}, found {}",
+            slots_per_historical_root,
+            period,
+            block_roots.len()
+        )));
+    }
+
+    let block_roots_tree = MerkleTree::create(&block_roots, vector_depth);
+    if block_roots_tree.hash() != summary.block_summary_root() {
+        return Err(custom_server_error(format!(
+            "block roots for period {} do not match the historical summary",
+            period
+        )));
+    }
+    let (block_root, mut proof) = block_roots_tree
+        .generate_proof(vector_index, vector_depth)
+        .map_err(|e| custom_server_error(format!("unable to build proof: {:?}", e)))?;
+
+    // `block_summary_root` is the left child of the `HistoricalSummary` container.
+    proof.push(summary.state_summary_root());
+
+    let list_depth =
+        <T::EthSpec as EthSpec>::HistoricalRootsLimit::to_usize().trailing_zeros() as usize;
+    let summary_leaves = historical_summaries
+        .iter()
+        .map(|summary| summary.tree_hash_root())
+        .collect::<Vec<_>>();
+    let (_, summaries_proof) = MerkleTree::create(&summary_leaves, list_depth)
+        .generate_proof(summary_index, list_depth)
+        .map_err(|e| custom_server_error(format!("unable to build proof: {:?}", e)))?;
+    proof.extend(summaries_proof);
+    proof.push((historical_summaries.len() as u64).tree_hash_root());
+
+    proof.extend(
+        state
+            .compute_field_merkle_proof(HISTORICAL_SUMMARIES_FIELD_INDEX)
+            .map_err(beacon_state_error)?,
+    );
+
+    let field_gindex = (state.num_fields_pow2() + HISTORICAL_SUMMARIES_FIELD_INDEX) as u64;
+    // Descend into the list data (left of the length mix-in), then the summary, then its
+    // `block_summary_root` and finally the vector of block roots.
+    let list_data_gindex = field_gindex << 1;
+    let summary_gindex = (list_data_gindex << list_depth) | summary_index as u64;
+    let block_summary_gindex = summary_gindex << 1;
+    let gindex = (block_summary_gindex << vector_depth) | vector_index as u64;
+
+    Ok((block_root, gindex, proof))
+}
+
+/// Produce a Merkle multiproof of the nodes at `gindices` within the state identified by
+/// `state_id`.
+pub fn state_multiproof<T: BeaconChainTypes>(
+    state_id: StateId,
+    gindices: Vec<u64>,
+    chain: Arc<BeaconChain<T>>,
+    cache: Arc<StateTreeCache>,
+) -> Result<ExecutionOptimisticFinalizedResponse<StateMultiproof>, warp::Rejection> {
+    if gindices.is_empty() {
+        return Err(custom_bad_request(
+            "at least one gindex must be provided".to_string(),
+        ));
+    }
+    if gindices.len() > MAX_MULTIPROOF_INDICES {
+        return Err(custom_bad_request(format!(
+            "at most {} gindices may be requested",
+            MAX_MULTIPROOF_INDICES
+        )));
+    }
+    if gindices.contains(&0) {
+        return Err(custom_bad_request("0 is not a valid gindex".to_string()));
+    }
+
+    let (state_root, execution_optimistic, finalized) = state_id.root(&chain)?;
+    let helper_indices = get_helper_indices(&gindices);
+    let nodes = || gindices.iter().chain(&helper_indices).copied();
+
+    // Only load the state if the cached tree is missing some of the nodes required. The tree is
+    // hashed outside of the cache's lock.
+    let tree = match cache.get(&state_root) {
+        Some(tree) if tree.has_field_trees(nodes()) => tree,
+        cached => {
+            let tree = state_id.map_state_and_execution_optimistic_and_finalized(
+                &chain,
+                |state, _, _| {
+                    let tree = cached
+                        .as_ref()
+                        .map_or_else(|| StateTree::new(state), |tree| StateTree::clone(tree));
+                    Ok(Arc::new(tree.with_field_trees(state, nodes())))
+                },
+            )?;
+            cache.insert(tree.clone());
+            tree
+        }
+    };
+
+    let leaves = gindices
+        .iter()
+        .map(|gindex| tree.node(*gindex))
+        .collect::<Result<Vec<_>, _>>()?;
+    let proof = helper_indices
+        .iter()
+        .map(|gindex| tree.node(*gindex))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(ExecutionOptimisticFinalizedResponse {
+        data: StateMultiproof {
+            state_root: tree.root(),
+            gindices,
+            leaves,
+            helper_indices,
+            proof,
+        },
+        execution_optimistic: Some(execution_optimistic),
+        finalized: Some(finalized),
+    })
+}
+
+/// Return the generalized indices of the nodes required to prove `gindices`, in descending order.
+///
+/// See `get_helper_indices` in the SSZ Merkle proof specification.
+fn get_helper_indices(gindices: &[u64]) -> Vec<u64> {
+    let mut branch_indices = BTreeSet::new();
+    let mut path_indices = BTreeSet::new();
+
+    for gindex in gindices {
+        let mut node = *gindex;
+        while node > 1 {
+            branch_indices.insert(node ^ 1);
+            path_indices.insert(node);
+            node /= 2;
+        }
+    }
+
+    branch_indices
+        .difference(&path_indices)
+        .rev()
+        .copied()
+        .collect()
+}
+
+/// An LRU cache of `StateTree`s, keyed by state root.
+///
+/// Only the tree hashes are retained, and the lock is only held to look up or insert a tree.
+pub struct StateTreeCache {
+    trees: Mutex<LruCache<Hash256, Arc<StateTree>>>,
+}
+
+impl Default for StateTreeCache {
+    fn default() -> Self {
+        Self {
+            trees: Mutex::new(LruCache::new(STATE_TREE_CACHE_SIZE)),
+        }
+    }
+}
+
+impl StateTreeCache {
+    fn get(&self, state_root: &Hash256) -> Option<Arc<StateTree>> {
+        self.trees.lock().get(state_root).cloned()
+    }
+
+    fn insert(&self, tree: Arc<StateTree>) {
+        self.trees.lock().put(tree.root(), tree);
+    }
+}
+
+/// The tree hashes of a `BeaconState`.
+///
+/// The roots of the top-level fields are computed upfront, whilst the trees of list and vector
+/// fields are only computed once a proof descends into them. A tree is never modified once it has
+/// been cached, instead a copy is cached with any additional field trees.
+#[derive(Clone)]
+struct StateTree {
+    fields: SparseTree,
+    /// The trees of the fields which have been descended into, or `None` if the field is not a
+    /// supported list or vector.
+    field_trees: HashMap<usize, Option<Arc<FieldTree>>>,
+}
+
+impl StateTree {
+    fn new<E: EthSpec>(state: &BeaconState<E>) -> Self {
+        let depth = state.num_fields_pow2().trailing_zeros() as usize;
+        let fields = SparseTree::new(state.field_roots(), depth);
+
+        Self {
+            fields,
+            field_trees: HashMap::new(),
+        }
+    }
+
+    fn root(&self) -> Hash256 {
+        self.fields.root()
+    }
+
+    /// Return the index of the top-level field containing `gindex`, if `gindex` is below it.
+    fn field_index(&self, gindex: u64) -> Option<usize> {
+        let depth = (u64::BITS - 1 - gindex.leading_zeros()) as usize;
+        let remaining_depth = depth.checked_sub(self.fields.depth).filter(|d| *d > 0)?;
+        Some(((gindex ^ (1 << depth)) >> remaining_depth) as usize)
+    }
+
+    /// Return `true` if the trees of every field containing `gindices` have been computed.
+    fn has_field_trees(&self, mut gindices: impl Iterator<Item = u64>) -> bool {
+        gindices.all(|gindex| {
+            self.field_index(gindex).map_or(true, |field_index| {
+                self.field_trees.contains_key(&field_index)
+            })
+        })
+    }
+
+    /// Compute the trees of the fields of `state` containing `gindices` which are not yet known.
+    fn with_field_trees<E: EthSpec>(
+        mut self,
+        state: &BeaconState<E>,
+        gindices: impl Iterator<Item = u64>,
+    ) -> Self {
+        for gindex in gindices {
+            if let Some(field_index) = self.field_index(gindex) {
+                self.field_trees
+                    .entry(field_index)
+                    .or_insert_with(|| field_tree(state, field_index).map(Arc::new));
+            }
+        }
+        self
+    }
+
+    /// Return the hash of the node at `gindex`.
+    fn node(&self, gindex: u64) -> Result<Hash256, warp::Rejection> {
+        let unsupported =
+            || custom_bad_request(format!("gindex {} is not supported for this state", gindex));
+
+        let depth = (u64::BITS - 1 - gindex.leading_zeros()) as usize;
+        let index = gindex ^ (1 << depth);
+
+        if depth <= self.fields.depth {
+            return self
+                .fields
+                .node(depth, index as usize)
+                .ok_or_else(unsupported);
+        }
+
+        // Descend into the tree of the field containing `gindex`.
+        let remaining_depth = depth - self.fields.depth;
+        let field_index = (index >> remaining_depth) as usize;
+        let index = index & ((1 << remaining_depth) - 1);
+        let field_tree = self
+            .field_trees
+            .get(&field_index)
+            .cloned()
+            .flatten()
+            .ok_or_else(unsupported)?;
+
+        match field_tree.length {
+            Some(length) => {
+                // The root of a list is the hash of its data and its length.
+                let remaining_depth = remaining_depth - 1;
+                let is_length = (index >> remaining_depth) == 1;
+                let index = index & ((1 << remaining_depth) - 1);
+                if is_length {
+                    if remaining_depth == 0 {
+                        Ok((length as u64).tree_hash_root())
+                    } else {
+                        Err(unsupported())
+                    }
+                } else {
+                    field_tree
+                        .data
+                        .node(remaining_depth, index as usize)
+                        .ok_or_else(unsupported)
+                }
+            }
+            None => field_tree
+                .data
+                .node(remaining_depth, index as usize)
+                .ok_or_else(unsupported),
+        }
+    }
+}
+
+/// Compute the tree of the field of `state` at `field_index`, if it is a list or vector.
+fn field_tree<E: EthSpec>(state: &BeaconState<E>, field_index: usize) -> Option<FieldTree> {
+    let field_tree = match field_index {
+        BLOCK_ROOTS_FIELD_INDEX => FieldTree::vector(
+            state.block_roots().iter(),
+            E::SlotsPerHistoricalRoot::to_usize(),
+        ),
+        STATE_ROOTS_FIELD_INDEX => FieldTree::vector(
+            state.state_roots().iter(),
+            E::SlotsPerHistoricalRoot::to_usize(),
+        ),
+        HISTORICAL_ROOTS_FIELD_INDEX => FieldTree::list(
+            state.historical_roots().iter(),
+            state.historical_roots().len(),
+            E::HistoricalRootsLimit::to_usize(),
+        ),
+        ETH1_DATA_VOTES_FIELD_INDEX => FieldTree::list(
+            state.eth1_data_votes().iter(),
+            state.eth1_data_votes().len(),
+            E::SlotsPerEth1VotingPeriod::to_usize(),
+        ),
+        VALIDATORS_FIELD_INDEX => FieldTree::list(
+            state.validators().iter(),
+            state.validators().len(),
+            E::ValidatorRegistryLimit::to_usize(),
+        ),
+        BALANCES_FIELD_INDEX => FieldTree::list(
+            state.balances().iter(),
+            state.balances().len(),
+            E::ValidatorRegistryLimit::to_usize(),
+        ),
+        RANDAO_MIXES_FIELD_INDEX => FieldTree::vector(
+            state.randao_mixes().iter(),
+            E::EpochsPerHistoricalVector::to_usize(),
+        ),
+        SLASHINGS_FIELD_INDEX => FieldTree::vector(
+            state.slashings().iter(),
+            E::EpochsPerSlashingsVector::to_usize(),
+        ),
+        PREVIOUS_EPOCH_PARTICIPATION_FIELD_INDEX => match state {
+            BeaconState::Base(inner) => FieldTree::list(
+                inner.previous_epoch_attestations.iter(),
+                inner.previous_epoch_attestations.len(),
+                E::MaxPendingAttestations::to_usize(),
+            ),
+            _ => {
+                let participation = state.previous_epoch_participation().ok()?;
+                FieldTree::list(
+                    participation.iter(),
+                    participation.len(),
+                    E::ValidatorRegistryLimit::to_usize(),
+                )
+            }
+        },
+        CURRENT_EPOCH_PARTICIPATION_FIELD_INDEX => match state {
+            BeaconState::Base(inner) => FieldTree::list(
+                inner.current_epoch_attestations.iter(),
+                inner.current_epoch_attestations.len(),
+                E::MaxPendingAttestations::to_usize(),
+            ),
+            _ => {
+                let participation = state.current_epoch_participation().ok()?;
+                FieldTree::list(
+                    participation.iter(),
+                    participation.len(),
+                    E::ValidatorRegistryLimit::to_usize(),
+                )
+            }
+        },
+        INACTIVITY_SCORES_FIELD_INDEX => {
+            let inactivity_scores = state.inactivity_scores().ok()?;
+            FieldTree::list(
+                inactivity_scores.iter(),
+                inactivity_scores.len(),
+                E::ValidatorRegistryLimit::to_usize(),
+            )
+        }
+        HISTORICAL_SUMMARIES_FIELD_INDEX => {
+            let historical_summaries = state.historical_summaries().ok()?;
+            FieldTree::list(
+                historical_summaries.iter(),
+                historical_summaries.len(),
+                E::HistoricalRootsLimit::to_usize(),
+            )
+        }
+        _ => return None,
+    };
+
+    Some(field_tree)
+}
+
+/// The tree of a list or vector field of the `BeaconState`.
+struct FieldTree {
+    data: SparseTree,
+    /// The length of the field, if it is a list (and hence has its length mixed into its root).
+    length: Option<usize>,
+}
+
+impl FieldTree {
+    fn vector<'a, T: TreeHash + 'a>(items: impl Iterator<Item = &'a T>, length: usize) -> Self {
+        Self {
+            data: SparseTree::new(chunks(items), chunk_depth::<T>(length)),
+            length: None,
+        }
+    }
+
+    fn list<'a, T: TreeHash + 'a>(
+        items: impl Iterator<Item = &'a T>,
+        length: usize,
+        limit: usize,
+    ) -> Self {
+        Self {
+            data: SparseTree::new(chunks(items), chunk_depth::<T>(limit)),
+            length: Some(length),
+        }
+    }
+}
+
+/// Return the tree hash chunks of `items`, packing basic types as per the SSZ specification.
+fn chunks<'a, T: TreeHash + 'a>(items: impl Iterator<Item = &'a T>) -> Vec<Hash256> {
+    match T::tree_hash_type() {
+        TreeHashType::Basic => {
+            let mut chunks = vec![];
+            let mut chunk = Vec::with_capacity(32);
+            for item in items {
+                chunk.extend_from_slice(&item.tree_hash_packed_encoding());
+                if chunk.len() == 32 {
+                    chunks.push(Hash256::from_slice(&chunk));
+                    chunk.clear();
+                }
+            }
+            if !chunk.is_empty() {
+                chunk.resize(32, 0);
+                chunks.push(Hash256::from_slice(&chunk));
+            }
+            chunks
+        }
+        _ => items.map(|item| item.tree_hash_root()).collect(),
+    }
+}
+
+/// Return the depth of the tree of chunks for a list or vector of up to `limit` items of type `T`.
+fn chunk_depth<T: TreeHash>(limit: usize) -> usize {
+    let items_per_chunk = match T::tree_hash_type() {
+        TreeHashType::Basic => T::tree_hash_packing_factor(),
+        _ => 1,
+    };
+    limit
+        .div_ceil(items_per_chunk)
+        .next_power_of_two()
+        .trailing_zeros() as usize
+}
+
+/// A Merkle tree with `2^depth` leaves, where all leaves after the first `layers[0].len()` are
+/// zero.
+///
+/// Only the non-zero nodes are stored.
+#[derive(Clone)]
+struct SparseTree {
+    depth: usize,
+    /// The nodes of the tree, from the leaves up to the root.
+    layers: Vec<Vec<Hash256>>,
+}
+
+impl SparseTree {
+    fn new(leaves: Vec<Hash256>, depth: usize) -> Self {
+        let mut layers = Vec::with_capacity(depth + 1);
+        layers.push(leaves);
+
+        for height in 0..depth {
+            let layer = layers[height]
+                .chunks(2)
+                .map(|pair| {
+                    let right = pair
+                        .get(1)
+                        .map_or(&ZERO_HASHES[height][..], |node| node.as_bytes());
+                    Hash256::from_slice(&hash32_concat(pair[0].as_bytes(), right))
+                })
+                .collect();
+            layers.push(layer);
+        }
+
+        Self { depth, layers }
+    }
+
+    fn root(&self) -> Hash256 {
+        // The root is always at depth `0`.
+        self.node(0, 0).unwrap_or_default()
+    }
+
+    /// Return the node at `index` amongst the `2^depth` nodes at `depth` below the root.
+    fn node(&self, depth: usize, index: usize) -> Option<Hash256> {
+        let height = self.depth.checked_sub(depth)?;
+        Some(
+            self.layers[height]
+                .get(index)
+                .copied()
+                .unwrap_or_else(|| Hash256::from_slice(&ZERO_HASHES[height])),
+        )
+    }
+}
@@ -8,6 +8,7 @@ use beacon_chain::{
 };
 use eth2::types::{into_full_block_and_blobs, BroadcastValidation, ErrorMessage};
 use eth2::types::{FullPayloadContents, PublishBlockRequest};
+use eth2::EQUIVOCATING_BLOCK_MESSAGE;
 use execution_layer::ProvenancedPayload;
 use lighthouse_network::PubsubMessage;
 use network::NetworkMessage;
@@ -17,6 +18,7 @@ use std::marker::PhantomData;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::mpsc::UnboundedSender;
+use tracing::instrument;
 use tree_hash::TreeHash;
 use types::{
     AbstractExecPayload, BeaconBlockRef, BlobSidecarList, BlockImportSource, EthSpec, ExecPayload,
@@ -45,6 +47,7 @@ impl<T: BeaconChainTypes, B: IntoGossipVerifiedBlockContents<T>> ProvenancedBloc
 }
 
 /// Handles a request from the HTTP API for full blocks.
+#[instrument(skip_all)]
 pub async fn publish_block<T: BeaconChainTypes, B: IntoGossipVerifiedBlockContents<T>>(
     block_root: Option<Hash256>,
     provenanced_block: ProvenancedBlock<T, B>,
@@ -144,6 +147,17 @@ pub async fn publish_block<T: BeaconChainTypes, B: IntoGossipVerifiedBlockConten
                 )
                 .into_response());
             }
+            Err(BlockContentsError::BlockError(BlockError::Slashable)) => {
+                warn!(
+                    log,
+                    "Not publishing equivocating block";
+                    "slot" => slot,
+                    "proposer_index" => proposer_index,
+                );
+                return Err(warp_utils::reject::custom_bad_request(
+                    EQUIVOCATING_BLOCK_MESSAGE.to_string(),
+                ));
+            }
             Err(e) => {
                 warn!(
                     log,
@@ -284,7 +298,7 @@ pub async fn publish_block<T: BeaconChainTypes, B: IntoGossipVerifiedBlockConten
             ))
         }
         Err(BlockError::Slashable) => Err(warp_utils::reject::custom_bad_request(
-            "proposal for this slot and proposer has already been seen".to_string(),
+            EQUIVOCATING_BLOCK_MESSAGE.to_string(),
         )),
         Err(e) => {
             if let BroadcastValidation::Gossip = validation_level {
@@ -315,6 +329,30 @@ pub async fn publish_blinded_block<T: BeaconChainTypes>(
     duplicate_status_code: StatusCode,
 ) -> Result<Response, Rejection> {
     let block_root = blinded_block.canonical_root();
+
+    // Check for an equivocation before revealing the block to the builder, since the builder will
+    // publish it regardless of our own checks.
+    let seen_block = chain
+        .observed_block_producers
+        .read()
+        .proposer_has_been_observed(blinded_block.message(), block_root)
+        .map_err(|e| {
+            warp_utils::reject::custom_server_error(format!(
+                "unable to check for equivocation: {e:?}"
+            ))
+        })?;
+    if seen_block.is_slashable() {
+        warn!(
+            log,
+            "Not publishing equivocating blinded block";
+            "slot" => blinded_block.slot(),
+            "proposer_index" => blinded_block.message().proposer_index(),
+        );
+        return Err(warp_utils::reject::custom_bad_request(
+            EQUIVOCATING_BLOCK_MESSAGE.to_string(),
+        ));
+    }
+
     let full_block: ProvenancedBlock<T, PublishBlockRequest<T::EthSpec>> =
         reconstruct_block(chain.clone(), block_root, blinded_block, log.clone()).await?;
     publish_block::<T, _>(
@@ -378,17 +416,45 @@ pub async fn reconstruct_block<T: BeaconChainTypes>(
                 &log,
             );
 
-            let full_payload = el
-                .propose_blinded_beacon_block(block_root, &block)
-                .await
-                .map_err(|e| {
-                    warp_utils::reject::custom_server_error(format!(
-                        "Blind block proposal failed: {:?}",
-                        e
-                    ))
-                })?;
-            info!(log, "Successfully published a block to the builder network"; "block_hash" => ?full_payload.block_hash());
-            ProvenancedPayload::Builder(full_payload)
+            match el.propose_blinded_beacon_block(block_root, &block).await {
+                Ok(full_payload) => {
+                    info!(log, "Successfully published a block to the builder network"; "block_hash" => ?full_payload.block_hash());
+                    ProvenancedPayload::Builder(full_payload)
+                }
+                Err(e) => {
+                    warn!(
+                        log,
+                        "Builder failed to reveal payload";
+                        "error" => ?e,
+                        "block_root" => ?block_root,
+                        "info" => "attempting to recover the block locally"
+                    );
+                    return match recover_unrevealed_block(&chain, block_root, &log).await {
+                        Some(full_block) => {
+                            metrics::inc_counter_vec(
+                                &metrics::HTTP_API_UNREVEALED_BLINDED_BLOCKS_TOTAL,
+                                &["recovered"],
+                            );
+                            info!(
+                                log,
+                                "Recovered block with unrevealed payload";
+                                "block_root" => ?block_root
+                            );
+                            Ok(full_block)
+                        }
+                        None => {
+                            metrics::inc_counter_vec(
+                                &metrics::HTTP_API_UNREVEALED_BLINDED_BLOCKS_TOTAL,
+                                &["unavailable"],
+                            );
+                            Err(warp_utils::reject::custom_server_error(format!(
+                                "Blind block proposal failed: {:?}",
+                                e
+                            )))
+                        }
+                    };
+                }
+            }
         };
 
         Some(full_payload_contents)
@@ -418,6 +484,47 @@ pub async fn reconstruct_block<T: BeaconChainTypes>(
     })
 }
 
+/// Attempt to recover the full block with `block_root` after the builder failed to reveal its
+/// payload.
+///
+/// The proposer signed the builder's payload header, so a different, locally built payload cannot
+/// be used in its place. However, the builder may have published the block to the network before
+/// failing to respond to us, in which case we have imported it and can publish it ourselves.
+async fn recover_unrevealed_block<T: BeaconChainTypes>(
+    chain: &BeaconChain<T>,
+    block_root: Hash256,
+    log: &Logger,
+) -> Option<ProvenancedBlock<T, PublishBlockRequest<T::EthSpec>>> {
+    let block = match chain.get_block(&block_root).await {
+        Ok(block) => block?,
+        Err(e) => {
+            warn!(log, "Unable to load block"; "error" => ?e, "block_root" => ?block_root);
+            return None;
+        }
+    };
+
+    let blob_items = match block.message().body().blob_kzg_commitments() {
+        Ok(commitments) => {
+            let blob_sidecars = chain.get_blobs(&block_root).ok()?;
+            if blob_sidecars.len() != commitments.len() {
+                return None;
+            }
+            let (proofs, blobs): (Vec<_>, Vec<_>) = blob_sidecars
+                .iter()
+                .map(|sidecar| (sidecar.kzg_proof, sidecar.blob.clone()))
+                .unzip();
+            Some((VariableList::from(proofs), VariableList::from(blobs)))
+        }
+        // Blocks prior to Deneb have no blobs.
+        Err(_) => None,
+    };
+
+    Some(ProvenancedBlock::builder(PublishBlockRequest::new(
+        Arc::new(block),
+        blob_items,
+    )))
+}
+
 /// If the `seen_timestamp` is some time after the start of the slot for
 /// `block`, create some logs to indicate that the block was published late.
 fn late_block_logging<T: BeaconChainTypes, P: AbstractExecPayload<T::EthSpec>>(
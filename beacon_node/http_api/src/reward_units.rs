@@ -0,0 +1,213 @@
+//! Converts the amounts in rewards responses from gwei to the units requested with `?units`.
+//!
+//! Amounts are converted exactly using integer arithmetic and returned as decimal strings, since
+//! amounts in wei may not fit in a `u64` and amounts in ETH are fractional.
+use eth2::lighthouse::attestation_rewards::{IdealAttestationRewards, TotalAttestationRewards};
+use eth2::lighthouse::{PagedAttestationRewards, StandardBlockReward, SyncCommitteeReward};
+use eth2::types::{RewardUnits, RewardsInUnitsResponse};
+use serde::Serialize;
+use serde_json::Value;
+use warp::reply::Response;
+use warp::Reply;
+
+const WEI_PER_GWEI: u128 = 1_000_000_000;
+const GWEI_PER_ETH: u128 = 1_000_000_000;
+
+/// A rewards response type whose amounts can be converted from gwei.
+///
+/// Implementations destructure the type exhaustively, so that adding a field to a rewards type
+/// requires deciding whether it holds an amount.
+pub trait InUnits {
+    /// Serialize `self` to JSON with its amounts converted from gwei to `units`.
+    fn in_units(&self, units: RewardUnits) -> Result<Value, String>;
+}
+
+/// Returns the JSON response for the rewards in `data`, with their amounts converted from gwei to
+/// `units`.
+pub fn rewards_in_units_response<T: InUnits>(
+    data: T,
+    execution_optimistic: bool,
+    finalized: Option<bool>,
+    units: RewardUnits,
+) -> Result<Response, warp::Rejection> {
+    let data = data
+        .in_units(units)
+        .map_err(warp_utils::reject::custom_server_error)?;
+
+    Ok(warp::reply::json(&RewardsInUnitsResponse {
+        execution_optimistic: Some(execution_optimistic),
+        finalized,
+        units,
+        data,
+    })
+    .into_response())
+}
+
+impl<T: InUnits> InUnits for Vec<T> {
+    fn in_units(&self, units: RewardUnits) -> Result<Value, String> {
+        self.iter()
+            .map(|item| item.in_units(units))
+            .collect::<Result<Vec<_>, _>>()
+            .map(Value::Array)
+    }
+}
+
+impl<T: InUnits> InUnits for Option<T> {
+    fn in_units(&self, units: RewardUnits) -> Result<Value, String> {
+        self.as_ref()
+            .map_or(Ok(Value::Null), |item| item.in_units(units))
+    }
+}
+
+impl InUnits for PagedAttestationRewards {
+    fn in_units(&self, units: RewardUnits) -> Result<Value, String> {
+        let Self {
+            ideal_rewards,
+            total_rewards,
+            next_page_token: _,
+        } = self;
+        with_fields(
+            self,
+            [
+                ("ideal_rewards", ideal_rewards.in_units(units)?),
+                ("total_rewards", total_rewards.in_units(units)?),
+            ],
+        )
+    }
+}
+
+impl InUnits for IdealAttestationRewards {
+    fn in_units(&self, units: RewardUnits) -> Result<Value, String> {
+        let Self {
+            effective_balance,
+            head,
+            target,
+            source,
+            inclusion_delay,
+            inactivity,
+            total_active_balance,
+            head_participating_balance,
+            target_participating_balance,
+            source_participating_balance,
+            is_in_inactivity_leak: _,
+        } = self;
+        let mut amounts = vec![
+            ("effective_balance", amount(*effective_balance, units)),
+            ("head", amount(*head, units)),
+            ("target", amount(*target, units)),
+            ("source", amount(*source, units)),
+            ("inactivity", amount(*inactivity, units)),
+            ("total_active_balance", amount(*total_active_balance, units)),
+            (
+                "head_participating_balance",
+                amount(*head_participating_balance, units),
+            ),
+            (
+                "target_participating_balance",
+                amount(*target_participating_balance, units),
+            ),
+            (
+                "source_participating_balance",
+                amount(*source_participating_balance, units),
+            ),
+        ];
+        if let Some(inclusion_delay) = inclusion_delay {
+            amounts.push(("inclusion_delay", amount(inclusion_delay.value, units)));
+        }
+        with_fields(self, amounts)
+    }
+}
+
+impl InUnits for TotalAttestationRewards {
+    fn in_units(&self, units: RewardUnits) -> Result<Value, String> {
+        let Self {
+            validator_index: _,
+            head,
+            target,
+            source,
+            inclusion_delay,
+            inactivity,
+        } = self;
+        let mut amounts = vec![
+            ("head", amount(*head, units)),
+            ("target", amount(*target, units)),
+            ("source", amount(*source, units)),
+            ("inactivity", amount(*inactivity, units)),
+        ];
+        if let Some(inclusion_delay) = inclusion_delay {
+            amounts.push(("inclusion_delay", amount(inclusion_delay.value, units)));
+        }
+        with_fields(self, amounts)
+    }
+}
+
+impl InUnits for StandardBlockReward {
+    fn in_units(&self, units: RewardUnits) -> Result<Value, String> {
+        let Self {
+            proposer_index: _,
+            total,
+            attestations,
+            sync_aggregate,
+            proposer_slashings,
+            attester_slashings,
+        } = self;
+        with_fields(
+            self,
+            [
+                ("total", amount(*total, units)),
+                ("attestations", amount(*attestations, units)),
+                ("sync_aggregate", amount(*sync_aggregate, units)),
+                ("proposer_slashings", amount(*proposer_slashings, units)),
+                ("attester_slashings", amount(*attester_slashings, units)),
+            ],
+        )
+    }
+}
+
+impl InUnits for SyncCommitteeReward {
+    fn in_units(&self, units: RewardUnits) -> Result<Value, String> {
+        let Self {
+            validator_index: _,
+            reward,
+        } = self;
+        with_fields(self, [("reward", amount(*reward, units))])
+    }
+}
+
+/// Serialize `item` as usual, replacing the values of the given `fields`.
+fn with_fields<T: Serialize>(
+    item: &T,
+    fields: impl IntoIterator<Item = (&'static str, Value)>,
+) -> Result<Value, String> {
+    let mut value =
+        serde_json::to_value(item).map_err(|e| format!("unable to serialize rewards: {:?}", e))?;
+    let object = value
+        .as_object_mut()
+        .ok_or_else(|| "rewards should serialize to an object".to_string())?;
+    for (name, field) in fields {
+        object.insert(name.to_string(), field);
+    }
+    Ok(value)
+}
+
+/// Convert an amount of `gwei` to a JSON decimal string in `units`.
+fn amount(gwei: impl Into<i128>, units: RewardUnits) -> Value {
+    Value::String(format_gwei(gwei.into(), units))
+}
+
+/// Format an amount of `gwei` as an exact decimal string in `units`.
+fn format_gwei(gwei: i128, units: RewardUnits) -> String {
+    let sign = if gwei < 0 { "-" } else { "" };
+    let magnitude = gwei.unsigned_abs();
+    match units {
+        RewardUnits::Gwei => gwei.to_string(),
+        // Amounts are at most `u64::MAX` gwei, so this cannot overflow.
+        RewardUnits::Wei => format!("{}{}", sign, magnitude * WEI_PER_GWEI),
+        RewardUnits::Eth => format!(
+            "{}{}.{:09}",
+            sign,
+            magnitude / GWEI_PER_ETH,
+            magnitude % GWEI_PER_ETH
+        ),
+    }
+}
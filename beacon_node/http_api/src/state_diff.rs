@@ -0,0 +1,69 @@
+use crate::state_id::StateId;
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use eth2::lighthouse::{StateDiff, StateDiffQuery, ValidatorDiff, ValidatorFields};
+use eth2::types::ValidatorStatus;
+use std::sync::Arc;
+use types::{BeaconState, Epoch, EthSpec};
+use warp_utils::reject::custom_bad_request;
+
+/// Compute the validators whose balance, status or withdrawal credentials changed between the
+/// states at `query.from` and `query.to`.
+pub fn get_state_diff<T: BeaconChainTypes>(
+    query: StateDiffQuery,
+    chain: Arc<BeaconChain<T>>,
+) -> Result<StateDiff, warp::Rejection> {
+    if query.from > query.to {
+        return Err(custom_bad_request(format!(
+            "from ({}) must not be greater than to ({})",
+            query.from, query.to
+        )));
+    }
+
+    // The execution status is not returned, the diff may include optimistic information without
+    // explicitly declaring so.
+    let (from_state, _, _) = StateId::from_slot(query.from).state(&chain)?;
+    let (to_state, _, _) = StateId::from_slot(query.to).state(&chain)?;
+
+    let far_future_epoch = chain.spec.far_future_epoch;
+
+    // The validator registry is append-only, so validators in both states share a prefix.
+    let mut from_fields = validator_fields(&from_state, far_future_epoch);
+    let validators = validator_fields(&to_state, far_future_epoch)
+        .enumerate()
+        .filter_map(|(index, to)| {
+            let from = from_fields.next();
+            if from.as_ref() == Some(&to) {
+                None
+            } else {
+                Some(ValidatorDiff {
+                    index: index as u64,
+                    from,
+                    to,
+                })
+            }
+        })
+        .collect();
+
+    Ok(StateDiff {
+        from_slot: from_state.slot(),
+        to_slot: to_state.slot(),
+        validators,
+    })
+}
+
+/// Return the compared fields of each validator in `state`, in registry order.
+fn validator_fields<E: EthSpec>(
+    state: &BeaconState<E>,
+    far_future_epoch: Epoch,
+) -> impl Iterator<Item = ValidatorFields> + '_ {
+    let epoch = state.current_epoch();
+    state
+        .validators()
+        .iter()
+        .zip(state.balances().iter())
+        .map(move |(validator, balance)| ValidatorFields {
+            balance: *balance,
+            status: ValidatorStatus::from_validator(validator, epoch, far_future_epoch),
+            withdrawal_credentials: validator.withdrawal_credentials,
+        })
+}
@@ -1,10 +1,14 @@
+use crate::metrics;
 use beacon_processor::{BeaconProcessorSend, BlockingOrAsync, Work, WorkEvent};
+use futures::future::{self, Either};
 use serde::Serialize;
 use std::future::Future;
+use std::time::Duration;
+use task_executor::cancellation::{self, CancellationToken};
 use tokio::sync::{mpsc::error::TrySendError, oneshot};
 use types::EthSpec;
 use warp::reply::{Reply, Response};
-use warp_utils::reject::convert_rejection;
+use warp_utils::reject::{convert_rejection, custom_server_error};
 
 /// Maps a request to a queue in the `BeaconProcessor`.
 #[derive(Clone, Copy)]
@@ -29,26 +33,98 @@ impl Priority {
     }
 }
 
+/// Returns an error if the request which spawned the blocking task running on this thread has been
+/// dropped, either because the client disconnected or because the request timed out.
+///
+/// Only tasks spawned by a `TaskSpawner` marked as `cancellable` are ever cancelled.
+///
+/// Long-running handlers should call this periodically (e.g., once per block or validator) to
+/// avoid computing a response which will never be sent.
+pub fn check_cancelled() -> Result<(), warp::Rejection> {
+    if cancellation::is_cancelled() {
+        Err(cancelled_error())
+    } else {
+        Ok(())
+    }
+}
+
+fn cancelled_error() -> warp::Rejection {
+    metrics::inc_counter(&metrics::HTTP_API_CANCELLED_TASKS_TOTAL);
+    custom_server_error("The request was cancelled.".to_string())
+}
+
 /// Spawns tasks on the `BeaconProcessor` or directly on the tokio executor.
 pub struct TaskSpawner<E: EthSpec> {
     /// Used to send tasks to the `BeaconProcessor`. The tokio executor will be
     /// used if this is `None`.
     beacon_processor_send: Option<BeaconProcessorSend<E>>,
+    /// The maximum time to wait for a cancellable task. The request fails and the task is
+    /// cancelled if it takes any longer.
+    timeout: Option<Duration>,
+    /// Whether the task is cancelled if the request is dropped or times out.
+    cancellable: bool,
 }
 
 impl<E: EthSpec> TaskSpawner<E> {
     pub fn new(beacon_processor_send: Option<BeaconProcessorSend<E>>) -> Self {
         Self {
             beacon_processor_send,
+            timeout: None,
+            cancellable: false,
         }
     }
 
+    /// Set the timeout for the task, which only applies if it is `cancellable`.
+    pub fn with_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Cancel the task if the request is dropped or times out.
+    ///
+    /// This must only be used for read-only requests. Tasks which publish or submit messages (e.g.
+    /// blocks or attestations) must always run to completion, even if the client has gone away.
+    pub fn cancellable(mut self) -> Self {
+        self.cancellable = true;
+        self
+    }
+
     /// Executes a "blocking" (non-async) task which returns an arbitrary value.
+    ///
+    /// If the spawner is `cancellable`, the task is cancelled if the returned future is dropped,
+    /// see `check_cancelled`.
     pub async fn blocking_task<F, T>(
         self,
         priority: Priority,
         func: F,
     ) -> Result<T, warp::Rejection>
+    where
+        F: FnOnce() -> Result<T, warp::Rejection> + Send + Sync + 'static,
+        T: Send + 'static,
+    {
+        if !self.cancellable {
+            return self.blocking_task_inner(priority, func).await;
+        }
+
+        let token = CancellationToken::new();
+        let _cancel_on_drop = token.drop_guard();
+        let func = move || {
+            // Skip the task entirely if the request was dropped whilst it was queued.
+            if token.is_cancelled() {
+                return Err(cancelled_error());
+            }
+            token.run(func)
+        };
+
+        let timeout = self.timeout;
+        with_timeout(timeout, self.blocking_task_inner(priority, func)).await
+    }
+
+    async fn blocking_task_inner<F, T>(
+        self,
+        priority: Priority,
+        func: F,
+    ) -> Result<T, warp::Rejection>
     where
         F: FnOnce() -> Result<T, warp::Rejection> + Send + Sync + 'static,
         T: Send + 'static,
@@ -123,13 +199,38 @@ impl<E: EthSpec> TaskSpawner<E> {
         priority: Priority,
         func: impl Future<Output = Result<Response, warp::Rejection>> + Send + Sync + 'static,
     ) -> Result<Response, warp::Rejection> {
+        if !self.cancellable {
+            return self.spawn_async_inner(priority, func).await;
+        }
+
+        let timeout = self.timeout;
+        with_timeout(timeout, self.spawn_async_inner(priority, func)).await
+    }
+
+    async fn spawn_async_inner(
+        self,
+        priority: Priority,
+        func: impl Future<Output = Result<Response, warp::Rejection>> + Send + Sync + 'static,
+    ) -> Result<Response, warp::Rejection> {
+        let cancellable = self.cancellable;
         if let Some(beacon_processor_send) = &self.beacon_processor_send {
             // Create a wrapper future that will execute `func` and send the
             // result to a channel held by this thread.
-            let (tx, rx) = oneshot::channel();
+            let (mut tx, rx) = oneshot::channel();
             let process_fn = async move {
-                // Await the future, collect the return value.
-                let func_result = func.await;
+                // Await the future, collect the return value. If the task is cancellable, stop
+                // early if the receiver is dropped, since the result is no longer required.
+                let func_result = if cancellable {
+                    match future::select(Box::pin(func), Box::pin(tx.closed())).await {
+                        Either::Left((func_result, _)) => func_result,
+                        Either::Right(_) => {
+                            metrics::inc_counter(&metrics::HTTP_API_CANCELLED_TASKS_TOTAL);
+                            return;
+                        }
+                    }
+                } else {
+                    func.await
+                };
                 // Send the result down the channel. Ignore any failures; the
                 // send can only fail if the receiver is dropped.
                 let _ = tx.send(func_result);
@@ -157,6 +258,27 @@ impl<E: EthSpec> TaskSpawner<E> {
     }
 }
 
+/// Await `future`, failing the request if it takes longer than `timeout`.
+///
+/// Dropping `future` on timeout cancels the task, see `check_cancelled`.
+async fn with_timeout<T>(
+    timeout: Option<Duration>,
+    future: impl Future<Output = Result<T, warp::Rejection>>,
+) -> Result<T, warp::Rejection> {
+    let Some(timeout) = timeout else {
+        return future.await;
+    };
+    tokio::time::timeout(timeout, future)
+        .await
+        .unwrap_or_else(|_| {
+            metrics::inc_counter(&metrics::HTTP_API_TIMED_OUT_REQUESTS_TOTAL);
+            Err(custom_server_error(format!(
+                "The request timed out after {}s.",
+                timeout.as_secs_f64()
+            )))
+        })
+}
+
 /// Send a task to the beacon processor and await execution.
 ///
 /// If the task is not executed, return an `Err` with an error message
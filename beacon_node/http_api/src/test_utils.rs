@@ -5,6 +5,7 @@ use beacon_chain::{
 };
 use beacon_processor::{
     BeaconProcessor, BeaconProcessorChannels, BeaconProcessorConfig, BeaconProcessorQueueLengths,
+    BeaconProcessorQueueStats,
 };
 use directory::DEFAULT_ROOT_DIR;
 use eth2::{BeaconNodeHttpClient, Timeouts};
@@ -18,7 +19,7 @@ use lighthouse_network::{
     types::{EnrAttestationBitfield, EnrSyncCommitteeBitfield, SyncState},
     ConnectedPoint, Enr, NetworkGlobals, PeerId, PeerManager,
 };
-use logging::test_logger;
+use logging::{test_logger, ModuleLevels};
 use network::{NetworkReceivers, NetworkSenders};
 use sensitive_url::SensitiveUrl;
 use slog::Logger;
@@ -194,11 +195,13 @@ pub async fn create_api_server<T: BeaconChainTypes>(
 
     let beacon_processor_send = beacon_processor_tx;
     let reprocess_send = work_reprocessing_tx.clone();
+    let queue_stats = BeaconProcessorQueueStats::default();
     BeaconProcessor {
         network_globals: network_globals.clone(),
         executor: test_runtime.task_executor.clone(),
         current_workers: 0,
         config: beacon_processor_config,
+        queue_stats: queue_stats.clone(),
         log: log.clone(),
     }
     .spawn_manager(
@@ -229,8 +232,10 @@ pub async fn create_api_server<T: BeaconChainTypes>(
         network_globals: Some(network_globals),
         beacon_processor_send: Some(beacon_processor_send),
         beacon_processor_reprocess_send: Some(reprocess_send),
+        beacon_processor_queue_stats: Some(queue_stats),
         eth1_service: Some(eth1_service),
         sse_logging_components: None,
+        module_levels: Some(ModuleLevels::default()),
         log,
     });
 
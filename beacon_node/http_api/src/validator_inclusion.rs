@@ -1,7 +1,9 @@
 use crate::state_id::StateId;
 use beacon_chain::{BeaconChain, BeaconChainTypes};
 use eth2::{
-    lighthouse::{GlobalValidatorInclusionData, ValidatorInclusionData},
+    lighthouse::{
+        EpochParticipation, FlagParticipation, GlobalValidatorInclusionData, ValidatorInclusionData,
+    },
     types::ValidatorId,
 };
 use state_processing::per_epoch_processing::{process_epoch, EpochProcessingSummary};
@@ -59,6 +61,92 @@ pub fn global_validator_inclusion_data<T: BeaconChainTypes>(
     })
 }
 
+/// Returns the network-wide participation of validators in attestations during `epoch`.
+///
+/// Participation is read from the state at the end of `epoch + 1`, after which attestations for
+/// `epoch` can no longer be included.
+pub fn epoch_participation<T: BeaconChainTypes>(
+    epoch: Epoch,
+    chain: &BeaconChain<T>,
+) -> Result<EpochParticipation, warp::Rejection> {
+    let mut state = end_of_epoch_state(epoch + 1, chain)?;
+    let validator_count = state.validators().len();
+    let (active_validators, active_gwei) = state
+        .validators()
+        .iter()
+        .filter(|validator| validator.is_active_at(epoch))
+        .fold((0, 0), |(count, gwei), validator| {
+            (count + 1, gwei + validator.effective_balance)
+        });
+
+    let summary = get_epoch_processing_summary(&mut state, &chain.spec)?;
+
+    let (mut source_validators, mut target_validators, mut head_validators) = (0, 0, 0);
+    for index in 0..validator_count {
+        // Prior to Altair, slashed validators are reported as attesters.
+        if !summary.is_active_unslashed_in_previous_epoch(index) {
+            continue;
+        }
+        if summary
+            .is_previous_epoch_source_attester(index)
+            .map_err(convert_cache_error)?
+        {
+            source_validators += 1;
+        }
+        if summary
+            .is_previous_epoch_target_attester(index)
+            .map_err(convert_cache_error)?
+        {
+            target_validators += 1;
+        }
+        if summary
+            .is_previous_epoch_head_attester(index)
+            .map_err(convert_cache_error)?
+        {
+            head_validators += 1;
+        }
+    }
+
+    let flag_participation = |validators: u64, gwei: u64| FlagParticipation {
+        validators,
+        gwei,
+        validator_rate: rate(validators, active_validators),
+        balance_rate: rate(gwei, active_gwei),
+    };
+
+    Ok(EpochParticipation {
+        epoch,
+        active_validators,
+        active_gwei,
+        source: flag_participation(
+            source_validators,
+            summary
+                .previous_epoch_source_attesting_balance()
+                .map_err(convert_cache_error)?,
+        ),
+        target: flag_participation(
+            target_validators,
+            summary
+                .previous_epoch_target_attesting_balance()
+                .map_err(convert_cache_error)?,
+        ),
+        head: flag_participation(
+            head_validators,
+            summary
+                .previous_epoch_head_attesting_balance()
+                .map_err(convert_cache_error)?,
+        ),
+    })
+}
+
+fn rate(numerator: u64, denominator: u64) -> f64 {
+    if denominator == 0 {
+        0.0
+    } else {
+        numerator as f64 / denominator as f64
+    }
+}
+
 /// Returns information about a single validator and how it performed during a given epoch.
 pub fn validator_inclusion_data<T: BeaconChainTypes>(
     epoch: Epoch,
@@ -0,0 +1,457 @@
+use crate::state_id::StateId;
+use beacon_chain::{BeaconChain, BeaconChainError, BeaconChainTypes};
+use eth2::lighthouse::{
+    ValidatorSetChange, ValidatorSetChangeKind, ValidatorSetChanges, ValidatorSetChangesQuery,
+};
+use eth2::types::ExecutionOptimisticFinalizedResponse;
+use serde_utils::quoted_u64::Quoted;
+use state_processing::{per_slot_processing, SlotProcessingError};
+use std::collections::HashSet;
+use std::sync::Arc;
+use types::{BeaconState, ChainSpec, Epoch, EthSpec, PendingBalanceDeposit, PublicKeyBytes, Slot};
+use warp_utils::reject::{beacon_chain_error, custom_server_error};
+
+/// The maximum number of epochs covered by a single response.
+///
+/// Each epoch requires its blocks, and since Electra a state, to be loaded, so a response is
+/// limited to bound the work done for a distant `since_epoch`.
+pub const MAX_EPOCHS_PER_RESPONSE: u64 = 64;
+
+/// Return the changes to the validator set processed between the start of `query.since_epoch` and
+/// the start of the current epoch of the head, or of the epoch `MAX_EPOCHS_PER_RESPONSE` later if
+/// that is earlier.
+///
+/// Only whole epochs are reported so that consecutive requests, each using the `next_epoch` of the
+/// previous response as their `since_epoch`, never report a change twice.
+pub fn get_validator_set_changes<T: BeaconChainTypes>(
+    query: ValidatorSetChangesQuery,
+    chain: Arc<BeaconChain<T>>,
+) -> Result<ExecutionOptimisticFinalizedResponse<ValidatorSetChanges>, warp::Rejection> {
+    let since_epoch = query.since_epoch;
+    let head_epoch = chain
+        .canonical_head
+        .cached_head()
+        .head_slot()
+        .epoch(T::EthSpec::slots_per_epoch());
+    let next_epoch = capped_next_epoch(since_epoch, head_epoch);
+
+    let slots_per_epoch = T::EthSpec::slots_per_epoch();
+    let to_state_id = StateId::from_slot(next_epoch.start_slot(slots_per_epoch));
+
+    if since_epoch >= next_epoch {
+        let (_, execution_optimistic, finalized) = to_state_id.root(&chain)?;
+        return Ok(ExecutionOptimisticFinalizedResponse {
+            data: ValidatorSetChanges {
+                since_epoch,
+                next_epoch: since_epoch,
+                changes: vec![],
+            },
+            execution_optimistic: Some(execution_optimistic),
+            finalized: Some(finalized),
+        });
+    }
+
+    let from_slot = since_epoch.start_slot(slots_per_epoch);
+    let (from_state, _, _) = StateId::from_slot(from_slot).state(&chain)?;
+    let (to_state, execution_optimistic, finalized) = to_state_id.state(&chain)?;
+
+    let from_validators = from_state.validators();
+    let mut changes = registry_changes(&from_state, &to_state, &chain.spec);
+
+    // Before Electra, deposits to existing validators don't change the registry, so they are found
+    // in the blocks. The first deposit of a new validator is already reported as a `Deposit` above.
+    let mut new_validators = HashSet::new();
+    for (pubkey, amount) in deposits_between(&chain, from_slot, to_state.slot())? {
+        let Some(index) = chain.validator_index(&pubkey).map_err(beacon_chain_error)? else {
+            // The deposit had an invalid signature and didn't create a validator.
+            continue;
+        };
+        if index >= from_validators.len() && new_validators.insert(index) {
+            continue;
+        }
+        changes.push(top_up(index, pubkey, amount));
+    }
+
+    // Since Electra, deposits are queued and credited by the epoch processing, subject to the
+    // churn limit, so they are reported when they are applied rather than when they are included.
+    for epoch in (since_epoch.as_u64() + 1..=next_epoch.as_u64()).map(Epoch::new) {
+        let last_slot = epoch.start_slot(slots_per_epoch) - 1;
+        if !chain
+            .spec
+            .fork_name_at_slot::<T::EthSpec>(last_slot)
+            .electra_enabled()
+        {
+            continue;
+        }
+        let (state, _, _) = StateId::from_slot(last_slot).state(&chain)?;
+        let applied = applied_pending_deposits(state, &chain.spec).map_err(|e| {
+            custom_server_error(format!("unable to process epoch {}: {:?}", epoch - 1, e))
+        })?;
+        for deposit in applied {
+            let index = deposit.index as usize;
+            let pubkey = to_state
+                .validators()
+                .get(index)
+                .map(|validator| validator.pubkey)
+                .ok_or_else(|| custom_server_error(format!("unknown validator {}", index)))?;
+            changes.push(top_up(index, pubkey, deposit.amount));
+        }
+    }
+
+    Ok(ExecutionOptimisticFinalizedResponse {
+        data: ValidatorSetChanges {
+            since_epoch,
+            next_epoch,
+            changes,
+        },
+        execution_optimistic: Some(execution_optimistic),
+        finalized: Some(finalized),
+    })
+}
+
+/// Return the epoch at which a response starting at `since_epoch` ends, given the current epoch of
+/// the head.
+fn capped_next_epoch(since_epoch: Epoch, head_epoch: Epoch) -> Epoch {
+    std::cmp::min(
+        head_epoch,
+        since_epoch.saturating_add(MAX_EPOCHS_PER_RESPONSE),
+    )
+}
+
+/// Return the new, activated, exited and slashed validators of `to_state`, relative to
+/// `from_state`.
+fn registry_changes<E: EthSpec>(
+    from_state: &BeaconState<E>,
+    to_state: &BeaconState<E>,
+    spec: &ChainSpec,
+) -> Vec<ValidatorSetChange> {
+    let far_future_epoch = spec.far_future_epoch;
+    let from_validators = from_state.validators();
+    let mut changes = vec![];
+
+    for (index, to) in to_state.validators().iter().enumerate() {
+        let from = from_validators.get(index);
+        let change = |kind, epoch| ValidatorSetChange {
+            validator_index: index as u64,
+            pubkey: to.pubkey,
+            kind,
+            epoch,
+            amount: None,
+        };
+
+        if from.is_none() {
+            changes.push(change(ValidatorSetChangeKind::Deposit, None));
+        }
+        if to.activation_epoch != far_future_epoch
+            && from.map_or(true, |from| from.activation_epoch != to.activation_epoch)
+        {
+            changes.push(change(
+                ValidatorSetChangeKind::Activation,
+                Some(to.activation_epoch),
+            ));
+        }
+        if to.exit_epoch != far_future_epoch
+            && from.map_or(true, |from| from.exit_epoch != to.exit_epoch)
+        {
+            changes.push(change(ValidatorSetChangeKind::Exit, Some(to.exit_epoch)));
+        }
+        if to.slashed && from.map_or(true, |from| !from.slashed) {
+            changes.push(change(ValidatorSetChangeKind::Slashing, None));
+        }
+    }
+
+    changes
+}
+
+fn top_up(index: usize, pubkey: PublicKeyBytes, amount: u64) -> ValidatorSetChange {
+    ValidatorSetChange {
+        validator_index: index as u64,
+        pubkey,
+        kind: ValidatorSetChangeKind::TopUp,
+        epoch: None,
+        amount: Some(Quoted { value: amount }),
+    }
+}
+
+/// Return the pending balance deposits credited by the epoch processing of `state`, which must be
+/// at the last slot of an epoch.
+fn applied_pending_deposits<E: EthSpec>(
+    mut state: BeaconState<E>,
+    spec: &ChainSpec,
+) -> Result<Vec<PendingBalanceDeposit>, SlotProcessingError> {
+    let pending = match state.pending_balance_deposits() {
+        Ok(pending) if !pending.is_empty() => pending.iter().cloned().collect::<Vec<_>>(),
+        _ => return Ok(vec![]),
+    };
+
+    // The deposits are consumed from the front of the queue.
+    per_slot_processing(&mut state, None, spec)?;
+    let remaining = state
+        .pending_balance_deposits()
+        .map_or(0, |pending| pending.len());
+    let applied = pending.len().saturating_sub(remaining);
+
+    Ok(pending.into_iter().take(applied).collect())
+}
+
+/// Return the pubkey and amount of each deposit processed by the pre-Electra blocks after
+/// `from_slot`, up to and including `to_slot`, in the order they were processed.
+fn deposits_between<T: BeaconChainTypes>(
+    chain: &BeaconChain<T>,
+    from_slot: Slot,
+    to_slot: Slot,
+) -> Result<Vec<(PublicKeyBytes, u64)>, warp::Rejection> {
+    let to_slot = match chain.spec.electra_fork_epoch {
+        Some(epoch) => std::cmp::min(to_slot, epoch.start_slot(T::EthSpec::slots_per_epoch()) - 1),
+        None => to_slot,
+    };
+
+    let mut deposits = vec![];
+    if from_slot >= to_slot {
+        return Ok(deposits);
+    }
+
+    let mut block_roots = chain
+        .forwards_iter_block_roots_until(from_slot + 1, to_slot)
+        .map_err(beacon_chain_error)?
+        .map(|res| res.map(|(root, _)| root))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(beacon_chain_error)?;
+    block_roots.dedup();
+
+    for root in block_roots {
+        let block = chain
+            .get_blinded_block(&root)
+            .map_err(beacon_chain_error)?
+            .ok_or_else(|| beacon_chain_error(BeaconChainError::MissingBeaconBlock(root)))?;
+        // Skipped slots repeat the root of the prior block.
+        if block.slot() > from_slot {
+            deposits.extend(
+                block
+                    .message()
+                    .body()
+                    .deposits()
+                    .iter()
+                    .map(|deposit| (deposit.data.pubkey, deposit.data.amount)),
+            );
+        }
+    }
+
+    Ok(deposits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use execution_layer::test_utils::generate_genesis_header;
+    use genesis::{interop_genesis_state, DEFAULT_ETH1_BLOCK_HASH};
+    use state_processing::common::initiate_validator_exit;
+    use state_processing::per_block_processing::process_operations::process_deposit_requests;
+    use state_processing::state_advance::complete_state_advance;
+    use state_processing::AllCaches;
+    use types::test_utils::generate_deterministic_keypairs;
+    use types::{
+        DepositData, DepositRequest, ForkName, Hash256, Keypair, MinimalEthSpec, SignatureBytes,
+        SignedRoot,
+    };
+
+    type E = MinimalEthSpec;
+
+    const VALIDATOR_COUNT: usize = 64;
+    const DEPOSIT_AMOUNT: u64 = 32_000_000_000;
+
+    fn genesis_state(spec: &ChainSpec) -> BeaconState<E> {
+        let keypairs = generate_deterministic_keypairs(VALIDATOR_COUNT);
+        let header = generate_genesis_header::<E>(spec, false);
+        let mut state = interop_genesis_state::<E>(
+            &keypairs,
+            0,
+            Hash256::from_slice(DEFAULT_ETH1_BLOCK_HASH),
+            header,
+            spec,
+        )
+        .unwrap();
+        state.build_all_caches(spec).unwrap();
+        state
+    }
+
+    /// A deposit request for `keypair`, signed by `signer`.
+    fn deposit_request(
+        keypair: &Keypair,
+        signer: &Keypair,
+        amount: u64,
+        index: u64,
+        spec: &ChainSpec,
+    ) -> DepositRequest {
+        let data = DepositData {
+            pubkey: keypair.pk.compress(),
+            withdrawal_credentials: Hash256::zero(),
+            amount,
+            signature: SignatureBytes::empty(),
+        };
+        let signing_root = data
+            .as_deposit_message()
+            .signing_root(spec.get_deposit_domain());
+        DepositRequest {
+            pubkey: data.pubkey,
+            withdrawal_credentials: data.withdrawal_credentials,
+            amount,
+            signature: signer.sk.sign(signing_root),
+            index,
+        }
+    }
+
+    #[test]
+    fn responses_are_capped() {
+        let head_epoch = Epoch::new(1000);
+
+        // A distant `since_epoch` is served in chunks of `MAX_EPOCHS_PER_RESPONSE` epochs.
+        let mut since_epoch = Epoch::new(0);
+        let mut responses = 0;
+        while since_epoch < head_epoch {
+            let next_epoch = capped_next_epoch(since_epoch, head_epoch);
+            assert!(next_epoch > since_epoch);
+            assert!(next_epoch - since_epoch <= MAX_EPOCHS_PER_RESPONSE);
+            since_epoch = next_epoch;
+            responses += 1;
+        }
+        assert_eq!(since_epoch, head_epoch);
+        assert_eq!(responses, 1000_u64.div_ceil(MAX_EPOCHS_PER_RESPONSE));
+
+        assert_eq!(
+            capped_next_epoch(Epoch::new(0), head_epoch),
+            Epoch::new(MAX_EPOCHS_PER_RESPONSE)
+        );
+        assert_eq!(capped_next_epoch(head_epoch - 1, head_epoch), head_epoch);
+        assert_eq!(
+            capped_next_epoch(Epoch::new(u64::MAX), head_epoch),
+            head_epoch
+        );
+    }
+
+    #[test]
+    fn registry_changes_report_activations_and_exits() {
+        let spec = ForkName::Electra.make_genesis_spec(E::default_spec());
+        let mut from_state = genesis_state(&spec);
+        let keypair = Keypair::random();
+        process_deposit_requests(
+            &mut from_state,
+            &[deposit_request(
+                &keypair,
+                &keypair,
+                DEPOSIT_AMOUNT,
+                0,
+                &spec,
+            )],
+            &spec,
+        )
+        .unwrap();
+
+        let mut to_state = from_state.clone();
+        initiate_validator_exit(&mut to_state, 1, &spec).unwrap();
+        let exit_epoch = to_state.validators().get(1).unwrap().exit_epoch;
+        to_state
+            .validators_mut()
+            .get_mut(VALIDATOR_COUNT)
+            .unwrap()
+            .activation_epoch = Epoch::new(5);
+
+        let changes = registry_changes(&from_state, &to_state, &spec);
+        let kinds = changes
+            .iter()
+            .map(|change| (change.validator_index, change.kind, change.epoch))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            kinds,
+            vec![
+                (1, ValidatorSetChangeKind::Exit, Some(exit_epoch)),
+                (
+                    VALIDATOR_COUNT as u64,
+                    ValidatorSetChangeKind::Activation,
+                    Some(Epoch::new(5))
+                ),
+            ]
+        );
+        assert_eq!(changes[1].pubkey, keypair.pk.compress());
+    }
+
+    /// Electra deposit requests are queued by the block which includes them, and only reported
+    /// once the epoch processing credits them, subject to the churn limit.
+    #[test]
+    fn deposit_requests_are_reported_when_applied() {
+        let spec = ForkName::Electra.make_genesis_spec(E::default_spec());
+        let from_state = genesis_state(&spec);
+        let existing = generate_deterministic_keypairs(1).remove(0);
+        let (new, invalid, queued) = (Keypair::random(), Keypair::random(), Keypair::random());
+
+        let mut state = from_state.clone();
+        process_deposit_requests(
+            &mut state,
+            &[
+                deposit_request(&existing, &existing, 1_000_000_000, 0, &spec),
+                deposit_request(&new, &new, DEPOSIT_AMOUNT, 1, &spec),
+                deposit_request(&invalid, &new, DEPOSIT_AMOUNT, 2, &spec),
+                deposit_request(&queued, &queued, DEPOSIT_AMOUNT, 3, &spec),
+            ],
+            &spec,
+        )
+        .unwrap();
+
+        // The block adds the new validators to the registry without crediting any balance. The
+        // request with an invalid signature is dropped.
+        let changes = registry_changes(&from_state, &state, &spec);
+        let deposits = changes
+            .iter()
+            .map(|change| (change.validator_index, change.kind, change.pubkey))
+            .collect::<Vec<_>>();
+        let new_index = VALIDATOR_COUNT as u64;
+        let queued_index = new_index + 1;
+        assert_eq!(
+            deposits,
+            vec![
+                (
+                    new_index,
+                    ValidatorSetChangeKind::Deposit,
+                    new.pk.compress()
+                ),
+                (
+                    queued_index,
+                    ValidatorSetChangeKind::Deposit,
+                    queued.pk.compress()
+                ),
+            ]
+        );
+        assert_eq!(state.balances().get(new_index as usize), Some(&0));
+
+        // The churn limit of the minimal spec is 64 ETH, so the last deposit waits an epoch.
+        let slots_per_epoch = E::slots_per_epoch();
+        complete_state_advance(&mut state, None, Slot::new(slots_per_epoch - 1), &spec).unwrap();
+        assert_eq!(
+            applied_pending_deposits(state.clone(), &spec).unwrap(),
+            vec![
+                PendingBalanceDeposit {
+                    index: 0,
+                    amount: 1_000_000_000
+                },
+                PendingBalanceDeposit {
+                    index: new_index,
+                    amount: DEPOSIT_AMOUNT
+                },
+            ]
+        );
+
+        complete_state_advance(&mut state, None, Slot::new(2 * slots_per_epoch - 1), &spec)
+            .unwrap();
+        assert_eq!(
+            applied_pending_deposits(state.clone(), &spec).unwrap(),
+            vec![PendingBalanceDeposit {
+                index: queued_index,
+                amount: DEPOSIT_AMOUNT
+            }]
+        );
+
+        complete_state_advance(&mut state, None, Slot::new(3 * slots_per_epoch - 1), &spec)
+            .unwrap();
+        assert!(applied_pending_deposits(state, &spec).unwrap().is_empty());
+    }
+}
@@ -1,16 +1,25 @@
 use crate::state_id::StateId;
-use beacon_chain::{BeaconChain, BeaconChainTypes};
+use beacon_chain::{balances_cache::Balances, BeaconChain, BeaconChainTypes};
 use eth2::types::{
-    self as api_types, ExecutionOptimisticFinalizedResponse, ValidatorBalanceData, ValidatorData,
-    ValidatorId, ValidatorStatus,
+    self as api_types, ExecutionOptimisticFinalizedResponse, StateId as CoreStateId,
+    ValidatorBalanceData, ValidatorData, ValidatorId, ValidatorStatus,
 };
-use std::{collections::HashSet, sync::Arc};
+use std::{collections::BTreeSet, sync::Arc};
+use types::{BeaconState, EthSpec, PublicKeyBytes};
 
+/// Returns the validators in the state identified by `state_id`, filtered by `query_ids` and
+/// `query_statuses`.
+///
+/// The matching validators are returned in ascending index order, skipping the first `offset` and
+/// returning at most `limit`. Validators are only copied out of the state once they are known to be
+/// part of the response.
 pub fn get_beacon_state_validators<T: BeaconChainTypes>(
     state_id: StateId,
     chain: Arc<BeaconChain<T>>,
     query_ids: &Option<Vec<ValidatorId>>,
     query_statuses: &Option<Vec<ValidatorStatus>>,
+    offset: usize,
+    limit: Option<usize>,
 ) -> Result<ExecutionOptimisticFinalizedResponse<Vec<ValidatorData>>, warp::Rejection> {
     let (data, execution_optimistic, finalized) = state_id
         .map_state_and_execution_optimistic_and_finalized(
@@ -18,50 +27,40 @@ pub fn get_beacon_state_validators<T: BeaconChainTypes>(
             |state, execution_optimistic, finalized| {
                 let epoch = state.current_epoch();
                 let far_future_epoch = chain.spec.far_future_epoch;
-                let ids_filter_set: Option<HashSet<&ValidatorId>> =
-                    query_ids.as_ref().map(HashSet::from_iter);
 
-                Ok((
-                    state
-                        .validators()
-                        .iter()
-                        .zip(state.balances().iter())
-                        .enumerate()
-                        // filter by validator id(s) if provided
-                        .filter(|(index, (validator, _))| {
-                            ids_filter_set.as_ref().map_or(true, |ids_set| {
-                                ids_set.contains(&ValidatorId::PublicKey(validator.pubkey))
-                                    || ids_set.contains(&ValidatorId::Index(*index as u64))
-                            })
-                        })
-                        // filter by status(es) if provided and map the result
-                        .filter_map(|(index, (validator, balance))| {
-                            let status = api_types::ValidatorStatus::from_validator(
-                                validator,
-                                epoch,
-                                far_future_epoch,
-                            );
+                // If ids are provided, look them up directly rather than iterating the entire
+                // validator registry.
+                let indices: Box<dyn Iterator<Item = usize>> = match query_ids {
+                    Some(ids) => Box::new(resolve_state_validator_indices(&chain, state, ids)),
+                    None => Box::new(0..state.validators().len()),
+                };
 
-                            let status_matches = query_statuses.as_ref().map_or(true, |statuses| {
-                                statuses.contains(&status)
-                                    || statuses.contains(&status.superstatus())
-                            });
+                let data = indices
+                    .filter_map(|index| {
+                        let validator = state.validators().get(index)?;
+                        let balance = *state.balances().get(index)?;
+                        let status = api_types::ValidatorStatus::from_validator(
+                            validator,
+                            epoch,
+                            far_future_epoch,
+                        );
+                        // filter by status(es) if provided
+                        let status_matches = query_statuses.as_ref().map_or(true, |statuses| {
+                            statuses.contains(&status) || statuses.contains(&status.superstatus())
+                        });
+                        status_matches.then_some((index, validator, balance, status))
+                    })
+                    .skip(offset)
+                    .take(limit.unwrap_or(usize::MAX))
+                    .map(|(index, validator, balance, status)| ValidatorData {
+                        index: index as u64,
+                        balance,
+                        status,
+                        validator: validator.clone(),
+                    })
+                    .collect::<Vec<_>>();
 
-                            if status_matches {
-                                Some(ValidatorData {
-                                    index: index as u64,
-                                    balance: *balance,
-                                    status,
-                                    validator: validator.clone(),
-                                })
-                            } else {
-                                None
-                            }
-                        })
-                        .collect::<Vec<_>>(),
-                    execution_optimistic,
-                    finalized,
-                ))
+                Ok((data, execution_optimistic, finalized))
             },
         )?;
 
@@ -72,36 +71,96 @@ pub fn get_beacon_state_validators<T: BeaconChainTypes>(
     })
 }
 
+/// Returns the indices of the validators in `state` identified by `ids`, in ascending order and
+/// without duplicates. Unknown validators are ignored.
+fn resolve_state_validator_indices<T: BeaconChainTypes>(
+    chain: &BeaconChain<T>,
+    state: &BeaconState<T::EthSpec>,
+    ids: &[ValidatorId],
+) -> impl Iterator<Item = usize> {
+    resolve_validator_indices(chain, ids, |index, pubkey| {
+        state
+            .validators()
+            .get(index)
+            .map_or(false, |validator| validator.pubkey == *pubkey)
+    })
+    .into_iter()
+}
+
+/// Returns the indices of the validators identified by `ids`, in ascending order and without
+/// duplicates.
+///
+/// Public keys are resolved using the validator pubkey cache, and are ignored unless
+/// `has_pubkey(index, pubkey)` confirms that the validator at the resolved index has that pubkey,
+/// since the pubkey cache may be ahead of an older state. Indices are returned as given, so
+/// callers must ignore any unknown indices.
+fn resolve_validator_indices<T: BeaconChainTypes>(
+    chain: &BeaconChain<T>,
+    ids: &[ValidatorId],
+    has_pubkey: impl Fn(usize, &PublicKeyBytes) -> bool,
+) -> BTreeSet<usize> {
+    ids.iter()
+        .filter_map(|id| match id {
+            ValidatorId::Index(index) => usize::try_from(*index).ok(),
+            ValidatorId::PublicKey(pubkey) => chain
+                .validator_index(pubkey)
+                .ok()
+                .flatten()
+                .filter(|index| has_pubkey(*index, pubkey)),
+        })
+        .collect()
+}
+
 pub fn get_beacon_state_validator_balances<T: BeaconChainTypes>(
     state_id: StateId,
     chain: Arc<BeaconChain<T>>,
     optional_ids: Option<&[ValidatorId]>,
 ) -> Result<ExecutionOptimisticFinalizedResponse<Vec<ValidatorBalanceData>>, warp::Rejection> {
+    // The balances of the head and finalized states are served from the balances cache, avoiding
+    // a state load.
+    if matches!(state_id.0, CoreStateId::Head | CoreStateId::Finalized) {
+        let (state_root, execution_optimistic, finalized) = state_id.root(&chain)?;
+        let balances = match chain.balances_cache.get(state_root) {
+            Some(balances) => Some(balances),
+            // The head balances are refreshed by the beacon chain, only populate the finalized
+            // balances here.
+            None if finalized => {
+                let (state, _, _) = state_id.state(&chain)?;
+                Some(chain.balances_cache.update_finalized(state_root, &state))
+            }
+            None => None,
+        };
+
+        if let Some(balances) = balances {
+            let indices: Box<dyn Iterator<Item = usize>> = match optional_ids {
+                // The cache doesn't hold pubkeys, but validator indices are never reassigned, so
+                // the pubkey cache agrees with any state that has the validator.
+                Some(ids) => Box::new(
+                    resolve_validator_indices(&chain, ids, |index, _| index < balances.len())
+                        .into_iter(),
+                ),
+                None => Box::new(0..balances.len()),
+            };
+
+            return Ok(api_types::ExecutionOptimisticFinalizedResponse {
+                data: validator_balances(&balances, indices),
+                execution_optimistic: Some(execution_optimistic),
+                finalized: Some(finalized),
+            });
+        }
+    }
+
     let (data, execution_optimistic, finalized) = state_id
         .map_state_and_execution_optimistic_and_finalized(
             &chain,
             |state, execution_optimistic, finalized| {
-                let ids_filter_set: Option<HashSet<&ValidatorId>> =
-                    optional_ids.map(|f| HashSet::from_iter(f.iter()));
+                let indices: Box<dyn Iterator<Item = usize>> = match optional_ids {
+                    Some(ids) => Box::new(resolve_state_validator_indices(&chain, state, ids)),
+                    None => Box::new(0..state.balances().len()),
+                };
 
                 Ok((
-                    state
-                        .validators()
-                        .iter()
-                        .zip(state.balances().iter())
-                        .enumerate()
-                        // filter by validator id(s) if provided
-                        .filter(|(index, (validator, _))| {
-                            ids_filter_set.as_ref().map_or(true, |ids_set| {
-                                ids_set.contains(&ValidatorId::PublicKey(validator.pubkey))
-                                    || ids_set.contains(&ValidatorId::Index(*index as u64))
-                            })
-                        })
-                        .map(|(index, (_, balance))| ValidatorBalanceData {
-                            index: index as u64,
-                            balance: *balance,
-                        })
-                        .collect::<Vec<_>>(),
+                    validator_balances(state.balances(), indices),
                     execution_optimistic,
                     finalized,
                 ))
@@ -114,3 +173,18 @@ pub fn get_beacon_state_validator_balances<T: BeaconChainTypes>(
         finalized: Some(finalized),
     })
 }
+
+/// Returns the balances of the validators with `indices`, ignoring unknown indices.
+fn validator_balances<E: EthSpec>(
+    balances: &Balances<E>,
+    indices: impl Iterator<Item = usize>,
+) -> Vec<ValidatorBalanceData> {
+    indices
+        .filter_map(|index| {
+            Some(ValidatorBalanceData {
+                index: index as u64,
+                balance: *balances.get(index)?,
+            })
+        })
+        .collect()
+}
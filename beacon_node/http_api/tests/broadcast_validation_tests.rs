@@ -1,6 +1,6 @@
 use beacon_chain::{
     test_utils::{AttestationStrategy, BlockStrategy},
-    GossipVerifiedBlock, IntoGossipVerifiedBlockContents,
+    GossipVerifiedBlock, IntoGossipVerifiedBlockContents, WhenSlotSkipped,
 };
 use eth2::reqwest::StatusCode;
 use eth2::types::{BroadcastValidation, PublishBlockRequest};
@@ -323,6 +323,58 @@ pub async fn consensus_gossip() {
     );
 }
 
+/// This test checks that the v1 endpoint also applies the requested validation level, rejecting a
+/// block that is only valid from a gossip perspective when using `broadcast_validation=consensus`.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+pub async fn consensus_gossip_v1() {
+    let validation_level: Option<BroadcastValidation> = Some(BroadcastValidation::Consensus);
+
+    // Validator count needs to be at least 32 or proposer boost gets set to 0 when computing
+    // `validator_count // 32`.
+    let validator_count = 64;
+    let num_initial: u64 = 31;
+    let tester = InteractiveTester::<E>::new(None, validator_count).await;
+
+    // Create some chain depth.
+    tester.harness.advance_slot();
+    tester
+        .harness
+        .extend_chain(
+            num_initial as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+    tester.harness.advance_slot();
+
+    let slot_a = Slot::new(num_initial);
+    let slot_b = slot_a + 1;
+
+    let state_a = tester.harness.get_current_state();
+    let ((block, blobs), _) = tester
+        .harness
+        .make_block_with_modifier(state_a, slot_b, |b| *b.state_root_mut() = Hash256::zero())
+        .await;
+
+    let response: Result<(), eth2::Error> = tester
+        .client
+        .post_beacon_blocks_with_validation(
+            &PublishBlockRequest::new(block, blobs),
+            validation_level,
+        )
+        .await;
+    assert!(response.is_err());
+
+    let error_response: eth2::Error = response.err().unwrap();
+
+    /* mandated by Beacon API spec */
+    assert_eq!(error_response.status(), Some(StatusCode::BAD_REQUEST));
+
+    assert!(
+        matches!(error_response, eth2::Error::ServerMessage(err) if err.message == "BAD_REQUEST: Invalid block: StateRootMismatch { block: 0x0000000000000000000000000000000000000000000000000000000000000000, local: 0xfc675d642ff7a06458eb33c7d7b62a5813e34d1b2bb1aee3e395100b579da026 }".to_string())
+    );
+}
+
 /// This test checks that a block that is valid from both a gossip and consensus perspective, but nonetheless equivocates, is accepted when using `broadcast_validation=consensus`.
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 pub async fn consensus_partial_pass_only_consensus() {
@@ -564,9 +616,10 @@ pub async fn equivocation_consensus_early_equivocation() {
     let error_response: eth2::Error = response.err().unwrap();
 
     assert_eq!(error_response.status(), Some(StatusCode::BAD_REQUEST));
+    assert!(error_response.is_equivocating_block());
 
     assert!(
-        matches!(error_response, eth2::Error::ServerMessage(err) if err.message ==  "BAD_REQUEST: BlockError(Slashable)".to_string())
+        matches!(error_response, eth2::Error::ServerMessage(err) if err.message == format!("BAD_REQUEST: {}", eth2::EQUIVOCATING_BLOCK_MESSAGE))
     );
 }
 
@@ -1191,9 +1244,10 @@ pub async fn blinded_equivocation_consensus_early_equivocation() {
     let error_response: eth2::Error = response.err().unwrap();
 
     assert_eq!(error_response.status(), Some(StatusCode::BAD_REQUEST));
+    assert!(error_response.is_equivocating_block());
 
     assert!(
-        matches!(error_response, eth2::Error::ServerMessage(err) if err.message ==  "BAD_REQUEST: BlockError(Slashable)".to_string())
+        matches!(error_response, eth2::Error::ServerMessage(err) if err.message == format!("BAD_REQUEST: {}", eth2::EQUIVOCATING_BLOCK_MESSAGE))
     );
 }
 
@@ -1395,3 +1449,60 @@ pub async fn blinded_equivocation_full_pass() {
         .chain
         .block_is_known_to_fork_choice(&block.canonical_root()));
 }
+
+/// This test checks that a blinded block whose payload the builder fails to reveal is recovered
+/// from the database when the block has already been imported, e.g. because the builder published
+/// it to the network before failing to respond.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+pub async fn blinded_reveal_fallback_recovers_imported_block() {
+    // Validator count needs to be at least 32 or proposer boost gets set to 0 when computing
+    // `validator_count // 32`.
+    let validator_count = 64;
+    let num_initial: u64 = 31;
+    let spec = ForkName::Capella.make_genesis_spec(MainnetEthSpec::default_spec());
+    let tester = InteractiveTester::<E>::new(Some(spec), validator_count).await;
+    let test_logger = tester.harness.logger().clone();
+
+    // Create enough chain depth that the payload of an early block has been evicted from the
+    // local payload cache.
+    tester.harness.advance_slot();
+    tester
+        .harness
+        .extend_chain(
+            num_initial as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+
+    let block_root = tester
+        .harness
+        .chain
+        .block_root_at_slot(Slot::new(1), WhenSlotSkipped::None)
+        .unwrap()
+        .unwrap();
+    let block = tester
+        .harness
+        .chain
+        .get_block(&block_root)
+        .await
+        .unwrap()
+        .unwrap();
+    let blinded_block = Arc::new(block.clone_as_blinded());
+
+    // There is no builder configured, so revealing the payload fails and the block is recovered.
+    let unblinded_block = reconstruct_block(
+        tester.harness.chain.clone(),
+        block_root,
+        blinded_block,
+        test_logger,
+    )
+    .await
+    .unwrap();
+
+    let ProvenancedBlock::Builder(PublishBlockRequest::Block(recovered_block), _) = unblinded_block
+    else {
+        panic!("should recover a full block from a builder");
+    };
+    assert_eq!(*recovered_block, block);
+}
@@ -922,3 +922,86 @@ async fn queue_attestations_from_http() {
 
     attestation_future.await.unwrap();
 }
+
+/// A node which rejects a chain conflicting with its weak subjectivity checkpoint reports the
+/// violation via the health endpoint.
+#[cfg(target_os = "linux")]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn weak_subjectivity_violation_reported_by_health() {
+    use beacon_chain::{block_verification_types::RpcBlock, BlockError, NotifyExecutionLayer};
+    use types::{BlockImportSource, Checkpoint, Hash256};
+
+    type E = MinimalEthSpec;
+
+    let validator_count = 32;
+
+    // A chain which finalizes beyond the checkpoint, but doesn't include it.
+    let source = InteractiveTester::<E>::new(None, validator_count).await;
+    source.harness.advance_slot();
+    source
+        .harness
+        .extend_chain(
+            5 * E::slots_per_epoch() as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+
+    let wss_checkpoint = Checkpoint {
+        epoch: Epoch::new(2),
+        root: Hash256::repeat_byte(0x42),
+    };
+    let tester = InteractiveTester::<E>::new_with_initializer_and_mutator(
+        None,
+        validator_count,
+        Some(Box::new(move |builder| {
+            builder
+                .deterministic_keypairs(validator_count)
+                .fresh_ephemeral_store()
+                .chain_config(ChainConfig {
+                    weak_subjectivity_checkpoint: Some(wss_checkpoint),
+                    ..ChainConfig::default()
+                })
+        })),
+        None,
+    )
+    .await;
+    let harness = &tester.harness;
+
+    let health = tester.client.get_lighthouse_health().await.unwrap().data;
+    assert!(!health.weak_subjectivity_violated);
+
+    let source_chain = &source.harness.chain;
+    let mut violated = false;
+    for snapshot in source_chain.chain_dump().unwrap().into_iter().skip(1) {
+        let block_root = snapshot.beacon_block_root;
+        let block = source_chain.get_block(&block_root).await.unwrap().unwrap();
+        let blobs = source_chain.get_blobs(&block_root).unwrap();
+
+        harness.set_current_slot(block.slot());
+        let result = harness
+            .chain
+            .process_block(
+                block_root,
+                RpcBlock::new(Some(block_root), Arc::new(block), Some(blobs)).unwrap(),
+                NotifyExecutionLayer::Yes,
+                BlockImportSource::RangeSync,
+                || Ok(()),
+            )
+            .await;
+        harness.chain.recompute_head_at_current_slot().await;
+
+        match result {
+            Ok(_) => {}
+            Err(BlockError::WeakSubjectivityConflict) => {
+                violated = true;
+                break;
+            }
+            Err(e) => panic!("unexpected error: {:?}", e),
+        }
+    }
+    assert!(violated, "a block should conflict with the checkpoint");
+
+    let health = tester.client.get_lighthouse_health().await.unwrap().data;
+    assert!(health.weak_subjectivity_violated);
+}
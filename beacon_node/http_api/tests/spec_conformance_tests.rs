@@ -0,0 +1,403 @@
+//! Checks the responses of a Lighthouse node against the published beacon-API OpenAPI spec.
+//!
+//! The spec is not vendored, so the tests are ignored by default. Run them with
+//! `make test-beacon-api-conformance` from the repository root, which downloads the bundled spec
+//! (`beacon-node-oapi.json`) from the `ethereum/beacon-APIs` releases. Alternatively point the
+//! `BEACON_API_SPEC` environment variable at a local copy. The tests fail if the spec is missing.
+//!
+//! Each endpoint is queried on a node built with the `InteractiveTester`, and the JSON response is
+//! validated against the schema of the spec's `200` response for that endpoint. Any mismatch
+//! (a missing required field, a field of the wrong type, a value outside an enum) is reported as a
+//! test failure, which flags drift between Lighthouse and the spec.
+use beacon_chain::test_utils::{AttestationStrategy, BlockStrategy, SyncCommitteeStrategy};
+use eth2::reqwest::{Client, Url};
+use http_api::test_utils::InteractiveTester;
+use serde_json::Value;
+use std::path::PathBuf;
+use types::{EthSpec, ForkName, MinimalEthSpec};
+
+type E = MinimalEthSpec;
+
+/// Environment variable which overrides the location of the spec.
+const SPEC_PATH_ENV: &str = "BEACON_API_SPEC";
+
+/// The location of the spec downloaded by the `Makefile`.
+const DEFAULT_SPEC_PATH: &str = "tests/beacon-apis/beacon-node-oapi.json";
+
+const VALIDATOR_COUNT: usize = 32;
+
+/// An endpoint to check, as a path template from the spec and a concrete path to request.
+struct Endpoint {
+    method: Method,
+    template: &'static str,
+    path: String,
+}
+
+#[derive(Clone, Copy)]
+enum Method {
+    Get,
+    /// A POST request with a JSON body.
+    Post(&'static str),
+}
+
+impl Method {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Method::Get => "get",
+            Method::Post(_) => "post",
+        }
+    }
+}
+
+fn get(template: &'static str, path: impl Into<String>) -> Endpoint {
+    Endpoint {
+        method: Method::Get,
+        template,
+        path: path.into(),
+    }
+}
+
+fn post(template: &'static str, path: impl Into<String>, body: &'static str) -> Endpoint {
+    Endpoint {
+        method: Method::Post(body),
+        template,
+        path: path.into(),
+    }
+}
+
+/// Load the spec, panicking if it has not been downloaded.
+fn load_spec() -> Value {
+    let path = std::env::var(SPEC_PATH_ENV)
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(DEFAULT_SPEC_PATH));
+
+    assert!(
+        path.exists(),
+        "{path:?} does not exist. Run `make` in beacon_node/http_api or set {SPEC_PATH_ENV} to \
+         download or locate the beacon-API spec."
+    );
+
+    let bytes = std::fs::read(&path).unwrap_or_else(|e| panic!("unable to read {path:?}: {e:?}"));
+    serde_json::from_slice(&bytes).unwrap_or_else(|e| panic!("invalid spec {path:?}: {e:?}"))
+}
+
+/// Resolve a local JSON reference such as `#/components/schemas/Uint64`.
+fn resolve<'a>(spec: &'a Value, reference: &str) -> &'a Value {
+    let pointer = reference
+        .strip_prefix('#')
+        .unwrap_or_else(|| panic!("unsupported non-local reference {reference}"));
+    spec.pointer(pointer)
+        .unwrap_or_else(|| panic!("unresolved reference {reference}"))
+}
+
+/// Follow `$ref`s until reaching a concrete value.
+fn deref<'a>(spec: &'a Value, mut value: &'a Value) -> &'a Value {
+    while let Some(reference) = value.get("$ref").and_then(Value::as_str) {
+        value = resolve(spec, reference);
+    }
+    value
+}
+
+/// Returns the schema of the `200` JSON response of `method` on the path `template`.
+fn response_schema<'a>(spec: &'a Value, template: &str, method: Method) -> &'a Value {
+    let operation = spec
+        .get("paths")
+        .and_then(|paths| paths.get(template))
+        .map(|path| deref(spec, path))
+        .and_then(|path| path.get(method.as_str()))
+        .unwrap_or_else(|| panic!("{} {template} is not in the spec", method.as_str()));
+
+    let response = operation
+        .get("responses")
+        .and_then(|responses| responses.get("200"))
+        .map(|response| deref(spec, response))
+        .unwrap_or_else(|| panic!("{} {template} has no 200 response", method.as_str()));
+
+    response
+        .pointer("/content/application~1json/schema")
+        .unwrap_or_else(|| panic!("{} {template} has no JSON schema", method.as_str()))
+}
+
+/// Validate `value` against the OpenAPI `schema`, appending any mismatches to `errors`.
+///
+/// Only the subset of JSON schema used by the beacon-API spec is supported. Additional properties
+/// are permitted, since the spec allows servers to return extra fields.
+fn validate(spec: &Value, schema: &Value, value: &Value, location: &str, errors: &mut Vec<String>) {
+    let schema = deref(spec, schema);
+
+    if value.is_null() && schema.get("nullable").and_then(Value::as_bool) == Some(true) {
+        return;
+    }
+
+    if let Some(all_of) = schema.get("allOf").and_then(Value::as_array) {
+        for sub_schema in all_of {
+            validate(spec, sub_schema, value, location, errors);
+        }
+    }
+
+    for keyword in ["oneOf", "anyOf"] {
+        if let Some(options) = schema.get(keyword).and_then(Value::as_array) {
+            let mut option_errors = vec![];
+            let matched = options.iter().any(|option| {
+                let mut errors = vec![];
+                validate(spec, option, value, location, &mut errors);
+                let matched = errors.is_empty();
+                option_errors.extend(errors);
+                matched
+            });
+            if !matched {
+                errors.push(format!(
+                    "{location}: matches none of the {keyword} schemas: [{}]",
+                    option_errors.join("; ")
+                ));
+            }
+        }
+    }
+
+    if let Some(expected) = schema.get("type").and_then(Value::as_str) {
+        let matches = match expected {
+            "object" => value.is_object(),
+            "array" => value.is_array(),
+            "string" => value.is_string(),
+            "integer" => value.is_i64() || value.is_u64(),
+            "number" => value.is_number(),
+            "boolean" => value.is_boolean(),
+            _ => true,
+        };
+        if !matches {
+            errors.push(format!("{location}: expected {expected}, got {value}"));
+            return;
+        }
+    }
+
+    if let Some(variants) = schema.get("enum").and_then(Value::as_array) {
+        if !variants.contains(value) {
+            errors.push(format!("{location}: {value} is not one of {variants:?}"));
+        }
+    }
+
+    if let Some(object) = value.as_object() {
+        if let Some(required) = schema.get("required").and_then(Value::as_array) {
+            for field in required.iter().filter_map(Value::as_str) {
+                if !object.contains_key(field) {
+                    errors.push(format!("{location}: missing required field `{field}`"));
+                }
+            }
+        }
+        if let Some(properties) = schema.get("properties").and_then(Value::as_object) {
+            for (field, field_schema) in properties {
+                if let Some(field_value) = object.get(field) {
+                    let location = format!("{location}.{field}");
+                    validate(spec, field_schema, field_value, &location, errors);
+                }
+            }
+        }
+    }
+
+    if let (Some(items), Some(array)) = (schema.get("items"), value.as_array()) {
+        for (i, item) in array.iter().enumerate() {
+            validate(spec, items, item, &format!("{location}[{i}]"), errors);
+        }
+    }
+}
+
+/// Request each of the `endpoints` and check the responses against the spec, panicking with a
+/// list of all mismatches.
+async fn check_endpoints(spec: &Value, tester: &InteractiveTester<E>, endpoints: &[Endpoint]) {
+    let http_client = Client::new();
+    let server = Url::parse(tester.client.as_ref()).unwrap();
+    let mut failures = vec![];
+
+    for endpoint in endpoints {
+        let url = server.join(&endpoint.path).unwrap();
+        let request = match endpoint.method {
+            Method::Get => http_client.get(url),
+            Method::Post(body) => http_client
+                .post(url)
+                .header("Content-Type", "application/json")
+                .body(body),
+        };
+        let response = request.send().await.unwrap();
+        let status = response.status();
+        if !status.is_success() {
+            failures.push(format!(
+                "{} {}: status {}: {}",
+                endpoint.method.as_str(),
+                endpoint.path,
+                status,
+                response.text().await.unwrap_or_default()
+            ));
+            continue;
+        }
+        let body: Value = response.json().await.unwrap();
+
+        let schema = response_schema(spec, endpoint.template, endpoint.method);
+        let mut errors = vec![];
+        validate(spec, schema, &body, "$", &mut errors);
+        failures.extend(
+            errors
+                .into_iter()
+                .map(|e| format!("{} {}: {e}", endpoint.method.as_str(), endpoint.path)),
+        );
+    }
+
+    assert!(
+        failures.is_empty(),
+        "responses do not conform to the beacon-API spec:\n{}",
+        failures.join("\n")
+    );
+}
+
+/// Build a chain spanning a few epochs on the latest fork, so that every endpoint has data to
+/// return.
+async fn tester() -> InteractiveTester<E> {
+    let spec = ForkName::latest().make_genesis_spec(E::default_spec());
+    let tester = InteractiveTester::<E>::new(Some(spec), VALIDATOR_COUNT).await;
+
+    let harness = &tester.harness;
+    let num_blocks = E::slots_per_epoch() * 3;
+    harness.advance_slot();
+    harness
+        .extend_chain_with_sync(
+            num_blocks as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+            SyncCommitteeStrategy::AllValidators,
+        )
+        .await;
+
+    tester
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+#[ignore = "requires the beacon-API spec, run with `make test-beacon-api-conformance`"]
+async fn beacon_endpoints() {
+    let spec = load_spec();
+    let tester = tester().await;
+
+    let endpoints = [
+        get("/eth/v1/beacon/genesis", "/eth/v1/beacon/genesis"),
+        get(
+            "/eth/v1/beacon/states/{state_id}/root",
+            "/eth/v1/beacon/states/head/root",
+        ),
+        get(
+            "/eth/v1/beacon/states/{state_id}/fork",
+            "/eth/v1/beacon/states/head/fork",
+        ),
+        get(
+            "/eth/v1/beacon/states/{state_id}/finality_checkpoints",
+            "/eth/v1/beacon/states/head/finality_checkpoints",
+        ),
+        get(
+            "/eth/v1/beacon/states/{state_id}/validators",
+            "/eth/v1/beacon/states/head/validators",
+        ),
+        get(
+            "/eth/v1/beacon/states/{state_id}/validators/{validator_id}",
+            "/eth/v1/beacon/states/head/validators/0",
+        ),
+        get(
+            "/eth/v1/beacon/states/{state_id}/validator_balances",
+            "/eth/v1/beacon/states/finalized/validator_balances",
+        ),
+        get(
+            "/eth/v1/beacon/states/{state_id}/committees",
+            "/eth/v1/beacon/states/head/committees",
+        ),
+        get(
+            "/eth/v1/beacon/states/{state_id}/sync_committees",
+            "/eth/v1/beacon/states/head/sync_committees",
+        ),
+        get(
+            "/eth/v1/beacon/states/{state_id}/randao",
+            "/eth/v1/beacon/states/head/randao",
+        ),
+        get("/eth/v1/beacon/headers", "/eth/v1/beacon/headers"),
+        get(
+            "/eth/v1/beacon/headers/{block_id}",
+            "/eth/v1/beacon/headers/head",
+        ),
+        get(
+            "/eth/v2/beacon/blocks/{block_id}",
+            "/eth/v2/beacon/blocks/head",
+        ),
+        get(
+            "/eth/v1/beacon/blocks/{block_id}/root",
+            "/eth/v1/beacon/blocks/head/root",
+        ),
+        get(
+            "/eth/v1/beacon/blocks/{block_id}/attestations",
+            "/eth/v1/beacon/blocks/head/attestations",
+        ),
+    ];
+
+    check_endpoints(&spec, &tester, &endpoints).await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+#[ignore = "requires the beacon-API spec, run with `make test-beacon-api-conformance`"]
+async fn rewards_endpoints() {
+    let spec = load_spec();
+    let tester = tester().await;
+
+    // Rewards are only available for completed epochs.
+    let epoch = tester.harness.get_current_state().current_epoch() - 2;
+
+    let endpoints = [
+        get(
+            "/eth/v1/beacon/rewards/blocks/{block_id}",
+            "/eth/v1/beacon/rewards/blocks/head",
+        ),
+        post(
+            "/eth/v1/beacon/rewards/sync_committee/{block_id}",
+            "/eth/v1/beacon/rewards/sync_committee/head",
+            "[]",
+        ),
+        post(
+            "/eth/v1/beacon/rewards/attestations/{epoch}",
+            format!("/eth/v1/beacon/rewards/attestations/{epoch}"),
+            "[]",
+        ),
+    ];
+
+    check_endpoints(&spec, &tester, &endpoints).await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+#[ignore = "requires the beacon-API spec, run with `make test-beacon-api-conformance`"]
+async fn node_config_and_validator_endpoints() {
+    let spec = load_spec();
+    let tester = tester().await;
+
+    let epoch = tester.harness.get_current_state().current_epoch();
+
+    let endpoints = [
+        get("/eth/v1/node/version", "/eth/v1/node/version"),
+        get("/eth/v1/node/syncing", "/eth/v1/node/syncing"),
+        get(
+            "/eth/v1/config/fork_schedule",
+            "/eth/v1/config/fork_schedule",
+        ),
+        get(
+            "/eth/v1/config/deposit_contract",
+            "/eth/v1/config/deposit_contract",
+        ),
+        get(
+            "/eth/v1/validator/duties/proposer/{epoch}",
+            format!("/eth/v1/validator/duties/proposer/{epoch}"),
+        ),
+        post(
+            "/eth/v1/validator/duties/attester/{epoch}",
+            format!("/eth/v1/validator/duties/attester/{epoch}"),
+            r#"["0","1","2"]"#,
+        ),
+        post(
+            "/eth/v1/validator/duties/sync/{epoch}",
+            format!("/eth/v1/validator/duties/sync/{epoch}"),
+            r#"["0","1","2"]"#,
+        ),
+    ];
+
+    check_endpoints(&spec, &tester, &endpoints).await;
+}
@@ -1,10 +1,12 @@
 use beacon_chain::test_utils::RelativeSyncCommittee;
 use beacon_chain::{
+    remote_slasher::RemoteSlasherQueue,
     test_utils::{AttestationStrategy, BeaconChainHarness, BlockStrategy, EphemeralHarnessType},
     BeaconChain, ChainConfig, StateSkipConfig, WhenSlotSkipped,
 };
 use environment::null_logger;
 use eth2::{
+    lighthouse::{ModuleLogLevels, PartialBlockBody, ProfileFormat, SszObjectType},
     mixin::{RequestAccept, ResponseForkName, ResponseOptional},
     reqwest::RequestBuilder,
     types::{
@@ -23,22 +25,28 @@ use http_api::{
     test_utils::{create_api_server, ApiServer},
     BlockId, StateId,
 };
-use lighthouse_network::{types::SyncState, Enr, EnrExt, PeerId};
+use lighthouse_network::{
+    peer_manager::peerdb::client::ClientKind,
+    types::{propagation::PropagationKind, SyncState},
+    Enr, EnrExt, PeerId,
+};
 use network::NetworkReceivers;
 use proto_array::ExecutionStatus;
 use sensitive_url::SensitiveUrl;
 use slot_clock::SlotClock;
+use ssz::Encode;
 use state_processing::per_block_processing::get_expected_withdrawals;
 use state_processing::per_slot_processing;
-use state_processing::state_advance::partial_state_advance;
+use state_processing::state_advance::{complete_state_advance, partial_state_advance};
 use std::convert::TryInto;
 use std::sync::Arc;
 use tokio::time::Duration;
 use tree_hash::TreeHash;
 use types::application_domain::ApplicationDomain;
 use types::{
-    attestation::AttestationBase, AggregateSignature, BitList, Domain, EthSpec, ExecutionBlockHash,
-    Hash256, Keypair, MainnetEthSpec, RelativeEpoch, SelectionProof, SignedRoot, Slot,
+    attestation::AttestationBase, AggregateSignature, BitList, Domain, Epoch, EthSpec,
+    ExecutionBlockHash, Hash256, Keypair, MainnetEthSpec, RelativeEpoch, SelectionProof,
+    SignedRoot, Slot,
 };
 
 type E = MainnetEthSpec;
@@ -83,6 +91,7 @@ struct ApiTester {
 struct ApiTesterConfig {
     spec: ChainSpec,
     retain_historic_states: bool,
+    remote_slasher: bool,
 }
 
 impl Default for ApiTesterConfig {
@@ -92,6 +101,7 @@ impl Default for ApiTesterConfig {
         Self {
             spec,
             retain_historic_states: false,
+            remote_slasher: false,
         }
     }
 }
@@ -101,6 +111,11 @@ impl ApiTesterConfig {
         self.retain_historic_states = true;
         self
     }
+
+    fn remote_slasher(mut self) -> Self {
+        self.remote_slasher = true;
+        self
+    }
 }
 
 impl ApiTester {
@@ -124,7 +139,7 @@ impl ApiTester {
     pub async fn new_from_config(config: ApiTesterConfig) -> Self {
         let spec = config.spec;
 
-        let mut harness = BeaconChainHarness::builder(MainnetEthSpec)
+        let mut harness_builder = BeaconChainHarness::builder(MainnetEthSpec)
             .spec(spec.clone())
             .chain_config(ChainConfig {
                 reconstruct_historic_states: config.retain_historic_states,
@@ -134,8 +149,13 @@ impl ApiTester {
             .deterministic_keypairs(VALIDATOR_COUNT)
             .deterministic_withdrawal_keypairs(VALIDATOR_COUNT)
             .fresh_ephemeral_store()
-            .mock_execution_layer_with_config()
-            .build();
+            .mock_execution_layer_with_config();
+        if config.remote_slasher {
+            harness_builder = harness_builder.initial_mutator(Box::new(|builder| {
+                builder.remote_slasher(Arc::new(RemoteSlasherQueue::default()))
+            }));
+        }
+        let mut harness = harness_builder.build();
 
         harness
             .mock_execution_layer
@@ -928,6 +948,113 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_beacon_states_validator_balances_cache(self) -> Self {
+        for state_id in [StateId(CoreStateId::Head), StateId(CoreStateId::Finalized)] {
+            let (state_root, _, _) = state_id.root(&self.chain).unwrap();
+            let (state, _, _) = state_id.state(&self.chain).unwrap();
+
+            // Unordered and duplicate ids, unknown indices and pubkeys, and indices of the same
+            // validator given by both index and pubkey.
+            let unknown_index = state.balances().len() as u64;
+            let ids = vec![
+                ValidatorId::Index(2),
+                ValidatorId::PublicKey(state.validators().get(0).unwrap().pubkey),
+                ValidatorId::Index(unknown_index),
+                ValidatorId::PublicKey(PublicKeyBytes::empty()),
+                ValidatorId::Index(0),
+                ValidatorId::Index(2),
+            ];
+            let expected = [0, 2]
+                .into_iter()
+                .map(|index| ValidatorBalanceData {
+                    index,
+                    balance: *state.balances().get(index as usize).unwrap(),
+                })
+                .collect::<Vec<_>>();
+
+            let balances = self
+                .client
+                .get_beacon_states_validator_balances(state_id.0, Some(&ids))
+                .await
+                .unwrap()
+                .unwrap()
+                .data;
+            assert_eq!(balances, expected, "{:?}", state_id);
+
+            // The balances are served from (and for the finalized state, populate) the cache.
+            assert_eq!(
+                self.chain.balances_cache.get(state_root).unwrap().to_vec(),
+                state.balances().to_vec(),
+                "{:?}",
+                state_id
+            );
+            let all_balances = self
+                .client
+                .get_beacon_states_validator_balances(state_id.0, None)
+                .await
+                .unwrap()
+                .unwrap()
+                .data;
+            assert_eq!(all_balances.len(), state.balances().len());
+        }
+
+        self
+    }
+
+    pub async fn test_beacon_states_validators_page(self) -> Self {
+        let all = self
+            .client
+            .get_beacon_states_validators(StateId::Head, None, None)
+            .await
+            .unwrap()
+            .unwrap()
+            .data;
+        assert_eq!(all.len(), self.validator_count());
+
+        for limit in [1, 3, self.validator_count()] {
+            let mut paged = vec![];
+            for offset in (0..self.validator_count()).step_by(limit) {
+                let page = self
+                    .client
+                    .get_beacon_states_validators_page(StateId::Head, None, None, offset, limit)
+                    .await
+                    .unwrap()
+                    .unwrap()
+                    .data;
+                assert!(page.len() <= limit);
+                paged.extend(page);
+            }
+            assert_eq!(paged, all, "limit {}", limit);
+        }
+
+        // Pagination applies after filtering.
+        let ids = [
+            ValidatorId::Index(3),
+            ValidatorId::Index(1),
+            ValidatorId::Index(2),
+        ];
+        let page = self
+            .client
+            .get_beacon_states_validators_page(StateId::Head, Some(&ids), None, 1, 1)
+            .await
+            .unwrap()
+            .unwrap()
+            .data;
+        assert_eq!(page, vec![all[2].clone()]);
+
+        // Offsets past the end return no validators.
+        let page = self
+            .client
+            .get_beacon_states_validators_page(StateId::Head, None, None, self.validator_count(), 1)
+            .await
+            .unwrap()
+            .unwrap()
+            .data;
+        assert!(page.is_empty());
+
+        self
+    }
+
     pub async fn test_beacon_states_validators(self) -> Self {
         for state_id in self.interesting_state_ids() {
             for statuses in self.interesting_validator_statuses() {
@@ -1558,6 +1685,52 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_beacon_blocks_with_pruned_payloads(self) -> Self {
+        let split_slot = self.chain.store.get_split_slot();
+        assert!(split_slot > 0, "precondition: chain is finalized");
+
+        let mut block_roots = self
+            .chain
+            .forwards_iter_block_roots(Slot::new(1))
+            .unwrap()
+            .map(Result::unwrap)
+            .take_while(|(_, slot)| *slot < split_slot)
+            .collect::<Vec<_>>();
+        block_roots.dedup_by_key(|(block_root, _)| *block_root);
+        assert!(!block_roots.is_empty());
+
+        for (block_root, _) in block_roots {
+            // Finalized payloads are pruned from the database, so they must be fetched from the
+            // execution layer.
+            assert!(!self
+                .chain
+                .store
+                .execution_payload_exists(&block_root)
+                .unwrap());
+            let blinded_block = self.chain.get_blinded_block(&block_root).unwrap().unwrap();
+
+            let block_id = CoreBlockId::Root(block_root);
+            let json_block = self
+                .client
+                .get_beacon_blocks::<E>(block_id)
+                .await
+                .unwrap()
+                .unwrap()
+                .data;
+            assert_eq!(json_block.clone_as_blinded(), blinded_block);
+
+            let ssz_block = self
+                .client
+                .get_beacon_blocks_ssz::<E>(block_id, &self.chain.spec)
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(ssz_block, json_block);
+        }
+
+        self
+    }
+
     pub async fn test_beacon_blinded_blocks(self) -> Self {
         for block_id in self.interesting_block_ids() {
             let expected = block_id
@@ -2000,6 +2173,17 @@ impl ApiTester {
             .data;
         assert_eq!(result, expected);
 
+        let fork_name = self
+            .chain
+            .spec
+            .fork_name_at_slot::<E>(self.chain.slot().unwrap());
+        let result = self
+            .client
+            .get_beacon_pool_attester_slashings_ssz::<E>(fork_name)
+            .await
+            .unwrap();
+        assert_eq!(result, expected);
+
         self
     }
 
@@ -2046,6 +2230,13 @@ impl ApiTester {
 
         assert_eq!(result, expected);
 
+        let result = self
+            .client
+            .get_beacon_pool_proposer_slashings_ssz()
+            .await
+            .unwrap();
+        assert_eq!(result, expected);
+
         self
     }
 
@@ -2296,7 +2487,8 @@ impl ApiTester {
                     result,
                     PeersData {
                         meta: PeersMetaData {
-                            count: expected_peers.len() as u64
+                            count: expected_peers.len() as u64,
+                            next_page_token: None,
                         },
                         data: expected_peers,
                     }
@@ -2306,6 +2498,62 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_get_node_peers_paged(self) -> Self {
+        for _ in 0..4 {
+            self.ctx
+                .network_globals
+                .as_ref()
+                .unwrap()
+                .peers
+                .write()
+                .__add_connected_peer_testing_only(&PeerId::random());
+        }
+
+        let all_peers = self.client.get_node_peers(None, None).await.unwrap();
+        assert_eq!(all_peers.meta.count, 5);
+        assert_eq!(all_peers.meta.next_page_token, None);
+
+        let mut paged_peers = vec![];
+        let mut page_token = None;
+        loop {
+            let page = self
+                .client
+                .get_node_peers_paged(None, None, 2, page_token.as_deref())
+                .await
+                .unwrap();
+            assert!(page.data.len() <= 2);
+            assert_eq!(page.meta.count, 5);
+            paged_peers.extend(page.data);
+            page_token = page.meta.next_page_token;
+            if page_token.is_none() {
+                break;
+            }
+        }
+        let mut expected_peers = all_peers.data;
+        expected_peers.sort_by_key(|peer| peer.peer_id.parse::<PeerId>().unwrap());
+        assert_eq!(paged_peers, expected_peers);
+
+        let outbound = self
+            .client
+            .get_node_peers_paged(None, Some(&[PeerDirection::Outbound]), 10, None)
+            .await
+            .unwrap();
+        assert_eq!(outbound.meta.count, 4);
+        assert_eq!(outbound.data.len(), 4);
+        assert_eq!(outbound.meta.next_page_token, None);
+
+        let result = self.client.get_node_peers_paged(None, None, 0, None).await;
+        assert_eq!(result.unwrap_err().status().unwrap(), 400);
+
+        let result = self
+            .client
+            .get_node_peers_paged(None, None, 2, Some("invalid"))
+            .await;
+        assert_eq!(result.unwrap_err().status().unwrap(), 400);
+
+        self
+    }
+
     pub async fn test_get_node_peer_count(self) -> Self {
         let result = self.client.get_node_peer_count().await.unwrap().data;
         assert_eq!(
@@ -5324,7 +5572,15 @@ impl ApiTester {
 
     #[cfg(target_os = "linux")]
     pub async fn test_get_lighthouse_health(self) -> Self {
-        self.client.get_lighthouse_health().await.unwrap();
+        let health = self.client.get_lighthouse_health().await.unwrap().data;
+        assert_eq!(
+            health.weak_subjectivity_violated,
+            self.chain.is_weak_subjectivity_violated()
+        );
+        assert_eq!(
+            health.clock_skew_detected,
+            self.chain.clock_sanity.is_skew_detected()
+        );
 
         self
     }
@@ -5348,6 +5604,46 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_get_lighthouse_fork_choice(self) -> Self {
+        let parameters = self.client.get_lighthouse_fork_choice().await.unwrap().data;
+
+        let fork_choice = self.chain.canonical_head.fork_choice_read_lock();
+        assert!(!parameters.unsafe_tuning);
+        assert_eq!(
+            parameters.proposer_boost_percentage,
+            self.chain.spec.proposer_score_boost
+        );
+        assert_eq!(parameters.committee_weight_override, None);
+        assert_eq!(
+            parameters.justified_checkpoint,
+            fork_choice.justified_checkpoint()
+        );
+        assert_eq!(
+            parameters.finalized_checkpoint,
+            fork_choice.finalized_checkpoint()
+        );
+        drop(fork_choice);
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_debug_fork_choice_snapshot(self) -> Self {
+        let snapshot = self
+            .client
+            .get_lighthouse_debug_fork_choice_snapshot()
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(snapshot.head_root, self.chain.head_beacon_block_root());
+        assert_eq!(
+            snapshot.get_head::<E>(&self.chain.spec).unwrap(),
+            snapshot.head_root
+        );
+
+        self
+    }
+
     pub async fn test_get_lighthouse_validator_inclusion_global(self) -> Self {
         let epoch = self.chain.epoch().unwrap() - 1;
         self.client
@@ -5358,6 +5654,189 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_get_lighthouse_analysis_participation(self) -> Self {
+        let epoch = self.chain.epoch().unwrap() - 2;
+        let participation = self
+            .client
+            .get_lighthouse_analysis_participation(epoch)
+            .await
+            .unwrap();
+
+        // Participation in `epoch` is the previous epoch participation at the end of `epoch + 1`.
+        let global = self
+            .client
+            .get_lighthouse_validator_inclusion_global(epoch + 1)
+            .await
+            .unwrap()
+            .data;
+
+        assert_eq!(participation.epoch, epoch);
+        assert_eq!(participation.active_validators, VALIDATOR_COUNT as u64);
+        assert_eq!(
+            participation.target.gwei,
+            global.previous_epoch_target_attesting_gwei
+        );
+        assert_eq!(
+            participation.head.gwei,
+            global.previous_epoch_head_attesting_gwei
+        );
+        for flag in [
+            &participation.source,
+            &participation.target,
+            &participation.head,
+        ] {
+            assert!(flag.validators > 0 && flag.validators <= participation.active_validators);
+            assert!(flag.validator_rate > 0.0 && flag.validator_rate <= 1.0);
+            assert!(flag.balance_rate > 0.0 && flag.balance_rate <= 1.0);
+        }
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_analysis_churn(self) -> Self {
+        let churn = self
+            .client
+            .get_lighthouse_analysis_churn()
+            .await
+            .unwrap()
+            .data;
+
+        // All validators have been active since genesis and none have exited, so both queues are
+        // empty and the churn limit is the minimum for such a small validator set.
+        let epoch = self.chain.head_snapshot().beacon_state.current_epoch();
+        let delayed_epoch = self
+            .chain
+            .spec
+            .compute_activation_exit_epoch(epoch)
+            .unwrap();
+        assert_eq!(churn.epoch, epoch);
+        assert_eq!(churn.activation_queue_length, 0);
+        assert_eq!(churn.exit_queue_length, 0);
+        assert_eq!(churn.pending_deposit_balance, None);
+        assert_eq!(
+            churn.activation_churn_limit,
+            self.chain.spec.min_per_epoch_churn_limit
+        );
+        assert_eq!(
+            churn.exit_churn_limit,
+            self.chain.spec.min_per_epoch_churn_limit
+        );
+        assert_eq!(churn.projected_activation_epoch, delayed_epoch);
+        assert_eq!(churn.projected_exit_epoch, delayed_epoch);
+
+        self
+    }
+
+    pub async fn test_post_lighthouse_debug_decode_ssz(self) -> Self {
+        let head = self.chain.head_snapshot();
+        let fork = head.beacon_state.fork_name_unchecked();
+
+        let block = self
+            .client
+            .post_lighthouse_debug_decode_ssz(
+                SszObjectType::Block,
+                fork,
+                head.beacon_block.as_ssz_bytes(),
+            )
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(block, serde_json::to_value(&*head.beacon_block).unwrap());
+
+        let state = self
+            .client
+            .post_lighthouse_debug_decode_ssz(
+                SszObjectType::State,
+                fork,
+                head.beacon_state.as_ssz_bytes(),
+            )
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(state, serde_json::to_value(&head.beacon_state).unwrap());
+
+        let attestation = self.attestations.first().unwrap();
+        let attestation_fork = self
+            .chain
+            .spec
+            .fork_name_at_slot::<E>(attestation.data().slot);
+        let decoded = self
+            .client
+            .post_lighthouse_debug_decode_ssz(
+                SszObjectType::Attestation,
+                attestation_fork,
+                attestation.as_ssz_bytes(),
+            )
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(decoded, serde_json::to_value(attestation).unwrap());
+
+        // A block is not a valid state.
+        let err = self
+            .client
+            .post_lighthouse_debug_decode_ssz(
+                SszObjectType::State,
+                fork,
+                head.beacon_block.as_ssz_bytes(),
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(err.status(), Some(StatusCode::BAD_REQUEST));
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_block_rewards_predicted(self) -> Self {
+        let head = self.chain.head_snapshot();
+        let slot = head.beacon_block.slot() + 1;
+        let num_op_pool_attestations = self.chain.op_pool.num_attestations();
+        let predicted = self
+            .client
+            .get_lighthouse_block_rewards_predicted(slot)
+            .await
+            .unwrap();
+
+        // Predictions must not import aggregates into the op pool.
+        assert_eq!(
+            self.chain.op_pool.num_attestations(),
+            num_op_pool_attestations
+        );
+
+        let mut state = head.beacon_state.clone();
+        complete_state_advance(&mut state, None, slot, &self.chain.spec).unwrap();
+        let proposer_index = state
+            .get_beacon_proposer_index(slot, &self.chain.spec)
+            .unwrap();
+
+        let rewards = &predicted.attestation_rewards;
+        assert_eq!(predicted.slot, slot);
+        assert_eq!(predicted.parent_root, head.beacon_block_root);
+        assert_eq!(predicted.proposer_index, proposer_index as u64);
+        assert_eq!(
+            rewards.total,
+            rewards.prev_epoch_total + rewards.curr_epoch_total
+        );
+        assert_eq!(
+            rewards.total,
+            rewards
+                .per_attestation_rewards
+                .iter()
+                .flat_map(|rewards| rewards.values())
+                .sum::<u64>()
+        );
+
+        // Blocks can't be proposed at or before the head slot.
+        let err = self
+            .client
+            .get_lighthouse_block_rewards_predicted(head.beacon_block.slot())
+            .await
+            .unwrap_err();
+        assert_eq!(err.status(), Some(StatusCode::BAD_REQUEST));
+
+        self
+    }
+
     pub async fn test_get_lighthouse_validator_inclusion(self) -> Self {
         let epoch = self.chain.epoch().unwrap() - 1;
         self.client
@@ -5394,34 +5873,794 @@ impl ApiTester {
         self
     }
 
-    pub async fn test_get_lighthouse_staking(self) -> Self {
-        let result = self.client.get_lighthouse_staking().await.unwrap();
-
-        assert_eq!(result, self.chain.eth1_chain.is_some());
+    pub async fn test_get_lighthouse_deposit_status(self) -> Self {
+        // Genesis validators are active from the start, without any deposits in the cache.
+        let pubkey = self.validator_keypairs()[0].pk.compress();
+        let status = self
+            .client
+            .get_lighthouse_deposit_status(&pubkey)
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(status.pubkey, pubkey);
+        assert!(status.deposits.is_empty());
+        assert_eq!(status.validator_index, Some(0));
+        assert_eq!(status.deposits_ahead, None);
+        assert_eq!(status.estimated_inclusion_slot, None);
+        assert_eq!(status.activation_eligibility_epoch, Some(Epoch::new(0)));
+        assert_eq!(status.expected_activation_epoch, Some(Epoch::new(0)));
+
+        // An unknown pubkey has no deposits and no validator.
+        let pubkey = Keypair::random().pk.compress();
+        let status = self
+            .client
+            .get_lighthouse_deposit_status(&pubkey)
+            .await
+            .unwrap()
+            .data;
+        assert!(status.deposits.is_empty());
+        assert_eq!(status.validator_index, None);
+        assert_eq!(status.expected_activation_epoch, None);
 
         self
     }
 
-    pub async fn test_get_lighthouse_database_info(self) -> Self {
-        let info = self.client.get_lighthouse_database_info().await.unwrap();
+    pub async fn test_get_lighthouse_execution_capabilities(self) -> Self {
+        let result = self
+            .client
+            .get_lighthouse_execution_capabilities()
+            .await
+            .unwrap()
+            .data;
 
-        assert_eq!(info.anchor, self.chain.store.get_anchor_info());
+        let expected = self
+            .chain
+            .execution_layer
+            .as_ref()
+            .unwrap()
+            .get_engine_capabilities(None)
+            .await
+            .unwrap()
+            .to_response();
+        assert!(!result.is_empty());
+        assert_eq!(result, expected);
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_execution_auth(self) -> Self {
+        let status = self
+            .client
+            .get_lighthouse_execution_auth()
+            .await
+            .unwrap()
+            .data;
+
+        assert!(status.authenticated, "{:?}", status.error);
+        assert_eq!(status.error, None);
+        assert!(status.secret_loaded_at > 0);
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_network_propagation(self) -> Self {
+        let slot_duration = self.chain.slot_clock.slot_duration();
+        let fast_peer = PeerId::random();
+        let slow_peer = PeerId::random();
+        {
+            let mut propagation = self
+                .ctx
+                .network_globals
+                .as_ref()
+                .unwrap()
+                .propagation
+                .write();
+            propagation.observe(fast_peer, PropagationKind::Block, Duration::ZERO);
+            propagation.observe(slow_peer, PropagationKind::Block, slot_duration);
+            propagation.observe(slow_peer, PropagationKind::Aggregate, slot_duration);
+        }
+
+        let result = self
+            .client
+            .get_lighthouse_network_propagation()
+            .await
+            .unwrap()
+            .data;
+
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].peer_id, fast_peer.to_string());
+        assert_eq!(result[0].score, Some(1.0));
+        assert_eq!(result[0].propagation.blocks.count, 1);
+        assert_eq!(result[0].propagation.aggregates.count, 0);
+        assert_eq!(result[1].peer_id, slow_peer.to_string());
+        assert_eq!(result[1].score, Some(0.0));
+        assert_eq!(result[1].propagation.aggregates.count, 1);
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_network_gossip_rejections(self) -> Self {
+        let buggy_peer = PeerId::random();
+        let other_peer = PeerId::random();
+        let gossip_rejections = &self.ctx.network_globals.as_ref().unwrap().gossip_rejections;
+        for (peer_id, epoch, reason) in [
+            (buggy_peer, 1, "InvalidSignature"),
+            (buggy_peer, 2, "InvalidSignature"),
+            (other_peer, 2, "FutureSlot"),
+        ] {
+            gossip_rejections.observe(peer_id, Epoch::new(epoch), reason, || ClientKind::Unknown);
+        }
+
+        let result = self
+            .client
+            .get_lighthouse_network_gossip_rejections()
+            .await
+            .unwrap()
+            .data;
+
+        assert_eq!(result.epochs.len(), 2);
+        assert_eq!(result.epochs[0].epoch, Epoch::new(1));
+        assert_eq!(result.epochs[0].rejections.total, 1);
+        assert_eq!(result.epochs[1].rejections.total, 2);
+        assert_eq!(result.epochs[1].rejections.reasons["FutureSlot"], 1);
+
+        assert_eq!(result.peers.len(), 2);
+        assert_eq!(result.peers[0].peer_id, buggy_peer.to_string());
+        assert_eq!(result.peers[0].client, "Unknown");
+        assert_eq!(result.peers[0].rejections.reasons["InvalidSignature"], 2);
+        assert_eq!(result.peers[1].peer_id, other_peer.to_string());
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_staking(self) -> Self {
+        let result = self.client.get_lighthouse_staking().await.unwrap();
+
+        assert_eq!(result, self.chain.eth1_chain.is_some());
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_database_info(self) -> Self {
+        let info = self.client.get_lighthouse_database_info().await.unwrap();
+
+        assert_eq!(info.anchor, self.chain.store.get_anchor_info());
         assert_eq!(info.split, self.chain.store.get_split_info());
         assert_eq!(
             info.schema_version,
             store::metadata::CURRENT_SCHEMA_VERSION.as_u64()
         );
+        assert_eq!(info.sizes, None);
+        let store_config = self.chain.store.get_config();
+        assert!(store_config.prune_blobs);
+        assert_eq!(
+            info.blob_retention_boundary,
+            self.chain
+                .data_availability_boundary()
+                .map(|boundary| boundary.saturating_sub(store_config.blob_prune_margin_epochs))
+        );
+
+        let sizes = self
+            .client
+            .get_lighthouse_database_info_with_sizes()
+            .await
+            .unwrap()
+            .sizes
+            .expect("sizes should be present when requested");
+        let blocks = sizes
+            .hot
+            .iter()
+            .find(|size| size.column == store::DBColumn::BeaconBlock.as_str())
+            .expect("hot database should contain blocks");
+        assert!(blocks.num_keys > 0);
+        assert!(blocks.bytes > 0);
+        assert!(sizes.hot.iter().all(|size| size.num_keys > 0));
+
+        self
+    }
+
+    pub async fn test_post_lighthouse_database_reconstruct(self) -> Self {
+        let response = self
+            .client
+            .post_lighthouse_database_reconstruct()
+            .await
+            .unwrap();
+        assert_eq!(response, "success");
+        self
+    }
+
+    pub async fn test_get_lighthouse_pool_stats(self) -> Self {
+        let stats = self.client.get_lighthouse_pool_stats().await.unwrap().data;
+        let op_pool = &self.chain.op_pool;
+
+        assert_eq!(stats.attestations.count, op_pool.num_attestations());
+        assert_eq!(
+            stats.attester_slashings.count,
+            op_pool.num_attester_slashings()
+        );
+        assert_eq!(
+            stats.proposer_slashings.count,
+            op_pool.num_proposer_slashings()
+        );
+        assert_eq!(stats.voluntary_exits.count, op_pool.num_voluntary_exits());
+
+        // Every type of operation in the pool must have been inserted at some point.
+        for operation in [
+            stats.attestations,
+            stats.sync_contributions,
+            stats.attester_slashings,
+            stats.proposer_slashings,
+            stats.voluntary_exits,
+            stats.bls_to_execution_changes,
+        ] {
+            if operation.count > 0 {
+                assert!(operation.last_insertion.is_some());
+            }
+        }
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_equivocations(self) -> Self {
+        let result = self
+            .client
+            .get_lighthouse_equivocations()
+            .await
+            .unwrap()
+            .data;
+        assert!(result.is_empty());
+
+        let proposer_slashing = self.harness.make_proposer_slashing(3);
+        self.chain
+            .observe_block_header_for_equivocation(&proposer_slashing.signed_header_1);
+        self.chain
+            .observe_block_header_for_equivocation(&proposer_slashing.signed_header_2);
+
+        let result = self
+            .client
+            .get_lighthouse_equivocations()
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(result, vec![proposer_slashing.clone()]);
+        assert!(self
+            .chain
+            .op_pool
+            .get_all_proposer_slashings()
+            .contains(&proposer_slashing));
+
+        self
+    }
+
+    pub async fn test_post_lighthouse_partial_block_body(self) -> Self {
+        let proposer_slashing = self.harness.make_proposer_slashing(4);
+        let partial_block_body = PartialBlockBody {
+            proposer_slashings: vec![proposer_slashing.clone()],
+            attester_slashings: vec![],
+            attestations: vec![],
+            voluntary_exits: vec![],
+            bls_to_execution_changes: vec![],
+        };
+
+        // Operations which fail verification are rejected.
+        let mut invalid_body = partial_block_body.clone();
+        invalid_body.proposer_slashings[0].signed_header_2.signature =
+            proposer_slashing.signed_header_1.signature.clone();
+        assert!(self
+            .client
+            .post_lighthouse_partial_block_body(&invalid_body)
+            .await
+            .is_err());
+        assert_eq!(
+            self.client
+                .get_lighthouse_partial_block_body::<E>()
+                .await
+                .unwrap()
+                .data,
+            None
+        );
+
+        self.client
+            .post_lighthouse_partial_block_body(&partial_block_body)
+            .await
+            .unwrap();
+        assert_eq!(
+            self.client
+                .get_lighthouse_partial_block_body::<E>()
+                .await
+                .unwrap()
+                .data,
+            Some(partial_block_body.clone())
+        );
+
+        // The next block produced includes the operations.
+        let head = self.chain.head_snapshot();
+        let slot = self.chain.slot().unwrap() + 1;
+        let ((block, blobs), _) = self
+            .harness
+            .make_block(head.beacon_state.clone(), slot)
+            .await;
+        assert!(block
+            .message()
+            .body()
+            .proposer_slashings()
+            .contains(&proposer_slashing));
+
+        // The operations are kept until the block is imported.
+        assert_eq!(
+            self.client
+                .get_lighthouse_partial_block_body::<E>()
+                .await
+                .unwrap()
+                .data,
+            Some(partial_block_body)
+        );
+        self.harness
+            .process_block(slot, block.canonical_root(), (block, blobs))
+            .await
+            .unwrap();
+        assert_eq!(
+            self.client
+                .get_lighthouse_partial_block_body::<E>()
+                .await
+                .unwrap()
+                .data,
+            None
+        );
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_debug_memory(self) -> Self {
+        let memory = self
+            .client
+            .get_lighthouse_debug_memory()
+            .await
+            .unwrap()
+            .data;
+
+        let state_cache = memory
+            .caches
+            .iter()
+            .find(|cache| cache.name == "state_cache")
+            .unwrap();
+        assert_eq!(state_cache.entries, self.chain.store.state_cache_len());
+        assert_eq!(state_cache.estimated_bytes, None);
+
+        let op_pool = memory
+            .caches
+            .iter()
+            .find(|cache| cache.name == "op_pool")
+            .unwrap();
+        assert_eq!(
+            op_pool.estimated_bytes,
+            Some(self.chain.op_pool.estimated_bytes())
+        );
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_debug_profile(self) -> Self {
+        // The test harness does not enable profiling.
+        let result = self
+            .client
+            .get_lighthouse_debug_profile(Duration::from_millis(100), ProfileFormat::Flamegraph)
+            .await
+            .unwrap();
+        assert_eq!(result, None);
+
+        self
+    }
+
+    pub async fn test_post_lighthouse_slasher_dequeue(self) -> Self {
+        // The test harness does not enable the remote slasher.
+        let result = self.client.post_lighthouse_slasher_dequeue::<E>(None).await;
+
+        match result {
+            Err(e) => {
+                assert_eq!(e.status().unwrap(), 404);
+            }
+            _ => panic!("query did not fail correctly"),
+        }
+
+        self
+    }
+
+    pub async fn test_post_lighthouse_slasher_dequeue_round_trip(self) -> Self {
+        // Take and acknowledge the attestations and blocks verified while building the chain.
+        let batch = self
+            .client
+            .post_lighthouse_slasher_dequeue::<E>(None)
+            .await
+            .unwrap()
+            .data;
+        let batch = self
+            .client
+            .post_lighthouse_slasher_dequeue::<E>(Some(batch.batch_id))
+            .await
+            .unwrap()
+            .data;
+
+        let block_header = self.chain.head_beacon_block().signed_block_header();
+        self.chain.slasher_accept_block_header(block_header.clone());
+
+        // The unacknowledged batch is handed out again, without the new block header.
+        let repeated_batch = self
+            .client
+            .post_lighthouse_slasher_dequeue::<E>(None)
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(repeated_batch, batch);
+
+        // Once acknowledged, the next batch contains the new block header.
+        let next_batch = self
+            .client
+            .post_lighthouse_slasher_dequeue::<E>(Some(batch.batch_id))
+            .await
+            .unwrap()
+            .data;
+        assert_ne!(next_batch.batch_id, batch.batch_id);
+        assert_eq!(next_batch.block_headers, vec![block_header]);
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_beacon_processor_queues(self) -> Self {
+        let queues = self
+            .client
+            .get_lighthouse_beacon_processor_queues()
+            .await
+            .unwrap()
+            .data;
+
+        let gossip_block_queue = queues
+            .iter()
+            .find(|queue| queue.name == beacon_processor::GOSSIP_BLOCK)
+            .expect("gossip block queue is reported");
+        assert!(gossip_block_queue.max_length > 0);
+        for queue in &queues {
+            assert!(queue.length <= queue.max_length);
+            assert!(queue.max_depth >= queue.length);
+        }
+
+        self.client
+            .post_lighthouse_beacon_processor_queues_reset()
+            .await
+            .unwrap();
+
+        let queues = self
+            .client
+            .get_lighthouse_beacon_processor_queues()
+            .await
+            .unwrap()
+            .data;
+        for queue in &queues {
+            assert_eq!(queue.dropped, 0);
+        }
+
+        self
+    }
+
+    pub async fn test_get_and_post_lighthouse_logging(self) -> Self {
+        let levels = |levels: &str| ModuleLogLevels {
+            levels: levels.to_string(),
+        };
+
+        let response = self.client.get_lighthouse_logging().await.unwrap().data;
+        assert_eq!(response, levels(""));
+
+        let response = self
+            .client
+            .post_lighthouse_logging(&levels("network=debug, http_api=trace"))
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(response, levels("network=debug,http_api=trace"));
+
+        assert_eq!(
+            self.client
+                .post_lighthouse_logging(&levels("network=loud"))
+                .await
+                .unwrap_err()
+                .status()
+                .map(Into::into),
+            Some(400),
+            "should reject unknown levels"
+        );
+
+        let response = self.client.get_lighthouse_logging().await.unwrap().data;
+        assert_eq!(response, levels("network=debug,http_api=trace"));
+
+        let response = self
+            .client
+            .post_lighthouse_logging(&levels(""))
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(response, levels(""));
+
+        self
+    }
+
+    pub async fn test_post_lighthouse_duties_all(self) -> Self {
+        let current_epoch = self.chain.epoch().unwrap();
+        let indices = (0..self.validator_keypairs().len() as u64).collect::<Vec<_>>();
+
+        for epoch in [current_epoch, current_epoch + 1] {
+            let result = self
+                .client
+                .post_lighthouse_duties_all(epoch, &indices)
+                .await
+                .unwrap()
+                .data;
+
+            let proposer = self
+                .client
+                .get_validator_duties_proposer(epoch)
+                .await
+                .unwrap();
+            let attester = self
+                .client
+                .post_validator_duties_attester(epoch, &indices)
+                .await
+                .unwrap();
+            let sync = self
+                .client
+                .post_validator_duties_sync(epoch, &indices)
+                .await
+                .unwrap();
+
+            assert_eq!(result.epoch, epoch);
+            assert_eq!(result.execution_optimistic, Some(false));
+            assert_eq!(result.proposer_dependent_root, proposer.dependent_root);
+            assert_eq!(result.attester_dependent_root, attester.dependent_root);
+            assert_eq!(result.proposer_duties, proposer.data);
+            assert_eq!(result.attester_duties, attester.data);
+            assert_eq!(result.sync_duties, sync.data);
+        }
+
+        // Only the duties of the requested validators are returned.
+        let result = self
+            .client
+            .post_lighthouse_duties_all(current_epoch, &[0])
+            .await
+            .unwrap()
+            .data;
+        assert!(result
+            .proposer_duties
+            .iter()
+            .all(|duty| duty.validator_index == 0));
+        assert_eq!(result.attester_duties.len(), 1);
+
+        // Duties can't be computed more than one epoch in the future.
+        assert_eq!(
+            self.client
+                .post_lighthouse_duties_all(current_epoch + 2, &indices)
+                .await
+                .unwrap_err()
+                .status()
+                .map(Into::into),
+            Some(400)
+        );
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_validator_set_changes(self) -> Self {
+        let head_epoch = self.chain.head_snapshot().beacon_state.current_epoch();
+
+        // All validators are active from genesis and none have exited or been slashed.
+        let result = self
+            .client
+            .get_lighthouse_validator_set_changes(Epoch::new(0))
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(result.since_epoch, Epoch::new(0));
+        assert_eq!(result.next_epoch, head_epoch);
+        assert!(result.changes.is_empty());
+
+        // Following the cursor returns no further changes until the next epoch is complete.
+        let response = self
+            .client
+            .get_lighthouse_validator_set_changes(result.next_epoch)
+            .await
+            .unwrap();
+        let finalized_epoch = self
+            .chain
+            .canonical_head
+            .cached_head()
+            .finalized_checkpoint()
+            .epoch;
+        assert_eq!(response.execution_optimistic, Some(false));
+        assert_eq!(response.finalized, Some(head_epoch <= finalized_epoch));
+        let result = response.data;
+        assert_eq!(result.since_epoch, head_epoch);
+        assert_eq!(result.next_epoch, head_epoch);
+        assert!(result.changes.is_empty());
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_proofs_historical_block_root(self) -> Self {
+        let head = self.chain.head_snapshot();
+        let head_slot = head.beacon_state.slot();
+
+        for slot in (0..head_slot.as_u64()).map(Slot::new) {
+            let result = self
+                .client
+                .get_lighthouse_proofs_historical_block_root(slot)
+                .await
+                .unwrap()
+                .data;
+
+            let expected_root = self
+                .chain
+                .block_root_at_slot(slot, WhenSlotSkipped::Prev)
+                .unwrap()
+                .unwrap();
+            assert_eq!(result.block_root, expected_root);
+            assert_eq!(result.state_root, head.beacon_state_root());
+
+            let depth = result.proof.len();
+            assert!(merkle_proof::verify_merkle_proof(
+                result.block_root,
+                &result.proof,
+                depth,
+                (result.gindex - (1 << depth)) as usize,
+                result.state_root,
+            ));
+        }
+
+        // The head state does not yet commit to its own block root.
+        assert!(self
+            .client
+            .get_lighthouse_proofs_historical_block_root(head_slot)
+            .await
+            .is_err());
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_proofs_state(self) -> Self {
+        let head = self.chain.head_snapshot();
+        let state = &head.beacon_state;
+        let fields_gindex = state.num_fields_pow2() as u64;
+
+        // The `slot` field, and the first chunk of the `balances` list.
+        let slot_gindex = fields_gindex + 2;
+        let balances_chunk_gindex = ((fields_gindex + 12) << 1) << 38;
+
+        for gindex in [slot_gindex, balances_chunk_gindex] {
+            let result = self
+                .client
+                .get_lighthouse_proofs_state(CoreStateId::Head, &[gindex])
+                .await
+                .unwrap()
+                .data;
+
+            assert_eq!(result.state_root, head.beacon_state_root());
+            assert_eq!(result.gindices, vec![gindex]);
+
+            // For a single leaf the helper nodes are exactly its branch, from the bottom up.
+            let depth = result.proof.len();
+            assert_eq!(result.helper_indices.len(), depth);
+            assert!(merkle_proof::verify_merkle_proof(
+                result.leaves[0],
+                &result.proof,
+                depth,
+                (gindex - (1 << depth)) as usize,
+                result.state_root,
+            ));
+        }
+
+        let result = self
+            .client
+            .get_lighthouse_proofs_state(CoreStateId::Head, &[slot_gindex])
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(result.leaves[0], state.slot().tree_hash_root());
+
+        let mut expected_chunk = [0; 32];
+        for (i, balance) in state.balances().iter().take(4).enumerate() {
+            expected_chunk[i * 8..(i + 1) * 8].copy_from_slice(&balance.to_le_bytes());
+        }
+        let result = self
+            .client
+            .get_lighthouse_proofs_state(CoreStateId::Head, &[balances_chunk_gindex])
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(result.leaves[0], Hash256::from(expected_chunk));
+
+        // Proving several leaves at once shares helper nodes between them.
+        let result = self
+            .client
+            .get_lighthouse_proofs_state(CoreStateId::Head, &[slot_gindex, slot_gindex + 1])
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(result.leaves.len(), 2);
+        assert!(!result.helper_indices.contains(&slot_gindex));
+        assert!(!result.helper_indices.contains(&(slot_gindex + 1)));
 
         self
     }
 
-    pub async fn test_post_lighthouse_database_reconstruct(self) -> Self {
-        let response = self
+    pub async fn test_get_lighthouse_analysis_state_diff(self) -> Self {
+        let from = Slot::new(0);
+        let to = self.chain.head_snapshot().beacon_state.slot();
+
+        let result = self
             .client
-            .post_lighthouse_database_reconstruct()
+            .get_lighthouse_analysis_state_diff(from, to)
             .await
+            .unwrap()
+            .data;
+
+        let from_state = self
+            .chain
+            .state_at_slot(from, StateSkipConfig::WithStateRoots)
             .unwrap();
-        assert_eq!(response, "success");
+        let to_state = self
+            .chain
+            .state_at_slot(to, StateSkipConfig::WithStateRoots)
+            .unwrap();
+
+        assert_eq!(result.from_slot, from);
+        assert_eq!(result.to_slot, to);
+        for diff in &result.validators {
+            let index = diff.index as usize;
+            assert!(
+                diff.balance_changed()
+                    || diff.status_changed()
+                    || diff.withdrawal_credentials_changed()
+            );
+            assert_eq!(
+                diff.to.balance,
+                to_state.balances().get(index).copied().unwrap()
+            );
+            if let Some(from_fields) = &diff.from {
+                assert_eq!(
+                    from_fields.balance,
+                    from_state.balances().get(index).copied().unwrap()
+                );
+            }
+        }
+
+        // Every validator with a changed balance must be reported.
+        let changed_balances = to_state
+            .balances()
+            .iter()
+            .zip(from_state.balances().iter())
+            .filter(|(to, from)| to != from)
+            .count();
+        assert!(
+            result
+                .validators
+                .iter()
+                .filter(|diff| diff.balance_changed())
+                .count()
+                >= changed_balances
+        );
+
+        let ssz_result = self
+            .client
+            .get_lighthouse_analysis_state_diff_ssz(from, to)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(ssz_result, result);
+
+        // The `from` slot must not be after the `to` slot.
+        assert!(self
+            .client
+            .get_lighthouse_analysis_state_diff(to, from)
+            .await
+            .is_err());
+
         self
     }
 
@@ -5770,6 +7009,243 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_post_beacon_rewards_attestations_paged(self) -> Self {
+        let epoch = Epoch::new(1);
+        let page_size = 7;
+
+        let expected = self
+            .client
+            .post_beacon_rewards_attestations(epoch, &[])
+            .await
+            .unwrap()
+            .data;
+
+        let mut total_rewards = vec![];
+        let mut page_token = None;
+        loop {
+            let page = self
+                .client
+                .post_beacon_rewards_attestations_paged(
+                    epoch,
+                    &[],
+                    page_size,
+                    page_token.as_deref(),
+                )
+                .await
+                .unwrap()
+                .data;
+
+            assert_eq!(page.ideal_rewards, expected.ideal_rewards);
+            assert!(page.total_rewards.len() <= page_size);
+            total_rewards.extend(page.total_rewards);
+
+            match page.next_page_token {
+                Some(token) => page_token = Some(token),
+                None => break,
+            }
+        }
+        assert_eq!(total_rewards, expected.total_rewards);
+
+        let result = self
+            .client
+            .post_beacon_rewards_attestations_paged(epoch, &[], 0, None)
+            .await;
+        assert_eq!(result.unwrap_err().status().unwrap(), 400);
+
+        let result = self
+            .client
+            .post_beacon_rewards_attestations_paged(epoch, &[], page_size, Some("invalid"))
+            .await;
+        assert_eq!(result.unwrap_err().status().unwrap(), 400);
+
+        self
+    }
+
+    pub async fn test_beacon_rewards_ssz(self) -> Self {
+        let epoch = Epoch::new(1);
+        let block_id = CoreBlockId::Head;
+
+        let attestation_rewards = self
+            .client
+            .post_beacon_rewards_attestations(epoch, &[])
+            .await
+            .unwrap()
+            .data;
+        let attestation_rewards_ssz = self
+            .client
+            .post_beacon_rewards_attestations_ssz(epoch, &[])
+            .await
+            .unwrap();
+        assert_eq!(attestation_rewards_ssz, attestation_rewards);
+
+        let block_rewards = self
+            .client
+            .get_beacon_rewards_blocks(block_id)
+            .await
+            .unwrap()
+            .data;
+        let block_rewards_ssz = self
+            .client
+            .get_beacon_rewards_blocks_ssz(block_id)
+            .await
+            .unwrap();
+        assert_eq!(block_rewards_ssz, block_rewards);
+
+        let sync_committee_rewards = self
+            .client
+            .post_beacon_rewards_sync_committee(block_id, &[])
+            .await
+            .unwrap()
+            .data;
+        let sync_committee_rewards_ssz = self
+            .client
+            .post_beacon_rewards_sync_committee_ssz(block_id, &[])
+            .await
+            .unwrap();
+        assert!(!sync_committee_rewards.is_empty());
+        assert_eq!(sync_committee_rewards_ssz, sync_committee_rewards);
+
+        self
+    }
+
+    pub async fn test_beacon_rewards_units(self) -> Self {
+        let epoch = Epoch::new(1);
+        let block_id = CoreBlockId::Head;
+        let eth = |gwei: u64| format!("{}.{:09}", gwei / 1_000_000_000, gwei % 1_000_000_000);
+        let wei = |gwei: i64| (gwei as i128 * 1_000_000_000).to_string();
+        // Inclusion delay rewards are only paid in phase0, and there are no sync committees.
+        let phase0 = self.chain.spec.fork_name_at_epoch(epoch) == ForkName::Base;
+
+        let block_rewards = self
+            .client
+            .get_beacon_rewards_blocks(block_id)
+            .await
+            .unwrap();
+        let block_rewards_eth = self
+            .client
+            .get_beacon_rewards_blocks_in_units(block_id, RewardUnits::Eth)
+            .await
+            .unwrap();
+        assert_eq!(block_rewards_eth.units, RewardUnits::Eth);
+        assert_eq!(block_rewards_eth.finalized, block_rewards.finalized);
+        assert_eq!(
+            block_rewards_eth.data["total"],
+            eth(block_rewards.data.total)
+        );
+        assert_eq!(
+            block_rewards_eth.data["proposer_index"],
+            block_rewards.data.proposer_index.to_string(),
+            "indices should not be converted"
+        );
+
+        let block_rewards_gwei = self
+            .client
+            .get_beacon_rewards_blocks_in_units(block_id, RewardUnits::Gwei)
+            .await
+            .unwrap();
+        assert_eq!(block_rewards_gwei.units, RewardUnits::Gwei);
+        assert_eq!(
+            block_rewards_gwei.data,
+            serde_json::to_value(&block_rewards.data).unwrap()
+        );
+
+        let attestation_rewards = self
+            .client
+            .post_beacon_rewards_attestations(epoch, &[])
+            .await
+            .unwrap()
+            .data;
+        let attestation_rewards_eth = self
+            .client
+            .post_beacon_rewards_attestations_in_units(epoch, &[], RewardUnits::Eth)
+            .await
+            .unwrap();
+        assert_eq!(attestation_rewards_eth.units, RewardUnits::Eth);
+        for (ideal, ideal_eth) in attestation_rewards.ideal_rewards.iter().zip(
+            attestation_rewards_eth.data["ideal_rewards"]
+                .as_array()
+                .unwrap(),
+        ) {
+            assert_eq!(ideal_eth["effective_balance"], eth(ideal.effective_balance));
+            assert_eq!(ideal_eth["head"], eth(ideal.head));
+            assert_eq!(
+                ideal_eth["total_active_balance"],
+                eth(ideal.total_active_balance)
+            );
+            assert_eq!(ideal.inclusion_delay.is_some(), phase0);
+            if let Some(inclusion_delay) = ideal.inclusion_delay {
+                assert_eq!(ideal_eth["inclusion_delay"], eth(inclusion_delay.value));
+            }
+        }
+        let attestation_rewards_wei = self
+            .client
+            .post_beacon_rewards_attestations_in_units(epoch, &[], RewardUnits::Wei)
+            .await
+            .unwrap();
+        for (total, total_wei) in attestation_rewards.total_rewards.iter().zip(
+            attestation_rewards_wei.data["total_rewards"]
+                .as_array()
+                .unwrap(),
+        ) {
+            assert_eq!(
+                total_wei["validator_index"],
+                total.validator_index.to_string()
+            );
+            assert_eq!(total_wei["head"], wei(total.head));
+            assert_eq!(total_wei["inactivity"], wei(total.inactivity));
+            assert_eq!(total.inclusion_delay.is_some(), phase0);
+            match total.inclusion_delay {
+                Some(inclusion_delay) => assert_eq!(
+                    total_wei["inclusion_delay"],
+                    wei(inclusion_delay.value as i64)
+                ),
+                None => assert!(total_wei.get("inclusion_delay").is_none()),
+            }
+        }
+        if phase0 {
+            assert!(
+                attestation_rewards
+                    .total_rewards
+                    .iter()
+                    .any(|total| total.inclusion_delay.is_some_and(|delay| delay.value > 0)),
+                "some inclusion delay rewards should be paid"
+            );
+        } else {
+            let sync_committee_rewards = self
+                .client
+                .post_beacon_rewards_sync_committee(block_id, &[])
+                .await
+                .unwrap()
+                .data;
+            let sync_committee_rewards_wei = self
+                .client
+                .post_beacon_rewards_sync_committee_in_units(block_id, &[], RewardUnits::Wei)
+                .await
+                .unwrap();
+            assert_eq!(sync_committee_rewards_wei.units, RewardUnits::Wei);
+            let rewards_wei = sync_committee_rewards_wei.data.as_array().unwrap();
+            assert_eq!(rewards_wei.len(), sync_committee_rewards.len());
+            for (reward, reward_wei) in sync_committee_rewards.iter().zip(rewards_wei) {
+                assert_eq!(reward_wei["reward"], wei(reward.reward));
+            }
+        }
+
+        // Only gwei can be encoded as SSZ.
+        let mut url = self.client.server().full.clone();
+        url.path_segments_mut()
+            .unwrap()
+            .pop_if_empty()
+            .extend(["eth", "v1", "beacon", "rewards", "blocks", "head"]);
+        url.query_pairs_mut().append_pair("units", "eth");
+        let result = self
+            .client
+            .get_response(url, |b| b.accept(Accept::Ssz))
+            .await;
+        assert_eq!(result.unwrap_err().status().unwrap(), 400);
+
+        self
+    }
+
     pub async fn test_get_events_altair(self) -> Self {
         let topics = vec![EventTopic::ContributionAndProof];
         let mut events_future = self
@@ -5960,8 +7436,12 @@ async fn beacon_get() {
         .await
         .test_beacon_states_validators()
         .await
+        .test_beacon_states_validators_page()
+        .await
         .test_beacon_states_validator_balances()
         .await
+        .test_beacon_states_validator_balances_cache()
+        .await
         .test_beacon_states_committees()
         .await
         .test_beacon_states_validator_id()
@@ -6109,6 +7589,42 @@ async fn beacon_pools_post_proposer_slashings_invalid() {
         .await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn lighthouse_pool_stats() {
+    ApiTester::new()
+        .await
+        .test_get_lighthouse_pool_stats()
+        .await
+        .test_post_beacon_pool_proposer_slashings_valid()
+        .await
+        .test_get_lighthouse_pool_stats()
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn lighthouse_slasher_dequeue() {
+    ApiTester::new_from_config(ApiTesterConfig::default().remote_slasher())
+        .await
+        .test_post_lighthouse_slasher_dequeue_round_trip()
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn lighthouse_partial_block_body() {
+    ApiTester::new()
+        .await
+        .test_post_lighthouse_partial_block_body()
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn lighthouse_equivocations() {
+    ApiTester::new()
+        .await
+        .test_get_lighthouse_equivocations()
+        .await;
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn beacon_pools_post_voluntary_exits_valid() {
     ApiTester::new()
@@ -6171,6 +7687,11 @@ async fn node_get() {
         .await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn node_get_peers_paged() {
+    ApiTester::new().await.test_get_node_peers_paged().await;
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn get_light_client_bootstrap() {
     let config = ApiTesterConfig {
@@ -6822,26 +8343,76 @@ async fn lighthouse_endpoints() {
         .await
         .test_get_lighthouse_proto_array()
         .await
+        .test_get_lighthouse_fork_choice()
+        .await
+        .test_get_lighthouse_debug_fork_choice_snapshot()
+        .await
         .test_get_lighthouse_validator_inclusion()
         .await
         .test_get_lighthouse_validator_inclusion_global()
         .await
+        .test_get_lighthouse_analysis_participation()
+        .await
+        .test_get_lighthouse_analysis_churn()
+        .await
+        .test_post_lighthouse_debug_decode_ssz()
+        .await
+        .test_get_lighthouse_block_rewards_predicted()
+        .await
         .test_get_lighthouse_eth1_syncing()
         .await
         .test_get_lighthouse_eth1_block_cache()
         .await
         .test_get_lighthouse_eth1_deposit_cache()
         .await
+        .test_get_lighthouse_deposit_status()
+        .await
         .test_get_lighthouse_staking()
         .await
+        .test_get_lighthouse_execution_capabilities()
+        .await
+        .test_get_lighthouse_execution_auth()
+        .await
+        .test_get_lighthouse_network_propagation()
+        .await
+        .test_get_lighthouse_network_gossip_rejections()
+        .await
         .test_get_lighthouse_database_info()
         .await
         .test_post_lighthouse_database_reconstruct()
         .await
+        .test_post_lighthouse_slasher_dequeue()
+        .await
+        .test_get_lighthouse_debug_memory()
+        .await
+        .test_get_lighthouse_debug_profile()
+        .await
+        .test_get_lighthouse_beacon_processor_queues()
+        .await
+        .test_get_and_post_lighthouse_logging()
+        .await
+        .test_get_lighthouse_validator_set_changes()
+        .await
+        .test_post_lighthouse_duties_all()
+        .await
+        .test_get_lighthouse_proofs_historical_block_root()
+        .await
+        .test_get_lighthouse_proofs_state()
+        .await
+        .test_get_lighthouse_analysis_state_diff()
+        .await
         .test_post_lighthouse_liveness()
         .await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn get_beacon_blocks_with_pruned_payloads() {
+    ApiTester::new_with_hard_forks(true, true)
+        .await
+        .test_beacon_blocks_with_pruned_payloads()
+        .await;
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn optimistic_responses() {
     ApiTester::new_with_hard_forks(true, true)
@@ -6850,6 +8421,44 @@ async fn optimistic_responses() {
         .await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn beacon_rewards_attestations_paged() {
+    let mut config = ApiTesterConfig::default();
+    config.spec.altair_fork_epoch = Some(Epoch::new(0));
+    ApiTester::new_from_config(config)
+        .await
+        .test_post_beacon_rewards_attestations_paged()
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn beacon_rewards_ssz() {
+    let mut config = ApiTesterConfig::default();
+    config.spec.altair_fork_epoch = Some(Epoch::new(0));
+    ApiTester::new_from_config(config)
+        .await
+        .test_beacon_rewards_ssz()
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn beacon_rewards_units() {
+    let mut config = ApiTesterConfig::default();
+    config.spec.altair_fork_epoch = Some(Epoch::new(0));
+    ApiTester::new_from_config(config)
+        .await
+        .test_beacon_rewards_units()
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn beacon_rewards_units_phase0() {
+    ApiTester::new_from_config(ApiTesterConfig::default())
+        .await
+        .test_beacon_rewards_units()
+        .await;
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn expected_withdrawals_invalid_pre_capella() {
     let mut config = ApiTesterConfig::default();
@@ -781,6 +781,79 @@ where
         Ok(msg_id)
     }
 
+    /// Publishes a message to the given `peers` only, ahead of publishing it to the rest of the
+    /// network with [`Behaviour::publish`].
+    ///
+    /// The message is not added to the duplicate cache or the memcache, so it is not gossiped to
+    /// other peers and may subsequently be published with [`Behaviour::publish`]. Peers which are
+    /// not connected or not subscribed to the topic are skipped.
+    pub fn publish_to_peers(
+        &mut self,
+        topic: impl Into<TopicHash>,
+        data: impl Into<Vec<u8>>,
+        peers: &[PeerId],
+    ) -> Result<MessageId, PublishError> {
+        let data = data.into();
+        let topic = topic.into();
+
+        let transformed_data = self
+            .data_transform
+            .outbound_transform(&topic, data.clone())?;
+
+        let raw_message = self.build_raw_message(topic, transformed_data)?;
+
+        let msg_id = self.config.message_id(&Message {
+            source: raw_message.source,
+            data,
+            sequence_number: raw_message.sequence_number,
+            topic: raw_message.topic.clone(),
+        });
+
+        if raw_message.raw_protobuf_len() > self.config.max_transmit_size() {
+            return Err(PublishError::MessageTooLarge);
+        }
+
+        if self.duplicate_cache.contains(&msg_id) {
+            return Err(PublishError::Duplicate);
+        }
+
+        let mut recipient_peers = 0;
+        let mut publish_failed = true;
+        for peer_id in peers {
+            let Some(peer) = self.connected_peers.get_mut(peer_id) else {
+                continue;
+            };
+            if !peer.topics.contains(&raw_message.topic) {
+                continue;
+            }
+            recipient_peers += 1;
+            tracing::trace!(peer=%peer_id, "Sending message to peer");
+            match peer.sender.publish(
+                raw_message.clone(),
+                self.config.publish_queue_duration(),
+                self.metrics.as_mut(),
+            ) {
+                Ok(_) => publish_failed = false,
+                Err(_) => {
+                    self.failed_messages.entry(*peer_id).or_default().priority += 1;
+                    tracing::warn!(peer_id=%peer_id, "Publish queue full. Could not publish to peer");
+                }
+            }
+        }
+
+        if recipient_peers == 0 {
+            return Err(PublishError::InsufficientPeers);
+        }
+
+        if publish_failed {
+            return Err(PublishError::AllQueuesFull(recipient_peers));
+        }
+
+        tracing::debug!(message=%msg_id, "Published message to selected peers");
+
+        Ok(msg_id)
+    }
+
     /// This function should be called when [`Config::validate_messages()`] is `true` after
     /// the message got validated by the caller. Messages are stored in the ['Memcache'] and
     /// validation is expected to be fast enough that the messages should still exist in the cache.
@@ -1378,6 +1451,9 @@ where
                 } else if let Some(peer) = &mut self.connected_peers.get_mut(peer_id) {
                     if peer.dont_send.get(&id).is_some() {
                         tracing::debug!(%peer_id, message=%id, "Peer already sent IDONTWANT for this message");
+                        if let Some(metrics) = self.metrics.as_mut() {
+                            metrics.register_idontwant_skipped(&msg.topic);
+                        }
                         continue;
                     }
 
@@ -1809,7 +1885,9 @@ where
         let msg_id = self.config.message_id(&message);
 
         // Broadcast IDONTWANT messages.
-        self.send_idontwant(&raw_message, &msg_id, propagation_source);
+        if self.config.idontwant() {
+            self.send_idontwant(&raw_message, &msg_id, propagation_source);
+        }
 
         // Check the validity of the message
         // Peers get penalized if this message is invalid. We don't add it to the duplicate cache
@@ -2792,6 +2870,9 @@ where
                 if let Some(peer) = self.connected_peers.get_mut(peer_id) {
                     if peer.dont_send.get(msg_id).is_some() {
                         tracing::debug!(%peer_id, message=%msg_id, "Peer doesn't want message");
+                        if let Some(metrics) = self.metrics.as_mut() {
+                            metrics.register_idontwant_skipped(&message.topic);
+                        }
                         continue;
                     }
 
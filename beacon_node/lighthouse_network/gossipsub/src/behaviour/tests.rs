@@ -750,6 +750,56 @@ fn test_publish_without_flood_publishing() {
     );
 }
 
+/// Test that publishing to selected peers only sends to those peers and still allows the message
+/// to be published to the network afterwards.
+#[test]
+fn test_publish_to_peers() {
+    let publish_topic = String::from("test_publish");
+    let (mut gs, peers, receivers, _) = inject_nodes1()
+        .peer_no(20)
+        .topics(vec![publish_topic.clone()])
+        .to_subscribe(true)
+        .create_network();
+
+    let selected = [peers[0], peers[1], PeerId::random()];
+    let publish_data = vec![0; 42];
+    let msg_id = gs
+        .publish_to_peers(
+            Topic::new(publish_topic.clone()),
+            publish_data.clone(),
+            &selected,
+        )
+        .unwrap();
+
+    // Only the selected, connected peers received the message.
+    let mut recipients = receivers
+        .into_iter()
+        .fold(vec![], |mut recipients, (peer_id, c)| {
+            let priority = c.priority.into_inner();
+            while !priority.is_empty() {
+                if let Ok(RpcOut::Publish { .. }) = priority.try_recv() {
+                    recipients.push(peer_id);
+                }
+            }
+            recipients
+        });
+    recipients.sort();
+    let mut expected = vec![peers[0], peers[1]];
+    expected.sort();
+    assert_eq!(recipients, expected);
+    assert!(
+        gs.mcache.get(&msg_id).is_none(),
+        "Message cache should not contain the message"
+    );
+
+    // The message can then be published to the network.
+    assert_eq!(
+        gs.publish(Topic::new(publish_topic), publish_data).unwrap(),
+        msg_id
+    );
+    assert!(gs.mcache.get(&msg_id).is_some());
+}
+
 /// Test local node publish to unsubscribed topic
 #[test]
 fn test_fanout() {
@@ -5327,6 +5377,46 @@ fn doesnt_send_idontwant() {
     );
 }
 
+/// Test that a node doesn't send IDONTWANT messages when they are disabled.
+#[test]
+fn doesnt_send_idontwant_when_disabled() {
+    let config = ConfigBuilder::default().idontwant(false).build().unwrap();
+    let (mut gs, peers, receivers, topic_hashes) = inject_nodes1()
+        .peer_no(5)
+        .topics(vec![String::from("topic1")])
+        .to_subscribe(true)
+        .gs_config(config)
+        .explicit(1)
+        .peer_kind(PeerKind::Gossipsubv1_2_beta)
+        .create_network();
+
+    let local_id = PeerId::random();
+
+    let message = RawMessage {
+        source: Some(peers[1]),
+        data: vec![12],
+        sequence_number: Some(0),
+        topic: topic_hashes[0].clone(),
+        signature: None,
+        key: None,
+        validated: true,
+    };
+    gs.handle_received_message(message.clone(), &local_id);
+    assert_eq!(
+        receivers.into_iter().fold(0, |mut idontwants, (_, c)| {
+            let non_priority = c.non_priority.into_inner();
+            while !non_priority.is_empty() {
+                if let Ok(RpcOut::IDontWant(_)) = non_priority.try_recv() {
+                    idontwants += 1;
+                }
+            }
+            idontwants
+        }),
+        0,
+        "IDONTWANT were sent"
+    );
+}
+
 /// Test that a node doesn't forward a messages to the mesh peers
 /// that sent IDONTWANT.
 #[test]
@@ -98,6 +98,7 @@ pub struct Config {
     connection_handler_queue_len: usize,
     connection_handler_publish_duration: Duration,
     connection_handler_forward_duration: Duration,
+    idontwant: bool,
 }
 
 impl Config {
@@ -370,6 +371,13 @@ impl Config {
     pub fn forward_queue_duration(&self) -> Duration {
         self.connection_handler_forward_duration
     }
+
+    /// Whether IDONTWANT control messages are sent to gossipsub v1.2 mesh peers upon receiving a
+    /// message, so that they do not forward duplicates of it to us. IDONTWANT messages received
+    /// from peers are always respected. The default is true.
+    pub fn idontwant(&self) -> bool {
+        self.idontwant
+    }
 }
 
 impl Default for Config {
@@ -440,6 +448,7 @@ impl Default for ConfigBuilder {
                 connection_handler_queue_len: 5000,
                 connection_handler_publish_duration: Duration::from_secs(5),
                 connection_handler_forward_duration: Duration::from_millis(1000),
+                idontwant: true,
             },
             invalid_protocol: false,
         }
@@ -825,6 +834,14 @@ impl ConfigBuilder {
         self
     }
 
+    /// Whether IDONTWANT control messages are sent to gossipsub v1.2 mesh peers upon receiving a
+    /// message, so that they do not forward duplicates of it to us. IDONTWANT messages received
+    /// from peers are always respected. The default is true.
+    pub fn idontwant(&mut self, idontwant: bool) -> &mut Self {
+        self.config.idontwant = idontwant;
+        self
+    }
+
     /// Constructs a [`Config`] from the given configuration and validates the settings.
     pub fn build(&self) -> Result<Config, ConfigBuilderError> {
         // check all constraints on config
@@ -895,6 +912,7 @@ impl std::fmt::Debug for Config {
             "published_message_ids_cache_time",
             &self.published_message_ids_cache_time,
         );
+        let _ = builder.field("idontwant", &self.idontwant);
         builder.finish()
     }
 }
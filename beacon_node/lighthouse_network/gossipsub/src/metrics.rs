@@ -185,6 +185,10 @@ pub(crate) struct Metrics {
     /// The number of msg_id's we have received in every IDONTWANT control message.
     idontwant_msgs_ids: Counter,
 
+    /// The number of messages on each topic that were not sent to a peer because the peer had
+    /// sent an IDONTWANT for them.
+    topic_idontwant_skipped_msgs: Family<TopicHash, Counter>,
+
     /// The size of the priority queue.
     priority_queue_size: Histogram,
     /// The size of the non-priority queue.
@@ -338,6 +342,11 @@ impl Metrics {
             metric
         };
 
+        let topic_idontwant_skipped_msgs = register_family!(
+            "topic_idontwant_skipped_msgs",
+            "Number of messages not sent to a peer on each topic because the peer sent an IDONTWANT"
+        );
+
         let memcache_misses = {
             let metric = Counter::default();
             registry.register(
@@ -391,6 +400,7 @@ impl Metrics {
             topic_iwant_msgs,
             idontwant_msgs,
             idontwant_msgs_ids,
+            topic_idontwant_skipped_msgs,
             priority_queue_size,
             non_priority_queue_size,
         }
@@ -595,6 +605,13 @@ impl Metrics {
         self.idontwant_msgs_ids.inc_by(msgs as u64);
     }
 
+    /// Register not sending a message on this topic because the peer sent an IDONTWANT for it.
+    pub(crate) fn register_idontwant_skipped(&mut self, topic: &TopicHash) {
+        if self.register_topic(topic).is_ok() {
+            self.topic_idontwant_skipped_msgs.get_or_create(topic).inc();
+        }
+    }
+
     /// Observes a heartbeat duration.
     pub(crate) fn observe_heartbeat_duration(&mut self, millis: u64) {
         self.heartbeat_duration.observe(millis as f64);
@@ -1,5 +1,9 @@
 use crate::listen_addr::{ListenAddr, ListenAddress};
+use crate::peer_manager::config::{
+    DEFAULT_INBOUND_CONNECTION_RATE_LIMIT, DEFAULT_NEW_PEER_GRACE_PERIOD,
+};
 use crate::rpc::config::{InboundRateLimiterConfig, OutboundRateLimiterConfig};
+use crate::service::TARGET_SUBNET_PEERS;
 use crate::types::GossipKind;
 use crate::{Enr, PeerIdSerialized};
 use directory::{
@@ -13,7 +17,7 @@ use std::num::NonZeroU16;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
-use types::{ForkContext, ForkName};
+use types::{ForkContext, ForkName, PublicKey};
 
 pub const DEFAULT_IPV4_ADDRESS: Ipv4Addr = Ipv4Addr::UNSPECIFIED;
 pub const DEFAULT_TCP_PORT: u16 = 9000u16;
@@ -32,6 +36,7 @@ pub fn gossip_max_size(is_merge_enabled: bool, gossip_max_size: usize) -> usize
 pub struct GossipsubConfigParams {
     pub message_domain_valid_snappy: [u8; 4],
     pub gossip_max_size: usize,
+    pub idontwant: bool,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -41,6 +46,10 @@ pub struct Config {
     /// Data directory where node's keyfile is stored
     pub network_dir: PathBuf,
 
+    /// A file containing the node's secp256k1 network key. If set, this key is always used instead
+    /// of the key in `network_dir`, giving the node a fixed identity.
+    pub network_key_file: Option<PathBuf>,
+
     /// IP addresses to listen on.
     listen_addresses: ListenAddress,
 
@@ -69,6 +78,10 @@ pub struct Config {
     /// Target number of connected peers.
     pub target_peers: usize,
 
+    /// The number of peers we aim to have on each subnet we need. Discovery queries are made for
+    /// subnets with fewer peers than this.
+    pub target_subnet_peers: usize,
+
     /// Discv5 configuration parameters.
     #[serde(skip)]
     pub discv5_config: discv5::Config,
@@ -79,12 +92,44 @@ pub struct Config {
     /// List of nodes to initially connect to, on Multiaddr format.
     pub boot_nodes_multiaddr: Vec<Multiaddr>,
 
+    /// An HTTPS URL from which an updated list of boot node ENRs is fetched at startup and
+    /// periodically thereafter. These are used in addition to `boot_nodes_enr`.
+    pub boot_nodes_url: Option<String>,
+
+    /// The public key which must have signed the list fetched from `boot_nodes_url`.
+    pub boot_nodes_public_key: Option<PublicKey>,
+
+    /// The node IDs of the ENRs which may be used from `boot_nodes_url`. If empty, any ENR with a
+    /// valid signature is used.
+    pub boot_nodes_allowlist: Vec<[u8; 32]>,
+
     /// List of libp2p nodes to initially connect to.
     pub libp2p_nodes: Vec<Multiaddr>,
 
     /// List of trusted libp2p nodes which are not scored and marked as explicit.
     pub trusted_peers: Vec<PeerIdSerialized>,
 
+    /// List of libp2p nodes, identified by multiaddrs ending in a peer id, which are always kept
+    /// connected. In addition to being treated as trusted peers, these are never pruned, do not
+    /// count towards the peer limits and are re-dialed when disconnected.
+    pub static_peers: Vec<Multiaddr>,
+
+    /// The maximum number of inbound connections accepted per second. A value of `0` disables the
+    /// limit.
+    pub inbound_connection_rate_limit: u32,
+
+    /// Time in seconds after connecting during which a peer is considered new. New peers are
+    /// pruned before established peers when we have too many peers.
+    pub new_peer_grace_period: u64,
+
+    /// Peers, such as private relays, which locally published blocks and blobs are sent to before
+    /// being published to the rest of the network. These peers are also treated as trusted peers.
+    pub trusted_mesh_peers: Vec<PeerIdSerialized>,
+
+    /// The delay between publishing blocks and blobs to the `trusted_mesh_peers` and publishing
+    /// them to the rest of the network.
+    pub trusted_mesh_delay: Duration,
+
     /// Disables peer scoring altogether.
     pub disable_peer_scoring: bool,
 
@@ -97,12 +142,19 @@ pub struct Config {
     /// Disables quic support.
     pub disable_quic_support: bool,
 
+    /// Disables sending gossipsub IDONTWANT messages to peers.
+    pub disable_gossipsub_idontwant: bool,
+
     /// Attempt to construct external port mappings with UPnP.
     pub upnp_enabled: bool,
 
     /// Subscribe to all subnets for the duration of the runtime.
     pub subscribe_all_subnets: bool,
 
+    /// Subscribe to all data column subnets once PeerDAS is scheduled, rather than only the
+    /// subnets this node custodies. The custody set itself is unaffected.
+    pub subscribe_all_data_column_subnets: bool,
+
     /// Import/aggregate all attestations received on subscribed subnets for the duration of the
     /// runtime.
     pub import_all_attestations: bool,
@@ -317,6 +369,7 @@ impl Default for Config {
         // NOTE: Some of these get overridden by the corresponding CLI default values.
         Config {
             network_dir,
+            network_key_file: None,
             listen_addresses,
             enr_address: (None, None),
             enr_udp4_port: None,
@@ -326,19 +379,30 @@ impl Default for Config {
             enr_quic6_port: None,
             enr_tcp6_port: None,
             target_peers: 100,
+            target_subnet_peers: TARGET_SUBNET_PEERS,
             discv5_config,
             boot_nodes_enr: vec![],
             boot_nodes_multiaddr: vec![],
+            boot_nodes_url: None,
+            boot_nodes_public_key: None,
+            boot_nodes_allowlist: vec![],
             libp2p_nodes: vec![],
             trusted_peers: vec![],
+            static_peers: vec![],
+            inbound_connection_rate_limit: DEFAULT_INBOUND_CONNECTION_RATE_LIMIT,
+            new_peer_grace_period: DEFAULT_NEW_PEER_GRACE_PERIOD,
+            trusted_mesh_peers: vec![],
+            trusted_mesh_delay: Duration::from_millis(100),
             disable_peer_scoring: false,
             client_version: lighthouse_version::version_with_platform(),
             disable_discovery: false,
             disable_quic_support: false,
+            disable_gossipsub_idontwant: false,
             upnp_enabled: true,
             network_load: 4,
             private: false,
             subscribe_all_subnets: false,
+            subscribe_all_data_column_subnets: false,
             import_all_attestations: false,
             shutdown_after_sync: false,
             topics: Vec::new(),
@@ -500,6 +564,7 @@ pub fn gossipsub_config(
         .duplicate_cache_time(duplicate_cache_time)
         .message_id_fn(gossip_message_id)
         .allow_self_origin(true)
+        .idontwant(gossipsub_config_params.idontwant)
         .build()
         .expect("valid gossipsub configuration")
 }
@@ -7,7 +7,6 @@ pub(crate) mod enr;
 pub mod enr_ext;
 
 // Allow external use of the lighthouse ENR builder
-use crate::service::TARGET_SUBNET_PEERS;
 use crate::{error, Enr, NetworkConfig, NetworkGlobals, Subnet, SubnetDiscovery};
 use crate::{metrics, ClearDialError};
 use discv5::{enr::NodeId, Discv5};
@@ -190,6 +189,9 @@ pub struct Discovery<E: EthSpec> {
     /// Specifies whether various port numbers should be updated after the discovery service has been started
     update_ports: UpdatePorts,
 
+    /// The number of peers we aim to have on each subnet we search for.
+    target_subnet_peers: usize,
+
     /// Logger for the discovery behaviour.
     log: slog::Logger,
     spec: Arc<ChainSpec>,
@@ -325,6 +327,7 @@ impl<E: EthSpec> Discovery<E> {
             event_stream,
             started: !config.disable_discovery,
             update_ports,
+            target_subnet_peers: config.target_subnet_peers,
             log,
             enr_dir,
             spec: Arc::new(spec.clone()),
@@ -734,16 +737,16 @@ impl<E: EthSpec> Discovery<E> {
                     .good_peers_on_subnet(subnet_query.subnet)
                     .count();
 
-                if peers_on_subnet >= TARGET_SUBNET_PEERS {
+                if peers_on_subnet >= self.target_subnet_peers {
                     debug!(self.log, "Discovery ignored";
                         "reason" => "Already connected to desired peers",
                         "connected_peers_on_subnet" => peers_on_subnet,
-                        "target_subnet_peers" => TARGET_SUBNET_PEERS,
+                        "target_subnet_peers" => self.target_subnet_peers,
                     );
                     return false;
                 }
 
-                let target_peers = TARGET_SUBNET_PEERS.saturating_sub(peers_on_subnet);
+                let target_peers = self.target_subnet_peers.saturating_sub(peers_on_subnet);
                 trace!(self.log, "Discovery query started for subnet";
                     "subnet_query" => ?subnet_query,
                     "connected_peers_on_subnet" => peers_on_subnet,
@@ -855,6 +858,10 @@ impl<E: EthSpec> Discovery<E> {
                     Ok(r) if r.is_empty() => {
                         debug!(self.log, "Grouped subnet discovery query yielded no results."; "subnets_searched_for" => ?subnets_searched_for);
                         queries.iter().for_each(|query| {
+                            metrics::inc_counter_vec(
+                                &metrics::SUBNET_QUERY_RESULTS,
+                                &[subnet_query_type(&query.subnet), "no_peers"],
+                            );
                             self.add_subnet_query(query.subnet, query.min_ttl, query.retries + 1);
                         })
                     }
@@ -870,11 +877,7 @@ impl<E: EthSpec> Discovery<E> {
 
                         // Map each subnet query's min_ttl to the set of ENR's returned for that subnet.
                         queries.iter().for_each(|query| {
-                            let query_str = match query.subnet {
-                                Subnet::Attestation(_) => "attestation",
-                                Subnet::SyncCommittee(_) => "sync_committee",
-                                Subnet::DataColumn(_) => "data_column",
-                            };
+                            let query_str = subnet_query_type(&query.subnet);
 
                             if let Some(v) = metrics::get_int_counter(
                                 &metrics::TOTAL_SUBNET_QUERIES,
@@ -892,10 +895,12 @@ impl<E: EthSpec> Discovery<E> {
                                 self.spec.clone(),
                             );
 
+                            let mut peers_found = 0;
                             r.clone()
                                 .into_iter()
                                 .filter(|enr| subnet_predicate(enr))
                                 .for_each(|enr| {
+                                    peers_found += 1;
                                     if let Some(v) = metrics::get_int_counter(
                                         &metrics::SUBNET_PEERS_FOUND,
                                         &[query_str],
@@ -930,6 +935,16 @@ impl<E: EthSpec> Discovery<E> {
                                         (None, Some(None)) => {} // No-op because this is a duplicate
                                     }
                                 });
+
+                            let result = if peers_found > 0 {
+                                "success"
+                            } else {
+                                "no_peers"
+                            };
+                            metrics::inc_counter_vec(
+                                &metrics::SUBNET_QUERY_RESULTS,
+                                &[query_str, result],
+                            );
                         });
 
                         if mapped_results.is_empty() {
@@ -940,6 +955,12 @@ impl<E: EthSpec> Discovery<E> {
                     }
                     Err(e) => {
                         warn!(self.log,"Grouped subnet discovery query failed"; "subnets_searched_for" => ?subnets_searched_for, "error" => %e);
+                        for query in queries.iter() {
+                            metrics::inc_counter_vec(
+                                &metrics::SUBNET_QUERY_RESULTS,
+                                &[subnet_query_type(&query.subnet), "error"],
+                            );
+                        }
                     }
                 }
             }
@@ -1192,6 +1213,15 @@ impl<E: EthSpec> Discovery<E> {
     }
 }
 
+/// The metrics label for the type of subnet being searched for.
+fn subnet_query_type(subnet: &Subnet) -> &'static str {
+    match subnet {
+        Subnet::Attestation(_) => "attestation",
+        Subnet::SyncCommittee(_) => "sync_committee",
+        Subnet::DataColumn(_) => "data_column",
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -120,7 +120,8 @@ pub use peer_manager::{
     peerdb::client::Client,
     peerdb::score::{PeerAction, ReportSource},
     peerdb::PeerDB,
-    ConnectionDirection, PeerConnectionStatus, PeerInfo, PeerManager, SyncInfo, SyncStatus,
+    ConnectionDirection, ConnectionState, PeerConnectionStatus, PeerInfo, PeerManager, SyncInfo,
+    SyncStatus,
 };
 // pub use service::{load_private_key, Context, Libp2pEvent, Service, NETWORK_KEY_FILENAME};
 pub use service::api_types::{PeerRequestId, Request, Response};
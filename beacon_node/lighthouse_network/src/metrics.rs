@@ -38,6 +38,10 @@ lazy_static! {
         "libp2p_peer_disconnect_event_total",
         "Count of libp2p peer disconnect events"
     );
+    pub static ref INBOUND_CONNECTIONS_RATE_LIMITED: Result<IntCounter> = try_create_int_counter(
+        "libp2p_inbound_connections_rate_limited_total",
+        "Count of inbound connections rejected because the inbound connection rate limit was reached"
+    );
     pub static ref DISCOVERY_BYTES: Result<IntGaugeVec> = try_create_int_gauge_vec(
         "discovery_bytes",
         "The number of bytes sent and received in discovery",
@@ -106,6 +110,12 @@ lazy_static! {
             "Messages that expired waiting to be published on retry to gossipsub per topic kind.",
             &["topic_kind"]
         );
+    pub static ref TRUSTED_MESH_PUBLISHES_PER_TOPIC_KIND: Result<IntCounterVec> =
+        try_create_int_counter_vec(
+            "gossipsub_trusted_mesh_publishes_per_topic_kind",
+            "Messages published to the trusted mesh ahead of the rest of the network",
+            &["topic_kind"]
+        );
     pub static ref GOSSIP_FAILED_LATE_PUBLISH_PER_TOPIC_KIND: Result<IntCounterVec> =
         try_create_int_counter_vec(
             "gossipsub_failed_late_publish_per_topic_kind",
@@ -137,6 +147,12 @@ lazy_static! {
             "Total number of discovery subnet queries",
             &["type"]
         );
+    pub static ref SUBNET_QUERY_RESULTS: Result<IntCounterVec> =
+        try_create_int_counter_vec(
+            "discovery_subnet_query_results_total",
+            "Completed discovery subnet queries, by whether they found peers on the subnet",
+            &["type", "result"]
+        );
 
     /*
      * Peer Reporting
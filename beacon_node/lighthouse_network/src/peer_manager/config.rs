@@ -1,3 +1,6 @@
+use crate::service::TARGET_SUBNET_PEERS;
+use libp2p::{Multiaddr, PeerId};
+
 /// The time in seconds between re-status's peers.
 pub const DEFAULT_STATUS_INTERVAL: u64 = 300;
 
@@ -10,6 +13,12 @@ pub const DEFAULT_PING_INTERVAL_INBOUND: u64 = 20;
 /// Default number of peers to connect to.
 pub const DEFAULT_TARGET_PEERS: usize = 50;
 
+/// Default maximum number of inbound connections accepted per second.
+pub const DEFAULT_INBOUND_CONNECTION_RATE_LIMIT: u32 = 10;
+
+/// Default time in seconds after connecting during which a peer is considered new.
+pub const DEFAULT_NEW_PEER_GRACE_PERIOD: u64 = 300;
+
 /// Configurations for the PeerManager.
 #[derive(Debug)]
 pub struct Config {
@@ -22,6 +31,17 @@ pub struct Config {
     pub quic_enabled: bool,
     /// Target number of peers to connect to.
     pub target_peer_count: usize,
+    /// Target number of peers on each subnet we need.
+    pub target_subnet_peers: usize,
+    /// Peers which are always kept connected, along with the address to dial them on. These peers
+    /// do not count towards the peer limits.
+    pub static_peers: Vec<(PeerId, Multiaddr)>,
+    /// The maximum number of inbound connections accepted per second. A value of `0` disables the
+    /// limit.
+    pub inbound_connection_rate_limit: u32,
+    /// Time in seconds after connecting during which a peer is considered new. When pruning, new
+    /// peers are disconnected before established peers.
+    pub new_peer_grace_period: u64,
 
     /* RPC related configurations */
     /// Time in seconds between status requests sent to peers.
@@ -41,6 +61,10 @@ impl Default for Config {
             metrics_enabled: false,
             quic_enabled: true,
             target_peer_count: DEFAULT_TARGET_PEERS,
+            target_subnet_peers: TARGET_SUBNET_PEERS,
+            static_peers: Vec::new(),
+            inbound_connection_rate_limit: DEFAULT_INBOUND_CONNECTION_RATE_LIMIT,
+            new_peer_grace_period: DEFAULT_NEW_PEER_GRACE_PERIOD,
             status_interval: DEFAULT_STATUS_INTERVAL,
             ping_interval_inbound: DEFAULT_PING_INTERVAL_INBOUND,
             ping_interval_outbound: DEFAULT_PING_INTERVAL_OUTBOUND,
@@ -2,7 +2,6 @@
 
 use crate::discovery::enr_ext::EnrExt;
 use crate::rpc::{GoodbyeReason, MetaData, Protocol, RPCError, RPCResponseErrorCode};
-use crate::service::TARGET_SUBNET_PEERS;
 use crate::{error, metrics, Gossipsub};
 use crate::{NetworkGlobals, PeerId};
 use crate::{Subnet, SubnetDiscovery};
@@ -18,7 +17,7 @@ use std::{
     sync::Arc,
     time::{Duration, Instant},
 };
-use types::{EthSpec, SyncSubnetId};
+use types::{EthSpec, SubnetId, SyncSubnetId};
 
 pub use libp2p::core::Multiaddr;
 pub use libp2p::identity::Keypair;
@@ -29,7 +28,7 @@ pub mod peerdb;
 use crate::peer_manager::peerdb::client::ClientKind;
 use libp2p::multiaddr;
 pub use peerdb::peer_info::{
-    ConnectionDirection, PeerConnectionStatus, PeerConnectionStatus::*, PeerInfo,
+    ConnectionDirection, ConnectionState, PeerConnectionStatus, PeerConnectionStatus::*, PeerInfo,
 };
 use peerdb::score::{PeerAction, ReportSource};
 pub use peerdb::sync_status::{SyncInfo, SyncStatus};
@@ -65,6 +64,11 @@ pub const MIN_OUTBOUND_ONLY_FACTOR: f32 = 0.2;
 /// limit is 55, and we are at 55 peers, the following parameter provisions a few more slots of
 /// dialing priority peers we need for validator duties.
 pub const PRIORITY_PEER_EXCESS: f32 = 0.2;
+/// The time we wait before re-dialing a disconnected static peer. This doubles after each failed
+/// attempt, up to `STATIC_PEER_MAX_BACKOFF`.
+pub const STATIC_PEER_MIN_BACKOFF: Duration = Duration::from_secs(HEARTBEAT_INTERVAL);
+/// The maximum time we wait between attempts to re-dial a disconnected static peer.
+pub const STATIC_PEER_MAX_BACKOFF: Duration = Duration::from_secs(600);
 
 /// The main struct that handles peer's reputation and connection status.
 pub struct PeerManager<E: EthSpec> {
@@ -80,8 +84,19 @@ pub struct PeerManager<E: EthSpec> {
     status_peers: HashSetDelay<PeerId>,
     /// The target number of peers we would like to connect to.
     target_peers: usize,
+    /// The target number of peers we would like on each subnet we need.
+    target_subnet_peers: usize,
     /// Peers queued to be dialed.
     peers_to_dial: Vec<Enr>,
+    /// Peers which are always kept connected and are not counted towards our peer limits.
+    static_peers: HashMap<PeerId, StaticPeer>,
+    /// Static peers queued to be dialed.
+    static_peers_to_dial: Vec<(PeerId, Multiaddr)>,
+    /// Limits the rate at which we accept inbound connections.
+    inbound_connection_limiter: InboundConnectionLimiter,
+    /// The time after connecting during which a peer is considered new and is pruned before
+    /// established peers.
+    new_peer_grace_period: Duration,
     /// The number of temporarily banned peers. This is used to prevent instantaneous
     /// reconnection.
     // NOTE: This just prevents re-connections. The state of the peer is otherwise unaffected. A
@@ -113,6 +128,50 @@ pub struct PeerManager<E: EthSpec> {
     log: slog::Logger,
 }
 
+/// A peer which we always keep connected to, re-dialing it with a backoff when disconnected.
+struct StaticPeer {
+    multiaddr: Multiaddr,
+    /// The time to wait after the next dial attempt before trying again.
+    backoff: Duration,
+    /// The earliest time at which the peer may be dialed.
+    next_dial: Instant,
+}
+
+/// Limits the number of inbound connections accepted in each one second window, so that a flood
+/// of connections cannot churn through our peer slots.
+struct InboundConnectionLimiter {
+    /// The maximum number of connections accepted per window. `0` disables the limit.
+    limit: u32,
+    window_start: Instant,
+    accepted: u32,
+}
+
+impl InboundConnectionLimiter {
+    fn new(limit: u32) -> Self {
+        InboundConnectionLimiter {
+            limit,
+            window_start: Instant::now(),
+            accepted: 0,
+        }
+    }
+
+    /// Returns `true` if a connection may be accepted at `now`, counting it towards the limit.
+    fn allow(&mut self, now: Instant) -> bool {
+        if self.limit == 0 {
+            return true;
+        }
+        if now.saturating_duration_since(self.window_start) >= Duration::from_secs(1) {
+            self.window_start = now;
+            self.accepted = 0;
+        }
+        if self.accepted >= self.limit {
+            return false;
+        }
+        self.accepted += 1;
+        true
+    }
+}
+
 /// The events that the `PeerManager` outputs (requests).
 #[derive(Debug)]
 pub enum PeerManagerEvent {
@@ -151,10 +210,14 @@ impl<E: EthSpec> PeerManager<E> {
             discovery_enabled,
             metrics_enabled,
             target_peer_count,
+            target_subnet_peers,
             status_interval,
             ping_interval_inbound,
             ping_interval_outbound,
             quic_enabled,
+            static_peers,
+            inbound_connection_rate_limit,
+            new_peer_grace_period,
         } = cfg;
 
         // Set up the peer manager heartbeat interval
@@ -164,10 +227,27 @@ impl<E: EthSpec> PeerManager<E> {
             network_globals,
             events: SmallVec::new(),
             peers_to_dial: Default::default(),
+            static_peers: static_peers
+                .into_iter()
+                .map(|(peer_id, multiaddr)| {
+                    let static_peer = StaticPeer {
+                        multiaddr,
+                        backoff: STATIC_PEER_MIN_BACKOFF,
+                        next_dial: Instant::now(),
+                    };
+                    (peer_id, static_peer)
+                })
+                .collect(),
+            static_peers_to_dial: Vec::new(),
+            inbound_connection_limiter: InboundConnectionLimiter::new(
+                inbound_connection_rate_limit,
+            ),
+            new_peer_grace_period: Duration::from_secs(new_peer_grace_period),
             inbound_ping_peers: HashSetDelay::new(Duration::from_secs(ping_interval_inbound)),
             outbound_ping_peers: HashSetDelay::new(Duration::from_secs(ping_interval_outbound)),
             status_peers: HashSetDelay::new(Duration::from_secs(status_interval)),
             target_peers: target_peer_count,
+            target_subnet_peers,
             temporary_banned_peers: LRUTimeCache::new(PEER_RECONNECTION_TIMEOUT),
             sync_committee_subnets: Default::default(),
             heartbeat,
@@ -438,18 +518,51 @@ impl<E: EthSpec> PeerManager<E> {
         self.network_globals.peers.read().is_connected(peer_id)
     }
 
+    /// Returns true if the peer is a static peer, which is always kept connected.
+    pub fn is_static_peer(&self, peer_id: &PeerId) -> bool {
+        self.static_peers.contains_key(peer_id)
+    }
+
+    /// Returns `true` if the peer is a trusted or static peer, neither of which is subject to the
+    /// inbound connection rate limit.
+    fn is_trusted_or_static_peer(&self, peer_id: &PeerId) -> bool {
+        self.is_static_peer(peer_id)
+            || self
+                .network_globals
+                .peers
+                .read()
+                .peer_info(peer_id)
+                .map_or(false, |info| info.is_trusted())
+    }
+
     /// Reports whether the peer limit is reached in which case we stop allowing new incoming
-    /// connections.
+    /// connections. Static peers are not counted towards the limit.
     pub fn peer_limit_reached(&self, count_dialing: bool) -> bool {
+        let static_peers = self.connected_static_peers();
         if count_dialing {
             // This is an incoming connection so limit by the standard max peers
-            self.network_globals.connected_or_dialing_peers() >= self.max_peers()
+            self.network_globals
+                .connected_or_dialing_peers()
+                .saturating_sub(static_peers)
+                >= self.max_peers()
         } else {
             // We dialed this peer, allow up to max_outbound_dialing_peers
-            self.network_globals.connected_peers() >= self.max_outbound_dialing_peers()
+            self.network_globals
+                .connected_peers()
+                .saturating_sub(static_peers)
+                >= self.max_outbound_dialing_peers()
         }
     }
 
+    /// The number of static peers we are currently connected to.
+    fn connected_static_peers(&self) -> usize {
+        let peers = self.network_globals.peers.read();
+        self.static_peers
+            .keys()
+            .filter(|peer_id| peers.is_connected(peer_id))
+            .count()
+    }
+
     /// Updates `PeerInfo` with `identify` information.
     pub fn identify(&mut self, peer_id: &PeerId, info: &IdentifyInfo) {
         if let Some(peer_info) = self.network_globals.peers.write().peer_info_mut(peer_id) {
@@ -840,7 +953,7 @@ impl<E: EthSpec> PeerManager<E> {
                     .read()
                     .good_peers_on_subnet(Subnet::SyncCommittee(*k))
                     .count()
-                    < TARGET_SUBNET_PEERS
+                    < self.target_subnet_peers
                 {
                     Some(SubnetDiscovery {
                         subnet: Subnet::SyncCommittee(*k),
@@ -864,6 +977,38 @@ impl<E: EthSpec> PeerManager<E> {
         }
     }
 
+    /// Run discovery queries for peers on our long-lived attestation subnets, as advertised in our
+    /// metadata, if we have fewer than `target_subnet_peers` on any of them.
+    fn maintain_long_lived_subnet_peers(&mut self) {
+        let attnets = self.network_globals.local_metadata.read().attnets().clone();
+        let subnets_to_discover: Vec<SubnetDiscovery> = {
+            let peers = self.network_globals.peers.read();
+            attnets
+                .iter()
+                .enumerate()
+                .filter(|(_, subscribed)| *subscribed)
+                .map(|(subnet_id, _)| Subnet::Attestation(SubnetId::new(subnet_id as u64)))
+                .filter(|subnet| {
+                    peers.good_peers_on_subnet(*subnet).count() < self.target_subnet_peers
+                })
+                .map(|subnet| SubnetDiscovery {
+                    subnet,
+                    min_ttl: None,
+                })
+                .collect()
+        };
+
+        if !subnets_to_discover.is_empty() {
+            debug!(
+                self.log,
+                "Making subnet queries for maintaining long-lived subnet peers";
+                "subnets" => ?subnets_to_discover.iter().map(|s| s.subnet).collect::<Vec<_>>()
+            );
+            self.events
+                .push(PeerManagerEvent::DiscoverSubnetPeers(subnets_to_discover));
+        }
+    }
+
     /// This function checks the status of our current peers and optionally requests a discovery
     /// query if we need to find more peers to maintain the current number of peers
     fn maintain_peer_count(&mut self, dialing_peers: usize) {
@@ -917,6 +1062,7 @@ impl<E: EthSpec> PeerManager<E> {
     ///     MIN_SYNC_COMMITTEE_PEERS
     ///     number should be set low as an absolute lower bound to maintain peers on the sync
     ///     committees.
+    /// - Do not prune static peers, nor count them towards our target peer count.
     /// - Do not prune trusted peers. NOTE: This means if a user has more trusted peers than the
     ///     excess peer limit, all of the following logic is subverted as we will not prune any peers.
     ///     Also, the more trusted peers a user has, the less room Lighthouse has to efficiently manage
@@ -929,8 +1075,11 @@ impl<E: EthSpec> PeerManager<E> {
     /// 4. Randomly remove peers if all the above are satisfied
     ///
     fn prune_excess_peers(&mut self) {
-        // The current number of connected peers.
-        let connected_peer_count = self.network_globals.connected_peers();
+        // The current number of connected peers, excluding static peers which are never pruned.
+        let connected_peer_count = self
+            .network_globals
+            .connected_peers()
+            .saturating_sub(self.connected_static_peers());
         if connected_peer_count <= self.target_peers {
             // No need to prune peers
             return;
@@ -983,13 +1132,38 @@ impl<E: EthSpec> PeerManager<E> {
         // 1. Look through peers that have the worst score (ignoring non-penalized scored peers).
         prune_peers!(|info: &PeerInfo<E>| { info.score().score() < 0.0 });
 
-        // 2. Attempt to remove peers that are not subscribed to a subnet, if we still need to
+        let grace_period = self.new_peer_grace_period;
+        let is_new = |info: &PeerInfo<E>| {
+            info.connected_duration()
+                .map_or(false, |duration| duration < grace_period)
+        };
+
+        // 2. Remove peers that are still within their grace period and are not subscribed to a
+        //    subnet, so that a flood of new connections cannot evict established peers. New peers
+        //    on subnets are only preferred over established peers on the same subnets in step 4,
+        //    so that we keep the peers our subnets rely on. If all peers are new, there are no
+        //    established peers to protect and the remaining steps decide.
+        if peers_to_prune.len() < connected_peer_count.saturating_sub(self.target_peers) {
+            let has_established_peers = self
+                .network_globals
+                .peers
+                .read()
+                .connected_peers()
+                .any(|(_, info)| !is_new(info));
+            if has_established_peers {
+                prune_peers!(|info: &PeerInfo<E>| {
+                    is_new(info) && !info.has_long_lived_subnet()
+                });
+            }
+        }
+
+        // 3. Attempt to remove peers that are not subscribed to a subnet, if we still need to
         //    prune more.
         if peers_to_prune.len() < connected_peer_count.saturating_sub(self.target_peers) {
             prune_peers!(|info: &PeerInfo<E>| { !info.has_long_lived_subnet() });
         }
 
-        // 3. and 4. Remove peers that are too grouped on any given subnet. If all subnets are
+        // 4. and 5. Remove peers that are too grouped on any given subnet. If all subnets are
         //    uniformly distributed, remove random peers.
         if peers_to_prune.len() < connected_peer_count.saturating_sub(self.target_peers) {
             // Of our connected peers, build a map from subnet_id -> Vec<(PeerId, PeerInfo)>
@@ -1044,9 +1218,12 @@ impl<E: EthSpec> PeerManager<E> {
                     // and the subnet still contains peers
                     if !peers_on_subnet.is_empty() {
                         // Order the peers by the number of subnets they are long-lived
-                        // subscribed too, shuffle equal peers.
+                        // subscribed too, then new peers before established peers, shuffle
+                        // equal peers.
                         peers_on_subnet.shuffle(&mut rand::thread_rng());
-                        peers_on_subnet.sort_by_key(|(_, info)| info.long_lived_subnet_count());
+                        peers_on_subnet.sort_by_key(|(_, info)| {
+                            (info.long_lived_subnet_count(), !is_new(info))
+                        });
 
                         // Try and find a candidate peer to remove from the subnet.
                         // We ignore peers that would put us below our target outbound peers
@@ -1138,6 +1315,22 @@ impl<E: EthSpec> PeerManager<E> {
         }
     }
 
+    /// Queues a dial to each disconnected static peer whose backoff has elapsed.
+    fn maintain_static_peers(&mut self) {
+        let now = Instant::now();
+        let peers = self.network_globals.peers.read();
+        for (peer_id, static_peer) in self.static_peers.iter_mut() {
+            if static_peer.next_dial > now || !peers.should_dial(peer_id) {
+                continue;
+            }
+            debug!(self.log, "Dialing static peer"; "peer_id" => %peer_id, "multiaddr" => %static_peer.multiaddr, "backoff" => ?static_peer.backoff);
+            self.static_peers_to_dial
+                .push((*peer_id, static_peer.multiaddr.clone()));
+            static_peer.next_dial = now + static_peer.backoff;
+            static_peer.backoff = (static_peer.backoff * 2).min(STATIC_PEER_MAX_BACKOFF);
+        }
+    }
+
     /// Unbans any temporarily banned peers that have served their timeout.
     fn unban_temporary_banned_peers(&mut self) {
         for peer_id in self.temporary_banned_peers.remove_expired() {
@@ -1175,6 +1368,12 @@ impl<E: EthSpec> PeerManager<E> {
         // Maintain minimum count for sync committee peers.
         self.maintain_sync_committee_peers();
 
+        // Search for more peers on any of our long-lived subnets which are thin.
+        self.maintain_long_lived_subnet_peers();
+
+        // Re-dial any static peers we have been disconnected from.
+        self.maintain_static_peers();
+
         // Prune any excess peers back to our target in such a way that incentivises good scores and
         // a uniform distribution of subnets.
         self.prune_excess_peers();
@@ -1478,6 +1677,212 @@ mod tests {
         assert_eq!(peer_manager.network_globals.connected_or_dialing_peers(), 3);
     }
 
+    #[tokio::test]
+    async fn test_peer_manager_discovers_long_lived_subnet_peers() {
+        let mut peer_manager = build_peer_manager(3).await;
+        peer_manager
+            .network_globals
+            .local_metadata
+            .write()
+            .attnets_mut()
+            .set(1, true)
+            .unwrap();
+
+        peer_manager.heartbeat();
+
+        let subnets = peer_manager
+            .events
+            .iter()
+            .filter_map(|event| match event {
+                PeerManagerEvent::DiscoverSubnetPeers(subnets) => Some(subnets),
+                _ => None,
+            })
+            .flatten()
+            .map(|discovery| discovery.subnet)
+            .collect::<Vec<_>>();
+        assert_eq!(subnets, vec![Subnet::Attestation(SubnetId::new(1))]);
+    }
+
+    #[tokio::test]
+    async fn test_peer_manager_prunes_new_peers_before_established_peers() {
+        // Connect 2 established peers and then a new peer, with a target of 2 peers. The new peer
+        // is pruned even though it is indistinguishable from the others in every other respect.
+        let mut peer_manager = build_peer_manager(2).await;
+        let established0 = PeerId::random();
+        let established1 = PeerId::random();
+        let new_peer = PeerId::random();
+
+        let established_since = Instant::now()
+            - Duration::from_secs(config::DEFAULT_NEW_PEER_GRACE_PERIOD)
+            - Duration::from_secs(1);
+        for peer in [established0, established1] {
+            peer_manager.inject_connect_ingoing(&peer, "/ip4/0.0.0.0".parse().unwrap(), None);
+            peer_manager
+                .network_globals
+                .peers
+                .write()
+                .peer_info_mut(&peer)
+                .unwrap()
+                .set_connected_since(established_since);
+        }
+        peer_manager.inject_connect_ingoing(&new_peer, "/ip4/0.0.0.0".parse().unwrap(), None);
+        assert_eq!(peer_manager.network_globals.connected_or_dialing_peers(), 3);
+
+        peer_manager.heartbeat();
+
+        assert_eq!(peer_manager.network_globals.connected_or_dialing_peers(), 2);
+        assert!(peer_manager.is_connected(&established0));
+        assert!(peer_manager.is_connected(&established1));
+        assert!(!peer_manager.is_connected(&new_peer));
+    }
+
+    #[tokio::test]
+    async fn test_peer_manager_keeps_new_subnet_peers_over_established_peers() {
+        // Connect 2 established peers and then a new peer on a long-lived subnet, with a target of
+        // 2 peers. The new peer is kept, since we have no other peers on its subnet.
+        let mut peer_manager = build_peer_manager(2).await;
+        let established0 = PeerId::random();
+        let established1 = PeerId::random();
+        let new_peer = PeerId::random();
+
+        let established_since = Instant::now()
+            - Duration::from_secs(config::DEFAULT_NEW_PEER_GRACE_PERIOD)
+            - Duration::from_secs(1);
+        for peer in [established0, established1] {
+            peer_manager.inject_connect_ingoing(&peer, "/ip4/0.0.0.0".parse().unwrap(), None);
+            peer_manager
+                .network_globals
+                .peers
+                .write()
+                .peer_info_mut(&peer)
+                .unwrap()
+                .set_connected_since(established_since);
+        }
+        peer_manager.inject_connect_ingoing(&new_peer, "/ip4/0.0.0.0".parse().unwrap(), None);
+
+        let mut attnets = crate::types::EnrAttestationBitfield::<E>::new();
+        attnets.set(1, true).unwrap();
+        let metadata = crate::rpc::MetaDataV2 {
+            seq_number: 0,
+            attnets,
+            syncnets: Default::default(),
+        };
+        let mut peer_db = peer_manager.network_globals.peers.write();
+        peer_db
+            .peer_info_mut(&new_peer)
+            .unwrap()
+            .set_meta_data(MetaData::V2(metadata));
+        peer_db.add_subscription(&new_peer, Subnet::Attestation(SubnetId::new(1)));
+        drop(peer_db);
+
+        peer_manager.heartbeat();
+
+        assert_eq!(peer_manager.network_globals.connected_or_dialing_peers(), 2);
+        assert!(peer_manager.is_connected(&new_peer));
+        assert!(
+            peer_manager.is_connected(&established0) != peer_manager.is_connected(&established1)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_inbound_connection_rate_limit_exempts_trusted_peers() {
+        use libp2p::swarm::{ConnectionId, NetworkBehaviour};
+
+        let trusted_peer = PeerId::random();
+        let mut peer_manager = build_peer_manager_with_trusted_peers(vec![trusted_peer], 10).await;
+        peer_manager.inbound_connection_limiter = InboundConnectionLimiter::new(1);
+
+        let addr: Multiaddr = "/ip4/0.0.0.0/tcp/9000".parse().unwrap();
+        let mut connect = |peer_id: PeerId| {
+            peer_manager
+                .handle_established_inbound_connection(
+                    ConnectionId::new_unchecked(0),
+                    peer_id,
+                    &addr,
+                    &addr,
+                )
+                .is_ok()
+        };
+
+        assert!(connect(PeerId::random()));
+        // The limit is reached for other peers, but not for the trusted peer.
+        assert!(!connect(PeerId::random()));
+        assert!(connect(trusted_peer));
+    }
+
+    #[test]
+    fn test_inbound_connection_limiter() {
+        let mut limiter = InboundConnectionLimiter::new(2);
+        let now = Instant::now();
+        assert!(limiter.allow(now));
+        assert!(limiter.allow(now));
+        assert!(!limiter.allow(now + Duration::from_millis(500)));
+        // The limit resets in the next window.
+        assert!(limiter.allow(now + Duration::from_secs(1)));
+
+        // A limit of 0 disables rate limiting.
+        let mut limiter = InboundConnectionLimiter::new(0);
+        assert!((0..100).all(|_| limiter.allow(now)));
+    }
+
+    #[tokio::test]
+    async fn test_peer_manager_static_peers() {
+        // With a target of 2 peers, connect 2 ingoing peers and a static peer. The static peer
+        // does not count towards the limits, so no peers are pruned.
+        let peer0 = PeerId::random();
+        let peer1 = PeerId::random();
+        let static_peer = PeerId::random();
+
+        let config = config::Config {
+            target_peer_count: 2,
+            discovery_enabled: false,
+            static_peers: vec![(static_peer, "/ip4/127.0.0.1/tcp/9000".parse().unwrap())],
+            ..Default::default()
+        };
+        let log = build_log(slog::Level::Debug, false);
+        let globals = NetworkGlobals::new_test_globals(vec![static_peer], &log);
+        let mut peer_manager: PeerManager<E> =
+            PeerManager::new(config, Arc::new(globals), &log).unwrap();
+
+        // The static peer is dialed on the first heartbeat.
+        peer_manager.heartbeat();
+        assert_eq!(peer_manager.static_peers_to_dial.len(), 1);
+        peer_manager.static_peers_to_dial.clear();
+
+        peer_manager.inject_connect_ingoing(&peer0, "/ip4/0.0.0.0".parse().unwrap(), None);
+        peer_manager.inject_connect_ingoing(&peer1, "/ip4/0.0.0.0".parse().unwrap(), None);
+        peer_manager.inject_connect_outgoing(
+            &static_peer,
+            "/ip4/127.0.0.1/tcp/9000".parse().unwrap(),
+            None,
+        );
+        assert_eq!(peer_manager.network_globals.connected_peers(), 3);
+        assert!(!peer_manager.peer_limit_reached(false));
+
+        peer_manager.heartbeat();
+        assert_eq!(peer_manager.network_globals.connected_peers(), 3);
+        assert!(peer_manager.is_connected(&static_peer));
+        assert!(peer_manager.static_peers_to_dial.is_empty());
+
+        // Once disconnected, the static peer is re-dialed after its backoff.
+        peer_manager.inject_disconnect(&static_peer);
+        peer_manager.heartbeat();
+        assert!(peer_manager.static_peers_to_dial.is_empty());
+
+        let static_info = peer_manager.static_peers.get_mut(&static_peer).unwrap();
+        assert_eq!(static_info.backoff, STATIC_PEER_MIN_BACKOFF * 2);
+        static_info.next_dial = Instant::now();
+        peer_manager.heartbeat();
+        assert_eq!(
+            peer_manager.static_peers_to_dial,
+            vec![(static_peer, "/ip4/127.0.0.1/tcp/9000".parse().unwrap())]
+        );
+        assert_eq!(
+            peer_manager.static_peers[&static_peer].backoff,
+            STATIC_PEER_MIN_BACKOFF * 4
+        );
+    }
+
     #[tokio::test]
     async fn test_peer_manager_not_enough_outbound_peers_no_panic_during_heartbeat() {
         let mut peer_manager = build_peer_manager(20).await;
@@ -1662,7 +2067,7 @@ mod tests {
             .network_globals
             .peers
             .write()
-            .add_subscription(&peer0, Subnet::Attestation(1.into()));
+            .add_subscription(&peer0, Subnet::Attestation(SubnetId::new(1)));
 
         let mut attnets = crate::types::EnrAttestationBitfield::<E>::new();
         attnets.set(10, true).unwrap();
@@ -2,6 +2,7 @@
 
 use std::net::IpAddr;
 use std::task::{Context, Poll};
+use std::time::Instant;
 
 use futures::StreamExt;
 use libp2p::core::ConnectedPoint;
@@ -18,7 +19,7 @@ use crate::rpc::GoodbyeReason;
 use crate::types::SyncState;
 use crate::{metrics, ClearDialError};
 
-use super::{ConnectingType, PeerManager, PeerManagerEvent};
+use super::{ConnectingType, PeerManager, PeerManagerEvent, STATIC_PEER_MIN_BACKOFF};
 
 impl<E: EthSpec> NetworkBehaviour for PeerManager<E> {
     type ConnectionHandler = ConnectionHandler;
@@ -119,6 +120,16 @@ impl<E: EthSpec> NetworkBehaviour for PeerManager<E> {
             });
         }
 
+        if let Some((peer_id, multiaddr)) = self.static_peers_to_dial.pop() {
+            self.inject_peer_connection(&peer_id, ConnectingType::Dialing, None);
+            return Poll::Ready(ToSwarm::Dial {
+                opts: DialOpts::peer_id(peer_id)
+                    .condition(PeerCondition::Disconnected)
+                    .addresses(vec![multiaddr])
+                    .build(),
+            });
+        }
+
         Poll::Pending
     }
 
@@ -205,6 +216,18 @@ impl<E: EthSpec> NetworkBehaviour for PeerManager<E> {
                 "Connection to peer rejected: peer has a bad score",
             ));
         }
+
+        // The rate limit is checked once the peer is known, so that trusted and static peers can
+        // always reconnect, even during a flood of connections.
+        if !self.is_trusted_or_static_peer(&peer_id)
+            && !self.inbound_connection_limiter.allow(Instant::now())
+        {
+            metrics::inc_counter(&metrics::INBOUND_CONNECTIONS_RATE_LIMITED);
+            return Err(ConnectionDenied::new(format!(
+                "Connection to peer rejected: inbound connection rate limit reached, from {remote_addr}"
+            )));
+        }
+
         Ok(ConnectionHandler)
     }
 
@@ -249,10 +272,15 @@ impl<E: EthSpec> PeerManager<E> {
             self.update_peer_count_metrics();
         }
 
+        if let Some(static_peer) = self.static_peers.get_mut(&peer_id) {
+            static_peer.backoff = STATIC_PEER_MIN_BACKOFF;
+        }
+
         // Count dialing peers in the limit if the peer dialed us.
         let count_dialing = endpoint.is_listener();
-        // Check the connection limits
-        if self.peer_limit_reached(count_dialing)
+        // Check the connection limits, which static peers are exempt from.
+        if !self.is_static_peer(&peer_id)
+            && self.peer_limit_reached(count_dialing)
             && self
                 .network_globals
                 .peers
@@ -1,14 +1,15 @@
 use crate::discovery::CombinedKey;
 use crate::{metrics, multiaddr::Multiaddr, types::Subnet, Enr, Gossipsub, PeerId};
-use peer_info::{ConnectionDirection, PeerConnectionStatus, PeerInfo};
+use peer_info::{ConnectionDirection, ConnectionState, PeerConnectionStatus, PeerInfo};
 use rand::seq::SliceRandom;
 use score::{PeerAction, ReportSource, Score, ScoreState};
 use slog::{crit, debug, error, trace, warn};
 use std::net::IpAddr;
+use std::ops::Bound;
 use std::time::Instant;
 use std::{cmp::Ordering, fmt::Display};
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeSet, HashMap, HashSet},
     fmt::Formatter,
 };
 use sync_status::SyncStatus;
@@ -36,6 +37,9 @@ const DIAL_TIMEOUT: u64 = 15;
 pub struct PeerDB<E: EthSpec> {
     /// The collection of known connected peers, their status and reputation
     peers: HashMap<PeerId, PeerInfo<E>>,
+    /// Index of the peers which have been connected at some point, by connection state and
+    /// direction. Peers which have never been connected have no direction and are not indexed.
+    connection_index: HashMap<(ConnectionState, ConnectionDirection), BTreeSet<PeerId>>,
     /// The number of disconnected nodes in the database.
     disconnected_peers: usize,
     /// Counts banned peers in total and per ip
@@ -59,6 +63,7 @@ impl<E: EthSpec> PeerDB<E> {
             banned_peers_count: BannedPeersCount::default(),
             disable_peer_scoring,
             peers,
+            connection_index: HashMap::new(),
         }
     }
 
@@ -82,6 +87,109 @@ impl<E: EthSpec> PeerDB<E> {
         self.peers.keys()
     }
 
+    /// Returns the number of peers which have been connected at some point and whose connection
+    /// state and direction match the given filters. A filter of `None` matches everything.
+    pub fn count_peers(
+        &self,
+        states: Option<&[ConnectionState]>,
+        directions: Option<&[ConnectionDirection]>,
+    ) -> usize {
+        self.indexed_peers(states, directions)
+            .map(|peers| peers.len())
+            .sum()
+    }
+
+    /// Returns up to `limit` peers which have been connected at some point and whose connection
+    /// state and direction match the given filters, ordered by peer id and starting after `after`.
+    /// A filter of `None` matches everything.
+    pub fn query_peers(
+        &self,
+        states: Option<&[ConnectionState]>,
+        directions: Option<&[ConnectionDirection]>,
+        after: Option<&PeerId>,
+        limit: usize,
+    ) -> Vec<(&PeerId, &PeerInfo<E>)> {
+        let lower_bound = after.map_or(Bound::Unbounded, Bound::Excluded);
+        let mut peer_ids = self
+            .indexed_peers(states, directions)
+            .flat_map(|peers| peers.range((lower_bound, Bound::Unbounded)).take(limit))
+            .collect::<Vec<_>>();
+        peer_ids.sort_unstable();
+        peer_ids.truncate(limit);
+        peer_ids
+            .into_iter()
+            .filter_map(|peer_id| self.peers.get_key_value(peer_id))
+            .collect()
+    }
+
+    /// Returns the sets of indexed peers matching the given filters.
+    fn indexed_peers<'a>(
+        &'a self,
+        states: Option<&'a [ConnectionState]>,
+        directions: Option<&'a [ConnectionDirection]>,
+    ) -> impl Iterator<Item = &'a BTreeSet<PeerId>> {
+        self.connection_index
+            .iter()
+            .filter(move |((state, direction), _)| {
+                states.map_or(true, |states| states.contains(state))
+                    && directions.map_or(true, |directions| directions.contains(direction))
+            })
+            .map(|(_, peers)| peers)
+    }
+
+    /// Returns the key under which the peer is stored in the connection index, if any.
+    fn connection_index_key(info: &PeerInfo<E>) -> Option<(ConnectionState, ConnectionDirection)> {
+        info.connection_direction().map(|direction| {
+            (
+                info.connection_status().connection_state(),
+                direction.clone(),
+            )
+        })
+    }
+
+    /// Moves the peer within the connection index, after its connection state may have changed
+    /// from `old_key`.
+    fn reindex_peer(
+        &mut self,
+        peer_id: &PeerId,
+        old_key: Option<(ConnectionState, ConnectionDirection)>,
+    ) {
+        let new_key = self.peers.get(peer_id).and_then(Self::connection_index_key);
+        if old_key == new_key {
+            return;
+        }
+        if let Some(old_key) = old_key {
+            self.unindex_peer(peer_id, &old_key);
+        }
+        if let Some(new_key) = new_key {
+            self.connection_index
+                .entry(new_key)
+                .or_default()
+                .insert(*peer_id);
+        }
+    }
+
+    fn unindex_peer(&mut self, peer_id: &PeerId, key: &(ConnectionState, ConnectionDirection)) {
+        if let Some(peers) = self.connection_index.get_mut(key) {
+            peers.remove(peer_id);
+            if peers.is_empty() {
+                self.connection_index.remove(key);
+            }
+        }
+    }
+
+    /// Removes a peer from the db and the connection index.
+    fn remove_peer(&mut self, peer_id: &PeerId) {
+        if let Some(key) = self
+            .peers
+            .remove(peer_id)
+            .as_ref()
+            .and_then(Self::connection_index_key)
+        {
+            self.unindex_peer(peer_id, &key);
+        }
+    }
+
     /// Returns a peer's info, if known.
     pub fn peer_info(&self, peer_id: &PeerId) -> Option<&PeerInfo<E>> {
         self.peers.get(peer_id)
@@ -696,6 +804,18 @@ impl<E: EthSpec> PeerDB<E> {
         &mut self,
         peer_id: &PeerId,
         new_state: NewConnectionState,
+    ) -> Option<BanOperation> {
+        let old_key = self.peers.get(peer_id).and_then(Self::connection_index_key);
+        let ban_operation = self.apply_connection_state(peer_id, new_state);
+        self.reindex_peer(peer_id, old_key);
+        ban_operation
+    }
+
+    /// Applies a connection state change to the peer, without updating the connection index.
+    fn apply_connection_state(
+        &mut self,
+        peer_id: &PeerId,
+        new_state: NewConnectionState,
     ) -> Option<BanOperation> {
         let log_ref = &self.log;
         let info = self.peers.entry(*peer_id).or_insert_with(|| {
@@ -1039,7 +1159,7 @@ impl<E: EthSpec> PeerDB<E> {
                 None
             } {
                 debug!(self.log, "Removing old banned peer"; "peer_id" => %to_drop);
-                self.peers.remove(&to_drop);
+                self.remove_peer(&to_drop);
                 unbanned_peers.push((to_drop, unbanned_ips))
             }
         }
@@ -1058,7 +1178,7 @@ impl<E: EthSpec> PeerDB<E> {
                 .map(|(id, _)| *id)
             {
                 debug!(self.log, "Removing old disconnected peer"; "peer_id" => %to_drop, "disconnected_size" => self.disconnected_peers.saturating_sub(1));
-                self.peers.remove(&to_drop);
+                self.remove_peer(&to_drop);
             }
             // If there is no minimum, this is a coding error. For safety we decrease
             // the count to avoid a potential infinite loop.
@@ -1347,6 +1467,68 @@ mod tests {
         assert_eq!(pdb.connected_outbound_only_peers().count(), 1);
     }
 
+    #[test]
+    fn test_query_peers() {
+        let mut pdb = get_db();
+        let inbound = (0..5).map(|_| PeerId::random()).collect::<Vec<_>>();
+        let outbound = (0..5).map(|_| PeerId::random()).collect::<Vec<_>>();
+        // Dialing peers have not been connected, so are not indexed.
+        pdb.dialing_peer(&PeerId::random(), None);
+
+        for peer_id in &inbound {
+            pdb.connect_ingoing(peer_id, "/ip4/0.0.0.0".parse().unwrap(), None);
+        }
+        for peer_id in &outbound {
+            pdb.connect_outgoing(peer_id, "/ip4/0.0.0.0".parse().unwrap(), None);
+        }
+        pdb.notify_disconnecting(&inbound[0], false);
+        pdb.inject_disconnect(&outbound[0]);
+
+        let connected = Some(&[ConnectionState::Connected][..]);
+        let incoming = Some(&[ConnectionDirection::Incoming][..]);
+        assert_eq!(pdb.count_peers(None, None), 10);
+        assert_eq!(pdb.count_peers(connected, None), 8);
+        assert_eq!(pdb.count_peers(connected, incoming), 4);
+        assert_eq!(
+            pdb.count_peers(Some(&[ConnectionState::Disconnected]), None),
+            1
+        );
+        assert_eq!(
+            pdb.count_peers(Some(&[ConnectionState::Disconnecting]), incoming),
+            1
+        );
+
+        // Paging through the results returns every matching peer in order.
+        let mut expected = inbound[1..]
+            .iter()
+            .chain(&outbound[1..])
+            .collect::<Vec<_>>();
+        expected.sort();
+        let mut paged = vec![];
+        let mut after = None;
+        loop {
+            let page = pdb
+                .query_peers(connected, None, after, 3)
+                .into_iter()
+                .map(|(peer_id, _)| peer_id)
+                .collect::<Vec<_>>();
+            if page.is_empty() {
+                break;
+            }
+            after = page.last().copied();
+            paged.extend(page);
+        }
+        assert_eq!(paged, expected);
+
+        // Peers move within the index as their state changes.
+        pdb.inject_disconnect(&inbound[1]);
+        assert_eq!(pdb.count_peers(connected, incoming), 3);
+        assert_eq!(
+            pdb.count_peers(Some(&[ConnectionState::Disconnected]), None),
+            2
+        );
+    }
+
     #[test]
     fn test_disconnected_removed_in_correct_order() {
         let mut pdb = get_db();
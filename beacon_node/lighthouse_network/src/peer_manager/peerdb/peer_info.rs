@@ -11,7 +11,7 @@ use serde::{
 };
 use std::collections::HashSet;
 use std::net::IpAddr;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use strum::AsRefStr;
 use types::EthSpec;
 use PeerConnectionStatus::*;
@@ -49,6 +49,9 @@ pub struct PeerInfo<E: EthSpec> {
     /// Direction of the first connection of the last (or current) connected session with this peer.
     /// None if this peer was never connected.
     connection_direction: Option<ConnectionDirection>,
+    /// The time at which the last (or current) connected session with this peer began.
+    #[serde(skip)]
+    connected_since: Option<Instant>,
     /// The enr of the peer, if known.
     enr: Option<Enr>,
 }
@@ -67,6 +70,7 @@ impl<E: EthSpec> Default for PeerInfo<E> {
             min_ttl: None,
             is_trusted: false,
             connection_direction: None,
+            connected_since: None,
             enr: None,
         }
     }
@@ -123,6 +127,14 @@ impl<E: EthSpec> PeerInfo<E> {
         self.connection_direction.as_ref()
     }
 
+    /// Returns how long the peer has been connected for, if it is connected.
+    pub fn connected_duration(&self) -> Option<Duration> {
+        if !self.is_connected() {
+            return None;
+        }
+        self.connected_since.map(|since| since.elapsed())
+    }
+
     /// Returns the sync status of the peer.
     pub fn sync_status(&self) -> &SyncStatus {
         &self.sync_status
@@ -446,6 +458,7 @@ impl<E: EthSpec> PeerInfo<E> {
                     multiaddr,
                 };
                 self.connection_direction = Some(ConnectionDirection::Incoming);
+                self.connected_since = Some(Instant::now());
             }
         }
     }
@@ -467,10 +480,17 @@ impl<E: EthSpec> PeerInfo<E> {
                     multiaddr,
                 };
                 self.connection_direction = Some(ConnectionDirection::Outgoing);
+                self.connected_since = Some(Instant::now());
             }
         }
     }
 
+    #[cfg(test)]
+    /// Set the time at which the current connected session with the peer began.
+    pub fn set_connected_since(&mut self, since: Instant) {
+        self.connected_since = Some(since);
+    }
+
     #[cfg(test)]
     /// Add an f64 to a non-trusted peer's score abiding by the limits.
     pub fn add_to_score(&mut self, score: f64) {
@@ -486,7 +506,7 @@ impl<E: EthSpec> PeerInfo<E> {
 }
 
 /// Connection Direction of connection.
-#[derive(Debug, Clone, Serialize, AsRefStr)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, AsRefStr)]
 #[strum(serialize_all = "snake_case")]
 pub enum ConnectionDirection {
     /// The connection was established by a peer dialing us.
@@ -532,6 +552,27 @@ pub enum PeerConnectionStatus {
     Unknown,
 }
 
+/// The coarse connection state of a peer, by which peers are indexed in the `PeerDB`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ConnectionState {
+    Connected,
+    Connecting,
+    Disconnected,
+    Disconnecting,
+}
+
+impl PeerConnectionStatus {
+    /// Returns the coarse connection state. Banned peers are considered disconnected.
+    pub fn connection_state(&self) -> ConnectionState {
+        match self {
+            Connected { .. } => ConnectionState::Connected,
+            Dialing { .. } => ConnectionState::Connecting,
+            Disconnecting { .. } => ConnectionState::Disconnecting,
+            Disconnected { .. } | Banned { .. } | Unknown => ConnectionState::Disconnected,
+        }
+    }
+}
+
 /// Serialization for http requests.
 impl Serialize for PeerConnectionStatus {
     fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
@@ -39,7 +39,9 @@ use std::pin::Pin;
 use std::{
     sync::Arc,
     task::{Context, Poll},
+    time::Duration,
 };
+use tokio_util::time::DelayQueue;
 use types::{
     consts::altair::SYNC_COMMITTEE_SUBNET_COUNT, EnrForkId, EthSpec, ForkContext, Slot, SubnetId,
 };
@@ -52,6 +54,7 @@ mod gossip_cache;
 pub mod gossipsub_scoring_parameters;
 pub mod utils;
 /// The number of peers we target per subnet for discovery queries.
+/// The default number of peers we aim to have on each subnet we need.
 pub const TARGET_SUBNET_PEERS: usize = 3;
 
 const MAX_IDENTIFY_ADDRESSES: usize = 10;
@@ -126,6 +129,15 @@ pub struct Network<E: EthSpec> {
     /// The interval for updating gossipsub scores
     update_gossipsub_scores: tokio::time::Interval,
     gossip_cache: GossipCache,
+    /// Peers which locally published blocks and blobs are sent to before the rest of the network.
+    trusted_mesh_peers: Vec<PeerId>,
+    /// The delay between publishing to the `trusted_mesh_peers` and to the rest of the network.
+    trusted_mesh_delay: Duration,
+    /// Messages that have been published to the `trusted_mesh_peers` and are waiting to be
+    /// published to the rest of the network.
+    delayed_publishes: DelayQueue<(GossipTopic, Vec<u8>)>,
+    /// The number of peers we aim to have on each subnet we need.
+    target_subnet_peers: usize,
     /// This node's PeerId.
     pub local_peer_id: PeerId,
     /// Logger for behaviour actions.
@@ -144,14 +156,39 @@ impl<E: EthSpec> Network<E> {
         let config = ctx.config.clone();
         trace!(log, "Libp2p Service starting");
         // initialise the node's ID
-        let local_keypair = utils::load_private_key(&config, &log);
+        let local_keypair = utils::load_network_key(&config, &log)?;
 
-        // Trusted peers will also be marked as explicit in GossipSub.
+        // Static peers are always kept connected. They are re-dialed at the address given, without
+        // the peer id suffix.
+        let static_peers: Vec<(PeerId, Multiaddr)> = config
+            .static_peers
+            .iter()
+            .filter_map(|multiaddr| {
+                let Some(peer_id) = utils::peer_id_from_multiaddr(multiaddr) else {
+                    warn!(log, "Ignoring static peer without a peer id"; "multiaddr" => %multiaddr);
+                    return None;
+                };
+                let mut multiaddr = multiaddr.clone();
+                utils::strip_peer_id(&mut multiaddr);
+                Some((peer_id, multiaddr))
+            })
+            .collect();
+
+        let trusted_mesh_peers: Vec<PeerId> = config
+            .trusted_mesh_peers
+            .iter()
+            .map(|x| PeerId::from(x.clone()))
+            .collect();
+
+        // Trusted peers will also be marked as explicit in GossipSub. Static peers and trusted
+        // mesh peers are trusted too.
         // Cfr. https://github.com/libp2p/specs/blob/master/pubsub/gossipsub/gossipsub-v1.1.md#explicit-peering-agreements
         let trusted_peers: Vec<PeerId> = config
             .trusted_peers
             .iter()
             .map(|x| PeerId::from(x.clone()))
+            .chain(static_peers.iter().map(|(peer_id, _)| *peer_id))
+            .chain(trusted_mesh_peers.iter().copied())
             .collect();
 
         // set up a collection of variables accessible outside of the network crate
@@ -184,6 +221,7 @@ impl<E: EthSpec> Network<E> {
         let gossipsub_config_params = GossipsubConfigParams {
             message_domain_valid_snappy: ctx.chain_spec.message_domain_valid_snappy,
             gossip_max_size: ctx.chain_spec.gossip_max_size as usize,
+            idontwant: !config.disable_gossipsub_idontwant,
         };
         let gs_config = gossipsub_config(
             config.network_load,
@@ -283,10 +321,16 @@ impl<E: EthSpec> Network<E> {
                 .with_peer_score(params, thresholds)
                 .expect("Valid score params and thresholds");
 
-            // Mark trusted peers as explicit.
+            // Mark trusted, static and trusted mesh peers as explicit.
             for explicit_peer in config.trusted_peers.iter() {
                 gossipsub.add_explicit_peer(&PeerId::from(explicit_peer.clone()));
             }
+            for (explicit_peer, _) in static_peers.iter() {
+                gossipsub.add_explicit_peer(explicit_peer);
+            }
+            for explicit_peer in trusted_mesh_peers.iter() {
+                gossipsub.add_explicit_peer(explicit_peer);
+            }
 
             // If we are using metrics, then register which topics we want to make sure to keep
             // track of
@@ -358,26 +402,35 @@ impl<E: EthSpec> Network<E> {
                 quic_enabled: !config.disable_quic_support,
                 metrics_enabled: config.metrics_enabled,
                 target_peer_count: config.target_peers,
+                target_subnet_peers: config.target_subnet_peers,
+                static_peers,
+                inbound_connection_rate_limit: config.inbound_connection_rate_limit,
+                new_peer_grace_period: config.new_peer_grace_period,
                 ..Default::default()
             };
             PeerManager::new(peer_manager_cfg, network_globals.clone(), &log)?
         };
 
         let connection_limits = {
+            // Static peers do not count towards the peer manager's limits, so leave room for them.
+            let static_peers = config.static_peers.len() as u32;
             let limits = libp2p::connection_limits::ConnectionLimits::default()
                 .with_max_pending_incoming(Some(5))
                 .with_max_pending_outgoing(Some(16))
                 .with_max_established_incoming(Some(
                     (config.target_peers as f32
                         * (1.0 + PEER_EXCESS_FACTOR - MIN_OUTBOUND_ONLY_FACTOR))
-                        .ceil() as u32,
+                        .ceil() as u32
+                        + static_peers,
                 ))
                 .with_max_established_outgoing(Some(
-                    (config.target_peers as f32 * (1.0 + PEER_EXCESS_FACTOR)).ceil() as u32,
+                    (config.target_peers as f32 * (1.0 + PEER_EXCESS_FACTOR)).ceil() as u32
+                        + static_peers,
                 ))
                 .with_max_established(Some(
                     (config.target_peers as f32 * (1.0 + PEER_EXCESS_FACTOR + PRIORITY_PEER_EXCESS))
-                        .ceil() as u32,
+                        .ceil() as u32
+                        + static_peers,
                 ))
                 .with_max_established_per_peer(Some(MAX_CONNECTIONS_PER_PEER));
 
@@ -452,6 +505,10 @@ impl<E: EthSpec> Network<E> {
             score_settings,
             update_gossipsub_scores,
             gossip_cache,
+            trusted_mesh_peers,
+            trusted_mesh_delay: config.trusted_mesh_delay,
+            delayed_publishes: DelayQueue::new(),
+            target_subnet_peers: config.target_subnet_peers,
             local_peer_id,
             log,
         };
@@ -777,57 +834,99 @@ impl<E: EthSpec> Network<E> {
     }
 
     /// Publishes a list of messages on the pubsub (gossipsub) behaviour, choosing the encoding.
+    ///
+    /// If trusted mesh peers are configured, blocks and blobs are first sent to those peers only,
+    /// and are published to the rest of the network once the trusted mesh delay has elapsed.
     pub fn publish(&mut self, messages: Vec<PubsubMessage<E>>) {
         for message in messages {
+            let use_trusted_mesh = !self.trusted_mesh_peers.is_empty()
+                && matches!(
+                    message,
+                    PubsubMessage::BeaconBlock(_) | PubsubMessage::BlobSidecar(_)
+                );
             for topic in message.topics(GossipEncoding::default(), self.enr_fork_id.fork_digest) {
                 let message_data = message.encode(GossipEncoding::default());
-                if let Err(e) = self
-                    .gossipsub_mut()
-                    .publish(Topic::from(topic.clone()), message_data.clone())
-                {
-                    match e {
-                        PublishError::Duplicate => {
-                            debug!(
-                                self.log,
-                                "Attempted to publish duplicate message";
-                                "kind" => %topic.kind(),
-                            );
+                if use_trusted_mesh {
+                    match self.swarm.behaviour_mut().gossipsub.publish_to_peers(
+                        Topic::from(topic.clone()),
+                        message_data.clone(),
+                        &self.trusted_mesh_peers,
+                    ) {
+                        Ok(_) => {
+                            if let Some(v) = metrics::get_int_counter(
+                                &metrics::TRUSTED_MESH_PUBLISHES_PER_TOPIC_KIND,
+                                &[topic.kind().as_ref()],
+                            ) {
+                                v.inc()
+                            };
+                            self.delayed_publishes
+                                .insert((topic, message_data), self.trusted_mesh_delay);
+                            continue;
                         }
-                        ref e => {
+                        // Fall back to publishing to the network immediately.
+                        Err(e) => {
                             warn!(
                                 self.log,
-                                "Could not publish message";
+                                "Could not publish message to trusted mesh";
                                 "error" => ?e,
                                 "kind" => %topic.kind(),
                             );
                         }
                     }
+                }
+                self.publish_to_topic(topic, message_data);
+            }
+        }
+    }
 
-                    // add to metrics
-                    match topic.kind() {
-                        GossipKind::Attestation(subnet_id) => {
-                            if let Some(v) = metrics::get_int_gauge(
-                                &metrics::FAILED_ATTESTATION_PUBLISHES_PER_SUBNET,
-                                &[subnet_id.as_ref()],
-                            ) {
-                                v.inc()
-                            };
-                        }
-                        kind => {
-                            if let Some(v) = metrics::get_int_gauge(
-                                &metrics::FAILED_PUBLISHES_PER_MAIN_TOPIC,
-                                &[&format!("{:?}", kind)],
-                            ) {
-                                v.inc()
-                            };
-                        }
-                    }
+    /// Publishes `message_data` on the gossipsub `topic`. If there are insufficient peers, the
+    /// message is cached to be published later.
+    fn publish_to_topic(&mut self, topic: GossipTopic, message_data: Vec<u8>) {
+        if let Err(e) = self
+            .gossipsub_mut()
+            .publish(Topic::from(topic.clone()), message_data.clone())
+        {
+            match e {
+                PublishError::Duplicate => {
+                    debug!(
+                        self.log,
+                        "Attempted to publish duplicate message";
+                        "kind" => %topic.kind(),
+                    );
+                }
+                ref e => {
+                    warn!(
+                        self.log,
+                        "Could not publish message";
+                        "error" => ?e,
+                        "kind" => %topic.kind(),
+                    );
+                }
+            }
 
-                    if let PublishError::InsufficientPeers = e {
-                        self.gossip_cache.insert(topic, message_data);
-                    }
+            // add to metrics
+            match topic.kind() {
+                GossipKind::Attestation(subnet_id) => {
+                    if let Some(v) = metrics::get_int_gauge(
+                        &metrics::FAILED_ATTESTATION_PUBLISHES_PER_SUBNET,
+                        &[subnet_id.as_ref()],
+                    ) {
+                        v.inc()
+                    };
+                }
+                kind => {
+                    if let Some(v) = metrics::get_int_gauge(
+                        &metrics::FAILED_PUBLISHES_PER_MAIN_TOPIC,
+                        &[&format!("{:?}", kind)],
+                    ) {
+                        v.inc()
+                    };
                 }
             }
+
+            if let PublishError::InsufficientPeers = e {
+                self.gossip_cache.insert(topic, message_data);
+            }
         }
     }
 
@@ -1035,14 +1134,14 @@ impl<E: EthSpec> Network<E> {
                     .read()
                     .good_peers_on_subnet(s.subnet)
                     .count();
-                if peers_on_subnet >= TARGET_SUBNET_PEERS {
+                if peers_on_subnet >= self.target_subnet_peers {
                     trace!(
                         self.log,
                         "Discovery query ignored";
                         "subnet" => ?s.subnet,
                         "reason" => "Already connected to desired peers",
                         "connected_peers_on_subnet" => peers_on_subnet,
-                        "target_subnet_peers" => TARGET_SUBNET_PEERS,
+                        "target_subnet_peers" => self.target_subnet_peers,
                     );
                     false
                 // Queue an outgoing connection request to the cached peers that are on `s.subnet_id`.
@@ -1866,6 +1965,14 @@ impl<E: EthSpec> Network<E> {
             this.peer_manager.update_gossipsub_scores(&this.gossipsub);
         }
 
+        // publish messages to the network once they have been sent to the trusted mesh for long
+        // enough. If a trusted mesh peer has already propagated a message back to us, gossipsub
+        // treats it as a duplicate and it is not re-published.
+        while let Poll::Ready(Some(expired)) = self.delayed_publishes.poll_expired(cx) {
+            let (topic, message_data) = expired.into_inner();
+            self.publish_to_topic(topic, message_data);
+        }
+
         // poll the gossipsub cache to clear expired messages
         while let Poll::Ready(Some(result)) = self.gossip_cache.poll_next_unpin(cx) {
             match result {
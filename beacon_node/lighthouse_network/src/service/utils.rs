@@ -10,7 +10,7 @@ use libp2p::core::{multiaddr::Multiaddr, muxing::StreamMuxerBox, transport::Boxe
 use libp2p::identity::{secp256k1, Keypair};
 use libp2p::{core, noise, yamux, PeerId, Transport};
 use prometheus_client::registry::Registry;
-use slog::{debug, warn};
+use slog::{debug, info, warn};
 use ssz::Decode;
 use ssz::Encode;
 use std::collections::HashSet;
@@ -22,6 +22,10 @@ use std::time::Duration;
 use types::{ChainSpec, EnrForkId, EthSpec, ForkContext, SubnetId, SyncSubnetId};
 
 pub const NETWORK_KEY_FILENAME: &str = "key";
+/// The filename of a network key which replaces the current key at the next startup.
+pub const NEXT_NETWORK_KEY_FILENAME: &str = "key.next";
+/// The filename the replaced network key is moved to after a key rotation.
+pub const PREVIOUS_NETWORK_KEY_FILENAME: &str = "key.previous";
 /// The maximum simultaneous libp2p connections per peer.
 pub const MAX_CONNECTIONS_PER_PEER: u32 = 1;
 /// The filename to store our local metadata.
@@ -81,8 +85,6 @@ pub fn build_transport(
     Ok(transport)
 }
 
-// Useful helper functions for debugging. Currently not used in the client.
-#[allow(dead_code)]
 fn keypair_from_hex(hex_bytes: &str) -> error::Result<Keypair> {
     let hex_bytes = if let Some(stripped) = hex_bytes.strip_prefix("0x") {
         stripped.to_string()
@@ -95,7 +97,6 @@ fn keypair_from_hex(hex_bytes: &str) -> error::Result<Keypair> {
         .and_then(keypair_from_bytes)
 }
 
-#[allow(dead_code)]
 fn keypair_from_bytes(mut bytes: Vec<u8>) -> error::Result<Keypair> {
     secp256k1::SecretKey::try_from_bytes(&mut bytes)
         .map(|secret| {
@@ -148,6 +149,87 @@ pub fn load_private_key(config: &NetworkConfig, log: &slog::Logger) -> Keypair {
     local_private_key.into()
 }
 
+/// Loads the network key of the beacon node.
+///
+/// If `config.network_key_file` is set, the key is read from that file and it is an error for it
+/// to be missing or invalid. Otherwise, any key staged by `lighthouse bn rotate-network-key` is
+/// applied and the key is loaded from the network directory.
+pub fn load_network_key(config: &NetworkConfig, log: &slog::Logger) -> Result<Keypair, String> {
+    if let Some(network_key_file) = &config.network_key_file {
+        let keypair = read_network_key_file(network_key_file)?;
+        debug!(log, "Loaded network key from file"; "file" => ?network_key_file);
+        return Ok(keypair);
+    }
+
+    apply_next_network_key(&config.network_dir, log)?;
+    Ok(load_private_key(config, log))
+}
+
+/// Reads a secp256k1 network key from `path`. The key may either be the raw 32 bytes written by
+/// Lighthouse or hex encoded.
+pub fn read_network_key_file(path: &Path) -> Result<Keypair, String> {
+    let bytes = std::fs::read(path)
+        .map_err(|e| format!("Unable to read network key file {:?}: {}", path, e))?;
+    let keypair = if bytes.len() == 32 {
+        keypair_from_bytes(bytes)
+    } else {
+        keypair_from_hex(String::from_utf8_lossy(&bytes).trim())
+    };
+    keypair.map_err(|e| format!("Invalid network key file {:?}: {}", path, e))
+}
+
+/// Generates a new network key and stages it in `network_dir`, to be used from the next time the
+/// beacon node starts. Returns the `PeerId` of the new key.
+///
+/// Any previously staged key is replaced.
+pub fn stage_next_network_key(network_dir: &Path) -> Result<PeerId, String> {
+    let keypair = secp256k1::Keypair::generate();
+    std::fs::create_dir_all(network_dir)
+        .map_err(|e| format!("Unable to create {:?}: {}", network_dir, e))?;
+    let next_key_f = network_dir.join(NEXT_NETWORK_KEY_FILENAME);
+    std::fs::write(&next_key_f, keypair.secret().to_bytes())
+        .map_err(|e| format!("Unable to write network key to {:?}: {}", next_key_f, e))?;
+    Ok(PeerId::from(Keypair::from(keypair).public()))
+}
+
+/// Replaces the network key in `network_dir` with the key staged by `stage_next_network_key`, if
+/// any. The replaced key is kept as `PREVIOUS_NETWORK_KEY_FILENAME`.
+///
+/// Only the identity of the node changes. The ENR is rebuilt for the new node ID and the peers
+/// and routing table persisted in the database are retained.
+pub fn apply_next_network_key(network_dir: &Path, log: &slog::Logger) -> Result<(), String> {
+    let next_key_f = network_dir.join(NEXT_NETWORK_KEY_FILENAME);
+    if !next_key_f.exists() {
+        return Ok(());
+    }
+
+    // Never replace a working key with one that cannot be loaded.
+    let keypair = read_network_key_file(&next_key_f)?;
+
+    let key_f = network_dir.join(NETWORK_KEY_FILENAME);
+    let previous_peer_id = if key_f.exists() {
+        let previous_peer_id = read_network_key_file(&key_f)
+            .ok()
+            .map(|keypair| PeerId::from(keypair.public()));
+        let previous_key_f = network_dir.join(PREVIOUS_NETWORK_KEY_FILENAME);
+        std::fs::rename(&key_f, &previous_key_f)
+            .map_err(|e| format!("Unable to move network key to {:?}: {}", previous_key_f, e))?;
+        previous_peer_id
+    } else {
+        None
+    };
+    std::fs::rename(&next_key_f, &key_f)
+        .map_err(|e| format!("Unable to move network key to {:?}: {}", key_f, e))?;
+
+    info!(
+        log,
+        "Rotated network key";
+        "previous_peer_id" => ?previous_peer_id,
+        "peer_id" => %PeerId::from(keypair.public()),
+    );
+    Ok(())
+}
+
 /// Generate authenticated XX Noise config from identity keys
 fn generate_noise_config(identity_keypair: &Keypair) -> noise::Config {
     noise::Config::new(identity_keypair).expect("signing can fail only once during starting a node")
@@ -164,6 +246,14 @@ pub fn strip_peer_id(addr: &mut Multiaddr) {
     }
 }
 
+/// Returns the peer id of a multiaddr that ends with one.
+pub fn peer_id_from_multiaddr(addr: &Multiaddr) -> Option<PeerId> {
+    match addr.iter().last() {
+        Some(Protocol::P2p(peer_id)) => Some(peer_id),
+        _ => None,
+    }
+}
+
 /// Load metadata from persisted file. Return default metadata if loading fails.
 pub fn load_or_build_metadata<E: EthSpec>(
     network_dir: &std::path::Path,
@@ -288,3 +378,61 @@ pub(crate) fn save_metadata_to_disk<E: EthSpec>(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn rotate_network_key() {
+        let log = logging::test_logger();
+        let dir = tempdir().unwrap();
+        let config = NetworkConfig {
+            network_dir: dir.path().to_path_buf(),
+            ..NetworkConfig::default()
+        };
+
+        let original = load_network_key(&config, &log).unwrap();
+        assert_eq!(
+            load_network_key(&config, &log).unwrap().public(),
+            original.public()
+        );
+
+        let next_peer_id = stage_next_network_key(dir.path()).unwrap();
+        let rotated = load_network_key(&config, &log).unwrap();
+        assert_eq!(PeerId::from(rotated.public()), next_peer_id);
+        assert!(!dir.path().join(NEXT_NETWORK_KEY_FILENAME).exists());
+
+        let previous =
+            read_network_key_file(&dir.path().join(PREVIOUS_NETWORK_KEY_FILENAME)).unwrap();
+        assert_eq!(previous.public(), original.public());
+    }
+
+    #[test]
+    fn pinned_network_key() {
+        let log = logging::test_logger();
+        let dir = tempdir().unwrap();
+        let keypair = secp256k1::Keypair::generate();
+        let key_file = dir.path().join("pinned");
+        std::fs::write(&key_file, hex::encode(keypair.secret().to_bytes())).unwrap();
+
+        let config = NetworkConfig {
+            network_dir: dir.path().join("network"),
+            network_key_file: Some(key_file.clone()),
+            ..NetworkConfig::default()
+        };
+        let loaded = load_network_key(&config, &log).unwrap();
+        assert_eq!(loaded.public(), Keypair::from(keypair).public());
+
+        // A staged key does not override the pinned key.
+        stage_next_network_key(&config.network_dir).unwrap();
+        assert_eq!(
+            load_network_key(&config, &log).unwrap().public(),
+            loaded.public()
+        );
+
+        std::fs::write(&key_file, "not a key").unwrap();
+        assert!(load_network_key(&config, &log).is_err());
+    }
+}
@@ -1,13 +1,16 @@
 //! A collection of variables that are accessible outside of the network thread itself.
 use crate::peer_manager::peerdb::PeerDB;
 use crate::rpc::{MetaData, MetaDataV2};
-use crate::types::{BackFillState, SyncState};
+use crate::types::{
+    gossip_rejections::GossipRejectionTracker, propagation::PropagationTracker, BackFillState,
+    SyncState,
+};
 use crate::Client;
 use crate::EnrExt;
 use crate::{Enr, GossipTopic, Multiaddr, PeerId};
 use parking_lot::RwLock;
 use std::collections::HashSet;
-use types::EthSpec;
+use types::{ChainSpec, ColumnIndex, DataColumnSubnetId, EthSpec};
 
 pub struct NetworkGlobals<E: EthSpec> {
     /// The current local ENR.
@@ -26,6 +29,10 @@ pub struct NetworkGlobals<E: EthSpec> {
     pub sync_state: RwLock<SyncState>,
     /// The current state of the backfill sync.
     pub backfill_state: RwLock<BackFillState>,
+    /// How quickly each peer propagates blocks and aggregates.
+    pub propagation: RwLock<PropagationTracker>,
+    /// Why gossip attestations from each peer have failed verification.
+    pub gossip_rejections: GossipRejectionTracker,
 }
 
 impl<E: EthSpec> NetworkGlobals<E> {
@@ -45,6 +52,8 @@ impl<E: EthSpec> NetworkGlobals<E> {
             gossipsub_subscriptions: RwLock::new(HashSet::new()),
             sync_state: RwLock::new(SyncState::Stalled),
             backfill_state: RwLock::new(BackFillState::NotRequired),
+            propagation: RwLock::new(PropagationTracker::default()),
+            gossip_rejections: GossipRejectionTracker::default(),
         }
     }
 
@@ -103,6 +112,25 @@ impl<E: EthSpec> NetworkGlobals<E> {
             .unwrap_or_default()
     }
 
+    /// Returns the data column subnets that this node must custody, as derived from its node ID.
+    pub fn custody_subnets(&self, spec: &ChainSpec) -> impl Iterator<Item = DataColumnSubnetId> {
+        DataColumnSubnetId::compute_custody_subnets::<E>(
+            self.local_enr().node_id().raw().into(),
+            spec.custody_requirement,
+            spec,
+        )
+    }
+
+    /// Returns the sorted list of data columns that this node must custody.
+    pub fn custody_columns(&self, spec: &ChainSpec) -> Vec<ColumnIndex> {
+        DataColumnSubnetId::compute_custody_columns::<E>(
+            self.local_enr().node_id().raw().into(),
+            spec.custody_requirement,
+            spec,
+        )
+        .collect()
+    }
+
     /// Updates the syncing state of the node.
     ///
     /// The old state is returned
@@ -0,0 +1,328 @@
+//! Counts the reasons that gossip attestations fail verification, per epoch and per peer.
+//!
+//! Failures which come mostly from a few peers suggest those peers are buggy, whereas failures
+//! spread across all peers (e.g. future slots or invalid signatures) suggest a problem with the
+//! local node's clock or shufflings.
+//!
+//! Rejections are counted from many beacon processor workers at once, so the counts are sharded
+//! by peer, each shard having its own lock. Per-peer counts are rolled over at epoch boundaries,
+//! so that they only cover the current and previous epochs.
+use crate::peer_manager::peerdb::client::ClientKind;
+use crate::PeerId;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::{Entry, RandomState};
+use std::collections::{BTreeMap, HashMap};
+use std::hash::BuildHasher;
+use types::Epoch;
+
+/// The number of most recent epochs to track.
+const MAX_TRACKED_EPOCHS: usize = 8;
+
+/// The number of shards, each of which tracks the peers whose IDs hash to it.
+const SHARDS: usize = 16;
+
+/// The maximum number of peers to track per epoch. Rejections from any further peers are only
+/// counted in the epoch totals.
+const MAX_TRACKED_PEERS: usize = 1_024;
+const MAX_TRACKED_PEERS_PER_SHARD: usize = MAX_TRACKED_PEERS / SHARDS;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RejectionCounts {
+    /// The total number of rejections.
+    pub total: u64,
+    /// The number of rejections for each reason.
+    pub reasons: BTreeMap<String, u64>,
+}
+
+impl RejectionCounts {
+    fn observe(&mut self, reason: &str) {
+        self.observe_many(reason, 1);
+    }
+
+    fn observe_many(&mut self, reason: &str, count: u64) {
+        self.total = self.total.saturating_add(count);
+        if let Some(existing) = self.reasons.get_mut(reason) {
+            *existing = existing.saturating_add(count);
+        } else {
+            self.reasons.insert(reason.to_string(), count);
+        }
+    }
+
+    fn merge(&mut self, other: &RejectionCounts) {
+        for (reason, count) in &other.reasons {
+            self.observe_many(reason, *count);
+        }
+    }
+}
+
+/// The rejection counts of a single peer.
+#[derive(Debug, Clone)]
+pub struct PeerRejectionCounts {
+    /// The client the peer was running when it was first counted.
+    pub client: ClientKind,
+    pub counts: RejectionCounts,
+}
+
+/// The counts of the peers whose IDs hash to a shard.
+#[derive(Default)]
+struct Shard {
+    epochs: BTreeMap<Epoch, RejectionCounts>,
+    /// The epoch of `current_peers`.
+    peers_epoch: Epoch,
+    current_peers: HashMap<PeerId, PeerRejectionCounts>,
+    /// The counts of the epoch prior to `peers_epoch`.
+    previous_peers: HashMap<PeerId, PeerRejectionCounts>,
+}
+
+impl Shard {
+    /// Returns the per-peer counts for `epoch`, rolling them over if `epoch` is a new epoch, or
+    /// `None` if per-peer counts are no longer held for `epoch`.
+    fn peers_mut(&mut self, epoch: Epoch) -> Option<&mut HashMap<PeerId, PeerRejectionCounts>> {
+        if epoch > self.peers_epoch {
+            if epoch == self.peers_epoch + 1 {
+                self.previous_peers = std::mem::take(&mut self.current_peers);
+            } else {
+                self.previous_peers.clear();
+                self.current_peers.clear();
+            }
+            self.peers_epoch = epoch;
+        }
+
+        if epoch == self.peers_epoch {
+            Some(&mut self.current_peers)
+        } else if epoch + 1 == self.peers_epoch {
+            Some(&mut self.previous_peers)
+        } else {
+            None
+        }
+    }
+}
+
+/// Rejection counts for recent epochs, and for each peer over the current and previous epochs.
+pub struct GossipRejectionTracker {
+    shards: [Mutex<Shard>; SHARDS],
+    hasher: RandomState,
+}
+
+impl Default for GossipRejectionTracker {
+    fn default() -> Self {
+        Self {
+            shards: std::array::from_fn(|_| Mutex::default()),
+            hasher: RandomState::new(),
+        }
+    }
+}
+
+impl GossipRejectionTracker {
+    /// Record that a message from `peer_id` was rejected for `reason` during `epoch`.
+    ///
+    /// `client` is only called the first time the peer is counted in an epoch. Returns the client
+    /// of the peer.
+    pub fn observe(
+        &self,
+        peer_id: PeerId,
+        epoch: Epoch,
+        reason: &str,
+        client: impl FnOnce() -> ClientKind,
+    ) -> ClientKind {
+        let mut shard = self.shard(&peer_id).lock();
+
+        shard.epochs.entry(epoch).or_default().observe(reason);
+        while shard.epochs.len() > MAX_TRACKED_EPOCHS {
+            shard.epochs.pop_first();
+        }
+
+        let Some(peers) = shard.peers_mut(epoch) else {
+            return client();
+        };
+        let num_peers = peers.len();
+        let counts = match peers.entry(peer_id) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) if num_peers < MAX_TRACKED_PEERS_PER_SHARD => {
+                entry.insert(PeerRejectionCounts {
+                    client: client(),
+                    counts: RejectionCounts::default(),
+                })
+            }
+            Entry::Vacant(_) => return client(),
+        };
+        counts.counts.observe(reason);
+        counts.client
+    }
+
+    /// Returns the rejection counts of each tracked epoch, oldest first.
+    pub fn epochs(&self) -> Vec<(Epoch, RejectionCounts)> {
+        let mut epochs = BTreeMap::<Epoch, RejectionCounts>::new();
+        for shard in &self.shards {
+            for (epoch, counts) in &shard.lock().epochs {
+                epochs.entry(*epoch).or_default().merge(counts);
+            }
+        }
+
+        // Shards may hold different epochs, depending on when each was last updated.
+        let skip = epochs.len().saturating_sub(MAX_TRACKED_EPOCHS);
+        epochs.into_iter().skip(skip).collect()
+    }
+
+    /// Returns the rejection counts of each peer over the current and previous epochs.
+    pub fn peers(&self) -> Vec<(PeerId, PeerRejectionCounts)> {
+        let latest_epoch = self
+            .shards
+            .iter()
+            .map(|shard| shard.lock().peers_epoch)
+            .max()
+            .unwrap_or_default();
+
+        let mut peers = HashMap::<PeerId, PeerRejectionCounts>::new();
+        for shard in &self.shards {
+            let shard = shard.lock();
+            let epoch_peers = [
+                (shard.peers_epoch, &shard.current_peers),
+                (
+                    shard.peers_epoch.saturating_sub(1u64),
+                    &shard.previous_peers,
+                ),
+            ];
+            for (epoch, epoch_peers) in epoch_peers {
+                // Skip the counts of shards which haven't been updated since an epoch boundary.
+                if epoch + 1 < latest_epoch {
+                    continue;
+                }
+                for (peer_id, peer_counts) in epoch_peers {
+                    peers
+                        .entry(*peer_id)
+                        .or_insert_with(|| PeerRejectionCounts {
+                            client: peer_counts.client,
+                            counts: RejectionCounts::default(),
+                        })
+                        .counts
+                        .merge(&peer_counts.counts);
+                }
+            }
+        }
+        peers.into_iter().collect()
+    }
+
+    /// Returns the rejection counts of `peer_id` over the current and previous epochs, if any.
+    pub fn peer(&self, peer_id: &PeerId) -> Option<PeerRejectionCounts> {
+        self.peers()
+            .into_iter()
+            .find_map(|(id, counts)| (id == *peer_id).then_some(counts))
+    }
+
+    fn shard(&self, peer_id: &PeerId) -> &Mutex<Shard> {
+        &self.shards[self.hasher.hash_one(peer_id) as usize % SHARDS]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn observe(tracker: &GossipRejectionTracker, peer_id: PeerId, epoch: u64, reason: &str) {
+        tracker.observe(peer_id, Epoch::new(epoch), reason, || ClientKind::Unknown);
+    }
+
+    #[test]
+    fn counts_reasons_per_epoch_and_peer() {
+        let tracker = GossipRejectionTracker::default();
+        let buggy = PeerId::random();
+        let honest = PeerId::random();
+
+        observe(&tracker, buggy, 1, "InvalidSignature");
+        observe(&tracker, buggy, 1, "InvalidSignature");
+        observe(&tracker, honest, 1, "FutureSlot");
+        observe(&tracker, buggy, 2, "InvalidSignature");
+
+        let epochs = tracker.epochs();
+        assert_eq!(epochs.len(), 2);
+        assert_eq!(epochs[0].0, Epoch::new(1));
+        assert_eq!(epochs[0].1.total, 3);
+        assert_eq!(epochs[0].1.reasons["InvalidSignature"], 2);
+        assert_eq!(epochs[0].1.reasons["FutureSlot"], 1);
+        assert_eq!(epochs[1].1.total, 1);
+
+        let buggy_counts = tracker.peer(&buggy).unwrap().counts;
+        assert_eq!(buggy_counts.total, 3);
+        assert_eq!(buggy_counts.reasons["InvalidSignature"], 3);
+        assert!(!buggy_counts.reasons.contains_key("FutureSlot"));
+        assert_eq!(tracker.peer(&honest).unwrap().counts.total, 1);
+        assert!(tracker.peer(&PeerId::random()).is_none());
+    }
+
+    #[test]
+    fn prunes_old_epochs() {
+        let tracker = GossipRejectionTracker::default();
+        let peer_id = PeerId::random();
+
+        for epoch in 0..MAX_TRACKED_EPOCHS as u64 + 2 {
+            observe(&tracker, peer_id, epoch, "PastSlot");
+        }
+
+        let epochs = tracker
+            .epochs()
+            .into_iter()
+            .map(|(epoch, _)| epoch)
+            .collect::<Vec<_>>();
+        assert_eq!(epochs.len(), MAX_TRACKED_EPOCHS);
+        assert_eq!(epochs[0], Epoch::new(2));
+    }
+
+    #[test]
+    fn rolls_over_peer_counts_at_epoch_boundaries() {
+        let tracker = GossipRejectionTracker::default();
+        let peer_id = PeerId::random();
+
+        observe(&tracker, peer_id, 1, "PastSlot");
+        observe(&tracker, peer_id, 2, "PastSlot");
+        observe(&tracker, peer_id, 2, "PastSlot");
+        // The previous epoch is still counted.
+        assert_eq!(tracker.peer(&peer_id).unwrap().counts.total, 3);
+
+        // Late rejections for the previous epoch are counted, but older ones aren't.
+        observe(&tracker, peer_id, 1, "PastSlot");
+        observe(&tracker, peer_id, 0, "PastSlot");
+        assert_eq!(tracker.peer(&peer_id).unwrap().counts.total, 4);
+
+        observe(&tracker, peer_id, 3, "PastSlot");
+        assert_eq!(tracker.peer(&peer_id).unwrap().counts.total, 3);
+
+        // Skipping an epoch drops all of the peer's previous counts.
+        observe(&tracker, peer_id, 5, "PastSlot");
+        assert_eq!(tracker.peer(&peer_id).unwrap().counts.total, 1);
+
+        // Peers which haven't been rejected since are no longer reported.
+        let other_peer = (0..)
+            .map(|_| PeerId::random())
+            .find(|other| !std::ptr::eq(tracker.shard(other), tracker.shard(&peer_id)))
+            .unwrap();
+        observe(&tracker, other_peer, 7, "PastSlot");
+        assert!(tracker.peer(&peer_id).is_none());
+        assert_eq!(tracker.peer(&other_peer).unwrap().counts.total, 1);
+
+        // Epoch totals are unaffected.
+        let total = tracker
+            .epochs()
+            .iter()
+            .map(|(_, counts)| counts.total)
+            .sum::<u64>();
+        assert_eq!(total, 8);
+    }
+
+    #[test]
+    fn limits_tracked_peers_per_epoch() {
+        let tracker = GossipRejectionTracker::default();
+
+        for _ in 0..MAX_TRACKED_PEERS * 2 {
+            observe(&tracker, PeerId::random(), 0, "PastSlot");
+        }
+        assert!(tracker.peers().len() <= MAX_TRACKED_PEERS);
+        assert_eq!(tracker.epochs()[0].1.total, MAX_TRACKED_PEERS as u64 * 2);
+
+        // The limit applies per epoch.
+        let peer_id = PeerId::random();
+        observe(&tracker, peer_id, 1, "PastSlot");
+        assert!(tracker.peer(&peer_id).is_some());
+    }
+}
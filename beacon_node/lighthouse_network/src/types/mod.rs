@@ -1,5 +1,7 @@
 pub mod error;
 mod globals;
+pub mod gossip_rejections;
+pub mod propagation;
 mod pubsub;
 mod subnet;
 mod sync_state;
@@ -0,0 +1,216 @@
+//! Tracks how quickly each peer propagates blocks and aggregates over gossip.
+//!
+//! For each peer, the delay between the start of a message's slot and its arrival from that peer
+//! is recorded as a moving average. The averages are combined into a propagation score between
+//! `0` and `1`, which can be used to choose better peers and to diagnose topology issues.
+use crate::PeerId;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// The maximum number of peers to track. Once reached, the peer which was updated least recently
+/// is forgotten.
+const MAX_TRACKED_PEERS: usize = 1_024;
+
+/// The weight given to each new delay in the moving average.
+const MOVING_AVERAGE_WEIGHT: f64 = 0.1;
+
+/// The kind of gossip message whose propagation is being measured.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PropagationKind {
+    Block,
+    Aggregate,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PropagationStats {
+    /// The number of messages received from the peer.
+    pub count: u64,
+    /// The exponential moving average of the delay after the start of the slot, in milliseconds.
+    pub average_delay_ms: f64,
+    pub min_delay_ms: u64,
+    pub max_delay_ms: u64,
+}
+
+impl PropagationStats {
+    fn observe(&mut self, delay: Duration) {
+        let delay_ms = delay.as_millis() as u64;
+        if self.count == 0 {
+            self.average_delay_ms = delay_ms as f64;
+            self.min_delay_ms = delay_ms;
+            self.max_delay_ms = delay_ms;
+        } else {
+            self.average_delay_ms +=
+                MOVING_AVERAGE_WEIGHT * (delay_ms as f64 - self.average_delay_ms);
+            self.min_delay_ms = self.min_delay_ms.min(delay_ms);
+            self.max_delay_ms = self.max_delay_ms.max(delay_ms);
+        }
+        self.count = self.count.saturating_add(1);
+    }
+
+    /// Returns a score between `0` and `1`, where `1` means messages arrive as soon as they can be
+    /// produced and `0` means they arrive `window` or more after that.
+    ///
+    /// Returns `None` if no messages have been received.
+    fn score(&self, production_delay: Duration, window: Duration) -> Option<f64> {
+        if self.count == 0 || window.is_zero() {
+            return None;
+        }
+        let late_ms = self.average_delay_ms - production_delay.as_millis() as f64;
+        Some((1.0 - late_ms / window.as_millis() as f64).clamp(0.0, 1.0))
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PeerPropagation {
+    pub blocks: PropagationStats,
+    pub aggregates: PropagationStats,
+}
+
+impl PeerPropagation {
+    /// Returns the propagation score of the peer, weighted by the number of each kind of message
+    /// received.
+    ///
+    /// Blocks are expected at the start of the slot and aggregates two thirds of the way through
+    /// it. Either scores `0` if it arrives a third of a slot or more after it is expected, since it
+    /// is then too late to be attested to or included in the next block, respectively.
+    pub fn score(&self, slot_duration: Duration) -> Option<f64> {
+        let window = slot_duration / 3;
+        let scores = [
+            (self.blocks.score(Duration::ZERO, window), self.blocks.count),
+            (
+                self.aggregates.score(slot_duration * 2 / 3, window),
+                self.aggregates.count,
+            ),
+        ];
+
+        let (total, count) = scores
+            .iter()
+            .filter_map(|(score, count)| score.map(|score| (score * *count as f64, *count)))
+            .fold((0.0, 0), |(total, n), (score, count)| {
+                (total + score, n + count)
+            });
+        (count > 0).then(|| total / count as f64)
+    }
+}
+
+/// Propagation statistics for recently active peers.
+#[derive(Default)]
+pub struct PropagationTracker {
+    /// The statistics of each peer, along with the number of the update which last changed them.
+    peers: HashMap<PeerId, (u64, PeerPropagation)>,
+    updates: u64,
+}
+
+impl PropagationTracker {
+    /// Record that a message of `kind` arrived from `peer_id` `delay` after the start of its slot.
+    pub fn observe(&mut self, peer_id: PeerId, kind: PropagationKind, delay: Duration) {
+        if !self.peers.contains_key(&peer_id) && self.peers.len() >= MAX_TRACKED_PEERS {
+            if let Some(oldest) = self
+                .peers
+                .iter()
+                .min_by_key(|(_, (updated, _))| *updated)
+                .map(|(peer_id, _)| *peer_id)
+            {
+                self.peers.remove(&oldest);
+            }
+        }
+
+        self.updates = self.updates.wrapping_add(1);
+        let (updated, propagation) = self.peers.entry(peer_id).or_default();
+        *updated = self.updates;
+        match kind {
+            PropagationKind::Block => propagation.blocks.observe(delay),
+            PropagationKind::Aggregate => propagation.aggregates.observe(delay),
+        }
+    }
+
+    /// Returns the propagation statistics of each tracked peer.
+    pub fn peers(&self) -> impl Iterator<Item = (&PeerId, &PeerPropagation)> {
+        self.peers
+            .iter()
+            .map(|(peer_id, (_, propagation))| (peer_id, propagation))
+    }
+
+    /// Returns the propagation statistics of `peer_id`, if any.
+    pub fn peer(&self, peer_id: &PeerId) -> Option<&PeerPropagation> {
+        self.peers.get(peer_id).map(|(_, propagation)| propagation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SLOT_DURATION: Duration = Duration::from_secs(12);
+
+    #[test]
+    fn records_delays() {
+        let mut tracker = PropagationTracker::default();
+        let peer_id = PeerId::random();
+
+        tracker.observe(
+            peer_id,
+            PropagationKind::Block,
+            Duration::from_millis(1_000),
+        );
+        tracker.observe(
+            peer_id,
+            PropagationKind::Block,
+            Duration::from_millis(2_000),
+        );
+        tracker.observe(
+            peer_id,
+            PropagationKind::Aggregate,
+            Duration::from_millis(8_500),
+        );
+
+        let propagation = tracker.peer(&peer_id).unwrap();
+        assert_eq!(propagation.blocks.count, 2);
+        assert_eq!(propagation.blocks.average_delay_ms, 1_100.0);
+        assert_eq!(propagation.blocks.min_delay_ms, 1_000);
+        assert_eq!(propagation.blocks.max_delay_ms, 2_000);
+        assert_eq!(propagation.aggregates.count, 1);
+        assert_eq!(propagation.aggregates.average_delay_ms, 8_500.0);
+        assert!(tracker.peer(&PeerId::random()).is_none());
+    }
+
+    #[test]
+    fn scores_peers() {
+        let mut tracker = PropagationTracker::default();
+        let fast = PeerId::random();
+        let slow = PeerId::random();
+        let silent = PeerId::random();
+
+        tracker.observe(fast, PropagationKind::Block, Duration::ZERO);
+        tracker.observe(fast, PropagationKind::Aggregate, SLOT_DURATION * 2 / 3);
+        tracker.observe(slow, PropagationKind::Block, Duration::from_secs(2));
+        tracker.observe(slow, PropagationKind::Aggregate, SLOT_DURATION);
+
+        assert_eq!(tracker.peer(&fast).unwrap().score(SLOT_DURATION), Some(1.0));
+        // The block is half way through its window and the aggregate is at the end of its window.
+        assert_eq!(
+            tracker.peer(&slow).unwrap().score(SLOT_DURATION),
+            Some(0.25)
+        );
+        assert_eq!(PeerPropagation::default().score(SLOT_DURATION), None);
+        assert!(tracker.peer(&silent).is_none());
+    }
+
+    #[test]
+    fn evicts_least_recently_updated_peer() {
+        let mut tracker = PropagationTracker::default();
+        let first = PeerId::random();
+
+        tracker.observe(first, PropagationKind::Block, Duration::ZERO);
+        for _ in 1..MAX_TRACKED_PEERS {
+            tracker.observe(PeerId::random(), PropagationKind::Block, Duration::ZERO);
+        }
+        assert_eq!(tracker.peers().count(), MAX_TRACKED_PEERS);
+        assert!(tracker.peer(&first).is_some());
+
+        tracker.observe(PeerId::random(), PropagationKind::Block, Duration::ZERO);
+        assert_eq!(tracker.peers().count(), MAX_TRACKED_PEERS);
+        assert!(tracker.peer(&first).is_none());
+    }
+}
@@ -0,0 +1,226 @@
+//! Keeps the routing table seeded with boot nodes fetched from a remote source.
+//!
+//! The boot nodes built into each network configuration rotate over time, which can leave a
+//! long-running node without working boot nodes. When `--boot-nodes-url` is set, the list of boot
+//! node ENRs is fetched from that URL at startup and periodically thereafter. The built-in boot
+//! nodes are always used as well, so a failed fetch falls back to them.
+//!
+//! The list must be signed with the BLS key configured with `--boot-nodes-public-key`, so that a
+//! compromised host can't point the node at boot nodes of its choosing. The signature is over the
+//! SHA256 hash of the list, and is fetched as a 0x-prefixed hex string from the list's URL with
+//! `.sig` appended. Every fetched ENR must also carry a valid signature and, if an allowlist is
+//! configured, must have one of the allowed node IDs.
+
+use crate::service::NetworkMessage;
+use lighthouse_network::discv5::enr::NodeId;
+use lighthouse_network::Enr;
+use slog::{debug, info, warn, Logger};
+use std::str::FromStr;
+use std::time::Duration;
+use task_executor::TaskExecutor;
+use tokio::sync::mpsc;
+use types::{EthSpec, Hash256, PublicKey, Signature};
+
+/// The interval between fetches of the boot node list.
+pub const BOOT_NODES_REFRESH_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// The maximum time to wait for the boot node list to be downloaded.
+const BOOT_NODES_FETCH_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Spawns a task which fetches the boot node list from `url` and sends it to the network service,
+/// immediately and then every `BOOT_NODES_REFRESH_INTERVAL`.
+///
+/// Lists which aren't signed by `public_key` are ignored.
+pub fn spawn_boot_nodes_refresh<E: EthSpec>(
+    url: String,
+    public_key: PublicKey,
+    allowlist: Vec<[u8; 32]>,
+    network_send: mpsc::UnboundedSender<NetworkMessage<E>>,
+    executor: &TaskExecutor,
+    log: Logger,
+) {
+    let allowlist = allowlist.iter().map(NodeId::new).collect::<Vec<_>>();
+
+    executor.spawn(
+        async move {
+            let client = match reqwest::Client::builder()
+                .timeout(BOOT_NODES_FETCH_TIMEOUT)
+                .https_only(true)
+                .build()
+            {
+                Ok(client) => client,
+                Err(e) => {
+                    warn!(log, "Unable to build boot node client"; "error" => %e);
+                    return;
+                }
+            };
+
+            let mut interval = tokio::time::interval(BOOT_NODES_REFRESH_INTERVAL);
+            loop {
+                interval.tick().await;
+
+                let body = match fetch_signed(&client, &url, &public_key).await {
+                    Ok(body) => body,
+                    Err(e) => {
+                        warn!(
+                            log,
+                            "Unable to fetch boot nodes";
+                            "info" => "continuing with the built-in boot nodes",
+                            "error" => e,
+                        );
+                        continue;
+                    }
+                };
+
+                let enrs = parse_boot_nodes(&body, &allowlist, &log);
+                info!(log, "Fetched boot nodes"; "count" => enrs.len());
+                if enrs.is_empty() {
+                    continue;
+                }
+                if network_send
+                    .send(NetworkMessage::AddBootNodes { enrs })
+                    .is_err()
+                {
+                    debug!(
+                        log,
+                        "Network service has stopped, no longer fetching boot nodes"
+                    );
+                    return;
+                }
+            }
+        },
+        "boot_nodes_refresh",
+    );
+}
+
+/// Fetch the boot node list from `url` and its signature from `url` with `.sig` appended, returning
+/// the list if it is signed by `public_key`.
+async fn fetch_signed(
+    client: &reqwest::Client,
+    url: &str,
+    public_key: &PublicKey,
+) -> Result<String, String> {
+    let body = fetch(client, url)
+        .await
+        .map_err(|e| format!("Unable to fetch boot node list: {}", e))?;
+    let signature = fetch(client, &format!("{}.sig", url))
+        .await
+        .map_err(|e| format!("Unable to fetch boot node list signature: {}", e))?;
+    verify_boot_nodes_signature(&body, &signature, public_key)?;
+    Ok(body)
+}
+
+async fn fetch(client: &reqwest::Client, url: &str) -> Result<String, reqwest::Error> {
+    client
+        .get(url)
+        .send()
+        .await?
+        .error_for_status()?
+        .text()
+        .await
+}
+
+/// Returns `Ok` if `signature`, a 0x-prefixed hex string, is a signature by `public_key` across the
+/// SHA256 hash of `body`.
+pub fn verify_boot_nodes_signature(
+    body: &str,
+    signature: &str,
+    public_key: &PublicKey,
+) -> Result<(), String> {
+    let signature = Signature::from_str(signature.trim())
+        .map_err(|e| format!("Invalid boot node list signature: {}", e))?;
+    let message = Hash256::from_slice(&ethereum_hashing::hash(body.as_bytes()));
+    if signature.verify(public_key, message) {
+        Ok(())
+    } else {
+        Err("Boot node list is not signed by the configured public key".to_string())
+    }
+}
+
+/// Parse a list of ENRs in the format of `boot_enr.yaml`, i.e. one ENR per line, optionally
+/// prefixed by `- ` and quoted. Blank lines and comments are ignored.
+///
+/// ENRs which are invalid, or not in a non-empty `allowlist`, are skipped.
+pub fn parse_boot_nodes(body: &str, allowlist: &[NodeId], log: &Logger) -> Vec<Enr> {
+    body.lines()
+        .map(|line| line.split('#').next().unwrap_or_default().trim())
+        .map(|line| line.strip_prefix('-').unwrap_or(line).trim())
+        .map(|line| line.trim_matches(|c| c == '"' || c == '\''))
+        .filter(|line| !line.is_empty())
+        .filter_map(|line| match Enr::from_str(line) {
+            Ok(enr) if allowlist.is_empty() || allowlist.contains(&enr.node_id()) => Some(enr),
+            Ok(enr) => {
+                warn!(
+                    log,
+                    "Ignoring boot node which is not allowlisted";
+                    "node_id" => %enr.node_id(),
+                );
+                None
+            }
+            Err(e) => {
+                warn!(
+                    log,
+                    "Ignoring invalid boot node ENR";
+                    "enr" => line,
+                    "error" => e,
+                );
+                None
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sloggers::{null::NullLoggerBuilder, Build};
+    use types::SecretKey;
+
+    const ENR: &str = "enr:-IS4QHCYrYZbAKWCBRlAy5zzaDZXJBGkcnh4MHcBFZntXNFrdvJjX04jRzjzCBOonrkTfj499SZuOh8R33Ls8RRcy5wBgmlkgnY0gmlwhH8AAAGJc2VjcDI1NmsxoQPKY0yuDUmstAHYpMa2_oxVtw0RW_QAdpzBQA8yWM0xOIN1ZHCCdl8";
+
+    #[test]
+    fn parse_boot_enr_yaml() {
+        let log = NullLoggerBuilder.build().unwrap();
+        let body = format!(
+            "# Example boot nodes\n- {ENR}\n\n- \"{ENR}\" # quoted\n- enr:-invalid\n{ENR}\n"
+        );
+
+        let enrs = parse_boot_nodes(&body, &[], &log);
+        assert_eq!(enrs.len(), 3);
+        assert!(enrs.iter().all(|enr| enr.to_base64() == ENR));
+    }
+
+    #[test]
+    fn parse_boot_nodes_allowlist() {
+        let log = NullLoggerBuilder.build().unwrap();
+        let node_id = Enr::from_str(ENR).unwrap().node_id();
+
+        assert_eq!(parse_boot_nodes(ENR, &[node_id], &log).len(), 1);
+        assert!(parse_boot_nodes(ENR, &[NodeId::new(&[0; 32])], &log).is_empty());
+    }
+
+    #[test]
+    fn verify_boot_node_list_signature() {
+        let secret_key = SecretKey::random();
+        let public_key = secret_key.public_key();
+        let body = format!("- {ENR}\n");
+        let message = Hash256::from_slice(&ethereum_hashing::hash(body.as_bytes()));
+        let signature = format!("{}\n", secret_key.sign(message));
+
+        assert_eq!(
+            verify_boot_nodes_signature(&body, &signature, &public_key),
+            Ok(())
+        );
+        // A modified list is rejected.
+        assert!(
+            verify_boot_nodes_signature(&format!("{body}{ENR}\n"), &signature, &public_key)
+                .is_err()
+        );
+        // A list signed by another key is rejected.
+        assert!(
+            verify_boot_nodes_signature(&body, &signature, &SecretKey::random().public_key())
+                .is_err()
+        );
+        assert!(verify_boot_nodes_signature(&body, "0x1234", &public_key).is_err());
+    }
+}
@@ -3,11 +3,13 @@ pub mod error;
 #[allow(clippy::mutable_key_type)] // PeerId in hashmaps are no longer permitted by clippy
 pub mod service;
 
+mod bootnodes;
 #[allow(clippy::mutable_key_type)] // PeerId in hashmaps are no longer permitted by clippy
 mod metrics;
 mod nat;
 mod network_beacon_processor;
 mod persisted_dht;
+mod persisted_peers;
 mod router;
 mod status;
 mod subnet_service;
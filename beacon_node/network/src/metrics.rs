@@ -68,6 +68,10 @@ lazy_static! {
         "beacon_processor_gossip_block_early_seconds",
         "Whenever a gossip block is received early this metrics is set to how early that block was."
     );
+    pub static ref BEACON_PROCESSOR_SHADOW_FORK_IGNORED_BLOCK_TOTAL: Result<IntCounter> = try_create_int_counter(
+        "beacon_processor_shadow_fork_ignored_block_total",
+        "Total number of gossip and range sync blocks for the network's fork ignored by a diverged shadow fork."
+    );
     pub static ref BEACON_PROCESSOR_GOSSIP_BLOB_VERIFIED_TOTAL: Result<IntCounter> = try_create_int_counter(
         "beacon_processor_gossip_blob_verified_total",
         "Total number of gossip blob verified for propagation."
@@ -191,6 +195,12 @@ lazy_static! {
             "Gossipsub attestation errors per error type",
             &["type"]
         );
+    pub static ref GOSSIP_ATTESTATION_ERRORS_PER_CLIENT: Result<IntCounterVec> =
+        try_create_int_counter_vec(
+            "gossipsub_attestation_errors_per_client",
+            "Gossipsub attestation errors per client of the peer and error type",
+            &["client", "type"]
+        );
     pub static ref GOSSIP_SYNC_COMMITTEE_ERRORS_PER_TYPE: Result<IntCounterVec> =
         try_create_int_counter_vec(
             "gossipsub_sync_committee_errors_per_type",
@@ -394,8 +404,12 @@ pub fn register_optimistic_update_error(error: &LightClientOptimisticUpdateError
     inc_counter_vec(&GOSSIP_OPTIMISTIC_UPDATE_ERRORS_PER_TYPE, &[error.as_ref()]);
 }
 
-pub fn register_attestation_error(error: &AttnError) {
+pub fn register_attestation_error(error: &AttnError, client: ClientKind) {
     inc_counter_vec(&GOSSIP_ATTESTATION_ERRORS_PER_TYPE, &[error.as_ref()]);
+    inc_counter_vec(
+        &GOSSIP_ATTESTATION_ERRORS_PER_CLIENT,
+        &[client.as_ref(), error.as_ref()],
+    );
 }
 
 pub fn register_sync_committee_error(error: &SyncCommitteeError) {
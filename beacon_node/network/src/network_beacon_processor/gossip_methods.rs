@@ -19,7 +19,10 @@ use beacon_chain::{
     AvailabilityProcessingStatus, BeaconChainError, BeaconChainTypes, BlockError, ForkChoiceError,
     GossipVerifiedBlock, NotifyExecutionLayer,
 };
-use lighthouse_network::{Client, MessageAcceptance, MessageId, PeerAction, PeerId, ReportSource};
+use lighthouse_network::{
+    peer_manager::peerdb::client::ClientKind, types::propagation::PropagationKind, Client,
+    MessageAcceptance, MessageId, PeerAction, PeerId, ReportSource,
+};
 use operation_pool::ReceivedPreCapella;
 use slog::{crit, debug, error, info, trace, warn, Logger};
 use slot_clock::SlotClock;
@@ -31,6 +34,7 @@ use std::sync::Arc;
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use store::hot_cold_store::HotColdDBError;
 use tokio::sync::mpsc;
+use tracing::instrument;
 use types::{
     beacon_block::BlockImportSource, Attestation, AttestationRef, AttesterSlashing, BlobSidecar,
     DataColumnSidecar, DataColumnSubnetId, EthSpec, Hash256, IndexedAttestation,
@@ -322,6 +326,11 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
                         indexed_attestation,
                         &self.chain.slot_clock,
                     );
+                self.chain.clock_sanity.observe_gossip_message(
+                    indexed_attestation.data().slot,
+                    seen_timestamp,
+                    &self.chain.slot_clock,
+                );
 
                 // If the attestation is still timely, propagate it.
                 self.propagate_attestation_if_timely(
@@ -526,6 +535,16 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
                     peer_id,
                 );
 
+                self.network_globals.propagation.write().observe(
+                    peer_id,
+                    PropagationKind::Aggregate,
+                    get_slot_delay_ms(
+                        seen_timestamp,
+                        aggregate.message().aggregate().data().slot,
+                        &self.chain.slot_clock,
+                    ),
+                );
+
                 // Register the attestation with any monitored validators.
                 self.chain
                     .validator_monitor
@@ -983,6 +1002,7 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
     ///
     /// Raises a log if there are errors.
     #[allow(clippy::too_many_arguments)]
+    #[instrument(skip_all, fields(slot = %block.slot(), %peer_id))]
     pub async fn process_gossip_block(
         self: Arc<Self>,
         message_id: MessageId,
@@ -1032,6 +1052,7 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
     /// if it passes gossip propagation criteria, tell the network thread to forward it.
     ///
     /// Returns the `GossipVerifiedBlock` if verification passes and raises a log if there are errors.
+    #[instrument(skip_all, fields(slot = %block.slot()))]
     pub async fn process_gossip_unverified_block(
         self: &Arc<Self>,
         message_id: MessageId,
@@ -1056,6 +1077,11 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
                 &metrics::BEACON_BLOCK_DELAY_GOSSIP,
                 block_delay.as_millis() as i64,
             );
+            self.network_globals.propagation.write().observe(
+                peer_id,
+                PropagationKind::Block,
+                block_delay,
+            );
         }
 
         let block_root = if let Ok(verified_block) = &verification_result {
@@ -1190,6 +1216,18 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
                 self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Ignore);
                 return None;
             }
+            // Once a shadow fork has diverged, honest peers on the network gossip blocks for the
+            // network's fork, which are inconsistent with ours through no fault of their own.
+            Err(e @ BlockError::InconsistentFork(_)) if self.chain.is_shadow_fork_diverged() => {
+                metrics::inc_counter(&metrics::BEACON_PROCESSOR_SHADOW_FORK_IGNORED_BLOCK_TOTAL);
+                debug!(
+                    self.log,
+                    "Could not verify block for gossip on diverged shadow fork. Ignoring the block";
+                    "error" => %e
+                );
+                self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Ignore);
+                return None;
+            }
             Err(e @ BlockError::StateRootMismatch { .. })
             | Err(e @ BlockError::IncorrectBlockProposer { .. })
             | Err(e @ BlockError::BlockSlotLimitReached)
@@ -1244,6 +1282,11 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
             verified_block.block_root,
             &self.chain.slot_clock,
         );
+        self.chain.clock_sanity.observe_gossip_message(
+            verified_block.block.slot(),
+            seen_duration,
+            &self.chain.slot_clock,
+        );
 
         let block_slot = verified_block.block.slot();
         let block_root = verified_block.block_root;
@@ -1328,6 +1371,7 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
     /// Process the beacon block that has already passed gossip verification.
     ///
     /// Raises a log if there are errors.
+    #[instrument(skip_all, fields(block_root = ?verified_block.block_root))]
     pub async fn process_gossip_verified_block(
         self: Arc<Self>,
         peer_id: PeerId,
@@ -2030,6 +2074,30 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
         };
     }
 
+    /// Count the failure to verify an attestation from `peer_id`, in metrics and in the
+    /// `gossip_rejections` reported by the HTTP API.
+    ///
+    /// The peer DB is only read the first time the peer is counted in each epoch, to find its
+    /// client.
+    fn register_attestation_rejection(&self, peer_id: PeerId, error: &AttnError) {
+        let epoch = self
+            .chain
+            .slot()
+            .unwrap_or(self.chain.spec.genesis_slot)
+            .epoch(T::EthSpec::slots_per_epoch());
+        let client =
+            self.network_globals
+                .gossip_rejections
+                .observe(peer_id, epoch, error.as_ref(), || {
+                    self.network_globals
+                        .peers
+                        .read()
+                        .peer_info(&peer_id)
+                        .map_or(ClientKind::Unknown, |peer_info| peer_info.client().kind)
+                });
+        metrics::register_attestation_error(error, client);
+    }
+
     /// Handle an error whilst verifying an `Attestation` or `SignedAggregateAndProof` from the
     /// network.
     fn handle_attestation_verification_failure(
@@ -2043,7 +2111,7 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
     ) {
         let beacon_block_root = failed_att.beacon_block_root();
         let attestation_type = failed_att.kind();
-        metrics::register_attestation_error(&error);
+        self.register_attestation_rejection(peer_id, &error);
         match &error {
             AttnError::FutureSlot { .. } => {
                 /*
@@ -22,6 +22,7 @@ use std::sync::Arc;
 use std::time::Duration;
 use store::KzgCommitment;
 use tokio::sync::mpsc;
+use tracing::instrument;
 use types::beacon_block_body::format_kzg_commitments;
 use types::blob_sidecar::FixedBlobSidecarList;
 use types::BlockImportSource;
@@ -100,6 +101,7 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
 
     /// Attempt to process a block received from a direct RPC request.
     #[allow(clippy::too_many_arguments)]
+    #[instrument(skip_all, fields(slot = %block.as_block().slot(), block_root = ?block_root))]
     pub async fn process_rpc_block(
         self: Arc<NetworkBeaconProcessor<T>>,
         block_root: Hash256,
@@ -660,6 +662,20 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
                 debug!(self.log, "Genesis block was processed");
                 Ok(())
             }
+            // Once a shadow fork has diverged, honest peers serve blocks for the network's fork,
+            // which are inconsistent with ours through no fault of their own.
+            BlockError::InconsistentFork(e) if self.chain.is_shadow_fork_diverged() => {
+                metrics::inc_counter(&metrics::BEACON_PROCESSOR_SHADOW_FORK_IGNORED_BLOCK_TOTAL);
+                debug!(
+                    self.log,
+                    "Block for the network's fork on diverged shadow fork";
+                    "error" => ?e,
+                );
+                Err(ChainSegmentFailed {
+                    message: format!("Block is for the network's fork: {:?}", e),
+                    peer_action: None,
+                })
+            }
             BlockError::BeaconChainError(e) => {
                 warn!(
                     self.log, "BlockProcessingFailure";
@@ -6,7 +6,7 @@ use crate::{
         ChainSegmentProcessId, DuplicateCache, InvalidBlockStorage, NetworkBeaconProcessor,
     },
     service::NetworkMessage,
-    sync::{manager::BlockProcessType, SyncMessage},
+    sync::{manager::BlockProcessType, BatchProcessResult, SyncMessage},
 };
 use beacon_chain::block_verification_types::RpcBlock;
 use beacon_chain::test_utils::{
@@ -21,7 +21,7 @@ use lighthouse_network::{
     discv5::enr::{self, CombinedKey},
     rpc::methods::{MetaData, MetaDataV2},
     types::{EnrAttestationBitfield, EnrSyncCommitteeBitfield},
-    Client, MessageId, NetworkGlobals, PeerId, Response,
+    Client, MessageAcceptance, MessageId, NetworkGlobals, PeerId, Response,
 };
 use slot_clock::SlotClock;
 use std::iter::Iterator;
@@ -30,9 +30,9 @@ use std::time::Duration;
 use tokio::sync::mpsc;
 use types::blob_sidecar::FixedBlobSidecarList;
 use types::{
-    Attestation, AttesterSlashing, BlobSidecar, BlobSidecarList, Epoch, Hash256, MainnetEthSpec,
-    ProposerSlashing, SignedAggregateAndProof, SignedBeaconBlock, SignedVoluntaryExit, Slot,
-    SubnetId,
+    Attestation, AttesterSlashing, BeaconBlock, BlobSidecar, BlobSidecarList, ChainSpec, Epoch,
+    Hash256, MainnetEthSpec, ProposerSlashing, Signature, SignedAggregateAndProof,
+    SignedBeaconBlock, SignedVoluntaryExit, Slot, SubnetId,
 };
 
 type E = MainnetEthSpec;
@@ -88,17 +88,31 @@ impl TestRig {
     }
 
     pub async fn new_parametric(chain_length: u64, enable_backfill_rate_limiting: bool) -> Self {
+        Self::new_with_network_spec(chain_length, enable_backfill_rate_limiting, None).await
+    }
+
+    /// Creates a rig whose chain follows the network's fork schedule in `network_spec`, as when
+    /// running with `--shadow-fork`.
+    pub async fn new_with_network_spec(
+        chain_length: u64,
+        enable_backfill_rate_limiting: bool,
+        network_spec: Option<ChainSpec>,
+    ) -> Self {
         // This allows for testing voluntary exits without building out a massive chain.
         let mut spec = test_spec::<E>();
         spec.shard_committee_period = 2;
 
-        let harness = BeaconChainHarness::builder(MainnetEthSpec)
+        let mut builder = BeaconChainHarness::builder(MainnetEthSpec)
             .spec(spec)
             .deterministic_keypairs(VALIDATOR_COUNT)
             .fresh_ephemeral_store()
             .mock_execution_layer()
-            .chain_config(<_>::default())
-            .build();
+            .chain_config(<_>::default());
+        if let Some(network_spec) = network_spec {
+            builder = builder
+                .initial_mutator(Box::new(move |builder| builder.network_spec(network_spec)));
+        }
+        let harness = builder.build();
 
         harness.advance_slot();
 
@@ -230,6 +244,7 @@ impl TestRig {
             executor,
             current_workers: 0,
             config: beacon_processor_config,
+            queue_stats: BeaconProcessorQueueStats::default(),
             log: log.clone(),
         }
         .spawn_manager(
@@ -557,6 +572,24 @@ fn junk_message_id() -> MessageId {
     MessageId::new(&[])
 }
 
+/// Returns a copy of `spec` whose fork at genesis differs from that of `spec`, as for the network
+/// followed by a shadow fork which has diverged from it.
+fn diverged_network_spec(spec: &ChainSpec) -> ChainSpec {
+    let fork = spec.fork_name_at_epoch(Epoch::new(0));
+    let network_fork = fork
+        .previous_fork()
+        .or_else(|| fork.next_fork())
+        .expect("there is more than one fork");
+    network_fork.make_genesis_spec(spec.clone())
+}
+
+/// Returns a block at `slot` with the structure of the fork dictated by `network_spec`.
+fn network_fork_block(network_spec: &ChainSpec, slot: Slot) -> Arc<SignedBeaconBlock<E>> {
+    let mut block = BeaconBlock::empty(network_spec);
+    *block.slot_mut() = slot;
+    Arc::new(SignedBeaconBlock::from_block(block, Signature::empty()))
+}
+
 /// Blocks that arrive early should be queued for later processing.
 #[tokio::test]
 async fn import_gossip_block_acceptably_early() {
@@ -650,6 +683,111 @@ async fn import_gossip_block_unacceptably_early() {
     );
 }
 
+/// Process a gossip block for the fork of the network rather than ours, returning the validation
+/// result and whether the sending peer was penalized.
+async fn process_network_fork_block(rig: &mut TestRig) -> (MessageAcceptance, bool) {
+    let network_spec = diverged_network_spec(&rig.chain.spec);
+    let block = network_fork_block(&network_spec, rig.next_block.slot());
+    rig.network_beacon_processor
+        .send_gossip_beacon_block(
+            junk_message_id(),
+            junk_peer_id(),
+            Client::default(),
+            block,
+            Duration::from_secs(0),
+        )
+        .unwrap();
+
+    rig.assert_event_journal(&[GOSSIP_BLOCK, WORKER_FREED, NOTHING_TO_DO])
+        .await;
+
+    let mut validation_result = None;
+    let mut penalized = false;
+    while let Ok(message) = rig._network_rx.try_recv() {
+        match message {
+            NetworkMessage::ValidationResult {
+                validation_result: result,
+                ..
+            } => validation_result = Some(result),
+            NetworkMessage::ReportPeer { .. } => penalized = true,
+            _ => {}
+        }
+    }
+    (
+        validation_result.expect("block should be validated"),
+        penalized,
+    )
+}
+
+/// Blocks with the wrong structure for their slot should be rejected and their sender penalized.
+#[tokio::test]
+async fn reject_gossip_block_inconsistent_fork() {
+    let mut rig = TestRig::new(SMALL_CHAIN).await;
+    assert!(!rig.chain.is_shadow_fork_diverged());
+
+    let (validation_result, penalized) = process_network_fork_block(&mut rig).await;
+
+    assert!(matches!(validation_result, MessageAcceptance::Reject));
+    assert!(penalized, "peer should be penalized");
+}
+
+/// Once a shadow fork has diverged from the network, blocks for the network's fork should be
+/// ignored without penalizing the honest peers that send them.
+#[tokio::test]
+async fn ignore_gossip_block_inconsistent_fork_on_diverged_shadow_fork() {
+    let network_spec = diverged_network_spec(&test_spec::<E>());
+    let mut rig = TestRig::new_with_network_spec(
+        SMALL_CHAIN,
+        BeaconProcessorConfig::default().enable_backfill_rate_limiting,
+        Some(network_spec),
+    )
+    .await;
+    assert!(rig.chain.is_shadow_fork_diverged());
+
+    let (validation_result, penalized) = process_network_fork_block(&mut rig).await;
+
+    assert!(matches!(validation_result, MessageAcceptance::Ignore));
+    assert!(!penalized, "peer should not be penalized");
+}
+
+/// Process a range sync batch containing a block for the fork of the network rather than ours,
+/// returning the result sent to sync.
+async fn process_network_fork_batch(rig: &mut TestRig) -> BatchProcessResult {
+    let network_spec = diverged_network_spec(&rig.chain.spec);
+    let block = network_fork_block(&network_spec, rig.next_block.slot());
+    rig.network_beacon_processor
+        .send_chain_segment(
+            ChainSegmentProcessId::RangeBatchId(0, Epoch::default()),
+            vec![RpcBlock::new_without_blobs(None, block)],
+        )
+        .unwrap();
+
+    rig.assert_event_journal(&[CHAIN_SEGMENT, WORKER_FREED, NOTHING_TO_DO])
+        .await;
+
+    match rig._sync_rx.try_recv() {
+        Ok(SyncMessage::BatchProcessed { result, .. }) => result,
+        other => panic!("expected a batch result, got {other:?}"),
+    }
+}
+
+/// Once a shadow fork has diverged from the network, range sync batches for the network's fork
+/// should fail without penalizing the honest peers that serve them.
+#[tokio::test]
+async fn ignore_range_sync_batch_inconsistent_fork_on_diverged_shadow_fork() {
+    let network_spec = diverged_network_spec(&test_spec::<E>());
+    let mut rig = TestRig::new_with_network_spec(
+        SMALL_CHAIN,
+        BeaconProcessorConfig::default().enable_backfill_rate_limiting,
+        Some(network_spec),
+    )
+    .await;
+
+    let result = process_network_fork_batch(&mut rig).await;
+
+    assert!(matches!(result, BatchProcessResult::NonFaultyFailure));
+}
+
 /// Blocks that arrive on-time should be processed normally.
 #[tokio::test]
 async fn import_gossip_block_at_current_slot() {
@@ -0,0 +1,182 @@
+use lighthouse_network::peer_manager::peerdb::client::ClientKind;
+use lighthouse_network::{Enr, PeerConnectionStatus, PeerDB};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use store::{DBColumn, Error as StoreError, HotColdDB, ItemStore, StoreItem};
+use types::{EthSpec, Hash256};
+
+/// 32-byte key for accessing the `PersistedPeers`. It shares a column with the `DhtEnrs`, so must
+/// differ from `DHT_DB_KEY`.
+pub const PEERS_DB_KEY: Hash256 = Hash256::repeat_byte(1);
+
+/// The maximum number of peers to persist.
+pub const MAX_PERSISTED_PEERS: usize = 100;
+
+/// Peers which we have not seen for longer than this are not dialed at startup.
+pub const MAX_PERSISTED_PEER_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
+pub fn load_peers<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
+    store: Arc<HotColdDB<E, Hot, Cold>>,
+) -> Vec<PersistedPeer> {
+    match store.get_item(&PEERS_DB_KEY) {
+        Ok(Some(p)) => {
+            let p: PersistedPeers = p;
+            p.peers
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Attempt to persist the `peers` to `store`, replacing any previously persisted peers.
+pub fn persist_peers<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
+    store: Arc<HotColdDB<E, Hot, Cold>>,
+    peers: Vec<PersistedPeer>,
+) -> Result<(), store::Error> {
+    store.put_item(&PEERS_DB_KEY, &PersistedPeers { peers })
+}
+
+/// Returns the best peers in the `PeerDB` that are worth reconnecting to after a restart.
+///
+/// These are the peers we have connected to which have a known ENR and a non-negative score,
+/// ordered from best to worst score.
+pub fn peers_to_persist<E: EthSpec>(peer_db: &PeerDB<E>, now: SystemTime) -> Vec<PersistedPeer> {
+    let mut peers: Vec<PersistedPeer> = peer_db
+        .peers()
+        .filter_map(|(_, info)| {
+            let enr = info.enr()?;
+            let score = info.score().score();
+            // Peers are only identified once we have connected to them.
+            if score < 0.0 || info.client().kind == ClientKind::Unknown {
+                return None;
+            }
+            let last_seen = match info.connection_status() {
+                PeerConnectionStatus::Connected { .. }
+                | PeerConnectionStatus::Disconnecting { .. } => now,
+                PeerConnectionStatus::Disconnected { since } => now.checked_sub(since.elapsed())?,
+                PeerConnectionStatus::Dialing { .. }
+                | PeerConnectionStatus::Banned { .. }
+                | PeerConnectionStatus::Unknown => return None,
+            };
+            Some(PersistedPeer {
+                enr: enr.clone(),
+                score,
+                last_seen: last_seen.duration_since(UNIX_EPOCH).ok()?.as_secs(),
+            })
+        })
+        .collect();
+
+    peers.sort_unstable_by(|a, b| b.score.total_cmp(&a.score));
+    peers.truncate(MAX_PERSISTED_PEERS);
+    peers
+}
+
+/// Returns the ENRs of up to `count` of the `peers` which were seen within
+/// `MAX_PERSISTED_PEER_AGE` of `now`, best first.
+pub fn peers_to_dial(peers: Vec<PersistedPeer>, now: SystemTime, count: usize) -> Vec<Enr> {
+    let now = now.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+    peers
+        .into_iter()
+        .filter(|peer| now.saturating_sub(peer.last_seen) <= MAX_PERSISTED_PEER_AGE.as_secs())
+        .take(count)
+        .map(|peer| peer.enr)
+        .collect()
+}
+
+/// A peer that we were connected to before shutting down.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PersistedPeer {
+    pub enr: Enr,
+    pub score: f64,
+    /// The number of seconds since the UNIX epoch at which we were last connected to the peer.
+    pub last_seen: u64,
+}
+
+/// Wrapper around the persisted peers for persistence to disk.
+pub struct PersistedPeers {
+    pub peers: Vec<PersistedPeer>,
+}
+
+impl StoreItem for PersistedPeers {
+    fn db_column() -> DBColumn {
+        DBColumn::DhtEnrs
+    }
+
+    fn as_store_bytes(&self) -> Vec<u8> {
+        let mut stream = rlp::RlpStream::new_list(self.peers.len());
+        for peer in &self.peers {
+            stream.begin_list(3);
+            stream.append(&peer.enr);
+            stream.append(&peer.score.to_bits());
+            stream.append(&peer.last_seen);
+        }
+        stream.out().to_vec()
+    }
+
+    fn from_store_bytes(bytes: &[u8]) -> Result<Self, StoreError> {
+        let peers = rlp::Rlp::new(bytes)
+            .iter()
+            .map(|peer| {
+                Ok(PersistedPeer {
+                    enr: peer.val_at(0)?,
+                    score: f64::from_bits(peer.val_at(1)?),
+                    last_seen: peer.val_at(2)?,
+                })
+            })
+            .collect::<Result<_, rlp::DecoderError>>()
+            .map_err(|e| StoreError::RlpError(format!("{}", e)))?;
+        Ok(PersistedPeers { peers })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sloggers::{null::NullLoggerBuilder, Build};
+    use std::str::FromStr;
+    use store::config::StoreConfig;
+    use store::MemoryStore;
+    use types::{ChainSpec, MinimalEthSpec};
+
+    fn enr() -> Enr {
+        Enr::from_str("enr:-IS4QHCYrYZbAKWCBRlAy5zzaDZXJBGkcnh4MHcBFZntXNFrdvJjX04jRzjzCBOonrkTfj499SZuOh8R33Ls8RRcy5wBgmlkgnY0gmlwhH8AAAGJc2VjcDI1NmsxoQPKY0yuDUmstAHYpMa2_oxVtw0RW_QAdpzBQA8yWM0xOIN1ZHCCdl8").unwrap()
+    }
+
+    #[test]
+    fn test_persisted_peers() {
+        let log = NullLoggerBuilder.build().unwrap();
+        let store: HotColdDB<
+            MinimalEthSpec,
+            MemoryStore<MinimalEthSpec>,
+            MemoryStore<MinimalEthSpec>,
+        > = HotColdDB::open_ephemeral(StoreConfig::default(), ChainSpec::minimal(), log).unwrap();
+        let store = Arc::new(store);
+        assert!(load_peers(store.clone()).is_empty());
+
+        let peers = vec![PersistedPeer {
+            enr: enr(),
+            score: 2.5,
+            last_seen: 1_700_000_000,
+        }];
+        persist_peers(store.clone(), peers.clone()).unwrap();
+        assert_eq!(load_peers(store), peers);
+    }
+
+    #[test]
+    fn test_peers_to_dial() {
+        let now = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let now_secs = 1_700_000_000;
+        let peer = |last_seen| PersistedPeer {
+            enr: enr(),
+            score: 0.0,
+            last_seen,
+        };
+        let peers = vec![
+            peer(now_secs - MAX_PERSISTED_PEER_AGE.as_secs() - 1),
+            peer(now_secs - 60),
+            peer(now_secs),
+        ];
+
+        assert_eq!(peers_to_dial(peers.clone(), now, 10).len(), 2);
+        assert_eq!(peers_to_dial(peers, now, 1).len(), 1);
+    }
+}
@@ -1,6 +1,8 @@
+use crate::bootnodes::spawn_boot_nodes_refresh;
 use crate::nat;
 use crate::network_beacon_processor::InvalidBlockStorage;
 use crate::persisted_dht::{clear_dht, load_dht, persist_dht};
+use crate::persisted_peers::{load_peers, peers_to_dial, peers_to_persist, persist_peers};
 use crate::router::{Router, RouterMessage};
 use crate::subnet_service::SyncCommitteeService;
 use crate::{error, metrics};
@@ -24,19 +26,24 @@ use lighthouse_network::{
 use lighthouse_network::{
     service::api_types::AppRequestId,
     types::{core_topics_to_subscribe, GossipEncoding, GossipTopic},
-    MessageId, NetworkEvent, NetworkGlobals, PeerId,
+    Enr, MessageId, NetworkEvent, NetworkGlobals, PeerId,
 };
 use slog::{crit, debug, error, info, o, trace, warn};
 use std::collections::BTreeSet;
-use std::{collections::HashSet, pin::Pin, sync::Arc, time::Duration};
+use std::{
+    collections::HashSet,
+    pin::Pin,
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
 use store::HotColdDB;
 use strum::IntoStaticStr;
 use task_executor::ShutdownReason;
 use tokio::sync::mpsc;
 use tokio::time::Sleep;
 use types::{
-    ChainSpec, EthSpec, ForkContext, Slot, SubnetId, SyncCommitteeSubscription, SyncSubnetId,
-    Unsigned, ValidatorSubscription,
+    ChainSpec, DataColumnSubnetId, EthSpec, ForkContext, Slot, SubnetId, SyncCommitteeSubscription,
+    SyncSubnetId, Unsigned, ValidatorSubscription,
 };
 
 mod tests;
@@ -101,6 +108,8 @@ pub enum NetworkMessage<E: EthSpec> {
         reason: GoodbyeReason,
         source: ReportSource,
     },
+    /// Add boot nodes fetched from the `--boot-nodes-url` to the routing table.
+    AddBootNodes { enrs: Vec<Enr> },
 }
 
 /// Messages triggered by validators that may trigger a subscription to a subnet.
@@ -185,6 +194,8 @@ pub struct NetworkService<T: BeaconChainTypes> {
     next_unsubscribe: Pin<Box<OptionFuture<Sleep>>>,
     /// Subscribe to all the subnets once synced.
     subscribe_all_subnets: bool,
+    /// Subscribe to all the data column subnets once synced, rather than just our custody subnets.
+    subscribe_all_data_column_subnets: bool,
     /// Shutdown beacon node after sync is complete.
     shutdown_after_sync: bool,
     /// Whether metrics are enabled or not.
@@ -258,11 +269,12 @@ impl<T: BeaconChainTypes> NetworkService<T> {
             .slot()
             .unwrap_or(beacon_chain.spec.genesis_slot);
 
-        // Create a fork context for the given config and genesis validators root
+        // Create a fork context for the given config and genesis validators root. This uses the
+        // network's spec, which differs from the chain's spec when running a shadow fork.
         let fork_context = Arc::new(ForkContext::new::<T::EthSpec>(
             current_slot,
             beacon_chain.genesis_validators_root,
-            &beacon_chain.spec,
+            &beacon_chain.network_spec,
         ));
 
         debug!(network_log, "Current fork"; "fork_name" => ?fork_context.current_fork());
@@ -272,7 +284,7 @@ impl<T: BeaconChainTypes> NetworkService<T> {
             config,
             enr_fork_id,
             fork_context: fork_context.clone(),
-            chain_spec: &beacon_chain.spec,
+            chain_spec: &beacon_chain.network_spec,
             libp2p_registry,
         };
 
@@ -290,6 +302,37 @@ impl<T: BeaconChainTypes> NetworkService<T> {
             for enr in enrs_to_load {
                 libp2p.add_enr(enr.clone());
             }
+
+            // Dial the peers we were connected to before the last shutdown, so that we rebuild our
+            // mesh without waiting on discovery.
+            let peers_to_load = peers_to_dial(
+                load_peers::<T::EthSpec, T::HotStore, T::ColdStore>(store.clone()),
+                SystemTime::now(),
+                config.target_peers,
+            );
+            debug!(
+                network_log,
+                "Dialing persisted peers"; "peers" => peers_to_load.len()
+            );
+            for enr in peers_to_load {
+                libp2p.add_enr(enr.clone());
+                libp2p.peer_manager_mut().dial_peer(enr);
+            }
+
+            if let Some((url, public_key)) = config
+                .boot_nodes_url
+                .clone()
+                .zip(config.boot_nodes_public_key.clone())
+            {
+                spawn_boot_nodes_refresh(
+                    url,
+                    public_key,
+                    config.boot_nodes_allowlist.clone(),
+                    network_senders.network_send(),
+                    &executor,
+                    network_log.clone(),
+                );
+            }
         }
 
         let invalid_block_storage = config
@@ -350,6 +393,7 @@ impl<T: BeaconChainTypes> NetworkService<T> {
             next_fork_subscriptions,
             next_unsubscribe,
             subscribe_all_subnets: config.subscribe_all_subnets,
+            subscribe_all_data_column_subnets: config.subscribe_all_data_column_subnets,
             shutdown_after_sync: config.shutdown_after_sync,
             metrics_enabled: config.metrics_enabled,
             metrics_update,
@@ -392,7 +436,7 @@ impl<T: BeaconChainTypes> NetworkService<T> {
     /// digests since we should be subscribed to post fork topics before the fork.
     pub fn required_gossip_fork_digests(&self) -> Vec<[u8; 4]> {
         let fork_context = &self.fork_context;
-        let spec = &self.beacon_chain.spec;
+        let spec = &self.beacon_chain.network_spec;
         let current_slot = self.beacon_chain.slot().unwrap_or(spec.genesis_slot);
         let current_fork = fork_context.current_fork();
 
@@ -473,7 +517,7 @@ impl<T: BeaconChainTypes> NetworkService<T> {
 
                     Some(_) = &mut self.next_fork_subscriptions => {
                         if let Some((fork_name, _)) = self.beacon_chain.duration_to_next_fork() {
-                            let fork_version = self.beacon_chain.spec.fork_version_for_name(fork_name);
+                            let fork_version = self.beacon_chain.network_spec.fork_version_for_name(fork_name);
                             let fork_digest = ChainSpec::compute_fork_digest(fork_version, self.beacon_chain.genesis_validators_root);
                             info!(self.log, "Subscribing to new fork topics");
                             self.libp2p.subscribe_new_fork_topics(fork_name, fork_digest);
@@ -648,6 +692,14 @@ impl<T: BeaconChainTypes> NetworkService<T> {
                 );
             }
             NetworkMessage::Publish { messages } => {
+                if self.beacon_chain.is_shadow_fork_diverged() {
+                    debug!(
+                        self.log,
+                        "Not publishing messages on shadow fork";
+                        "count" => messages.len(),
+                    );
+                    return;
+                }
                 let mut topic_kinds = Vec::new();
                 for message in &messages {
                     if !topic_kinds.contains(&message.kind()) {
@@ -673,6 +725,14 @@ impl<T: BeaconChainTypes> NetworkService<T> {
                 reason,
                 source,
             } => self.libp2p.goodbye_peer(&peer_id, reason, source),
+            NetworkMessage::AddBootNodes { enrs } => {
+                let local_node_id = self.network_globals.local_enr().node_id();
+                for enr in enrs {
+                    if enr.node_id() != local_node_id {
+                        self.libp2p.add_enr(enr);
+                    }
+                }
+            }
             NetworkMessage::SubscribeCoreTopics => {
                 if self.subscribed_core_topics() {
                     return;
@@ -768,6 +828,12 @@ impl<T: BeaconChainTypes> NetworkService<T> {
                     }
                 }
 
+                // Data column subnets are subscribed from the start so that the mesh is formed
+                // by the time PeerDAS activates.
+                if self.fork_context.spec.is_peer_das_scheduled() {
+                    self.subscribe_to_data_column_subnets(&mut subscribed_topics);
+                }
+
                 if !subscribed_topics.is_empty() {
                     info!(
                         self.log,
@@ -779,6 +845,27 @@ impl<T: BeaconChainTypes> NetworkService<T> {
         }
     }
 
+    /// Subscribe to the data column subnets that we custody, or all of them if configured to.
+    fn subscribe_to_data_column_subnets(&mut self, subscribed_topics: &mut Vec<GossipTopic>) {
+        let subnets = data_column_subnets_to_subscribe(
+            &self.network_globals,
+            self.subscribe_all_data_column_subnets,
+            &self.fork_context.spec,
+        );
+
+        for subnet_id in subnets {
+            let subnet = Subnet::DataColumn(subnet_id);
+            for fork_digest in self.required_gossip_fork_digests() {
+                let topic = GossipTopic::new(subnet.into(), GossipEncoding::default(), fork_digest);
+                if self.libp2p.subscribe(topic.clone()) {
+                    subscribed_topics.push(topic);
+                } else {
+                    warn!(self.log, "Could not subscribe to topic"; "topic" => %topic);
+                }
+            }
+        }
+    }
+
     /// Handle a message sent to the network service.
     async fn on_validator_subscription_msg(&mut self, msg: ValidatorSubscriptionMessage) {
         match msg {
@@ -965,6 +1052,22 @@ fn next_fork_subscriptions_delay<T: BeaconChainTypes>(
     None
 }
 
+/// Returns the data column subnets to subscribe to: those which the node custodies, or all of them
+/// if `subscribe_all` is set. Subscribing to every subnet does not change the custody set.
+fn data_column_subnets_to_subscribe<E: EthSpec>(
+    network_globals: &NetworkGlobals<E>,
+    subscribe_all: bool,
+    spec: &ChainSpec,
+) -> Vec<DataColumnSubnetId> {
+    if subscribe_all {
+        (0..spec.data_column_sidecar_subnet_count)
+            .map(DataColumnSubnetId::new)
+            .collect()
+    } else {
+        network_globals.custody_subnets(spec).collect()
+    }
+}
+
 impl<T: BeaconChainTypes> Drop for NetworkService<T> {
     fn drop(&mut self) {
         // network thread is terminating
@@ -989,6 +1092,18 @@ impl<T: BeaconChainTypes> Drop for NetworkService<T> {
                 "Saved DHT state";
             ),
         }
+
+        let peers = peers_to_persist(&self.network_globals.peers.read(), SystemTime::now());
+        debug!(
+            self.log,
+            "Persisting peers to store";
+            "Number of peers" => peers.len(),
+        );
+        if let Err(e) =
+            persist_peers::<T::EthSpec, T::HotStore, T::ColdStore>(self.store.clone(), peers)
+        {
+            error!(self.log, "Failed to persist peers on drop"; "error" => ?e);
+        }
         info!(self.log, "Network service shutdown");
     }
 }
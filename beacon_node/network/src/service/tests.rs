@@ -2,19 +2,21 @@
 #[cfg(test)]
 mod tests {
     use crate::persisted_dht::load_dht;
+    use crate::service::data_column_subnets_to_subscribe;
     use crate::{NetworkConfig, NetworkService};
     use beacon_chain::test_utils::BeaconChainHarness;
     use beacon_chain::BeaconChainTypes;
     use beacon_processor::{BeaconProcessorChannels, BeaconProcessorConfig};
     use futures::StreamExt;
     use lighthouse_network::types::{GossipEncoding, GossipKind};
-    use lighthouse_network::{Enr, GossipTopic};
+    use lighthouse_network::{Enr, GossipTopic, NetworkGlobals};
     use slog::{o, Drain, Level, Logger};
     use sloggers::{null::NullLoggerBuilder, Build};
+    use std::collections::HashSet;
     use std::str::FromStr;
     use std::sync::Arc;
     use tokio::runtime::Runtime;
-    use types::{Epoch, EthSpec, ForkName, MinimalEthSpec, SubnetId};
+    use types::{DataColumnSubnetId, Epoch, EthSpec, ForkName, MinimalEthSpec, SubnetId};
 
     impl<T: BeaconChainTypes> NetworkService<T> {
         fn get_topic_params(&self, topic: GossipTopic) -> Option<&gossipsub::TopicScoreParams> {
@@ -233,4 +235,40 @@ mod tests {
             .expect("topic score params");
         assert_eq!(0.0, old_topic_params2.topic_weight);
     }
+
+    // Subscribing to all data column subnets subscribes to every subnet's topic without changing
+    // the columns which the node custodies.
+    #[test]
+    fn test_subscribe_all_data_column_subnets_keeps_custody_set() {
+        let log = get_logger(false);
+        let spec = MinimalEthSpec::default_spec();
+        let network_globals = NetworkGlobals::<MinimalEthSpec>::new_test_globals(vec![], &log);
+
+        let custody_subnets = data_column_subnets_to_subscribe(&network_globals, false, &spec);
+        assert_eq!(custody_subnets.len() as u64, spec.custody_requirement);
+
+        let all_subnets = data_column_subnets_to_subscribe(&network_globals, true, &spec)
+            .into_iter()
+            .collect::<HashSet<_>>();
+        assert_eq!(
+            all_subnets,
+            (0..spec.data_column_sidecar_subnet_count)
+                .map(DataColumnSubnetId::new)
+                .collect::<HashSet<_>>()
+        );
+
+        let custody_columns = network_globals.custody_columns(&spec);
+        assert_eq!(
+            custody_columns.len(),
+            spec.custody_requirement as usize * spec.data_columns_per_subnet()
+        );
+        let expected_columns = custody_subnets
+            .iter()
+            .flat_map(|subnet| subnet.columns::<MinimalEthSpec>(&spec))
+            .collect::<HashSet<_>>();
+        assert_eq!(
+            custody_columns.into_iter().collect::<HashSet<_>>(),
+            expected_columns
+        );
+    }
 }
@@ -140,6 +140,10 @@ pub struct BackFillSync<T: BeaconChainTypes> {
     /// This signifies that we are able to attempt to restart a failed chain.
     restart_failed_sync: bool,
 
+    /// Set when syncing was paused to allow blocks to be imported from era files, after which
+    /// syncing must restart from the new oldest block.
+    paused_for_era_import: bool,
+
     /// Reference to the beacon chain to obtain initial starting points for the backfill sync.
     beacon_chain: Arc<BeaconChain<T>>,
 
@@ -191,6 +195,7 @@ impl<T: BeaconChainTypes> BackFillSync<T> {
             validated_batches: 0,
             participating_peers: HashSet::new(),
             restart_failed_sync: false,
+            paused_for_era_import: false,
             beacon_chain,
             log,
         };
@@ -216,9 +221,33 @@ impl<T: BeaconChainTypes> BackFillSync<T> {
         &mut self,
         network: &mut SyncNetworkContext<T>,
     ) -> Result<SyncStart, BackFillError> {
+        if self.beacon_chain.is_era_import_in_progress() {
+            self.pause_for_era_import();
+            return Ok(SyncStart::NotSyncing);
+        }
+
         match self.state() {
             BackFillState::Syncing => {} // already syncing ignore.
             BackFillState::Paused => {
+                if std::mem::take(&mut self.paused_for_era_import) {
+                    // The era import has moved the oldest block, so restart from there.
+                    match self.reset_start_epoch() {
+                        Err(ResetEpochError::SyncCompleted) => {
+                            info!(self.log, "Backfill sync completed by era import");
+                            self.set_state(BackFillState::Completed);
+                            return Ok(SyncStart::NotSyncing);
+                        }
+                        Err(ResetEpochError::NotRequired) => {
+                            self.set_state(BackFillState::NotRequired);
+                            return Ok(SyncStart::NotSyncing);
+                        }
+                        Ok(()) => {
+                            self.processing_target = self.current_start;
+                            self.to_be_downloaded = self.current_start;
+                        }
+                    }
+                }
+
                 if self
                     .network_globals
                     .peers
@@ -470,17 +499,25 @@ impl<T: BeaconChainTypes> BackFillSync<T> {
         }
     }
 
-    /// The syncing process has failed.
+    /// Pauses syncing whilst blocks are imported from era files.
     ///
-    /// This resets past variables, to allow for a fresh start when resuming.
-    fn fail_sync(&mut self, error: BackFillError) -> Result<(), BackFillError> {
-        // Some errors shouldn't fail the chain.
-        if matches!(error, BackFillError::Paused) {
-            return Ok(());
+    /// All batches are discarded, as the era import moves the oldest block. Syncing restarts from
+    /// the new oldest block once the era import has completed.
+    fn pause_for_era_import(&mut self) {
+        if !matches!(self.state(), BackFillState::Syncing | BackFillState::Paused)
+            || self.paused_for_era_import
+        {
+            return;
         }
 
-        // Set the state
-        self.set_state(BackFillState::Failed);
+        debug!(self.log, "Backfill sync paused for era import"; "processed_epochs" => self.validated_batches);
+        self.set_state(BackFillState::Paused);
+        self.reset_batches();
+        self.paused_for_era_import = true;
+    }
+
+    /// Removes all batches and active requests, resetting the downloading and processing targets.
+    fn reset_batches(&mut self) {
         // Remove all batches and active requests and participating peers.
         self.batches.clear();
         self.active_requests.clear();
@@ -494,6 +531,20 @@ impl<T: BeaconChainTypes> BackFillSync<T> {
         self.current_processing_batch = None;
 
         // NOTE: Lets keep validated_batches for posterity
+    }
+
+    /// The syncing process has failed.
+    ///
+    /// This resets past variables, to allow for a fresh start when resuming.
+    fn fail_sync(&mut self, error: BackFillError) -> Result<(), BackFillError> {
+        // Some errors shouldn't fail the chain.
+        if matches!(error, BackFillError::Paused) {
+            return Ok(());
+        }
+
+        // Set the state
+        self.set_state(BackFillState::Failed);
+        self.reset_batches();
 
         // Emit the log here
         error!(self.log, "Backfill sync failed"; "error" => ?error);
@@ -1042,6 +1093,11 @@ impl<T: BeaconChainTypes> BackFillSync<T> {
             return Ok(());
         }
 
+        if self.beacon_chain.is_era_import_in_progress() {
+            self.pause_for_era_import();
+            return Ok(());
+        }
+
         // find the next pending batch and request it from the peer
 
         // randomize the peers for load balancing
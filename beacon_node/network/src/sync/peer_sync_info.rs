@@ -33,6 +33,17 @@ pub fn remote_sync_type<T: BeaconChainTypes>(
     let near_range_start = local.head_slot - SLOT_IMPORT_TOLERANCE as u64;
     let near_range_end = local.head_slot + SLOT_IMPORT_TOLERANCE as u64;
 
+    // Once a shadow fork has diverged, the chains of peers ahead of us are for the network's fork
+    // and can't be imported. Consider them synced, so that the node keeps following its own chain
+    // rather than range syncing indefinitely.
+    if chain.is_shadow_fork_diverged() {
+        return if remote.head_slot < near_range_start {
+            PeerSyncType::Behind
+        } else {
+            PeerSyncType::FullySynced
+        };
+    }
+
     match remote.finalized_epoch.cmp(&local.finalized_epoch) {
         Ordering::Less => {
             // The node has a lower finalized epoch, their chain is not useful to us. There are two
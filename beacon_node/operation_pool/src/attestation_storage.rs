@@ -1,5 +1,6 @@
 use crate::AttestationStats;
 use itertools::Itertools;
+use ssz::Encode;
 use std::collections::{BTreeMap, HashMap};
 use types::{
     attestation::{AttestationBase, AttestationElectra},
@@ -13,7 +14,7 @@ pub struct CheckpointKey {
     pub target_epoch: Epoch,
 }
 
-#[derive(Debug, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct CompactAttestationData {
     pub slot: Slot,
     pub index: u64,
@@ -21,8 +22,11 @@ pub struct CompactAttestationData {
     pub target_root: Hash256,
 }
 
-#[superstruct(variants(Base, Electra), variant_attributes(derive(Debug, PartialEq,)))]
-#[derive(Debug, PartialEq)]
+#[superstruct(
+    variants(Base, Electra),
+    variant_attributes(derive(Debug, Clone, PartialEq,))
+)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct CompactIndexedAttestation<E: EthSpec> {
     pub attesting_indices: Vec<u64>,
     #[superstruct(only(Base), partial_getter(rename = "aggregation_bits_base"))]
@@ -48,12 +52,12 @@ pub struct CompactAttestationRef<'a, E: EthSpec> {
     pub indexed: &'a CompactIndexedAttestation<E>,
 }
 
-#[derive(Debug, Default, PartialEq)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct AttestationMap<E: EthSpec> {
     checkpoint_map: HashMap<CheckpointKey, AttestationDataMap<E>>,
 }
 
-#[derive(Debug, Default, PartialEq)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct AttestationDataMap<E: EthSpec> {
     attestations: HashMap<CompactAttestationData, Vec<CompactIndexedAttestation<E>>>,
 }
@@ -447,6 +451,23 @@ impl<E: EthSpec> AttestationMap<E> {
                 acc
             })
     }
+
+    /// Estimate the memory used by the attestations in the map, excluding the map itself.
+    pub fn estimated_bytes(&self) -> usize {
+        self.iter()
+            .map(|attestation| {
+                let indexed = attestation.indexed;
+                let aggregation_bits = match indexed {
+                    CompactIndexedAttestation::Base(att) => att.aggregation_bits.ssz_bytes_len(),
+                    CompactIndexedAttestation::Electra(att) => att.aggregation_bits.ssz_bytes_len(),
+                };
+                std::mem::size_of::<CompactAttestationData>()
+                    + std::mem::size_of::<CompactIndexedAttestation<E>>()
+                    + std::mem::size_of_val(indexed.attesting_indices().as_slice())
+                    + aggregation_bits
+            })
+            .sum()
+    }
 }
 
 impl<E: EthSpec> AttestationDataMap<E> {
@@ -28,6 +28,7 @@ use max_cover::maximum_cover;
 use parking_lot::{RwLock, RwLockWriteGuard};
 use rand::seq::SliceRandom;
 use rand::thread_rng;
+use ssz::Encode;
 use state_processing::per_block_processing::errors::AttestationValidationError;
 use state_processing::per_block_processing::{
     get_slashable_indices_modular, verify_exit, VerifySignatures,
@@ -36,6 +37,7 @@ use state_processing::{SigVerifiedOp, VerifyOperation};
 use std::collections::{hash_map::Entry, HashMap, HashSet};
 use std::marker::PhantomData;
 use std::ptr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use types::{
     sync_aggregate::Error as SyncAggregateError, typenum::Unsigned, AbstractExecPayload,
     Attestation, AttestationData, AttesterSlashing, BeaconState, BeaconStateError, ChainSpec,
@@ -61,6 +63,10 @@ pub struct OperationPool<E: EthSpec + Default> {
     bls_to_execution_changes: RwLock<BlsToExecutionChanges<E>>,
     /// Reward cache for accelerating attestation packing.
     reward_cache: RwLock<RewardCache>,
+    /// The time of the most recent insertion of each type of operation.
+    ///
+    /// This is not persisted, so it is empty for operations loaded from disk.
+    insertion_times: RwLock<InsertionTimes>,
     _phantom: PhantomData<E>,
 }
 
@@ -90,6 +96,22 @@ pub struct AttestationStats {
     pub max_aggregates_per_data: usize,
 }
 
+/// The time of the most recent insertion of each type of operation, as a duration since the
+/// UNIX epoch.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct InsertionTimes {
+    pub attestations: Option<Duration>,
+    pub sync_contributions: Option<Duration>,
+    pub attester_slashings: Option<Duration>,
+    pub proposer_slashings: Option<Duration>,
+    pub voluntary_exits: Option<Duration>,
+    pub bls_to_execution_changes: Option<Duration>,
+}
+
+fn now() -> Option<Duration> {
+    SystemTime::now().duration_since(UNIX_EPOCH).ok()
+}
+
 impl From<SyncAggregateError> for OpPoolError {
     fn from(e: SyncAggregateError) -> Self {
         OpPoolError::SyncAggregateError(e)
@@ -148,6 +170,7 @@ impl<E: EthSpec> OperationPool<E> {
                 }
             }
         };
+        self.insertion_times.write().sync_contributions = now();
         Ok(())
     }
 
@@ -206,9 +229,20 @@ impl<E: EthSpec> OperationPool<E> {
         self.attestations
             .write()
             .insert(attestation, attesting_indices);
+        self.insertion_times.write().attestations = now();
         Ok(())
     }
 
+    /// Returns a new pool holding a copy of this pool's attestations and no other operations.
+    ///
+    /// Attestations can be inserted into and packed from the copy without modifying this pool.
+    pub fn clone_attestations(&self) -> Self {
+        Self {
+            attestations: RwLock::new(self.attestations.read().clone()),
+            ..Self::default()
+        }
+    }
+
     /// Total number of attestations in the pool, including attestations for the same data.
     pub fn num_attestations(&self) -> usize {
         self.attestation_stats().num_attestations
@@ -371,6 +405,7 @@ impl<E: EthSpec> OperationPool<E> {
             verified_proposer_slashing.as_inner().proposer_index(),
             verified_proposer_slashing,
         );
+        self.insertion_times.write().proposer_slashings = now();
     }
 
     /// Insert an attester slashing into the pool.
@@ -379,6 +414,7 @@ impl<E: EthSpec> OperationPool<E> {
         verified_slashing: SigVerifiedOp<AttesterSlashing<E>, E>,
     ) {
         self.attester_slashings.write().insert(verified_slashing);
+        self.insertion_times.write().attester_slashings = now();
     }
 
     /// Get proposer and attester slashings for inclusion in a block.
@@ -501,11 +537,17 @@ impl<E: EthSpec> OperationPool<E> {
         self.proposer_slashings.read().len()
     }
 
+    /// The time of the most recent insertion of each type of operation.
+    pub fn insertion_times(&self) -> InsertionTimes {
+        *self.insertion_times.read()
+    }
+
     /// Insert a voluntary exit that has previously been checked elsewhere.
     pub fn insert_voluntary_exit(&self, exit: SigVerifiedOp<SignedVoluntaryExit, E>) {
         self.voluntary_exits
             .write()
             .insert(exit.as_inner().message.validator_index, exit);
+        self.insertion_times.write().voluntary_exits = now();
     }
 
     /// Get a list of voluntary exits for inclusion in a block.
@@ -565,9 +607,14 @@ impl<E: EthSpec> OperationPool<E> {
         verified_change: SigVerifiedOp<SignedBlsToExecutionChange, E>,
         received_pre_capella: ReceivedPreCapella,
     ) -> bool {
-        self.bls_to_execution_changes
+        let inserted = self
+            .bls_to_execution_changes
             .write()
-            .insert(verified_change, received_pre_capella)
+            .insert(verified_change, received_pre_capella);
+        if inserted {
+            self.insertion_times.write().bls_to_execution_changes = now();
+        }
+        inserted
     }
 
     /// Get a list of execution changes for inclusion in a block.
@@ -662,6 +709,57 @@ impl<E: EthSpec> OperationPool<E> {
         self.voluntary_exits.read().len()
     }
 
+    /// Total number of BLS to execution changes in the pool.
+    pub fn num_bls_to_execution_changes(&self) -> usize {
+        self.bls_to_execution_changes.read().iter_lifo().count()
+    }
+
+    /// Estimate the memory used by the operations in the pool.
+    ///
+    /// Operations other than attestations are measured by their SSZ encoded size, and the
+    /// overhead of the maps which index the operations is not included.
+    pub fn estimated_bytes(&self) -> usize {
+        let attestations = self.attestations.read().estimated_bytes();
+        let sync_contributions = self
+            .sync_contributions
+            .read()
+            .values()
+            .flatten()
+            .map(Encode::ssz_bytes_len)
+            .sum::<usize>();
+        let attester_slashings = self
+            .attester_slashings
+            .read()
+            .iter()
+            .map(|op| op.as_inner().ssz_bytes_len())
+            .sum::<usize>();
+        let proposer_slashings = self
+            .proposer_slashings
+            .read()
+            .values()
+            .map(|op| op.as_inner().ssz_bytes_len())
+            .sum::<usize>();
+        let voluntary_exits = self
+            .voluntary_exits
+            .read()
+            .values()
+            .map(|op| op.as_inner().ssz_bytes_len())
+            .sum::<usize>();
+        let bls_to_execution_changes = self
+            .bls_to_execution_changes
+            .read()
+            .iter_lifo()
+            .map(|op| op.as_inner().ssz_bytes_len())
+            .sum::<usize>();
+
+        attestations
+            + sync_contributions
+            + attester_slashings
+            + proposer_slashings
+            + voluntary_exits
+            + bls_to_execution_changes
+    }
+
     /// Returns all known `Attestation` objects.
     ///
     /// This method may return objects that are invalid for block inclusion.
@@ -35,6 +35,17 @@ pub fn cli_app() -> Command {
                 .action(ArgAction::Set)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("network-key-file")
+                .long("network-key-file")
+                .value_name("FILE")
+                .help("A file containing the secp256k1 network key to use, either as raw bytes \
+                       or hex encoded. This pins the node's peer ID and ENR node ID, e.g. for \
+                       fleet deployments. The key in the network directory, and any key staged \
+                       by rotate-network-key, is ignored.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         .arg(
             Arg::new("freezer-dir")
                 .long("freezer-dir")
@@ -63,6 +74,17 @@ pub fn cli_app() -> Command {
                        This will also advertise the beacon node as being long-lived subscribed to all subnets.")
                 .display_order(0)
         )
+        .arg(
+            Arg::new("subscribe-all-data-column-subnets")
+                .long("subscribe-all-data-column-subnets")
+                .action(ArgAction::SetTrue)
+                .help_heading(FLAG_HEADER)
+                .help("Subscribe to the gossip topics of all data column subnets, rather than only \
+                       those of the subnets this node custodies. This does not change the columns \
+                       which the node custodies. This is an experimental flag for PeerDAS devnets.")
+                .hide(true)
+                .display_order(0)
+        )
         .arg(
             Arg::new("import-all-attestations")
                 .long("import-all-attestations")
@@ -181,6 +203,40 @@ pub fn cli_app() -> Command {
                 .action(ArgAction::Set)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("target-subnet-peers")
+                .long("target-subnet-peers")
+                .value_name("COUNT")
+                .help("The target number of peers on each attestation and sync committee subnet \
+                       that the node needs. Peers advertising these subnets are searched for via \
+                       discovery whenever there are fewer than this many.")
+                .action(ArgAction::Set)
+                .default_value("3")
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("inbound-connection-rate-limit")
+                .long("inbound-connection-rate-limit")
+                .value_name("COUNT")
+                .help("The maximum number of inbound connections accepted per second. Connections \
+                       in excess of this are rejected, so that a flood of connections cannot \
+                       churn through the node's peers. Trusted and static peers are exempt from \
+                       the limit. Set to 0 to disable the limit.")
+                .action(ArgAction::Set)
+                .default_value("10")
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("new-peer-grace-period")
+                .long("new-peer-grace-period")
+                .value_name("SECONDS")
+                .help("The time after connecting during which a peer is considered new. When the \
+                       node has too many peers, new peers are disconnected before established \
+                       peers.")
+                .action(ArgAction::Set)
+                .default_value("300")
+                .display_order(0)
+        )
         .arg(
             Arg::new("boot-nodes")
                 .long("boot-nodes")
@@ -190,6 +246,41 @@ pub fn cli_app() -> Command {
                 .action(ArgAction::Set)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("boot-nodes-url")
+                .long("boot-nodes-url")
+                .value_name("URL")
+                .help("An HTTPS URL from which a list of boot node ENRs, in the format of \
+                       boot_enr.yaml, is fetched at startup and every hour thereafter. The fetched \
+                       boot nodes are used in addition to the built-in boot nodes, which remain \
+                       in use if the list cannot be fetched. The list must be signed by the key \
+                       given with --boot-nodes-public-key, with the signature served at the same \
+                       URL with .sig appended.")
+                .requires("boot-nodes-public-key")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("boot-nodes-public-key")
+                .long("boot-nodes-public-key")
+                .value_name("PUBKEY")
+                .help("The 0x-prefixed BLS public key which must have signed the boot node list \
+                       fetched from --boot-nodes-url. The signature is across the SHA256 hash of \
+                       the list.")
+                .requires("boot-nodes-url")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("boot-nodes-allowlist")
+                .long("boot-nodes-allowlist")
+                .value_name("NODE_IDS")
+                .help("Comma-separated hex-encoded node IDs. If set, only the boot nodes fetched \
+                       from --boot-nodes-url with one of these node IDs are used.")
+                .requires("boot-nodes-url")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         .arg(
             Arg::new("network-load")
                 .long("network-load")
@@ -334,6 +425,16 @@ pub fn cli_app() -> Command {
                 .help("Disables the quic transport. The node will rely solely on the TCP transport for libp2p connections.")
                 .display_order(0)
         )
+        .arg(
+            Arg::new("disable-gossipsub-idontwant")
+                .long("disable-gossipsub-idontwant")
+                .action(ArgAction::SetTrue)
+                .help_heading(FLAG_HEADER)
+                .help("Disables sending gossipsub IDONTWANT messages to peers. IDONTWANT messages \
+                       ask peers not to forward duplicates of messages we have already received, \
+                       which reduces bandwidth usage. IDONTWANT messages from peers are still respected.")
+                .display_order(0)
+        )
         .arg(
             Arg::new("disable-peer-scoring")
                 .long("disable-peer-scoring")
@@ -353,6 +454,39 @@ pub fn cli_app() -> Command {
                 .display_order(0)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("trusted-mesh-peers")
+                .long("trusted-mesh-peers")
+                .value_name("PEER_IDS")
+                .help("One or more comma-delimited peer ids, such as private relays, which blocks \
+                       and blobs published by this node are sent to before the rest of the \
+                       network. These peers are treated as trusted peers. They should also be \
+                       given as --static-peers so that they are kept connected.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("trusted-mesh-delay")
+                .long("trusted-mesh-delay")
+                .value_name("MILLISECONDS")
+                .help("The delay between publishing a block or blob to the --trusted-mesh-peers \
+                       and publishing it to the rest of the network.")
+                .action(ArgAction::Set)
+                .default_value("100")
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("static-peers")
+                .long("static-peers")
+                .value_name("MULTIADDR")
+                .help("One or more comma-delimited multiaddrs of peers to always stay connected to. \
+                       Each multiaddr must end with the peer id, e.g. /ip4/1.2.3.4/tcp/9000/p2p/<peer-id>. \
+                       Static peers are trusted, are never disconnected to make room for other peers, \
+                       do not count towards the peer limits and are re-dialed with a backoff when \
+                       disconnected.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         .arg(
             Arg::new("genesis-backfill")
                 .long("genesis-backfill")
@@ -361,6 +495,17 @@ pub fn cli_app() -> Command {
                 .help_heading(FLAG_HEADER)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("import-era-dir")
+                .long("import-era-dir")
+                .value_name("DIR")
+                .help("Directory of era files from which to read historic blocks during backfill \
+                       sync, instead of downloading them from peers. Blocks within the data \
+                       availability window are still downloaded from peers, as era files do not \
+                       contain blobs.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         .arg(
             Arg::new("enable-private-discovery")
                 .long("enable-private-discovery")
@@ -577,6 +722,31 @@ pub fn cli_app() -> Command {
                 .display_order(0)
                 .default_value_if("enable_http", ArgPredicate::IsPresent, "true")
         )
+        .arg(
+            Arg::new("http-enable-profiling")
+                .long("http-enable-profiling")
+                .requires("enable_http")
+                .help("Serves the /lighthouse/debug/profile endpoint, which captures a CPU profile \
+                    of the beacon node over a requested duration. Sampling adds a small CPU \
+                    overhead whilst a profile is being captured. Intended for diagnosing \
+                    performance problems, only supported on Linux.")
+                .action(ArgAction::SetTrue)
+                .help_heading(FLAG_HEADER)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("http-route-timeouts")
+                .long("http-route-timeouts")
+                .requires("enable_http")
+                .value_name("ROUTE=SECONDS,...")
+                .help("Comma-separated list of timeouts for HTTP API routes, e.g. \
+                    \"/eth/v1/beacon/rewards=30,/lighthouse/analysis=120\". Read-only requests to \
+                    a path starting with ROUTE fail if they take longer than SECONDS and any work \
+                    still in progress for them is cancelled. Requests which publish or submit \
+                    messages are never cancelled. The longest matching route applies.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         /* Prometheus metrics HTTP server related arguments */
         .arg(
             Arg::new("metrics")
@@ -723,6 +893,18 @@ pub fn cli_app() -> Command {
                 .action(ArgAction::Set)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("eth1-fallback-endpoints")
+                .long("eth1-fallback-endpoints")
+                .value_name("EXECUTION-ENDPOINTS")
+                .help("One or more comma-delimited JSON-RPC endpoints which are used, in order, to \
+                       download deposit contract logs if the execution endpoint fails to provide \
+                       them, for example because it has pruned historical logs. These endpoints \
+                       are not authenticated with the JWT secret, and are skipped if they are \
+                       not on the same chain as the execution endpoint.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         .arg(
             Arg::new("slots-per-restore-point")
                 .long("slots-per-restore-point")
@@ -744,6 +926,50 @@ pub fn cli_app() -> Command {
                 .action(ArgAction::Set)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("finality-stall-epochs")
+                .long("finality-stall-epochs")
+                .value_name("EPOCHS")
+                .help("The number of epochs without finalization after which the chain is \
+                       considered stalled. Stalls are reported via the `finality_stall` event and \
+                       the `beacon_finality_stall_active` metric.")
+                .default_value("5")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("finality-stall-safe-mode")
+                .long("finality-stall-safe-mode")
+                .help("Whilst the chain is failing to finalize, double the size of the state \
+                       cache and defer blob pruning until it finalizes again. This uses more \
+                       memory during a stall.")
+                .action(ArgAction::SetTrue)
+                .help_heading(FLAG_HEADER)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("clock-skew-threshold-ms")
+                .long("clock-skew-threshold-ms")
+                .value_name("MILLIS")
+                .help("The estimated skew of the local clock, in milliseconds, above which a \
+                       warning is logged and the `/lighthouse/health` API reports \
+                       `clock_skew_detected`. The skew is estimated from the arrival times of \
+                       blocks and attestations from peers, and from the NTP server if \
+                       `--ntp-server` is set.")
+                .default_value("2000")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("ntp-server")
+                .long("ntp-server")
+                .value_name("ADDRESS")
+                .help("An NTP server against which to periodically check the local clock, e.g. \
+                       `pool.ntp.org`. The port defaults to 123. Without an NTP server, a local \
+                       clock which is running behind can only be partially detected.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         .arg(
             Arg::new("block-cache-size")
                 .long("block-cache-size")
@@ -791,7 +1017,8 @@ pub fn cli_app() -> Command {
                 .value_name("EXECUTION-JWT")
                 .alias("jwt-secrets")
                 .help("File path which contains the hex-encoded JWT secret for the \
-                       execution endpoint provided in the --execution-endpoint flag.")
+                       execution endpoint provided in the --execution-endpoint flag. Changes to \
+                       the file are applied without restarting.")
                 .requires("execution-endpoint")
                 .action(ArgAction::Set)
                 .display_order(0)
@@ -862,6 +1089,39 @@ pub fn cli_app() -> Command {
                 .action(ArgAction::Set)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("engine-record-dir")
+                .long("engine-record-dir")
+                .value_name("DIR")
+                .help("Record every engine API request and response to files in this directory, \
+                       for debugging disagreements with the execution engine. The JWT is never \
+                       recorded. Files are rotated once they reach the size given by \
+                       --engine-record-max-size, and at most --engine-record-max-number files \
+                       are kept.")
+                .requires("execution-endpoint")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("engine-record-max-size")
+                .long("engine-record-max-size")
+                .value_name("SIZE")
+                .help("The maximum size (in MB) of each engine API record file.")
+                .requires("engine-record-dir")
+                .default_value("100")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("engine-record-max-number")
+                .long("engine-record-max-number")
+                .value_name("COUNT")
+                .help("The maximum number of engine API record files to keep.")
+                .requires("engine-record-dir")
+                .default_value("10")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         .arg(
             Arg::new("builder-header-timeout")
                 .long("builder-header-timeout")
@@ -922,11 +1182,23 @@ pub fn cli_app() -> Command {
         .arg(
             Arg::new("auto-compact-db")
                 .long("auto-compact-db")
-                .help("Enable or disable automatic compaction of the database on finalization.")
+                .help("Enable or disable automatic compaction of the database. Compactions are \
+                       scheduled after finalization and run once the node is idle.")
                 .action(ArgAction::Set)
                 .default_value("true")
                 .display_order(0)
         )
+        .arg(
+            Arg::new("auto-compact-db-io-limit")
+                .long("auto-compact-db-io-limit")
+                .value_name("PERCENT")
+                .help("The maximum percentage of time that automatic database compaction may \
+                       spend compacting. Compaction pauses between steps to stay within this \
+                       limit, bounding its disk I/O. Must be between 1 and 100.")
+                .action(ArgAction::Set)
+                .default_value("50")
+                .display_order(0)
+        )
         .arg(
             Arg::new("prune-payloads")
                 .long("prune-payloads")
@@ -977,7 +1249,9 @@ pub fn cli_app() -> Command {
                 .long("graffiti")
                 .help(
                     "Specify your custom graffiti to be included in blocks. \
-                    Defaults to the current version and commit, truncated to fit in 32 bytes. "
+                    Defaults to the client codes and commits of the execution client and \
+                    Lighthouse (e.g. GE1234LH5678), or to the current Lighthouse version and \
+                    commit if the execution client does not report its version."
                 )
                 .value_name("GRAFFITI")
                 .action(ArgAction::Set)
@@ -1110,6 +1384,17 @@ pub fn cli_app() -> Command {
                 .default_missing_value("true")
                 .display_order(0)
         )
+        .arg(
+            Arg::new("slasher-backfill")
+                .long("slasher-backfill")
+                .help("Provide the slasher with the attestations and proposals of blocks \
+                       imported by backfill sync, which would otherwise only be seen by \
+                       the slasher if they were received on gossip.")
+                .action(ArgAction::SetTrue)
+                .help_heading(FLAG_HEADER)
+                .requires("slasher")
+                .display_order(0)
+        )
         .arg(
             Arg::new("slasher-backend")
                 .long("slasher-backend")
@@ -1120,14 +1405,30 @@ pub fn cli_app() -> Command {
                 .requires("slasher")
                 .display_order(0)
         )
+        .arg(
+            Arg::new("slasher-remote")
+                .long("slasher-remote")
+                .help("Queue verified attestations and blocks for a slasher running in another \
+                       process, which takes them via the /lighthouse/slasher/dequeue HTTP API \
+                       endpoint and publishes slashings via the operation pool endpoints. \
+                       Requires the HTTP API to be enabled.")
+                .action(ArgAction::SetTrue)
+                .help_heading(FLAG_HEADER)
+                .conflicts_with("slasher")
+                .requires("http")
+                .display_order(0)
+        )
         .arg(
             Arg::new("wss-checkpoint")
                 .long("wss-checkpoint")
                 .help(
                     "Specify a weak subjectivity checkpoint in `block_root:epoch` format to verify \
-                     the node's sync against. The block root should be 0x-prefixed. Note that this \
-                     flag is for verification only, to perform a checkpoint sync from a recent \
-                     state use --checkpoint-sync-url."
+                     the node's sync against. The block root should be 0x-prefixed. Blocks which \
+                     finalize a chain that does not include the checkpoint are rejected and the node \
+                     shuts down. The violation is recorded in the database and reported by the \
+                     /lighthouse/health API until the database is purged. Note that this flag is \
+                     for verification only, to perform a checkpoint sync from a recent state use \
+                     --checkpoint-sync-url."
                 )
                 .value_name("WSS_CHECKPOINT")
                 .action(ArgAction::Set)
@@ -1193,6 +1494,19 @@ pub fn cli_app() -> Command {
                 .help_heading(FLAG_HEADER)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("shadow-fork")
+                .long("shadow-fork")
+                .value_name("CONFIG_PATH")
+                .help("Run a shadow fork using the config.yaml at this path. Blocks are processed \
+                    with the fork schedule and execution settings of this config, whilst the \
+                    node follows the gossip of the network given by --network or --testnet-dir. \
+                    Once the fork schedules diverge, the node only follows blocks produced by its \
+                    own validators: messages are not published, and blocks for the network's fork \
+                    are ignored rather than synced. Only use this flag for testing forks.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         .arg(
             Arg::new("reconstruct-historic-states")
                 .long("reconstruct-historic-states")
@@ -1222,6 +1536,17 @@ pub fn cli_app() -> Command {
                 .action(ArgAction::Set)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("validator-monitor-withdrawal-address")
+                .long("validator-monitor-withdrawal-address")
+                .help("A comma-separated list of 0x-prefixed execution addresses. Validators \
+                    whose withdrawal credentials point at one of these addresses will receive \
+                    special monitoring and additional logging, including validators which \
+                    appear or change their withdrawal credentials while the node is running.")
+                .value_name("ADDRESSES")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         .arg(
             Arg::new("validator-monitor-file")
                 .long("validator-monitor-file")
@@ -1315,6 +1640,37 @@ pub fn cli_app() -> Command {
                 .conflicts_with("disable-proposer-reorgs")
                 .display_order(0)
         )
+        .arg(
+            Arg::new("unsafe-tuning")
+                .long("unsafe-tuning")
+                .help("Permit fork choice parameters to be changed from those of the network's \
+                       specification using --proposer-boost-percentage and \
+                       --committee-weight-override. This makes the node follow a different fork \
+                       choice rule to the rest of the network and must only be used on devnets.")
+                .action(ArgAction::SetTrue)
+                .help_heading(FLAG_HEADER)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("proposer-boost-percentage")
+                .long("proposer-boost-percentage")
+                .value_name("PERCENT")
+                .help("Percentage of the committee weight given to a timely block proposal, \
+                       replacing PROPOSER_SCORE_BOOST. Requires --unsafe-tuning.")
+                .requires("unsafe-tuning")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("committee-weight-override")
+                .long("committee-weight-override")
+                .value_name("GWEI")
+                .help("Committee weight used to compute the proposer boost, replacing the total \
+                       active balance divided by SLOTS_PER_EPOCH. Requires --unsafe-tuning.")
+                .requires("unsafe-tuning")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         .arg(
             Arg::new("prepare-payload-lookahead")
                 .long("prepare-payload-lookahead")
@@ -1560,4 +1916,20 @@ pub fn cli_app() -> Command {
                 .display_order(0)
         )
         .group(ArgGroup::new("enable_http").args(["http", "gui", "staking"]).multiple(true))
+        .subcommand(
+            Command::new("rotate-network-key")
+                .about("Generates a new network key which replaces the current key the next time \
+                        the beacon node starts, giving the node a new peer ID and ENR. The \
+                        previous key is kept as key.previous in the network directory. Known \
+                        peers and the routing table are retained across the rotation.")
+                .arg(
+                    Arg::new("help")
+                    .long("help")
+                    .short('h')
+                    .help("Prints help information")
+                    .action(ArgAction::HelpLong)
+                    .display_order(0)
+                    .help_heading(FLAG_HEADER)
+                )
+        )
 }
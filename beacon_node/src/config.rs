@@ -11,7 +11,7 @@ use clap_utils::{parse_flag, parse_required};
 use client::{ClientConfig, ClientGenesis};
 use directory::{DEFAULT_BEACON_NODE_DIR, DEFAULT_NETWORK_DIR, DEFAULT_ROOT_DIR};
 use environment::RuntimeContext;
-use execution_layer::DEFAULT_JWT_FILE;
+use execution_layer::{EngineRecordConfig, DEFAULT_JWT_FILE};
 use genesis::Eth1Endpoint;
 use http_api::TlsConfig;
 use lighthouse_network::ListenAddress;
@@ -28,7 +28,7 @@ use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::time::Duration;
 use types::graffiti::GraffitiString;
-use types::{Checkpoint, Epoch, EthSpec, Hash256, PublicKeyBytes};
+use types::{Address, Checkpoint, Epoch, EthSpec, Hash256, PublicKey, PublicKeyBytes};
 
 /// Gets the fully-initialized global client.
 ///
@@ -171,6 +171,23 @@ pub fn get_config<E: EthSpec>(
 
         client_config.http_api.enable_light_client_server =
             cli_args.get_flag("light-client-server");
+
+        client_config.http_api.enable_profiling = cli_args.get_flag("http-enable-profiling");
+
+        if let Some(route_timeouts) = cli_args.get_one::<String>("http-route-timeouts") {
+            client_config.http_api.route_timeouts = route_timeouts
+                .split(',')
+                .map(|route_timeout| {
+                    let (route, seconds) = route_timeout
+                        .split_once('=')
+                        .ok_or_else(|| format!("Invalid HTTP route timeout: {}", route_timeout))?;
+                    let seconds = seconds
+                        .parse::<u64>()
+                        .map_err(|e| format!("Invalid HTTP route timeout {}: {:?}", seconds, e))?;
+                    Ok((route.trim().to_string(), Duration::from_secs(seconds)))
+                })
+                .collect::<Result<_, String>>()?;
+        }
     }
 
     if cli_args.get_flag("light-client-server") {
@@ -267,6 +284,14 @@ pub fn get_config<E: EthSpec>(
         client_config.eth1.purge_cache = true;
     }
 
+    if let Some(endpoints) = cli_args.get_one::<String>("eth1-fallback-endpoints") {
+        client_config.eth1.fallback_endpoints = endpoints
+            .split(',')
+            .map(SensitiveUrl::parse)
+            .collect::<Result<_, _>>()
+            .map_err(|e| format!("eth1-fallback-endpoints contains an invalid URL {:?}", e))?;
+    }
+
     if let Some(follow_distance) =
         clap_utils::parse_optional(cli_args, "eth1-cache-follow-distance")?
     {
@@ -357,6 +382,22 @@ pub fn get_config<E: EthSpec>(
             clap_utils::parse_required(cli_args, "execution-timeout-multiplier")?;
         el_config.execution_timeout_multiplier = Some(execution_timeout_multiplier);
 
+        if let Some(dir) = clap_utils::parse_optional::<PathBuf>(cli_args, "engine-record-dir")? {
+            let max_size_mb: u64 = clap_utils::parse_required(cli_args, "engine-record-max-size")?;
+            let max_files = clap_utils::parse_required(cli_args, "engine-record-max-number")?;
+            if max_size_mb == 0 || max_files == 0 {
+                return Err(
+                    "--engine-record-max-size and --engine-record-max-number must be non-zero"
+                        .to_string(),
+                );
+            }
+            el_config.engine_record = Some(EngineRecordConfig {
+                dir,
+                max_file_size: max_size_mb.saturating_mul(1024 * 1024),
+                max_files,
+            });
+        }
+
         client_config.eth1.endpoint = Eth1Endpoint::Auth {
             endpoint: execution_endpoint,
             jwt_path: secret_file,
@@ -428,6 +469,12 @@ pub fn get_config<E: EthSpec>(
             .map_err(|_| "auto-compact-db takes a boolean".to_string())?;
     }
 
+    client_config.store.compaction_io_limit_percent =
+        clap_utils::parse_required(cli_args, "auto-compact-db-io-limit")?;
+    if !(1..=100).contains(&client_config.store.compaction_io_limit_percent) {
+        return Err("auto-compact-db-io-limit must be between 1 and 100".to_string());
+    }
+
     if let Some(prune_payloads) = clap_utils::parse_optional(cli_args, "prune-payloads")? {
         client_config.store.prune_payloads = prune_payloads;
     }
@@ -438,6 +485,19 @@ pub fn get_config<E: EthSpec>(
         client_config.chain.epochs_per_migration = epochs_per_migration;
     }
 
+    let finality_stall_epochs: u64 = clap_utils::parse_required(cli_args, "finality-stall-epochs")?;
+    if finality_stall_epochs == 0 {
+        return Err("--finality-stall-epochs must be greater than 0".to_string());
+    }
+    client_config.chain.finality_stall_epochs = finality_stall_epochs;
+    client_config.chain.finality_stall_safe_mode = cli_args.get_flag("finality-stall-safe-mode");
+
+    client_config.chain.clock_skew_threshold = Duration::from_millis(clap_utils::parse_required(
+        cli_args,
+        "clock-skew-threshold-ms",
+    )?);
+    client_config.chain.ntp_server = clap_utils::parse_optional(cli_args, "ntp-server")?;
+
     if let Some(prune_blobs) = clap_utils::parse_optional(cli_args, "prune-blobs")? {
         client_config.store.prune_blobs = prune_blobs;
     }
@@ -531,6 +591,11 @@ pub fn get_config<E: EthSpec>(
 
     client_config.allow_insecure_genesis_sync = cli_args.get_flag("allow-insecure-genesis-sync");
 
+    if let Some(shadow_fork_path) = clap_utils::parse_optional::<PathBuf>(cli_args, "shadow-fork")?
+    {
+        client_config.shadow_fork = Some(types::Config::from_file(&shadow_fork_path)?);
+    }
+
     client_config.genesis = if eth2_network_config.genesis_state_is_known() {
         // Set up weak subjectivity sync, or start from the hardcoded genesis state.
         if let (Some(initial_state_path), Some(initial_block_path), opt_initial_blobs_path) = (
@@ -702,6 +767,10 @@ pub fn get_config<E: EthSpec>(
             slasher_config.broadcast = broadcast;
         }
 
+        if cli_args.get_flag("slasher-backfill") {
+            slasher_config.backfill = true;
+        }
+
         if let Some(backend) = clap_utils::parse_optional(cli_args, "slasher-backend")? {
             slasher_config.backend = backend;
         }
@@ -709,6 +778,10 @@ pub fn get_config<E: EthSpec>(
         client_config.slasher = Some(slasher_config);
     }
 
+    if cli_args.get_flag("slasher-remote") {
+        client_config.remote_slasher = true;
+    }
+
     if cli_args.get_flag("validator-monitor-auto") {
         client_config.validator_monitor.auto_register = true;
     }
@@ -744,6 +817,23 @@ pub fn get_config<E: EthSpec>(
             .extend_from_slice(&pubkeys);
     }
 
+    if let Some(addresses) = cli_args.get_one::<String>("validator-monitor-withdrawal-address") {
+        let addresses = addresses
+            .split(',')
+            .map(Address::from_str)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| {
+                format!(
+                    "Invalid --validator-monitor-withdrawal-address value: {:?}",
+                    e
+                )
+            })?;
+        client_config
+            .validator_monitor
+            .withdrawal_addresses
+            .extend_from_slice(&addresses);
+    }
+
     if let Some(count) =
         clap_utils::parse_optional(cli_args, "validator-monitor-individual-tracking-threshold")?
     {
@@ -797,6 +887,13 @@ pub fn get_config<E: EthSpec>(
         }
     }
 
+    if cli_args.get_flag("unsafe-tuning") {
+        client_config.chain.proposer_boost_percentage =
+            clap_utils::parse_optional(cli_args, "proposer-boost-percentage")?;
+        client_config.chain.committee_weight_override =
+            clap_utils::parse_optional(cli_args, "committee-weight-override")?;
+    }
+
     // Note: This overrides any previous flags that enable this option.
     if cli_args.get_flag("disable-deposit-contract-sync") {
         client_config.sync_eth1_chain = false;
@@ -850,6 +947,8 @@ pub fn get_config<E: EthSpec>(
         client_config.chain.genesis_backfill = true;
     }
 
+    client_config.chain.import_era_dir = clap_utils::parse_optional(cli_args, "import-era-dir")?;
+
     // Backfill sync rate-limiting
     client_config.beacon_processor.enable_backfill_rate_limiting =
         !cli_args.get_flag("disable-backfill-rate-limiting");
@@ -1123,17 +1222,20 @@ pub fn set_network_config(
     data_dir: &Path,
     log: &Logger,
 ) -> Result<(), String> {
-    // If a network dir has been specified, override the `datadir` definition.
-    if let Some(dir) = cli_args.get_one::<String>("network-dir") {
-        config.network_dir = PathBuf::from(dir);
-    } else {
-        config.network_dir = data_dir.join(DEFAULT_NETWORK_DIR);
-    };
+    config.network_dir = get_network_dir(cli_args, data_dir);
+
+    if let Some(network_key_file) = cli_args.get_one::<String>("network-key-file") {
+        config.network_key_file = Some(PathBuf::from(network_key_file));
+    }
 
     if parse_flag(cli_args, "subscribe-all-subnets") {
         config.subscribe_all_subnets = true;
     }
 
+    if parse_flag(cli_args, "subscribe-all-data-column-subnets") {
+        config.subscribe_all_data_column_subnets = true;
+    }
+
     if parse_flag(cli_args, "import-all-attestations") {
         config.import_all_attestations = true;
     }
@@ -1151,6 +1253,27 @@ pub fn set_network_config(
             .map_err(|_| format!("Invalid number of target peers: {}", target_peers_str))?;
     }
 
+    if let Some(target_subnet_peers_str) = cli_args.get_one::<String>("target-subnet-peers") {
+        config.target_subnet_peers = target_subnet_peers_str.parse::<usize>().map_err(|_| {
+            format!(
+                "Invalid number of target subnet peers: {}",
+                target_subnet_peers_str
+            )
+        })?;
+    }
+
+    if let Some(rate_limit_str) = cli_args.get_one::<String>("inbound-connection-rate-limit") {
+        config.inbound_connection_rate_limit = rate_limit_str
+            .parse::<u32>()
+            .map_err(|_| format!("Invalid inbound connection rate limit: {}", rate_limit_str))?;
+    }
+
+    if let Some(grace_period_str) = cli_args.get_one::<String>("new-peer-grace-period") {
+        config.new_peer_grace_period = grace_period_str
+            .parse::<u64>()
+            .map_err(|_| format!("Invalid new peer grace period: {}", grace_period_str))?;
+    }
+
     if let Some(value) = cli_args.get_one::<String>("network-load") {
         let network_load = value
             .parse::<u8>()
@@ -1183,6 +1306,35 @@ pub fn set_network_config(
         config.boot_nodes_multiaddr = multiaddrs;
     }
 
+    if let Some(boot_nodes_url) = cli_args.get_one::<String>("boot-nodes-url") {
+        if !boot_nodes_url.starts_with("https://") {
+            return Err(format!(
+                "--boot-nodes-url must be an HTTPS URL: {}",
+                boot_nodes_url
+            ));
+        }
+        config.boot_nodes_url = Some(boot_nodes_url.clone());
+    }
+
+    if let Some(public_key) = cli_args.get_one::<String>("boot-nodes-public-key") {
+        config.boot_nodes_public_key = Some(
+            PublicKey::from_str(public_key)
+                .map_err(|e| format!("Invalid --boot-nodes-public-key: {}", e))?,
+        );
+    }
+
+    if let Some(allowlist) = cli_args.get_one::<String>("boot-nodes-allowlist") {
+        config.boot_nodes_allowlist = allowlist
+            .split(',')
+            .map(|node_id| {
+                hex::decode(node_id.trim().trim_start_matches("0x"))
+                    .ok()
+                    .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+                    .ok_or_else(|| format!("Invalid node ID: {}", node_id))
+            })
+            .collect::<Result<_, _>>()?;
+    }
+
     if let Some(libp2p_addresses_str) = cli_args.get_one::<String>("libp2p-addresses") {
         config.libp2p_nodes = libp2p_addresses_str
             .split(',')
@@ -1212,6 +1364,42 @@ pub fn set_network_config(
         }
     }
 
+    if let Some(trusted_mesh_peers_str) = cli_args.get_one::<String>("trusted-mesh-peers") {
+        config.trusted_mesh_peers = trusted_mesh_peers_str
+            .split(',')
+            .map(|peer_id| {
+                peer_id
+                    .parse()
+                    .map_err(|_| format!("Invalid trusted mesh peer id: {}", peer_id))
+            })
+            .collect::<Result<Vec<PeerIdSerialized>, _>>()?;
+    }
+
+    if let Some(delay_str) = cli_args.get_one::<String>("trusted-mesh-delay") {
+        config.trusted_mesh_delay = delay_str
+            .parse::<u64>()
+            .map(Duration::from_millis)
+            .map_err(|_| format!("Invalid trusted mesh delay: {}", delay_str))?;
+    }
+
+    if let Some(static_peers_str) = cli_args.get_one::<String>("static-peers") {
+        config.static_peers = static_peers_str
+            .split(',')
+            .map(|multiaddr_str| {
+                let multiaddr: Multiaddr = multiaddr_str
+                    .parse()
+                    .map_err(|_| format!("Invalid Multiaddr: {}", multiaddr_str))?;
+                if lighthouse_network::peer_id_from_multiaddr(&multiaddr).is_none() {
+                    return Err(format!(
+                        "Static peer multiaddr must end with a peer id: {}",
+                        multiaddr_str
+                    ));
+                }
+                Ok(multiaddr)
+            })
+            .collect::<Result<Vec<Multiaddr>, _>>()?;
+    }
+
     if let Some(enr_udp_port_str) = cli_args.get_one::<String>("enr-udp-port") {
         config.enr_udp4_port = Some(
             enr_udp_port_str
@@ -1394,6 +1582,10 @@ pub fn set_network_config(
         config.disable_quic_support = true;
     }
 
+    if parse_flag(cli_args, "disable-gossipsub-idontwant") {
+        config.disable_gossipsub_idontwant = true;
+    }
+
     if parse_flag(cli_args, "disable-upnp") {
         config.upnp_enabled = false;
     }
@@ -1471,6 +1663,15 @@ pub fn get_data_dir(cli_args: &ArgMatches) -> PathBuf {
         .unwrap_or_else(|| PathBuf::from("."))
 }
 
+/// Gets the network directory, where the node's network key is stored.
+pub fn get_network_dir(cli_args: &ArgMatches, data_dir: &Path) -> PathBuf {
+    // If a network dir has been specified, override the `datadir` definition.
+    cli_args
+        .get_one::<String>("network-dir")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| data_dir.join(DEFAULT_NETWORK_DIR))
+}
+
 /// Get the `slots_per_restore_point` value to use for the database.
 ///
 /// Return `(sprp, set_explicitly)` where `set_explicitly` is `true` if the user provided the value.
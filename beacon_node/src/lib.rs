@@ -9,11 +9,13 @@ use beacon_chain::{
 use clap::ArgMatches;
 pub use cli::cli_app;
 pub use client::{Client, ClientBuilder, ClientConfig, ClientGenesis};
-pub use config::{get_config, get_data_dir, get_slots_per_restore_point, set_network_config};
+pub use config::{
+    get_config, get_data_dir, get_network_dir, get_slots_per_restore_point, set_network_config,
+};
 use environment::RuntimeContext;
 pub use eth2_config::Eth2Config;
 use slasher::{DatabaseBackendOverride, Slasher};
-use slog::{info, warn};
+use slog::{info, warn, Logger};
 use std::ops::{Deref, DerefMut};
 use std::sync::Arc;
 use types::{ChainSpec, Epoch, EthSpec, ForkName};
@@ -53,7 +55,8 @@ impl<E: EthSpec> ProductionBeaconNode<E> {
         context: RuntimeContext<E>,
         mut client_config: ClientConfig,
     ) -> Result<Self, String> {
-        let spec = context.eth2_config().spec.clone();
+        let mut spec = context.eth2_config().spec.clone();
+        let mut network_spec = None;
         let client_genesis = client_config.genesis.clone();
         let store_config = client_config.store.clone();
         let log = context.log().clone();
@@ -72,6 +75,26 @@ impl<E: EthSpec> ProductionBeaconNode<E> {
             )
         }
 
+        if let Some(shadow_fork) = &client_config.shadow_fork {
+            let shadow_spec = shadow_fork
+                .apply_to_chain_spec::<E>(&spec)
+                .ok_or("Shadow fork config is for a different preset")?;
+            if shadow_spec.genesis_fork_version != spec.genesis_fork_version {
+                return Err(
+                    "Shadow fork config must have the same genesis fork version as the network"
+                        .into(),
+                );
+            }
+            warn!(
+                log,
+                "Running a shadow fork";
+                "info" => "blocks are processed with a fork schedule which differs from the \
+                    network, only use for testing",
+                "config_name" => ?shadow_fork.config_name,
+            );
+            network_spec = Some(std::mem::replace(&mut spec, shadow_spec));
+        }
+
         if let Err(misaligned_forks) = validator_fork_epochs(&spec) {
             warn!(
                 log,
@@ -82,6 +105,17 @@ impl<E: EthSpec> ProductionBeaconNode<E> {
             );
         }
 
+        if client_config.chain.unsafe_tuning() {
+            client_config.chain.apply_unsafe_tuning(&mut spec);
+            warn!(
+                log,
+                "Fork choice parameters changed with --unsafe-tuning";
+                "info" => "fork choice deviates from the specification, only use on devnets",
+                "proposer_score_boost" => ?spec.proposer_score_boost,
+                "committee_weight_override" => ?spec.committee_weight_override,
+            );
+        }
+
         let builder = ClientBuilder::new(context.eth_spec_instance.clone())
             .runtime_context(context)
             .chain_spec(spec.clone())
@@ -95,6 +129,12 @@ impl<E: EthSpec> ProductionBeaconNode<E> {
                 log.clone(),
             )?;
 
+        let builder = if let Some(network_spec) = network_spec {
+            builder.network_spec(network_spec)
+        } else {
+            builder
+        };
+
         let builder = if let Some(mut slasher_config) = client_config.slasher.clone() {
             match slasher_config.override_backend() {
                 DatabaseBackendOverride::Success(old_backend) => {
@@ -209,6 +249,26 @@ fn validator_fork_epochs(spec: &ChainSpec) -> Result<(), Vec<(ForkName, Epoch)>>
     }
 }
 
+/// Stages a new network key, which replaces the current key the next time the beacon node starts.
+///
+/// This runs offline, a running beacon node keeps its identity until it is restarted.
+pub fn rotate_network_key(cli_args: &ArgMatches, log: &Logger) -> Result<(), String> {
+    if cli_args.get_one::<String>("network-key-file").is_some() {
+        return Err("The network key is pinned by --network-key-file and cannot be rotated".into());
+    }
+
+    let network_dir = get_network_dir(cli_args, &get_data_dir(cli_args));
+    let peer_id = lighthouse_network::stage_next_network_key(&network_dir)?;
+    info!(
+        log,
+        "Staged new network key";
+        "info" => "the key is used from the next time the beacon node starts",
+        "peer_id" => %peer_id,
+        "network_dir" => ?network_dir,
+    );
+    Ok(())
+}
+
 impl<E: EthSpec> Deref for ProductionBeaconNode<E> {
     type Target = ProductionClient<E>;
 
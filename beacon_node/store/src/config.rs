@@ -13,6 +13,7 @@ pub const DEFAULT_STATE_CACHE_SIZE: NonZeroUsize = new_non_zero_usize(128);
 pub const DEFAULT_HISTORIC_STATE_CACHE_SIZE: NonZeroUsize = new_non_zero_usize(1);
 pub const DEFAULT_EPOCHS_PER_BLOB_PRUNE: u64 = 1;
 pub const DEFAULT_BLOB_PUNE_MARGIN_EPOCHS: u64 = 0;
+pub const DEFAULT_COMPACTION_IO_LIMIT_PERCENT: u8 = 50;
 
 /// Database configuration parameters.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -31,6 +32,10 @@ pub struct StoreConfig {
     pub compact_on_init: bool,
     /// Whether to compact the database during database pruning.
     pub compact_on_prune: bool,
+    /// The maximum percentage of time that background compactions may spend compacting.
+    ///
+    /// Compactions pause between steps to stay within this limit, bounding their disk I/O.
+    pub compaction_io_limit_percent: u8,
     /// Whether to prune payloads on initialization and finalization.
     pub prune_payloads: bool,
     /// Whether to prune blobs older than the blob data availability boundary.
@@ -64,6 +69,7 @@ impl Default for StoreConfig {
             historic_state_cache_size: DEFAULT_HISTORIC_STATE_CACHE_SIZE,
             compact_on_init: false,
             compact_on_prune: true,
+            compaction_io_limit_percent: DEFAULT_COMPACTION_IO_LIMIT_PERCENT,
             prune_payloads: true,
             prune_blobs: true,
             epochs_per_blob_prune: DEFAULT_EPOCHS_PER_BLOB_PRUNE,
@@ -0,0 +1,332 @@
+//! Reading and writing of era files.
+//!
+//! Era files are the format used by clients to archive and distribute the history of the beacon
+//! chain. Each era file covers `SLOTS_PER_HISTORICAL_ROOT` slots and is a sequence of `e2store`
+//! records:
+//!
+//! ```text
+//! era := Version | CompressedSignedBeaconBlock* | CompressedBeaconState | SlotIndex(block)? |
+//!        SlotIndex(state)
+//! ```
+//!
+//! Era `N` contains the canonical blocks from slots `[(N - 1) * SLOTS_PER_HISTORICAL_ROOT,
+//! N * SLOTS_PER_HISTORICAL_ROOT)` and the state at slot `N * SLOTS_PER_HISTORICAL_ROOT`. Era 0
+//! contains only the genesis state.
+//!
+//! See https://github.com/status-im/nimbus-eth2/blob/stable/docs/e2store.md
+use snap::read::FrameDecoder;
+use snap::write::FrameEncoder;
+use std::fs::File;
+use std::io::{self, BufReader, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+use types::Slot;
+
+/// The `e2store` record types used by era files.
+pub const VERSION: [u8; 2] = [0x65, 0x32];
+pub const COMPRESSED_SIGNED_BEACON_BLOCK: [u8; 2] = [0x01, 0x00];
+pub const COMPRESSED_BEACON_STATE: [u8; 2] = [0x02, 0x00];
+pub const SLOT_INDEX: [u8; 2] = [0x69, 0x32];
+
+/// Length of the header preceding each record: 2 bytes of type, 4 bytes of length and 2 reserved
+/// bytes.
+pub const HEADER_LENGTH: u64 = 8;
+
+/// Returns the name of the file for `era`, e.g. `mainnet-01234-0a1b2c3d.era`.
+pub fn era_file_name(config_name: &str, era: u64, short_historical_root: &str) -> String {
+    format!("{config_name}-{era:05}-{short_historical_root}.era")
+}
+
+/// Parses a file name produced by `era_file_name`, returning the config name and era number.
+pub fn parse_era_file_name(file_name: &str) -> Option<(&str, u64)> {
+    let mut parts = file_name.strip_suffix(".era")?.rsplitn(3, '-');
+    let _short_historical_root = parts.next()?;
+    let era = parts.next()?.parse().ok()?;
+    let config_name = parts.next()?;
+    Some((config_name, era))
+}
+
+/// Writes `e2store` records, keeping track of the offset of each record within the file.
+pub struct E2StoreWriter<W: Write> {
+    writer: W,
+    position: u64,
+}
+
+impl<W: Write> E2StoreWriter<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            position: 0,
+        }
+    }
+
+    /// Write a record, returning the offset of its header.
+    pub fn write_record(&mut self, record_type: [u8; 2], data: &[u8]) -> io::Result<u64> {
+        let length = u32::try_from(data.len())
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "record is too large"))?;
+
+        let offset = self.position;
+        self.writer.write_all(&record_type)?;
+        self.writer.write_all(&length.to_le_bytes())?;
+        self.writer.write_all(&[0, 0])?;
+        self.writer.write_all(data)?;
+        self.position += HEADER_LENGTH + data.len() as u64;
+        Ok(offset)
+    }
+
+    /// Write an SSZ value compressed using the snappy framing format.
+    pub fn write_compressed(&mut self, record_type: [u8; 2], ssz_bytes: &[u8]) -> io::Result<u64> {
+        let mut encoder = FrameEncoder::new(vec![]);
+        encoder.write_all(ssz_bytes)?;
+        let compressed = encoder.into_inner().map_err(|e| e.into_error())?;
+        self.write_record(record_type, &compressed)
+    }
+
+    /// Write a slot index for the records at `offsets`, one per slot from `start_slot`.
+    ///
+    /// A `None` offset indicates that there is no record for the slot. Offsets are stored relative
+    /// to the start of the index.
+    pub fn write_slot_index(
+        &mut self,
+        start_slot: Slot,
+        offsets: &[Option<u64>],
+    ) -> io::Result<()> {
+        let index_position = self.position as i64;
+        let mut data = Vec::with_capacity((offsets.len() + 2) * 8);
+        data.extend_from_slice(&start_slot.as_u64().to_le_bytes());
+        for offset in offsets {
+            let relative = offset.map_or(0, |offset| offset as i64 - index_position);
+            data.extend_from_slice(&relative.to_le_bytes());
+        }
+        data.extend_from_slice(&(offsets.len() as i64).to_le_bytes());
+        self.write_record(SLOT_INDEX, &data)?;
+        Ok(())
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+/// Reads the blocks from an era file, using its slot indices to locate them.
+pub struct EraReader<R: Read + Seek> {
+    reader: R,
+    /// The slot of the era state, which is also the end of the block range (exclusive).
+    state_slot: Slot,
+    /// The offset of the block at each slot of the era, or `None` for skipped slots.
+    block_offsets: Vec<Option<u64>>,
+}
+
+impl EraReader<BufReader<File>> {
+    pub fn open(path: &Path) -> io::Result<Self> {
+        Self::new(BufReader::new(File::open(path)?))
+    }
+}
+
+impl<R: Read + Seek> EraReader<R> {
+    pub fn new(mut reader: R) -> io::Result<Self> {
+        // The state index, which contains a single offset, is always the final record.
+        let state_index_position = reader
+            .seek(SeekFrom::End(0))?
+            .checked_sub(HEADER_LENGTH + 3 * 8)
+            .ok_or_else(|| invalid_data("file is too short"))?;
+        let (state_slot, _) = read_slot_index(&mut reader, state_index_position)?;
+
+        // The block index precedes the state index, except in the genesis era which has no blocks.
+        let block_offsets = if state_slot == 0 {
+            vec![]
+        } else {
+            reader.seek(SeekFrom::Start(state_index_position - 8))?;
+            let count = u64::from_le_bytes(read_array(&mut reader)?);
+            let block_index_position = count
+                .checked_add(2)
+                .and_then(|words| words.checked_mul(8))
+                .and_then(|len| len.checked_add(HEADER_LENGTH))
+                .and_then(|len| state_index_position.checked_sub(len))
+                .ok_or_else(|| invalid_data("invalid block index"))?;
+            let (start_slot, offsets) = read_slot_index(&mut reader, block_index_position)?;
+            if start_slot + offsets.len() as u64 != state_slot {
+                return Err(invalid_data("block index does not precede the state"));
+            }
+            offsets
+        };
+
+        Ok(Self {
+            reader,
+            state_slot,
+            block_offsets,
+        })
+    }
+
+    /// The first slot covered by the era's blocks.
+    pub fn start_slot(&self) -> Slot {
+        self.state_slot - self.block_offsets.len() as u64
+    }
+
+    /// The slot of the era state, which follows the last slot covered by the era's blocks.
+    pub fn state_slot(&self) -> Slot {
+        self.state_slot
+    }
+
+    /// Returns the slots in the era which have a block.
+    pub fn block_slots(&self) -> impl DoubleEndedIterator<Item = Slot> + '_ {
+        let start_slot = self.start_slot();
+        self.block_offsets
+            .iter()
+            .enumerate()
+            .filter(|(_, offset)| offset.is_some())
+            .map(move |(i, _)| start_slot + i as u64)
+    }
+
+    /// Returns the SSZ bytes of the block at `slot`, or `None` if the slot was skipped.
+    pub fn read_block(&mut self, slot: Slot) -> io::Result<Option<Vec<u8>>> {
+        let Some(offset) = slot
+            .as_u64()
+            .checked_sub(self.start_slot().as_u64())
+            .and_then(|i| self.block_offsets.get(i as usize))
+            .copied()
+            .flatten()
+        else {
+            return Ok(None);
+        };
+
+        let (record_type, data) = read_record(&mut self.reader, offset)?;
+        if record_type != COMPRESSED_SIGNED_BEACON_BLOCK {
+            return Err(invalid_data("block index does not point to a block"));
+        }
+        let mut ssz_bytes = vec![];
+        FrameDecoder::new(data.as_slice()).read_to_end(&mut ssz_bytes)?;
+        Ok(Some(ssz_bytes))
+    }
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+fn read_array<const N: usize, R: Read>(reader: &mut R) -> io::Result<[u8; N]> {
+    let mut bytes = [0; N];
+    reader.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
+
+/// Read the record at `position`, returning its type and data.
+fn read_record<R: Read + Seek>(reader: &mut R, position: u64) -> io::Result<([u8; 2], Vec<u8>)> {
+    reader.seek(SeekFrom::Start(position))?;
+    let header: [u8; HEADER_LENGTH as usize] = read_array(reader)?;
+    let record_type = [header[0], header[1]];
+    let length = u32::from_le_bytes([header[2], header[3], header[4], header[5]]);
+
+    let mut data = vec![0; length as usize];
+    reader.read_exact(&mut data)?;
+    Ok((record_type, data))
+}
+
+/// Read the slot index at `position`, returning its start slot and the absolute offset of the
+/// record for each slot.
+fn read_slot_index<R: Read + Seek>(
+    reader: &mut R,
+    position: u64,
+) -> io::Result<(Slot, Vec<Option<u64>>)> {
+    let (record_type, data) = read_record(reader, position)?;
+    if record_type != SLOT_INDEX || data.len() < 16 || data.len() % 8 != 0 {
+        return Err(invalid_data("invalid slot index"));
+    }
+
+    let words = data
+        .chunks_exact(8)
+        .map(|word| i64::from_le_bytes(word.try_into().expect("chunk is 8 bytes")))
+        .collect::<Vec<_>>();
+    let (start_slot, rest) = words.split_first().expect("index has at least 2 words");
+    let (count, relative_offsets) = rest.split_last().expect("index has at least 2 words");
+    if *count as usize != relative_offsets.len() {
+        return Err(invalid_data("slot index count does not match its length"));
+    }
+
+    let offsets = relative_offsets
+        .iter()
+        .map(|&relative| {
+            if relative == 0 {
+                Ok(None)
+            } else {
+                u64::try_from(position as i64 + relative)
+                    .map(Some)
+                    .map_err(|_| invalid_data("slot index offset is out of bounds"))
+            }
+        })
+        .collect::<Result<_, _>>()?;
+    Ok((Slot::new(*start_slot as u64), offsets))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn slot_index_offsets_are_relative_to_index() {
+        let mut writer = E2StoreWriter::new(vec![]);
+        assert_eq!(writer.write_record(VERSION, &[]).unwrap(), 0);
+        let block_offset = writer
+            .write_record(COMPRESSED_SIGNED_BEACON_BLOCK, &[1, 2, 3])
+            .unwrap();
+        assert_eq!(block_offset, HEADER_LENGTH);
+
+        let index_position = writer.position;
+        writer
+            .write_slot_index(Slot::new(16), &[None, Some(block_offset)])
+            .unwrap();
+        let bytes = writer.into_inner();
+
+        let index = &bytes[index_position as usize..];
+        assert_eq!(&index[..2], &SLOT_INDEX);
+        assert_eq!(u32::from_le_bytes(index[2..6].try_into().unwrap()), 32);
+
+        let read_i64 = |i: usize| {
+            let start = HEADER_LENGTH as usize + i * 8;
+            i64::from_le_bytes(index[start..start + 8].try_into().unwrap())
+        };
+        assert_eq!(read_i64(0), 16);
+        assert_eq!(read_i64(1), 0);
+        assert_eq!(read_i64(2), block_offset as i64 - index_position as i64);
+        assert_eq!(read_i64(3), 2);
+    }
+
+    #[test]
+    fn read_written_era() {
+        let start_slot = Slot::new(8);
+        let blocks = [None, Some(vec![1; 100]), None, Some(vec![2; 50])];
+
+        let mut writer = E2StoreWriter::new(vec![]);
+        writer.write_record(VERSION, &[]).unwrap();
+        let block_offsets = blocks
+            .iter()
+            .map(|block| {
+                block.as_ref().map(|block| {
+                    writer
+                        .write_compressed(COMPRESSED_SIGNED_BEACON_BLOCK, block)
+                        .unwrap()
+                })
+            })
+            .collect::<Vec<_>>();
+        let state_offset = writer
+            .write_compressed(COMPRESSED_BEACON_STATE, &[3; 200])
+            .unwrap();
+        writer.write_slot_index(start_slot, &block_offsets).unwrap();
+        let state_slot = start_slot + blocks.len() as u64;
+        writer
+            .write_slot_index(state_slot, &[Some(state_offset)])
+            .unwrap();
+
+        let mut reader = EraReader::new(Cursor::new(writer.into_inner())).unwrap();
+        assert_eq!(reader.start_slot(), start_slot);
+        assert_eq!(reader.state_slot(), state_slot);
+        assert_eq!(
+            reader.block_slots().collect::<Vec<_>>(),
+            vec![Slot::new(9), Slot::new(11)]
+        );
+        for (i, block) in blocks.iter().enumerate() {
+            assert_eq!(&reader.read_block(start_slot + i as u64).unwrap(), block);
+        }
+        assert_eq!(reader.read_block(state_slot).unwrap(), None);
+    }
+}
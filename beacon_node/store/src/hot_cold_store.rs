@@ -12,17 +12,19 @@ use crate::leveldb_store::BytesKey;
 use crate::leveldb_store::LevelDB;
 use crate::memory_store::MemoryStore;
 use crate::metadata::{
-    AnchorInfo, BlobInfo, CompactionTimestamp, PruningCheckpoint, SchemaVersion, ANCHOR_INFO_KEY,
-    BLOB_INFO_KEY, COMPACTION_TIMESTAMP_KEY, CONFIG_KEY, CURRENT_SCHEMA_VERSION,
+    AnchorInfo, BlobInfo, CompactionTimestamp, ForkSchedule, PersistedForkSchedule,
+    PruningCheckpoint, SchemaVersion, WeakSubjectivityViolation, ANCHOR_INFO_KEY, BLOB_INFO_KEY,
+    COMPACTION_TIMESTAMP_KEY, CONFIG_KEY, CURRENT_SCHEMA_VERSION, FORK_SCHEDULE_KEY,
     PRUNING_CHECKPOINT_KEY, SCHEMA_VERSION_KEY, SPLIT_KEY, STATE_UPPER_LIMIT_NO_RETAIN,
+    WEAK_SUBJECTIVITY_VIOLATION_KEY,
 };
 use crate::metrics;
 use crate::state_cache::{PutStateOutcome, StateCache};
 use crate::{
-    get_key_for_col, ChunkWriter, DBColumn, DatabaseBlock, Error, ItemStore, KeyValueStoreOp,
-    PartialBeaconState, StoreItem, StoreOp,
+    get_key_for_col, ChunkWriter, ColumnSize, DBColumn, DatabaseBlock, Error, ItemStore,
+    KeyValueStore, KeyValueStoreOp, PartialBeaconState, StoreItem, StoreOp, COMPACTION_COLUMNS,
 };
-use itertools::process_results;
+use itertools::{process_results, Itertools};
 use leveldb::iterator::LevelDBIterator;
 use lru::LruCache;
 use parking_lot::{Mutex, RwLock};
@@ -39,9 +41,16 @@ use std::marker::PhantomData;
 use std::num::NonZeroUsize;
 use std::path::Path;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
+use strum::IntoEnumIterator;
 use types::*;
 
+/// The number of key ranges each column is divided into by `HotColdDB::compact_in_steps`.
+pub const COMPACTION_STEPS_PER_COLUMN: u16 = 16;
+
+/// The number of blocks deleted per write by `HotColdDB::prune_history_before`.
+const BLOCK_DELETION_BATCH_SIZE: usize = 1024;
+
 /// On-disk database that stores finalized states efficiently.
 ///
 /// Stores vector fields like the `block_roots` and `state_roots` separately, and only stores
@@ -143,6 +152,12 @@ pub enum HotColdDBError {
     MissingFullBlockExecutionPayloadPruned(Hash256, Slot),
     MissingAnchorInfo,
     BlobsPreviouslyInDefaultStore,
+    ForkScheduleChangedBeforeSplit {
+        fork_name: ForkName,
+        previous_epoch: Option<Epoch>,
+        new_epoch: Option<Epoch>,
+        split_slot: Slot,
+    },
     HotStateSummaryError(BeaconStateError),
     RestorePointDecodeError(ssz::DecodeError),
     BlockReplayBeaconError(BeaconStateError),
@@ -155,6 +170,7 @@ pub enum HotColdDBError {
         slots_per_epoch: u64,
     },
     ZeroEpochsPerBlobPrune,
+    InvalidCompactionIoLimitPercent(u8),
     BlobPruneLogicError,
     RestorePointBlockHashError(BeaconStateError),
     IterationError {
@@ -166,6 +182,18 @@ pub enum HotColdDBError {
         block_root: Hash256,
     },
     Rollback,
+    /// Blocks can't be pruned while historic states which depend on them are stored.
+    HistoricStatesNotPruned,
+    /// History can only be adjusted for slots prior to the split.
+    SlotNotFinalized {
+        slot: Slot,
+        split_slot: Slot,
+    },
+    /// A block needed to extend the block history is missing from the database.
+    MissingHistoricBlock {
+        block_root: Hash256,
+        oldest_available_slot: Slot,
+    },
 }
 
 impl<E: EthSpec> HotColdDB<E, MemoryStore<E>, MemoryStore<E>> {
@@ -263,6 +291,17 @@ impl<E: EthSpec> HotColdDB<E, LevelDB<E>, LevelDB<E>> {
             );
         }
 
+        // Check for fork epochs which were announced or rescheduled since the last run.
+        let fork_schedule = ForkSchedule::from_spec(&db.spec);
+        let fork_schedule_changes = db
+            .load_fork_schedule()?
+            .map(|prev_fork_schedule| db.check_fork_schedule_changes(&prev_fork_schedule))
+            .transpose()?
+            .unwrap_or_default();
+        let deneb_rescheduled = fork_schedule_changes
+            .iter()
+            .any(|(fork_name, _, _)| *fork_name == ForkName::Deneb);
+
         // Open separate blobs directory if configured and same configuration was used on previous
         // run.
         let blob_info = db.load_blob_info()?;
@@ -277,10 +316,16 @@ impl<E: EthSpec> HotColdDB<E, LevelDB<E>, LevelDB<E>> {
                 if blob_info.oldest_blob_slot.is_some() && !blob_info.blobs_db {
                     return Err(HotColdDBError::BlobsPreviouslyInDefaultStore.into());
                 }
-                // Set the oldest blob slot to the Deneb fork slot if it is not yet set.
+                // Set the oldest blob slot to the Deneb fork slot if it is not yet set, or if the
+                // Deneb fork has been rescheduled. No blobs can have been pruned in the latter
+                // case, because the fork is after the split.
                 // Always initialize `blobs_db` to true, we no longer support storing the blobs
                 // in the freezer DB, because the UX is strictly worse for relocating the DB.
-                let oldest_blob_slot = blob_info.oldest_blob_slot.or(deneb_fork_slot);
+                let oldest_blob_slot = if deneb_rescheduled {
+                    deneb_fork_slot
+                } else {
+                    blob_info.oldest_blob_slot.or(deneb_fork_slot)
+                };
                 BlobInfo {
                     oldest_blob_slot,
                     blobs_db: true,
@@ -301,6 +346,17 @@ impl<E: EthSpec> HotColdDB<E, LevelDB<E>, LevelDB<E>> {
             "oldest_blob_slot" => ?new_blob_info.oldest_blob_slot,
         );
 
+        for (fork_name, previous_epoch, new_epoch) in fork_schedule_changes {
+            info!(
+                db.log,
+                "Fork schedule updated";
+                "fork" => %fork_name,
+                "previous_epoch" => ?previous_epoch,
+                "new_epoch" => ?new_epoch,
+            );
+        }
+        db.store_fork_schedule(&fork_schedule)?;
+
         // Ensure that the schema version of the on-disk database matches the software.
         // If the version is mismatched, an automatic migration will be attempted.
         let db = Arc::new(db);
@@ -373,6 +429,15 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         self.state_cache.lock().len()
     }
 
+    pub fn state_cache_capacity(&self) -> usize {
+        self.state_cache.lock().capacity()
+    }
+
+    /// Change the capacity of the state cache, culling states if it is being reduced.
+    pub fn set_state_cache_capacity(&self, capacity: NonZeroUsize) {
+        self.state_cache.lock().set_capacity(capacity)
+    }
+
     /// Store a block and update the LRU cache.
     pub fn put_block(
         &self,
@@ -1562,6 +1627,17 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         &self.log
     }
 
+    /// Return the approximate size of every non-empty column in each of the databases.
+    ///
+    /// This reads every key and value in the databases, so it may take a long time to complete.
+    pub fn column_sizes(&self) -> Result<DatabaseColumnSizes, Error> {
+        Ok(DatabaseColumnSizes {
+            hot: non_empty_column_sizes(&self.hot_db)?,
+            freezer: non_empty_column_sizes(&self.cold_db)?,
+            blobs: non_empty_column_sizes(&self.blobs_db)?,
+        })
+    }
+
     /// Fetch a copy of the current split slot from memory.
     pub fn get_split_slot(&self) -> Slot {
         self.split.read_recursive().slot
@@ -1850,6 +1926,66 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         self.hot_db.put(&CONFIG_KEY, &self.config.as_disk_config())
     }
 
+    /// Load the fork schedule that the database was last opened with.
+    fn load_fork_schedule(&self) -> Result<Option<ForkSchedule>, Error> {
+        Ok(self
+            .hot_db
+            .get::<PersistedForkSchedule>(&FORK_SCHEDULE_KEY)?
+            .map(PersistedForkSchedule::into_latest))
+    }
+
+    /// Write the fork schedule to disk.
+    fn store_fork_schedule(&self, fork_schedule: &ForkSchedule) -> Result<(), Error> {
+        self.hot_db.put(
+            &FORK_SCHEDULE_KEY,
+            &PersistedForkSchedule::from(fork_schedule.clone()),
+        )
+    }
+
+    /// Return the differences between `prev_fork_schedule` and the fork schedule of the spec.
+    ///
+    /// Forks may only be announced or rescheduled if neither their previous nor their new epoch is
+    /// prior to the split, as finalized blocks and states can't be migrated to a different fork.
+    ///
+    /// The blob info is the only persisted item which needs to be migrated when a fork after the
+    /// split changes. The other fork-dependent data is handled as follows:
+    ///
+    /// - Hot blocks and states are decoded according to the fork at their slot. If the head block
+    ///   no longer decodes, the beacon chain builder reverts to the last fork boundary and resets
+    ///   fork choice, leaving any blocks and states from the missed fork unused.
+    /// - The persisted op pool tags each attestation with its variant, and re-checks the
+    ///   signatures of slashings and exits against the fork of the state they're packed into.
+    /// - Fork choice, the split, the anchor and the pruning checkpoint only refer to slots and
+    ///   roots.
+    fn check_fork_schedule_changes(
+        &self,
+        prev_fork_schedule: &ForkSchedule,
+    ) -> Result<Vec<(ForkName, Option<Epoch>, Option<Epoch>)>, Error> {
+        let split_slot = self.get_split_slot();
+        let changes = prev_fork_schedule.changes(&ForkSchedule::from_spec(&self.spec));
+
+        for (fork_name, previous_epoch, new_epoch) in &changes {
+            let earliest_epoch = match (previous_epoch, new_epoch) {
+                (Some(prev), Some(new)) => Some(std::cmp::min(*prev, *new)),
+                (Some(epoch), None) | (None, Some(epoch)) => Some(*epoch),
+                (None, None) => None,
+            };
+            if earliest_epoch.map_or(false, |epoch| {
+                epoch.start_slot(E::slots_per_epoch()) <= split_slot
+            }) {
+                return Err(HotColdDBError::ForkScheduleChangedBeforeSplit {
+                    fork_name: *fork_name,
+                    previous_epoch: *previous_epoch,
+                    new_epoch: *new_epoch,
+                    split_slot,
+                }
+                .into());
+            }
+        }
+
+        Ok(changes)
+    }
+
     /// Load the split point from disk, sans block root.
     fn load_split_partial(&self) -> Result<Option<Split>, Error> {
         self.hot_db.get(&SPLIT_KEY)
@@ -1931,7 +2067,8 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
     /// Verify that a parsed config is valid.
     fn verify_config(config: &StoreConfig) -> Result<(), HotColdDBError> {
         Self::verify_slots_per_restore_point(config.slots_per_restore_point)?;
-        Self::verify_epochs_per_blob_prune(config.epochs_per_blob_prune)
+        Self::verify_epochs_per_blob_prune(config.epochs_per_blob_prune)?;
+        Self::verify_compaction_io_limit_percent(config.compaction_io_limit_percent)
     }
 
     /// Check that the restore point frequency is valid.
@@ -1974,12 +2111,45 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         }
     }
 
+    // Check that the compaction I/O limit is a percentage which allows compaction to progress.
+    fn verify_compaction_io_limit_percent(
+        compaction_io_limit_percent: u8,
+    ) -> Result<(), HotColdDBError> {
+        if (1..=100).contains(&compaction_io_limit_percent) {
+            Ok(())
+        } else {
+            Err(HotColdDBError::InvalidCompactionIoLimitPercent(
+                compaction_io_limit_percent,
+            ))
+        }
+    }
+
     /// Run a compaction pass to free up space used by deleted states.
     pub fn compact(&self) -> Result<(), Error> {
         self.hot_db.compact()?;
         Ok(())
     }
 
+    /// Run the same compaction as `compact`, divided into smaller steps.
+    ///
+    /// Each column is compacted in `COMPACTION_STEPS_PER_COLUMN` key ranges, and `after_step` is
+    /// called with the duration of each step so that the caller may pace the compaction.
+    pub fn compact_in_steps(&self, mut after_step: impl FnMut(Duration)) -> Result<(), Error> {
+        let width = 256 / COMPACTION_STEPS_PER_COLUMN;
+        for column in COMPACTION_COLUMNS {
+            for step in 0..COMPACTION_STEPS_PER_COLUMN {
+                let first_byte = (step * width) as u8;
+                let last_byte = (step * width + width - 1) as u8;
+
+                let start = Instant::now();
+                self.hot_db
+                    .compact_column_range(column, first_byte..=last_byte)?;
+                after_step(start.elapsed());
+            }
+        }
+        Ok(())
+    }
+
     /// Return `true` if compaction on finalization/pruning is enabled.
     pub fn compact_on_prune(&self) -> bool {
         self.config.compact_on_prune
@@ -2020,6 +2190,24 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         )
     }
 
+    /// Load the block recorded as violating the weak subjectivity checkpoint, if any.
+    pub fn load_weak_subjectivity_violation(
+        &self,
+    ) -> Result<Option<WeakSubjectivityViolation>, Error> {
+        self.hot_db.get(&WEAK_SUBJECTIVITY_VIOLATION_KEY)
+    }
+
+    /// Record a block which violates the weak subjectivity checkpoint.
+    ///
+    /// The record is only removed by purging the database.
+    pub fn store_weak_subjectivity_violation(
+        &self,
+        violation: WeakSubjectivityViolation,
+    ) -> Result<(), Error> {
+        self.hot_db
+            .put(&WEAK_SUBJECTIVITY_VIOLATION_KEY, &violation)
+    }
+
     /// Update the linear array of frozen block roots with the block root for several skipped slots.
     ///
     /// Write the block root at all slots from `start_slot` (inclusive) to `end_slot` (exclusive).
@@ -2486,6 +2674,204 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         Ok(())
     }
 
+    /// Get the root of the canonical block prior to the split at `slot` (or the most recent block
+    /// before it if `slot` is skipped), using the block roots in the freezer database.
+    fn get_frozen_block_root(&self, slot: Slot) -> Result<Hash256, Error> {
+        let split_slot = self.get_split_slot();
+        if slot >= split_slot {
+            return Err(HotColdDBError::SlotNotFinalized { slot, split_slot }.into());
+        }
+
+        let (block_root, _) = self
+            .forwards_block_roots_iterator_until(
+                slot,
+                slot,
+                || {
+                    Err(Error::DBError {
+                        message: "Should not require end state".to_string(),
+                    })
+                },
+                &self.spec,
+            )?
+            .next()
+            .ok_or(Error::HistoryUnavailable)??;
+        Ok(block_root)
+    }
+
+    /// Load the canonical block prior to the split at `slot`, see `get_frozen_block_root`.
+    fn get_frozen_block_at_slot(
+        &self,
+        slot: Slot,
+    ) -> Result<(Hash256, SignedBlindedBeaconBlock<E>), Error> {
+        let block_root = self.get_frozen_block_root(slot)?;
+        let block = self
+            .get_blinded_block(&block_root)?
+            .ok_or(Error::BlockNotFound(block_root))?;
+        Ok((block_root, block))
+    }
+
+    /// Set the `oldest_block_slot` of the anchor, for recovery of a database whose anchor doesn't
+    /// reflect the blocks stored.
+    ///
+    /// If `slot` is newer than the current oldest block then blocks prior to `slot` are ignored
+    /// (but not deleted). If it is older, the blocks back to `slot` must already be stored in the
+    /// hot database, and the freezer block roots for their slots are filled in, as for backfill.
+    pub fn set_oldest_block_slot(&self, slot: Slot) -> Result<AnchorInfo, Error> {
+        let anchor = self
+            .get_anchor_info()
+            .ok_or(HotColdDBError::MissingAnchorInfo)?;
+
+        let new_anchor = if slot >= anchor.oldest_block_slot {
+            let (_, block) = self.get_frozen_block_at_slot(slot)?;
+            AnchorInfo {
+                oldest_block_slot: block.slot(),
+                oldest_block_parent: block.parent_root(),
+                ..anchor.clone()
+            }
+        } else {
+            let mut prev_block_slot = anchor.oldest_block_slot;
+            let mut expected_block_root = anchor.oldest_block_parent;
+            let mut chunk_writer =
+                ChunkWriter::<BlockRoots, _, _>::new(&self.cold_db, prev_block_slot.as_usize())?;
+            let mut cold_batch = vec![];
+
+            // The genesis block's parent root is zero, so this stops at genesis.
+            while prev_block_slot > slot && !expected_block_root.is_zero() {
+                let block = self.get_blinded_block(&expected_block_root)?.ok_or(
+                    HotColdDBError::MissingHistoricBlock {
+                        block_root: expected_block_root,
+                        oldest_available_slot: prev_block_slot,
+                    },
+                )?;
+
+                // Store block roots, including at all skip slots in the freezer DB.
+                for i in (block.slot().as_usize()..prev_block_slot.as_usize()).rev() {
+                    chunk_writer.set(i, expected_block_root, &mut cold_batch)?;
+                }
+
+                prev_block_slot = block.slot();
+                expected_block_root = block.parent_root();
+            }
+            chunk_writer.write(&mut cold_batch)?;
+            self.cold_db.do_atomically(cold_batch)?;
+
+            AnchorInfo {
+                oldest_block_slot: prev_block_slot,
+                oldest_block_parent: expected_block_root,
+                ..anchor.clone()
+            }
+        };
+
+        self.compare_and_set_anchor_info_with_write(Some(anchor), Some(new_anchor.clone()))?;
+        Ok(new_anchor)
+    }
+
+    /// Delete the blocks, payloads and blobs older than the block at `slot`, which becomes the
+    /// oldest block in the database. The genesis block is always retained.
+    ///
+    /// Historic states must have been pruned first, as they can't be reconstructed without the
+    /// blocks. The block roots in the freezer database are not deleted. Blocks are deleted before
+    /// the anchor is updated, so an interrupted prune can be resumed by running it again.
+    ///
+    /// Returns the number of blocks deleted.
+    pub fn prune_history_before(&self, slot: Slot) -> Result<usize, Error> {
+        let anchor = self
+            .get_anchor_info()
+            .ok_or(HotColdDBError::HistoricStatesNotPruned)?;
+        if anchor.state_lower_limit != self.spec.genesis_slot || anchor.state_upper_limit < slot {
+            return Err(HotColdDBError::HistoricStatesNotPruned.into());
+        }
+        if slot <= anchor.oldest_block_slot {
+            return Ok(0);
+        }
+
+        let (new_oldest_block_root, new_oldest_block) = self.get_frozen_block_at_slot(slot)?;
+        // The genesis block is only in range if history is stored back to genesis.
+        let genesis_block_root = if anchor.oldest_block_slot == self.spec.genesis_slot {
+            Some(self.get_frozen_block_root(self.spec.genesis_slot)?)
+        } else {
+            None
+        };
+
+        let block_roots = process_results(
+            self.forwards_block_roots_iterator_until(
+                anchor.oldest_block_slot,
+                new_oldest_block.slot(),
+                || {
+                    Err(Error::DBError {
+                        message: "Should not require end state".to_string(),
+                    })
+                },
+                &self.spec,
+            )?,
+            |iter| {
+                iter.map(|(block_root, _)| block_root)
+                    .dedup()
+                    .filter(|block_root| {
+                        Some(*block_root) != genesis_block_root
+                            && *block_root != new_oldest_block_root
+                    })
+                    .collect::<Vec<_>>()
+            },
+        )?;
+
+        info!(
+            self.log,
+            "Deleting historic blocks";
+            "num_blocks" => block_roots.len(),
+            "new_oldest_block_slot" => new_oldest_block.slot(),
+        );
+
+        for chunk in block_roots.chunks(BLOCK_DELETION_BATCH_SIZE) {
+            let mut hot_ops = Vec::with_capacity(2 * chunk.len());
+            let mut blob_ops = Vec::with_capacity(chunk.len());
+            for block_root in chunk {
+                self.block_cache.lock().delete(block_root);
+                for column in [DBColumn::BeaconBlock, DBColumn::ExecPayload] {
+                    hot_ops.push(KeyValueStoreOp::DeleteKey(get_key_for_col(
+                        column.into(),
+                        block_root.as_bytes(),
+                    )));
+                }
+                blob_ops.push(KeyValueStoreOp::DeleteKey(get_key_for_col(
+                    DBColumn::BeaconBlob.into(),
+                    block_root.as_bytes(),
+                )));
+            }
+            self.blobs_db.do_atomically(blob_ops)?;
+            self.hot_db.do_atomically(hot_ops)?;
+        }
+
+        let mut anchor_and_blob_batch = Vec::with_capacity(2);
+
+        let blob_info = self.get_blob_info();
+        if let Some(oldest_blob_slot) = blob_info.oldest_blob_slot {
+            if oldest_blob_slot < new_oldest_block.slot() {
+                let new_blob_info = BlobInfo {
+                    oldest_blob_slot: Some(new_oldest_block.slot()),
+                    ..blob_info.clone()
+                };
+                anchor_and_blob_batch
+                    .push(self.compare_and_set_blob_info(blob_info, new_blob_info)?);
+            }
+        }
+
+        let new_anchor = AnchorInfo {
+            oldest_block_slot: new_oldest_block.slot(),
+            oldest_block_parent: new_oldest_block.parent_root(),
+            ..anchor.clone()
+        };
+        anchor_and_blob_batch
+            .push(self.compare_and_set_anchor_info(Some(anchor), Some(new_anchor))?);
+        self.hot_db.do_atomically(anchor_and_blob_batch)?;
+
+        // In order to reclaim space, we need to compact the hot and blobs DBs.
+        self.hot_db.compact()?;
+        self.blobs_db.compact()?;
+
+        Ok(block_roots.len())
+    }
+
     /// Prune states from the hot database which are prior to the split.
     ///
     /// This routine is important for cleaning up advanced states which are stored in the database
@@ -2744,6 +3130,24 @@ fn no_state_root_iter() -> Option<std::iter::Empty<Result<(Hash256, Slot), Error
     None
 }
 
+/// The approximate sizes of the non-empty columns in each of the databases.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct DatabaseColumnSizes {
+    pub hot: Vec<ColumnSize>,
+    pub freezer: Vec<ColumnSize>,
+    pub blobs: Vec<ColumnSize>,
+}
+
+/// Return the size of every column in `db` which contains at least one key.
+pub fn non_empty_column_sizes<E: EthSpec, S: KeyValueStore<E>>(
+    db: &S,
+) -> Result<Vec<ColumnSize>, Error> {
+    DBColumn::iter()
+        .map(|column| db.column_size(column))
+        .filter(|res| res.as_ref().map_or(true, |size| size.num_keys > 0))
+        .collect()
+}
+
 /// Struct for summarising a state in the hot database.
 ///
 /// Allows full reconstruction by replaying blocks.
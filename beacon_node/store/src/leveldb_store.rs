@@ -9,6 +9,7 @@ use leveldb::iterator::{Iterable, KeyIterator, LevelDBIterator};
 use leveldb::options::{Options, ReadOptions, WriteOptions};
 use parking_lot::Mutex;
 use std::marker::PhantomData;
+use std::ops::RangeInclusive;
 use std::path::Path;
 
 /// A wrapped leveldb database.
@@ -180,6 +181,21 @@ impl<E: EthSpec> KeyValueStore<E> for LevelDB<E> {
         Ok(())
     }
 
+    fn compact_column_range(
+        &self,
+        column: DBColumn,
+        first_bytes: RangeInclusive<u8>,
+    ) -> Result<(), Error> {
+        // As above, pad the end key so that it sorts after every key starting with the last byte.
+        let start_key =
+            BytesKey::from_vec(get_key_for_col(column.as_str(), &[*first_bytes.start()]));
+        let mut end_suffix = vec![0xff; std::cmp::max(column.key_size(), 32)];
+        end_suffix[0] = *first_bytes.end();
+        let end_key = BytesKey::from_vec(get_key_for_col(column.as_str(), &end_suffix));
+        self.db.compact(&start_key, &end_key);
+        Ok(())
+    }
+
     fn iter_column_from<K: Key>(&self, column: DBColumn, from: &[u8]) -> ColumnIter<K> {
         let start_key = BytesKey::from_vec(get_key_for_col(column.into(), from));
 
@@ -229,6 +245,22 @@ impl<E: EthSpec> KeyValueStore<E> for LevelDB<E> {
         )
     }
 
+    /// Size the column using raw iteration, so that keys of an unexpected length are counted
+    /// rather than causing an error.
+    fn column_size(&self, column: DBColumn) -> Result<ColumnSize, Error> {
+        let mut size = ColumnSize {
+            column,
+            num_keys: 0,
+            bytes: 0,
+        };
+        for res in self.iter_raw_entries(column, &[]) {
+            let (key, value) = res?;
+            size.num_keys += 1;
+            size.bytes += (column.as_bytes().len() + key.len() + value.len()) as u64;
+        }
+        Ok(size)
+    }
+
     /// Iterate through all keys and values in a particular column.
     fn iter_column_keys<K: Key>(&self, column: DBColumn) -> ColumnKeyIter<K> {
         let start_key =
@@ -12,6 +12,7 @@ pub mod chunked_iter;
 pub mod chunked_vector;
 pub mod config;
 pub mod consensus_context;
+pub mod era;
 pub mod errors;
 mod forwards_iter;
 mod garbage_collection;
@@ -30,18 +31,21 @@ pub mod iter;
 pub use self::chunk_writer::ChunkWriter;
 pub use self::config::StoreConfig;
 pub use self::consensus_context::OnDiskConsensusContext;
-pub use self::hot_cold_store::{HotColdDB, HotStateSummary, Split};
+pub use self::hot_cold_store::{
+    non_empty_column_sizes, DatabaseColumnSizes, HotColdDB, HotStateSummary, Split,
+};
 pub use self::leveldb_store::LevelDB;
 pub use self::memory_store::MemoryStore;
 pub use self::partial_beacon_state::PartialBeaconState;
 pub use crate::metadata::BlobInfo;
 pub use errors::Error;
 pub use impls::beacon_state::StorageContainer as BeaconStateStorageContainer;
-pub use metadata::AnchorInfo;
+pub use metadata::{AnchorInfo, WeakSubjectivityViolation};
 pub use metrics::scrape_for_metrics;
 use parking_lot::MutexGuard;
+use std::ops::RangeInclusive;
 use std::sync::Arc;
-use strum::{EnumString, IntoStaticStr};
+use strum::{EnumIter, EnumString, IntoStaticStr};
 pub use types::*;
 
 pub type ColumnIter<'a, K> = Box<dyn Iterator<Item = Result<(K, Vec<u8>), Error>> + 'a>;
@@ -83,15 +87,17 @@ pub trait KeyValueStore<E: EthSpec>: Sync + Send + Sized + 'static {
     /// Compact a single column in the database, freeing space used by deleted items.
     fn compact_column(&self, column: DBColumn) -> Result<(), Error>;
 
+    /// Compact the keys of `column` whose first byte (after the column prefix) lies within
+    /// `first_bytes`.
+    fn compact_column_range(
+        &self,
+        column: DBColumn,
+        first_bytes: RangeInclusive<u8>,
+    ) -> Result<(), Error>;
+
     /// Compact a default set of columns that are likely to free substantial space.
     fn compact(&self) -> Result<(), Error> {
-        // Compact state and block related columns as they are likely to have the most churn,
-        // i.e. entries being created and deleted.
-        for column in [
-            DBColumn::BeaconState,
-            DBColumn::BeaconStateSummary,
-            DBColumn::BeaconBlock,
-        ] {
+        for column in COMPACTION_COLUMNS {
             self.compact_column(column)?;
         }
         Ok(())
@@ -115,6 +121,31 @@ pub trait KeyValueStore<E: EthSpec>: Sync + Send + Sized + 'static {
 
     /// Iterate through all keys in a particular column.
     fn iter_column_keys<K: Key>(&self, column: DBColumn) -> ColumnKeyIter<K>;
+
+    /// Return the number of keys in a column and the total size of its keys and values.
+    ///
+    /// The size is measured before compression, so it only approximates the space used on disk.
+    fn column_size(&self, column: DBColumn) -> Result<ColumnSize, Error> {
+        let mut size = ColumnSize {
+            column,
+            num_keys: 0,
+            bytes: 0,
+        };
+        for res in self.iter_column_from::<Vec<u8>>(column, &[]) {
+            let (key, value) = res?;
+            size.num_keys += 1;
+            size.bytes += (column.as_bytes().len() + key.len() + value.len()) as u64;
+        }
+        Ok(size)
+    }
+}
+
+/// The approximate size of a single column, as returned by `KeyValueStore::column_size`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ColumnSize {
+    pub column: DBColumn,
+    pub num_keys: u64,
+    pub bytes: u64,
 }
 
 pub trait Key: Sized + 'static {
@@ -220,8 +251,18 @@ pub enum StoreOp<'a, E: EthSpec> {
     KeyValueOp(KeyValueStoreOp),
 }
 
+/// The columns compacted by `KeyValueStore::compact`.
+///
+/// State and block related columns are likely to have the most churn, i.e. entries being created
+/// and deleted.
+pub const COMPACTION_COLUMNS: [DBColumn; 3] = [
+    DBColumn::BeaconState,
+    DBColumn::BeaconStateSummary,
+    DBColumn::BeaconBlock,
+];
+
 /// A unique column identifier.
-#[derive(Debug, Clone, Copy, PartialEq, IntoStaticStr, EnumString)]
+#[derive(Debug, Clone, Copy, PartialEq, IntoStaticStr, EnumString, EnumIter)]
 pub enum DBColumn {
     /// For data related to the database itself.
     #[strum(serialize = "bma")]
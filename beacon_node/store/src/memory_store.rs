@@ -5,6 +5,7 @@ use crate::{
 use parking_lot::{Mutex, MutexGuard, RwLock};
 use std::collections::BTreeMap;
 use std::marker::PhantomData;
+use std::ops::RangeInclusive;
 use types::*;
 
 type DBMap = BTreeMap<BytesKey, Vec<u8>>;
@@ -111,6 +112,14 @@ impl<E: EthSpec> KeyValueStore<E> for MemoryStore<E> {
     fn compact_column(&self, _column: DBColumn) -> Result<(), Error> {
         Ok(())
     }
+
+    fn compact_column_range(
+        &self,
+        _column: DBColumn,
+        _first_bytes: RangeInclusive<u8>,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
 }
 
 impl<E: EthSpec> ItemStore<E> for MemoryStore<E> {}
@@ -2,7 +2,8 @@ use crate::{DBColumn, Error, StoreItem};
 use serde::{Deserialize, Serialize};
 use ssz::{Decode, Encode};
 use ssz_derive::{Decode, Encode};
-use types::{Checkpoint, Hash256, Slot};
+use types::superstruct;
+use types::{ChainSpec, Checkpoint, Epoch, ForkName, Hash256, Slot};
 
 pub const CURRENT_SCHEMA_VERSION: SchemaVersion = SchemaVersion(21);
 
@@ -16,6 +17,8 @@ pub const PRUNING_CHECKPOINT_KEY: Hash256 = Hash256::repeat_byte(3);
 pub const COMPACTION_TIMESTAMP_KEY: Hash256 = Hash256::repeat_byte(4);
 pub const ANCHOR_INFO_KEY: Hash256 = Hash256::repeat_byte(5);
 pub const BLOB_INFO_KEY: Hash256 = Hash256::repeat_byte(6);
+pub const FORK_SCHEDULE_KEY: Hash256 = Hash256::repeat_byte(7);
+pub const WEAK_SUBJECTIVITY_VIOLATION_KEY: Hash256 = Hash256::repeat_byte(8);
 
 /// State upper limit value used to indicate that a node is not storing historic states.
 pub const STATE_UPPER_LIMIT_NO_RETAIN: Slot = Slot::new(u64::MAX);
@@ -84,6 +87,30 @@ impl StoreItem for CompactionTimestamp {
     }
 }
 
+/// A block which finalized a chain conflicting with the weak subjectivity checkpoint.
+///
+/// Once stored, the database holds a chain which must not be followed and should be purged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WeakSubjectivityViolation {
+    pub block_root: Hash256,
+}
+
+impl StoreItem for WeakSubjectivityViolation {
+    fn db_column() -> DBColumn {
+        DBColumn::BeaconMeta
+    }
+
+    fn as_store_bytes(&self) -> Vec<u8> {
+        self.block_root.as_ssz_bytes()
+    }
+
+    fn from_store_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        Ok(WeakSubjectivityViolation {
+            block_root: Hash256::from_ssz_bytes(bytes)?,
+        })
+    }
+}
+
 /// Database parameters relevant to weak subjectivity sync.
 #[derive(Debug, PartialEq, Eq, Clone, Encode, Decode, Serialize, Deserialize)]
 pub struct AnchorInfo {
@@ -152,3 +179,134 @@ impl StoreItem for BlobInfo {
         Ok(Self::from_ssz_bytes(bytes)?)
     }
 }
+
+// If adding a new fork you should add a new version, update this type alias and convert the
+// previous versions in `PersistedForkSchedule::into_latest`.
+pub type ForkSchedule = ForkScheduleV21;
+
+/// The fork epochs of the spec that the database was last opened with.
+///
+/// This allows fork epochs which are announced or rescheduled after a release to be picked up
+/// from an updated config at restart.
+#[superstruct(
+    variants(V21),
+    variant_attributes(derive(
+        Debug,
+        PartialEq,
+        Eq,
+        Clone,
+        Encode,
+        Decode,
+        Serialize,
+        Deserialize,
+        Default
+    )),
+    no_enum
+)]
+pub struct ForkSchedule {
+    pub altair_fork_epoch: Option<Epoch>,
+    pub bellatrix_fork_epoch: Option<Epoch>,
+    pub capella_fork_epoch: Option<Epoch>,
+    pub deneb_fork_epoch: Option<Epoch>,
+    pub electra_fork_epoch: Option<Epoch>,
+}
+
+impl ForkSchedule {
+    pub fn from_spec(spec: &ChainSpec) -> Self {
+        Self {
+            altair_fork_epoch: spec.altair_fork_epoch,
+            bellatrix_fork_epoch: spec.bellatrix_fork_epoch,
+            capella_fork_epoch: spec.capella_fork_epoch,
+            deneb_fork_epoch: spec.deneb_fork_epoch,
+            electra_fork_epoch: spec.electra_fork_epoch,
+        }
+    }
+
+    pub fn fork_epoch(&self, fork_name: ForkName) -> Option<Epoch> {
+        match fork_name {
+            ForkName::Base => Some(Epoch::new(0)),
+            ForkName::Altair => self.altair_fork_epoch,
+            ForkName::Bellatrix => self.bellatrix_fork_epoch,
+            ForkName::Capella => self.capella_fork_epoch,
+            ForkName::Deneb => self.deneb_fork_epoch,
+            ForkName::Electra => self.electra_fork_epoch,
+        }
+    }
+
+    /// Return the forks whose epoch differs between `self` and `new`, along with the epochs from
+    /// each.
+    pub fn changes(&self, new: &Self) -> Vec<(ForkName, Option<Epoch>, Option<Epoch>)> {
+        ForkName::list_all()
+            .into_iter()
+            .filter_map(|fork_name| {
+                let prev_epoch = self.fork_epoch(fork_name);
+                let new_epoch = new.fork_epoch(fork_name);
+                (prev_epoch != new_epoch).then_some((fork_name, prev_epoch, new_epoch))
+            })
+            .collect()
+    }
+}
+
+/// A `ForkSchedule` tagged with its version, as stored on disk.
+///
+/// The fork schedule is checked when the database is opened, before any schema migration has run,
+/// so a schedule written by an earlier version must always remain decodable.
+#[derive(Debug, PartialEq, Eq, Clone, Encode, Decode)]
+#[ssz(enum_behaviour = "union")]
+pub enum PersistedForkSchedule {
+    V21(ForkScheduleV21),
+}
+
+impl PersistedForkSchedule {
+    /// Convert the persisted schedule to the latest version, treating forks which it predates as
+    /// unscheduled.
+    pub fn into_latest(self) -> ForkSchedule {
+        match self {
+            Self::V21(fork_schedule) => fork_schedule,
+        }
+    }
+}
+
+impl From<ForkSchedule> for PersistedForkSchedule {
+    fn from(fork_schedule: ForkSchedule) -> Self {
+        Self::V21(fork_schedule)
+    }
+}
+
+impl StoreItem for PersistedForkSchedule {
+    fn db_column() -> DBColumn {
+        DBColumn::BeaconMeta
+    }
+
+    fn as_store_bytes(&self) -> Vec<u8> {
+        self.as_ssz_bytes()
+    }
+
+    fn from_store_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        Ok(Self::from_ssz_bytes(bytes)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fork_schedule_is_stored_with_its_version() {
+        let fork_schedule = ForkSchedule {
+            altair_fork_epoch: Some(Epoch::new(1)),
+            deneb_fork_epoch: Some(Epoch::new(4)),
+            ..ForkSchedule::default()
+        };
+        let bytes = PersistedForkSchedule::from(fork_schedule.clone()).as_store_bytes();
+
+        // The first byte is the union selector of the version.
+        assert_eq!(bytes[0], 0);
+        assert_eq!(
+            PersistedForkSchedule::from_store_bytes(&bytes)
+                .unwrap()
+                .into_latest(),
+            fork_schedule
+        );
+    }
+}
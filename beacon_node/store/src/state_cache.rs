@@ -64,6 +64,26 @@ impl<E: EthSpec> StateCache<E> {
         self.states.cap().get()
     }
 
+    /// Change the maximum number of states held by the cache.
+    ///
+    /// If the cache holds more than `capacity` states they are culled using the usual order.
+    pub fn set_capacity(&mut self, capacity: NonZeroUsize) {
+        if let Some(excess) = self.len().checked_sub(capacity.get()) {
+            if excess > 0 {
+                self.cull(excess);
+            }
+        }
+        // Culling exempts the most recently used states, so fall back to plain LRU eviction for
+        // any that remain.
+        while self.len() > capacity.get() {
+            let Some((state_root, _)) = self.states.pop_lru() else {
+                break;
+            };
+            self.block_map.delete(&state_root);
+        }
+        self.states.resize(capacity);
+    }
+
     pub fn update_finalized_state(
         &mut self,
         state_root: Hash256,
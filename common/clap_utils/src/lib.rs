@@ -5,6 +5,7 @@ use clap::ArgMatches;
 use eth2_network_config::{Eth2NetworkConfig, DEFAULT_HARDCODED_NETWORK};
 use ethereum_types::U256 as Uint256;
 use ssz::Decode;
+use std::fs::File;
 use std::path::PathBuf;
 use std::str::FromStr;
 use types::{ChainSpec, Config, EthSpec};
@@ -62,6 +63,25 @@ pub fn get_eth2_network_config(cli_args: &ArgMatches) -> Result<Eth2NetworkConfi
             .safe_slots_to_import_optimistically = slots;
     }
 
+    if let Some(path) = parse_optional::<PathBuf>(cli_args, "chain-config-overrides")? {
+        let file = File::open(&path).map_err(|e| {
+            format!(
+                "Unable to open --chain-config-overrides file {:?}: {:?}",
+                path, e
+            )
+        })?;
+        let overrides = serde_yaml::from_reader(file).map_err(|e| {
+            format!(
+                "Unable to parse --chain-config-overrides file {:?}: {:?}",
+                path, e
+            )
+        })?;
+        eth2_network_config.config = eth2_network_config
+            .config
+            .with_overrides(overrides)
+            .map_err(|e| format!("Invalid --chain-config-overrides: {}", e))?;
+    }
+
     Ok(eth2_network_config)
 }
 
@@ -28,7 +28,7 @@ use reqwest::{
 pub use reqwest::{StatusCode, Url};
 pub use sensitive_url::{SensitiveError, SensitiveUrl};
 use serde::{de::DeserializeOwned, Serialize};
-use ssz::Encode;
+use ssz::{Decode, Encode};
 use std::fmt;
 use std::future::Future;
 use std::path::PathBuf;
@@ -44,6 +44,11 @@ pub const EXECUTION_PAYLOAD_BLINDED_HEADER: &str = "Eth-Execution-Payload-Blinde
 pub const EXECUTION_PAYLOAD_VALUE_HEADER: &str = "Eth-Execution-Payload-Value";
 pub const CONSENSUS_BLOCK_VALUE_HEADER: &str = "Eth-Consensus-Block-Value";
 
+/// The message returned with a `400 Bad Request` when publishing a block for a slot and proposer
+/// which the beacon node has already seen a different block for.
+pub const EQUIVOCATING_BLOCK_MESSAGE: &str =
+    "proposal for this slot and proposer has already been seen";
+
 pub const CONTENT_TYPE_HEADER: &str = "Content-Type";
 pub const SSZ_CONTENT_TYPE_HEADER: &str = "application/octet-stream";
 
@@ -107,6 +112,18 @@ impl Error {
             Error::NoServerPubkey | Error::NoToken => None,
         }
     }
+
+    /// Returns `true` if the beacon node refused to publish a block because it has already seen a
+    /// different block from the same proposer at the same slot.
+    pub fn is_equivocating_block(&self) -> bool {
+        match self {
+            Error::ServerMessage(msg) => {
+                msg.code == StatusCode::BAD_REQUEST.as_u16()
+                    && msg.message.contains(EQUIVOCATING_BLOCK_MESSAGE)
+            }
+            _ => false,
+        }
+    }
 }
 
 impl fmt::Display for Error {
@@ -194,6 +211,11 @@ impl BeaconNodeHttpClient {
         }
     }
 
+    /// Returns the URL of the beacon node.
+    pub fn server(&self) -> &SensitiveUrl {
+        &self.server
+    }
+
     /// Return the path with the standard `/eth/vX` prefix applied.
     fn eth_path(&self, version: EndpointVersion) -> Result<Url, Error> {
         let mut path = self.server.full.clone();
@@ -325,6 +347,23 @@ impl BeaconNodeHttpClient {
             .map_err(Into::into)
     }
 
+    /// Perform a HTTP POST request, returning the bytes of an SSZ response.
+    #[cfg(feature = "lighthouse")]
+    async fn post_with_ssz_response<T: Serialize, U: IntoUrl>(
+        &self,
+        url: U,
+        body: &T,
+    ) -> Result<Vec<u8>, Error> {
+        let response = self
+            .client
+            .post(url)
+            .accept(Accept::Ssz)
+            .json(body)
+            .send()
+            .await?;
+        Ok(ok_or_error(response).await?.bytes().await?.to_vec())
+    }
+
     async fn post_with_opt_response<T: Serialize, U: IntoUrl, R: DeserializeOwned>(
         &self,
         url: U,
@@ -608,6 +647,40 @@ impl BeaconNodeHttpClient {
         ids: Option<&[ValidatorId]>,
         statuses: Option<&[ValidatorStatus]>,
     ) -> Result<Option<ExecutionOptimisticFinalizedResponse<Vec<ValidatorData>>>, Error> {
+        let path = self.beacon_states_validators_path(state_id, ids, statuses)?;
+
+        self.get_opt(path).await
+    }
+
+    /// `GET beacon/states/{state_id}/validators?id,status,offset,limit`
+    ///
+    /// Returns at most `limit` of the matching validators, skipping the first `offset`. This is a
+    /// Lighthouse extension to the standard endpoint.
+    ///
+    /// Returns `Ok(None)` on a 404 error.
+    pub async fn get_beacon_states_validators_page(
+        &self,
+        state_id: StateId,
+        ids: Option<&[ValidatorId]>,
+        statuses: Option<&[ValidatorStatus]>,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Option<ExecutionOptimisticFinalizedResponse<Vec<ValidatorData>>>, Error> {
+        let mut path = self.beacon_states_validators_path(state_id, ids, statuses)?;
+
+        path.query_pairs_mut()
+            .append_pair("offset", &offset.to_string())
+            .append_pair("limit", &limit.to_string());
+
+        self.get_opt(path).await
+    }
+
+    fn beacon_states_validators_path(
+        &self,
+        state_id: StateId,
+        ids: Option<&[ValidatorId]>,
+        statuses: Option<&[ValidatorStatus]>,
+    ) -> Result<Url, Error> {
         let mut path = self.eth_path(V1)?;
 
         path.path_segments_mut()
@@ -635,7 +708,7 @@ impl BeaconNodeHttpClient {
             path.query_pairs_mut().append_pair("status", &status_string);
         }
 
-        self.get_opt(path).await
+        Ok(path)
     }
 
     /// `POST beacon/states/{state_id}/validators`
@@ -871,15 +944,40 @@ impl BeaconNodeHttpClient {
         &self,
         block_contents: &PublishBlockRequest<E>,
     ) -> Result<(), Error> {
-        let mut path = self.eth_path(V1)?;
+        self.post_beacon_blocks_with_validation(block_contents, None)
+            .await
+    }
 
+    pub fn post_beacon_blocks_path(
+        &self,
+        validation_level: Option<BroadcastValidation>,
+    ) -> Result<Url, Error> {
+        let mut path = self.eth_path(V1)?;
         path.path_segments_mut()
-            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
-            .push("beacon")
-            .push("blocks");
+            .map_err(|_| Error::InvalidUrl(self.server.clone()))?
+            .extend(&["beacon", "blocks"]);
 
-        self.post_with_timeout(path, block_contents, self.timeouts.proposal)
-            .await?;
+        path.set_query(
+            validation_level
+                .map(|v| format!("broadcast_validation={}", v))
+                .as_deref(),
+        );
+
+        Ok(path)
+    }
+
+    /// `POST beacon/blocks?broadcast_validation`
+    pub async fn post_beacon_blocks_with_validation<E: EthSpec>(
+        &self,
+        block_contents: &PublishBlockRequest<E>,
+        validation_level: Option<BroadcastValidation>,
+    ) -> Result<(), Error> {
+        self.post_with_timeout(
+            self.post_beacon_blocks_path(validation_level)?,
+            block_contents,
+            self.timeouts.proposal,
+        )
+        .await?;
 
         Ok(())
     }
@@ -1429,6 +1527,41 @@ impl BeaconNodeHttpClient {
         self.get(path).await
     }
 
+    /// `GET v2/beacon/pool/attester_slashings`, requesting an SSZ response.
+    ///
+    /// The slashings are decoded according to `fork_name`, which should be the fork at the
+    /// current slot of the beacon node.
+    pub async fn get_beacon_pool_attester_slashings_ssz<E: EthSpec>(
+        &self,
+        fork_name: ForkName,
+    ) -> Result<Vec<AttesterSlashing<E>>, Error> {
+        let mut path = self.eth_path(V2)?;
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("beacon")
+            .push("pool")
+            .push("attester_slashings");
+
+        let bytes = self
+            .get_response(path, |b| b.accept(Accept::Ssz))
+            .await?
+            .bytes()
+            .await?;
+        if fork_name.electra_enabled() {
+            Vec::<AttesterSlashingElectra<E>>::from_ssz_bytes(&bytes).map(|slashings| {
+                slashings
+                    .into_iter()
+                    .map(AttesterSlashing::Electra)
+                    .collect()
+            })
+        } else {
+            Vec::<AttesterSlashingBase<E>>::from_ssz_bytes(&bytes)
+                .map(|slashings| slashings.into_iter().map(AttesterSlashing::Base).collect())
+        }
+        .map_err(Error::InvalidSsz)
+    }
+
     /// `POST beacon/pool/proposer_slashings`
     pub async fn post_beacon_pool_proposer_slashings(
         &self,
@@ -1462,6 +1595,26 @@ impl BeaconNodeHttpClient {
         self.get(path).await
     }
 
+    /// `GET beacon/pool/proposer_slashings`, requesting an SSZ response.
+    pub async fn get_beacon_pool_proposer_slashings_ssz(
+        &self,
+    ) -> Result<Vec<ProposerSlashing>, Error> {
+        let mut path = self.eth_path(V1)?;
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("beacon")
+            .push("pool")
+            .push("proposer_slashings");
+
+        let bytes = self
+            .get_response(path, |b| b.accept(Accept::Ssz))
+            .await?
+            .bytes()
+            .await?;
+        Vec::<ProposerSlashing>::from_ssz_bytes(&bytes).map_err(Error::InvalidSsz)
+    }
+
     /// `POST beacon/pool/voluntary_exits`
     pub async fn post_beacon_pool_voluntary_exits(
         &self,
@@ -1543,56 +1696,156 @@ impl BeaconNodeHttpClient {
             .map(|opt| opt.map(|r| r.data))
     }
 
-    /// `POST beacon/rewards/sync_committee`
-    pub async fn post_beacon_rewards_sync_committee(
-        &self,
-        rewards: &[Option<Vec<lighthouse::SyncCommitteeReward>>],
-    ) -> Result<(), Error> {
+    fn beacon_rewards_path(&self, endpoint: &str, id: &str) -> Result<Url, Error> {
         let mut path = self.eth_path(V1)?;
-
         path.path_segments_mut()
             .map_err(|()| Error::InvalidUrl(self.server.clone()))?
             .push("beacon")
             .push("rewards")
-            .push("sync_committee");
+            .push(endpoint)
+            .push(id);
+        Ok(path)
+    }
 
-        self.post(path, &rewards).await?;
+    /// `POST beacon/rewards/sync_committee/{block_id}`
+    pub async fn post_beacon_rewards_sync_committee(
+        &self,
+        block_id: BlockId,
+        validators: &[ValidatorId],
+    ) -> Result<ExecutionOptimisticFinalizedResponse<Vec<lighthouse::SyncCommitteeReward>>, Error>
+    {
+        let path = self.beacon_rewards_path("sync_committee", &block_id.to_string())?;
+        self.post_with_response(path, &validators).await
+    }
 
-        Ok(())
+    /// `POST beacon/rewards/sync_committee/{block_id}` as SSZ
+    pub async fn post_beacon_rewards_sync_committee_ssz(
+        &self,
+        block_id: BlockId,
+        validators: &[ValidatorId],
+    ) -> Result<Vec<lighthouse::SyncCommitteeReward>, Error> {
+        let path = self.beacon_rewards_path("sync_committee", &block_id.to_string())?;
+        let bytes = self.post_with_ssz_response(path, &validators).await?;
+        Vec::from_ssz_bytes(&bytes).map_err(Error::InvalidSsz)
+    }
+
+    /// `GET beacon/rewards/blocks/{block_id}`
+    pub async fn get_beacon_rewards_blocks(
+        &self,
+        block_id: BlockId,
+    ) -> Result<ExecutionOptimisticFinalizedResponse<lighthouse::StandardBlockReward>, Error> {
+        let path = self.beacon_rewards_path("blocks", &block_id.to_string())?;
+        self.get(path).await
     }
 
-    /// `GET beacon/rewards/blocks`
-    pub async fn get_beacon_rewards_blocks(&self, epoch: Epoch) -> Result<(), Error> {
+    /// `GET beacon/rewards/blocks/{block_id}` as SSZ
+    pub async fn get_beacon_rewards_blocks_ssz(
+        &self,
+        block_id: BlockId,
+    ) -> Result<lighthouse::StandardBlockReward, Error> {
+        let path = self.beacon_rewards_path("blocks", &block_id.to_string())?;
+        let bytes = self
+            .get_response(path, |b| b.accept(Accept::Ssz))
+            .await?
+            .bytes()
+            .await?;
+        lighthouse::StandardBlockReward::from_ssz_bytes(&bytes).map_err(Error::InvalidSsz)
+    }
+
+    /// `POST beacon/rewards/attestations/{epoch}`
+    pub async fn post_beacon_rewards_attestations(
+        &self,
+        epoch: Epoch,
+        validators: &[ValidatorId],
+    ) -> Result<ExecutionOptimisticResponse<lighthouse::StandardAttestationRewards>, Error> {
         let mut path = self.eth_path(V1)?;
 
         path.path_segments_mut()
             .map_err(|()| Error::InvalidUrl(self.server.clone()))?
             .push("beacon")
             .push("rewards")
-            .push("blocks");
+            .push("attestations")
+            .push(&epoch.to_string());
 
-        path.query_pairs_mut()
-            .append_pair("epoch", &epoch.to_string());
+        self.post_with_response(path, &validators).await
+    }
 
-        self.get(path).await
+    /// `POST beacon/rewards/attestations/{epoch}` as SSZ
+    pub async fn post_beacon_rewards_attestations_ssz(
+        &self,
+        epoch: Epoch,
+        validators: &[ValidatorId],
+    ) -> Result<lighthouse::StandardAttestationRewards, Error> {
+        let path = self.beacon_rewards_path("attestations", &epoch.to_string())?;
+        let bytes = self.post_with_ssz_response(path, &validators).await?;
+        lighthouse::StandardAttestationRewards::from_ssz_bytes(&bytes).map_err(Error::InvalidSsz)
     }
 
-    /// `POST beacon/rewards/attestations`
-    pub async fn post_beacon_rewards_attestations(
+    /// `POST beacon/rewards/attestations/{epoch}?page_size,page_token`
+    ///
+    /// Fetches a single page of attestation rewards. Pass the `next_page_token` from the previous
+    /// page to fetch the next, until it is `None`.
+    pub async fn post_beacon_rewards_attestations_paged(
         &self,
-        attestations: &[ValidatorId],
-    ) -> Result<(), Error> {
+        epoch: Epoch,
+        validators: &[ValidatorId],
+        page_size: usize,
+        page_token: Option<&str>,
+    ) -> Result<ExecutionOptimisticResponse<lighthouse::PagedAttestationRewards>, Error> {
         let mut path = self.eth_path(V1)?;
 
         path.path_segments_mut()
             .map_err(|()| Error::InvalidUrl(self.server.clone()))?
             .push("beacon")
             .push("rewards")
-            .push("attestations");
+            .push("attestations")
+            .push(&epoch.to_string());
 
-        self.post(path, &attestations).await?;
+        path.query_pairs_mut()
+            .append_pair("page_size", &page_size.to_string());
+        if let Some(page_token) = page_token {
+            path.query_pairs_mut().append_pair("page_token", page_token);
+        }
 
-        Ok(())
+        self.post_with_response(path, &validators).await
+    }
+
+    /// `GET beacon/rewards/blocks/{block_id}?units`
+    pub async fn get_beacon_rewards_blocks_in_units(
+        &self,
+        block_id: BlockId,
+        units: RewardUnits,
+    ) -> Result<RewardsInUnitsResponse, Error> {
+        let mut path = self.beacon_rewards_path("blocks", &block_id.to_string())?;
+        path.query_pairs_mut()
+            .append_pair("units", &units.to_string());
+        self.get(path).await
+    }
+
+    /// `POST beacon/rewards/attestations/{epoch}?units`
+    pub async fn post_beacon_rewards_attestations_in_units(
+        &self,
+        epoch: Epoch,
+        validators: &[ValidatorId],
+        units: RewardUnits,
+    ) -> Result<RewardsInUnitsResponse, Error> {
+        let mut path = self.beacon_rewards_path("attestations", &epoch.to_string())?;
+        path.query_pairs_mut()
+            .append_pair("units", &units.to_string());
+        self.post_with_response(path, &validators).await
+    }
+
+    /// `POST beacon/rewards/sync_committee/{block_id}?units`
+    pub async fn post_beacon_rewards_sync_committee_in_units(
+        &self,
+        block_id: BlockId,
+        validators: &[ValidatorId],
+        units: RewardUnits,
+    ) -> Result<RewardsInUnitsResponse, Error> {
+        let mut path = self.beacon_rewards_path("sync_committee", &block_id.to_string())?;
+        path.query_pairs_mut()
+            .append_pair("units", &units.to_string());
+        self.post_with_response(path, &validators).await
     }
 
     // GET builder/states/{state_id}/expected_withdrawals
@@ -1783,6 +2036,37 @@ impl BeaconNodeHttpClient {
         states: Option<&[PeerState]>,
         directions: Option<&[PeerDirection]>,
     ) -> Result<PeersData, Error> {
+        let path = self.node_peers_path(states, directions)?;
+        self.get(path).await
+    }
+
+    /// `GET node/peers?page_size,page_token`
+    ///
+    /// Fetches a single page of peers. Pass the `next_page_token` from the previous page to fetch
+    /// the next, until it is `None`.
+    pub async fn get_node_peers_paged(
+        &self,
+        states: Option<&[PeerState]>,
+        directions: Option<&[PeerDirection]>,
+        page_size: usize,
+        page_token: Option<&str>,
+    ) -> Result<PeersData, Error> {
+        let mut path = self.node_peers_path(states, directions)?;
+
+        path.query_pairs_mut()
+            .append_pair("page_size", &page_size.to_string());
+        if let Some(page_token) = page_token {
+            path.query_pairs_mut().append_pair("page_token", page_token);
+        }
+
+        self.get(path).await
+    }
+
+    fn node_peers_path(
+        &self,
+        states: Option<&[PeerState]>,
+        directions: Option<&[PeerDirection]>,
+    ) -> Result<Url, Error> {
         let mut path = self.eth_path(V1)?;
 
         path.path_segments_mut()
@@ -1808,7 +2092,7 @@ impl BeaconNodeHttpClient {
             path.query_pairs_mut().append_pair("direction", &dir_string);
         }
 
-        self.get(path).await
+        Ok(path)
     }
 
     /// `GET node/peer_count`
@@ -4,32 +4,70 @@ mod attestation_performance;
 pub mod attestation_rewards;
 mod block_packing_efficiency;
 mod block_rewards;
+mod churn;
+mod decode_ssz;
+mod deposit_status;
+mod duties;
+mod participation;
+mod proofs;
 mod standard_block_rewards;
+mod state_diff;
+mod sync_aggregation;
 mod sync_committee_rewards;
+mod validator_set_changes;
 
 use crate::{
     types::{
-        DepositTreeSnapshot, Epoch, EthSpec, FinalizedExecutionBlock, GenericResponse, ValidatorId,
+        Attestation, AttesterSlashing, Checkpoint, DepositTreeSnapshot, Epoch, EthSpec,
+        ExecutionOptimisticFinalizedResponse, FinalizedExecutionBlock, ForkName, GenericResponse,
+        IndexedAttestation, IndexedAttestationBase, IndexedAttestationElectra, ProposerSlashing,
+        PublicKeyBytes, SignedBeaconBlockHeader, SignedBlsToExecutionChange, SignedVoluntaryExit,
+        StateId, ValidatorId, ValidatorIndexDataRef,
     },
-    BeaconNodeHttpClient, DepositData, Error, Eth1Data, Hash256, Slot,
+    Accept, BeaconNodeHttpClient, DepositData, Error, Eth1Data, Hash256, Slot,
 };
 use proto_array::core::ProtoArray;
 use serde::{Deserialize, Serialize};
-use ssz::four_byte_option_impl;
+use ssz::{four_byte_option_impl, Decode};
 use ssz_derive::{Decode, Encode};
-use store::{AnchorInfo, BlobInfo, Split, StoreConfig};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::time::Duration;
+use store::{AnchorInfo, BlobInfo, ColumnSize, DatabaseColumnSizes, Split, StoreConfig};
 
 pub use attestation_performance::{
     AttestationPerformance, AttestationPerformanceQuery, AttestationPerformanceStatistics,
 };
-pub use attestation_rewards::StandardAttestationRewards;
+pub use attestation_rewards::{PagedAttestationRewards, StandardAttestationRewards};
 pub use block_packing_efficiency::{
     BlockPackingEfficiency, BlockPackingEfficiencyQuery, ProposerInfo, UniqueAttestation,
 };
-pub use block_rewards::{AttestationRewards, BlockReward, BlockRewardMeta, BlockRewardsQuery};
-pub use lighthouse_network::{types::SyncState, PeerInfo};
+pub use block_rewards::{
+    AttestationRewards, BlockReward, BlockRewardMeta, BlockRewardsQuery, PredictedBlockReward,
+    PredictedBlockRewardQuery,
+};
+pub use churn::ChurnAnalysis;
+pub use decode_ssz::{DecodeSszQuery, SszObjectType};
+pub use deposit_status::{DepositStatus, ObservedDeposit};
+pub use duties::{AllDuties, AllDutiesQuery};
+pub use fork_choice::ForkChoiceSnapshot;
+pub use lighthouse_network::{
+    types::{
+        gossip_rejections::RejectionCounts,
+        propagation::{PeerPropagation, PropagationStats},
+        SyncState,
+    },
+    PeerInfo,
+};
+pub use participation::{EpochParticipation, FlagParticipation};
+pub use proofs::{HistoricalBlockRootProof, StateMultiproof, StateProofQuery};
 pub use standard_block_rewards::StandardBlockReward;
+pub use state_diff::{StateDiff, StateDiffQuery, ValidatorDiff, ValidatorFields};
+pub use sync_aggregation::SyncAggregationSummary;
 pub use sync_committee_rewards::SyncCommitteeReward;
+pub use validator_set_changes::{
+    ValidatorSetChange, ValidatorSetChangeKind, ValidatorSetChanges, ValidatorSetChangesQuery,
+};
 
 // Define "legacy" implementations of `Option<T>` which use four bytes for encoding the union
 // selector.
@@ -101,6 +139,14 @@ pub struct Health {
     pub system: SystemHealth,
     #[serde(flatten)]
     pub process: ProcessHealth,
+    /// True if the beacon node has rejected a chain which conflicts with its configured weak
+    /// subjectivity checkpoint. Always false for the validator client.
+    #[serde(default)]
+    pub weak_subjectivity_violated: bool,
+    /// True if the beacon node has detected that its clock is skewed beyond the configured
+    /// threshold. Always false for the validator client.
+    #[serde(default)]
+    pub clock_skew_detected: bool,
 }
 
 /// System related health.
@@ -293,6 +339,8 @@ impl Health {
         Ok(Self {
             process: ProcessHealth::observe()?,
             system: SystemHealth::observe()?,
+            weak_subjectivity_violated: false,
+            clock_skew_detected: false,
         })
     }
 }
@@ -356,6 +404,74 @@ impl From<Eth1Block> for FinalizedExecutionBlock {
     }
 }
 
+/// How quickly a peer propagates blocks and aggregates over gossip.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PeerPropagationScore {
+    pub peer_id: String,
+    /// A score between `0` and `1`, where `1` means that messages arrive from the peer as soon as
+    /// they can be produced. `None` if no messages have been received from the peer.
+    pub score: Option<f64>,
+    #[serde(flatten)]
+    pub propagation: PeerPropagation,
+}
+
+/// Why gossip attestations have failed verification, per epoch and per peer.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GossipRejections {
+    /// Counts for recent epochs, oldest first.
+    pub epochs: Vec<EpochRejections>,
+    /// Counts for each peer over the current and previous epochs, with the most rejections first.
+    pub peers: Vec<PeerRejections>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EpochRejections {
+    pub epoch: Epoch,
+    #[serde(flatten)]
+    pub rejections: RejectionCounts,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PeerRejections {
+    pub peer_id: String,
+    /// The client the peer is running, if known.
+    pub client: String,
+    #[serde(flatten)]
+    pub rejections: RejectionCounts,
+}
+
+/// The status of authentication with the execution engine.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExecutionAuthStatus {
+    /// Whether the execution engine accepted a request signed with the current JWT secret.
+    pub authenticated: bool,
+    /// The UNIX timestamp (in seconds) at which the current JWT secret was loaded.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub secret_loaded_at: u64,
+    /// The error returned when contacting the execution engine, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// The fork choice parameters in use by the beacon node.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ForkChoiceParameters {
+    /// True if the parameters have been changed from the specification using `--unsafe-tuning`.
+    pub unsafe_tuning: bool,
+    /// The percentage of the committee weight given to a timely block proposal.
+    pub proposer_boost_percentage: Option<u64>,
+    /// The committee weight used to compute the proposer boost, if it has been overridden.
+    pub committee_weight_override: Option<u64>,
+    /// The percentage of the committee weight below which a late head block may be re-orged.
+    pub re_org_head_threshold: Option<u64>,
+    /// The percentage of the committee weight above which the parent of a re-orged block must be.
+    pub re_org_parent_threshold: Option<u64>,
+    /// The root of the block which currently receives the proposer boost, or zero if none.
+    pub proposer_boost_root: Hash256,
+    pub justified_checkpoint: Checkpoint,
+    pub finalized_checkpoint: Checkpoint,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct DatabaseInfo {
     pub schema_version: u64,
@@ -363,6 +479,237 @@ pub struct DatabaseInfo {
     pub split: Split,
     pub anchor: Option<AnchorInfo>,
     pub blob_info: BlobInfo,
+    /// The oldest epoch from which blobs are retained. Blobs from earlier epochs are pruned once
+    /// they are older than the split.
+    ///
+    /// `None` if blobs are never pruned, either because blob pruning is disabled or because the
+    /// Deneb fork is not scheduled.
+    #[serde(default)]
+    pub blob_retention_boundary: Option<Epoch>,
+    /// The approximate size of each column, only present if requested via `DatabaseInfoQuery`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sizes: Option<DatabaseSizes>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DatabaseInfoQuery {
+    /// Compute the size of each column. This reads the entire database.
+    #[serde(default)]
+    pub sizes: bool,
+}
+
+/// The approximate size of the non-empty columns in each database.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DatabaseSizes {
+    pub hot: Vec<DatabaseColumnSize>,
+    pub freezer: Vec<DatabaseColumnSize>,
+    pub blobs: Vec<DatabaseColumnSize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DatabaseColumnSize {
+    /// The column's tag, as accepted by `lighthouse db inspect --column`.
+    pub column: String,
+    pub name: String,
+    pub num_keys: u64,
+    /// The total size of the column's keys and values before compression.
+    pub bytes: u64,
+}
+
+impl From<DatabaseColumnSizes> for DatabaseSizes {
+    fn from(sizes: DatabaseColumnSizes) -> Self {
+        let convert = |sizes: Vec<ColumnSize>| {
+            sizes
+                .into_iter()
+                .map(|size| DatabaseColumnSize {
+                    column: size.column.as_str().to_string(),
+                    name: format!("{:?}", size.column),
+                    num_keys: size.num_keys,
+                    bytes: size.bytes,
+                })
+                .collect()
+        };
+        Self {
+            hot: convert(sizes.hot),
+            freezer: convert(sizes.freezer),
+            blobs: convert(sizes.blobs),
+        }
+    }
+}
+
+/// The depth and drop count of a single beacon processor work queue.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BeaconProcessorQueue {
+    pub name: String,
+    pub length: usize,
+    pub max_length: usize,
+    /// The greatest length of the queue since startup or the last reset.
+    pub max_depth: usize,
+    /// The number of work items dropped since startup or the last reset.
+    pub dropped: u64,
+}
+
+/// The occupancy of the operation pool for a single type of operation.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PoolOperationStats {
+    /// The number of operations of this type in the pool.
+    pub count: usize,
+    /// The UNIX timestamp (in seconds) of the most recent insertion of this type of operation,
+    /// or `None` if none have been inserted since startup.
+    pub last_insertion: Option<u64>,
+}
+
+/// Operations to include in the next block proposed by the beacon node, ahead of the operations
+/// from its operation pool.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(bound = "E: EthSpec")]
+pub struct PartialBlockBody<E: EthSpec> {
+    #[serde(default)]
+    pub proposer_slashings: Vec<ProposerSlashing>,
+    #[serde(default)]
+    pub attester_slashings: Vec<AttesterSlashing<E>>,
+    #[serde(default)]
+    pub attestations: Vec<Attestation<E>>,
+    #[serde(default)]
+    pub voluntary_exits: Vec<SignedVoluntaryExit>,
+    #[serde(default)]
+    pub bls_to_execution_changes: Vec<SignedBlsToExecutionChange>,
+}
+
+impl<E: EthSpec> PartialBlockBody<E> {
+    pub fn is_empty(&self) -> bool {
+        self.proposer_slashings.is_empty()
+            && self.attester_slashings.is_empty()
+            && self.attestations.is_empty()
+            && self.voluntary_exits.is_empty()
+            && self.bls_to_execution_changes.is_empty()
+    }
+}
+
+/// The occupancy of the operation pool, by operation type.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PoolStats {
+    pub attestations: PoolOperationStats,
+    pub sync_contributions: PoolOperationStats,
+    pub attester_slashings: PoolOperationStats,
+    pub proposer_slashings: PoolOperationStats,
+    pub voluntary_exits: PoolOperationStats,
+    pub bls_to_execution_changes: PoolOperationStats,
+}
+
+/// Memory usage of the beacon node, from `GET lighthouse/debug/memory`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MemoryInfo {
+    /// Statistics from the memory allocator, or `None` if it doesn't provide any.
+    pub allocator: Option<AllocatorInfo>,
+    pub caches: Vec<CacheMemory>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AllocatorInfo {
+    /// The allocator in use, e.g. `jemalloc` or `glibc`.
+    pub name: String,
+    /// Statistics reported by the allocator, in bytes unless named otherwise.
+    pub stats: BTreeMap<String, u64>,
+}
+
+/// The size of one of the beacon node's in-memory caches.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CacheMemory {
+    pub name: String,
+    pub entries: usize,
+    /// The maximum number of entries, for caches which have one.
+    pub capacity: Option<usize>,
+    /// A rough estimate of the memory used by the entries, or `None` if it can't be estimated
+    /// cheaply.
+    pub estimated_bytes: Option<usize>,
+}
+
+/// The output format of `GET lighthouse/debug/profile`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProfileFormat {
+    /// An SVG flamegraph.
+    #[default]
+    Flamegraph,
+    /// A protobuf profile, as read by `go tool pprof`.
+    Pprof,
+}
+
+impl fmt::Display for ProfileFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProfileFormat::Flamegraph => write!(f, "flamegraph"),
+            ProfileFormat::Pprof => write!(f, "pprof"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProfileQuery {
+    /// How long to profile for, e.g. `10s`, `500ms` or `2m`. A bare number is in seconds.
+    pub duration: String,
+    #[serde(default)]
+    pub format: ProfileFormat,
+}
+
+/// Per-module log level overrides, e.g. `network=debug,http_api=trace`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModuleLogLevels {
+    pub levels: String,
+}
+
+/// The attestations and block headers verified by a beacon node since the last batch was taken,
+/// for processing by a remote slasher.
+///
+/// Attestations are split by variant so that they can be deserialized unambiguously.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(bound = "E: EthSpec")]
+pub struct SlasherBatch<E: EthSpec> {
+    /// Identifies the batch, so that the remote slasher can acknowledge it once processed.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub batch_id: u64,
+    pub attestations_base: Vec<IndexedAttestationBase<E>>,
+    pub attestations_electra: Vec<IndexedAttestationElectra<E>>,
+    pub block_headers: Vec<SignedBeaconBlockHeader>,
+}
+
+impl<E: EthSpec> Default for SlasherBatch<E> {
+    fn default() -> Self {
+        Self {
+            batch_id: 0,
+            attestations_base: vec![],
+            attestations_electra: vec![],
+            block_headers: vec![],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SlasherDequeueQuery {
+    /// The `batch_id` of the batch most recently processed by the remote slasher.
+    #[serde(default)]
+    pub ack: Option<u64>,
+}
+
+impl<E: EthSpec> SlasherBatch<E> {
+    pub fn push_attestation(&mut self, attestation: IndexedAttestation<E>) {
+        match attestation {
+            IndexedAttestation::Base(att) => self.attestations_base.push(att),
+            IndexedAttestation::Electra(att) => self.attestations_electra.push(att),
+        }
+    }
+
+    pub fn into_attestations(self) -> impl Iterator<Item = IndexedAttestation<E>> {
+        self.attestations_base
+            .into_iter()
+            .map(IndexedAttestation::Base)
+            .chain(
+                self.attestations_electra
+                    .into_iter()
+                    .map(IndexedAttestation::Electra),
+            )
+    }
 }
 
 impl BeaconNodeHttpClient {
@@ -410,6 +757,40 @@ impl BeaconNodeHttpClient {
         self.get(path).await
     }
 
+    /// `GET lighthouse/fork_choice`
+    pub async fn get_lighthouse_fork_choice(
+        &self,
+    ) -> Result<GenericResponse<ForkChoiceParameters>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("fork_choice");
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/debug/fork_choice_snapshot`
+    ///
+    /// Returns `Ok(None)` on a 404 error.
+    pub async fn get_lighthouse_debug_fork_choice_snapshot(
+        &self,
+    ) -> Result<Option<ForkChoiceSnapshot>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("debug")
+            .push("fork_choice_snapshot");
+
+        self.get_bytes_opt_accept_header(path, Accept::Ssz, self.timeouts.get_debug_beacon_states)
+            .await?
+            .map(|bytes| ForkChoiceSnapshot::from_ssz_bytes(&bytes).map_err(Error::InvalidSsz))
+            .transpose()
+    }
+
     /// `GET lighthouse/validator_inclusion/{epoch}/global`
     pub async fn get_lighthouse_validator_inclusion_global(
         &self,
@@ -460,6 +841,68 @@ impl BeaconNodeHttpClient {
         self.get(path).await
     }
 
+    /// `GET lighthouse/execution/capabilities`
+    ///
+    /// Returns the engine API methods supported by both Lighthouse and the execution engine.
+    pub async fn get_lighthouse_execution_capabilities(
+        &self,
+    ) -> Result<GenericResponse<Vec<String>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("execution")
+            .push("capabilities");
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/network/propagation`
+    pub async fn get_lighthouse_network_propagation(
+        &self,
+    ) -> Result<GenericResponse<Vec<PeerPropagationScore>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("network")
+            .push("propagation");
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/network/gossip_rejections`
+    pub async fn get_lighthouse_network_gossip_rejections(
+        &self,
+    ) -> Result<GenericResponse<GossipRejections>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("network")
+            .push("gossip_rejections");
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/execution/auth`
+    pub async fn get_lighthouse_execution_auth(
+        &self,
+    ) -> Result<GenericResponse<ExecutionAuthStatus>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("execution")
+            .push("auth");
+
+        self.get(path).await
+    }
+
     /// `GET lighthouse/eth1/block_cache`
     pub async fn get_lighthouse_eth1_block_cache(
         &self,
@@ -515,6 +958,21 @@ impl BeaconNodeHttpClient {
         self.get(path).await
     }
 
+    /// `GET lighthouse/database/info?sizes=true`
+    pub async fn get_lighthouse_database_info_with_sizes(&self) -> Result<DatabaseInfo, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("database")
+            .push("info");
+
+        path.query_pairs_mut().append_pair("sizes", "true");
+
+        self.get(path).await
+    }
+
     /// `POST lighthouse/database/reconstruct`
     pub async fn post_lighthouse_database_reconstruct(&self) -> Result<String, Error> {
         let mut path = self.server.full.clone();
@@ -528,6 +986,312 @@ impl BeaconNodeHttpClient {
         self.post_with_response(path, &()).await
     }
 
+    /// `GET lighthouse/beacon_processor/queues`
+    pub async fn get_lighthouse_beacon_processor_queues(
+        &self,
+    ) -> Result<GenericResponse<Vec<BeaconProcessorQueue>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("beacon_processor")
+            .push("queues");
+
+        self.get(path).await
+    }
+
+    /// `POST lighthouse/beacon_processor/queues/reset`
+    pub async fn post_lighthouse_beacon_processor_queues_reset(&self) -> Result<(), Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("beacon_processor")
+            .push("queues")
+            .push("reset");
+
+        self.post(path, &()).await?;
+        Ok(())
+    }
+
+    /// `GET lighthouse/pool/stats`
+    pub async fn get_lighthouse_pool_stats(&self) -> Result<GenericResponse<PoolStats>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("pool")
+            .push("stats");
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/partial_block_body`
+    pub async fn get_lighthouse_partial_block_body<E: EthSpec>(
+        &self,
+    ) -> Result<GenericResponse<Option<PartialBlockBody<E>>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("partial_block_body");
+
+        self.get(path).await
+    }
+
+    /// `POST lighthouse/partial_block_body`
+    pub async fn post_lighthouse_partial_block_body<E: EthSpec>(
+        &self,
+        partial_block_body: &PartialBlockBody<E>,
+    ) -> Result<(), Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("partial_block_body");
+
+        self.post(path, partial_block_body).await?;
+        Ok(())
+    }
+
+    /// `GET lighthouse/equivocations`
+    pub async fn get_lighthouse_equivocations(
+        &self,
+    ) -> Result<GenericResponse<Vec<ProposerSlashing>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("equivocations");
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/debug/memory`
+    pub async fn get_lighthouse_debug_memory(&self) -> Result<GenericResponse<MemoryInfo>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("debug")
+            .push("memory");
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/debug/profile`
+    ///
+    /// Captures a CPU profile of the beacon node for `duration`. Returns `Ok(None)` on a 404 error,
+    /// i.e. if profiling is not enabled.
+    pub async fn get_lighthouse_debug_profile(
+        &self,
+        duration: Duration,
+        format: ProfileFormat,
+    ) -> Result<Option<Vec<u8>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("debug")
+            .push("profile");
+
+        path.query_pairs_mut()
+            .append_pair("duration", &format!("{}ms", duration.as_millis()))
+            .append_pair("format", &format.to_string());
+
+        // The response only arrives once the profile is complete.
+        let timeout = duration + self.timeouts.get_debug_beacon_states;
+        self.get_bytes_opt_accept_header(path, Accept::Any, timeout)
+            .await
+    }
+
+    /// `POST lighthouse/slasher/dequeue?ack`
+    ///
+    /// Takes the attestations and block headers queued for a remote slasher. The beacon node
+    /// returns the same batch until it is acknowledged by passing its `batch_id` as `ack`.
+    pub async fn post_lighthouse_slasher_dequeue<E: EthSpec>(
+        &self,
+        ack: Option<u64>,
+    ) -> Result<GenericResponse<SlasherBatch<E>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("slasher")
+            .push("dequeue");
+
+        if let Some(ack) = ack {
+            path.query_pairs_mut().append_pair("ack", &ack.to_string());
+        }
+
+        self.post_with_response(path, &()).await
+    }
+
+    /// `GET lighthouse/logging`
+    pub async fn get_lighthouse_logging(&self) -> Result<GenericResponse<ModuleLogLevels>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("logging");
+
+        self.get(path).await
+    }
+
+    /// `POST lighthouse/logging`
+    pub async fn post_lighthouse_logging(
+        &self,
+        levels: &ModuleLogLevels,
+    ) -> Result<GenericResponse<ModuleLogLevels>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("logging");
+
+        self.post_with_response(path, levels).await
+    }
+
+    /// `POST lighthouse/debug/decode_ssz?type,fork`
+    pub async fn post_lighthouse_debug_decode_ssz(
+        &self,
+        object_type: SszObjectType,
+        fork: ForkName,
+        ssz_bytes: Vec<u8>,
+    ) -> Result<GenericResponse<serde_json::Value>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("debug")
+            .push("decode_ssz");
+
+        path.query_pairs_mut()
+            .append_pair("type", &object_type.to_string())
+            .append_pair("fork", &fork.to_string());
+
+        self.post_generic_with_consensus_version_and_ssz_body(path, ssz_bytes, None, fork)
+            .await?
+            .json()
+            .await
+            .map_err(Into::into)
+    }
+
+    /// `GET lighthouse/deposits/{pubkey}/status`
+    pub async fn get_lighthouse_deposit_status(
+        &self,
+        pubkey: &PublicKeyBytes,
+    ) -> Result<GenericResponse<DepositStatus>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("deposits")
+            .push(&pubkey.to_string())
+            .push("status");
+
+        self.get(path).await
+    }
+
+    /// `POST lighthouse/duties/all?epoch`
+    pub async fn post_lighthouse_duties_all(
+        &self,
+        epoch: Epoch,
+        indices: &[u64],
+    ) -> Result<GenericResponse<AllDuties>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("duties")
+            .push("all");
+
+        path.query_pairs_mut()
+            .append_pair("epoch", &epoch.to_string());
+
+        self.post_with_timeout_and_response(
+            path,
+            &ValidatorIndexDataRef(indices),
+            self.timeouts.attester_duties,
+        )
+        .await
+    }
+
+    /// `GET lighthouse/validator_set/changes?since_epoch`
+    pub async fn get_lighthouse_validator_set_changes(
+        &self,
+        since_epoch: Epoch,
+    ) -> Result<ExecutionOptimisticFinalizedResponse<ValidatorSetChanges>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("validator_set")
+            .push("changes");
+
+        path.query_pairs_mut()
+            .append_pair("since_epoch", &since_epoch.to_string());
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/proofs/historical_block_root/{slot}`
+    pub async fn get_lighthouse_proofs_historical_block_root(
+        &self,
+        slot: Slot,
+    ) -> Result<GenericResponse<HistoricalBlockRootProof>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("proofs")
+            .push("historical_block_root")
+            .push(&slot.to_string());
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/proofs/state/{state_id}?gindex`
+    pub async fn get_lighthouse_proofs_state(
+        &self,
+        state_id: StateId,
+        gindices: &[u64],
+    ) -> Result<ExecutionOptimisticFinalizedResponse<StateMultiproof>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("proofs")
+            .push("state")
+            .push(&state_id.to_string());
+
+        let gindices = gindices
+            .iter()
+            .map(|gindex| gindex.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        path.query_pairs_mut().append_pair("gindex", &gindices);
+
+        self.get(path).await
+    }
+
     ///
     /// Analysis endpoints.
     ///
@@ -553,6 +1317,72 @@ impl BeaconNodeHttpClient {
         self.get(path).await
     }
 
+    /// `GET` lighthouse/block_rewards/predicted?slot
+    pub async fn get_lighthouse_block_rewards_predicted(
+        &self,
+        slot: Slot,
+    ) -> Result<PredictedBlockReward, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("block_rewards")
+            .push("predicted");
+
+        path.query_pairs_mut()
+            .append_pair("slot", &slot.to_string());
+
+        self.get(path).await
+    }
+
+    fn get_lighthouse_analysis_state_diff_path(
+        &self,
+        from: Slot,
+        to: Slot,
+    ) -> Result<reqwest::Url, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("analysis")
+            .push("state_diff");
+
+        path.query_pairs_mut()
+            .append_pair("from", &from.to_string())
+            .append_pair("to", &to.to_string());
+
+        Ok(path)
+    }
+
+    /// `GET` lighthouse/analysis/state_diff?from,to
+    pub async fn get_lighthouse_analysis_state_diff(
+        &self,
+        from: Slot,
+        to: Slot,
+    ) -> Result<GenericResponse<StateDiff>, Error> {
+        let path = self.get_lighthouse_analysis_state_diff_path(from, to)?;
+
+        self.get(path).await
+    }
+
+    /// `GET` lighthouse/analysis/state_diff?from,to
+    ///
+    /// Returns `Ok(None)` on a 404 error.
+    pub async fn get_lighthouse_analysis_state_diff_ssz(
+        &self,
+        from: Slot,
+        to: Slot,
+    ) -> Result<Option<StateDiff>, Error> {
+        let path = self.get_lighthouse_analysis_state_diff_path(from, to)?;
+
+        self.get_bytes_opt_accept_header(path, Accept::Ssz, self.timeouts.get_debug_beacon_states)
+            .await?
+            .map(|bytes| StateDiff::from_ssz_bytes(&bytes).map_err(Error::InvalidSsz))
+            .transpose()
+    }
+
     /// `GET` lighthouse/analysis/block_packing?start_epoch,end_epoch
     pub async fn get_lighthouse_analysis_block_packing(
         &self,
@@ -574,6 +1404,53 @@ impl BeaconNodeHttpClient {
         self.get(path).await
     }
 
+    /// `GET` lighthouse/analysis/participation/{epoch}
+    pub async fn get_lighthouse_analysis_participation(
+        &self,
+        epoch: Epoch,
+    ) -> Result<EpochParticipation, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("analysis")
+            .push("participation")
+            .push(&epoch.to_string());
+
+        self.get(path).await
+    }
+
+    /// `GET` lighthouse/analysis/churn
+    pub async fn get_lighthouse_analysis_churn(
+        &self,
+    ) -> Result<GenericResponse<ChurnAnalysis>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("analysis")
+            .push("churn");
+
+        self.get(path).await
+    }
+
+    /// `GET` lighthouse/validator_monitor/sync_aggregation
+    pub async fn get_lighthouse_validator_monitor_sync_aggregation(
+        &self,
+    ) -> Result<GenericResponse<Vec<SyncAggregationSummary>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("validator_monitor")
+            .push("sync_aggregation");
+
+        self.get(path).await
+    }
+
     /// `GET` lighthouse/analysis/attestation_performance/{index}?start_epoch,end_epoch
     pub async fn get_lighthouse_analysis_attestation_performance(
         &self,
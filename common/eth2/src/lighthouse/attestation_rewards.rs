@@ -1,10 +1,64 @@
 use serde::{Deserialize, Serialize};
 use serde_utils::quoted_u64::Quoted;
+use ssz::four_byte_option_impl;
+use ssz_derive::{Decode, Encode};
+
+four_byte_option_impl!(four_byte_option_u64, u64);
+
+/// SSZ encoding of an `Option<Quoted<u64>>`, identical to that of an `Option<u64>`.
+#[allow(dead_code)]
+mod four_byte_option_quoted_u64 {
+    use serde_utils::quoted_u64::Quoted;
+
+    fn unquote(opt: &Option<Quoted<u64>>) -> Option<u64> {
+        opt.as_ref().map(|quoted| quoted.value)
+    }
+
+    pub mod encode {
+        use super::super::four_byte_option_u64::encode as inner;
+        use super::unquote;
+        use serde_utils::quoted_u64::Quoted;
+
+        pub fn is_ssz_fixed_len() -> bool {
+            inner::is_ssz_fixed_len()
+        }
+
+        pub fn ssz_fixed_len() -> usize {
+            inner::ssz_fixed_len()
+        }
+
+        pub fn ssz_bytes_len(opt: &Option<Quoted<u64>>) -> usize {
+            inner::ssz_bytes_len(&unquote(opt))
+        }
+
+        pub fn ssz_append(opt: &Option<Quoted<u64>>, buf: &mut Vec<u8>) {
+            inner::ssz_append(&unquote(opt), buf)
+        }
+    }
+
+    pub mod decode {
+        use super::super::four_byte_option_u64::decode as inner;
+        use serde_utils::quoted_u64::Quoted;
+        use ssz::DecodeError;
+
+        pub fn is_ssz_fixed_len() -> bool {
+            inner::is_ssz_fixed_len()
+        }
+
+        pub fn ssz_fixed_len() -> usize {
+            inner::ssz_fixed_len()
+        }
+
+        pub fn from_ssz_bytes(bytes: &[u8]) -> Result<Option<Quoted<u64>>, DecodeError> {
+            inner::from_ssz_bytes(bytes).map(|opt| opt.map(|value| Quoted { value }))
+        }
+    }
+}
 
 // Details about the rewards paid for attestations
 // All rewards in GWei
 
-#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, Encode, Decode)]
 pub struct IdealAttestationRewards {
     // Validator's effective balance in gwei
     #[serde(with = "serde_utils::quoted_u64")]
@@ -20,13 +74,33 @@ pub struct IdealAttestationRewards {
     pub source: u64,
     // Ideal attester's inclusion_delay reward in gwei (phase0 only)
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[ssz(with = "four_byte_option_quoted_u64")]
     pub inclusion_delay: Option<Quoted<u64>>,
     // Ideal attester's inactivity penalty in gwei
     #[serde(with = "serde_utils::quoted_i64")]
     pub inactivity: i64,
+    // The network context from which the ideal rewards are computed, which is the same for every
+    // effective balance. Slashed validators never count as participating.
+    //
+    // Total active balance of the epoch in gwei
+    #[serde(default, with = "serde_utils::quoted_u64")]
+    pub total_active_balance: u64,
+    // Balance of unslashed validators with a timely head vote in gwei
+    #[serde(default, with = "serde_utils::quoted_u64")]
+    pub head_participating_balance: u64,
+    // Balance of unslashed validators with a timely target vote in gwei
+    #[serde(default, with = "serde_utils::quoted_u64")]
+    pub target_participating_balance: u64,
+    // Balance of unslashed validators with a timely source vote in gwei
+    #[serde(default, with = "serde_utils::quoted_u64")]
+    pub source_participating_balance: u64,
+    // Whether the network is in an inactivity leak, in which case no head, target or source
+    // rewards are paid after Altair
+    #[serde(default)]
+    pub is_in_inactivity_leak: bool,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, Encode, Decode)]
 pub struct TotalAttestationRewards {
     // one entry for every validator based on their attestations in the epoch
     #[serde(with = "serde_utils::quoted_u64")]
@@ -42,14 +116,26 @@ pub struct TotalAttestationRewards {
     pub source: i64,
     // attester's inclusion_delay reward in gwei (phase0 only)
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[ssz(with = "four_byte_option_quoted_u64")]
     pub inclusion_delay: Option<Quoted<u64>>,
     // attester's inactivity penalty in gwei
     #[serde(with = "serde_utils::quoted_i64")]
     pub inactivity: i64,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, Encode, Decode)]
 pub struct StandardAttestationRewards {
     pub ideal_rewards: Vec<IdealAttestationRewards>,
     pub total_rewards: Vec<TotalAttestationRewards>,
 }
+
+// A single page of `StandardAttestationRewards`, returned when `page_size` or `page_token` is
+// provided. The `ideal_rewards` are included in every page.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct PagedAttestationRewards {
+    pub ideal_rewards: Vec<IdealAttestationRewards>,
+    pub total_rewards: Vec<TotalAttestationRewards>,
+    // Token with which to request the next page, absent on the final page
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_page_token: Option<String>,
+}
@@ -58,3 +58,28 @@ pub struct BlockRewardsQuery {
     #[serde(default)]
     pub include_attestations: bool,
 }
+
+/// The attestation rewards that the operation pool would currently pack into a block.
+///
+/// All rewards in GWei.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct PredictedBlockReward {
+    /// Slot of the hypothetical block.
+    pub slot: Slot,
+    /// Root of the head block, which the hypothetical block would build upon.
+    pub parent_root: Hash256,
+    /// The validator due to propose at `slot`.
+    pub proposer_index: u64,
+    /// Rewards due to the attestations which would be included.
+    pub attestation_rewards: AttestationRewards,
+}
+
+/// Query parameters for the `/lighthouse/block_rewards/predicted` endpoint.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct PredictedBlockRewardQuery {
+    /// Slot of the hypothetical block.
+    pub slot: Slot,
+    /// Include the full attestations themselves?
+    #[serde(default)]
+    pub include_attestations: bool,
+}
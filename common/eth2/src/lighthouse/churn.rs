@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+use types::Epoch;
+
+/// The state of the activation and exit queues at `epoch`, and how long a validator joining
+/// either queue now can expect to wait.
+///
+/// Before Electra the churn limits are numbers of validators. From Electra onwards they are
+/// balances in Gwei, and projections are for a validator with `MIN_ACTIVATION_BALANCE`.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct ChurnAnalysis {
+    pub epoch: Epoch,
+    /// The number of validators which are eligible for activation but have not been activated.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub activation_queue_length: u64,
+    /// The number of validators which have initiated an exit which has not yet taken effect.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub exit_queue_length: u64,
+    /// The total balance of deposits waiting to be applied, from Electra onwards.
+    pub pending_deposit_balance: Option<u64>,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub activation_churn_limit: u64,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub exit_churn_limit: u64,
+    /// The projected activation epoch of a validator whose deposit is processed now.
+    pub projected_activation_epoch: Epoch,
+    /// The projected exit epoch of a validator which initiates an exit now.
+    pub projected_exit_epoch: Epoch,
+}
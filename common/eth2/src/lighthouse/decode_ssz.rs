@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use types::ForkName;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DecodeSszQuery {
+    #[serde(rename = "type")]
+    pub object_type: SszObjectType,
+    pub fork: ForkName,
+}
+
+/// The types of object which can be decoded by `POST lighthouse/debug/decode_ssz`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SszObjectType {
+    /// A `SignedBeaconBlock`.
+    Block,
+    /// A `BeaconState`.
+    State,
+    /// An unaggregated or aggregated `Attestation`.
+    Attestation,
+    /// A `BlobSidecar`.
+    Blob,
+}
+
+impl fmt::Display for SszObjectType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Block => write!(f, "block"),
+            Self::State => write!(f, "state"),
+            Self::Attestation => write!(f, "attestation"),
+            Self::Blob => write!(f, "blob"),
+        }
+    }
+}
@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+use types::{Epoch, PublicKeyBytes, Slot};
+
+/// The progress of the deposits for `pubkey` from the deposit contract to activation.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct DepositStatus {
+    pub pubkey: PublicKeyBytes,
+    /// The deposits for `pubkey` in the deposit cache, in the order they were made.
+    pub deposits: Vec<ObservedDeposit>,
+    /// The index of the validator, if its first deposit has been processed into the head state.
+    pub validator_index: Option<u64>,
+    /// The number of deposits which must be processed into the beacon state before the first
+    /// deposit for `pubkey`, or `None` if it has already been processed or hasn't been observed.
+    pub deposits_ahead: Option<u64>,
+    /// The estimated slot at which the first deposit for `pubkey` will be processed.
+    ///
+    /// This is only known once the deposit has been included in the `eth1_data` of the head state,
+    /// after which the deposits ahead of it are included at `MAX_DEPOSITS` per block.
+    pub estimated_inclusion_slot: Option<Slot>,
+    pub activation_eligibility_epoch: Option<Epoch>,
+    /// The epoch at which the validator is, or is expected to be, activated.
+    ///
+    /// Estimates assume that the activation queue is processed at the current churn limit.
+    pub expected_activation_epoch: Option<Epoch>,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct ObservedDeposit {
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub index: u64,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub block_number: u64,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub amount: u64,
+    pub signature_is_valid: bool,
+    /// The number of execution blocks built on top of the block containing the deposit, if the
+    /// execution head is known.
+    pub confirmations: Option<u64>,
+}
@@ -0,0 +1,25 @@
+use crate::types::{AttesterData, ProposerData};
+use serde::{Deserialize, Serialize};
+use types::{Epoch, Hash256, SyncDuty};
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct AllDutiesQuery {
+    pub epoch: Epoch,
+}
+
+/// The proposer, attester and sync committee duties of a set of validators at `epoch`.
+///
+/// Proposer and attester duties are each valid for as long as the block at their respective
+/// dependent root remains canonical. Sync committee duties apply to the whole sync committee
+/// period containing `epoch`.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct AllDuties {
+    pub epoch: Epoch,
+    /// `true` if any of the duties were computed from an optimistic head.
+    pub execution_optimistic: Option<bool>,
+    pub proposer_dependent_root: Hash256,
+    pub attester_dependent_root: Hash256,
+    pub proposer_duties: Vec<ProposerData>,
+    pub attester_duties: Vec<AttesterData>,
+    pub sync_duties: Vec<SyncDuty>,
+}
@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+use types::Epoch;
+
+/// Network-wide attestation participation during an epoch, as used by epoch processing.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct EpochParticipation {
+    pub epoch: Epoch,
+    /// The number of validators active during the epoch.
+    pub active_validators: u64,
+    /// The total effective balance of the validators active during the epoch.
+    pub active_gwei: u64,
+    pub source: FlagParticipation,
+    pub target: FlagParticipation,
+    pub head: FlagParticipation,
+}
+
+/// Participation of unslashed validators for a single participation flag.
+///
+/// Prior to Altair, an attestation included on chain at any inclusion delay counts towards source
+/// participation, towards target participation if its target root matches and towards head
+/// participation if both its target and head block roots match. Since Altair, each flag is only set
+/// by attestations included within that flag's timeliness window.
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize)]
+pub struct FlagParticipation {
+    /// The number of participating validators.
+    pub validators: u64,
+    /// The total effective balance of the participating validators.
+    pub gwei: u64,
+    /// The fraction of active validators which participated.
+    pub validator_rate: f64,
+    /// The fraction of the active balance which participated.
+    pub balance_rate: f64,
+}
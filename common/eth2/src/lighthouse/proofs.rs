@@ -0,0 +1,42 @@
+use crate::types::query_vec;
+use serde::{Deserialize, Serialize};
+use types::{Hash256, Slot};
+
+/// A Merkle proof of the block root at `slot` against the state root `state_root`.
+///
+/// The `proof` is ordered from the leaf upwards and can be verified with
+/// `merkle_proof::verify_merkle_proof` using `depth = proof.len()` and
+/// `index = gindex - 2^depth`.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct HistoricalBlockRootProof {
+    pub slot: Slot,
+    pub block_root: Hash256,
+    pub state_slot: Slot,
+    pub state_root: Hash256,
+    /// The generalized index of `block_root` within the state.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub gindex: u64,
+    pub proof: Vec<Hash256>,
+}
+
+/// A Merkle multiproof of the nodes at `gindices` against the state root `state_root`.
+///
+/// The `proof` contains the hashes of the nodes at `helper_indices`, in the same order, as
+/// described by the `get_helper_indices` function of the SSZ specification.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct StateMultiproof {
+    pub state_root: Hash256,
+    #[serde(with = "serde_utils::quoted_u64_vec")]
+    pub gindices: Vec<u64>,
+    pub leaves: Vec<Hash256>,
+    #[serde(with = "serde_utils::quoted_u64_vec")]
+    pub helper_indices: Vec<u64>,
+    pub proof: Vec<Hash256>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StateProofQuery {
+    #[serde(deserialize_with = "query_vec")]
+    pub gindex: Vec<u64>,
+}
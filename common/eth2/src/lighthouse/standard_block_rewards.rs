@@ -1,8 +1,9 @@
 use serde::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode};
 
 // Details about the rewards for a single block
 // All rewards in GWei
-#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Encode, Decode)]
 pub struct StandardBlockReward {
     // proposer of the block, the proposer index who receives these rewards
     #[serde(with = "serde_utils::quoted_u64")]
@@ -0,0 +1,58 @@
+use crate::types::ValidatorStatus;
+use serde::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode};
+use types::{Hash256, Slot};
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct StateDiffQuery {
+    pub from: Slot,
+    pub to: Slot,
+}
+
+/// The validators whose balance, status or withdrawal credentials differ between the states at
+/// `from_slot` and `to_slot`.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Encode, Decode)]
+pub struct StateDiff {
+    pub from_slot: Slot,
+    pub to_slot: Slot,
+    pub validators: Vec<ValidatorDiff>,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Encode, Decode)]
+pub struct ValidatorDiff {
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub index: u64,
+    /// The validator in the `from` state, or `None` if it was added to the registry afterwards.
+    pub from: Option<ValidatorFields>,
+    /// The validator in the `to` state.
+    pub to: ValidatorFields,
+}
+
+impl ValidatorDiff {
+    pub fn balance_changed(&self) -> bool {
+        self.from
+            .as_ref()
+            .map_or(true, |from| from.balance != self.to.balance)
+    }
+
+    pub fn status_changed(&self) -> bool {
+        self.from
+            .as_ref()
+            .map_or(true, |from| from.status != self.to.status)
+    }
+
+    pub fn withdrawal_credentials_changed(&self) -> bool {
+        self.from.as_ref().map_or(true, |from| {
+            from.withdrawal_credentials != self.to.withdrawal_credentials
+        })
+    }
+}
+
+/// The fields of a validator which are compared by the state diff.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Encode, Decode)]
+pub struct ValidatorFields {
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub balance: u64,
+    pub status: ValidatorStatus,
+    pub withdrawal_credentials: Hash256,
+}
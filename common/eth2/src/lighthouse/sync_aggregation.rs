@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+use types::Epoch;
+
+/// The quality of the sync committee contributions published by monitored validators on a single
+/// subnet during an epoch.
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize)]
+pub struct SyncAggregationSummary {
+    pub epoch: Epoch,
+    pub subnet_id: u64,
+    /// The number of contributions published by monitored aggregators.
+    pub contributions: u64,
+    /// The number of sync signatures aggregated into the published contributions.
+    pub contribution_participants: u64,
+    /// The number of sync signatures for the subnet included in blocks, for the slots at which
+    /// contributions were published.
+    pub block_participants: u64,
+    /// The number of sync signatures from the published contributions which were included in
+    /// blocks.
+    pub block_inclusions: u64,
+}
@@ -1,9 +1,10 @@
 use serde::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode};
 
 // Details about the rewards paid to sync committee members for attesting headers
 // All rewards in GWei
 
-#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Encode, Decode)]
 pub struct SyncCommitteeReward {
     #[serde(with = "serde_utils::quoted_u64")]
     pub validator_index: u64,
@@ -0,0 +1,54 @@
+use serde::{Deserialize, Serialize};
+use serde_utils::quoted_u64::Quoted;
+use types::{Epoch, PublicKeyBytes};
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct ValidatorSetChangesQuery {
+    pub since_epoch: Epoch,
+}
+
+/// The changes to the validator set processed between the first slot of `since_epoch` and the
+/// first slot of `next_epoch`.
+///
+/// `next_epoch` should be used as the `since_epoch` of the subsequent request. A single response
+/// covers a limited number of epochs, so `next_epoch` may be before the current epoch of the head,
+/// in which case further changes are available immediately.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct ValidatorSetChanges {
+    pub since_epoch: Epoch,
+    pub next_epoch: Epoch,
+    pub changes: Vec<ValidatorSetChange>,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct ValidatorSetChange {
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub validator_index: u64,
+    pub pubkey: PublicKeyBytes,
+    pub kind: ValidatorSetChangeKind,
+    /// The epoch at which the change takes effect, if applicable.
+    ///
+    /// For activations and exits this is the scheduled activation or exit epoch, which may be in
+    /// the future.
+    pub epoch: Option<Epoch>,
+    /// The amount deposited in gwei, for top-ups.
+    pub amount: Option<Quoted<u64>>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValidatorSetChangeKind {
+    /// A deposit added the validator to the registry.
+    Deposit,
+    /// The validator was scheduled for activation.
+    Activation,
+    /// The validator initiated an exit, or was ejected.
+    Exit,
+    /// The validator was slashed.
+    Slashing,
+    /// A deposit added to the balance of an existing validator.
+    ///
+    /// Since Electra, deposits are credited by the epoch processing subject to the churn limit, and
+    /// are reported when credited. This includes the initial balance of new validators.
+    TopUp,
+}
@@ -305,6 +305,21 @@ impl ValidatorClientHttpClient {
         self.get(path).await
     }
 
+    /// `GET lighthouse/beacon/health`
+    pub async fn get_lighthouse_beacon_health(
+        &self,
+    ) -> Result<GenericResponse<Vec<BeaconNodeHealth>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("beacon")
+            .push("health");
+
+        self.get(path).await
+    }
+
     /// `GET lighthouse/spec`
     pub async fn get_lighthouse_spec<T: Serialize + DeserializeOwned>(
         &self,
@@ -349,6 +364,40 @@ impl ValidatorClientHttpClient {
         self.get_opt(path).await
     }
 
+    /// `GET lighthouse/validators/{validator_pubkey}/next_duties`
+    pub async fn get_lighthouse_validators_next_duties(
+        &self,
+        validator_pubkey: &PublicKeyBytes,
+    ) -> Result<GenericResponse<NextDuties>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("validators")
+            .push(&validator_pubkey.to_string())
+            .push("next_duties");
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/validators/{validator_pubkey}/fee_recipients`
+    pub async fn get_lighthouse_validators_fee_recipients(
+        &self,
+        validator_pubkey: &PublicKeyBytes,
+    ) -> Result<GenericResponse<Vec<BeaconNodeFeeRecipient>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("validators")
+            .push(&validator_pubkey.to_string())
+            .push("fee_recipients");
+
+        self.get(path).await
+    }
+
     /// `POST lighthouse/validators`
     pub async fn post_lighthouse_validators(
         &self,
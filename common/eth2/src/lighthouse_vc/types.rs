@@ -197,3 +197,47 @@ pub struct SingleExportKeystoresResponse {
 pub struct SetGraffitiRequest {
     pub graffiti: GraffitiString,
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BeaconNodeStatus {
+    /// The node is synced and ready to perform duties.
+    Synced,
+    /// The node is synced, but its head has not been verified by the execution layer.
+    Optimistic,
+    NotSynced,
+    Offline,
+    Incompatible,
+    /// The node has not yet been checked.
+    Uninitialized,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BeaconNodeHealth {
+    pub endpoint: String,
+    pub status: BeaconNodeStatus,
+}
+
+/// The upcoming duties of a validator, as currently known to the validator client.
+///
+/// Each duty is `None` if the validator client has no such duty cached, which does not necessarily
+/// mean that the validator will not be assigned one later.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NextDuties {
+    /// The index of the validator, or `None` if it is not yet known to the beacon chain.
+    pub validator_index: Option<u64>,
+    pub current_slot: Slot,
+    pub next_attestation_slot: Option<Slot>,
+    pub next_proposal_slot: Option<Slot>,
+    /// The first sync committee period, starting from the current period, in which the validator
+    /// is a member of the sync committee.
+    pub next_sync_committee_period: Option<u64>,
+}
+
+/// The fee recipient of a validator for blocks proposed via one of the configured beacon nodes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BeaconNodeFeeRecipient {
+    pub endpoint: String,
+    /// The fee recipient, or `None` if no fee recipient is configured for the validator.
+    pub fee_recipient: Option<Address>,
+}
@@ -5,7 +5,9 @@ use crate::{
     Error as ServerError, CONSENSUS_BLOCK_VALUE_HEADER, CONSENSUS_VERSION_HEADER,
     EXECUTION_PAYLOAD_BLINDED_HEADER, EXECUTION_PAYLOAD_VALUE_HEADER,
 };
-use lighthouse_network::{ConnectionDirection, Enr, Multiaddr, PeerConnectionStatus};
+use lighthouse_network::{
+    ConnectionDirection, ConnectionState, Enr, Multiaddr, PeerConnectionStatus,
+};
 use mediatype::{names, MediaType, MediaTypeList};
 use reqwest::header::HeaderMap;
 use serde::{Deserialize, Deserializer, Serialize};
@@ -354,8 +356,9 @@ pub struct ValidatorBalanceData {
 // this proposal:
 //
 // https://hackmd.io/bQxMDRt1RbS1TLno8K4NPg?view
-#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Encode, Decode)]
 #[serde(rename_all = "snake_case")]
+#[ssz(enum_behaviour = "tag")]
 pub enum ValidatorStatus {
     PendingInitialized,
     PendingQueued,
@@ -488,6 +491,53 @@ pub struct RandaoQuery {
     pub epoch: Option<Epoch>,
 }
 
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct AttestationRewardsQuery {
+    pub page_size: Option<usize>,
+    pub page_token: Option<String>,
+    pub units: Option<RewardUnits>,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct RewardsQuery {
+    pub units: Option<RewardUnits>,
+}
+
+/// The units of the amounts in a rewards response.
+///
+/// Amounts are in gwei by default. Amounts in other units are returned as exact decimal strings.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RewardUnits {
+    #[default]
+    Gwei,
+    Wei,
+    Eth,
+}
+
+impl fmt::Display for RewardUnits {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RewardUnits::Gwei => write!(f, "gwei"),
+            RewardUnits::Wei => write!(f, "wei"),
+            RewardUnits::Eth => write!(f, "eth"),
+        }
+    }
+}
+
+/// A rewards response with its amounts converted to `units`.
+///
+/// The shape of `data` matches the response without `units`, but the amounts may not fit the
+/// integer types of the typed response.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct RewardsInUnitsResponse {
+    pub execution_optimistic: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub finalized: Option<bool>,
+    pub units: RewardUnits,
+    pub data: Value,
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct AttestationPoolQuery {
     pub slot: Option<Slot>,
@@ -501,6 +551,10 @@ pub struct ValidatorsQuery {
     pub id: Option<Vec<ValidatorId>>,
     #[serde(default, deserialize_with = "option_query_vec")]
     pub status: Option<Vec<ValidatorStatus>>,
+    /// Lighthouse extension: the number of matching validators to skip.
+    pub offset: Option<usize>,
+    /// Lighthouse extension: the maximum number of validators to return.
+    pub limit: Option<usize>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -616,7 +670,7 @@ pub struct QueryVec<T: FromStr> {
     values: Vec<T>,
 }
 
-fn query_vec<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+pub(crate) fn query_vec<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
 where
     D: serde::Deserializer<'de>,
     T: FromStr,
@@ -803,6 +857,8 @@ pub struct PeersQuery {
     pub state: Option<Vec<PeerState>>,
     #[serde(default, deserialize_with = "option_query_vec")]
     pub direction: Option<Vec<PeerDirection>>,
+    pub page_size: Option<usize>,
+    pub page_token: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -823,6 +879,9 @@ pub struct PeersData {
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PeersMetaData {
     pub count: u64,
+    /// The token with which to request the next page, if any peers remain.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_page_token: Option<String>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
@@ -836,13 +895,20 @@ pub enum PeerState {
 
 impl PeerState {
     pub fn from_peer_connection_status(status: &PeerConnectionStatus) -> Self {
-        match status {
-            PeerConnectionStatus::Connected { .. } => PeerState::Connected,
-            PeerConnectionStatus::Dialing { .. } => PeerState::Connecting,
-            PeerConnectionStatus::Disconnecting { .. } => PeerState::Disconnecting,
-            PeerConnectionStatus::Disconnected { .. }
-            | PeerConnectionStatus::Banned { .. }
-            | PeerConnectionStatus::Unknown => PeerState::Disconnected,
+        match status.connection_state() {
+            ConnectionState::Connected => PeerState::Connected,
+            ConnectionState::Connecting => PeerState::Connecting,
+            ConnectionState::Disconnecting => PeerState::Disconnecting,
+            ConnectionState::Disconnected => PeerState::Disconnected,
+        }
+    }
+
+    pub fn connection_state(&self) -> ConnectionState {
+        match self {
+            PeerState::Connected => ConnectionState::Connected,
+            PeerState::Connecting => ConnectionState::Connecting,
+            PeerState::Disconnecting => ConnectionState::Disconnecting,
+            PeerState::Disconnected => ConnectionState::Disconnected,
         }
     }
 }
@@ -886,6 +952,13 @@ impl PeerDirection {
             ConnectionDirection::Outgoing => PeerDirection::Outbound,
         }
     }
+
+    pub fn connection_direction(&self) -> ConnectionDirection {
+        match self {
+            PeerDirection::Inbound => ConnectionDirection::Incoming,
+            PeerDirection::Outbound => ConnectionDirection::Outgoing,
+        }
+    }
 }
 
 impl FromStr for PeerDirection {
@@ -1004,6 +1077,17 @@ pub struct SseLateHead {
     pub execution_optimistic: bool,
 }
 
+/// Emitted when the chain starts or stops failing to finalize.
+#[derive(PartialEq, Debug, Serialize, Deserialize, Clone)]
+pub struct SseFinalityStall {
+    /// `true` when a stall has been detected, `false` once the chain has finalized again.
+    pub stalled: bool,
+    pub current_epoch: Epoch,
+    pub finalized_epoch: Epoch,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub epochs_since_finalization: u64,
+}
+
 #[superstruct(
     variants(V1, V2, V3),
     variant_attributes(derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize))
@@ -1097,6 +1181,7 @@ pub enum EventKind<E: EthSpec> {
     ChainReorg(SseChainReorg),
     ContributionAndProof(Box<SignedContributionAndProof<E>>),
     LateHead(SseLateHead),
+    FinalityStall(SseFinalityStall),
     LightClientFinalityUpdate(Box<LightClientFinalityUpdate<E>>),
     LightClientOptimisticUpdate(Box<LightClientOptimisticUpdate<E>>),
     #[cfg(feature = "lighthouse")]
@@ -1121,6 +1206,7 @@ impl<E: EthSpec> EventKind<E> {
             EventKind::ContributionAndProof(_) => "contribution_and_proof",
             EventKind::PayloadAttributes(_) => "payload_attributes",
             EventKind::LateHead(_) => "late_head",
+            EventKind::FinalityStall(_) => "finality_stall",
             EventKind::LightClientFinalityUpdate(_) => "light_client_finality_update",
             EventKind::LightClientOptimisticUpdate(_) => "light_client_optimistic_update",
             #[cfg(feature = "lighthouse")]
@@ -1174,6 +1260,11 @@ impl<E: EthSpec> EventKind<E> {
             "late_head" => Ok(EventKind::LateHead(serde_json::from_str(data).map_err(
                 |e| ServerError::InvalidServerSentEvent(format!("Late Head: {:?}", e)),
             )?)),
+            "finality_stall" => Ok(EventKind::FinalityStall(
+                serde_json::from_str(data).map_err(|e| {
+                    ServerError::InvalidServerSentEvent(format!("Finality Stall: {:?}", e))
+                })?,
+            )),
             "voluntary_exit" => Ok(EventKind::VoluntaryExit(
                 serde_json::from_str(data).map_err(|e| {
                     ServerError::InvalidServerSentEvent(format!("Voluntary Exit: {:?}", e))
@@ -1253,6 +1344,7 @@ pub enum EventTopic {
     ChainReorg,
     ContributionAndProof,
     LateHead,
+    FinalityStall,
     PayloadAttributes,
     LightClientFinalityUpdate,
     LightClientOptimisticUpdate,
@@ -1279,6 +1371,7 @@ impl FromStr for EventTopic {
             "contribution_and_proof" => Ok(EventTopic::ContributionAndProof),
             "payload_attributes" => Ok(EventTopic::PayloadAttributes),
             "late_head" => Ok(EventTopic::LateHead),
+            "finality_stall" => Ok(EventTopic::FinalityStall),
             "light_client_finality_update" => Ok(EventTopic::LightClientFinalityUpdate),
             "light_client_optimistic_update" => Ok(EventTopic::LightClientOptimisticUpdate),
             #[cfg(feature = "lighthouse")]
@@ -1305,6 +1398,7 @@ impl fmt::Display for EventTopic {
             EventTopic::ContributionAndProof => write!(f, "contribution_and_proof"),
             EventTopic::PayloadAttributes => write!(f, "payload_attributes"),
             EventTopic::LateHead => write!(f, "late_head"),
+            EventTopic::FinalityStall => write!(f, "finality_stall"),
             EventTopic::LightClientFinalityUpdate => write!(f, "light_client_finality_update"),
             EventTopic::LightClientOptimisticUpdate => write!(f, "light_client_optimistic_update"),
             #[cfg(feature = "lighthouse")]
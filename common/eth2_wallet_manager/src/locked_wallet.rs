@@ -91,4 +91,20 @@ impl LockedWallet {
 
         Ok(keystores)
     }
+
+    /// Calls `Wallet::set_nextaccount` on the underlying `wallet`.
+    ///
+    /// Ensures that the wallet JSON file is updated after the call.
+    ///
+    /// ## Errors
+    ///
+    /// - If `nextaccount` is less than the wallet's current `nextaccount`.
+    /// - If there is a file-system error.
+    pub fn set_nextaccount(&mut self, nextaccount: u32) -> Result<(), Error> {
+        self.wallet.set_nextaccount(nextaccount)?;
+
+        update(&self.wallet_dir, &self.wallet)?;
+
+        Ok(())
+    }
 }
@@ -0,0 +1,128 @@
+//! Allows the buckets of histograms to be overridden at runtime.
+//!
+//! The configuration is a TOML file mapping metric names to bucket upper bounds. A key ending in
+//! `*` names a metric group and applies to every histogram whose name starts with the preceding
+//! prefix:
+//!
+//! ```toml
+//! beacon_block_processing_seconds = [0.05, 0.1, 0.25, 0.5, 1, 2, 5, 10]
+//! "beacon_processor_*" = [0.001, 0.01, 0.1, 1, 10]
+//! ```
+//!
+//! An exact metric name takes precedence over a group, and a longer group prefix takes precedence
+//! over a shorter one.
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// The suffix which denotes a metric group rather than a single metric.
+const GROUP_WILDCARD: char = '*';
+
+static HISTOGRAM_BUCKETS_CONFIG: OnceLock<HistogramBucketsConfig> = OnceLock::new();
+
+/// Overrides for the buckets of histograms, keyed by metric name or metric group.
+#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+#[serde(transparent)]
+pub struct HistogramBucketsConfig {
+    buckets: HashMap<String, Vec<f64>>,
+}
+
+impl HistogramBucketsConfig {
+    pub fn from_toml_file(path: &Path) -> Result<Self, String> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| format!("Unable to read {}: {:?}", path.display(), e))?;
+        Self::from_toml_str(&contents).map_err(|e| format!("Invalid {}: {}", path.display(), e))
+    }
+
+    pub fn from_toml_str(contents: &str) -> Result<Self, String> {
+        let config: Self = toml::from_str(contents).map_err(|e| e.to_string())?;
+
+        for (key, buckets) in &config.buckets {
+            if buckets.is_empty() {
+                return Err(format!("no buckets provided for {}", key));
+            }
+            if buckets.iter().any(|bound| !bound.is_finite()) {
+                return Err(format!("buckets for {} must be finite", key));
+            }
+            if buckets.windows(2).any(|pair| pair[0] >= pair[1]) {
+                return Err(format!("buckets for {} must be strictly increasing", key));
+            }
+        }
+
+        Ok(config)
+    }
+
+    /// Returns the buckets configured for the histogram called `name`, if any.
+    pub fn buckets_for(&self, name: &str) -> Option<&[f64]> {
+        if let Some(buckets) = self.buckets.get(name) {
+            return Some(buckets);
+        }
+
+        self.buckets
+            .iter()
+            .filter_map(|(key, buckets)| {
+                let prefix = key.strip_suffix(GROUP_WILDCARD)?;
+                name.starts_with(prefix).then_some((prefix.len(), buckets))
+            })
+            .max_by_key(|(prefix_len, _)| *prefix_len)
+            .map(|(_, buckets)| buckets.as_slice())
+    }
+}
+
+/// Sets the global histogram bucket overrides.
+///
+/// This must be called before any histograms are created, since a histogram's buckets are fixed
+/// when it is registered. Returns an error if the overrides have already been set.
+pub fn set_histogram_buckets_config(config: HistogramBucketsConfig) -> Result<(), String> {
+    HISTOGRAM_BUCKETS_CONFIG
+        .set(config)
+        .map_err(|_| "histogram buckets have already been configured".to_string())
+}
+
+/// Returns the globally configured buckets for the histogram called `name`, if any.
+pub(crate) fn configured_buckets(name: &str) -> Option<Vec<f64>> {
+    HISTOGRAM_BUCKETS_CONFIG
+        .get()?
+        .buckets_for(name)
+        .map(<[f64]>::to_vec)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exact_name_takes_precedence_over_groups() {
+        let config = HistogramBucketsConfig::from_toml_str(
+            r#"
+            beacon_block_processing_seconds = [1, 2]
+            "beacon_*" = [3, 4]
+            "beacon_block_*" = [5, 6]
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.buckets_for("beacon_block_processing_seconds"),
+            Some([1.0, 2.0].as_slice())
+        );
+        assert_eq!(
+            config.buckets_for("beacon_block_imported_seconds"),
+            Some([5.0, 6.0].as_slice())
+        );
+        assert_eq!(
+            config.buckets_for("beacon_processor_work_seconds"),
+            Some([3.0, 4.0].as_slice())
+        );
+        assert_eq!(config.buckets_for("http_api_seconds"), None);
+    }
+
+    #[test]
+    fn invalid_buckets_are_rejected() {
+        assert!(HistogramBucketsConfig::from_toml_str("a = []").is_err());
+        assert!(HistogramBucketsConfig::from_toml_str("a = [2, 1]").is_err());
+        assert!(HistogramBucketsConfig::from_toml_str("a = [1, 1]").is_err());
+        assert!(HistogramBucketsConfig::from_toml_str("a = 1").is_err());
+    }
+}
@@ -53,9 +53,13 @@
 //! }
 //! ```
 
+mod buckets_config;
+
 use prometheus::{Error, HistogramOpts, Opts};
 use std::time::Duration;
 
+pub use buckets_config::{set_histogram_buckets_config, HistogramBucketsConfig};
+
 use prometheus::core::{Atomic, GenericGauge, GenericGaugeVec};
 pub use prometheus::{
     exponential_buckets, linear_buckets,
@@ -104,12 +108,18 @@ pub fn try_create_histogram(name: &str, help: &str) -> Result<Histogram> {
 
 /// Attempts to create a `Histogram` with specified buckets, returning `Err` if the registry does not accept the counter
 /// (potentially due to naming conflict) or no valid buckets are provided.
+///
+/// The buckets are overridden by any configured via `set_histogram_buckets_config`.
 pub fn try_create_histogram_with_buckets(
     name: &str,
     help: &str,
     buckets: Result<Vec<f64>>,
 ) -> Result<Histogram> {
-    let opts = HistogramOpts::new(name, help).buckets(buckets?);
+    let buckets = match buckets_config::configured_buckets(name) {
+        Some(configured) => configured,
+        None => buckets?,
+    };
+    let opts = HistogramOpts::new(name, help).buckets(buckets);
     let histogram = Histogram::with_opts(opts)?;
     prometheus::register(Box::new(histogram.clone()))?;
     Ok(histogram)
@@ -127,13 +137,19 @@ pub fn try_create_histogram_vec(
 
 /// Attempts to create a `HistogramVec` with specified buckets, returning `Err` if the registry does not accept the counter
 /// (potentially due to naming conflict) or no valid buckets are provided.
+///
+/// The buckets are overridden by any configured via `set_histogram_buckets_config`.
 pub fn try_create_histogram_vec_with_buckets(
     name: &str,
     help: &str,
     buckets: Result<Vec<f64>>,
     label_names: &[&str],
 ) -> Result<HistogramVec> {
-    let opts = HistogramOpts::new(name, help).buckets(buckets?);
+    let buckets = match buckets_config::configured_buckets(name) {
+        Some(configured) => configured,
+        None => buckets?,
+    };
+    let opts = HistogramOpts::new(name, help).buckets(buckets);
     let histogram_vec = HistogramVec::new(opts, label_names)?;
     prometheus::register(Box::new(histogram_vec.clone()))?;
     Ok(histogram_vec)
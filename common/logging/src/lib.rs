@@ -10,16 +10,20 @@ use std::time::{Duration, Instant};
 use tracing_appender::non_blocking::NonBlocking;
 use tracing_appender::rolling::{RollingFileAppender, Rotation};
 use tracing_logging_layer::LoggingLayer;
-use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, Layer};
 
 pub const MAX_MESSAGE_WIDTH: usize = 40;
 
 pub mod async_record;
+mod module_levels;
 mod sse_logging_components;
+mod telemetry;
 mod tracing_logging_layer;
 mod tracing_metrics_layer;
 
+pub use module_levels::{ModuleLevelFilter, ModuleLevels};
 pub use sse_logging_components::SSELoggingComponents;
+pub use telemetry::{shutdown_telemetry, telemetry_layer, TelemetryConfig};
 pub use tracing_metrics_layer::MetricsLayer;
 
 /// The minimum interval between log messages indicating that a queue is full.
@@ -221,7 +225,7 @@ impl TimeLatch {
     }
 }
 
-pub fn create_tracing_layer(base_tracing_log_path: PathBuf) {
+pub fn create_tracing_layer(base_tracing_log_path: PathBuf, telemetry: Option<TelemetryConfig>) {
     let filter_layer = match tracing_subscriber::EnvFilter::try_from_default_env()
         .or_else(|_| tracing_subscriber::EnvFilter::try_new("warn"))
     {
@@ -264,12 +268,23 @@ pub fn create_tracing_layer(base_tracing_log_path: PathBuf) {
         _discv5_guard,
     };
 
-    if let Err(e) = tracing_subscriber::fmt()
-        .with_env_filter(filter_layer)
-        .with_writer(std::io::sink)
-        .finish()
-        .with(MetricsLayer)
-        .with(custom_layer)
+    let telemetry_layer = match telemetry.as_ref().map(telemetry_layer).transpose() {
+        Ok(layer) => layer,
+        Err(e) => {
+            eprintln!("Failed to initialize telemetry export {e}");
+            None
+        }
+    };
+
+    // The env filter only applies to the dependency logging layers, so that it doesn't prevent
+    // spans being exported to the telemetry collector.
+    if let Err(e) = tracing_subscriber::registry()
+        .with(
+            MetricsLayer
+                .and_then(custom_layer)
+                .with_filter(filter_layer),
+        )
+        .with(telemetry_layer)
         .try_init()
     {
         eprintln!("Failed to initialize dependency logging {e}");
@@ -0,0 +1,171 @@
+//! Allows the log level of individual modules to be changed whilst the process is running.
+//!
+//! Overrides are expressed as comma-separated directives, e.g. `network=debug,http_api=trace`.
+//! Each directive applies to every record logged from within the named module (or any of its
+//! sub-modules), taking precedence over the level the drain was created with. When several
+//! directives match a record, the most specific (longest) one wins.
+use parking_lot::RwLock;
+use slog::{Drain, Level, OwnedKVList, Record};
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// A shareable handle to the per-module log level overrides.
+#[derive(Clone, Default)]
+pub struct ModuleLevels {
+    directives: Arc<RwLock<Vec<(String, Level)>>>,
+}
+
+impl ModuleLevels {
+    /// Replaces all existing overrides with those in `directives`.
+    ///
+    /// An empty string removes all overrides.
+    pub fn set(&self, directives: &str) -> Result<(), String> {
+        let parsed = parse_directives(directives)?;
+        *self.directives.write() = parsed;
+        Ok(())
+    }
+
+    /// Returns the current overrides in the same format accepted by `Self::set`.
+    pub fn directives(&self) -> String {
+        self.directives
+            .read()
+            .iter()
+            .map(|(module, level)| format!("{}={}", module, level.as_str().to_lowercase()))
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
+    /// Returns the override for the given module path, if any.
+    fn level_for(&self, module: &str) -> Option<Level> {
+        self.directives
+            .read()
+            .iter()
+            .filter(|(prefix, _)| module_matches(module, prefix))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, level)| *level)
+    }
+
+    /// Returns the most verbose level of any override.
+    fn most_verbose(&self) -> Option<Level> {
+        self.directives
+            .read()
+            .iter()
+            .map(|(_, level)| *level)
+            .max_by_key(|level| level.as_usize())
+    }
+}
+
+/// Returns `true` if `module` is `prefix` or one of its sub-modules.
+fn module_matches(module: &str, prefix: &str) -> bool {
+    module
+        .strip_prefix(prefix)
+        .map_or(false, |rest| rest.is_empty() || rest.starts_with("::"))
+}
+
+fn parse_directives(directives: &str) -> Result<Vec<(String, Level)>, String> {
+    directives
+        .split(',')
+        .map(str::trim)
+        .filter(|directive| !directive.is_empty())
+        .map(|directive| {
+            let (module, level) = directive.split_once('=').ok_or_else(|| {
+                format!("Directive {} must be of the form module=level", directive)
+            })?;
+            let module = module.trim();
+            if module.is_empty() {
+                return Err(format!("Directive {} is missing a module", directive));
+            }
+            let level = Level::from_str(level.trim())
+                .map_err(|()| format!("Directive {} has an unknown level", directive))?;
+            Ok((module.to_string(), level))
+        })
+        .collect()
+}
+
+/// A drain which filters records by level, respecting any per-module overrides.
+pub struct ModuleLevelFilter<D> {
+    drain: D,
+    default_level: Level,
+    module_levels: ModuleLevels,
+}
+
+impl<D> ModuleLevelFilter<D> {
+    pub fn new(drain: D, default_level: Level, module_levels: ModuleLevels) -> Self {
+        Self {
+            drain,
+            default_level,
+            module_levels,
+        }
+    }
+}
+
+impl<D: Drain> Drain for ModuleLevelFilter<D> {
+    type Ok = Option<D::Ok>;
+    type Err = D::Err;
+
+    fn log(&self, record: &Record, values: &OwnedKVList) -> Result<Self::Ok, Self::Err> {
+        let level = self
+            .module_levels
+            .level_for(record.module())
+            .unwrap_or(self.default_level);
+        if record.level().is_at_least(level) {
+            self.drain.log(record, values).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn is_enabled(&self, level: Level) -> bool {
+        let enabled = level.is_at_least(self.default_level)
+            || self
+                .module_levels
+                .most_verbose()
+                .map_or(false, |verbose| level.is_at_least(verbose));
+        enabled && self.drain.is_enabled(level)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn most_specific_directive_wins() {
+        let levels = ModuleLevels::default();
+        levels
+            .set("network=debug, network::sync=trace,http_api=warn")
+            .unwrap();
+
+        assert_eq!(levels.level_for("network"), Some(Level::Debug));
+        assert_eq!(levels.level_for("network::router"), Some(Level::Debug));
+        assert_eq!(
+            levels.level_for("network::sync::manager"),
+            Some(Level::Trace)
+        );
+        assert_eq!(
+            levels.level_for("http_api::publish_blocks"),
+            Some(Level::Warning)
+        );
+        assert_eq!(levels.level_for("network_utils"), None);
+        assert_eq!(levels.most_verbose(), Some(Level::Trace));
+        assert_eq!(
+            levels.directives(),
+            "network=debug,network::sync=trace,http_api=warn"
+        );
+
+        levels.set("").unwrap();
+        assert_eq!(levels.level_for("network"), None);
+    }
+
+    #[test]
+    fn invalid_directives_are_rejected() {
+        let levels = ModuleLevels::default();
+        levels.set("network=debug").unwrap();
+
+        assert!(levels.set("network").is_err());
+        assert!(levels.set("=debug").is_err());
+        assert!(levels.set("network=loud").is_err());
+        // Existing overrides are retained after an error.
+        assert_eq!(levels.directives(), "network=debug");
+    }
+}
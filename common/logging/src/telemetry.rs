@@ -0,0 +1,65 @@
+//! Exports `tracing` spans to an OpenTelemetry collector over OTLP.
+//!
+//! Only spans from the crates listed in `TELEMETRY_TARGETS` are exported. These cover the block
+//! import and block production pipelines, which allows the timeline of a slow slot to be viewed in
+//! a tracing backend such as Grafana Tempo or Jaeger.
+use opentelemetry::KeyValue;
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::{runtime, trace, Resource};
+use tracing::Subscriber;
+use tracing_subscriber::filter::{LevelFilter, Targets};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Layer;
+
+/// The crates whose spans are exported, along with the most verbose level exported for each.
+const TELEMETRY_TARGETS: &[(&str, LevelFilter)] = &[
+    ("beacon_chain", LevelFilter::INFO),
+    ("http_api", LevelFilter::INFO),
+    ("network", LevelFilter::INFO),
+];
+
+/// Configuration for exporting spans to an OpenTelemetry collector.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TelemetryConfig {
+    /// The OTLP/gRPC endpoint of the collector, e.g. `http://localhost:4317`.
+    pub collector_url: String,
+    /// The `service.name` attached to every exported span.
+    pub service_name: String,
+}
+
+/// Returns a layer which exports spans to the collector described by `config`.
+///
+/// Spans are exported in batches by a background task, so this must be called from within a
+/// Tokio runtime.
+pub fn telemetry_layer<S>(
+    config: &TelemetryConfig,
+) -> Result<impl Layer<S> + Send + Sync + 'static, String>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+{
+    let tracer = opentelemetry_otlp::new_pipeline()
+        .tracing()
+        .with_exporter(
+            opentelemetry_otlp::new_exporter()
+                .tonic()
+                .with_endpoint(config.collector_url.clone()),
+        )
+        .with_trace_config(trace::Config::default().with_resource(Resource::new(vec![
+            KeyValue::new("service.name", config.service_name.clone()),
+        ])))
+        .install_batch(runtime::Tokio)
+        .map_err(|e| format!("Unable to initialize OTLP exporter: {:?}", e))?;
+
+    let targets = Targets::new().with_targets(TELEMETRY_TARGETS.iter().copied());
+
+    Ok(tracing_opentelemetry::layer()
+        .with_tracer(tracer)
+        .with_filter(targets))
+}
+
+/// Exports any buffered spans and shuts down the exporter.
+///
+/// This is a no-op if `telemetry_layer` was never called.
+pub fn shutdown_telemetry() {
+    opentelemetry::global::shutdown_tracer_provider();
+}
@@ -7,6 +7,7 @@
 //!
 //! A) `JEMALLOC_SYS_WITH_MALLOC_CONF` at compile-time.
 //! B) `_RJEM_MALLOC_CONF` at runtime.
+use crate::AllocatorStats;
 use jemalloc_ctl::{arenas, epoch, stats, Error};
 use lazy_static::lazy_static;
 use lighthouse_metrics::{set_gauge, try_create_int_gauge, IntGauge};
@@ -50,3 +51,26 @@ pub fn scrape_jemalloc_metrics_fallible() -> Result<(), Error> {
 
     Ok(())
 }
+
+/// Read the current jemalloc statistics.
+pub fn jemalloc_stats() -> Result<AllocatorStats, String> {
+    jemalloc_stats_fallible().map_err(|e| format!("unable to read jemalloc stats: {:?}", e))
+}
+
+fn jemalloc_stats_fallible() -> Result<AllocatorStats, Error> {
+    // Advance the epoch so that the underlying statistics are updated.
+    epoch::advance()?;
+
+    Ok(AllocatorStats {
+        allocator: "jemalloc",
+        stats: vec![
+            ("num_arenas", arenas::narenas::read()? as u64),
+            ("allocated", stats::allocated::read()? as u64),
+            ("active", stats::active::read()? as u64),
+            ("mapped", stats::mapped::read()? as u64),
+            ("metadata", stats::metadata::read()? as u64),
+            ("resident", stats::resident::read()? as u64),
+            ("retained", stats::retained::read()? as u64),
+        ],
+    })
+}
@@ -36,6 +36,15 @@ mod jemalloc;
 
 pub use interface::*;
 
+/// A snapshot of the statistics reported by the allocator.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AllocatorStats {
+    /// The name of the allocator, e.g. `jemalloc` or `glibc`.
+    pub allocator: &'static str,
+    /// Each statistic as a `(name, value)` pair. Values are in bytes unless named otherwise.
+    pub stats: Vec<(&'static str, u64)>,
+}
+
 #[cfg(all(
     target_os = "linux",
     not(target_env = "musl"),
@@ -43,6 +52,7 @@ pub use interface::*;
 ))]
 mod interface {
     pub use crate::glibc::configure_glibc_malloc as configure_memory_allocator;
+    pub use crate::glibc::mallinfo_stats as allocator_stats;
     pub use crate::glibc::scrape_mallinfo_metrics as scrape_allocator_metrics;
 }
 
@@ -53,6 +63,7 @@ mod interface {
         Ok(())
     }
 
+    pub use crate::jemalloc::jemalloc_stats as allocator_stats;
     pub use crate::jemalloc::scrape_jemalloc_metrics as scrape_allocator_metrics;
 }
 
@@ -68,4 +79,9 @@ mod interface {
 
     #[allow(dead_code)]
     pub fn scrape_allocator_metrics() {}
+
+    #[allow(dead_code)]
+    pub fn allocator_stats() -> Result<crate::AllocatorStats, String> {
+        Err("the system allocator does not provide statistics".to_string())
+    }
 }
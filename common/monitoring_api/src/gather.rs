@@ -15,6 +15,10 @@ pub struct JsonMetric {
     json_output_key: &'static str,
     /// Type of the json value to be sent to the remote monitoring endpoint
     ty: JsonType,
+    /// Label values which select the metrics to be sent, for metrics with labels.
+    ///
+    /// The values of all metrics with matching labels are summed.
+    labels: &'static [(&'static str, &'static str)],
 }
 
 impl JsonMetric {
@@ -22,11 +26,21 @@ impl JsonMetric {
         lighthouse_metric_name: &'static str,
         json_output_key: &'static str,
         ty: JsonType,
+    ) -> Self {
+        Self::with_labels(lighthouse_metric_name, json_output_key, ty, &[])
+    }
+
+    const fn with_labels(
+        lighthouse_metric_name: &'static str,
+        json_output_key: &'static str,
+        ty: JsonType,
+        labels: &'static [(&'static str, &'static str)],
     ) -> Self {
         Self {
             lighthouse_metric_name,
             json_output_key,
             ty,
+            labels,
         }
     }
 
@@ -94,6 +108,18 @@ const BEACON_PROCESS_METRICS: &[JsonMetric] = &[
         JsonType::Integer,
     ),
     JsonMetric::new("sync_eth2_synced", "sync_eth2_synced", JsonType::Boolean),
+    JsonMetric::with_labels(
+        "validator_monitor_missed_blocks_total",
+        "validator_monitor_proposals_missed_total",
+        JsonType::Integer,
+        &[("validator", "total")],
+    ),
+    JsonMetric::with_labels(
+        "validator_monitor_sync_committee_messages_total",
+        "validator_monitor_sync_committee_messages_total",
+        JsonType::Integer,
+        &[("validator", "total")],
+    ),
 ];
 
 const VALIDATOR_PROCESS_METRICS: &[JsonMetric] = &[
@@ -117,6 +143,37 @@ const VALIDATOR_PROCESS_METRICS: &[JsonMetric] = &[
         "sync_eth2_fallback_connected",
         JsonType::Boolean,
     ),
+    JsonMetric::new(
+        "validator_actual_reward",
+        "validator_epoch_reward_gwei",
+        JsonType::Integer,
+    ),
+    JsonMetric::new(
+        "validator_expected_reward",
+        "validator_epoch_expected_reward_gwei",
+        JsonType::Integer,
+    ),
+    JsonMetric::new(
+        "vc_beacon_block_proposals_published_total",
+        "validator_proposals_made_total",
+        JsonType::Integer,
+    ),
+    JsonMetric::new(
+        "vc_beacon_block_proposals_failed_total",
+        "validator_proposals_missed_total",
+        JsonType::Integer,
+    ),
+    JsonMetric::new(
+        "vc_beacon_block_proposals_refused_total",
+        "validator_proposals_refused_total",
+        JsonType::Integer,
+    ),
+    JsonMetric::with_labels(
+        "vc_signed_sync_committee_messages_total",
+        "validator_sync_committee_messages_total",
+        JsonType::Integer,
+        &[("status", "success")],
+    ),
 ];
 
 /// Represents the type for the JSON output.
@@ -140,14 +197,33 @@ lazy_static! {
         .collect();
 }
 
-/// Returns the value from a Counter/Gauge `MetricType` assuming that it has no associated labels
-/// else it returns `None`.
-fn get_value(mf: &MetricFamily) -> Option<i64> {
-    let metric = mf.get_metric().first()?;
-    match mf.get_field_type() {
-        MetricType::COUNTER => Some(metric.get_counter().get_value() as i64),
-        MetricType::GAUGE => Some(metric.get_gauge().get_value() as i64),
-        _ => None,
+/// Returns the sum of the values of the Counter/Gauge `MetricType` metrics whose labels match
+/// `labels`, or `None` if there are no such metrics.
+///
+/// Metrics without labels always match.
+fn get_value(mf: &MetricFamily, labels: &[(&str, &str)]) -> Option<i64> {
+    let values = mf
+        .get_metric()
+        .iter()
+        .filter(|metric| {
+            labels.iter().all(|(name, value)| {
+                metric
+                    .get_label()
+                    .iter()
+                    .any(|label| label.get_name() == *name && label.get_value() == *value)
+            })
+        })
+        .map(|metric| match mf.get_field_type() {
+            MetricType::COUNTER => Some(metric.get_counter().get_value() as i64),
+            MetricType::GAUGE => Some(metric.get_gauge().get_value() as i64),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    if values.is_empty() {
+        None
+    } else {
+        Some(values.iter().sum())
     }
 }
 
@@ -159,8 +235,8 @@ pub fn gather_metrics(metrics_map: &HashMap<String, JsonMetric>) -> Option<serde
     for mf in metric_families.iter() {
         let metric_name = mf.get_name();
         if metrics_map.contains_key(metric_name) {
-            let value = get_value(mf).unwrap_or_default();
             let metric = metrics_map.get(metric_name)?;
+            let value = get_value(mf, metric.labels).unwrap_or_default();
             let value = metric.get_typed_value(value);
             let _ = res.insert(metric.json_output_key.to_string(), value);
         };
@@ -207,3 +283,31 @@ pub fn gather_validator_metrics() -> Result<ValidatorProcessMetrics, String> {
         common: process,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lighthouse_metrics::try_create_int_counter_vec;
+
+    #[test]
+    fn get_value_sums_metrics_with_matching_labels() {
+        let name = "monitoring_api_test_messages_total";
+        let counter = try_create_int_counter_vec(name, "Test counter", &["validator", "status"]);
+        lighthouse_metrics::inc_counter_vec_by(&counter, &["1", "success"], 2);
+        lighthouse_metrics::inc_counter_vec_by(&counter, &["2", "success"], 3);
+        lighthouse_metrics::inc_counter_vec_by(&counter, &["2", "failure"], 5);
+
+        let mf = lighthouse_metrics::gather()
+            .into_iter()
+            .find(|mf| mf.get_name() == name)
+            .unwrap();
+        assert_eq!(get_value(&mf, &[]), Some(10));
+        assert_eq!(get_value(&mf, &[("status", "success")]), Some(5));
+        assert_eq!(
+            get_value(&mf, &[("validator", "2"), ("status", "failure")]),
+            Some(5)
+        );
+        assert_eq!(get_value(&mf, &[("status", "unknown")]), None);
+        assert_eq!(get_value(&mf, &[("other", "success")]), None);
+    }
+}
@@ -0,0 +1,111 @@
+//! Cooperative cancellation of blocking tasks.
+//!
+//! A blocking task can't be interrupted once it has started, so a task spawned on behalf of a
+//! request (e.g., an HTTP API request) keeps running after the request has been dropped. Instead,
+//! the task is run with `CancellationToken::run` and long-running loops call `is_cancelled` to
+//! stop early once the result is no longer wanted.
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+thread_local! {
+    /// The token of the task currently running on this thread, if any.
+    static CURRENT: RefCell<Option<CancellationToken>> = const { RefCell::new(None) };
+}
+
+/// A flag which is set when the result of a task is no longer required.
+#[derive(Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// Returns a guard which cancels `self` when it is dropped.
+    ///
+    /// The guard is intended to be held by the future awaiting the task, so that the task is
+    /// cancelled if that future is dropped.
+    pub fn drop_guard(&self) -> DropGuard {
+        DropGuard(self.clone())
+    }
+
+    /// Run `func` on the current thread, during which `is_cancelled` reflects the state of `self`.
+    pub fn run<F: FnOnce() -> T, T>(&self, func: F) -> T {
+        let previous = CURRENT.with(|current| current.replace(Some(self.clone())));
+        // Restore the previous token even if `func` panics.
+        let _restore = RestoreOnDrop(previous);
+        func()
+    }
+}
+
+/// Cancels a `CancellationToken` when dropped.
+pub struct DropGuard(CancellationToken);
+
+impl Drop for DropGuard {
+    fn drop(&mut self) {
+        self.0.cancel();
+    }
+}
+
+struct RestoreOnDrop(Option<CancellationToken>);
+
+impl Drop for RestoreOnDrop {
+    fn drop(&mut self) {
+        let previous = self.0.take();
+        CURRENT.with(|current| *current.borrow_mut() = previous);
+    }
+}
+
+/// Returns `true` if the task running on the current thread has been cancelled.
+///
+/// Always returns `false` outside of `CancellationToken::run`.
+pub fn is_cancelled() -> bool {
+    CURRENT.with(|current| {
+        current
+            .borrow()
+            .as_ref()
+            .map_or(false, CancellationToken::is_cancelled)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn cancellation() {
+        let token = CancellationToken::new();
+        assert!(!is_cancelled());
+
+        token.run(|| {
+            assert!(!is_cancelled());
+            drop(token.drop_guard());
+            assert!(is_cancelled());
+        });
+
+        // The token only applies whilst running.
+        assert!(token.is_cancelled());
+        assert!(!is_cancelled());
+    }
+
+    #[test]
+    fn nested() {
+        let outer = CancellationToken::new();
+        let inner = CancellationToken::new();
+        outer.cancel();
+
+        outer.run(|| {
+            inner.run(|| assert!(!is_cancelled()));
+            assert!(is_cancelled());
+        });
+    }
+}
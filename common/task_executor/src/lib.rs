@@ -1,3 +1,4 @@
+pub mod cancellation;
 mod metrics;
 pub mod test_utils;
 
@@ -275,8 +276,10 @@ impl TaskExecutor {
         let timer = metrics::start_timer_vec(&metrics::BLOCKING_TASKS_HISTOGRAM, &[name]);
         metrics::inc_gauge_vec(&metrics::BLOCKING_TASKS_COUNT, &[name]);
 
+        // Run the task within the caller's span so that its spans are nested beneath it.
+        let span = tracing::Span::current();
         let join_handle = if let Some(handle) = self.handle() {
-            handle.spawn_blocking(task)
+            handle.spawn_blocking(move || span.in_scope(task))
         } else {
             debug!(self.log, "Couldn't spawn task. Runtime shutting down");
             return None;
@@ -59,6 +59,7 @@ pub struct Builder<'a> {
     pub(crate) withdrawal_keystore: Option<(Keystore, PlainText)>,
     store_withdrawal_keystore: bool,
     deposit_info: Option<(u64, &'a ChainSpec)>,
+    withdrawal_credentials: Option<Hash256>,
 }
 
 impl<'a> Builder<'a> {
@@ -71,6 +72,7 @@ impl<'a> Builder<'a> {
             withdrawal_keystore: None,
             store_withdrawal_keystore: true,
             deposit_info: None,
+            withdrawal_credentials: None,
         }
     }
 
@@ -125,6 +127,15 @@ impl<'a> Builder<'a> {
         self
     }
 
+    /// Use the given `withdrawal_credentials` in the deposit, rather than BLS withdrawal
+    /// credentials derived from the withdrawal keystore.
+    ///
+    /// This permits deposits for validators with execution or compounding withdrawal credentials.
+    pub fn withdrawal_credentials(mut self, withdrawal_credentials: Hash256) -> Self {
+        self.withdrawal_credentials = Some(withdrawal_credentials);
+        self
+    }
+
     /// If `should_store == true`, the validator keystore will be saved in the `ValidatorDir` (and
     /// the password to it stored in the `password_dir`). If `should_store == false`, the
     /// withdrawal keystore will be dropped after `Self::build`.
@@ -184,10 +195,12 @@ impl<'a> Builder<'a> {
 
             // If a deposit amount was specified, create a deposit.
             if let Some((amount, spec)) = self.deposit_info {
-                let withdrawal_credentials = Hash256::from_slice(&get_withdrawal_credentials(
-                    &withdrawal_keypair.pk,
-                    spec.bls_withdrawal_prefix_byte,
-                ));
+                let withdrawal_credentials = self.withdrawal_credentials.unwrap_or_else(|| {
+                    Hash256::from_slice(&get_withdrawal_credentials(
+                        &withdrawal_keypair.pk,
+                        spec.bls_withdrawal_prefix_byte,
+                    ))
+                });
 
                 let mut deposit_data = DepositData {
                     pubkey: voting_keypair.pk.clone().into(),
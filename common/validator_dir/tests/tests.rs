@@ -4,7 +4,9 @@ use eth2_keystore::{Keystore, KeystoreBuilder, PlainText};
 use std::fs::{self, File};
 use std::path::Path;
 use tempfile::{tempdir, TempDir};
-use types::{test_utils::generate_deterministic_keypair, EthSpec, Keypair, MainnetEthSpec};
+use types::{
+    test_utils::generate_deterministic_keypair, EthSpec, Hash256, Keypair, MainnetEthSpec,
+};
 use validator_dir::{
     Builder, BuilderError, ValidatorDir, ETH1_DEPOSIT_DATA_FILE, ETH1_DEPOSIT_TX_HASH_FILE,
     VOTING_KEYSTORE_FILE, WITHDRAWAL_KEYSTORE_FILE,
@@ -18,6 +20,7 @@ struct BuildConfig {
     random_voting_keystore: bool,
     random_withdrawal_keystore: bool,
     deposit_amount: Option<u64>,
+    withdrawal_credentials: Option<Hash256>,
     store_withdrawal_keystore: bool,
 }
 
@@ -27,6 +30,7 @@ impl Default for BuildConfig {
             random_voting_keystore: true,
             random_withdrawal_keystore: true,
             deposit_amount: None,
+            withdrawal_credentials: None,
             store_withdrawal_keystore: true,
         }
     }
@@ -104,6 +108,12 @@ impl Harness {
             builder
         };
 
+        let builder = if let Some(withdrawal_credentials) = config.withdrawal_credentials {
+            builder.withdrawal_credentials(withdrawal_credentials)
+        } else {
+            builder
+        };
+
         let mut validator = builder.build().unwrap();
 
         /*
@@ -146,6 +156,13 @@ impl Harness {
             // Ensure the amount is consistent.
             assert_eq!(data.deposit_data.amount, amount);
 
+            if let Some(withdrawal_credentials) = config.withdrawal_credentials {
+                assert_eq!(
+                    data.deposit_data.withdrawal_credentials,
+                    withdrawal_credentials
+                );
+            }
+
             // Check that the eth1 deposit data file is a valid format.
             let hex =
                 String::from_utf8(fs::read(validator.dir().join(ETH1_DEPOSIT_DATA_FILE)).unwrap())
@@ -348,6 +365,24 @@ fn eth1_data() {
     harness.create_and_test(&config);
 }
 
+#[test]
+fn eth1_data_compounding_withdrawal_credentials() {
+    let harness = Harness::new();
+
+    let spec = MainnetEthSpec::default_spec();
+    let mut withdrawal_credentials = [0x42; 32];
+    withdrawal_credentials[0] = spec.compounding_withdrawal_prefix_byte;
+    withdrawal_credentials[1..12].fill(0);
+
+    let config = BuildConfig {
+        deposit_amount: Some(spec.max_effective_balance_electra),
+        withdrawal_credentials: Some(Hash256::from(withdrawal_credentials)),
+        ..BuildConfig::default()
+    };
+
+    harness.create_and_test(&config);
+}
+
 #[test]
 fn store_withdrawal_keystore_without_eth1_data() {
     let harness = Harness::new();
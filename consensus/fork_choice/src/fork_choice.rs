@@ -1,7 +1,7 @@
 use crate::{ForkChoiceStore, InvalidationOperation};
 use proto_array::{
-    Block as ProtoBlock, DisallowedReOrgOffsets, ExecutionStatus, ProposerHeadError,
-    ProposerHeadInfo, ProtoArrayForkChoice, ReOrgThreshold,
+    Block as ProtoBlock, DisallowedReOrgOffsets, ExecutionStatus, JustifiedBalances,
+    ProposerHeadError, ProposerHeadInfo, ProtoArrayForkChoice, ReOrgThreshold,
 };
 use slog::{crit, debug, warn, Logger};
 use ssz_derive::{Decode, Encode};
@@ -1381,6 +1381,32 @@ where
         self.fc_store.proposer_boost_root()
     }
 
+    /// Returns a snapshot of every input to `Self::get_head`, which can be replayed offline.
+    ///
+    /// Queued attestations are not included since they do not influence the head until they are
+    /// dequeued in a later slot.
+    pub fn snapshot(&self) -> ForkChoiceSnapshot {
+        ForkChoiceSnapshot {
+            current_slot: self.fc_store.get_current_slot(),
+            head_root: self.forkchoice_update_parameters.head_root,
+            justified_checkpoint: *self.fc_store.justified_checkpoint(),
+            finalized_checkpoint: *self.fc_store.finalized_checkpoint(),
+            justified_balances: self
+                .fc_store
+                .justified_balances()
+                .effective_balances
+                .clone(),
+            proposer_boost_root: self.fc_store.proposer_boost_root(),
+            equivocating_indices: self
+                .fc_store
+                .equivocating_indices()
+                .iter()
+                .copied()
+                .collect(),
+            proto_array_bytes: self.proto_array.as_bytes(),
+        }
+    }
+
     /// Prunes the underlying fork choice DAG.
     pub fn prune(&mut self) -> Result<(), Error<T::Error>> {
         let finalized_root = self.fc_store.finalized_checkpoint().root;
@@ -1513,6 +1539,50 @@ pub struct PersistedForkChoice {
     queued_attestations: Vec<QueuedAttestation>,
 }
 
+/// A snapshot of the inputs to `ForkChoice::get_head`, encoded as SSZ.
+///
+/// This is used to replay the head computation offline, e.g. when debugging a consensus split.
+#[derive(Debug, Encode, Decode, Clone, PartialEq)]
+pub struct ForkChoiceSnapshot {
+    /// The slot of the fork choice store at the time of the snapshot.
+    pub current_slot: Slot,
+    /// The head at the time of the snapshot.
+    pub head_root: Hash256,
+    pub justified_checkpoint: Checkpoint,
+    pub finalized_checkpoint: Checkpoint,
+    /// The effective balances of the justified state.
+    pub justified_balances: Vec<u64>,
+    pub proposer_boost_root: Hash256,
+    pub equivocating_indices: Vec<u64>,
+    /// The SSZ encoded `ProtoArrayForkChoice`, which includes the latest vote of every validator.
+    pub proto_array_bytes: Vec<u8>,
+}
+
+impl ForkChoiceSnapshot {
+    /// Decode the snapshotted `ProtoArrayForkChoice`.
+    pub fn proto_array(&self) -> Result<ProtoArrayForkChoice, String> {
+        ProtoArrayForkChoice::from_bytes(&self.proto_array_bytes)
+    }
+
+    /// Run `get_head` on the snapshot, returning the head block root.
+    pub fn get_head<E: EthSpec>(&self, spec: &ChainSpec) -> Result<Hash256, String> {
+        let justified_balances =
+            JustifiedBalances::from_effective_balances(self.justified_balances.clone())
+                .map_err(|e| format!("Invalid justified balances: {:?}", e))?;
+        let equivocating_indices = self.equivocating_indices.iter().copied().collect();
+
+        self.proto_array()?.find_head::<E>(
+            self.justified_checkpoint,
+            self.finalized_checkpoint,
+            &justified_balances,
+            self.proposer_boost_root,
+            &equivocating_indices,
+            self.current_slot,
+            spec,
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use types::MainnetEthSpec;
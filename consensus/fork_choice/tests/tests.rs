@@ -8,8 +8,10 @@ use beacon_chain::{
     StateSkipConfig, WhenSlotSkipped,
 };
 use fork_choice::{
-    ForkChoiceStore, InvalidAttestation, InvalidBlock, PayloadVerificationStatus, QueuedAttestation,
+    ForkChoiceSnapshot, ForkChoiceStore, InvalidAttestation, InvalidBlock,
+    PayloadVerificationStatus, QueuedAttestation,
 };
+use ssz::{Decode, Encode};
 use std::fmt;
 use std::sync::Mutex;
 use std::time::Duration;
@@ -367,6 +369,24 @@ impl ForkChoiceTest {
         self
     }
 
+    /// Checks that replaying a snapshot of fork choice computes the same head.
+    fn check_snapshot_head(self) -> Self {
+        let snapshot = self
+            .harness
+            .chain
+            .canonical_head
+            .fork_choice_read_lock()
+            .snapshot();
+        let snapshot = ForkChoiceSnapshot::from_ssz_bytes(&snapshot.as_ssz_bytes()).unwrap();
+
+        assert_eq!(
+            snapshot.get_head::<E>(&self.harness.chain.spec).unwrap(),
+            snapshot.head_root
+        );
+        assert_eq!(snapshot.head_root, self.harness.head_block_root());
+        self
+    }
+
     /// Compares the justified balances in the `ForkChoiceStore` verses a direct lookup from the
     /// database.
     fn check_justified_balances(&self) {
@@ -682,6 +702,15 @@ async fn justified_balances() {
         .check_justified_balances()
 }
 
+/// Check that the head can be recomputed from a fork choice snapshot.
+#[tokio::test]
+async fn snapshot_get_head() {
+    ForkChoiceTest::new()
+        .apply_blocks(E::slots_per_epoch() as usize * 2)
+        .await
+        .check_snapshot_head();
+}
+
 macro_rules! assert_invalid_block {
     ($err: tt, $($error: pat_param) |+ $( if $guard: expr )?) => {
         assert!(
@@ -229,9 +229,10 @@ impl ProtoArray {
                     // Invalid nodes (or their ancestors) should not receive a proposer boost.
                     && !execution_status_is_invalid
                 {
-                    proposer_score = calculate_committee_fraction::<E>(
+                    proposer_score = calculate_proposer_score::<E>(
                         new_justified_balances,
                         proposer_score_boost,
+                        spec,
                     )
                     .ok_or(Error::ProposerBoostOverflow(node_index))?;
                     node_delta = node_delta
@@ -1058,6 +1059,21 @@ pub fn calculate_committee_fraction<E: EthSpec>(
         .checked_div(100)
 }
 
+/// Calculate the proposer boost score, using `spec.committee_weight_override` in place of the
+/// committee weight derived from `justified_balances` if it is set.
+pub fn calculate_proposer_score<E: EthSpec>(
+    justified_balances: &JustifiedBalances,
+    proposer_score_boost: u64,
+    spec: &ChainSpec,
+) -> Option<u64> {
+    match spec.committee_weight_override {
+        Some(committee_weight) => committee_weight
+            .checked_mul(proposer_score_boost)?
+            .checked_div(100),
+        None => calculate_committee_fraction::<E>(justified_balances, proposer_score_boost),
+    }
+}
+
 /// Reverse iterator over one path through a `ProtoArray`.
 pub struct Iter<'a> {
     next_node_index: Option<usize>,
@@ -1,8 +1,8 @@
 use crate::{
     error::Error,
     proto_array::{
-        calculate_committee_fraction, InvalidationOperation, Iter, ProposerBoost, ProtoArray,
-        ProtoNode,
+        calculate_committee_fraction, calculate_proposer_score, InvalidationOperation, Iter,
+        ProposerBoost, ProtoArray, ProtoNode,
     },
     ssz_container::SszContainer,
     JustifiedBalances,
@@ -710,9 +710,10 @@ impl ProtoArrayForkChoice {
                             // Compute the score based upon the current balances. We can't rely on
                             // the `previous_proposr_boost.score` since it is set to zero with an
                             // invalid node.
-                            let proposer_score = calculate_committee_fraction::<E>(
+                            let proposer_score = calculate_proposer_score::<E>(
                                 &self.balances,
                                 proposer_score_boost,
+                                spec,
                             )
                             .ok_or("Failed to compute proposer boost")?;
                             // Store the score we've applied here so it can be removed in
@@ -1000,6 +1001,25 @@ mod test_compute_deltas {
         Hash256::from_low_u64_be(i as u64 + 1)
     }
 
+    #[test]
+    fn proposer_score_committee_weight_override() {
+        let balances =
+            JustifiedBalances::from_effective_balances(vec![32_000_000_000; 64]).unwrap();
+        let mut spec = MainnetEthSpec::default_spec();
+
+        // 64 validators at 32 ETH over 32 slots gives a committee weight of 64 ETH.
+        assert_eq!(
+            calculate_proposer_score::<MainnetEthSpec>(&balances, 40, &spec),
+            Some(25_600_000_000)
+        );
+
+        spec.committee_weight_override = Some(1_000_000_000);
+        assert_eq!(
+            calculate_proposer_score::<MainnetEthSpec>(&balances, 40, &spec),
+            Some(400_000_000)
+        );
+    }
+
     #[test]
     fn finalized_descendant() {
         let genesis_slot = Slot::new(0);
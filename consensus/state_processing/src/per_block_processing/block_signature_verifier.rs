@@ -170,7 +170,6 @@ where
         self.include_exits(block)?;
         self.include_sync_aggregate(block)?;
         self.include_bls_to_execution_changes(block)?;
-        self.include_consolidations(block)?;
 
         Ok(())
     }
@@ -359,27 +358,6 @@ where
         Ok(())
     }
 
-    /// Includes all signatures in `self.block.body.consolidations` for verification.
-    pub fn include_consolidations<Payload: AbstractExecPayload<E>>(
-        &mut self,
-        block: &'a SignedBeaconBlock<E, Payload>,
-    ) -> Result<()> {
-        if let Ok(consolidations) = block.message().body().consolidations() {
-            self.sets.sets.reserve(consolidations.len());
-            for consolidation in consolidations {
-                let set = consolidation_signature_set(
-                    self.state,
-                    self.get_pubkey.clone(),
-                    consolidation,
-                    self.spec,
-                )?;
-
-                self.sets.push(set);
-            }
-        }
-        Ok(())
-    }
-
     /// Verify all the signatures that have been included in `self`, returning `true` if and only if
     /// all the signatures are valid.
     ///
@@ -13,7 +13,7 @@ use std::time::Duration;
 use tree_hash::TreeHash;
 
 /// Each of the BLS signature domains.
-#[derive(Debug, PartialEq, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
 pub enum Domain {
     BlsToExecutionChange,
     BeaconProposer,
@@ -26,7 +26,6 @@ pub enum Domain {
     SyncCommittee,
     ContributionAndProof,
     SyncCommitteeSelectionProof,
-    Consolidation,
     ApplicationMask(ApplicationDomain),
 }
 
@@ -111,7 +110,6 @@ pub struct ChainSpec {
     pub(crate) domain_voluntary_exit: u32,
     pub(crate) domain_selection_proof: u32,
     pub(crate) domain_aggregate_and_proof: u32,
-    pub(crate) domain_consolidation: u32,
 
     /*
      * Fork choice
@@ -120,6 +118,9 @@ pub struct ChainSpec {
     pub proposer_score_boost: Option<u64>,
     pub reorg_head_weight_threshold: Option<u64>,
     pub reorg_parent_weight_threshold: Option<u64>,
+    /// Replaces the committee weight (`total_active_balance // SLOTS_PER_EPOCH`) used to compute
+    /// the proposer boost score. Not part of the specification, only set via `--unsafe-tuning`.
+    pub committee_weight_override: Option<u64>,
 
     /*
      * Eth1
@@ -426,6 +427,13 @@ impl ChainSpec {
         })
     }
 
+    /// Returns true if `EIP7594_FORK_EPOCH` is set and is not set to `FAR_FUTURE_EPOCH`.
+    pub fn is_peer_das_scheduled(&self) -> bool {
+        self.eip7594_fork_epoch.map_or(false, |eip7594_fork_epoch| {
+            eip7594_fork_epoch != self.far_future_epoch
+        })
+    }
+
     /// Returns a full `Fork` struct for a given epoch.
     pub fn fork_at_epoch(&self, epoch: Epoch) -> Fork {
         let current_fork_name = self.fork_name_at_epoch(epoch);
@@ -471,7 +479,6 @@ impl ChainSpec {
             Domain::SyncCommitteeSelectionProof => self.domain_sync_committee_selection_proof,
             Domain::ApplicationMask(application_domain) => application_domain.get_domain_constant(),
             Domain::BlsToExecutionChange => self.domain_bls_to_execution_change,
-            Domain::Consolidation => self.domain_consolidation,
         }
     }
 
@@ -696,7 +703,6 @@ impl ChainSpec {
             domain_voluntary_exit: 4,
             domain_selection_proof: 5,
             domain_aggregate_and_proof: 6,
-            domain_consolidation: 0x0B,
 
             /*
              * Fork choice
@@ -705,6 +711,7 @@ impl ChainSpec {
             proposer_score_boost: Some(40),
             reorg_head_weight_threshold: Some(20),
             reorg_parent_weight_threshold: Some(160),
+            committee_weight_override: None,
 
             /*
              * Eth1
@@ -1016,7 +1023,6 @@ impl ChainSpec {
             domain_voluntary_exit: 4,
             domain_selection_proof: 5,
             domain_aggregate_and_proof: 6,
-            domain_consolidation: 0x0B,
 
             /*
              * Fork choice
@@ -1025,6 +1031,7 @@ impl ChainSpec {
             proposer_score_boost: Some(40),
             reorg_head_weight_threshold: Some(20),
             reorg_parent_weight_threshold: Some(160),
+            committee_weight_override: None,
 
             /*
              * Eth1
@@ -1729,6 +1736,85 @@ impl Config {
             .map_err(|e| format!("Error parsing spec at {}: {:?}", filename.display(), e))
     }
 
+    /// Returns a copy of `self` with the values in `overrides` replacing its own.
+    ///
+    /// Each key in `overrides` must be the name of a config value, such as `SECONDS_PER_SLOT`.
+    /// Preset values and `PRESET_BASE` can't be overridden since they're fixed by the `EthSpec`.
+    pub fn with_overrides(&self, overrides: serde_yaml::Mapping) -> Result<Self, String> {
+        let mut config = serde_yaml::to_value(self)
+            .map_err(|e| format!("Unable to serialize config: {:?}", e))?;
+        let values = config
+            .as_mapping_mut()
+            .ok_or("Config is not a YAML mapping")?;
+
+        for (key, value) in overrides {
+            let name = key
+                .as_str()
+                .ok_or_else(|| format!("Invalid config key: {:?}", key))?;
+            if name == "PRESET_BASE" {
+                return Err("PRESET_BASE cannot be overridden".to_string());
+            }
+            if name != "CONFIG_NAME" && !values.contains_key(name) {
+                return Err(format!(
+                    "{} is not a config value, preset values cannot be overridden",
+                    name
+                ));
+            }
+            values.insert(key, value);
+        }
+
+        let config: Config = serde_yaml::from_value(config)
+            .map_err(|e| format!("Invalid config override: {:?}", e))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Check that the config values are consistent with one another.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.seconds_per_slot == 0 {
+            return Err("SECONDS_PER_SLOT must be greater than zero".to_string());
+        }
+
+        let fork_epoch = |epoch: &Option<MaybeQuoted<Epoch>>| epoch.as_ref().map(|e| e.value);
+        let forks = [
+            ("ALTAIR", fork_epoch(&self.altair_fork_epoch)),
+            ("BELLATRIX", fork_epoch(&self.bellatrix_fork_epoch)),
+            ("CAPELLA", fork_epoch(&self.capella_fork_epoch)),
+            ("DENEB", fork_epoch(&self.deneb_fork_epoch)),
+            ("ELECTRA", fork_epoch(&self.electra_fork_epoch)),
+        ];
+        for pair in forks.windows(2) {
+            let (previous_fork, previous_epoch) = pair[0];
+            let (fork, epoch) = pair[1];
+            match (previous_epoch, epoch) {
+                (None, Some(_)) => {
+                    return Err(format!(
+                        "{}_FORK_EPOCH is scheduled but {}_FORK_EPOCH is not",
+                        fork, previous_fork
+                    ))
+                }
+                (Some(previous_epoch), Some(epoch)) if epoch < previous_epoch => {
+                    return Err(format!(
+                        "{}_FORK_EPOCH ({}) is before {}_FORK_EPOCH ({})",
+                        fork, epoch, previous_fork, previous_epoch
+                    ))
+                }
+                _ => (),
+            }
+        }
+
+        if let (Some(eip7594_epoch), deneb_epoch) = (
+            fork_epoch(&self.eip7594_fork_epoch),
+            fork_epoch(&self.deneb_fork_epoch),
+        ) {
+            if deneb_epoch.map_or(true, |deneb_epoch| eip7594_epoch < deneb_epoch) {
+                return Err("EIP7594_FORK_EPOCH must not be before DENEB_FORK_EPOCH".to_string());
+            }
+        }
+
+        Ok(())
+    }
+
     pub fn apply_to_chain_spec<E: EthSpec>(&self, chain_spec: &ChainSpec) -> Option<ChainSpec> {
         // Pattern match here to avoid missing any fields.
         let &Config {
@@ -1938,7 +2024,6 @@ mod tests {
             &spec,
         );
         test_domain(Domain::SyncCommittee, spec.domain_sync_committee, &spec);
-        test_domain(Domain::Consolidation, spec.domain_consolidation, &spec);
 
         // The builder domain index is zero
         let builder_domain_pre_mask = [0; 4];
@@ -2080,6 +2165,48 @@ mod yaml_tests {
         assert_eq!(new_spec, ChainSpec::minimal());
     }
 
+    #[test]
+    fn overrides() {
+        let config = Config::from_chain_spec::<MainnetEthSpec>(&ChainSpec::mainnet());
+        let overrides = |yaml: &str| serde_yaml::from_str::<serde_yaml::Mapping>(yaml).unwrap();
+
+        let overridden = config
+            .with_overrides(overrides(
+                "SECONDS_PER_SLOT: 6\nELECTRA_FORK_EPOCH: 400000\nCONFIG_NAME: devnet",
+            ))
+            .unwrap();
+        let spec = overridden
+            .apply_to_chain_spec::<MainnetEthSpec>(&ChainSpec::mainnet())
+            .unwrap();
+        assert_eq!(spec.seconds_per_slot, 6);
+        assert_eq!(spec.electra_fork_epoch, Some(Epoch::new(400000)));
+        assert_eq!(spec.config_name.as_deref(), Some("devnet"));
+        assert_eq!(
+            spec.altair_fork_epoch,
+            ChainSpec::mainnet().altair_fork_epoch
+        );
+
+        // Preset values and unknown keys are rejected.
+        assert!(config
+            .with_overrides(overrides("PRESET_BASE: minimal"))
+            .is_err());
+        assert!(config
+            .with_overrides(overrides("SLOTS_PER_EPOCH: 8"))
+            .is_err());
+        assert!(config.with_overrides(overrides("NOT_A_VALUE: 1")).is_err());
+
+        // Forks must be scheduled in order.
+        assert!(config
+            .with_overrides(overrides("DENEB_FORK_EPOCH: 1"))
+            .is_err());
+        assert!(config
+            .with_overrides(overrides("ELECTRA_FORK_EPOCH: 18446744073709551615"))
+            .is_ok());
+        assert!(config
+            .with_overrides(overrides("SECONDS_PER_SLOT: 0"))
+            .is_err());
+    }
+
     #[test]
     fn test_defaults() {
         // Spec yaml string. Fields that serialize/deserialize with a default value are commented out.
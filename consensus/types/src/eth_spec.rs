@@ -154,11 +154,11 @@ pub trait EthSpec:
     type PendingBalanceDepositsLimit: Unsigned + Clone + Sync + Send + Debug + PartialEq;
     type PendingPartialWithdrawalsLimit: Unsigned + Clone + Sync + Send + Debug + PartialEq;
     type PendingConsolidationsLimit: Unsigned + Clone + Sync + Send + Debug + PartialEq;
-    type MaxConsolidations: Unsigned + Clone + Sync + Send + Debug + PartialEq;
     type MaxDepositRequestsPerPayload: Unsigned + Clone + Sync + Send + Debug + PartialEq;
     type MaxAttesterSlashingsElectra: Unsigned + Clone + Sync + Send + Debug + PartialEq;
     type MaxAttestationsElectra: Unsigned + Clone + Sync + Send + Debug + PartialEq;
     type MaxWithdrawalRequestsPerPayload: Unsigned + Clone + Sync + Send + Debug + PartialEq;
+    type MaxConsolidationRequestsPerPayload: Unsigned + Clone + Sync + Send + Debug + PartialEq;
 
     fn default_spec() -> ChainSpec;
 
@@ -346,11 +346,6 @@ pub trait EthSpec:
         Self::PendingConsolidationsLimit::to_usize()
     }
 
-    /// Returns the `MAX_CONSOLIDATIONS` constant for this specification.
-    fn max_consolidations() -> usize {
-        Self::MaxConsolidations::to_usize()
-    }
-
     /// Returns the `MAX_DEPOSIT_REQUESTS_PER_PAYLOAD` constant for this specification.
     fn max_deposit_requests_per_payload() -> usize {
         Self::MaxDepositRequestsPerPayload::to_usize()
@@ -371,6 +366,11 @@ pub trait EthSpec:
         Self::MaxWithdrawalRequestsPerPayload::to_usize()
     }
 
+    /// Returns the `MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD` constant for this specification.
+    fn max_consolidation_requests_per_payload() -> usize {
+        Self::MaxConsolidationRequestsPerPayload::to_usize()
+    }
+
     fn kzg_commitments_inclusion_proof_depth() -> usize {
         Self::KzgCommitmentsInclusionProofDepth::to_usize()
     }
@@ -433,11 +433,11 @@ impl EthSpec for MainnetEthSpec {
     type PendingBalanceDepositsLimit = U134217728;
     type PendingPartialWithdrawalsLimit = U134217728;
     type PendingConsolidationsLimit = U262144;
-    type MaxConsolidations = U1;
     type MaxDepositRequestsPerPayload = U8192;
     type MaxAttesterSlashingsElectra = U1;
     type MaxAttestationsElectra = U8;
     type MaxWithdrawalRequestsPerPayload = U16;
+    type MaxConsolidationRequestsPerPayload = U1;
 
     fn default_spec() -> ChainSpec {
         ChainSpec::mainnet()
@@ -501,9 +501,9 @@ impl EthSpec for MinimalEthSpec {
         MaxBlobsPerBlock,
         BytesPerFieldElement,
         PendingBalanceDepositsLimit,
-        MaxConsolidations,
         MaxAttesterSlashingsElectra,
-        MaxAttestationsElectra
+        MaxAttestationsElectra,
+        MaxConsolidationRequestsPerPayload
     });
 
     fn default_spec() -> ChainSpec {
@@ -560,11 +560,11 @@ impl EthSpec for GnosisEthSpec {
     type PendingBalanceDepositsLimit = U134217728;
     type PendingPartialWithdrawalsLimit = U134217728;
     type PendingConsolidationsLimit = U262144;
-    type MaxConsolidations = U1;
     type MaxDepositRequestsPerPayload = U8192;
     type MaxAttesterSlashingsElectra = U1;
     type MaxAttestationsElectra = U8;
     type MaxWithdrawalRequestsPerPayload = U16;
+    type MaxConsolidationRequestsPerPayload = U1;
     type FieldElementsPerCell = U64;
     type FieldElementsPerExtBlob = U8192;
     type BytesPerCell = U2048;
@@ -246,8 +246,6 @@ pub struct ElectraPreset {
     #[serde(with = "serde_utils::quoted_u64")]
     pub pending_consolidations_limit: u64,
     #[serde(with = "serde_utils::quoted_u64")]
-    pub max_consolidations: u64,
-    #[serde(with = "serde_utils::quoted_u64")]
     pub max_deposit_requests_per_payload: u64,
     #[serde(with = "serde_utils::quoted_u64")]
     pub max_attester_slashings_electra: u64,
@@ -255,6 +253,8 @@ pub struct ElectraPreset {
     pub max_attestations_electra: u64,
     #[serde(with = "serde_utils::quoted_u64")]
     pub max_withdrawal_requests_per_payload: u64,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub max_consolidation_requests_per_payload: u64,
 }
 
 impl ElectraPreset {
@@ -269,11 +269,12 @@ impl ElectraPreset {
             pending_balance_deposits_limit: E::pending_balance_deposits_limit() as u64,
             pending_partial_withdrawals_limit: E::pending_partial_withdrawals_limit() as u64,
             pending_consolidations_limit: E::pending_consolidations_limit() as u64,
-            max_consolidations: E::max_consolidations() as u64,
             max_deposit_requests_per_payload: E::max_deposit_requests_per_payload() as u64,
             max_attester_slashings_electra: E::max_attester_slashings_electra() as u64,
             max_attestations_electra: E::max_attestations_electra() as u64,
             max_withdrawal_requests_per_payload: E::max_withdrawal_requests_per_payload() as u64,
+            max_consolidation_requests_per_payload: E::max_consolidation_requests_per_payload()
+                as u64,
         }
     }
 }
@@ -2,6 +2,7 @@ pub use clap::{Arg, ArgAction, Args, Command, FromArgMatches, Parser};
 use clap_utils::get_color_style;
 use clap_utils::FLAG_HEADER;
 use serde::{Deserialize, Serialize};
+use slasher::DatabaseBackend;
 use std::path::PathBuf;
 
 use crate::InspectTarget;
@@ -78,7 +79,13 @@ pub enum DatabaseManagerSubcommand {
     PrunePayloads(PrunePayloads),
     PruneBlobs(PruneBlobs),
     PruneStates(PruneStates),
+    PruneHistoryBefore(PruneHistoryBefore),
+    SetOldestBlockSlot(SetOldestBlockSlot),
     Compact(Compact),
+    Export(Export),
+    Import(Import),
+    ExportEra(ExportEra),
+    ResizeSlasher(ResizeSlasher),
 }
 
 #[derive(Parser, Clone, Deserialize, Serialize, Debug)]
@@ -99,10 +106,21 @@ pub struct Inspect {
     #[clap(
         long,
         value_name = "TAG",
+        required_unless_present = "sizes",
         help = "3-byte column ID (see `DBColumn`)",
         display_order = 0
     )]
-    pub column: String,
+    pub column: Option<String>,
+
+    #[clap(
+        long,
+        conflicts_with_all = ["column", "output", "skip", "limit", "output_dir"],
+        help = "Show the approximate size of every column rather than inspecting a single \
+                column. This reads the entire database so may take a long time",
+        display_order = 0,
+        help_heading = FLAG_HEADER
+    )]
+    pub sizes: bool,
 
     #[clap(
         long,
@@ -190,6 +208,51 @@ pub struct PruneStates {
     pub confirm: bool,
 }
 
+#[derive(Parser, Clone, Deserialize, Serialize, Debug)]
+#[clap(
+    about = "Delete all blocks, payloads and blobs older than the block at a slot. Historic \
+             states must be pruned first with prune-states. Blocks and blobs from the weak \
+             subjectivity period can't be deleted, because backfill sync would download them \
+             again.",
+    alias = "prune_history_before"
+)]
+pub struct PruneHistoryBefore {
+    #[clap(
+        long,
+        value_name = "SLOT",
+        help = "The block at this slot (or the most recent block prior to it) becomes the \
+                oldest block in the database. The genesis block is always kept.",
+        display_order = 0
+    )]
+    pub slot: u64,
+
+    #[clap(
+        long,
+        help = "Commit to deleting blocks irreversably. Without this flag the command will \
+                just check that the database is capable of being pruned.",
+        help_heading = FLAG_HEADER,
+    )]
+    pub confirm: bool,
+}
+
+#[derive(Parser, Clone, Deserialize, Serialize, Debug)]
+#[clap(
+    about = "Set the oldest block slot of the database's anchor, for recovery of a database \
+             whose anchor doesn't match the blocks it stores. No blocks are deleted.",
+    alias = "set_oldest_block_slot"
+)]
+pub struct SetOldestBlockSlot {
+    #[clap(
+        long,
+        value_name = "SLOT",
+        help = "The new oldest block slot. Blocks prior to this slot are ignored. If it is \
+                older than the current oldest block, every block back to this slot must \
+                already be stored in the database.",
+        display_order = 0
+    )]
+    pub slot: u64,
+}
+
 #[derive(Parser, Clone, Deserialize, Serialize, Debug)]
 #[clap(about = "Compact database manually.")]
 pub struct Compact {
@@ -227,3 +290,126 @@ pub struct Compact {
     )]
     pub output_dir: Option<PathBuf>,
 }
+
+#[derive(Parser, Clone, Deserialize, Serialize, Debug)]
+#[clap(about = "Export a snapshot of the database which can be imported by another node.")]
+pub struct Export {
+    #[clap(
+        long,
+        value_name = "FILE",
+        help = "Path of the snapshot archive to create, e.g. snapshot.tar.zst. An interrupted \
+                export is resumed when run again with the same path.",
+        display_order = 0
+    )]
+    pub output: PathBuf,
+
+    #[clap(
+        long,
+        help = "Include historic states from the freezer database. Without this flag the \
+                imported database will only be able to serve states from after the split.",
+        display_order = 0,
+        help_heading = FLAG_HEADER
+    )]
+    pub historic_states: bool,
+}
+
+#[derive(Parser, Clone, Deserialize, Serialize, Debug)]
+#[clap(about = "Import a snapshot created by `lighthouse db export` into an empty database.")]
+pub struct Import {
+    #[clap(
+        long,
+        value_name = "FILE",
+        help = "Path of the snapshot archive to import. An interrupted import is resumed when \
+                run again with the same snapshot.",
+        display_order = 0
+    )]
+    pub input: PathBuf,
+}
+
+#[derive(Parser, Clone, Deserialize, Serialize, Debug)]
+#[clap(about = "Export finalized history as era files.")]
+pub struct ExportEra {
+    #[clap(
+        long,
+        value_name = "ERA",
+        help = "The first era to export. Era N contains the blocks prior to slot \
+                N * SLOTS_PER_HISTORICAL_ROOT and the state at that slot.",
+        display_order = 0
+    )]
+    pub start_era: u64,
+
+    #[clap(
+        long,
+        value_name = "ERA",
+        help = "The last era to export. Defaults to the most recent finalized era.",
+        display_order = 0
+    )]
+    pub end_era: Option<u64>,
+
+    #[clap(
+        long,
+        value_name = "DIR",
+        help = "Directory in which to write the era files. Defaults to the current directory",
+        display_order = 0
+    )]
+    pub output_dir: Option<PathBuf>,
+}
+
+#[derive(Parser, Clone, Deserialize, Serialize, Debug)]
+#[clap(
+    about = "Re-chunk an existing slasher database to use a new chunk size or history length. \
+             The beacon node must not be running.",
+    alias = "resize_slasher"
+)]
+pub struct ResizeSlasher {
+    #[clap(
+        long,
+        value_name = "PATH",
+        help = "Path of the slasher database. Defaults to the slasher_db directory inside the \
+                beacon node's datadir.",
+        display_order = 0
+    )]
+    pub slasher_dir: Option<PathBuf>,
+
+    #[clap(
+        long,
+        value_name = "EPOCHS",
+        help = "New number of epochs of history for the slasher to keep. Data for older \
+                epochs is deleted. [default: 4096]",
+        display_order = 0
+    )]
+    pub slasher_history_length: Option<usize>,
+
+    #[clap(
+        long,
+        value_name = "EPOCHS",
+        help = "New number of epochs per slasher chunk. [default: 16]",
+        display_order = 0
+    )]
+    pub slasher_chunk_size: Option<usize>,
+
+    #[clap(
+        long,
+        value_name = "NUM_VALIDATORS",
+        help = "New number of validators per slasher chunk. [default: 256]",
+        display_order = 0
+    )]
+    pub slasher_validator_chunk_size: Option<usize>,
+
+    #[clap(
+        long,
+        value_name = "GIGABYTES",
+        help = "Maximum size of the slasher database in gigabytes, as configured for the \
+                beacon node.",
+        display_order = 0
+    )]
+    pub slasher_max_db_size: Option<usize>,
+
+    #[clap(
+        long,
+        value_name = "DATABASE",
+        help = "Database backend of the slasher database, as configured for the beacon node.",
+        display_order = 0
+    )]
+    pub slasher_backend: Option<DatabaseBackend>,
+}
@@ -0,0 +1,234 @@
+//! Export of finalized history as era files.
+//!
+//! See `store::era` for a description of the format.
+use crate::cli::ExportEra;
+use beacon_node::ClientConfig;
+use slog::{info, Logger};
+use ssz::Encode;
+use std::fs::{self, File};
+use std::io::{self, BufWriter};
+use std::path::{Path, PathBuf};
+use store::era::{
+    era_file_name, E2StoreWriter, COMPRESSED_BEACON_STATE, COMPRESSED_SIGNED_BEACON_BLOCK, VERSION,
+};
+use store::{HotColdDB, LevelDB};
+use tree_hash::TreeHash;
+use types::{BeaconState, ChainSpec, EthSpec, Hash256, Slot};
+
+pub struct ExportEraConfig {
+    start_era: u64,
+    end_era: Option<u64>,
+    output_dir: PathBuf,
+}
+
+pub fn parse_export_era_config(export_era_config: &ExportEra) -> Result<ExportEraConfig, String> {
+    if let Some(end_era) = export_era_config.end_era {
+        if end_era < export_era_config.start_era {
+            return Err("--end-era must not be less than --start-era".to_string());
+        }
+    }
+
+    Ok(ExportEraConfig {
+        start_era: export_era_config.start_era,
+        end_era: export_era_config.end_era,
+        output_dir: export_era_config.output_dir.clone().unwrap_or_default(),
+    })
+}
+
+/// Export every era from `start_era` to `end_era` (inclusive) to the output directory.
+///
+/// If no `end_era` is specified, the export continues up to the most recent finalized era.
+pub fn export_era<E: EthSpec>(
+    export_config: ExportEraConfig,
+    client_config: ClientConfig,
+    spec: &ChainSpec,
+    log: Logger,
+) -> Result<(), String> {
+    let db = HotColdDB::<E, LevelDB<E>, LevelDB<E>>::open(
+        &client_config.get_db_path(),
+        &client_config.get_freezer_db_path(),
+        &client_config.get_blobs_db_path(),
+        |_, _, _| Ok(()),
+        client_config.store,
+        spec.clone(),
+        log.clone(),
+    )
+    .map_err(|e| format!("Unable to open database: {e:?}"))?;
+
+    let slots_per_era = E::slots_per_historical_root() as u64;
+    let split = db.get_split_info();
+    let last_finalized_era = split.slot.as_u64() / slots_per_era;
+    let end_era = export_config.end_era.unwrap_or(last_finalized_era);
+    if end_era > last_finalized_era {
+        return Err(format!(
+            "Era {end_era} is not finalized, the most recent finalized era is {last_finalized_era}"
+        ));
+    }
+    if let Some(anchor) = db
+        .get_anchor_info()
+        .filter(|anchor| anchor.oldest_block_slot > 0)
+    {
+        let first_complete_era = anchor.oldest_block_slot.as_u64().div_ceil(slots_per_era) + 1;
+        if export_config.start_era < first_complete_era {
+            return Err(format!(
+                "Blocks prior to slot {} are not stored, the first era which can be exported is \
+                 {first_complete_era}",
+                anchor.oldest_block_slot,
+            ));
+        }
+    }
+
+    fs::create_dir_all(&export_config.output_dir).map_err(|e| {
+        format!(
+            "Unable to create {}: {e:?}",
+            export_config.output_dir.display()
+        )
+    })?;
+
+    let config_name = spec.config_name.as_deref().unwrap_or("unknown");
+    for era in export_config.start_era..=end_era {
+        let path = write_era_file(
+            &db,
+            era,
+            config_name,
+            split.slot,
+            split.state_root,
+            &export_config.output_dir,
+        )?;
+        info!(
+            log,
+            "Exported era";
+            "era" => era,
+            "end_era" => end_era,
+            "path" => path.display(),
+        );
+    }
+
+    Ok(())
+}
+
+fn write_era_file<E: EthSpec>(
+    db: &HotColdDB<E, LevelDB<E>, LevelDB<E>>,
+    era: u64,
+    config_name: &str,
+    split_slot: Slot,
+    split_state_root: Hash256,
+    output_dir: &Path,
+) -> Result<PathBuf, String> {
+    let slots_per_era = E::slots_per_historical_root() as u64;
+    let state_slot = Slot::new(era * slots_per_era);
+
+    // The era state's `block_roots` contain the canonical block root for every slot of the era.
+    let state = if state_slot == split_slot {
+        db.get_state(&split_state_root, Some(split_slot))
+    } else {
+        db.load_cold_state_by_slot(state_slot)
+    }
+    .map_err(|e| format!("Unable to load state at slot {state_slot}: {e:?}"))?
+    .ok_or_else(|| {
+        format!(
+            "State at slot {state_slot} is not stored. Era export requires historic states, which \
+             may have been pruned or not yet reconstructed"
+        )
+    })?;
+
+    let tmp_path = output_dir.join(format!("{config_name}-{era:05}.era.tmp"));
+    let file = File::create(&tmp_path)
+        .map_err(|e| format!("Unable to create {}: {e:?}", tmp_path.display()))?;
+    let mut writer = E2StoreWriter::new(BufWriter::new(file));
+    let write_err = |e: io::Error| format!("Unable to write {}: {e:?}", tmp_path.display());
+
+    writer.write_record(VERSION, &[]).map_err(write_err)?;
+
+    let mut block_offsets = vec![];
+    if era > 0 {
+        let start_slot = state_slot - slots_per_era;
+        let mut prev_block_root = None;
+        for slot in (start_slot.as_u64()..state_slot.as_u64()).map(Slot::new) {
+            let block_root = *state
+                .get_block_root(slot)
+                .map_err(|e| format!("Unable to read block root at slot {slot}: {e:?}"))?;
+
+            // Skipped slots repeat the root of the previous block. The genesis block is not
+            // included, as it is unsigned.
+            if slot == 0 || prev_block_root == Some(block_root) {
+                block_offsets.push(None);
+                continue;
+            }
+            prev_block_root = Some(block_root);
+
+            let blinded_block = db
+                .get_blinded_block(&block_root)
+                .map_err(|e| format!("Unable to load block {block_root:?}: {e:?}"))?
+                .ok_or_else(|| format!("Block {block_root:?} at slot {slot} is not stored"))?;
+            // The first slot of the era may be skipped, in which case its root is for a block from
+            // the previous era.
+            if blinded_block.slot() != slot {
+                block_offsets.push(None);
+                continue;
+            }
+            let block = db
+                .make_full_block(&block_root, blinded_block)
+                .map_err(|e| {
+                    format!(
+                        "Unable to load execution payload for block {block_root:?}: {e:?}. Era \
+                     export requires execution payloads, which may have been pruned"
+                    )
+                })?;
+
+            let offset = writer
+                .write_compressed(COMPRESSED_SIGNED_BEACON_BLOCK, &block.as_ssz_bytes())
+                .map_err(write_err)?;
+            block_offsets.push(Some(offset));
+        }
+    }
+
+    let state_offset = writer
+        .write_compressed(COMPRESSED_BEACON_STATE, &state.as_ssz_bytes())
+        .map_err(write_err)?;
+
+    if era > 0 {
+        writer
+            .write_slot_index(state_slot - slots_per_era, &block_offsets)
+            .map_err(write_err)?;
+    }
+    writer
+        .write_slot_index(state_slot, &[Some(state_offset)])
+        .map_err(write_err)?;
+
+    let file = writer
+        .into_inner()
+        .into_inner()
+        .map_err(|e| write_err(e.into_error()))?;
+    file.sync_all().map_err(write_err)?;
+
+    let short_root = short_historical_root(&state, era)?;
+    let path = output_dir.join(era_file_name(config_name, era, &short_root));
+    fs::rename(&tmp_path, &path)
+        .map_err(|e| format!("Unable to write {}: {e:?}", path.display()))?;
+    Ok(path)
+}
+
+/// The first 4 bytes of the historical root for the era, which identify the era file.
+///
+/// The genesis era uses the genesis validators root instead.
+fn short_historical_root<E: EthSpec>(state: &BeaconState<E>, era: u64) -> Result<String, String> {
+    let root = if era == 0 {
+        state.genesis_validators_root()
+    } else {
+        let index = era as usize - 1;
+        let historical_roots = state.historical_roots();
+        if let Some(root) = historical_roots.get(index) {
+            *root
+        } else {
+            // Since Capella, `historical_summaries` replace `historical_roots`, which are frozen.
+            state
+                .historical_summaries()
+                .ok()
+                .and_then(|summaries| summaries.get(index - historical_roots.len()))
+                .ok_or_else(|| format!("Historical root for era {era} is missing"))?
+                .tree_hash_root()
+        }
+    };
+    Ok(hex::encode(&root.as_bytes()[..4]))
+}
@@ -1,26 +1,34 @@
 pub mod cli;
+pub mod era;
+pub mod snapshot;
 use crate::cli::DatabaseManager;
 use crate::cli::Migrate;
 use crate::cli::PruneStates;
+use crate::cli::{PruneHistoryBefore, SetOldestBlockSlot};
 use beacon_chain::{
-    builder::Witness, eth1_chain::CachingEth1Backend, schema_change::migrate_schema,
-    slot_clock::SystemTimeSlotClock,
+    builder::{weak_subjectivity_backfill_slot, Witness},
+    eth1_chain::CachingEth1Backend,
+    schema_change::migrate_schema,
+    slot_clock::{SlotClock, SystemTimeSlotClock},
 };
 use beacon_node::{get_data_dir, get_slots_per_restore_point, ClientConfig};
 use clap::ArgMatches;
 use clap::ValueEnum;
-use cli::{Compact, Inspect};
+use cli::{Compact, Inspect, ResizeSlasher};
 use environment::{Environment, RuntimeContext};
 use serde::{Deserialize, Serialize};
+use slasher::{DatabaseBackendOverride, SlasherDB};
 use slog::{info, warn, Logger};
 use std::fs;
 use std::io::Write;
 use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
 use store::metadata::STATE_UPPER_LIMIT_NO_RETAIN;
 use store::{
     errors::Error,
     metadata::{SchemaVersion, CURRENT_SCHEMA_VERSION},
-    DBColumn, HotColdDB, KeyValueStore, LevelDB,
+    non_empty_column_sizes, DBColumn, HotColdDB, KeyValueStore, LevelDB,
 };
 use strum::{EnumString, EnumVariantNames};
 use types::{BeaconState, EthSpec, Slot};
@@ -106,7 +114,8 @@ pub enum InspectTarget {
 }
 
 pub struct InspectConfig {
-    column: DBColumn,
+    /// The column to inspect, or `None` to show the size of every column.
+    column: Option<DBColumn>,
     target: InspectTarget,
     skip: Option<usize>,
     limit: Option<usize>,
@@ -117,9 +126,11 @@ pub struct InspectConfig {
 }
 
 fn parse_inspect_config(inspect_config: &Inspect) -> Result<InspectConfig, String> {
-    let column: DBColumn = inspect_config
+    let column: Option<DBColumn> = inspect_config
         .column
-        .parse()
+        .as_ref()
+        .map(|column| column.parse())
+        .transpose()
         .map_err(|e| format!("Unable to parse column flag: {e:?}"))?;
     let target: InspectTarget = inspect_config.output.clone();
     let skip = inspect_config.skip;
@@ -158,6 +169,10 @@ pub fn inspect_db<E: EthSpec>(
         LevelDB::<E>::open(&hot_path).map_err(|e| format!("Unable to open hot DB: {e:?}"))?
     };
 
+    let Some(column) = inspect_config.column else {
+        return inspect_column_sizes(&sub_db);
+    };
+
     let skip = inspect_config.skip.unwrap_or(0);
     let limit = inspect_config.limit.unwrap_or(usize::MAX);
 
@@ -171,11 +186,7 @@ pub fn inspect_db<E: EthSpec>(
             .map_err(|e| format!("Unable to create import directory: {:?}", e))?;
     }
 
-    for res in sub_db
-        .iter_column::<Vec<u8>>(inspect_config.column)
-        .skip(skip)
-        .take(limit)
-    {
+    for res in sub_db.iter_column::<Vec<u8>>(column).skip(skip).take(limit) {
         let (key, value) = res.map_err(|e| format!("{:?}", e))?;
 
         match inspect_config.target {
@@ -201,11 +212,8 @@ pub fn inspect_db<E: EthSpec>(
             }
             InspectTarget::ValueTotal => (),
             InspectTarget::Values => {
-                let file_path = base_path.join(format!(
-                    "{}_{}.ssz",
-                    inspect_config.column.as_str(),
-                    hex::encode(&key)
-                ));
+                let file_path =
+                    base_path.join(format!("{}_{}.ssz", column.as_str(), hex::encode(&key)));
 
                 let write_result = fs::OpenOptions::new()
                     .create(true)
@@ -238,6 +246,25 @@ pub fn inspect_db<E: EthSpec>(
     Ok(())
 }
 
+fn inspect_column_sizes<E: EthSpec>(db: &LevelDB<E>) -> Result<(), String> {
+    let sizes = non_empty_column_sizes(db).map_err(|e| format!("{:?}", e))?;
+
+    let mut total = 0;
+    for size in sizes {
+        println!(
+            "{} ({:?}): {} keys, {} bytes",
+            size.column.as_str(),
+            size.column,
+            size.num_keys,
+            size.bytes
+        );
+        total += size.bytes;
+    }
+    println!("Total: {} bytes", total);
+
+    Ok(())
+}
+
 pub struct CompactConfig {
     column: DBColumn,
     freezer: bool,
@@ -473,6 +500,251 @@ pub fn prune_states<E: EthSpec>(
     Ok(())
 }
 
+pub struct PruneHistoryBeforeConfig {
+    slot: Slot,
+    confirm: bool,
+}
+
+fn parse_prune_history_before_config(
+    prune_history_before_config: &PruneHistoryBefore,
+) -> Result<PruneHistoryBeforeConfig, String> {
+    Ok(PruneHistoryBeforeConfig {
+        slot: Slot::new(prune_history_before_config.slot),
+        confirm: prune_history_before_config.confirm,
+    })
+}
+
+pub fn prune_history_before<E: EthSpec>(
+    client_config: ClientConfig,
+    prune_config: PruneHistoryBeforeConfig,
+    runtime_context: &RuntimeContext<E>,
+    log: Logger,
+) -> Result<(), String> {
+    let spec = &runtime_context.eth2_config.spec;
+    let hot_path = client_config.get_db_path();
+    let cold_path = client_config.get_freezer_db_path();
+    let blobs_path = client_config.get_blobs_db_path();
+
+    let db = HotColdDB::<E, LevelDB<E>, LevelDB<E>>::open(
+        &hot_path,
+        &cold_path,
+        &blobs_path,
+        |_, _, _| Ok(()),
+        client_config.store,
+        spec.clone(),
+        log.clone(),
+    )
+    .map_err(|e| format!("Unable to open database: {e:?}"))?;
+
+    let anchor_info = db.get_anchor_info();
+    let states_pruned = anchor_info.as_ref().map_or(false, |anchor_info| {
+        anchor_info.state_lower_limit == spec.genesis_slot
+            && anchor_info.state_upper_limit >= prune_config.slot
+    });
+    if !states_pruned {
+        return Err(
+            "Error: historic states must be pruned first, see the prune-states command".into(),
+        );
+    }
+    let split = db.get_split_info();
+    if prune_config.slot >= split.slot {
+        return Err(format!(
+            "Error: slot {} is not finalized, the database is finalized up to slot {}",
+            prune_config.slot, split.slot
+        ));
+    }
+
+    // Backfill sync would download any blocks and blobs deleted from the weak subjectivity period
+    // again.
+    let genesis_time = db
+        .get_state(&split.state_root, Some(split.slot))
+        .map_err(|e| format!("Unable to load split state: {e:?}"))?
+        .ok_or("Split state is missing")?
+        .genesis_time();
+    let slot_clock = SystemTimeSlotClock::new(
+        spec.genesis_slot,
+        Duration::from_secs(genesis_time),
+        Duration::from_secs(spec.seconds_per_slot),
+    );
+    let backfill_slot = weak_subjectivity_backfill_slot::<E>(slot_clock.now(), spec);
+    if prune_config.slot > backfill_slot {
+        return Err(format!(
+            "Error: slot {} is within the weak subjectivity period, which backfill sync would \
+             download again. The latest slot that can be pruned before is {}",
+            prune_config.slot, backfill_slot
+        ));
+    }
+
+    // Check that the user has confirmed they want to proceed.
+    if !prune_config.confirm {
+        match anchor_info {
+            Some(anchor_info) if anchor_info.oldest_block_slot >= prune_config.slot => {
+                info!(
+                    log,
+                    "History has already been pruned";
+                    "oldest_block_slot" => anchor_info.oldest_block_slot,
+                );
+                return Ok(());
+            }
+            _ => {
+                info!(log, "Ready to prune history"; "slot" => prune_config.slot);
+            }
+        }
+        warn!(
+            log,
+            "Pruning history is irreversible";
+        );
+        warn!(
+            log,
+            "Re-run this command with --confirm to commit to block deletion"
+        );
+        info!(log, "Nothing has been pruned on this run");
+        return Err("Error: confirmation flag required".into());
+    }
+
+    let num_blocks = db
+        .prune_history_before(prune_config.slot)
+        .map_err(|e| format!("Failed to prune due to error: {e:?}"))?;
+
+    info!(
+        log,
+        "History pruned successfully";
+        "blocks_deleted" => num_blocks,
+        "oldest_block_slot" => db.get_oldest_block_slot(),
+    );
+    Ok(())
+}
+
+pub fn set_oldest_block_slot<E: EthSpec>(
+    client_config: ClientConfig,
+    set_oldest_block_slot_config: &SetOldestBlockSlot,
+    runtime_context: &RuntimeContext<E>,
+    log: Logger,
+) -> Result<(), Error> {
+    let spec = &runtime_context.eth2_config.spec;
+    let hot_path = client_config.get_db_path();
+    let cold_path = client_config.get_freezer_db_path();
+    let blobs_path = client_config.get_blobs_db_path();
+
+    let db = HotColdDB::<E, LevelDB<E>, LevelDB<E>>::open(
+        &hot_path,
+        &cold_path,
+        &blobs_path,
+        |_, _, _| Ok(()),
+        client_config.store,
+        spec.clone(),
+        log.clone(),
+    )?;
+
+    let anchor_info = db.set_oldest_block_slot(Slot::new(set_oldest_block_slot_config.slot))?;
+
+    info!(
+        log,
+        "Updated oldest block slot";
+        "oldest_block_slot" => anchor_info.oldest_block_slot,
+        "oldest_block_parent" => ?anchor_info.oldest_block_parent,
+    );
+    Ok(())
+}
+
+fn parse_resize_slasher_config(
+    resize_slasher_config: &ResizeSlasher,
+    client_config: &ClientConfig,
+) -> Result<slasher::Config, String> {
+    let slasher_dir = resize_slasher_config
+        .slasher_dir
+        .clone()
+        .unwrap_or_else(|| client_config.data_dir().join("slasher_db"));
+
+    let mut slasher_config = slasher::Config::new(slasher_dir);
+    if let Some(history_length) = resize_slasher_config.slasher_history_length {
+        slasher_config.history_length = history_length;
+    }
+    if let Some(chunk_size) = resize_slasher_config.slasher_chunk_size {
+        slasher_config.chunk_size = chunk_size;
+    }
+    if let Some(validator_chunk_size) = resize_slasher_config.slasher_validator_chunk_size {
+        slasher_config.validator_chunk_size = validator_chunk_size;
+    }
+    if let Some(max_db_size_gbs) = resize_slasher_config.slasher_max_db_size {
+        slasher_config.max_db_size_mbs = max_db_size_gbs * 1024;
+    }
+    if let Some(backend) = resize_slasher_config.slasher_backend {
+        slasher_config.backend = backend;
+    }
+
+    Ok(slasher_config)
+}
+
+/// Re-chunk the slasher database to match the chunk parameters of `slasher_config`.
+pub fn resize_slasher<E: EthSpec>(
+    mut slasher_config: slasher::Config,
+    runtime_context: &RuntimeContext<E>,
+    log: Logger,
+) -> Result<(), String> {
+    if !slasher_config.database_path.exists() {
+        return Err(format!(
+            "Slasher database not found at {}",
+            slasher_config.database_path.display()
+        ));
+    }
+
+    match slasher_config.override_backend() {
+        DatabaseBackendOverride::Success(old_backend) => {
+            info!(
+                log,
+                "Slasher backend overridden";
+                "reason" => "database exists",
+                "configured_backend" => %old_backend,
+                "override_backend" => %slasher_config.backend,
+            );
+        }
+        DatabaseBackendOverride::Failure(path) => {
+            return Err(format!(
+                "Slasher database at {} uses a backend which is not enabled",
+                path.display()
+            ));
+        }
+        DatabaseBackendOverride::Noop => {}
+    }
+
+    SlasherDB::<E>::open_and_resize(
+        Arc::new(slasher_config),
+        Arc::new(runtime_context.eth2_config.spec.clone()),
+        log,
+    )
+    .map_err(|e| format!("Unable to resize slasher database: {:?}", e))?;
+
+    Ok(())
+}
+
+/// Fetch the genesis state for the network, which may require downloading it.
+fn get_genesis_state<E: EthSpec>(
+    client_config: &ClientConfig,
+    env: &Environment<E>,
+    log: &Logger,
+) -> Result<BeaconState<E>, String> {
+    let context = env.core_context();
+    let network_config = context
+        .eth2_network_config
+        .clone()
+        .ok_or("Missing network config")?;
+
+    context
+        .executor
+        .block_on_dangerous(
+            network_config.genesis_state::<E>(
+                client_config.genesis_state_url.as_deref(),
+                client_config.genesis_state_url_timeout,
+                log,
+            ),
+            "get_genesis_state",
+        )
+        .ok_or("Shutting down")?
+        .map_err(|e| format!("Error getting genesis state: {e}"))?
+        .ok_or_else(|| "Genesis state missing".to_string())
+}
+
 /// Run the database manager, returning an error string if the operation did not succeed.
 pub fn run<E: EthSpec>(
     cli_args: &ArgMatches,
@@ -503,32 +775,58 @@ pub fn run<E: EthSpec>(
             prune_blobs(client_config, &context, log).map_err(format_err)
         }
         cli::DatabaseManagerSubcommand::PruneStates(prune_states_config) => {
-            let executor = env.core_context().executor;
-            let network_config = context
-                .eth2_network_config
-                .clone()
-                .ok_or("Missing network config")?;
-
-            let genesis_state = executor
-                .block_on_dangerous(
-                    network_config.genesis_state::<E>(
-                        client_config.genesis_state_url.as_deref(),
-                        client_config.genesis_state_url_timeout,
-                        &log,
-                    ),
-                    "get_genesis_state",
-                )
-                .ok_or("Shutting down")?
-                .map_err(|e| format!("Error getting genesis state: {e}"))?
-                .ok_or("Genesis state missing")?;
-
+            let genesis_state = get_genesis_state(&client_config, &env, &log)?;
             let prune_config = parse_prune_states_config(prune_states_config)?;
 
             prune_states(client_config, prune_config, genesis_state, &context, log)
         }
+        cli::DatabaseManagerSubcommand::PruneHistoryBefore(prune_history_before_config) => {
+            let prune_config = parse_prune_history_before_config(prune_history_before_config)?;
+            prune_history_before(client_config, prune_config, &context, log)
+        }
+        cli::DatabaseManagerSubcommand::SetOldestBlockSlot(set_oldest_block_slot_config) => {
+            set_oldest_block_slot(client_config, set_oldest_block_slot_config, &context, log)
+                .map_err(format_err)
+        }
         cli::DatabaseManagerSubcommand::Compact(compact_config) => {
             let compact_config = parse_compact_config(compact_config)?;
             compact_db::<E>(compact_config, client_config, log).map_err(format_err)
         }
+        cli::DatabaseManagerSubcommand::Export(export_config) => {
+            let export_config = snapshot::parse_export_config(export_config)?;
+            snapshot::export_snapshot::<E>(
+                export_config,
+                client_config,
+                &context.eth2_config.spec,
+                log,
+            )
+        }
+        cli::DatabaseManagerSubcommand::Import(import_config) => {
+            let import_config = snapshot::parse_import_config(import_config)?;
+            // The genesis state is only needed if the snapshot excludes historic states, so it is
+            // fetched lazily.
+            let get_genesis = || get_genesis_state(&client_config, &env, &log);
+            snapshot::import_snapshot::<E>(
+                import_config,
+                client_config.clone(),
+                &context.eth2_config.spec,
+                get_genesis,
+                log.clone(),
+            )
+        }
+        cli::DatabaseManagerSubcommand::ExportEra(export_era_config) => {
+            let export_era_config = era::parse_export_era_config(export_era_config)?;
+            era::export_era::<E>(
+                export_era_config,
+                client_config,
+                &context.eth2_config.spec,
+                log,
+            )
+        }
+        cli::DatabaseManagerSubcommand::ResizeSlasher(resize_slasher_config) => {
+            let slasher_config =
+                parse_resize_slasher_config(resize_slasher_config, &client_config)?;
+            resize_slasher(slasher_config, &context, log)
+        }
     }
 }
@@ -0,0 +1,1157 @@
+//! Export and import of database snapshots.
+//!
+//! A snapshot is a `tar` archive compressed with `zstd`, containing:
+//!
+//! - `manifest.json`: describes the snapshot.
+//! - `chunks/*`: the raw key-value pairs of the database, split into chunks of approximately
+//!   `CHUNK_SIZE_BYTES` each. Each chunk is preceded by a `chunks/*.json` entry describing it,
+//!   including its SHA-256 checksum.
+//! - `chunks.json`: lists every chunk, so that a truncated snapshot can be detected.
+//!
+//! The manifest is always the first entry in the archive and the chunk list the last. Chunks are
+//! written to the archive as they are read from the database, and verified as they are streamed
+//! during import, so neither operation needs to stage a copy of the database.
+//!
+//! A snapshot contains every block and blob, the finalized (split) state and a minimal hot
+//! database: the metadata, fork choice and states needed to resume from the head, and only the
+//! execution payloads of the non-finalized blocks. Data which is specific to a single node or
+//! which is rebuilt on startup is not exported. Historic states are only exported from the
+//! freezer database if requested; otherwise the imported database is left in the same condition
+//! as after `lighthouse db prune-states`.
+//!
+//! Both operations can be resumed after being interrupted. Each chunk is written to the archive
+//! as a separate `zstd` frame, and export records the length of the partial archive after each
+//! chunk alongside the output file, so that a resumed export can discard an incomplete chunk and
+//! continue from there. Import records the chunks it has written in the data directory. The
+//! manifest records the split and anchor of the exported database: an export is only resumed if
+//! they are unchanged, and an import is only completed if the imported database has the same split
+//! and anchor.
+use crate::cli::{Export, Import};
+use beacon_node::ClientConfig;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use slog::{debug, info, Logger};
+use std::collections::HashSet;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Seek, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use store::errors::Error as StoreError;
+use store::metadata::{SchemaVersion, CURRENT_SCHEMA_VERSION};
+use store::{
+    get_key_for_col, AnchorInfo, DBColumn, HotColdDB, HotStateSummary, KeyValueStore,
+    KeyValueStoreOp, LevelDB, StoreItem,
+};
+use strum::IntoEnumIterator;
+use types::{BeaconState, ChainSpec, EthSpec, Hash256, Slot};
+
+/// Incremented whenever the layout of the archive or chunks changes incompatibly.
+const SNAPSHOT_FORMAT_VERSION: u64 = 1;
+const MANIFEST_FILE: &str = "manifest.json";
+const CHUNK_LIST_FILE: &str = "chunks.json";
+const CHUNKS_DIR: &str = "chunks";
+/// Suffix of the entry describing a chunk, which immediately precedes the chunk.
+const CHUNK_DESCRIPTOR_SUFFIX: &str = ".json";
+/// Name of the file recording the progress of an import, stored in the data directory.
+const IMPORT_PROGRESS_FILE: &str = "snapshot_import.json";
+/// Chunks are closed once they reach this size, so a chunk containing a single large value may
+/// exceed it.
+const CHUNK_SIZE_BYTES: usize = 64 * 1024 * 1024;
+const ZSTD_COMPRESSION_LEVEL: i32 = 3;
+const TAR_BLOCK_SIZE: usize = 512;
+
+/// Hot database columns which are not exported, as they are specific to a node or rebuilt on
+/// startup.
+const EXCLUDED_HOT_COLUMNS: &[DBColumn] = &[
+    DBColumn::OpPool,
+    DBColumn::DhtEnrs,
+    DBColumn::OverflowLRUCache,
+    DBColumn::Eth1Cache,
+];
+
+/// Freezer database columns which hold historic states.
+const HISTORIC_STATE_COLUMNS: &[DBColumn] = &[
+    DBColumn::BeaconState,
+    DBColumn::BeaconStateSummary,
+    DBColumn::BeaconRestorePoint,
+    DBColumn::BeaconStateRoots,
+    DBColumn::BeaconHistoricalRoots,
+    DBColumn::BeaconRandaoMixes,
+    DBColumn::BeaconHistoricalSummaries,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SnapshotDb {
+    Hot,
+    Freezer,
+    Blobs,
+}
+
+impl SnapshotDb {
+    fn as_str(self) -> &'static str {
+        match self {
+            SnapshotDb::Hot => "hot",
+            SnapshotDb::Freezer => "freezer",
+            SnapshotDb::Blobs => "blobs",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotManifest {
+    pub format_version: u64,
+    pub config_name: Option<String>,
+    pub schema_version: u64,
+    /// Unix timestamp at which the export began, which distinguishes snapshots of a database
+    /// whose split hasn't changed.
+    pub created_at: u64,
+    pub split_slot: Slot,
+    pub split_state_root: Hash256,
+    /// The anchor of the exported database, which is `None` if it was synced from genesis.
+    pub anchor_info: Option<AnchorInfo>,
+    /// Whether historic states from the freezer database are included.
+    pub historic_states: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotChunk {
+    /// Path of the chunk within the archive.
+    pub name: String,
+    pub db: SnapshotDb,
+    /// 3-byte column ID (see `DBColumn`).
+    pub column: String,
+    pub num_entries: u64,
+    pub bytes: u64,
+    /// Hex-encoded SHA-256 checksum of the chunk.
+    pub sha256: String,
+    /// Hex-encoded key of the final entry in the chunk, used to resume an export.
+    pub last_key: String,
+}
+
+/// The progress of an export, persisted alongside the partial archive.
+#[derive(Debug, Serialize, Deserialize)]
+struct ExportProgress {
+    manifest: SnapshotManifest,
+    /// The chunks which have been written to the partial archive.
+    chunks: Vec<SnapshotChunk>,
+    /// Columns which have been exported in full, as `db/column`.
+    completed_columns: Vec<String>,
+    /// The length of the partial archive up to the end of the last chunk.
+    archive_len: u64,
+}
+
+/// The progress of an import, persisted in the data directory.
+#[derive(Debug, Serialize, Deserialize)]
+struct ImportProgress {
+    /// Hex-encoded SHA-256 checksum of the manifest of the snapshot being imported.
+    manifest_sha256: String,
+    /// Names of the chunks which have been written to the database.
+    completed_chunks: Vec<String>,
+}
+
+pub struct ExportConfig {
+    output: PathBuf,
+    historic_states: bool,
+}
+
+pub fn parse_export_config(export_config: &Export) -> Result<ExportConfig, String> {
+    Ok(ExportConfig {
+        output: export_config.output.clone(),
+        historic_states: export_config.historic_states,
+    })
+}
+
+pub struct ImportConfig {
+    input: PathBuf,
+}
+
+pub fn parse_import_config(import_config: &Import) -> Result<ImportConfig, String> {
+    Ok(ImportConfig {
+        input: import_config.input.clone(),
+    })
+}
+
+/// Export a snapshot of the database to `export_config.output`.
+pub fn export_snapshot<E: EthSpec>(
+    export_config: ExportConfig,
+    client_config: ClientConfig,
+    spec: &ChainSpec,
+    log: Logger,
+) -> Result<(), String> {
+    let hot_path = client_config.get_db_path();
+    if !hot_path.exists() {
+        return Err(format!("No database found at {}", hot_path.display()));
+    }
+    if export_config.output.exists() {
+        return Err(format!("{} already exists", export_config.output.display()));
+    }
+
+    // Opening the database checks its schema and config, and holds the lock on each LevelDB
+    // directory for the duration of the export, which ensures the snapshot is consistent.
+    let db = HotColdDB::<E, LevelDB<E>, LevelDB<E>>::open(
+        &hot_path,
+        &client_config.get_freezer_db_path(),
+        &client_config.get_blobs_db_path(),
+        |_, from, to| {
+            if from == to {
+                Ok(())
+            } else {
+                Err(StoreError::SchemaMigrationError(format!(
+                    "database schema v{} must be migrated to v{} before it can be exported",
+                    from.as_u64(),
+                    to.as_u64()
+                )))
+            }
+        },
+        client_config.store.clone(),
+        spec.clone(),
+        log.clone(),
+    )
+    .map_err(|e| format!("Unable to open database: {e:?}"))?;
+
+    let split = db.get_split_info();
+    let created_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|e| format!("Invalid system time: {e:?}"))?
+        .as_secs();
+    let manifest = SnapshotManifest {
+        format_version: SNAPSHOT_FORMAT_VERSION,
+        config_name: spec.config_name.clone(),
+        schema_version: CURRENT_SCHEMA_VERSION.as_u64(),
+        created_at,
+        split_slot: split.slot,
+        split_state_root: split.state_root,
+        anchor_info: db.get_anchor_info(),
+        historic_states: export_config.historic_states,
+    };
+
+    let (partial_output, progress_path) = partial_export_paths(&export_config.output);
+    let mut writer = if progress_path.exists() {
+        let progress: ExportProgress = read_json(&progress_path)?;
+        // The database must not have been migrated or backfilled since the export began, as the
+        // chunks which have already been written would be inconsistent with the remaining ones.
+        if (
+            progress.manifest.split_slot,
+            progress.manifest.split_state_root,
+        ) != (manifest.split_slot, manifest.split_state_root)
+            || progress.manifest.anchor_info != manifest.anchor_info
+        {
+            return Err(format!(
+                "The split or anchor of the database has changed since the partial export in {} \
+                 was started, delete it and try again",
+                partial_output.display()
+            ));
+        }
+        let resumed_manifest = SnapshotManifest {
+            created_at: progress.manifest.created_at,
+            ..manifest
+        };
+        if progress.manifest != resumed_manifest {
+            return Err(format!(
+                "The partial export in {} does not match the database, delete it and try again",
+                partial_output.display()
+            ));
+        }
+        info!(
+            log,
+            "Resuming export";
+            "chunks" => progress.chunks.len(),
+            "partial_output" => partial_output.display(),
+        );
+        ArchiveWriter::resume(partial_output, progress_path, progress)?
+    } else {
+        ArchiveWriter::create(partial_output, progress_path, manifest)?
+    };
+
+    info!(
+        log,
+        "Exporting database";
+        "split_slot" => split.slot,
+        "historic_states" => export_config.historic_states,
+    );
+
+    // Only the execution payloads of blocks with a state in the hot database are exported, i.e.
+    // those of the non-finalized blocks and the split block, as though payloads had been pruned.
+    // Temporary states have already been garbage collected by opening the database.
+    let hot_block_roots = db
+        .hot_db
+        .iter_raw_entries(DBColumn::BeaconStateSummary, &[])
+        .map(|res| {
+            let (_, value) = res.map_err(|e| format!("Unable to read state summaries: {e:?}"))?;
+            let summary = HotStateSummary::from_store_bytes(&value)
+                .map_err(|e| format!("Invalid state summary: {e:?}"))?;
+            Ok(summary.latest_block_root.as_bytes().to_vec())
+        })
+        .collect::<Result<HashSet<_>, String>>()?;
+
+    let sub_dbs = [
+        (SnapshotDb::Hot, &db.hot_db),
+        (SnapshotDb::Freezer, &db.cold_db),
+        (SnapshotDb::Blobs, &db.blobs_db),
+    ];
+    for (snapshot_db, sub_db) in sub_dbs {
+        for column in DBColumn::iter() {
+            let excluded = match snapshot_db {
+                SnapshotDb::Hot => EXCLUDED_HOT_COLUMNS.contains(&column),
+                SnapshotDb::Freezer => {
+                    !export_config.historic_states && HISTORIC_STATE_COLUMNS.contains(&column)
+                }
+                SnapshotDb::Blobs => false,
+            };
+            if excluded {
+                continue;
+            }
+            let include_key = |key: &[u8]| {
+                snapshot_db != SnapshotDb::Hot
+                    || column != DBColumn::ExecPayload
+                    || hot_block_roots.contains(key)
+            };
+            export_column(sub_db, snapshot_db, column, include_key, &mut writer, &log)?;
+        }
+    }
+
+    let num_chunks = writer.finish(&export_config.output)?;
+
+    info!(
+        log,
+        "Export complete";
+        "chunks" => num_chunks,
+        "output" => export_config.output.display(),
+    );
+    Ok(())
+}
+
+/// Write every entry of `column` for which `include_key` returns `true` to the archive, resuming
+/// from the last chunk written by a previous run.
+fn export_column<E: EthSpec>(
+    sub_db: &LevelDB<E>,
+    snapshot_db: SnapshotDb,
+    column: DBColumn,
+    include_key: impl Fn(&[u8]) -> bool,
+    writer: &mut ArchiveWriter,
+    log: &Logger,
+) -> Result<(), String> {
+    let column_id = format!("{}/{}", snapshot_db.as_str(), column.as_str());
+    if writer.progress.completed_columns.contains(&column_id) {
+        return Ok(());
+    }
+
+    let previous_chunks = writer
+        .progress
+        .chunks
+        .iter()
+        .filter(|chunk| chunk.db == snapshot_db && chunk.column == column.as_str())
+        .collect::<Vec<_>>();
+    let mut chunk_index = previous_chunks.len();
+    let resume_from = previous_chunks
+        .last()
+        .map(|chunk| hex::decode(&chunk.last_key))
+        .transpose()
+        .map_err(|e| format!("Invalid key in partial export: {e:?}"))?;
+
+    let mut buffer = vec![];
+    let mut num_entries = 0;
+    let mut last_key = vec![];
+    for res in sub_db.iter_raw_entries(column, &[]) {
+        let (key, value) = res.map_err(|e| format!("Unable to read {column_id}: {e:?}"))?;
+        // Keys are iterated in order, so everything up to the last key of the previous chunk has
+        // already been exported.
+        if resume_from.as_ref().map_or(false, |resume| key <= *resume) || !include_key(&key) {
+            continue;
+        }
+
+        encode_entry(&mut buffer, &key, &value);
+        num_entries += 1;
+        last_key = key;
+
+        if buffer.len() >= CHUNK_SIZE_BYTES {
+            writer.append_chunk(
+                snapshot_db,
+                column,
+                chunk_index,
+                &std::mem::take(&mut buffer),
+                num_entries,
+                &last_key,
+            )?;
+            chunk_index += 1;
+            num_entries = 0;
+        }
+    }
+
+    if !buffer.is_empty() {
+        writer.append_chunk(
+            snapshot_db,
+            column,
+            chunk_index,
+            &buffer,
+            num_entries,
+            &last_key,
+        )?;
+        chunk_index += 1;
+    }
+    writer.progress.completed_columns.push(column_id.clone());
+    write_json(&writer.progress_path, &writer.progress)?;
+
+    debug!(log, "Exported column"; "column" => column_id, "chunks" => chunk_index);
+    Ok(())
+}
+
+/// Writes the archive as chunks are read from the database.
+///
+/// Each chunk is appended as a separate `zstd` frame, after which the progress is recorded, so
+/// that a resumed export can truncate the archive to the end of the last complete chunk.
+struct ArchiveWriter {
+    file: File,
+    path: PathBuf,
+    progress: ExportProgress,
+    progress_path: PathBuf,
+}
+
+impl ArchiveWriter {
+    /// Create a new partial archive at `path`, beginning with the manifest.
+    fn create(
+        path: PathBuf,
+        progress_path: PathBuf,
+        manifest: SnapshotManifest,
+    ) -> Result<Self, String> {
+        let file = File::create(&path)
+            .map_err(|e| format!("Unable to create {}: {e:?}", path.display()))?;
+        let manifest_bytes = serde_json::to_vec_pretty(&manifest)
+            .map_err(|e| format!("Unable to serialize manifest: {e:?}"))?;
+        let mut writer = Self {
+            file,
+            path,
+            progress: ExportProgress {
+                manifest,
+                chunks: vec![],
+                completed_columns: vec![],
+                archive_len: 0,
+            },
+            progress_path,
+        };
+        writer.append_frame(&[(MANIFEST_FILE, manifest_bytes.as_slice())], false)?;
+        Ok(writer)
+    }
+
+    /// Re-open the partial archive at `path`, discarding anything written after the last chunk.
+    fn resume(
+        path: PathBuf,
+        progress_path: PathBuf,
+        progress: ExportProgress,
+    ) -> Result<Self, String> {
+        let mut file = OpenOptions::new()
+            .write(true)
+            .open(&path)
+            .map_err(|e| format!("Unable to open {}: {e:?}", path.display()))?;
+        file.set_len(progress.archive_len)
+            .and_then(|()| file.seek(io::SeekFrom::End(0)))
+            .map_err(|e| format!("Unable to truncate {}: {e:?}", path.display()))?;
+        Ok(Self {
+            file,
+            path,
+            progress,
+            progress_path,
+        })
+    }
+
+    fn append_chunk(
+        &mut self,
+        snapshot_db: SnapshotDb,
+        column: DBColumn,
+        chunk_index: usize,
+        data: &[u8],
+        num_entries: u64,
+        last_key: &[u8],
+    ) -> Result<(), String> {
+        let chunk = SnapshotChunk {
+            name: format!(
+                "{}/{}-{}-{:06}",
+                CHUNKS_DIR,
+                snapshot_db.as_str(),
+                column.as_str(),
+                chunk_index
+            ),
+            db: snapshot_db,
+            column: column.as_str().to_string(),
+            num_entries,
+            bytes: data.len() as u64,
+            sha256: sha256_hex(data),
+            last_key: hex::encode(last_key),
+        };
+        let descriptor_name = format!("{}{}", chunk.name, CHUNK_DESCRIPTOR_SUFFIX);
+        let descriptor = serde_json::to_vec_pretty(&chunk)
+            .map_err(|e| format!("Unable to serialize chunk descriptor: {e:?}"))?;
+
+        let name = chunk.name.clone();
+        self.progress.chunks.push(chunk);
+        self.append_frame(
+            &[
+                (descriptor_name.as_str(), descriptor.as_slice()),
+                (name.as_str(), data),
+            ],
+            false,
+        )
+    }
+
+    /// Append the chunk list and the end of the archive, and move it to `output`.
+    ///
+    /// Returns the number of chunks in the archive.
+    fn finish(mut self, output: &Path) -> Result<usize, String> {
+        let chunk_list = serde_json::to_vec_pretty(&self.progress.chunks)
+            .map_err(|e| format!("Unable to serialize chunk list: {e:?}"))?;
+        self.append_frame(&[(CHUNK_LIST_FILE, chunk_list.as_slice())], true)?;
+
+        fs::rename(&self.path, output)
+            .and_then(|()| fs::remove_file(&self.progress_path))
+            .map_err(|e| format!("Unable to write {}: {e:?}", output.display()))?;
+        Ok(self.progress.chunks.len())
+    }
+
+    /// Append `entries` to the archive as a single `zstd` frame, and record the progress once they
+    /// are on disk.
+    ///
+    /// The progress is not advanced past the end of the archive, so that an export interrupted
+    /// before the archive is moved to the output path rewrites the chunk list when resumed.
+    fn append_frame(
+        &mut self,
+        entries: &[(&str, &[u8])],
+        end_of_archive: bool,
+    ) -> Result<(), String> {
+        let archive_err = |e: io::Error| format!("Unable to write {}: {e:?}", self.path.display());
+
+        let mut encoder =
+            zstd::Encoder::new(&mut self.file, ZSTD_COMPRESSION_LEVEL).map_err(archive_err)?;
+        for (name, data) in entries {
+            append_tar_entry(&mut encoder, name, data).map_err(archive_err)?;
+        }
+        if end_of_archive {
+            encoder
+                .write_all(&[0; 2 * TAR_BLOCK_SIZE])
+                .map_err(archive_err)?;
+        }
+        encoder.finish().map_err(archive_err)?;
+
+        self.file.sync_data().map_err(archive_err)?;
+        if end_of_archive {
+            return Ok(());
+        }
+        self.progress.archive_len = self.file.stream_position().map_err(archive_err)?;
+        write_json(&self.progress_path, &self.progress)
+    }
+}
+
+/// Write a file to a `tar` stream.
+///
+/// `tar::Builder` isn't used as it terminates the archive when dropped, whereas a resumed export
+/// continues the archive written by a previous run.
+fn append_tar_entry(writer: &mut impl Write, name: &str, data: &[u8]) -> io::Result<()> {
+    let mut header = tar::Header::new_gnu();
+    header.set_path(name)?;
+    header.set_size(data.len() as u64);
+    header.set_mode(0o644);
+    header.set_cksum();
+    writer.write_all(header.as_bytes())?;
+    writer.write_all(data)?;
+    let padding = data.len().next_multiple_of(TAR_BLOCK_SIZE) - data.len();
+    writer.write_all(&[0; TAR_BLOCK_SIZE][..padding])
+}
+
+/// Import the snapshot at `import_config.input` into an empty database.
+///
+/// If the snapshot does not contain historic states, `get_genesis_state` is used to re-store the
+/// genesis state in the freezer database.
+pub fn import_snapshot<E: EthSpec>(
+    import_config: ImportConfig,
+    client_config: ClientConfig,
+    spec: &ChainSpec,
+    get_genesis_state: impl FnOnce() -> Result<BeaconState<E>, String>,
+    log: Logger,
+) -> Result<(), String> {
+    let input = &import_config.input;
+    let file =
+        File::open(input).map_err(|e| format!("Unable to open {}: {e:?}", input.display()))?;
+    let decoder =
+        zstd::Decoder::new(file).map_err(|e| format!("Unable to create zstd decoder: {e:?}"))?;
+    let mut archive = tar::Archive::new(decoder);
+    let archive_err = |e: io::Error| format!("Unable to read {}: {e:?}", input.display());
+    let mut entries = archive.entries().map_err(archive_err)?;
+
+    // The manifest must be the first entry.
+    let mut manifest_entry = entries
+        .next()
+        .ok_or("Snapshot is empty")?
+        .map_err(archive_err)?;
+    if manifest_entry.path().map_err(archive_err)?.as_ref() != Path::new(MANIFEST_FILE) {
+        return Err(format!("Snapshot does not begin with {MANIFEST_FILE}"));
+    }
+    let mut manifest_bytes = vec![];
+    manifest_entry
+        .read_to_end(&mut manifest_bytes)
+        .map_err(archive_err)?;
+    let manifest: SnapshotManifest = serde_json::from_slice(&manifest_bytes)
+        .map_err(|e| format!("Invalid snapshot manifest: {e:?}"))?;
+    verify_manifest(&manifest, spec)?;
+
+    let hot_path = client_config.get_db_path();
+    let cold_path = client_config.get_freezer_db_path();
+    let blobs_path = client_config.get_blobs_db_path();
+
+    let progress_path = client_config.get_data_dir().join(IMPORT_PROGRESS_FILE);
+    let manifest_sha256 = sha256_hex(&manifest_bytes);
+    let mut progress = if progress_path.exists() {
+        let progress: ImportProgress = read_json(&progress_path)?;
+        if progress.manifest_sha256 != manifest_sha256 {
+            return Err(format!(
+                "A different snapshot is partially imported, remove the database and {} to start \
+                 again",
+                progress_path.display()
+            ));
+        }
+        info!(
+            log,
+            "Resuming import";
+            "completed_chunks" => progress.completed_chunks.len(),
+        );
+        progress
+    } else {
+        for path in [&hot_path, &cold_path, &blobs_path] {
+            if path.exists() {
+                return Err(format!(
+                    "A database already exists at {}, remove it (e.g. with --purge-db) before \
+                     importing a snapshot",
+                    path.display()
+                ));
+            }
+        }
+        client_config.create_data_dir()?;
+        let progress = ImportProgress {
+            manifest_sha256,
+            completed_chunks: vec![],
+        };
+        write_json(&progress_path, &progress)?;
+        progress
+    };
+
+    info!(
+        log,
+        "Importing snapshot";
+        "split_slot" => manifest.split_slot,
+        "historic_states" => manifest.historic_states,
+    );
+
+    let open_err = |e| format!("Unable to open database: {e:?}");
+    let hot_db = LevelDB::<E>::open(&client_config.create_db_path()?).map_err(open_err)?;
+    let cold_db = LevelDB::<E>::open(&client_config.create_freezer_db_path()?).map_err(open_err)?;
+    let blobs_db = LevelDB::<E>::open(&client_config.create_blobs_db_path()?).map_err(open_err)?;
+
+    let mut completed = progress
+        .completed_chunks
+        .iter()
+        .cloned()
+        .collect::<HashSet<_>>();
+    let mut descriptor: Option<SnapshotChunk> = None;
+    let mut chunk_list: Option<Vec<SnapshotChunk>> = None;
+    for entry in entries {
+        let mut entry = entry.map_err(archive_err)?;
+        let name = entry
+            .path()
+            .map_err(archive_err)?
+            .to_string_lossy()
+            .into_owned();
+        let mut data = vec![];
+
+        if name == CHUNK_LIST_FILE {
+            entry.read_to_end(&mut data).map_err(archive_err)?;
+            chunk_list = Some(
+                serde_json::from_slice(&data)
+                    .map_err(|e| format!("Invalid {CHUNK_LIST_FILE}: {e:?}"))?,
+            );
+            break;
+        }
+
+        if let Some(chunk_name) = name.strip_suffix(CHUNK_DESCRIPTOR_SUFFIX) {
+            entry.read_to_end(&mut data).map_err(archive_err)?;
+            let chunk: SnapshotChunk = serde_json::from_slice(&data)
+                .map_err(|e| format!("Invalid chunk descriptor {name}: {e:?}"))?;
+            if chunk.name != chunk_name {
+                return Err(format!("Chunk descriptor {name} describes {}", chunk.name));
+            }
+            descriptor = Some(chunk);
+            continue;
+        }
+
+        // Every chunk is immediately preceded by its descriptor.
+        let chunk = descriptor
+            .take()
+            .filter(|chunk| chunk.name == name)
+            .ok_or_else(|| format!("Snapshot contains {name} without a chunk descriptor"))?;
+        if completed.contains(&name) {
+            continue;
+        }
+
+        entry.read_to_end(&mut data).map_err(archive_err)?;
+        if data.len() as u64 != chunk.bytes || sha256_hex(&data) != chunk.sha256 {
+            return Err(format!(
+                "Checksum mismatch for {name}, the snapshot is corrupt"
+            ));
+        }
+
+        let column: DBColumn = chunk
+            .column
+            .parse()
+            .map_err(|e| format!("Unknown column in {name}: {e:?}"))?;
+        let ops = decode_entries(&data)
+            .map_err(|e| format!("Invalid chunk {name}: {e}"))?
+            .into_iter()
+            .map(|(key, value)| {
+                KeyValueStoreOp::PutKeyValue(get_key_for_col(column.as_str(), &key), value)
+            })
+            .collect::<Vec<_>>();
+        if ops.len() as u64 != chunk.num_entries {
+            return Err(format!("Entry count mismatch for {name}"));
+        }
+
+        let sub_db = match chunk.db {
+            SnapshotDb::Hot => &hot_db,
+            SnapshotDb::Freezer => &cold_db,
+            SnapshotDb::Blobs => &blobs_db,
+        };
+        // Sync the write before recording it, so that progress is never ahead of the database.
+        sub_db
+            .do_atomically(ops)
+            .and_then(|()| sub_db.sync())
+            .map_err(|e| format!("Unable to write {name}: {e:?}"))?;
+
+        completed.insert(name.clone());
+        progress.completed_chunks.push(name);
+        write_json(&progress_path, &progress)?;
+        debug!(log, "Imported chunk"; "completed" => completed.len());
+    }
+    drop((hot_db, cold_db, blobs_db));
+
+    // The chunk list is the final entry, so its absence means the snapshot was truncated.
+    let missing = match &chunk_list {
+        Some(chunks) => chunks
+            .iter()
+            .find(|chunk| !completed.contains(&chunk.name))
+            .map(|chunk| chunk.name.as_str()),
+        None => Some(CHUNK_LIST_FILE),
+    };
+    if let Some(missing) = missing {
+        return Err(format!(
+            "Snapshot is truncated, {missing} is missing. Re-run the import with the complete \
+             snapshot to resume"
+        ));
+    }
+
+    let db = HotColdDB::<E, LevelDB<E>, LevelDB<E>>::open(
+        &hot_path,
+        &cold_path,
+        &blobs_path,
+        |_, _, _| Ok(()),
+        client_config.store,
+        spec.clone(),
+        log.clone(),
+    )
+    .map_err(open_err)?;
+    verify_imported_db(&db, &manifest)?;
+
+    if !manifest.historic_states {
+        let mut genesis_state = get_genesis_state()?;
+        let genesis_state_root = genesis_state
+            .update_tree_hash_cache()
+            .map_err(|e| format!("Error computing genesis state root: {e:?}"))?;
+
+        db.prune_historic_states(genesis_state_root, &genesis_state)
+            .map_err(|e| format!("Unable to mark historic states as pruned: {e:?}"))?;
+    }
+
+    fs::remove_file(&progress_path)
+        .map_err(|e| format!("Unable to remove {}: {e:?}", progress_path.display()))?;
+
+    info!(log, "Import complete"; "split_slot" => manifest.split_slot);
+    Ok(())
+}
+
+fn verify_manifest(manifest: &SnapshotManifest, spec: &ChainSpec) -> Result<(), String> {
+    if manifest.format_version != SNAPSHOT_FORMAT_VERSION {
+        return Err(format!(
+            "Unsupported snapshot format version {}, expected {}",
+            manifest.format_version, SNAPSHOT_FORMAT_VERSION
+        ));
+    }
+    if manifest.config_name != spec.config_name {
+        return Err(format!(
+            "Snapshot is for network {}, not {}",
+            manifest
+                .config_name
+                .as_deref()
+                .unwrap_or("<unknown network>"),
+            spec.config_name.as_deref().unwrap_or("<unknown network>"),
+        ));
+    }
+    if SchemaVersion(manifest.schema_version) != CURRENT_SCHEMA_VERSION {
+        return Err(format!(
+            "Snapshot has schema v{}, but this version of Lighthouse requires v{}",
+            manifest.schema_version,
+            CURRENT_SCHEMA_VERSION.as_u64()
+        ));
+    }
+    Ok(())
+}
+
+/// Check that the imported database has the split and anchor recorded in the manifest.
+///
+/// This must be done before historic states are pruned, which modifies the anchor.
+fn verify_imported_db<E: EthSpec>(
+    db: &HotColdDB<E, LevelDB<E>, LevelDB<E>>,
+    manifest: &SnapshotManifest,
+) -> Result<(), String> {
+    let split = db.get_split_info();
+    if (split.slot, split.state_root) != (manifest.split_slot, manifest.split_state_root) {
+        return Err(format!(
+            "Imported database has split slot {} ({:?}), but the snapshot has {} ({:?})",
+            split.slot, split.state_root, manifest.split_slot, manifest.split_state_root
+        ));
+    }
+    let anchor_info = db.get_anchor_info();
+    if anchor_info != manifest.anchor_info {
+        return Err(format!(
+            "Imported database has anchor {:?}, but the snapshot has {:?}",
+            anchor_info, manifest.anchor_info
+        ));
+    }
+    Ok(())
+}
+
+/// Returns the paths of the partial archive and the export progress for `output`.
+fn partial_export_paths(output: &Path) -> (PathBuf, PathBuf) {
+    let with_suffix = |suffix: &str| {
+        let mut path = output.as_os_str().to_owned();
+        path.push(suffix);
+        PathBuf::from(path)
+    };
+    (with_suffix(".partial"), with_suffix(".progress.json"))
+}
+
+fn sha256_hex(data: &[u8]) -> String {
+    hex::encode(Sha256::digest(data))
+}
+
+/// Append an entry to a chunk, as a length-prefixed key followed by a length-prefixed value.
+fn encode_entry(buffer: &mut Vec<u8>, key: &[u8], value: &[u8]) {
+    buffer.extend_from_slice(&(key.len() as u32).to_be_bytes());
+    buffer.extend_from_slice(key);
+    buffer.extend_from_slice(&(value.len() as u32).to_be_bytes());
+    buffer.extend_from_slice(value);
+}
+
+/// Decode all of the entries in a chunk.
+#[allow(clippy::type_complexity)]
+fn decode_entries(mut data: &[u8]) -> Result<Vec<(Vec<u8>, Vec<u8>)>, String> {
+    fn take<'a>(data: &mut &'a [u8], len: usize) -> Result<&'a [u8], String> {
+        if data.len() < len {
+            return Err("unexpected end of chunk".to_string());
+        }
+        let (head, tail) = data.split_at(len);
+        *data = tail;
+        Ok(head)
+    }
+    fn take_len(data: &mut &[u8]) -> Result<usize, String> {
+        let bytes = take(data, 4)?;
+        Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize)
+    }
+
+    let mut entries = vec![];
+    while !data.is_empty() {
+        let key_len = take_len(&mut data)?;
+        let key = take(&mut data, key_len)?.to_vec();
+        let value_len = take_len(&mut data)?;
+        let value = take(&mut data, value_len)?.to_vec();
+        entries.push((key, value));
+    }
+    Ok(entries)
+}
+
+fn read_json<T: for<'de> Deserialize<'de>>(path: &Path) -> Result<T, String> {
+    let bytes = fs::read(path).map_err(|e| format!("Unable to read {}: {e:?}", path.display()))?;
+    serde_json::from_slice(&bytes).map_err(|e| format!("Unable to parse {}: {e:?}", path.display()))
+}
+
+/// Write `value` to `path` atomically, by writing to a temporary file and renaming it.
+fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<(), String> {
+    let bytes =
+        serde_json::to_vec_pretty(value).map_err(|e| format!("Unable to serialize: {e:?}"))?;
+    let tmp_path = path.with_extension("tmp");
+    fs::write(&tmp_path, bytes)
+        .and_then(|()| fs::rename(&tmp_path, path))
+        .map_err(|e| format!("Unable to write {}: {e:?}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use beacon_chain::test_utils::{
+        AttestationStrategy, BeaconChainHarness, BlockStrategy, DiskHarnessType,
+    };
+    use std::sync::Arc;
+    use store::metadata::{SPLIT_KEY, STATE_UPPER_LIMIT_NO_RETAIN};
+    use store::{Split, StoreConfig};
+    use tempfile::tempdir;
+    use types::MinimalEthSpec;
+
+    type E = MinimalEthSpec;
+
+    fn client_config(data_dir: &Path) -> ClientConfig {
+        let mut client_config = ClientConfig::default();
+        client_config.set_data_dir(data_dir.to_path_buf());
+        client_config
+    }
+
+    fn open_db(
+        client_config: &ClientConfig,
+        spec: &ChainSpec,
+        log: &Logger,
+    ) -> Arc<HotColdDB<E, LevelDB<E>, LevelDB<E>>> {
+        HotColdDB::open(
+            &client_config.create_db_path().unwrap(),
+            &client_config.create_freezer_db_path().unwrap(),
+            &client_config.create_blobs_db_path().unwrap(),
+            |_, _, _| Ok(()),
+            StoreConfig::default(),
+            spec.clone(),
+            log.clone(),
+        )
+        .unwrap()
+    }
+
+    /// Every exported column of `db`, keyed by `db/column`.
+    fn exported_entries(
+        db: &HotColdDB<E, LevelDB<E>, LevelDB<E>>,
+    ) -> Vec<(String, Vec<(Vec<u8>, Vec<u8>)>)> {
+        let sub_dbs = [
+            (SnapshotDb::Hot, &db.hot_db),
+            (SnapshotDb::Freezer, &db.cold_db),
+            (SnapshotDb::Blobs, &db.blobs_db),
+        ];
+        sub_dbs
+            .into_iter()
+            .flat_map(|(snapshot_db, sub_db)| {
+                DBColumn::iter()
+                    .filter(move |column| {
+                        snapshot_db != SnapshotDb::Hot || !EXCLUDED_HOT_COLUMNS.contains(column)
+                    })
+                    .map(move |column| {
+                        let entries = sub_db
+                            .iter_raw_entries(column, &[])
+                            .collect::<Result<Vec<_>, _>>()
+                            .unwrap();
+                        (
+                            format!("{}/{}", snapshot_db.as_str(), column.as_str()),
+                            entries,
+                        )
+                    })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn entries_roundtrip() {
+        let entries = vec![
+            (vec![0; 32], vec![1, 2, 3]),
+            (vec![1; 8], vec![]),
+            (vec![], vec![4; 1000]),
+        ];
+        let mut buffer = vec![];
+        for (key, value) in &entries {
+            encode_entry(&mut buffer, key, value);
+        }
+        assert_eq!(decode_entries(&buffer).unwrap(), entries);
+
+        // A truncated chunk is rejected rather than silently dropping the final entry.
+        assert!(decode_entries(&buffer[..buffer.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn export_import_roundtrip() {
+        let spec = E::default_spec();
+        let log = Logger::root(slog::Discard, slog::o!());
+        let source_dir = tempdir().unwrap();
+        let target_dir = tempdir().unwrap();
+        let output = source_dir.path().join("snapshot.tar.zst");
+
+        let source_config = client_config(&source_dir.path().join("source"));
+        let expected = {
+            let db = open_db(&source_config, &spec, &log);
+            let split = Split {
+                slot: Slot::new(64),
+                state_root: Hash256::repeat_byte(0xaa),
+                block_root: Hash256::repeat_byte(0xbb),
+            };
+            db.hot_db.put_sync(&SPLIT_KEY, &split).unwrap();
+            let anchor = AnchorInfo {
+                anchor_slot: Slot::new(64),
+                oldest_block_slot: Slot::new(32),
+                oldest_block_parent: Hash256::repeat_byte(0xcc),
+                state_upper_limit: Slot::new(128),
+                state_lower_limit: Slot::new(0),
+            };
+            db.compare_and_set_anchor_info_with_write(None, Some(anchor))
+                .unwrap();
+
+            for i in 0..10u8 {
+                let value = vec![i; 1024];
+                let key = [i; 32];
+                db.hot_db
+                    .put_bytes(DBColumn::BeaconBlock.as_str(), &key, &value)
+                    .unwrap();
+                db.cold_db
+                    .put_bytes(DBColumn::BeaconState.as_str(), &key, &[i])
+                    .unwrap();
+                db.blobs_db
+                    .put_bytes(DBColumn::BeaconBlob.as_str(), &key, &[i, i])
+                    .unwrap();
+            }
+            // Node-specific data is not exported.
+            db.hot_db
+                .put_bytes(DBColumn::OpPool.as_str(), &[0; 32], &[1])
+                .unwrap();
+            exported_entries(&db)
+        };
+        // Reopen the database, so that the split and anchor are loaded from disk as they are
+        // during an export.
+        assert!(open_db(&source_config, &spec, &log)
+            .get_anchor_info()
+            .is_some());
+
+        export_snapshot::<E>(
+            ExportConfig {
+                output: output.clone(),
+                historic_states: true,
+            },
+            source_config,
+            &spec,
+            log.clone(),
+        )
+        .unwrap();
+
+        let target_config = client_config(target_dir.path());
+        import_snapshot::<E>(
+            ImportConfig { input: output },
+            target_config.clone(),
+            &spec,
+            || panic!("genesis state is not required when importing historic states"),
+            log.clone(),
+        )
+        .unwrap();
+        assert!(!target_config
+            .get_data_dir()
+            .join(IMPORT_PROGRESS_FILE)
+            .exists());
+
+        let db = open_db(&target_config, &spec, &log);
+        assert_eq!(exported_entries(&db), expected);
+        assert!(db
+            .hot_db
+            .iter_raw_entries(DBColumn::OpPool, &[])
+            .next()
+            .is_none());
+    }
+
+    fn column_keys(sub_db: &LevelDB<E>, column: DBColumn) -> Vec<Vec<u8>> {
+        sub_db
+            .iter_column_keys::<Vec<u8>>(column)
+            .collect::<Result<_, _>>()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn export_import_without_historic_states() {
+        let spec = E::default_spec();
+        let log = Logger::root(slog::Discard, slog::o!());
+        let source_dir = tempdir().unwrap();
+        let target_dir = tempdir().unwrap();
+        let output = source_dir.path().join("snapshot.tar.zst");
+
+        let source_config = client_config(&source_dir.path().join("source"));
+        let db = open_db(&source_config, &spec, &log);
+        let harness = BeaconChainHarness::<DiskHarnessType<E>>::builder(E::default())
+            .spec(spec.clone())
+            .deterministic_keypairs(32)
+            .fresh_disk_store(db.clone())
+            .build();
+        let genesis_state = harness.get_current_state();
+        harness.advance_slot();
+        harness
+            .extend_chain(
+                5 * E::slots_per_epoch() as usize,
+                BlockStrategy::OnCanonicalHead,
+                AttestationStrategy::AllValidators,
+            )
+            .await;
+        let split = db.get_split_info();
+        assert!(split.slot > 0);
+
+        // The payload of a finalized block is not exported, nor is the Eth1 cache, whereas the
+        // payload of the head block is.
+        let head_block_root = harness.head_block_root();
+        let finalized_block_root = harness.chain.genesis_block_root;
+        for block_root in [head_block_root, finalized_block_root] {
+            db.hot_db
+                .put_bytes(DBColumn::ExecPayload.as_str(), block_root.as_bytes(), &[1])
+                .unwrap();
+        }
+        db.hot_db
+            .put_bytes(DBColumn::Eth1Cache.as_str(), &[0; 32], &[1])
+            .unwrap();
+
+        harness.chain.persist_head_and_fork_choice().unwrap();
+        let slot_clock = harness.chain.slot_clock.clone();
+        drop(harness);
+        drop(db);
+
+        export_snapshot::<E>(
+            ExportConfig {
+                output: output.clone(),
+                historic_states: false,
+            },
+            source_config,
+            &spec,
+            log.clone(),
+        )
+        .unwrap();
+
+        let target_config = client_config(target_dir.path());
+        import_snapshot::<E>(
+            ImportConfig { input: output },
+            target_config.clone(),
+            &spec,
+            move || Ok(genesis_state),
+            log.clone(),
+        )
+        .unwrap();
+
+        let db = open_db(&target_config, &spec, &log);
+        assert_eq!(db.get_split_info(), split);
+        assert_eq!(
+            db.get_anchor_info().unwrap().state_upper_limit,
+            STATE_UPPER_LIMIT_NO_RETAIN
+        );
+        assert_eq!(
+            column_keys(&db.hot_db, DBColumn::ExecPayload),
+            vec![head_block_root.as_bytes().to_vec()]
+        );
+        assert!(column_keys(&db.hot_db, DBColumn::Eth1Cache).is_empty());
+        assert!(column_keys(&db.cold_db, DBColumn::BeaconState).is_empty());
+
+        // The imported database can be resumed from.
+        let harness = BeaconChainHarness::<DiskHarnessType<E>>::builder(E::default())
+            .spec(spec)
+            .deterministic_keypairs(32)
+            .resumed_disk_store(db)
+            .testing_slot_clock(slot_clock)
+            .build();
+        assert_eq!(harness.head_block_root(), head_block_root);
+    }
+}
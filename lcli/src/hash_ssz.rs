@@ -0,0 +1,262 @@
+//! # Hash SSZ
+//!
+//! Compute the tree hash root of an SSZ-encoded object from a file.
+//!
+//! With `--type autodetect` (the default) the bytes are inspected to determine which kind of
+//! object they represent and which fork's container to decode them with, so the exact type does
+//! not need to be known ahead of time. States, blocks and blob sidecars are supported.
+//!
+//! ## Examples
+//!
+//! ```ignore
+//! lcli hash-ssz /tmp/pre-state-0x6c69.ssz
+//! lcli hash-ssz --type SignedBeaconBlock /tmp/block-0x6c69.ssz
+//! ```
+use clap::ArgMatches;
+use clap_utils::parse_required;
+use eth2_network_config::Eth2NetworkConfig;
+use log::info;
+use snap::raw::Decoder;
+use ssz::Decode;
+use std::fs;
+use std::path::PathBuf;
+use std::str::FromStr;
+use tree_hash::TreeHash;
+use types::{
+    BeaconBlock, BeaconState, BlobSidecar, ChainSpec, EthSpec, ForkName, Hash256, SignedBeaconBlock,
+};
+
+/// The byte range of `state.fork.current_version` in an SSZ-encoded `BeaconState`.
+///
+/// It is preceded by `genesis_time`, `genesis_validators_root`, `slot` and
+/// `fork.previous_version`, all of which are fixed-length in every fork.
+const STATE_FORK_VERSION_START: usize = 8 + 32 + 8 + 4;
+const STATE_FORK_VERSION_END: usize = STATE_FORK_VERSION_START + 4;
+
+enum SszType {
+    Autodetect,
+    BeaconState,
+    SignedBeaconBlock,
+    BeaconBlock,
+    BlobSidecar,
+}
+
+impl FromStr for SszType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "autodetect" => Ok(Self::Autodetect),
+            "BeaconState" => Ok(Self::BeaconState),
+            "SignedBeaconBlock" => Ok(Self::SignedBeaconBlock),
+            "BeaconBlock" => Ok(Self::BeaconBlock),
+            "BlobSidecar" => Ok(Self::BlobSidecar),
+            other => Err(format!("Unknown type: {}", other)),
+        }
+    }
+}
+
+/// An object decoded from SSZ bytes.
+enum Decoded<E: EthSpec> {
+    BeaconState(BeaconState<E>),
+    SignedBeaconBlock(SignedBeaconBlock<E>),
+    BeaconBlock(BeaconBlock<E>),
+    BlobSidecar(BlobSidecar<E>),
+}
+
+impl<E: EthSpec> Decoded<E> {
+    fn description(&self, spec: &ChainSpec) -> String {
+        match self {
+            Self::BeaconState(state) => format!("BeaconState ({})", state.fork_name_unchecked()),
+            Self::SignedBeaconBlock(block) => {
+                format!("SignedBeaconBlock ({})", block.fork_name_unchecked())
+            }
+            Self::BeaconBlock(block) => format!("BeaconBlock ({})", block.fork_name_unchecked()),
+            Self::BlobSidecar(blob) => {
+                format!("BlobSidecar ({})", spec.fork_name_at_slot::<E>(blob.slot()))
+            }
+        }
+    }
+
+    /// Returns the root of the object.
+    ///
+    /// For a `SignedBeaconBlock` this is the block root (i.e. the root of the message), as it is
+    /// the value that is referenced elsewhere in the protocol.
+    fn root(self) -> Result<Hash256, String> {
+        match self {
+            Self::BeaconState(mut state) => state
+                .update_tree_hash_cache()
+                .map_err(|e| format!("Error computing state root: {:?}", e)),
+            Self::SignedBeaconBlock(block) => Ok(block.canonical_root()),
+            Self::BeaconBlock(block) => Ok(block.tree_hash_root()),
+            Self::BlobSidecar(blob) => Ok(blob.tree_hash_root()),
+        }
+    }
+}
+
+pub fn run<E: EthSpec>(
+    network_config: Eth2NetworkConfig,
+    matches: &ArgMatches,
+) -> Result<(), String> {
+    let ssz_type: SszType = parse_required(matches, "type")?;
+    let path: PathBuf = parse_required(matches, "ssz-file")?;
+
+    let bytes = fs::read(&path).map_err(|e| format!("Unable to read {:?}: {:?}", path, e))?;
+    let bytes = if path.to_string_lossy().ends_with("ssz_snappy") {
+        Decoder::new()
+            .decompress_vec(&bytes)
+            .map_err(|e| format!("Unable to decompress {:?}: {:?}", path, e))?
+    } else {
+        bytes
+    };
+
+    let spec = &network_config.chain_spec::<E>()?;
+    info!(
+        "Using {} network config ({} preset)",
+        spec.config_name.as_deref().unwrap_or("unknown"),
+        E::spec_name()
+    );
+
+    let decode_err = |e| format!("SSZ decode failed: {:?}", e);
+    let decoded = match ssz_type {
+        SszType::Autodetect => autodetect::<E>(&bytes, spec)?,
+        SszType::BeaconState => {
+            Decoded::BeaconState(BeaconState::from_ssz_bytes(&bytes, spec).map_err(decode_err)?)
+        }
+        SszType::SignedBeaconBlock => Decoded::SignedBeaconBlock(
+            SignedBeaconBlock::from_ssz_bytes(&bytes, spec).map_err(decode_err)?,
+        ),
+        SszType::BeaconBlock => {
+            Decoded::BeaconBlock(BeaconBlock::from_ssz_bytes(&bytes, spec).map_err(decode_err)?)
+        }
+        SszType::BlobSidecar => {
+            Decoded::BlobSidecar(BlobSidecar::from_ssz_bytes(&bytes).map_err(decode_err)?)
+        }
+    };
+
+    info!("Type: {}", decoded.description(spec));
+    let root = decoded.root()?;
+    info!("Root is {:?}", root);
+
+    Ok(())
+}
+
+/// Attempt to decode `bytes` as each of the supported types in turn, returning the first success.
+///
+/// States are decoded using the fork version embedded in the state itself, falling back to the
+/// fork dictated by the state's slot if the version is unknown to `spec`. Blocks carry no fork
+/// version, so every variant is attempted (newest first).
+fn autodetect<E: EthSpec>(bytes: &[u8], spec: &ChainSpec) -> Result<Decoded<E>, String> {
+    let state_fork = bytes
+        .get(STATE_FORK_VERSION_START..STATE_FORK_VERSION_END)
+        .and_then(|version| {
+            ForkName::list_all()
+                .into_iter()
+                .rev()
+                .find(|fork_name| spec.fork_version_for_name(*fork_name) == version)
+        });
+    let state = match state_fork {
+        Some(fork_name) => BeaconState::from_ssz_bytes_for_fork(bytes, fork_name),
+        None => BeaconState::from_ssz_bytes(bytes, spec),
+    };
+    if let Ok(state) = state {
+        return Ok(Decoded::BeaconState(state));
+    }
+
+    if let Ok(block) = SignedBeaconBlock::any_from_ssz_bytes(bytes) {
+        return Ok(Decoded::SignedBeaconBlock(block));
+    }
+
+    if let Ok(block) = BeaconBlock::any_from_ssz_bytes(bytes) {
+        return Ok(Decoded::BeaconBlock(block));
+    }
+
+    if let Ok(blob) = BlobSidecar::from_ssz_bytes(bytes) {
+        return Ok(Decoded::BlobSidecar(blob));
+    }
+
+    Err(
+        "Unable to decode bytes as a BeaconState, SignedBeaconBlock, BeaconBlock or \
+         BlobSidecar for any known fork"
+            .into(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use beacon_chain::test_utils::{AttestationStrategy, BeaconChainHarness, BlockStrategy};
+    use beacon_chain::WhenSlotSkipped;
+    use ssz::Encode;
+    use types::{Epoch, MinimalEthSpec, Slot};
+
+    type E = MinimalEthSpec;
+
+    const VALIDATOR_COUNT: usize = 32;
+
+    /// Hashes a state, block and blob sidecar from either side of the Electra fork and checks
+    /// that each is decoded with the right fork and has the root recorded by the chain.
+    #[tokio::test]
+    async fn autodetect_fork_and_root() {
+        let slots_per_epoch = E::slots_per_epoch();
+        let mut spec = ForkName::Deneb.make_genesis_spec(E::default_spec());
+        spec.electra_fork_epoch = Some(Epoch::new(1));
+
+        let harness = BeaconChainHarness::builder(E::default())
+            .spec(spec.clone())
+            .deterministic_keypairs(VALIDATOR_COUNT)
+            .fresh_ephemeral_store()
+            .mock_execution_layer()
+            .build();
+        harness.advance_slot();
+        harness
+            .extend_chain(
+                2 * slots_per_epoch as usize,
+                BlockStrategy::OnCanonicalHead,
+                AttestationStrategy::AllValidators,
+            )
+            .await;
+
+        for (slot, fork_name) in [
+            (Slot::new(slots_per_epoch - 1), ForkName::Deneb),
+            (Slot::new(slots_per_epoch + 1), ForkName::Electra),
+        ] {
+            let block_root = harness
+                .chain
+                .block_root_at_slot(slot, WhenSlotSkipped::None)
+                .unwrap()
+                .unwrap();
+            let block = harness
+                .chain
+                .store
+                .get_full_block(&block_root)
+                .unwrap()
+                .unwrap();
+            let state_root = block.state_root();
+            let state = harness
+                .chain
+                .get_state(&state_root, Some(slot))
+                .unwrap()
+                .unwrap();
+            let mut blob = BlobSidecar::<E>::empty();
+            blob.signed_block_header = block.signed_block_header();
+            let blob_root = blob.tree_hash_root();
+            let (message, _) = block.clone().deconstruct();
+
+            let cases = [
+                ("BeaconState", state.as_ssz_bytes(), state_root),
+                ("SignedBeaconBlock", block.as_ssz_bytes(), block_root),
+                ("BeaconBlock", message.as_ssz_bytes(), block_root),
+                ("BlobSidecar", blob.as_ssz_bytes(), blob_root),
+            ];
+            for (type_name, bytes, root) in cases {
+                let decoded = autodetect::<E>(&bytes, &spec).unwrap();
+                assert_eq!(
+                    decoded.description(&spec),
+                    format!("{} ({})", type_name, fork_name)
+                );
+                assert_eq!(decoded.root().unwrap(), root, "{} at {}", type_name, slot);
+            }
+        }
+    }
+}
@@ -1,13 +1,18 @@
 mod block_root;
 mod check_deposit_data;
 mod generate_bootnode_enr;
+mod hash_ssz;
 mod indexed_attestations;
 mod mnemonic_validators;
 mod mock_el;
+mod mutate_genesis;
+mod new_testnet;
 mod parse_ssz;
+mod replay_fork_choice;
 mod skip_slots;
 mod state_root;
 mod transition_blocks;
+mod transition_rewards;
 
 use clap::{Arg, ArgAction, ArgMatches, Command};
 use clap_utils::{parse_optional, FLAG_HEADER};
@@ -177,6 +182,15 @@ fn main() {
                         .help("Path to output the block, useful when used with --beacon-url.")
                         .display_order(0)
                 )
+                .arg(
+                    Arg::new("rewards-output")
+                        .long("rewards-output")
+                        .value_name("PATH")
+                        .action(ArgAction::Set)
+                        .help("Path to output a JSON breakdown of the proposer, sync committee \
+                            and attester rewards earned during the transition.")
+                        .display_order(0)
+                )
                 .arg(
                     Arg::new("beacon-url")
                         .long("beacon-url")
@@ -262,6 +276,35 @@ fn main() {
                         .display_order(0)
                 )
         )
+        .subcommand(
+            Command::new("hash-ssz")
+                .about("Computes the tree hash root of SSZ-encoded data from a file")
+                .arg(
+                    Arg::new("type")
+                        .long("type")
+                        .value_name("TYPE")
+                        .action(ArgAction::Set)
+                        .default_value("autodetect")
+                        .value_parser([
+                            "autodetect",
+                            "BeaconState",
+                            "SignedBeaconBlock",
+                            "BeaconBlock",
+                            "BlobSidecar",
+                        ])
+                        .help("Type to decode. With \"autodetect\" the type and fork are \
+                            inferred from the bytes.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("ssz-file")
+                        .value_name("FILE")
+                        .action(ArgAction::Set)
+                        .required(true)
+                        .help("Path to SSZ bytes")
+                        .display_order(0)
+                )
+        )
         .subcommand(
             Command::new("check-deposit-data")
                 .about("Checks the integrity of some deposit data.")
@@ -443,6 +486,79 @@ fn main() {
                         .display_order(0)
                 )
         )
+        .subcommand(
+            Command::new("replay-fork-choice")
+                .about("Re-runs get_head on a fork choice snapshot, optionally perturbing its inputs.")
+                .arg(
+                    Arg::new("snapshot-path")
+                        .long("snapshot-path")
+                        .value_name("PATH")
+                        .action(ArgAction::Set)
+                        .conflicts_with("beacon-url")
+                        .help("Path to load a fork choice snapshot from as SSZ.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("beacon-url")
+                        .long("beacon-url")
+                        .value_name("URL")
+                        .action(ArgAction::Set)
+                        .help("URL to a Lighthouse beacon node to download a fork choice snapshot from.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("output-path")
+                        .long("output-path")
+                        .value_name("PATH")
+                        .action(ArgAction::Set)
+                        .help("Path to save the unperturbed snapshot to as SSZ.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("current-slot")
+                        .long("current-slot")
+                        .value_name("SLOT")
+                        .action(ArgAction::Set)
+                        .help("Override the current slot of the snapshot.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("proposer-boost-root")
+                        .long("proposer-boost-root")
+                        .value_name("ROOT")
+                        .action(ArgAction::Set)
+                        .help("Override the proposer boost root. Use the zero hash to remove the \
+                            proposer boost.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("proposer-score-boost")
+                        .long("proposer-score-boost")
+                        .value_name("PERCENTAGE")
+                        .action(ArgAction::Set)
+                        .help("Override the proposer score boost, as a percentage of the \
+                            committee weight.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("equivocating-indices")
+                        .long("equivocating-indices")
+                        .value_name("INDICES")
+                        .action(ArgAction::Set)
+                        .help("Comma-separated list of additional validator indices to treat as \
+                            equivocating.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("vote")
+                        .long("vote")
+                        .value_name("INDEX:ROOT")
+                        .action(ArgAction::Append)
+                        .help("Replace the latest vote of validator INDEX with a vote for block \
+                            ROOT. May be supplied multiple times.")
+                        .display_order(0)
+                )
+        )
         .subcommand(
             Command::new("state-root")
                 .about("Computes the state root of some state.")
@@ -552,6 +668,95 @@ fn main() {
                         .display_order(0)
                 )
         )
+        .subcommand(
+            Command::new("new-testnet")
+                .about("Produces a testnet directory for a post-merge network, deriving the \
+                        genesis state from an execution layer genesis file and validator keys \
+                        from a mnemonic. The directory is written to --testnet-dir. For testing \
+                        purposes only, DO NOT USE IN PRODUCTION!")
+                .arg(
+                    Arg::new("interop-from-el")
+                        .long("interop-from-el")
+                        .value_name("GENESIS_JSON")
+                        .action(ArgAction::Set)
+                        .required(true)
+                        .help("Path to the genesis.json of the execution layer. The execution \
+                            payload header, genesis time, fork schedule and deposit contract \
+                            address (if given) are derived from it.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("validator-count")
+                        .long("validator-count")
+                        .value_name("INTEGER")
+                        .action(ArgAction::Set)
+                        .required(true)
+                        .help("The number of validators in the genesis state.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("mnemonic-phrase")
+                        .long("mnemonic-phrase")
+                        .value_name("MNEMONIC_PHRASE")
+                        .action(ArgAction::Set)
+                        .required(true)
+                        .help("The mnemonic from which the validator keys are derived.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("force")
+                        .long("force")
+                        .short('f')
+                        .action(ArgAction::SetTrue)
+                        .help_heading(FLAG_HEADER)
+                        .help("Overwrites any existing testnet directory.")
+                        .display_order(0)
+                )
+        )
+        .subcommand(
+            Command::new("mutate-genesis")
+                .about("Applies a script of mutations to a genesis or checkpoint state, e.g. to \
+                        produce the genesis state of a shadow fork. Roots derived from the \
+                        mutated fields are recomputed. For testing purposes only, DO NOT USE IN \
+                        PRODUCTION!")
+                .arg(
+                    Arg::new("state-path")
+                        .long("state-path")
+                        .value_name("PATH")
+                        .action(ArgAction::Set)
+                        .required(true)
+                        .help("Path to load the BeaconState from as SSZ.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("mutations")
+                        .long("mutations")
+                        .value_name("PATH")
+                        .action(ArgAction::Set)
+                        .required(true)
+                        .help("Path to a YAML list of mutations to apply in order. Supported \
+                            mutations are set_balance, activate, exit and set_fork_epoch.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("output-path")
+                        .long("output-path")
+                        .value_name("PATH")
+                        .action(ArgAction::Set)
+                        .required(true)
+                        .help("Path to write the mutated BeaconState as SSZ.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("config-output-path")
+                        .long("config-output-path")
+                        .value_name("PATH")
+                        .action(ArgAction::Set)
+                        .help("Path to write the network config, including any changed fork \
+                            epochs, as YAML.")
+                        .display_order(0)
+                )
+        )
         .get_matches();
 
     let result = matches
@@ -636,6 +841,11 @@ fn run<E: EthSpec>(env_builder: EnvironmentBuilder<E>, matches: &ArgMatches) ->
             run_parse_ssz::<E>(network_config, matches)
                 .map_err(|e| format!("Failed to pretty print hex: {}", e))
         }
+        Some(("hash-ssz", matches)) => {
+            let network_config = get_network_config()?;
+            hash_ssz::run::<E>(network_config, matches)
+                .map_err(|e| format!("Failed to run hash-ssz command: {}", e))
+        }
         Some(("check-deposit-data", matches)) => check_deposit_data::run(matches)
             .map_err(|e| format!("Failed to run check-deposit-data command: {}", e)),
         Some(("generate-bootnode-enr", matches)) => generate_bootnode_enr::run::<E>(matches)
@@ -649,6 +859,11 @@ fn run<E: EthSpec>(env_builder: EnvironmentBuilder<E>, matches: &ArgMatches) ->
             block_root::run::<E>(env, network_config, matches)
                 .map_err(|e| format!("Failed to run block-root command: {}", e))
         }
+        Some(("replay-fork-choice", matches)) => {
+            let network_config = get_network_config()?;
+            replay_fork_choice::run::<E>(env, network_config, matches)
+                .map_err(|e| format!("Failed to run replay-fork-choice command: {}", e))
+        }
         Some(("state-root", matches)) => {
             let network_config = get_network_config()?;
             state_root::run::<E>(env, network_config, matches)
@@ -656,6 +871,16 @@ fn run<E: EthSpec>(env_builder: EnvironmentBuilder<E>, matches: &ArgMatches) ->
         }
         Some(("mock-el", matches)) => mock_el::run::<E>(env, matches)
             .map_err(|e| format!("Failed to run mock-el command: {}", e)),
+        Some(("new-testnet", matches)) => {
+            let testnet_dir = testnet_dir.ok_or("new-testnet requires --testnet-dir")?;
+            new_testnet::run::<E>(testnet_dir, matches)
+                .map_err(|e| format!("Failed to run new-testnet command: {}", e))
+        }
+        Some(("mutate-genesis", matches)) => {
+            let network_config = get_network_config()?;
+            mutate_genesis::run::<E>(network_config, matches)
+                .map_err(|e| format!("Failed to run mutate-genesis command: {}", e))
+        }
         Some((other, _)) => Err(format!("Unknown subcommand {}. See --help.", other)),
         _ => Err("No subcommand provided. See --help.".to_string()),
     }
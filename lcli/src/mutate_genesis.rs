@@ -0,0 +1,391 @@
+//! # Mutate-Genesis
+//!
+//! Use this tool to produce test states (e.g. for shadow forks) by applying a script of mutations
+//! to a genesis or checkpoint `BeaconState`.
+//!
+//! The mutations are read from a YAML file containing a list, applied in order:
+//!
+//! ```yaml
+//! - set_balance:
+//!     validators: "0..64"
+//!     balance: 64000000000
+//! - activate:
+//!     validators: "64..128,130"
+//! - exit:
+//!     validators: 7
+//! - set_fork_epoch:
+//!     fork: electra
+//!     epoch: 10
+//! ```
+//!
+//! Validators are selected by index, using a single index or a comma-separated list of indices and
+//! half-open ranges. Fork epochs are changed in the network config, which can be written to
+//! `--config-output-path` for use alongside the new state.
+//!
+//! Caches and roots derived from the mutated fields are recomputed. For a genesis state this
+//! includes the `genesis_validators_root` and sync committees. If the latest block header belongs
+//! to the state's slot its state root is cleared, so that the block root is derived from the
+//! mutated state as it is at genesis.
+//!
+//! ## Example
+//!
+//! ```ignore
+//! lcli mutate-genesis \
+//!     --testnet-dir ~/shadowfork \
+//!     --state-path ~/genesis.ssz \
+//!     --mutations ~/mutations.yaml \
+//!     --output-path ~/shadowfork/genesis.ssz \
+//!     --config-output-path ~/shadowfork/config.yaml
+//! ```
+use crate::transition_blocks::load_from_ssz_with;
+use clap::ArgMatches;
+use clap_utils::{parse_optional, parse_required};
+use eth2_network_config::Eth2NetworkConfig;
+use log::info;
+use serde::Deserialize;
+use ssz::Encode;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::PathBuf;
+use std::sync::Arc;
+use types::{BeaconState, ChainSpec, Config, Epoch, EthSpec, ForkName, Hash256};
+
+/// A single change to the state or the network config.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+enum Mutation {
+    /// Set the balance of validators, and their effective balance to match.
+    SetBalance {
+        validators: ValidatorIndices,
+        #[serde(with = "serde_utils::quoted_u64")]
+        balance: u64,
+    },
+    /// Make validators active from the current epoch.
+    Activate { validators: ValidatorIndices },
+    /// Exit validators at the current epoch.
+    Exit { validators: ValidatorIndices },
+    /// Schedule a fork at `epoch`, or unschedule it if `epoch` is omitted.
+    SetForkEpoch {
+        fork: ForkName,
+        #[serde(default)]
+        epoch: Option<Epoch>,
+    },
+}
+
+/// A set of validator indices such as `7` or `"0..64,70"`.
+#[derive(Debug, Deserialize)]
+#[serde(try_from = "RawValidatorIndices")]
+struct ValidatorIndices(Vec<usize>);
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawValidatorIndices {
+    Index(usize),
+    List(String),
+}
+
+impl TryFrom<RawValidatorIndices> for ValidatorIndices {
+    type Error = String;
+
+    fn try_from(raw: RawValidatorIndices) -> Result<Self, Self::Error> {
+        let list = match raw {
+            RawValidatorIndices::Index(index) => return Ok(Self(vec![index])),
+            RawValidatorIndices::List(list) => list,
+        };
+        let parse = |s: &str| {
+            s.trim()
+                .parse::<usize>()
+                .map_err(|e| format!("invalid validator index {:?}: {:?}", s, e))
+        };
+
+        let mut indices = vec![];
+        for item in list.split(',') {
+            if let Some((start, end)) = item.split_once("..") {
+                indices.extend(parse(start)?..parse(end)?);
+            } else {
+                indices.push(parse(item)?);
+            }
+        }
+        Ok(Self(indices))
+    }
+}
+
+pub fn run<E: EthSpec>(
+    network_config: Eth2NetworkConfig,
+    matches: &ArgMatches,
+) -> Result<(), String> {
+    let state_path: PathBuf = parse_required(matches, "state-path")?;
+    let mutations_path: PathBuf = parse_required(matches, "mutations")?;
+    let output_path: PathBuf = parse_required(matches, "output-path")?;
+    let config_output_path: Option<PathBuf> = parse_optional(matches, "config-output-path")?;
+
+    let mut spec = network_config.chain_spec::<E>()?;
+
+    info!("Using {} spec", E::spec_name());
+    info!("State path: {:?}", state_path);
+    let mut state = load_from_ssz_with(&state_path, &spec, BeaconState::from_ssz_bytes)?;
+
+    let mutations_file = File::open(&mutations_path)
+        .map_err(|e| format!("Unable to open {:?}: {:?}", mutations_path, e))?;
+    let mutations = parse_mutations(mutations_file)?;
+
+    let state_root = mutate_state(&mut state, &mut spec, mutations)?;
+    let mut latest_block_header = state.latest_block_header().clone();
+    if latest_block_header.state_root.is_zero() {
+        latest_block_header.state_root = state_root;
+    }
+    info!("State root: {:?}", state_root);
+    info!("Block root: {:?}", latest_block_header.canonical_root());
+
+    let mut output_file =
+        File::create(&output_path).map_err(|e| format!("Unable to create output file: {:?}", e))?;
+    output_file
+        .write_all(&state.as_ssz_bytes())
+        .map_err(|e| format!("Unable to write to output file: {:?}", e))?;
+
+    if let Some(config_output_path) = config_output_path {
+        let config = Config::from_chain_spec::<E>(&spec);
+        config.validate()?;
+        let config_file = File::create(&config_output_path)
+            .map_err(|e| format!("Unable to create config file: {:?}", e))?;
+        serde_yaml::to_writer(config_file, &config)
+            .map_err(|e| format!("Unable to write config file: {:?}", e))?;
+    }
+
+    Ok(())
+}
+
+fn parse_mutations(reader: impl Read) -> Result<Vec<Mutation>, String> {
+    serde_yaml::from_reader(reader).map_err(|e| format!("Unable to parse mutations: {:?}", e))
+}
+
+/// Apply `mutations` in order, returning the new state root.
+fn mutate_state<E: EthSpec>(
+    state: &mut BeaconState<E>,
+    spec: &mut ChainSpec,
+    mutations: Vec<Mutation>,
+) -> Result<Hash256, String> {
+    for mutation in mutations {
+        info!("Applying {:?}", mutation);
+        apply_mutation(state, spec, mutation)?;
+    }
+
+    update_derived_fields(state, spec)
+}
+
+fn apply_mutation<E: EthSpec>(
+    state: &mut BeaconState<E>,
+    spec: &mut ChainSpec,
+    mutation: Mutation,
+) -> Result<(), String> {
+    let current_epoch = state.current_epoch();
+    let fork_name = state.fork_name_unchecked();
+    let num_validators = state.validators().len();
+    let check_index = |index: usize| {
+        if index < num_validators {
+            Ok(index)
+        } else {
+            Err(format!(
+                "validator index {} out of bounds, the state has {} validators",
+                index, num_validators
+            ))
+        }
+    };
+
+    match mutation {
+        Mutation::SetBalance {
+            validators,
+            balance,
+        } => {
+            for index in validators.0 {
+                let index = check_index(index)?;
+                *state
+                    .get_balance_mut(index)
+                    .map_err(|e| format!("Unable to set balance: {:?}", e))? = balance;
+                let validator = state
+                    .get_validator_mut(index)
+                    .map_err(|e| format!("Unable to set balance: {:?}", e))?;
+                let max_effective_balance =
+                    validator.get_validator_max_effective_balance(spec, fork_name);
+                validator.effective_balance = std::cmp::min(
+                    balance - balance % spec.effective_balance_increment,
+                    max_effective_balance,
+                );
+            }
+        }
+        Mutation::Activate { validators } => {
+            for index in validators.0 {
+                let index = check_index(index)?;
+                let validator = state
+                    .get_validator_mut(index)
+                    .map_err(|e| format!("Unable to activate validator: {:?}", e))?;
+                validator.activation_eligibility_epoch = current_epoch;
+                validator.activation_epoch = current_epoch;
+                validator.exit_epoch = spec.far_future_epoch;
+                validator.withdrawable_epoch = spec.far_future_epoch;
+            }
+        }
+        Mutation::Exit { validators } => {
+            for index in validators.0 {
+                let index = check_index(index)?;
+                let validator = state
+                    .get_validator_mut(index)
+                    .map_err(|e| format!("Unable to exit validator: {:?}", e))?;
+                validator.exit_epoch = current_epoch;
+                validator.withdrawable_epoch =
+                    current_epoch + spec.min_validator_withdrawability_delay;
+            }
+        }
+        Mutation::SetForkEpoch { fork, epoch } => {
+            let fork_epoch = match fork {
+                ForkName::Base => return Err("the base fork is always at genesis".into()),
+                ForkName::Altair => &mut spec.altair_fork_epoch,
+                ForkName::Bellatrix => &mut spec.bellatrix_fork_epoch,
+                ForkName::Capella => &mut spec.capella_fork_epoch,
+                ForkName::Deneb => &mut spec.deneb_fork_epoch,
+                ForkName::Electra => &mut spec.electra_fork_epoch,
+            };
+            *fork_epoch = epoch;
+        }
+    }
+    Ok(())
+}
+
+/// Recompute the fields of `state` which are derived from the mutated fields, returning the new
+/// state root.
+fn update_derived_fields<E: EthSpec>(
+    state: &mut BeaconState<E>,
+    spec: &ChainSpec,
+) -> Result<Hash256, String> {
+    let current_epoch = state.current_epoch();
+    let expected_fork = spec.fork_name_at_epoch(current_epoch);
+    if state.fork_name_unchecked() != expected_fork {
+        return Err(format!(
+            "the state is a {} state but the fork schedule has {} at epoch {}",
+            state.fork_name_unchecked(),
+            expected_fork,
+            current_epoch
+        ));
+    }
+    *state.fork_mut() = spec.fork_at_epoch(current_epoch);
+
+    state
+        .drop_all_caches()
+        .map_err(|e| format!("Unable to drop caches: {:?}", e))?;
+
+    if state.slot() == spec.genesis_slot {
+        *state.genesis_validators_root_mut() = state
+            .update_validators_tree_hash_cache()
+            .map_err(|e| format!("Unable to compute genesis validators root: {:?}", e))?;
+
+        if expected_fork != ForkName::Base {
+            let sync_committee = Arc::new(
+                state
+                    .get_next_sync_committee(spec)
+                    .map_err(|e| format!("Unable to compute sync committee: {:?}", e))?,
+            );
+            *state
+                .current_sync_committee_mut()
+                .map_err(|e| format!("{:?}", e))? = sync_committee.clone();
+            *state
+                .next_sync_committee_mut()
+                .map_err(|e| format!("{:?}", e))? = sync_committee;
+        }
+    }
+
+    if state.latest_block_header().slot == state.slot() {
+        state.latest_block_header_mut().state_root = Hash256::zero();
+    }
+
+    state
+        .update_tree_hash_cache()
+        .map_err(|e| format!("Unable to compute state root: {:?}", e))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use genesis::interop_genesis_state;
+    use tree_hash::TreeHash;
+    use types::{test_utils::generate_deterministic_keypairs, MinimalEthSpec};
+
+    type E = MinimalEthSpec;
+
+    const MUTATIONS: &str = r#"
+- set_balance:
+    validators: "0..2,3"
+    balance: 40000000000
+- exit:
+    validators: "4..6"
+- exit:
+    validators: 7
+- activate:
+    validators: 7
+- set_fork_epoch:
+    fork: bellatrix
+    epoch: 10
+"#;
+
+    #[test]
+    fn mutation_round_trip() {
+        let mut spec = ForkName::Altair.make_genesis_spec(E::default_spec());
+        let keypairs = generate_deterministic_keypairs(16);
+        let mut state =
+            interop_genesis_state::<E>(&keypairs, 0, Hash256::repeat_byte(0x42), None, &spec)
+                .unwrap();
+
+        let mutations = parse_mutations(MUTATIONS.as_bytes()).unwrap();
+        let state_root = mutate_state(&mut state, &mut spec, mutations).unwrap();
+
+        // The state decoded from the output has the reported root and the mutations applied.
+        let mut decoded = BeaconState::<E>::from_ssz_bytes(&state.as_ssz_bytes(), &spec).unwrap();
+        assert_eq!(decoded.update_tree_hash_cache().unwrap(), state_root);
+
+        for index in 0..16 {
+            let validator = decoded.get_validator(index).unwrap();
+            if [0, 1, 3].contains(&index) {
+                assert_eq!(decoded.get_balance(index).unwrap(), 40_000_000_000);
+                assert_eq!(validator.effective_balance, spec.max_effective_balance);
+            } else {
+                assert_eq!(
+                    decoded.get_balance(index).unwrap(),
+                    spec.max_effective_balance
+                );
+            }
+            if [4, 5].contains(&index) {
+                assert_eq!(validator.exit_epoch, Epoch::new(0));
+            } else {
+                assert_eq!(validator.exit_epoch, spec.far_future_epoch);
+            }
+        }
+
+        // The roots derived from the mutated fields are consistent with them.
+        assert_eq!(
+            decoded.genesis_validators_root(),
+            decoded.validators().tree_hash_root()
+        );
+        let sync_committee = decoded.get_next_sync_committee(&spec).unwrap();
+        assert_eq!(**decoded.current_sync_committee().unwrap(), sync_committee);
+        assert_eq!(**decoded.next_sync_committee().unwrap(), sync_committee);
+        assert!(decoded.latest_block_header().state_root.is_zero());
+
+        // The fork epoch is changed in the network config written alongside the state.
+        let config = serde_yaml::to_string(&Config::from_chain_spec::<E>(&spec)).unwrap();
+        let config = serde_yaml::from_str::<Config>(&config).unwrap();
+        let decoded_spec = config.apply_to_chain_spec::<E>(&E::default_spec()).unwrap();
+        assert_eq!(decoded_spec.bellatrix_fork_epoch, Some(Epoch::new(10)));
+        assert_eq!(decoded_spec.altair_fork_epoch, Some(Epoch::new(0)));
+    }
+
+    #[test]
+    fn reject_out_of_bounds_validator() {
+        let mut spec = E::default_spec();
+        let keypairs = generate_deterministic_keypairs(4);
+        let mut state =
+            interop_genesis_state::<E>(&keypairs, 0, Hash256::repeat_byte(0x42), None, &spec)
+                .unwrap();
+
+        let mutations = parse_mutations("- exit:\n    validators: \"2..5\"".as_bytes()).unwrap();
+        assert!(mutate_state(&mut state, &mut spec, mutations).is_err());
+    }
+}
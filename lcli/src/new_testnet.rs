@@ -0,0 +1,101 @@
+use account_utils::eth2_keystore::keypair_from_secret;
+use clap::ArgMatches;
+use clap_utils::parse_required;
+use eth2_network_config::{Eth2NetworkConfig, GenesisStateSource};
+use eth2_wallet::bip39::{Language, Mnemonic, Seed};
+use eth2_wallet::{recover_validator_secret_from_mnemonic, KeyType};
+use execution_layer::genesis::ExecutionGenesis;
+use rayon::prelude::*;
+use ssz::Encode;
+use std::path::PathBuf;
+use types::{Config, Epoch, EthSpec, Keypair, Uint256};
+
+pub fn run<E: EthSpec>(testnet_dir: PathBuf, matches: &ArgMatches) -> Result<(), String> {
+    let el_genesis_path: PathBuf = parse_required(matches, "interop-from-el")?;
+    let validator_count: usize = parse_required(matches, "validator-count")?;
+    let mnemonic_phrase: String = parse_required(matches, "mnemonic-phrase")?;
+    let overwrite = matches.get_flag("force");
+
+    let el_genesis = ExecutionGenesis::from_file(&el_genesis_path)?;
+    let execution_payload_header = el_genesis.execution_payload_header::<E>()?;
+    let genesis_time = el_genesis.timestamp;
+
+    let mut spec = E::default_spec();
+    spec.deposit_chain_id = el_genesis.config.chain_id;
+    spec.deposit_network_id = el_genesis.config.chain_id;
+    if let Some(deposit_contract_address) = el_genesis.config.deposit_contract_address {
+        spec.deposit_contract_address = deposit_contract_address;
+    }
+    spec.min_genesis_time = genesis_time;
+    spec.terminal_total_difficulty = Uint256::zero();
+    spec.altair_fork_epoch = Some(Epoch::new(0));
+    spec.bellatrix_fork_epoch = Some(Epoch::new(0));
+
+    // Schedule each consensus fork at the epoch which starts when its execution counterpart is
+    // activated.
+    let seconds_per_epoch = spec.seconds_per_slot * E::slots_per_epoch();
+    let fork_epoch = |fork_time: Option<u64>, name: &str| -> Result<Option<Epoch>, String> {
+        fork_time
+            .map(|fork_time| {
+                let offset = fork_time.saturating_sub(genesis_time);
+                if offset % seconds_per_epoch != 0 {
+                    return Err(format!(
+                        "{} time {} is not at the start of an epoch",
+                        name, fork_time
+                    ));
+                }
+                Ok(Epoch::new(offset / seconds_per_epoch))
+            })
+            .transpose()
+    };
+    spec.capella_fork_epoch = fork_epoch(el_genesis.config.shanghai_time, "Shanghai")?;
+    spec.deneb_fork_epoch = fork_epoch(el_genesis.config.cancun_time, "Cancun")?;
+    spec.electra_fork_epoch = fork_epoch(el_genesis.config.prague_time, "Prague")?;
+    let config = Config::from_chain_spec::<E>(&spec);
+    config.validate()?;
+
+    let keypairs = derive_keypairs(&mnemonic_phrase, validator_count)?;
+    let genesis_state = genesis::interop_genesis_state::<E>(
+        &keypairs,
+        genesis_time,
+        execution_payload_header.block_hash().into_root(),
+        Some(execution_payload_header),
+        &spec,
+    )?;
+
+    let testnet = Eth2NetworkConfig {
+        deposit_contract_deploy_block: 0,
+        boot_enr: Some(vec![]),
+        genesis_state_source: GenesisStateSource::IncludedBytes,
+        genesis_state_bytes: Some(genesis_state.as_ssz_bytes().into()),
+        config,
+        kzg_trusted_setup: None,
+    };
+
+    testnet.write_to_file(testnet_dir, overwrite)
+}
+
+/// Derives the voting keypairs of the first `count` validators from the given mnemonic.
+fn derive_keypairs(mnemonic_phrase: &str, count: usize) -> Result<Vec<Keypair>, String> {
+    let mnemonic = Mnemonic::from_phrase(mnemonic_phrase, Language::English).map_err(|e| {
+        format!(
+            "Unable to derive mnemonic from string {:?}: {:?}",
+            mnemonic_phrase, e
+        )
+    })?;
+    let seed = Seed::new(&mnemonic, "");
+
+    (0..count)
+        .into_par_iter()
+        .map(|index| {
+            let (secret, _) = recover_validator_secret_from_mnemonic(
+                seed.as_bytes(),
+                index as u32,
+                KeyType::Voting,
+            )
+            .map_err(|e| format!("Unable to recover validator keys: {:?}", e))?;
+            keypair_from_secret(secret.as_bytes())
+                .map_err(|e| format!("Unable to build keypair: {:?}", e))
+        })
+        .collect()
+}
@@ -0,0 +1,171 @@
+//! # Replay Fork Choice
+//!
+//! Use this tool to re-run `get_head` on a snapshot of a beacon node's fork choice, optionally
+//! perturbing its inputs. Useful for debugging consensus splits after the fact, e.g. to check
+//! whether a late vote or a missing proposer boost would have changed the head.
+//!
+//! It can load a snapshot from a SSZ file or download it from the
+//! `/lighthouse/debug/fork_choice_snapshot` endpoint of a Lighthouse beacon node. Snapshots pulled
+//! from a beacon node can be saved to disk for later replays.
+//!
+//! ## Examples
+//!
+//! ### Example 1.
+//!
+//! Download a snapshot and save it to disk, checking that the replayed head matches:
+//!
+//! ```ignore
+//! lcli replay-fork-choice \
+//!     --beacon-url http://localhost:5052 \
+//!     --output-path /tmp/fork_choice.ssz
+//! ```
+//!
+//! ### Example 2.
+//!
+//! Replay the snapshot from the previous example without the proposer boost and with validator 42
+//! voting for a different block:
+//!
+//! ```ignore
+//! lcli replay-fork-choice \
+//!     --snapshot-path /tmp/fork_choice.ssz \
+//!     --proposer-boost-root 0x0000000000000000000000000000000000000000000000000000000000000000 \
+//!     --vote 42:0x3cdc33cd02713d8d6cc33a6dbe2d3a5bf9af1d357de0d175a403496486ff845e
+//! ```
+use clap::ArgMatches;
+use clap_utils::parse_optional;
+use environment::Environment;
+use eth2::{lighthouse::ForkChoiceSnapshot, BeaconNodeHttpClient, SensitiveUrl, Timeouts};
+use eth2_network_config::Eth2NetworkConfig;
+use log::info;
+use ssz::{Decode, Encode};
+use std::fs::File;
+use std::io::prelude::*;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use types::{Epoch, EthSpec, Hash256, Slot};
+
+const HTTP_TIMEOUT: Duration = Duration::from_secs(30);
+
+pub fn run<E: EthSpec>(
+    env: Environment<E>,
+    network_config: Eth2NetworkConfig,
+    matches: &ArgMatches,
+) -> Result<(), String> {
+    let mut spec = network_config.chain_spec::<E>()?;
+    let executor = env.core_context().executor;
+
+    let snapshot_path: Option<PathBuf> = parse_optional(matches, "snapshot-path")?;
+    let beacon_url: Option<SensitiveUrl> = parse_optional(matches, "beacon-url")?;
+    let output_path: Option<PathBuf> = parse_optional(matches, "output-path")?;
+
+    let mut snapshot = match (snapshot_path, beacon_url) {
+        (Some(snapshot_path), None) => {
+            info!("Snapshot path: {:?}", snapshot_path);
+            let mut bytes = vec![];
+            File::open(&snapshot_path)
+                .and_then(|mut file| file.read_to_end(&mut bytes))
+                .map_err(|e| format!("Unable to read {:?}: {:?}", snapshot_path, e))?;
+            ForkChoiceSnapshot::from_ssz_bytes(&bytes)
+                .map_err(|e| format!("Ssz decode failed: {:?}", e))?
+        }
+        (None, Some(beacon_url)) => {
+            let client = BeaconNodeHttpClient::new(beacon_url, Timeouts::set_all(HTTP_TIMEOUT));
+            executor
+                .handle()
+                .ok_or("shutdown in progress")?
+                .block_on(async move {
+                    client
+                        .get_lighthouse_debug_fork_choice_snapshot()
+                        .await
+                        .map_err(|e| format!("Failed to download snapshot: {:?}", e))
+                })?
+                .ok_or("Beacon node does not support fork choice snapshots")?
+        }
+        _ => return Err("must supply either --snapshot-path or --beacon-url".into()),
+    };
+
+    if let Some(output_path) = output_path {
+        let mut output_file = File::create(&output_path)
+            .map_err(|e| format!("Unable to create output file {:?}: {:?}", output_path, e))?;
+        output_file
+            .write_all(&snapshot.as_ssz_bytes())
+            .map_err(|e| format!("Unable to write to output file {:?}: {:?}", output_path, e))?;
+        info!("Wrote snapshot to {:?}", output_path);
+    }
+
+    info!(
+        "Loaded snapshot at slot {} with head {:?}",
+        snapshot.current_slot, snapshot.head_root
+    );
+
+    /*
+     * Apply perturbations.
+     */
+
+    if let Some(current_slot) = parse_optional::<Slot>(matches, "current-slot")? {
+        info!("Setting current slot to {}", current_slot);
+        snapshot.current_slot = current_slot;
+    }
+
+    if let Some(root) = parse_optional::<Hash256>(matches, "proposer-boost-root")? {
+        info!("Setting proposer boost root to {:?}", root);
+        snapshot.proposer_boost_root = root;
+    }
+
+    if let Some(percentage) = parse_optional::<u64>(matches, "proposer-score-boost")? {
+        info!("Setting proposer score boost to {}%", percentage);
+        spec.proposer_score_boost = Some(percentage);
+    }
+
+    if let Some(indices) = parse_optional::<String>(matches, "equivocating-indices")? {
+        for index in indices.split(',') {
+            let index = index
+                .trim()
+                .parse::<u64>()
+                .map_err(|e| format!("Invalid validator index {}: {:?}", index, e))?;
+            snapshot.equivocating_indices.push(index);
+        }
+        info!(
+            "Treating {} validators as equivocating",
+            snapshot.equivocating_indices.len()
+        );
+    }
+
+    if let Some(votes) = matches.get_many::<String>("vote") {
+        let mut proto_array = snapshot.proto_array()?;
+        for vote in votes {
+            let (index, root) = vote
+                .split_once(':')
+                .ok_or_else(|| format!("Vote {} is not in the form INDEX:ROOT", vote))?;
+            let index = index
+                .parse::<usize>()
+                .map_err(|e| format!("Invalid validator index {}: {:?}", index, e))?;
+            let root = root
+                .parse::<Hash256>()
+                .map_err(|e| format!("Invalid block root {}: {:?}", root, e))?;
+            info!("Setting vote of validator {} to {:?}", index, root);
+            // Use the maximum epoch so the vote always replaces the latest message.
+            proto_array.process_attestation(index, root, Epoch::max_value())?;
+        }
+        snapshot.proto_array_bytes = proto_array.as_bytes();
+    }
+
+    /*
+     * Replay `get_head`.
+     */
+
+    let start = Instant::now();
+    let head_root = snapshot.get_head::<E>(&spec)?;
+    info!("Replayed get_head in {:?}", start.elapsed());
+
+    if head_root == snapshot.head_root {
+        info!("Head is unchanged: {:?}", head_root);
+    } else {
+        info!(
+            "Head changed from {:?} to {:?}",
+            snapshot.head_root, head_root
+        );
+    }
+
+    Ok(())
+}
@@ -61,6 +61,19 @@
 //!     --exclude-cache-builds \
 //!     --exclude-post-block-thc
 //! ```
+//!
+//! ### Output a reward breakdown
+//!
+//! Apply the block and write the rewards earned by the proposer, the sync committee and (if an
+//! epoch boundary was crossed whilst advancing the pre-state) the attesters to a JSON file:
+//!
+//! ```ignore
+//! lcli transition-blocks \
+//!     --block-path /tmp/block-0x6c69.ssz \
+//!     --pre-state-path /tmp/pre-state-0x6c69.ssz \
+//!     --rewards-output /tmp/rewards-0x6c69.json
+//! ```
+use crate::transition_rewards::{self, TransitionRewards};
 use beacon_chain::{
     test_utils::EphemeralHarnessType, validator_pubkey_cache::ValidatorPubkeyCache,
 };
@@ -115,6 +128,7 @@ pub fn run<E: EthSpec>(
         parse_optional(matches, "post-state-output-path")?;
     let pre_state_output_path: Option<PathBuf> = parse_optional(matches, "pre-state-output-path")?;
     let block_output_path: Option<PathBuf> = parse_optional(matches, "block-output-path")?;
+    let rewards_output_path: Option<PathBuf> = parse_optional(matches, "rewards-output")?;
     let beacon_url: Option<SensitiveUrl> = parse_optional(matches, "beacon-url")?;
     let runs: usize = parse_required(matches, "runs")?;
     let config = Config {
@@ -233,6 +247,7 @@ pub fn run<E: EthSpec>(
      */
 
     let mut output_post_state = None;
+    let mut output_rewards = None;
     let mut saved_ctxt = None;
     for i in 0..runs {
         let pre_state = pre_state.clone();
@@ -240,7 +255,10 @@ pub fn run<E: EthSpec>(
 
         let start = Instant::now();
 
-        let post_state = do_transition(
+        // Only compute rewards on the first run so they don't skew the timings of later runs.
+        let compute_rewards = rewards_output_path.is_some() && i == 0;
+
+        let (post_state, rewards) = do_transition(
             pre_state,
             block_root,
             block,
@@ -248,6 +266,7 @@ pub fn run<E: EthSpec>(
             &config,
             &validator_pubkey_cache,
             &mut saved_ctxt,
+            compute_rewards,
             spec,
         )?;
 
@@ -257,6 +276,9 @@ pub fn run<E: EthSpec>(
         if output_post_state.is_none() {
             output_post_state = Some(post_state)
         }
+        if output_rewards.is_none() {
+            output_rewards = rewards;
+        }
     }
 
     /*
@@ -279,6 +301,21 @@ pub fn run<E: EthSpec>(
             .map_err(|e| format!("Unable to write to output file: {:?}", e))?;
     }
 
+    if let Some(path) = rewards_output_path {
+        let output_rewards = output_rewards.ok_or_else(|| {
+            format!(
+                "Rewards were not computed, cannot save to disk (runs = {})",
+                runs
+            )
+        })?;
+
+        let output_file =
+            File::create(path).map_err(|e| format!("Unable to create output file: {:?}", e))?;
+
+        serde_json::to_writer_pretty(output_file, &output_rewards)
+            .map_err(|e| format!("Unable to write to output file: {:?}", e))?;
+    }
+
     if let Some(path) = pre_state_output_path {
         let mut output_file =
             File::create(path).map_err(|e| format!("Unable to create output file: {:?}", e))?;
@@ -311,8 +348,9 @@ fn do_transition<E: EthSpec>(
     config: &Config,
     validator_pubkey_cache: &ValidatorPubkeyCache<EphemeralHarnessType<E>>,
     saved_ctxt: &mut Option<ConsensusContext<E>>,
+    compute_rewards: bool,
     spec: &ChainSpec,
-) -> Result<BeaconState<E>, String> {
+) -> Result<(BeaconState<E>, Option<TransitionRewards>), String> {
     if !config.exclude_cache_builds {
         let t = Instant::now();
         pre_state
@@ -338,6 +376,10 @@ fn do_transition<E: EthSpec>(
 
     let state_root = state_root_opt.ok_or("Failed to compute state root, internal error")?;
 
+    // Attestation rewards are computed from the state at each epoch boundary crossed by the slot
+    // processing, so keep a copy of the parent state to advance separately.
+    let parent_state = compute_rewards.then(|| pre_state.clone());
+
     // Transition the parent state to the block slot.
     let t = Instant::now();
     complete_state_advance(&mut pre_state, Some(state_root), block.slot(), spec)
@@ -353,6 +395,26 @@ fn do_transition<E: EthSpec>(
         .map_err(|e| format!("Unable to build caches: {:?}", e))?;
     debug!("Build all caches (again): {:?}", t.elapsed());
 
+    let rewards = if let Some(parent_state) = parent_state {
+        let t = Instant::now();
+        let rewards = TransitionRewards {
+            slot: block.slot(),
+            block_root,
+            proposer: transition_rewards::block_reward(&block, &pre_state, spec)?,
+            sync_committee: transition_rewards::sync_committee_rewards(&block, &pre_state, spec)?,
+            attesters: transition_rewards::attester_rewards(
+                parent_state,
+                state_root,
+                block.slot(),
+                spec,
+            )?,
+        };
+        debug!("Compute rewards: {:?}", t.elapsed());
+        Some(rewards)
+    } else {
+        None
+    };
+
     let mut ctxt = if let Some(ctxt) = saved_ctxt {
         ctxt.clone()
     } else {
@@ -415,7 +477,7 @@ fn do_transition<E: EthSpec>(
         debug!("Post-block tree hash: {:?}", t.elapsed());
     }
 
-    Ok(pre_state)
+    Ok((pre_state, rewards))
 }
 
 pub fn load_from_ssz_with<T>(
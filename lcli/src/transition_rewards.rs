@@ -0,0 +1,198 @@
+//! Reward accounting for `lcli transition-blocks --rewards-output`.
+//!
+//! The rewards are computed by the same functions the beacon node uses to serve the standard
+//! `/eth/v1/beacon/rewards/*` endpoints, applied directly to a `BeaconState` so they can be used
+//! without a `BeaconChain`.
+use beacon_chain::attestation_rewards::compute_attestation_rewards_for_state;
+use beacon_chain::beacon_block_reward::compute_beacon_block_reward;
+use beacon_chain::sync_committee_rewards::compute_sync_committee_rewards;
+use environment::null_logger;
+use eth2::lighthouse::{StandardAttestationRewards, StandardBlockReward, SyncCommitteeReward};
+use serde::Serialize;
+use state_processing::state_advance::complete_state_advance;
+use types::{BeaconState, ChainSpec, Epoch, EthSpec, Hash256, SignedBeaconBlock, Slot};
+
+/// The rewards earned by validators whilst transitioning a state to and through a block.
+#[derive(Debug, Serialize)]
+pub struct TransitionRewards {
+    pub slot: Slot,
+    pub block_root: Hash256,
+    /// Rewards paid to the proposer for the contents of the block.
+    pub proposer: StandardBlockReward,
+    /// Rewards (or penalties) for sync committee participation in the block.
+    pub sync_committee: Vec<SyncCommitteeReward>,
+    /// Attestation rewards paid by the epoch processing whilst advancing the pre-state to the
+    /// block slot. Empty if no epoch boundary was crossed.
+    pub attesters: Vec<EpochAttesterRewards>,
+}
+
+/// The attestation rewards paid for an epoch, as per `/eth/v1/beacon/rewards/attestations`.
+#[derive(Debug, Serialize)]
+pub struct EpochAttesterRewards {
+    pub epoch: Epoch,
+    #[serde(flatten)]
+    pub rewards: StandardAttestationRewards,
+}
+
+/// Computes the attestation rewards paid at each epoch boundary crossed whilst advancing `state`
+/// (with root `state_root`) to `slot`.
+pub fn attester_rewards<E: EthSpec>(
+    mut state: BeaconState<E>,
+    state_root: Hash256,
+    slot: Slot,
+    spec: &ChainSpec,
+) -> Result<Vec<EpochAttesterRewards>, String> {
+    let log = null_logger()?;
+    let mut state_root_opt = Some(state_root);
+    let mut rewards = vec![];
+    loop {
+        let boundary_slot = state
+            .next_epoch()
+            .map_err(|e| format!("Unable to get next epoch: {e:?}"))?
+            .start_slot(E::slots_per_epoch());
+        if boundary_slot > slot {
+            return Ok(rewards);
+        }
+
+        // Epoch processing is applied to the state at the last slot of the epoch.
+        complete_state_advance(&mut state, state_root_opt.take(), boundary_slot - 1, spec)
+            .map_err(|e| format!("Unable to advance state: {e:?}"))?;
+
+        // No rewards are paid at the end of the genesis epoch.
+        if state.current_epoch() > E::genesis_epoch() {
+            let epoch = state.previous_epoch();
+            let epoch_rewards =
+                compute_attestation_rewards_for_state(state.clone(), vec![], spec, &log).map_err(
+                    |e| format!("Unable to compute attestation rewards for epoch {epoch}: {e:?}"),
+                )?;
+            rewards.push(EpochAttesterRewards {
+                epoch,
+                rewards: epoch_rewards,
+            });
+        }
+
+        complete_state_advance(&mut state, None, boundary_slot, spec)
+            .map_err(|e| format!("Unable to advance state: {e:?}"))?;
+    }
+}
+
+/// Computes the proposer reward for `block`.
+///
+/// The `state` must be advanced to the slot of the block (but not have the block applied).
+pub fn block_reward<E: EthSpec>(
+    block: &SignedBeaconBlock<E>,
+    state: &BeaconState<E>,
+    spec: &ChainSpec,
+) -> Result<StandardBlockReward, String> {
+    let log = null_logger()?;
+    compute_beacon_block_reward(block.message(), &mut state.clone(), spec, &log)
+        .map_err(|e| format!("Unable to compute block rewards: {:?}", e))
+}
+
+/// Computes the reward (or penalty) for each member of the sync committee that signed (or failed
+/// to sign) the sync aggregate in `block`, sorted by validator index.
+///
+/// The proposer's reward for including the aggregate is excluded, as per the standard API.
+pub fn sync_committee_rewards<E: EthSpec>(
+    block: &SignedBeaconBlock<E>,
+    state: &BeaconState<E>,
+    spec: &ChainSpec,
+) -> Result<Vec<SyncCommitteeReward>, String> {
+    if block.message().body().sync_aggregate().is_err() {
+        return Ok(vec![]);
+    }
+
+    let log = null_logger()?;
+    let mut rewards =
+        compute_sync_committee_rewards(block.message(), &mut state.clone(), spec, &log)
+            .map_err(|e| format!("Unable to compute sync committee rewards: {:?}", e))?;
+    rewards.sort_by_key(|reward| reward.validator_index);
+    Ok(rewards)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use beacon_chain::test_utils::{AttestationStrategy, BlockStrategy};
+    use eth2::types::BlockId;
+    use http_api::test_utils::InteractiveTester;
+    use types::{ForkName, MinimalEthSpec};
+
+    type E = MinimalEthSpec;
+
+    const VALIDATOR_COUNT: usize = 64;
+
+    /// Replays the block at the first slot of an epoch and checks that the rewards match those
+    /// served by the beacon node's rewards endpoints for the same block and epoch.
+    #[tokio::test]
+    async fn rewards_match_http_api() {
+        let spec = ForkName::Altair.make_genesis_spec(E::default_spec());
+        let tester = InteractiveTester::<E>::new(Some(spec.clone()), VALIDATOR_COUNT).await;
+        let harness = &tester.harness;
+        let slots_per_epoch = E::slots_per_epoch();
+
+        // Build the chain up to the first slot of epoch 3, so that the final block crosses an epoch
+        // boundary at which the attestations of epoch 1 are rewarded.
+        let block_slot = Slot::new(3 * slots_per_epoch);
+        harness.advance_slot();
+        harness
+            .extend_chain(
+                block_slot.as_usize(),
+                BlockStrategy::OnCanonicalHead,
+                AttestationStrategy::AllValidators,
+            )
+            .await;
+
+        let head = harness.chain.head_snapshot();
+        let block = head.beacon_block.as_ref().clone();
+        assert_eq!(block.slot(), block_slot);
+
+        let parent_block = harness
+            .chain
+            .get_blinded_block(&block.parent_root())
+            .unwrap()
+            .unwrap();
+        let parent_state_root = parent_block.state_root();
+        let parent_state = harness
+            .chain
+            .get_state(&parent_state_root, Some(parent_block.slot()))
+            .unwrap()
+            .unwrap();
+
+        let mut pre_state = parent_state.clone();
+        complete_state_advance(&mut pre_state, Some(parent_state_root), block_slot, &spec).unwrap();
+        pre_state.build_all_caches(&spec).unwrap();
+
+        let proposer = block_reward(&block, &pre_state, &spec).unwrap();
+        let expected_proposer = tester
+            .client
+            .get_beacon_rewards_blocks(BlockId::Slot(block_slot))
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(proposer, expected_proposer);
+
+        let sync_committee = sync_committee_rewards(&block, &pre_state, &spec).unwrap();
+        assert!(!sync_committee.is_empty());
+        let mut expected_sync_committee = tester
+            .client
+            .post_beacon_rewards_sync_committee(BlockId::Slot(block_slot), &[])
+            .await
+            .unwrap()
+            .data;
+        expected_sync_committee.sort_by_key(|reward| reward.validator_index);
+        assert_eq!(sync_committee, expected_sync_committee);
+
+        let attesters =
+            attester_rewards(parent_state, parent_state_root, block_slot, &spec).unwrap();
+        assert_eq!(attesters.len(), 1);
+        assert_eq!(attesters[0].epoch, Epoch::new(1));
+        let expected_attesters = tester
+            .client
+            .post_beacon_rewards_attestations(Epoch::new(1), &[])
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(attesters[0].rewards, expected_attesters);
+    }
+}
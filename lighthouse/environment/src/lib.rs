@@ -12,7 +12,7 @@ use eth2_network_config::Eth2NetworkConfig;
 use futures::channel::mpsc::{channel, Receiver, Sender};
 use futures::{future, StreamExt};
 
-use logging::SSELoggingComponents;
+use logging::{ModuleLevelFilter, ModuleLevels, SSELoggingComponents};
 use serde::{Deserialize, Serialize};
 use slog::{error, info, o, warn, Drain, Duplicate, Level, Logger};
 use sloggers::{file::FileLoggerBuilder, types::Format, types::Severity, Build};
@@ -91,6 +91,7 @@ pub struct RuntimeContext<E: EthSpec> {
     pub eth2_config: Eth2Config,
     pub eth2_network_config: Option<Arc<Eth2NetworkConfig>>,
     pub sse_logging_components: Option<SSELoggingComponents>,
+    pub module_levels: Option<ModuleLevels>,
 }
 
 impl<E: EthSpec> RuntimeContext<E> {
@@ -104,6 +105,7 @@ impl<E: EthSpec> RuntimeContext<E> {
             eth2_config: self.eth2_config.clone(),
             eth2_network_config: self.eth2_network_config.clone(),
             sse_logging_components: self.sse_logging_components.clone(),
+            module_levels: self.module_levels.clone(),
         }
     }
 
@@ -123,6 +125,7 @@ pub struct EnvironmentBuilder<E: EthSpec> {
     runtime: Option<Arc<Runtime>>,
     log: Option<Logger>,
     sse_logging_components: Option<SSELoggingComponents>,
+    module_levels: Option<ModuleLevels>,
     eth_spec_instance: E,
     eth2_config: Eth2Config,
     eth2_network_config: Option<Eth2NetworkConfig>,
@@ -135,6 +138,7 @@ impl EnvironmentBuilder<MinimalEthSpec> {
             runtime: None,
             log: None,
             sse_logging_components: None,
+            module_levels: None,
             eth_spec_instance: MinimalEthSpec,
             eth2_config: Eth2Config::minimal(),
             eth2_network_config: None,
@@ -149,6 +153,7 @@ impl EnvironmentBuilder<MainnetEthSpec> {
             runtime: None,
             log: None,
             sse_logging_components: None,
+            module_levels: None,
             eth_spec_instance: MainnetEthSpec,
             eth2_config: Eth2Config::mainnet(),
             eth2_network_config: None,
@@ -163,6 +168,7 @@ impl EnvironmentBuilder<GnosisEthSpec> {
             runtime: None,
             log: None,
             sse_logging_components: None,
+            module_levels: None,
             eth_spec_instance: GnosisEthSpec,
             eth2_config: Eth2Config::gnosis(),
             eth2_network_config: None,
@@ -235,16 +241,24 @@ impl<E: EthSpec> EnvironmentBuilder<E> {
                 .build()
         };
 
-        let stdout_drain = match config.debug_level.as_str() {
-            "info" => stdout_drain.filter_level(Level::Info),
-            "debug" => stdout_drain.filter_level(Level::Debug),
-            "trace" => stdout_drain.filter_level(Level::Trace),
-            "warn" => stdout_drain.filter_level(Level::Warning),
-            "error" => stdout_drain.filter_level(Level::Error),
-            "crit" => stdout_drain.filter_level(Level::Critical),
+        let stdout_level = match config.debug_level.as_str() {
+            "info" => Level::Info,
+            "debug" => Level::Debug,
+            "trace" => Level::Trace,
+            "warn" => Level::Warning,
+            "error" => Level::Error,
+            "crit" => Level::Critical,
             unknown => return Err(format!("Unknown debug-level: {}", unknown)),
         };
 
+        // Both the stdout and file drains respect the per-module overrides, which may be changed
+        // at runtime.
+        let module_levels = ModuleLevels::default();
+        self.module_levels = Some(module_levels.clone());
+
+        let stdout_drain =
+            ModuleLevelFilter::new(stdout_drain, stdout_level, module_levels.clone());
+
         let stdout_logger = Logger::root(stdout_drain.fuse(), o!());
 
         // Disable file logging if values set to 0.
@@ -285,22 +299,26 @@ impl<E: EthSpec> EnvironmentBuilder<E> {
         }
 
         let logfile_level = match config.logfile_debug_level.as_str() {
-            "info" => Severity::Info,
-            "debug" => Severity::Debug,
-            "trace" => Severity::Trace,
-            "warn" => Severity::Warning,
-            "error" => Severity::Error,
-            "crit" => Severity::Critical,
+            "info" => Level::Info,
+            "debug" => Level::Debug,
+            "trace" => Level::Trace,
+            "warn" => Level::Warning,
+            "error" => Level::Error,
+            "crit" => Level::Critical,
             unknown => return Err(format!("Unknown loglevel-debug-level: {}", unknown)),
         };
 
+        let logfile_format = match config.logfile_format.as_deref().map(str::to_uppercase) {
+            Some(format) if format == "JSON" => Format::Json,
+            _ => Format::default(),
+        };
+
+        // Levels are filtered by the `ModuleLevelFilter` rather than the file logger, so that
+        // per-module overrides can be more verbose than `logfile_level`.
         let file_logger = FileLoggerBuilder::new(&path)
-            .level(logfile_level)
+            .level(Severity::Trace)
             .channel_size(LOG_CHANNEL_SIZE)
-            .format(match config.logfile_format.as_deref() {
-                Some("JSON") => Format::Json,
-                _ => Format::default(),
-            })
+            .format(logfile_format)
             .rotate_size(config.max_log_size)
             .rotate_keep(config.max_log_number)
             .rotate_compress(config.compression)
@@ -308,7 +326,9 @@ impl<E: EthSpec> EnvironmentBuilder<E> {
             .build()
             .map_err(|e| format!("Unable to build file logger: {}", e))?;
 
-        let mut log = Logger::root(Duplicate::new(stdout_logger, file_logger).fuse(), o!());
+        let file_drain = ModuleLevelFilter::new(file_logger, logfile_level, module_levels);
+
+        let mut log = Logger::root(Duplicate::new(stdout_logger, file_drain).fuse(), o!());
 
         info!(
             log,
@@ -355,6 +375,7 @@ impl<E: EthSpec> EnvironmentBuilder<E> {
             exit,
             log: self.log.ok_or("Cannot build environment without log")?,
             sse_logging_components: self.sse_logging_components,
+            module_levels: self.module_levels,
             eth_spec_instance: self.eth_spec_instance,
             eth2_config: self.eth2_config,
             eth2_network_config: self.eth2_network_config.map(Arc::new),
@@ -374,6 +395,7 @@ pub struct Environment<E: EthSpec> {
     exit: async_channel::Receiver<()>,
     log: Logger,
     sse_logging_components: Option<SSELoggingComponents>,
+    module_levels: Option<ModuleLevels>,
     eth_spec_instance: E,
     pub eth2_config: Eth2Config,
     pub eth2_network_config: Option<Arc<Eth2NetworkConfig>>,
@@ -401,6 +423,7 @@ impl<E: EthSpec> Environment<E> {
             eth2_config: self.eth2_config.clone(),
             eth2_network_config: self.eth2_network_config.clone(),
             sse_logging_components: self.sse_logging_components.clone(),
+            module_levels: self.module_levels.clone(),
         }
     }
 
@@ -417,6 +440,7 @@ impl<E: EthSpec> Environment<E> {
             eth2_config: self.eth2_config.clone(),
             eth2_network_config: self.eth2_network_config.clone(),
             sse_logging_components: self.sse_logging_components.clone(),
+            module_levels: self.module_levels.clone(),
         }
     }
 
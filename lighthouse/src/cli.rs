@@ -1,9 +1,12 @@
 use clap::Parser;
 use database_manager::cli::DatabaseManager;
 use serde::{Deserialize, Serialize};
+use slasher_service::cli::RemoteSlasher;
 
 #[derive(Parser, Clone, Deserialize, Serialize, Debug)]
 pub enum LighthouseSubcommands {
     #[clap(name = "database_manager")]
     DatabaseManager(DatabaseManager),
+    #[clap(name = "remote_slasher")]
+    RemoteSlasher(RemoteSlasher),
 }
@@ -19,7 +19,7 @@ use lighthouse_version::VERSION;
 use malloc_utils::configure_memory_allocator;
 use slog::{crit, info};
 use std::backtrace::Backtrace;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::exit;
 use task_executor::ShutdownReason;
 use types::{EthSpec, EthSpecId};
@@ -145,6 +145,7 @@ fn main() {
                 .value_name("FORMAT")
                 .help("Specifies the log format used when emitting logs to the logfile.")
                 .value_parser(["DEFAULT", "JSON"])
+                .ignore_case(true)
                 .action(ArgAction::Set)
                 .global(true)
                 .display_order(0)
@@ -374,6 +375,18 @@ fn main() {
                 .global(true)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("chain-config-overrides")
+                .long("chain-config-overrides")
+                .value_name("FILE")
+                .help("Path to a YAML file of config values, such as SECONDS_PER_SLOT or \
+                       ALTAIR_FORK_EPOCH, which replace those of the selected network. Intended \
+                       for devnets which differ from a built-in network in only a few values. \
+                       Preset values cannot be overridden.")
+                .action(ArgAction::Set)
+                .global(true)
+                .display_order(0)
+        )
         .arg(
             Arg::new("genesis-state-url")
                 .long("genesis-state-url")
@@ -400,6 +413,42 @@ fn main() {
                 .global(true)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("metrics-buckets-config")
+                .long("metrics-buckets-config")
+                .value_name("FILE")
+                .help(
+                    "Path to a TOML file which overrides the buckets of Prometheus histograms. \
+                    Each key is either a metric name or a metric group ending in `*` (e.g. \
+                    `beacon_processor_*`), mapped to a list of bucket upper bounds.",
+                )
+                .action(ArgAction::Set)
+                .global(true)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("telemetry-collector-url")
+                .long("telemetry-collector-url")
+                .value_name("URL")
+                .help(
+                    "The OTLP/gRPC endpoint of an OpenTelemetry collector (e.g. \
+                    http://localhost:4317). When set, tracing spans covering block import and \
+                    block production are exported to the collector.",
+                )
+                .action(ArgAction::Set)
+                .global(true)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("telemetry-service-name")
+                .long("telemetry-service-name")
+                .value_name("NAME")
+                .help("The service name attached to spans exported to the telemetry collector.")
+                .default_value("lighthouse")
+                .action(ArgAction::Set)
+                .global(true)
+                .display_order(0)
+        )
         .arg(
             Arg::new("help")
             .long("help")
@@ -419,6 +468,17 @@ fn main() {
 
     let matches = cli.get_matches();
 
+    // Histogram buckets are fixed when each metric is first registered, so the overrides must be
+    // installed before anything has the chance to touch a metric.
+    if let Some(path) = matches.get_one::<String>("metrics-buckets-config") {
+        if let Err(e) = metrics::HistogramBucketsConfig::from_toml_file(Path::new(path))
+            .and_then(metrics::set_histogram_buckets_config)
+        {
+            eprintln!("Unable to load --metrics-buckets-config: {}", e);
+            exit(1)
+        }
+    }
+
     // Configure the allocator early in the process, before it has the chance to use the default values for
     // anything important.
     //
@@ -627,7 +687,19 @@ fn run<E: EthSpec>(
 
     let path = tracing_log_path.clone().unwrap();
 
-    logging::create_tracing_layer(path);
+    let telemetry = match clap_utils::parse_optional(matches, "telemetry-collector-url")? {
+        Some(collector_url) => Some(logging::TelemetryConfig {
+            collector_url,
+            service_name: clap_utils::parse_required(matches, "telemetry-service-name")?,
+        }),
+        None => None,
+    };
+
+    // The telemetry exporter runs on the Tokio runtime, so it must be created within its context.
+    {
+        let _guard = environment.runtime().enter();
+        logging::create_tracing_layer(path, telemetry);
+    }
 
     // Allow Prometheus to export the time at which the process was started.
     metrics::expose_process_start_time(&log);
@@ -698,9 +770,23 @@ fn run<E: EthSpec>(
     );
 
     match matches.subcommand() {
+        Some((slasher_service::cli::CMD, sub_matches)) => {
+            let Ok(LighthouseSubcommands::RemoteSlasher(remote_slasher_config)) =
+                LighthouseSubcommands::from_arg_matches(matches)
+            else {
+                return Err("Invalid remote slasher arguments".into());
+            };
+            let config =
+                slasher_service::remote::parse_config(sub_matches, &remote_slasher_config)?;
+            slasher_service::remote::run::<E>(config, environment.core_context())?;
+        }
         Some(("beacon_node", matches)) => {
             let context = environment.core_context();
             let log = context.log().clone();
+            if matches.subcommand_matches("rotate-network-key").is_some() {
+                return beacon_node::rotate_network_key(matches, &log);
+            }
+
             let executor = context.executor.clone();
             let mut config = beacon_node::get_config::<E>(matches, &context)?;
             config.logger_config = logger_config;
@@ -771,6 +857,9 @@ fn run<E: EthSpec>(
 
     environment.fire_signal();
 
+    // Flush any spans which have not yet been exported whilst the runtime is still running.
+    logging::shutdown_telemetry();
+
     // Shutdown the environment once all tasks have completed.
     environment.shutdown_on_idle();
 
@@ -399,6 +399,157 @@ fn validator_create() {
     assert_eq!(dir_validator_count(validator_dir.path()), 6);
 }
 
+/// Returns a `lighthouse account validator create` command for the `wallet`.
+fn validator_create_cmd(wallet: &TestWallet, validator_dir: &Path, secrets_dir: &Path) -> Command {
+    let mut cmd = validator_cmd();
+    cmd.arg(format!("--{}", VALIDATOR_DIR_FLAG))
+        .arg(validator_dir.as_os_str())
+        .arg(CREATE_CMD)
+        .arg(format!("--{}", WALLETS_DIR_FLAG))
+        .arg(wallet.base_dir().into_os_string())
+        .arg(format!("--{}", WALLET_NAME_FLAG))
+        .arg(&wallet.name)
+        .arg(format!("--{}", WALLET_PASSWORD_FLAG))
+        .arg(wallet.password_path().into_os_string())
+        .arg(format!("--{}", SECRETS_DIR_FLAG))
+        .arg(secrets_dir.as_os_str());
+    cmd
+}
+
+/// Returns the `(index, pubkey)` pairs printed to stdout by the create command.
+fn stdout_pubkeys(output: &Output) -> Vec<(String, String)> {
+    from_utf8(&output.stdout)
+        .expect("stdout is not utf8")
+        .lines()
+        .map(|line| {
+            let (index, pubkey) = line.split_once('\t').expect("line must have tab");
+            (index.to_string(), pubkey.to_string())
+        })
+        .collect()
+}
+
+#[test]
+fn validator_create_derivation_index_range() {
+    let base_dir = tempdir().unwrap();
+    let validator_dir = tempdir().unwrap();
+    let secrets_dir = tempdir().unwrap();
+    let manifest_dir = tempdir().unwrap();
+
+    let wallet = TestWallet::new(base_dir.path(), "wally");
+    wallet.create_expect_success();
+
+    // Write a manifest of the keys at indices 2, 3 and 4 without creating any validators.
+    let manifest_path = manifest_dir.path().join("manifest.json");
+    let output = output_result(
+        validator_create_cmd(&wallet, validator_dir.path(), secrets_dir.path())
+            .arg(format!("--{}", DERIVATION_INDEX_RANGE_FLAG))
+            .arg("2..5")
+            .arg(format!("--{}", DRY_RUN_MANIFEST_FLAG))
+            .arg(manifest_path.as_os_str()),
+    )
+    .unwrap();
+    assert_eq!(dir_child_count(validator_dir.path()), 0);
+
+    let manifest: Vec<DerivedKeyManifestEntry> =
+        serde_json::from_reader(File::open(&manifest_path).unwrap()).unwrap();
+    assert_eq!(
+        manifest.iter().map(|entry| entry.index).collect::<Vec<_>>(),
+        vec![2, 3, 4]
+    );
+    assert_eq!(
+        stdout_pubkeys(&output)
+            .into_iter()
+            .map(|(_, pubkey)| pubkey)
+            .collect::<Vec<_>>(),
+        manifest
+            .iter()
+            .map(|entry| entry.voting_pubkey.as_hex_string())
+            .collect::<Vec<_>>()
+    );
+
+    // The manifest should not be overwritten.
+    output_result(
+        validator_create_cmd(&wallet, validator_dir.path(), secrets_dir.path())
+            .arg(format!("--{}", DERIVATION_INDEX_RANGE_FLAG))
+            .arg("0..1")
+            .arg(format!("--{}", DRY_RUN_MANIFEST_FLAG))
+            .arg(manifest_path.as_os_str()),
+    )
+    .unwrap_err();
+
+    // Creating the validators in the range should produce the keys in the manifest.
+    let output = output_result(
+        validator_create_cmd(&wallet, validator_dir.path(), secrets_dir.path())
+            .arg(format!("--{}", DERIVATION_INDEX_RANGE_FLAG))
+            .arg("2..5"),
+    )
+    .unwrap();
+    let created = stdout_pubkeys(&output);
+    assert_eq!(created.len(), 3);
+    for ((_, pubkey), entry) in created.iter().zip(&manifest) {
+        assert_eq!(*pubkey, entry.voting_pubkey.as_hex_string());
+        ValidatorDir::open(validator_dir.path().join(pubkey)).expect("should open validator dir");
+    }
+    assert_eq!(dir_validator_count(validator_dir.path()), 3);
+
+    // Indices below the wallet's next unused index cannot be created.
+    output_result(
+        validator_create_cmd(&wallet, validator_dir.path(), secrets_dir.path())
+            .arg(format!("--{}", DERIVATION_INDEX_RANGE_FLAG))
+            .arg("4..6"),
+    )
+    .unwrap_err();
+    assert_eq!(dir_validator_count(validator_dir.path()), 3);
+
+    // The wallet continues from the end of the range.
+    let output = output_result(
+        validator_create_cmd(&wallet, validator_dir.path(), secrets_dir.path())
+            .arg(format!("--{}", DERIVATION_INDEX_RANGE_FLAG))
+            .arg("5..6"),
+    )
+    .unwrap();
+    assert_eq!(stdout_pubkeys(&output).len(), 1);
+}
+
+#[test]
+fn validator_create_deposit_amount() {
+    let base_dir = tempdir().unwrap();
+    let validator_dir = tempdir().unwrap();
+    let secrets_dir = tempdir().unwrap();
+
+    let wallet = TestWallet::new(base_dir.path(), "wally");
+    wallet.create_expect_success();
+
+    let create_with_deposit = |deposit_gwei: &str, compounding: bool| {
+        let mut cmd = validator_create_cmd(&wallet, validator_dir.path(), secrets_dir.path());
+        cmd.arg(format!("--{}", COUNT_FLAG))
+            .arg("1")
+            .arg(format!("--{}", DEPOSIT_GWEI_FLAG))
+            .arg(deposit_gwei);
+        if compounding {
+            cmd.arg(format!("--{}", COMPOUNDING_WITHDRAWAL_ADDRESS_FLAG))
+                .arg("0x00000000219ab540356cbb839cbe05303d7705fa");
+        }
+        output_result(&mut cmd)
+    };
+
+    // Deposits below the minimum deposit amount are rejected.
+    create_with_deposit("999999999", false).unwrap_err();
+
+    // Deposits above 32 ETH require compounding withdrawal credentials.
+    let err = create_with_deposit("64000000000", false).unwrap_err();
+    assert!(err.contains(COMPOUNDING_WITHDRAWAL_ADDRESS_FLAG), "{}", err);
+
+    // Compounding withdrawal credentials require Electra, which is not scheduled on mainnet.
+    let err = create_with_deposit("64000000000", true).unwrap_err();
+    assert!(err.contains("Electra"), "{}", err);
+
+    assert_eq!(dir_child_count(validator_dir.path()), 0);
+
+    create_with_deposit("1000000000", false).unwrap();
+    assert_eq!(dir_validator_count(validator_dir.path()), 1);
+}
+
 #[test]
 fn validator_import_launchpad() {
     const PASSWORD: &str = "cats";
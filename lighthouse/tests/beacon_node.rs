@@ -21,7 +21,7 @@ use std::string::ToString;
 use std::time::Duration;
 use tempfile::TempDir;
 use types::non_zero_usize::new_non_zero_usize;
-use types::{Address, Checkpoint, Epoch, ExecutionBlockHash, Hash256, MainnetEthSpec};
+use types::{Address, Checkpoint, Epoch, EthSpec, ExecutionBlockHash, Hash256, MainnetEthSpec};
 use unused_port::{unused_tcp4_port, unused_tcp6_port, unused_udp4_port, unused_udp6_port};
 
 const DEFAULT_ETH1_ENDPOINT: &str = "http://localhost:8545/";
@@ -136,6 +136,38 @@ fn allow_insecure_genesis_sync_enabled() {
         });
 }
 
+#[test]
+fn shadow_fork_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert!(config.shadow_fork.is_none()));
+}
+
+#[test]
+fn shadow_fork_flag() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    let shadow_fork_path = dir.path().join("config.yaml");
+    let mut spec = MainnetEthSpec::default_spec();
+    spec.electra_fork_epoch = Some(Epoch::new(1_000_000));
+    let shadow_config = types::Config::from_chain_spec::<MainnetEthSpec>(&spec);
+    let file = File::create(&shadow_fork_path).expect("Unable to create shadow fork config");
+    serde_yaml::to_writer(file, &shadow_config).expect("Unable to write shadow fork config");
+
+    CommandLineTest::new()
+        .flag("shadow-fork", shadow_fork_path.as_os_str().to_str())
+        .run_with_zero_port()
+        .with_config(|config| {
+            let shadow_fork = config.shadow_fork.as_ref().unwrap();
+            assert_eq!(
+                shadow_fork
+                    .electra_fork_epoch
+                    .as_ref()
+                    .map(|epoch| epoch.value),
+                Some(Epoch::new(1_000_000))
+            );
+        });
+}
+
 #[test]
 fn wss_checkpoint_flag() {
     let state = Some(Checkpoint {
@@ -371,6 +403,121 @@ fn trusted_peers_flag() {
         });
 }
 
+#[test]
+fn target_subnet_peers_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.network.target_subnet_peers, 3));
+}
+#[test]
+fn target_subnet_peers_flag() {
+    CommandLineTest::new()
+        .flag("target-subnet-peers", Some("6"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.network.target_subnet_peers, 6));
+}
+
+#[test]
+fn inbound_connection_rate_limit_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.network.inbound_connection_rate_limit, 10));
+}
+#[test]
+fn inbound_connection_rate_limit_flag() {
+    CommandLineTest::new()
+        .flag("inbound-connection-rate-limit", Some("0"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.network.inbound_connection_rate_limit, 0));
+}
+#[test]
+#[should_panic]
+fn inbound_connection_rate_limit_invalid() {
+    CommandLineTest::new()
+        .flag("inbound-connection-rate-limit", Some("-1"))
+        .run_with_zero_port();
+}
+
+#[test]
+fn new_peer_grace_period_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.network.new_peer_grace_period, 300));
+}
+#[test]
+fn new_peer_grace_period_flag() {
+    CommandLineTest::new()
+        .flag("new-peer-grace-period", Some("60"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.network.new_peer_grace_period, 60));
+}
+
+#[test]
+fn trusted_mesh_peers_flag() {
+    let peers = vec![PeerId::random(), PeerId::random()];
+    CommandLineTest::new()
+        .flag(
+            "trusted-mesh-peers",
+            Some(format!("{},{}", peers[0], peers[1]).as_str()),
+        )
+        .flag("trusted-mesh-delay", Some("250"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            let trusted_mesh_peers = config
+                .network
+                .trusted_mesh_peers
+                .iter()
+                .map(|peer| PeerId::from(peer.clone()))
+                .collect::<Vec<_>>();
+            assert_eq!(trusted_mesh_peers, peers);
+            assert_eq!(
+                config.network.trusted_mesh_delay,
+                Duration::from_millis(250)
+            );
+        });
+}
+#[test]
+fn trusted_mesh_peers_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert!(config.network.trusted_mesh_peers.is_empty());
+            assert_eq!(
+                config.network.trusted_mesh_delay,
+                Duration::from_millis(100)
+            );
+        });
+}
+
+#[test]
+fn static_peers_flag() {
+    let peers = vec![PeerId::random(), PeerId::random()];
+    let multiaddrs = vec![
+        format!("/ip4/127.0.0.1/tcp/9000/p2p/{}", peers[0]),
+        format!("/ip4/127.0.0.2/udp/9001/quic-v1/p2p/{}", peers[1]),
+    ];
+    CommandLineTest::new()
+        .flag("static-peers", Some(multiaddrs.join(",").as_str()))
+        .run_with_zero_port()
+        .with_config(|config| {
+            let static_peers = config
+                .network
+                .static_peers
+                .iter()
+                .map(|multiaddr| lighthouse_network::peer_id_from_multiaddr(multiaddr).unwrap())
+                .collect::<Vec<_>>();
+            assert_eq!(static_peers, peers);
+            assert_eq!(config.network.static_peers[0].to_string(), multiaddrs[0]);
+        });
+}
+#[test]
+#[should_panic]
+fn static_peers_flag_without_peer_id() {
+    CommandLineTest::new()
+        .flag("static-peers", Some("/ip4/127.0.0.1/tcp/9000"))
+        .run_with_zero_port();
+}
+
 #[test]
 fn genesis_backfill_flag() {
     CommandLineTest::new()
@@ -388,6 +535,23 @@ fn genesis_backfill_with_historic_flag() {
         .with_config(|config| assert_eq!(config.chain.genesis_backfill, true));
 }
 
+#[test]
+fn import_era_dir_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.chain.import_era_dir, None));
+}
+
+#[test]
+fn import_era_dir_flag() {
+    CommandLineTest::new()
+        .flag("import-era-dir", Some("/tmp/era"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(config.chain.import_era_dir, Some(PathBuf::from("/tmp/era")))
+        });
+}
+
 // Tests for Eth1 flags.
 #[test]
 fn dummy_eth1_flag() {
@@ -436,6 +600,28 @@ fn eth1_cache_follow_distance_manual() {
             assert_eq!(config.eth1.cache_follow_distance(), 128);
         });
 }
+#[test]
+fn eth1_fallback_endpoints_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert!(config.eth1.fallback_endpoints.is_empty()));
+}
+#[test]
+fn eth1_fallback_endpoints_flag() {
+    use sensitive_url::SensitiveUrl;
+    let urls = vec!["http://sigp.io/no-way:1337", "http://infura.not_real:4242"];
+    CommandLineTest::new()
+        .flag("eth1-fallback-endpoints", Some(&urls.join(",")))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.eth1.fallback_endpoints,
+                urls.iter()
+                    .map(|url| SensitiveUrl::parse(url).unwrap())
+                    .collect::<Vec<_>>()
+            )
+        });
+}
 
 // Tests for Bellatrix flags.
 fn run_bellatrix_execution_endpoints_flag_test(flag: &str) {
@@ -522,6 +708,81 @@ fn execution_timeout_multiplier_flag() {
         });
 }
 #[test]
+fn engine_record_dir_default() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    CommandLineTest::new()
+        .flag("execution-endpoint", Some("http://meow.cats"))
+        .flag(
+            "execution-jwt",
+            dir.path().join("jwt-file").as_os_str().to_str(),
+        )
+        .run_with_zero_port()
+        .with_config(|config| {
+            let config = config.execution_layer.as_ref().unwrap();
+            assert_eq!(config.engine_record, None);
+        });
+}
+#[test]
+fn engine_record_dir_flag() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    let record_dir = dir.path().join("engine-records");
+    CommandLineTest::new()
+        .flag("execution-endpoint", Some("http://meow.cats"))
+        .flag(
+            "execution-jwt",
+            dir.path().join("jwt-file").as_os_str().to_str(),
+        )
+        .flag("engine-record-dir", record_dir.as_os_str().to_str())
+        .run_with_zero_port()
+        .with_config(|config| {
+            let config = config.execution_layer.as_ref().unwrap();
+            let record_config = config.engine_record.as_ref().unwrap();
+            assert_eq!(record_config.dir, record_dir);
+            assert_eq!(record_config.max_file_size, 100 * 1024 * 1024);
+            assert_eq!(record_config.max_files, 10);
+        });
+}
+#[test]
+fn engine_record_max_size_and_number_flags() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    CommandLineTest::new()
+        .flag("execution-endpoint", Some("http://meow.cats"))
+        .flag(
+            "execution-jwt",
+            dir.path().join("jwt-file").as_os_str().to_str(),
+        )
+        .flag(
+            "engine-record-dir",
+            dir.path().join("engine-records").as_os_str().to_str(),
+        )
+        .flag("engine-record-max-size", Some("2"))
+        .flag("engine-record-max-number", Some("3"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            let config = config.execution_layer.as_ref().unwrap();
+            let record_config = config.engine_record.as_ref().unwrap();
+            assert_eq!(record_config.max_file_size, 2 * 1024 * 1024);
+            assert_eq!(record_config.max_files, 3);
+        });
+}
+#[test]
+#[should_panic]
+fn engine_record_max_number_zero() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    CommandLineTest::new()
+        .flag("execution-endpoint", Some("http://meow.cats"))
+        .flag(
+            "execution-jwt",
+            dir.path().join("jwt-file").as_os_str().to_str(),
+        )
+        .flag(
+            "engine-record-dir",
+            dir.path().join("engine-records").as_os_str().to_str(),
+        )
+        .flag("engine-record-max-number", Some("0"))
+        .run_with_zero_port();
+}
+#[test]
 fn bellatrix_execution_endpoints_flag() {
     run_bellatrix_execution_endpoints_flag_test("execution-endpoints")
 }
@@ -805,6 +1066,31 @@ fn safe_slots_to_import_optimistically_flag() {
             assert_eq!(spec.safe_slots_to_import_optimistically, 421337)
         });
 }
+#[test]
+fn chain_config_overrides_flag() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    let path = dir.path().join("overrides.yaml");
+    std::fs::write(&path, "SECONDS_PER_SLOT: 6\nELECTRA_FORK_EPOCH: 400000\n")
+        .expect("Unable to write overrides");
+    CommandLineTest::new()
+        .flag("chain-config-overrides", path.to_str())
+        .run_with_zero_port()
+        .with_spec::<MainnetEthSpec, _>(|spec| {
+            assert_eq!(spec.seconds_per_slot, 6);
+            assert_eq!(spec.electra_fork_epoch, Some(Epoch::new(400000)));
+            assert_eq!(spec.altair_fork_epoch, Some(Epoch::new(74240)));
+        });
+}
+#[test]
+#[should_panic]
+fn chain_config_overrides_flag_invalid_fork_order() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    let path = dir.path().join("overrides.yaml");
+    std::fs::write(&path, "DENEB_FORK_EPOCH: 1\n").expect("Unable to write overrides");
+    CommandLineTest::new()
+        .flag("chain-config-overrides", path.to_str())
+        .run_with_zero_port();
+}
 
 // Tests for Network flags.
 #[test]
@@ -816,6 +1102,21 @@ fn network_dir_flag() {
         .with_config(|config| assert_eq!(config.network.network_dir, dir.path()));
 }
 #[test]
+fn network_key_file_flag() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    let key_file = dir.path().join("key");
+    CommandLineTest::new()
+        .flag("network-key-file", key_file.to_str())
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.network.network_key_file, Some(key_file.clone())));
+}
+#[test]
+fn network_key_file_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.network.network_key_file, None));
+}
+#[test]
 fn network_target_peers_flag() {
     CommandLineTest::new()
         .flag("target-peers", Some("55"))
@@ -832,6 +1133,13 @@ fn network_subscribe_all_subnets_flag() {
         .with_config(|config| assert!(config.network.subscribe_all_subnets));
 }
 #[test]
+fn network_subscribe_all_data_column_subnets_flag() {
+    CommandLineTest::new()
+        .flag("subscribe-all-data-column-subnets", None)
+        .run_with_zero_port()
+        .with_config(|config| assert!(config.network.subscribe_all_data_column_subnets));
+}
+#[test]
 fn network_import_all_attestations_flag() {
     CommandLineTest::new()
         .flag("import-all-attestations", None)
@@ -1194,6 +1502,19 @@ fn disable_quic_flag() {
         .with_config(|config| assert!(config.network.disable_quic_support));
 }
 #[test]
+fn disable_gossipsub_idontwant_flag() {
+    CommandLineTest::new()
+        .flag("disable-gossipsub-idontwant", None)
+        .run_with_zero_port()
+        .with_config(|config| assert!(config.network.disable_gossipsub_idontwant));
+}
+#[test]
+fn disable_gossipsub_idontwant_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert!(!config.network.disable_gossipsub_idontwant));
+}
+#[test]
 fn disable_peer_scoring_flag() {
     CommandLineTest::new()
         .flag("disable-peer-scoring", None)
@@ -1263,6 +1584,56 @@ fn boot_nodes_multiaddr_flag() {
             );
         });
 }
+// The compressed G1 generator, which is a valid BLS public key.
+const BOOT_NODES_PUBLIC_KEY: &str = "0x97f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb";
+#[test]
+fn boot_nodes_url_flag() {
+    let url = "https://example.com/boot_enr.yaml";
+    let node_id = "0x".to_string() + &"ab".repeat(32);
+    CommandLineTest::new()
+        .flag("boot-nodes-url", Some(url))
+        .flag("boot-nodes-public-key", Some(BOOT_NODES_PUBLIC_KEY))
+        .flag("boot-nodes-allowlist", Some(&node_id))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(config.network.boot_nodes_url.as_deref(), Some(url));
+            assert_eq!(
+                config
+                    .network
+                    .boot_nodes_public_key
+                    .as_ref()
+                    .map(ToString::to_string)
+                    .as_deref(),
+                Some(BOOT_NODES_PUBLIC_KEY)
+            );
+            assert_eq!(config.network.boot_nodes_allowlist, vec![[0xab; 32]]);
+        });
+}
+#[test]
+fn boot_nodes_url_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(config.network.boot_nodes_url, None);
+            assert!(config.network.boot_nodes_public_key.is_none());
+            assert!(config.network.boot_nodes_allowlist.is_empty());
+        });
+}
+#[test]
+#[should_panic]
+fn boot_nodes_url_requires_https() {
+    CommandLineTest::new()
+        .flag("boot-nodes-url", Some("http://example.com/boot_enr.yaml"))
+        .flag("boot-nodes-public-key", Some(BOOT_NODES_PUBLIC_KEY))
+        .run_with_zero_port();
+}
+#[test]
+#[should_panic]
+fn boot_nodes_url_requires_public_key() {
+    CommandLineTest::new()
+        .flag("boot-nodes-url", Some("https://example.com/boot_enr.yaml"))
+        .run_with_zero_port();
+}
 #[test]
 fn private_flag() {
     CommandLineTest::new()
@@ -1674,6 +2045,20 @@ fn http_enable_beacon_processor() {
         .run_with_zero_port()
         .with_config(|config| assert_eq!(config.http_api.enable_beacon_processor, false));
 }
+#[test]
+fn http_enable_profiling() {
+    CommandLineTest::new()
+        .flag("http", None)
+        .run_with_zero_port()
+        .with_config(|config| assert!(!config.http_api.enable_profiling));
+
+    CommandLineTest::new()
+        .flag("http", None)
+        .flag("http-enable-profiling", None)
+        .run_with_zero_port()
+        .with_config(|config| assert!(config.http_api.enable_profiling));
+}
+
 #[test]
 fn http_tls_flags() {
     CommandLineTest::new()
@@ -1798,6 +2183,31 @@ fn validator_monitor_pubkeys_flag() {
         });
 }
 #[test]
+fn validator_monitor_withdrawal_address_flag() {
+    CommandLineTest::new()
+        .flag(
+            "validator-monitor-withdrawal-address",
+            Some("0x00000000219ab540356cbb839cbe05303d7705fa,0x4242424242424242424242424242424242424242"),
+        )
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.validator_monitor.withdrawal_addresses,
+                vec![
+                    Address::from_str("0x00000000219ab540356cbb839cbe05303d7705fa").unwrap(),
+                    Address::from_str("0x4242424242424242424242424242424242424242").unwrap(),
+                ]
+            );
+        });
+}
+#[test]
+#[should_panic]
+fn validator_monitor_withdrawal_address_invalid_flag() {
+    CommandLineTest::new()
+        .flag("validator-monitor-withdrawal-address", Some("0xdeadbeef"))
+        .run_with_zero_port();
+}
+#[test]
 fn validator_monitor_file_flag() {
     let dir = TempDir::new().expect("Unable to create temporary directory");
     let mut file = File::create(dir.path().join("pubkeys.txt")).expect("Unable to create file");
@@ -1932,6 +2342,25 @@ fn auto_compact_db_flag() {
         .with_config(|config| assert!(!config.store.compact_on_prune));
 }
 #[test]
+fn auto_compact_db_io_limit_default() {
+    use beacon_node::beacon_chain::store::config::DEFAULT_COMPACTION_IO_LIMIT_PERCENT;
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.store.compaction_io_limit_percent,
+                DEFAULT_COMPACTION_IO_LIMIT_PERCENT
+            )
+        });
+}
+#[test]
+fn auto_compact_db_io_limit_flag() {
+    CommandLineTest::new()
+        .flag("auto-compact-db-io-limit", Some("20"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.store.compaction_io_limit_percent, 20));
+}
+#[test]
 fn compact_db_flag() {
     CommandLineTest::new()
         .flag("auto-compact-db", Some("false"))
@@ -2023,6 +2452,66 @@ fn epochs_per_migration_override() {
         .with_config(|config| assert_eq!(config.chain.epochs_per_migration, 128));
 }
 
+#[test]
+fn finality_stall_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.chain.finality_stall_epochs,
+                beacon_node::beacon_chain::chain_config::DEFAULT_FINALITY_STALL_EPOCHS
+            );
+            assert!(!config.chain.finality_stall_safe_mode);
+        });
+}
+#[test]
+fn finality_stall_flags() {
+    CommandLineTest::new()
+        .flag("finality-stall-epochs", Some("16"))
+        .flag("finality-stall-safe-mode", None)
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(config.chain.finality_stall_epochs, 16);
+            assert!(config.chain.finality_stall_safe_mode);
+        });
+}
+#[test]
+#[should_panic]
+fn finality_stall_epochs_zero() {
+    CommandLineTest::new()
+        .flag("finality-stall-epochs", Some("0"))
+        .run_with_zero_port();
+}
+
+#[test]
+fn clock_sanity_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.chain.clock_skew_threshold,
+                Duration::from_millis(
+                    beacon_node::beacon_chain::chain_config::DEFAULT_CLOCK_SKEW_THRESHOLD_MS
+                )
+            );
+            assert_eq!(config.chain.ntp_server, None);
+        });
+}
+#[test]
+fn clock_sanity_flags() {
+    CommandLineTest::new()
+        .flag("clock-skew-threshold-ms", Some("500"))
+        .flag("ntp-server", Some("pool.ntp.org"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.chain.clock_skew_threshold,
+                Duration::from_millis(500)
+            );
+            assert_eq!(config.chain.ntp_server.as_deref(), Some("pool.ntp.org"));
+        });
+}
+
 // Tests for Slasher flags.
 // Using `--slasher-max-db-size` to work around https://github.com/sigp/lighthouse/issues/2342
 #[test]
@@ -2110,6 +2599,52 @@ fn slasher_history_length_flag() {
         });
 }
 #[test]
+fn slasher_backfill_flag() {
+    CommandLineTest::new()
+        .flag("slasher", None)
+        .flag("slasher-max-db-size", Some("1"))
+        .flag("slasher-backfill", None)
+        .run_with_zero_port()
+        .with_config(|config| {
+            let slasher_config = config
+                .slasher
+                .as_ref()
+                .expect("Unable to parse Slasher config");
+            assert!(slasher_config.backfill);
+        });
+}
+#[test]
+fn slasher_backfill_default() {
+    CommandLineTest::new()
+        .flag("slasher", None)
+        .flag("slasher-max-db-size", Some("1"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            let slasher_config = config
+                .slasher
+                .as_ref()
+                .expect("Unable to parse Slasher config");
+            assert!(!slasher_config.backfill);
+        });
+}
+#[test]
+fn slasher_remote_flag() {
+    CommandLineTest::new()
+        .flag("http", None)
+        .flag("slasher-remote", None)
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert!(config.remote_slasher);
+            assert!(config.slasher.is_none());
+        });
+}
+#[test]
+fn slasher_remote_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert!(!config.remote_slasher));
+}
+#[test]
 fn slasher_max_db_size_flag() {
     CommandLineTest::new()
         .flag("slasher", None)
@@ -2391,6 +2926,39 @@ fn proposer_re_org_disallowed_offsets_invalid() {
         .run_with_zero_port();
 }
 
+#[test]
+fn unsafe_tuning_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert!(!config.chain.unsafe_tuning());
+            assert_eq!(config.chain.proposer_boost_percentage, None);
+            assert_eq!(config.chain.committee_weight_override, None);
+        });
+}
+
+#[test]
+fn unsafe_tuning_flags() {
+    CommandLineTest::new()
+        .flag("unsafe-tuning", None)
+        .flag("proposer-boost-percentage", Some("70"))
+        .flag("committee-weight-override", Some("32000000000"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert!(config.chain.unsafe_tuning());
+            assert_eq!(config.chain.proposer_boost_percentage, Some(70));
+            assert_eq!(config.chain.committee_weight_override, Some(32_000_000_000));
+        });
+}
+
+#[test]
+#[should_panic]
+fn proposer_boost_percentage_requires_unsafe_tuning() {
+    CommandLineTest::new()
+        .flag("proposer-boost-percentage", Some("70"))
+        .run_with_zero_port();
+}
+
 #[test]
 fn monitoring_endpoint() {
     CommandLineTest::new()
@@ -2665,6 +3233,47 @@ fn http_sse_capacity_multiplier_override() {
         .with_config(|config| assert_eq!(config.http_api.sse_capacity_multiplier, 10));
 }
 
+#[test]
+fn http_route_timeouts_default() {
+    CommandLineTest::new()
+        .flag("http", None)
+        .run_with_zero_port()
+        .with_config(|config| assert!(config.http_api.route_timeouts.is_empty()));
+}
+
+#[test]
+fn http_route_timeouts_flag() {
+    CommandLineTest::new()
+        .flag("http", None)
+        .flag(
+            "http-route-timeouts",
+            Some("/eth/v1/beacon/rewards=30,/eth/v1/beacon/rewards/blocks=5"),
+        )
+        .run_with_zero_port()
+        .with_config(|config| {
+            let http_api = &config.http_api;
+            assert_eq!(http_api.route_timeouts.len(), 2);
+            assert_eq!(
+                http_api.route_timeout("/eth/v1/beacon/rewards/attestations/1"),
+                Some(Duration::from_secs(30))
+            );
+            assert_eq!(
+                http_api.route_timeout("/eth/v1/beacon/rewards/blocks/head"),
+                Some(Duration::from_secs(5))
+            );
+            assert_eq!(http_api.route_timeout("/eth/v1/node/health"), None);
+        });
+}
+
+#[test]
+#[should_panic]
+fn http_route_timeouts_invalid() {
+    CommandLineTest::new()
+        .flag("http", None)
+        .flag("http-route-timeouts", Some("/eth/v1/beacon/rewards"))
+        .run_with_zero_port();
+}
+
 #[test]
 fn http_duplicate_block_status_default() {
     CommandLineTest::new()
@@ -11,6 +11,7 @@ use validator_manager::{
     create_validators::CreateConfig,
     import_validators::ImportConfig,
     move_validators::{MoveConfig, PasswordSource, Validators},
+    update_validators::UpdateConfig,
 };
 
 const EXAMPLE_ETH1_ADDRESS: &str = "0x00000000219ab540356cBB839Cbe05303d7705Fa";
@@ -105,6 +106,12 @@ impl CommandLineTest<MoveConfig> {
     }
 }
 
+impl CommandLineTest<UpdateConfig> {
+    fn validators_update() -> Self {
+        Self::default().flag("update", None)
+    }
+}
+
 #[test]
 pub fn validator_create_without_output_path() {
     CommandLineTest::validators_create().assert_failed();
@@ -394,3 +401,44 @@ pub fn validator_move_count() {
             assert_eq!(expected, config);
         });
 }
+
+#[test]
+pub fn validator_update_defaults() {
+    CommandLineTest::validators_update()
+        .flag("--updates-file", Some("./updates.csv"))
+        .flag("--vc-token", Some("./token.json"))
+        .assert_success(|config| {
+            let expected = UpdateConfig {
+                updates_file_path: PathBuf::from("./updates.csv"),
+                vc_url: SensitiveUrl::parse("http://localhost:5062").unwrap(),
+                vc_token_path: PathBuf::from("./token.json"),
+                dry_run: false,
+            };
+            assert_eq!(expected, config);
+        });
+}
+
+#[test]
+pub fn validator_update_dry_run() {
+    CommandLineTest::validators_update()
+        .flag("--updates-file", Some("./updates.yaml"))
+        .flag("--vc-url", Some("http://localhost:1"))
+        .flag("--vc-token", Some("./token.json"))
+        .flag("--dry-run", None)
+        .assert_success(|config| {
+            let expected = UpdateConfig {
+                updates_file_path: PathBuf::from("./updates.yaml"),
+                vc_url: SensitiveUrl::parse("http://localhost:1").unwrap(),
+                vc_token_path: PathBuf::from("./token.json"),
+                dry_run: true,
+            };
+            assert_eq!(expected, config);
+        });
+}
+
+#[test]
+pub fn validator_update_missing_updates_file() {
+    CommandLineTest::validators_update()
+        .flag("--vc-token", Some("./token.json"))
+        .assert_failed();
+}
@@ -29,6 +29,7 @@ vm_cli=$($CMD vm --help)
 vm_cli_create=$($CMD vm create --help)
 vm_cli_import=$($CMD vm import --help)
 vm_cli_move=$($CMD vm move --help)
+vm_cli_update=$($CMD vm update --help)
 
 general=./help_general.md
 bn=./help_bn.md
@@ -38,6 +39,7 @@ vm=./help_vm.md
 vm_create=./help_vm_create.md
 vm_import=./help_vm_import.md
 vm_move=./help_vm_move.md
+vm_update=./help_vm_update.md
 
 # create .md files
 write_to_file "$general_cli" "$general" "Lighthouse General Commands"
@@ -47,10 +49,11 @@ write_to_file "$vm_cli" "$vm" "Validator Manager"
 write_to_file "$vm_cli_create" "$vm_create" "Validator Manager Create"
 write_to_file "$vm_cli_import" "$vm_import" "Validator Manager Import"
 write_to_file "$vm_cli_move" "$vm_move" "Validator Manager Move"
+write_to_file "$vm_cli_update" "$vm_update" "Validator Manager Update"
 
 #input 1 = $1 = files; input 2 = $2 = new files
-files=(./book/src/help_general.md ./book/src/help_bn.md ./book/src/help_vc.md ./book/src/help_vm.md ./book/src/help_vm_create.md ./book/src/help_vm_import.md ./book/src/help_vm_move.md)
-new_files=($general $bn $vc $vm $vm_create $vm_import $vm_move)
+files=(./book/src/help_general.md ./book/src/help_bn.md ./book/src/help_vc.md ./book/src/help_vm.md ./book/src/help_vm_create.md ./book/src/help_vm_import.md ./book/src/help_vm_move.md ./book/src/help_vm_update.md)
+new_files=($general $bn $vc $vm $vm_create $vm_import $vm_move $vm_update)
 
 # function to check
 check() {
@@ -84,9 +87,10 @@ check ${files[3]} ${new_files[3]}
 check ${files[4]} ${new_files[4]}
 check ${files[5]} ${new_files[5]}
 check ${files[6]} ${new_files[6]}
+check ${files[7]} ${new_files[7]}
 
 # remove help files
-rm -f help_general.md help_bn.md help_vc.md help_am.md help_vm.md help_vm_create.md help_vm_import.md help_vm_move.md
+rm -f help_general.md help_bn.md help_vc.md help_am.md help_vm.md help_vm_create.md help_vm_import.md help_vm_move.md help_vm_update.md
 
 # only exit at the very end
 if [[ $changes == true ]]; then
@@ -0,0 +1,84 @@
+use clap::Parser;
+use clap_utils::{get_color_style, FLAG_HEADER};
+use serde::{Deserialize, Serialize};
+use slasher::DatabaseBackend;
+use std::path::PathBuf;
+
+pub const CMD: &str = "remote_slasher";
+
+#[derive(Parser, Clone, Deserialize, Serialize, Debug)]
+#[clap(
+    name = "remote_slasher",
+    about = "Run a slasher in a separate process to the beacon node. The beacon node must be \
+             started with --slasher-remote. Attestations and blocks are taken from the beacon \
+             node via its HTTP API, and slashings are submitted to its operation pool.",
+    styles = get_color_style(),
+    next_line_help = true,
+    term_width = 80,
+    disable_help_flag = true,
+    disable_help_subcommand = true,
+    display_order = 0,
+)]
+pub struct RemoteSlasher {
+    #[clap(
+        long,
+        value_name = "NETWORK_ADDRESS",
+        default_value = "http://localhost:5052/",
+        help = "Address of the beacon node HTTP API.",
+        display_order = 0
+    )]
+    pub beacon_node: String,
+
+    #[clap(
+        long,
+        value_name = "PATH",
+        help = "Path of the slasher database. Defaults to the slasher_db directory inside the \
+                datadir.",
+        display_order = 0
+    )]
+    pub slasher_dir: Option<PathBuf>,
+
+    #[clap(
+        long,
+        value_name = "SECONDS",
+        help = "Configure how often the slasher takes and processes a batch of attestations \
+                from the beacon node. [default: 12]",
+        display_order = 0
+    )]
+    pub slasher_update_period: Option<u64>,
+
+    #[clap(
+        long,
+        value_name = "EPOCHS",
+        help = "Configure how many epochs of history the slasher keeps. Immutable after \
+                initialization. [default: 4096]",
+        display_order = 0
+    )]
+    pub slasher_history_length: Option<usize>,
+
+    #[clap(
+        long,
+        value_name = "GIGABYTES",
+        help = "Maximum size of the database used by the slasher. [default: 512]",
+        display_order = 0
+    )]
+    pub slasher_max_db_size: Option<usize>,
+
+    #[clap(
+        long,
+        value_name = "DATABASE",
+        help = "Set the database backend to be used by the slasher.",
+        display_order = 0
+    )]
+    pub slasher_backend: Option<DatabaseBackend>,
+
+    #[clap(
+        long,
+        global = true,
+        help = "Prints help information",
+        action = clap::ArgAction::HelpLong,
+        display_order = 0,
+        help_heading = FLAG_HEADER
+    )]
+    help: Option<bool>,
+}
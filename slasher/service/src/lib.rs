@@ -1,3 +1,5 @@
+pub mod cli;
+pub mod remote;
 mod service;
 
 pub use service::SlasherService;
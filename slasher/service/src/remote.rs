@@ -0,0 +1,308 @@
+//! A slasher which runs in a separate process to the beacon node.
+//!
+//! The beacon node (started with `--slasher-remote`) queues the attestations and blocks that it
+//! verifies. This service periodically takes that queue via the HTTP API, processes it with a
+//! local `Slasher` and submits any slashings found to the beacon node's operation pool, from
+//! where they are included in blocks and published to the network.
+use crate::cli::RemoteSlasher;
+use clap::ArgMatches;
+use directory::size_of_dir;
+use environment::RuntimeContext;
+use eth2::{BeaconNodeHttpClient, Timeouts};
+use sensitive_url::SensitiveUrl;
+use slasher::{
+    metrics::{self, SLASHER_DATABASE_SIZE, SLASHER_RUN_TIME},
+    DatabaseBackendOverride, Slasher,
+};
+use slog::{debug, error, info, warn, Logger};
+use slot_clock::{SlotClock, SystemTimeSlotClock};
+use std::sync::Arc;
+use task_executor::TaskExecutor;
+use tokio::time::{interval_at, sleep, Duration, Instant};
+use types::{ChainSpec, Epoch, EthSpec};
+
+/// Timeout for requests to the beacon node.
+const HTTP_TIMEOUT: Duration = Duration::from_secs(12);
+
+/// Delay between attempts to contact the beacon node on startup.
+const RETRY_DELAY: Duration = Duration::from_secs(2);
+
+pub struct Config {
+    pub beacon_node: SensitiveUrl,
+    pub slasher: slasher::Config,
+}
+
+/// Parse the `remote_slasher` subcommand.
+///
+/// The database is stored beneath the global `--datadir` unless `--slasher-dir` is provided.
+pub fn parse_config(
+    cli_args: &ArgMatches,
+    remote_slasher_config: &RemoteSlasher,
+) -> Result<Config, String> {
+    let beacon_node = SensitiveUrl::parse(&remote_slasher_config.beacon_node)
+        .map_err(|e| format!("Invalid beacon node URL: {:?}", e))?;
+
+    let slasher_dir = match &remote_slasher_config.slasher_dir {
+        Some(slasher_dir) => slasher_dir.clone(),
+        None => directory::parse_path_or_default(cli_args, "datadir")?.join("slasher_db"),
+    };
+
+    let mut slasher = slasher::Config::new(slasher_dir);
+    if let Some(update_period) = remote_slasher_config.slasher_update_period {
+        slasher.update_period = update_period;
+    }
+    if let Some(history_length) = remote_slasher_config.slasher_history_length {
+        slasher.history_length = history_length;
+    }
+    if let Some(max_db_size_gbs) = remote_slasher_config.slasher_max_db_size {
+        slasher.max_db_size_mbs = max_db_size_gbs * 1024;
+    }
+    if let Some(backend) = remote_slasher_config.slasher_backend {
+        slasher.backend = backend;
+    }
+
+    Ok(Config {
+        beacon_node,
+        slasher,
+    })
+}
+
+/// Open the slasher database and spawn the service which feeds it from the beacon node.
+pub fn run<E: EthSpec>(mut config: Config, context: RuntimeContext<E>) -> Result<(), String> {
+    let log = context.log().clone();
+
+    match config.slasher.override_backend() {
+        DatabaseBackendOverride::Success(old_backend) => {
+            info!(
+                log,
+                "Slasher backend overridden";
+                "reason" => "database exists",
+                "configured_backend" => %old_backend,
+                "override_backend" => %config.slasher.backend,
+            );
+        }
+        DatabaseBackendOverride::Failure(path) => {
+            warn!(
+                log,
+                "Slasher backend override failed";
+                "advice" => "delete old MDBX database or enable MDBX backend",
+                "path" => path.display()
+            );
+        }
+        DatabaseBackendOverride::Noop => {}
+    }
+
+    let spec = Arc::new(context.eth2_config.spec.clone());
+    let slasher = Arc::new(
+        Slasher::open(config.slasher, spec.clone(), log.clone())
+            .map_err(|e| format!("Slasher open error: {:?}", e))?,
+    );
+    let beacon_node =
+        BeaconNodeHttpClient::new(config.beacon_node, Timeouts::set_all(HTTP_TIMEOUT));
+
+    info!(
+        log,
+        "Starting remote slasher";
+        "beacon_node" => %beacon_node,
+        "database_path" => slasher.config().database_path.display(),
+    );
+
+    let executor = context.executor.clone();
+    context.executor.spawn(
+        async move {
+            let slot_clock = wait_for_genesis::<E>(&beacon_node, &spec, &log).await;
+            run_updates(slasher, beacon_node, slot_clock, spec, executor, log).await
+        },
+        "remote_slasher",
+    );
+
+    Ok(())
+}
+
+/// Wait until the genesis time can be read from the beacon node, returning a slot clock.
+async fn wait_for_genesis<E: EthSpec>(
+    beacon_node: &BeaconNodeHttpClient,
+    spec: &ChainSpec,
+    log: &Logger,
+) -> SystemTimeSlotClock {
+    loop {
+        match beacon_node.get_beacon_genesis().await {
+            Ok(genesis) => {
+                return SystemTimeSlotClock::new(
+                    spec.genesis_slot,
+                    Duration::from_secs(genesis.data.genesis_time),
+                    Duration::from_secs(spec.seconds_per_slot),
+                );
+            }
+            Err(e) => {
+                warn!(
+                    log,
+                    "Unable to contact beacon node";
+                    "error" => %e,
+                    "retry_in" => ?RETRY_DELAY,
+                );
+                sleep(RETRY_DELAY).await;
+            }
+        }
+    }
+}
+
+/// Take a batch from the beacon node every update period and apply it to the slasher.
+async fn run_updates<E: EthSpec>(
+    slasher: Arc<Slasher<E>>,
+    beacon_node: BeaconNodeHttpClient,
+    slot_clock: SystemTimeSlotClock,
+    spec: Arc<ChainSpec>,
+    executor: TaskExecutor,
+    log: Logger,
+) {
+    let slot_offset = Duration::from_secs_f64(slasher.config().slot_offset);
+    let start_instant = if let Some(duration_to_next_slot) = slot_clock.duration_to_next_slot() {
+        Instant::now() + duration_to_next_slot + slot_offset
+    } else {
+        error!(log, "Error aligning slasher to slot clock");
+        Instant::now()
+    };
+    let mut interval = interval_at(
+        start_instant,
+        Duration::from_secs(slasher.config().update_period),
+    );
+
+    // The ID of the last batch processed, which is acknowledged when taking the next batch.
+    let mut ack = None;
+
+    loop {
+        interval.tick().await;
+        let Some(current_epoch) = slot_clock
+            .now()
+            .map(|slot| slot.epoch(E::slots_per_epoch()))
+        else {
+            debug!(log, "Slasher has nothing to do: we are pre-genesis");
+            continue;
+        };
+
+        // If the request fails, the beacon node keeps the batch and hands it out again at the next
+        // update.
+        let mut batch = match beacon_node.post_lighthouse_slasher_dequeue::<E>(ack).await {
+            Ok(response) => response.data,
+            Err(e) => {
+                warn!(
+                    log,
+                    "Unable to take batch from beacon node";
+                    "error" => %e,
+                );
+                continue;
+            }
+        };
+        let batch_id = batch.batch_id;
+        for block_header in std::mem::take(&mut batch.block_headers) {
+            slasher.accept_block_header(block_header);
+        }
+        for attestation in batch.into_attestations() {
+            slasher.accept_attestation(attestation);
+        }
+
+        let processor_slasher = slasher.clone();
+        let Some(handle) = executor.spawn_blocking_handle(
+            move || process_queued(&processor_slasher, current_epoch),
+            "remote_slasher_processor",
+        ) else {
+            // The executor is shutting down.
+            return;
+        };
+        if let Err(e) = handle.await {
+            error!(log, "Remote slasher processor failed"; "error" => ?e);
+            continue;
+        }
+        ack = Some(batch_id);
+
+        submit_slashings(&slasher, &beacon_node, &spec, &log).await;
+    }
+}
+
+/// Process the queued attestations and blocks and prune the database.
+fn process_queued<E: EthSpec>(slasher: &Slasher<E>, current_epoch: Epoch) {
+    let log = slasher.log();
+    let t = Instant::now();
+
+    let batch_timer = metrics::start_timer(&SLASHER_RUN_TIME);
+    let stats = match slasher.process_queued(current_epoch) {
+        Ok(stats) => Some(stats),
+        Err(e) => {
+            error!(
+                log,
+                "Error during scheduled slasher processing";
+                "epoch" => current_epoch,
+                "error" => ?e,
+            );
+            None
+        }
+    };
+    drop(batch_timer);
+
+    // Prune the database, even in the case where batch processing failed.
+    if let Err(e) = slasher.prune_database(current_epoch) {
+        error!(
+            log,
+            "Error during slasher database pruning";
+            "epoch" => current_epoch,
+            "error" => ?e,
+        );
+    }
+
+    let database_size = size_of_dir(&slasher.config().database_path);
+    metrics::set_gauge(&SLASHER_DATABASE_SIZE, database_size as i64);
+
+    if let Some(stats) = stats {
+        debug!(
+            log,
+            "Completed slasher update";
+            "epoch" => current_epoch,
+            "time_taken" => format!("{}ms", t.elapsed().as_millis()),
+            "num_attestations" => stats.attestation_stats.num_processed,
+            "num_blocks" => stats.block_stats.num_processed,
+        );
+    }
+}
+
+/// Submit the slashings found to the beacon node's operation pool.
+///
+/// The beacon node verifies each slashing, so slashings of validators which have already been
+/// slashed are rejected there.
+async fn submit_slashings<E: EthSpec>(
+    slasher: &Slasher<E>,
+    beacon_node: &BeaconNodeHttpClient,
+    spec: &ChainSpec,
+    log: &Logger,
+) {
+    for slashing in slasher.get_attester_slashings() {
+        let fork_name = spec.fork_name_at_epoch(slashing.attestation_1().data().target.epoch);
+        match beacon_node
+            .post_beacon_pool_attester_slashings_v2(&slashing, fork_name)
+            .await
+        {
+            Ok(()) => info!(log, "Submitted attester slashing"; "slashing" => ?slashing),
+            Err(e) => warn!(
+                log,
+                "Beacon node rejected attester slashing";
+                "error" => %e,
+                "slashing" => ?slashing,
+            ),
+        }
+    }
+
+    for slashing in slasher.get_proposer_slashings() {
+        match beacon_node
+            .post_beacon_pool_proposer_slashings(&slashing)
+            .await
+        {
+            Ok(()) => info!(log, "Submitted proposer slashing"; "slashing" => ?slashing),
+            Err(e) => warn!(
+                log,
+                "Beacon node rejected proposer slashing";
+                "error" => %e,
+                "slashing" => ?slashing,
+            ),
+        }
+    }
+}
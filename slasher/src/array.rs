@@ -5,14 +5,19 @@ use crate::{
 };
 use flate2::bufread::{ZlibDecoder, ZlibEncoder};
 use serde::{Deserialize, Serialize};
-use std::borrow::Borrow;
+use std::borrow::{Borrow, Cow};
 use std::collections::{btree_map::Entry, BTreeMap, HashSet};
 use std::io::Read;
+use std::ops::Range;
 use std::sync::Arc;
 use types::{AttesterSlashing, Epoch, EthSpec, IndexedAttestation};
 
 pub const MAX_DISTANCE: u16 = u16::MAX;
 
+/// Offset added to the disk keys of resized chunks whilst they are being written, so that they
+/// don't collide with the keys of the chunks being replaced.
+const RESIZE_KEY_OFFSET: usize = 1 << (usize::BITS - 1);
+
 /// Terminology:
 ///
 /// Let
@@ -51,6 +56,21 @@ impl Chunk {
             .ok_or(Error::ChunkIndexOutOfBounds(cell_index))
     }
 
+    pub fn get_raw_distance(
+        &self,
+        validator_index: u64,
+        epoch: Epoch,
+        config: &Config,
+    ) -> Result<u16, Error> {
+        let validator_offset = config.validator_offset(validator_index);
+        let chunk_offset = config.chunk_offset(epoch);
+        let cell_index = config.cell_index(validator_offset, chunk_offset);
+        self.data
+            .get(cell_index)
+            .copied()
+            .ok_or(Error::ChunkIndexOutOfBounds(cell_index))
+    }
+
     pub fn set_target(
         &mut self,
         validator_index: u64,
@@ -175,6 +195,15 @@ pub trait TargetArrayChunk: Sized + serde::Serialize + serde::de::DeserializeOwn
         config: &Config,
     ) -> Result<(), Error> {
         let disk_key = config.disk_key(validator_chunk_index, chunk_index);
+        self.store_at_disk_key(db, txn, disk_key)
+    }
+
+    fn store_at_disk_key<E: EthSpec>(
+        &self,
+        db: &SlasherDB<E>,
+        txn: &mut RwTransaction<'_>,
+        disk_key: usize,
+    ) -> Result<(), Error> {
         let value = bincode::serialize(self)?;
         let mut encoder = ZlibEncoder::new(&value[..], flate2::Compression::default());
         let mut compressed_value = vec![];
@@ -623,3 +652,137 @@ pub fn update_array<E: EthSpec, T: TargetArrayChunk>(
 
     Ok(slashings)
 }
+
+/// Write re-chunked copies of the `T` chunks of the given validator chunks, in the layout of
+/// `new_config`.
+///
+/// The copies are stored at offset keys so that they don't replace the chunks in the layout of
+/// `old_config`, which are removed by `delete_old_chunks`. Only the distances for epochs within
+/// the history length of both configs are retained, and chunks which would contain only neutral
+/// elements are not stored.
+pub fn copy_resized_chunks<E: EthSpec, T: TargetArrayChunk>(
+    db: &SlasherDB<E>,
+    txn: &mut RwTransaction<'_>,
+    validator_chunk_indices: Range<usize>,
+    old_config: &Config,
+    new_config: &Config,
+) -> Result<(), Error> {
+    let history_length = std::cmp::min(old_config.history_length, new_config.history_length);
+    // Map from `(validator_chunk_index, chunk_index)` to the old chunk at that index, if any.
+    let mut old_chunks = BTreeMap::new();
+
+    for validator_chunk_index in validator_chunk_indices {
+        // Map from chunk index to new chunk at that index.
+        let mut new_chunks = BTreeMap::new();
+
+        for validator_index in new_config.validator_indices_in_chunk(validator_chunk_index) {
+            let Some(current_epoch) = db.get_current_epoch_for_validator(validator_index, txn)?
+            else {
+                continue;
+            };
+            let old_validator_chunk_index = old_config.validator_chunk_index(validator_index);
+            let start_epoch = (current_epoch + 1u64).saturating_sub(history_length as u64);
+
+            for epoch in (start_epoch.as_u64()..=current_epoch.as_u64()).map(Epoch::new) {
+                let old_chunk_index = old_config.chunk_index(epoch);
+                let old_chunk = match old_chunks.entry((old_validator_chunk_index, old_chunk_index))
+                {
+                    Entry::Occupied(occupied) => occupied.into_mut(),
+                    Entry::Vacant(vacant) => vacant.insert(T::load(
+                        db,
+                        txn,
+                        old_validator_chunk_index,
+                        old_chunk_index,
+                        old_config,
+                    )?),
+                };
+                let Some(old_chunk) = old_chunk else {
+                    continue;
+                };
+
+                let distance =
+                    old_chunk
+                        .chunk()
+                        .get_raw_distance(validator_index, epoch, old_config)?;
+                if distance == T::neutral_element() {
+                    continue;
+                }
+                new_chunks
+                    .entry(new_config.chunk_index(epoch))
+                    .or_insert_with(|| T::empty(new_config))
+                    .chunk()
+                    .set_raw_distance(validator_index, epoch, distance, new_config)?;
+            }
+        }
+
+        // Drop the old chunks which only contain validators from this validator chunk or earlier.
+        let next_validator_index =
+            (validator_chunk_index as u64 + 1) * new_config.validator_chunk_size as u64;
+        let next_old_validator_chunk_index = old_config.validator_chunk_index(next_validator_index);
+        old_chunks.retain(|(old_validator_chunk_index, _), _| {
+            *old_validator_chunk_index >= next_old_validator_chunk_index
+        });
+
+        for (chunk_index, chunk) in new_chunks {
+            let disk_key = new_config.disk_key(validator_chunk_index, chunk_index);
+            chunk.store_at_disk_key(db, txn, RESIZE_KEY_OFFSET + disk_key)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Delete up to `limit` of the `T` chunks which were stored before resizing began.
+///
+/// Returns `true` once all of the old chunks have been deleted.
+pub fn delete_old_chunks<E: EthSpec, T: TargetArrayChunk>(
+    db: &SlasherDB<E>,
+    txn: &mut RwTransaction<'_>,
+    limit: usize,
+) -> Result<bool, Error> {
+    let database = T::select_db(db);
+    for _ in 0..limit {
+        let mut cursor = txn.cursor(database)?;
+        let Some(disk_key) = cursor.first_key()?.map(parse_disk_key).transpose()? else {
+            return Ok(true);
+        };
+        if disk_key >= RESIZE_KEY_OFFSET {
+            return Ok(true);
+        }
+        cursor.delete_current()?;
+    }
+    Ok(false)
+}
+
+/// Move up to `limit` of the re-chunked copies written by `copy_resized_chunks` to their final
+/// keys. Must only be called once `delete_old_chunks` has completed.
+///
+/// Returns `true` once all of the copies have been moved.
+pub fn move_resized_chunks<E: EthSpec, T: TargetArrayChunk>(
+    db: &SlasherDB<E>,
+    txn: &mut RwTransaction<'_>,
+    limit: usize,
+) -> Result<bool, Error> {
+    // Start from the last chunk so that the cursor never revisits a moved chunk.
+    let database = T::select_db(db);
+    for _ in 0..limit {
+        let mut cursor = txn.cursor(database)?;
+        let Some(disk_key) = cursor.last_key()?.map(parse_disk_key).transpose()? else {
+            return Ok(true);
+        };
+        if disk_key < RESIZE_KEY_OFFSET {
+            return Ok(true);
+        }
+        let (_, value) = cursor.get_current()?.ok_or(Error::MissingKey)?;
+        let value = value.into_owned();
+        cursor.delete_current()?;
+        cursor.put((disk_key - RESIZE_KEY_OFFSET).to_be_bytes(), value)?;
+    }
+    Ok(false)
+}
+
+fn parse_disk_key(data: Cow<[u8]>) -> Result<usize, Error> {
+    let bytes = <[u8; std::mem::size_of::<usize>()]>::try_from(&data[..])
+        .map_err(|_| Error::ChunkKeyCorrupt { length: data.len() })?;
+    Ok(usize::from_be_bytes(bytes))
+}
@@ -1,16 +1,37 @@
+use crate::metrics::{self, SLASHER_NUM_ATTESTATIONS_DUPLICATE};
 use crate::{AttesterRecord, Config, IndexedAttesterRecord};
+use lru::LruCache;
 use parking_lot::Mutex;
 use std::collections::BTreeMap;
+use std::num::NonZeroUsize;
 use std::sync::{Arc, Weak};
+use types::non_zero_usize::new_non_zero_usize;
 use types::{EthSpec, Hash256, IndexedAttestation};
 
+/// The number of recently queued attestations to remember for de-duplication.
+pub const SEEN_ATTESTATIONS_CACHE_SIZE: NonZeroUsize = new_non_zero_usize(65_536);
+
 /// Staging area for attestations received from the network.
 ///
 /// Attestations are not grouped by validator index at this stage so that they can be easily
 /// filtered for timeliness.
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct AttestationQueue<E: EthSpec> {
     pub queue: Mutex<SimpleBatch<E>>,
+    /// Hashes of recently queued indexed attestations.
+    ///
+    /// The same attestation is often received more than once, e.g. as a gossip aggregate and then
+    /// again in a block, so repeats are ignored rather than queued.
+    seen: Mutex<LruCache<Hash256, ()>>,
+}
+
+impl<E: EthSpec> Default for AttestationQueue<E> {
+    fn default() -> Self {
+        Self {
+            queue: Mutex::new(vec![]),
+            seen: Mutex::new(LruCache::new(SEEN_ATTESTATIONS_CACHE_SIZE)),
+        }
+    }
 }
 
 pub type SimpleBatch<E> = Vec<Arc<IndexedAttesterRecord<E>>>;
@@ -90,6 +111,15 @@ impl<E: EthSpec> AttestationBatch<E> {
 impl<E: EthSpec> AttestationQueue<E> {
     pub fn queue(&self, attestation: IndexedAttestation<E>) {
         let attester_record = AttesterRecord::from(attestation.clone());
+        if self
+            .seen
+            .lock()
+            .put(attester_record.indexed_attestation_hash, ())
+            .is_some()
+        {
+            metrics::inc_counter(&SLASHER_NUM_ATTESTATIONS_DUPLICATE);
+            return;
+        }
         let indexed_record = IndexedAttesterRecord::new(attestation, attester_record);
         self.queue.lock().push(indexed_record);
     }
@@ -110,3 +140,26 @@ impl<E: EthSpec> AttestationQueue<E> {
         self.len() == 0
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_utils::{indexed_att, E};
+
+    #[test]
+    fn duplicate_attestations_ignored() {
+        let queue = AttestationQueue::<E>::default();
+        let att1 = indexed_att([0, 1], 0, 1, 0);
+        let att2 = indexed_att([0, 1], 0, 1, 1);
+
+        queue.queue(att1.clone());
+        queue.queue(att1.clone());
+        queue.queue(att2);
+        assert_eq!(queue.len(), 2);
+
+        // Attestations remain de-duplicated after the queue is drained.
+        queue.dequeue();
+        queue.queue(att1);
+        assert!(queue.is_empty());
+    }
+}
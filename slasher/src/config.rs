@@ -14,6 +14,7 @@ pub const DEFAULT_SLOT_OFFSET: f64 = 10.5;
 pub const DEFAULT_MAX_DB_SIZE: usize = 512 * 1024; // 512 GiB
 pub const DEFAULT_ATTESTATION_ROOT_CACHE_SIZE: NonZeroUsize = new_non_zero_usize(100_000);
 pub const DEFAULT_BROADCAST: bool = false;
+pub const DEFAULT_BACKFILL: bool = false;
 
 #[cfg(all(feature = "mdbx", not(any(feature = "lmdb", feature = "redb"))))]
 pub const DEFAULT_BACKEND: DatabaseBackend = DatabaseBackend::Mdbx;
@@ -46,6 +47,8 @@ pub struct Config {
     pub attestation_root_cache_size: NonZeroUsize,
     /// Whether to broadcast slashings found to the network.
     pub broadcast: bool,
+    /// Whether to process the attestations and proposals of blocks imported by backfill sync.
+    pub backfill: bool,
     /// Database backend to use.
     pub backend: DatabaseBackend,
 }
@@ -91,6 +94,7 @@ impl Config {
             max_db_size_mbs: DEFAULT_MAX_DB_SIZE,
             attestation_root_cache_size: DEFAULT_ATTESTATION_ROOT_CACHE_SIZE,
             broadcast: DEFAULT_BROADCAST,
+            backfill: DEFAULT_BACKFILL,
             backend: DEFAULT_BACKEND,
         }
     }
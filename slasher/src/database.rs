@@ -3,6 +3,8 @@ mod lmdb_impl;
 mod mdbx_impl;
 mod redb_impl;
 
+use crate::config::DiskConfig;
+use crate::resize::ResizeProgress;
 use crate::{
     metrics, AttesterRecord, AttesterSlashingStatus, CompactAttesterRecord, Config, Error,
     ProposerSlashingStatus,
@@ -59,6 +61,8 @@ const MAX_NUM_DBS: usize = 9;
 const METADATA_VERSION_KEY: &[u8] = &[0];
 /// Constant key under which the slasher configuration is stored in the `metadata_db`.
 const METADATA_CONFIG_KEY: &[u8] = &[1];
+/// Constant key under which the progress of an unfinished resize is stored in the `metadata_db`.
+const METADATA_RESIZE_PROGRESS_KEY: &[u8] = &[2];
 
 const ATTESTER_KEY_SIZE: usize = 7;
 const PROPOSER_KEY_SIZE: usize = 16;
@@ -156,6 +160,14 @@ impl CurrentEpochKey {
             validator_index: validator_index.to_be_bytes(),
         }
     }
+
+    pub fn parse(data: Cow<[u8]>) -> Result<u64, Error> {
+        if data.len() == CURRENT_EPOCH_KEY_SIZE {
+            Ok(BigEndian::read_u64(&data))
+        } else {
+            Err(Error::CurrentEpochKeyCorrupt { length: data.len() })
+        }
+    }
 }
 
 impl AsRef<[u8]> for CurrentEpochKey {
@@ -288,6 +300,35 @@ fn ssz_decode<T: Decode>(bytes: Cow<[u8]>) -> Result<T, Error> {
 
 impl<E: EthSpec> SlasherDB<E> {
     pub fn open(config: Arc<Config>, spec: Arc<ChainSpec>, log: Logger) -> Result<Self, Error> {
+        let db = Self::open_without_config_check(config, spec, log)?;
+
+        let mut txn = db.begin_rw_txn()?;
+        if let Some(progress) = db.load_resize_progress(&mut txn)? {
+            return Err(Error::ResizeInProgress { to: progress.to });
+        }
+        if let Some(on_disk_config) = db.load_config(&mut txn)? {
+            let current_disk_config = db.config.disk_config();
+            if current_disk_config != on_disk_config {
+                return Err(Error::ConfigIncompatible {
+                    on_disk_config,
+                    config: current_disk_config,
+                });
+            }
+        }
+        db.store_schema_version(&mut txn)?;
+        db.store_config(&db.config.disk_config(), &mut txn)?;
+        txn.commit()?;
+
+        Ok(db)
+    }
+
+    /// Open the database and run any schema migrations, without checking that the chunk
+    /// parameters of `config` match those of the database.
+    pub(crate) fn open_without_config_check(
+        config: Arc<Config>,
+        spec: Arc<ChainSpec>,
+        log: Logger,
+    ) -> Result<Self, Error> {
         info!(log, "Opening slasher database"; "backend" => %config.backend);
 
         std::fs::create_dir_all(&config.database_path)?;
@@ -316,18 +357,6 @@ impl<E: EthSpec> SlasherDB<E> {
 
         db = db.migrate()?;
 
-        let mut txn = db.begin_rw_txn()?;
-        if let Some(on_disk_config) = db.load_config(&mut txn)? {
-            let current_disk_config = db.config.disk_config();
-            if current_disk_config != on_disk_config {
-                return Err(Error::ConfigIncompatible {
-                    on_disk_config,
-                    config: current_disk_config,
-                });
-            }
-        }
-        txn.commit()?;
-
         Ok(db)
     }
 
@@ -363,7 +392,11 @@ impl<E: EthSpec> SlasherDB<E> {
             .transpose()
     }
 
-    pub fn store_config(&self, config: &Config, txn: &mut RwTransaction<'_>) -> Result<(), Error> {
+    pub fn store_config(
+        &self,
+        config: &DiskConfig,
+        txn: &mut RwTransaction<'_>,
+    ) -> Result<(), Error> {
         txn.put(
             &self.databases.metadata_db,
             METADATA_CONFIG_KEY,
@@ -372,6 +405,32 @@ impl<E: EthSpec> SlasherDB<E> {
         Ok(())
     }
 
+    pub(crate) fn load_resize_progress(
+        &self,
+        txn: &mut RwTransaction<'_>,
+    ) -> Result<Option<ResizeProgress>, Error> {
+        txn.get(&self.databases.metadata_db, METADATA_RESIZE_PROGRESS_KEY)?
+            .map(bincode_deserialize)
+            .transpose()
+    }
+
+    pub(crate) fn store_resize_progress(
+        &self,
+        progress: &ResizeProgress,
+        txn: &mut RwTransaction<'_>,
+    ) -> Result<(), Error> {
+        txn.put(
+            &self.databases.metadata_db,
+            METADATA_RESIZE_PROGRESS_KEY,
+            &bincode::serialize(progress)?,
+        )?;
+        Ok(())
+    }
+
+    pub(crate) fn delete_resize_progress(&self, txn: &mut RwTransaction<'_>) -> Result<(), Error> {
+        txn.del(&self.databases.metadata_db, METADATA_RESIZE_PROGRESS_KEY)
+    }
+
     pub fn get_attester_max_target(
         &self,
         validator_index: u64,
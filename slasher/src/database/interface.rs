@@ -231,6 +231,19 @@ impl<'env> Cursor<'env> {
         }
     }
 
+    /// Return the key and value at the cursor's current position.
+    pub fn get_current(&mut self) -> Result<Option<(Key, Value)>, Error> {
+        match self {
+            #[cfg(feature = "mdbx")]
+            Cursor::Mdbx(cursor) => cursor.get_current(),
+            #[cfg(feature = "lmdb")]
+            Cursor::Lmdb(cursor) => cursor.get_current(),
+            #[cfg(feature = "redb")]
+            Cursor::Redb(cursor) => cursor.get_current(),
+            _ => Err(Error::MismatchedDatabaseVariant),
+        }
+    }
+
     pub fn delete_current(&mut self) -> Result<(), Error> {
         match self {
             #[cfg(feature = "mdbx")]
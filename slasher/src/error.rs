@@ -39,6 +39,10 @@ pub enum Error {
         config: DiskConfig,
     },
     ConfigMissing,
+    /// A resize of the database to `to` was interrupted and must be completed before use.
+    ResizeInProgress {
+        to: DiskConfig,
+    },
     DistanceTooLarge,
     DistanceCalculationOverflow,
     /// Missing an attester record that we expected to exist.
@@ -55,6 +59,12 @@ pub enum Error {
     ProposerKeyCorrupt {
         length: usize,
     },
+    CurrentEpochKeyCorrupt {
+        length: usize,
+    },
+    ChunkKeyCorrupt {
+        length: usize,
+    },
     IndexedAttestationIdKeyCorrupt {
         length: usize,
     },
@@ -14,6 +14,7 @@ mod database;
 mod error;
 pub mod metrics;
 mod migrate;
+mod resize;
 mod slasher;
 pub mod test_utils;
 
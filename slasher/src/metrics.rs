@@ -18,6 +18,10 @@ lazy_static! {
         "slasher_num_attestations_deferred",
         "Number of attestations deferred per batch"
     );
+    pub static ref SLASHER_NUM_ATTESTATIONS_DUPLICATE: Result<IntCounter> = try_create_int_counter(
+        "slasher_num_attestations_duplicate_total",
+        "Number of attestations ignored because they were recently queued"
+    );
     pub static ref SLASHER_NUM_ATTESTATIONS_VALID: Result<IntGauge> = try_create_int_gauge(
         "slasher_num_attestations_valid",
         "Number of valid attestations per batch"
@@ -0,0 +1,586 @@
+use crate::array::{
+    copy_resized_chunks, delete_old_chunks, move_resized_chunks, MaxTargetChunk, MinTargetChunk,
+    TargetArrayChunk,
+};
+use crate::config::DiskConfig;
+use crate::database::{AttesterKey, CurrentEpochKey};
+use crate::{CompactAttesterRecord, Config, Database, Error, RwTransaction, SlasherDB};
+use serde::{Deserialize, Serialize};
+use slog::{info, warn, Logger};
+use std::sync::Arc;
+use types::{ChainSpec, Epoch, EthSpec};
+
+/// The number of validators whose data is re-chunked or re-keyed in each transaction.
+const VALIDATORS_PER_BATCH: u64 = 4_096;
+/// The number of chunks deleted or moved in each transaction.
+const CHUNKS_PER_BATCH: usize = 4_096;
+
+/// One of the min-max target arrays.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TargetArray {
+    Min,
+    Max,
+}
+
+/// The next step of a resize, which is performed in many transactions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ResizeStage {
+    /// Writing re-chunked copies of the chunks of `array`, from `next_validator_chunk_index` in the
+    /// new layout.
+    CopyChunks {
+        array: TargetArray,
+        next_validator_chunk_index: usize,
+    },
+    /// Deleting the chunks of `array` in the old layout.
+    DeleteOldChunks { array: TargetArray },
+    /// Moving the re-chunked copies of the chunks of `array` to their final keys.
+    MoveChunks { array: TargetArray },
+    /// Re-keying the attester records, from `next_validator_index`.
+    AttesterRecords { next_validator_index: u64 },
+}
+
+/// The progress of a resize, stored in the database so that an interrupted resize can be resumed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResizeProgress {
+    pub from: DiskConfig,
+    pub to: DiskConfig,
+    pub stage: ResizeStage,
+}
+
+impl<E: EthSpec> SlasherDB<E> {
+    /// Open the database, re-chunking it if it was created with different chunk parameters to
+    /// those of `config`.
+    pub fn open_and_resize(
+        config: Arc<Config>,
+        spec: Arc<ChainSpec>,
+        log: Logger,
+    ) -> Result<Self, Error> {
+        let db = Self::open_without_config_check(config.clone(), spec, log.clone())?;
+        db.resize(config, &log)
+    }
+
+    /// Re-chunk the min-max target arrays and re-key the attester records of the database to
+    /// match the chunk parameters of `config`, returning a database which uses `config`.
+    ///
+    /// The resize is split across many transactions and its progress is recorded in the database,
+    /// so an interrupted resize is resumed by resizing to the same `config` again. The database
+    /// can't be opened normally until the resize completes.
+    ///
+    /// Data for epochs older than the new history length is discarded.
+    pub fn resize(self, config: Arc<Config>, log: &Logger) -> Result<Self, Error> {
+        self.resize_in_batches(config, log, VALIDATORS_PER_BATCH, CHUNKS_PER_BATCH)
+    }
+
+    fn resize_in_batches(
+        mut self,
+        config: Arc<Config>,
+        log: &Logger,
+        validators_per_batch: u64,
+        chunks_per_batch: usize,
+    ) -> Result<Self, Error> {
+        config.validate()?;
+        let new_disk_config = config.disk_config();
+
+        let mut txn = self.begin_rw_txn()?;
+        let mut progress = if let Some(progress) = self.load_resize_progress(&mut txn)? {
+            if progress.to != new_disk_config {
+                return Err(Error::ResizeInProgress { to: progress.to });
+            }
+            info!(
+                log,
+                "Resuming slasher database resize";
+                "from" => ?progress.from,
+                "to" => ?progress.to,
+                "stage" => ?progress.stage,
+            );
+            progress
+        } else {
+            // Databases created before the config was stored use the default parameters.
+            let on_disk_config = match self.load_config::<DiskConfig>(&mut txn)? {
+                Some(on_disk_config) => on_disk_config,
+                None => {
+                    let default_config = Config::new(config.database_path.clone()).disk_config();
+                    warn!(
+                        log,
+                        "Slasher database config missing, assuming defaults";
+                        "config" => ?default_config,
+                    );
+                    default_config
+                }
+            };
+
+            if on_disk_config == new_disk_config {
+                info!(
+                    log,
+                    "Slasher database does not require resizing";
+                    "config" => ?on_disk_config,
+                );
+                self.store_config(&new_disk_config, &mut txn)?;
+                txn.commit()?;
+                self.config = config;
+                return Ok(self);
+            }
+
+            info!(
+                log,
+                "Resizing slasher database";
+                "from" => ?on_disk_config,
+                "to" => ?new_disk_config,
+            );
+            ResizeProgress {
+                from: on_disk_config,
+                to: new_disk_config.clone(),
+                stage: ResizeStage::CopyChunks {
+                    array: TargetArray::Min,
+                    next_validator_chunk_index: 0,
+                },
+            }
+        };
+
+        let old_config = Config {
+            chunk_size: progress.from.chunk_size,
+            validator_chunk_size: progress.from.validator_chunk_size,
+            history_length: progress.from.history_length,
+            ..(*config).clone()
+        };
+        old_config.validate()?;
+
+        self.store_resize_progress(&progress, &mut txn)?;
+        txn.commit()?;
+
+        loop {
+            let mut txn = self.begin_rw_txn()?;
+            let next_stage = self.resize_batch(
+                &mut txn,
+                progress.stage,
+                &old_config,
+                &config,
+                validators_per_batch,
+                chunks_per_batch,
+            )?;
+
+            if let Some(stage) = next_stage {
+                progress.stage = stage;
+                self.store_resize_progress(&progress, &mut txn)?;
+                txn.commit()?;
+            } else {
+                self.store_config(&new_disk_config, &mut txn)?;
+                self.delete_resize_progress(&mut txn)?;
+                txn.commit()?;
+                break;
+            }
+        }
+
+        info!(log, "Finished resizing slasher database");
+
+        self.config = config;
+        Ok(self)
+    }
+
+    /// Perform a batch of the work of `stage`, returning the next stage or `None` if the resize
+    /// is complete.
+    fn resize_batch(
+        &self,
+        txn: &mut RwTransaction<'_>,
+        stage: ResizeStage,
+        old_config: &Config,
+        new_config: &Config,
+        validators_per_batch: u64,
+        chunks_per_batch: usize,
+    ) -> Result<Option<ResizeStage>, Error> {
+        let array = match stage {
+            ResizeStage::CopyChunks { array, .. }
+            | ResizeStage::DeleteOldChunks { array }
+            | ResizeStage::MoveChunks { array } => array,
+            ResizeStage::AttesterRecords {
+                next_validator_index,
+            } => {
+                return self.resize_attester_records(
+                    txn,
+                    next_validator_index,
+                    validators_per_batch,
+                    old_config,
+                    new_config,
+                );
+            }
+        };
+
+        let next_stage = match array {
+            TargetArray::Min => self.resize_array_batch::<MinTargetChunk>(
+                txn,
+                stage,
+                old_config,
+                new_config,
+                validators_per_batch,
+                chunks_per_batch,
+            )?,
+            TargetArray::Max => self.resize_array_batch::<MaxTargetChunk>(
+                txn,
+                stage,
+                old_config,
+                new_config,
+                validators_per_batch,
+                chunks_per_batch,
+            )?,
+        };
+
+        // Resize the arrays one after the other, and then the attester records.
+        Ok(Some(next_stage.unwrap_or(match array {
+            TargetArray::Min => ResizeStage::CopyChunks {
+                array: TargetArray::Max,
+                next_validator_chunk_index: 0,
+            },
+            TargetArray::Max => ResizeStage::AttesterRecords {
+                next_validator_index: 0,
+            },
+        })))
+    }
+
+    /// Perform a batch of the work of `stage` for the `T` array, returning the next stage or
+    /// `None` if the array has been resized.
+    fn resize_array_batch<T: TargetArrayChunk>(
+        &self,
+        txn: &mut RwTransaction<'_>,
+        stage: ResizeStage,
+        old_config: &Config,
+        new_config: &Config,
+        validators_per_batch: u64,
+        chunks_per_batch: usize,
+    ) -> Result<Option<ResizeStage>, Error> {
+        match stage {
+            ResizeStage::CopyChunks {
+                array,
+                next_validator_chunk_index,
+            } => {
+                let end = self
+                    .max_validator_index(&self.databases.current_epochs_db, txn)?
+                    .map_or(0, |index| new_config.validator_chunk_index(index) + 1);
+                let batch_size = std::cmp::max(
+                    validators_per_batch as usize / new_config.validator_chunk_size,
+                    1,
+                );
+                let batch_end = std::cmp::min(next_validator_chunk_index + batch_size, end);
+
+                copy_resized_chunks::<E, T>(
+                    self,
+                    txn,
+                    next_validator_chunk_index..batch_end,
+                    old_config,
+                    new_config,
+                )?;
+
+                Ok(Some(if batch_end < end {
+                    ResizeStage::CopyChunks {
+                        array,
+                        next_validator_chunk_index: batch_end,
+                    }
+                } else {
+                    ResizeStage::DeleteOldChunks { array }
+                }))
+            }
+            ResizeStage::DeleteOldChunks { array } => Ok(Some(
+                if delete_old_chunks::<E, T>(self, txn, chunks_per_batch)? {
+                    ResizeStage::MoveChunks { array }
+                } else {
+                    stage
+                },
+            )),
+            ResizeStage::MoveChunks { .. } => {
+                if move_resized_chunks::<E, T>(self, txn, chunks_per_batch)? {
+                    Ok(None)
+                } else {
+                    Ok(Some(stage))
+                }
+            }
+            ResizeStage::AttesterRecords { .. } => Ok(Some(stage)),
+        }
+    }
+
+    /// Re-key the attester records of a batch of validators from `next_validator_index`, from the
+    /// history length of `old_config` to the history length of `new_config`.
+    ///
+    /// Returns the next stage, or `None` if all of the attester records have been re-keyed.
+    fn resize_attester_records(
+        &self,
+        txn: &mut RwTransaction<'_>,
+        next_validator_index: u64,
+        validators_per_batch: u64,
+        old_config: &Config,
+        new_config: &Config,
+    ) -> Result<Option<ResizeStage>, Error> {
+        if old_config.history_length == new_config.history_length {
+            return Ok(None);
+        }
+
+        let Some(max_validator_index) =
+            self.max_validator_index(&self.databases.attesters_max_targets_db, txn)?
+        else {
+            return Ok(None);
+        };
+        let batch_end = std::cmp::min(
+            next_validator_index + validators_per_batch,
+            max_validator_index + 1,
+        );
+
+        for validator_index in next_validator_index..batch_end {
+            let Some(max_target) = self.get_attester_max_target(validator_index, txn)? else {
+                continue;
+            };
+
+            // Remove all of the validator's records, retaining those which are within the new
+            // history length.
+            let start_epoch = (max_target + 1u64).saturating_sub(old_config.history_length as u64);
+            let mut records = vec![];
+
+            for target_epoch in (start_epoch.as_u64()..=max_target.as_u64()).map(Epoch::new) {
+                let attester_key = AttesterKey::new(validator_index, target_epoch, old_config);
+                let Some(record) = txn
+                    .get(&self.databases.attesters_db, attester_key.as_ref())?
+                    .map(CompactAttesterRecord::parse)
+                    .transpose()?
+                else {
+                    continue;
+                };
+                txn.del(&self.databases.attesters_db, attester_key)?;
+
+                if !record.is_null() && target_epoch + new_config.history_length as u64 > max_target
+                {
+                    records.push((target_epoch, record));
+                }
+            }
+
+            for (target_epoch, record) in records {
+                txn.put(
+                    &self.databases.attesters_db,
+                    AttesterKey::new(validator_index, target_epoch, new_config),
+                    record.as_bytes(),
+                )?;
+            }
+        }
+
+        if batch_end <= max_validator_index {
+            Ok(Some(ResizeStage::AttesterRecords {
+                next_validator_index: batch_end,
+            }))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Return the greatest validator index in `database`, which must be keyed by
+    /// `CurrentEpochKey`.
+    fn max_validator_index(
+        &self,
+        database: &Database,
+        txn: &mut RwTransaction<'_>,
+    ) -> Result<Option<u64>, Error> {
+        let mut cursor = txn.cursor(database)?;
+        cursor.last_key()?.map(CurrentEpochKey::parse).transpose()
+    }
+}
+
+#[cfg(all(test, any(feature = "mdbx", feature = "lmdb", feature = "redb")))]
+mod test {
+    use super::*;
+    use crate::test_utils::{chain_spec, E};
+    use crate::IndexedAttestationId;
+    use logging::test_logger;
+    use std::ops::Range;
+    use tempfile::{tempdir, TempDir};
+
+    const CURRENT_EPOCH: Epoch = Epoch::new(40);
+    const VALIDATORS: Range<u64> = 0..5;
+
+    fn distance(validator_index: u64, epoch: Epoch) -> u16 {
+        (validator_index + epoch.as_u64()) as u16
+    }
+
+    fn configs() -> (TempDir, Arc<Config>, Arc<Config>) {
+        let tempdir = tempdir().unwrap();
+        let mut old_config = Config::new(tempdir.path().into());
+        old_config.chunk_size = 4;
+        old_config.validator_chunk_size = 2;
+        old_config.history_length = 32;
+
+        let mut new_config = old_config.clone();
+        new_config.chunk_size = 8;
+        new_config.validator_chunk_size = 3;
+        new_config.history_length = 16;
+
+        (tempdir, Arc::new(old_config), Arc::new(new_config))
+    }
+
+    /// Store a distance and an attester record for every validator and epoch in the history of
+    /// `config`.
+    fn populate(db: &SlasherDB<E>, config: &Config) {
+        let mut txn = db.begin_rw_txn().unwrap();
+        for validator_index in VALIDATORS {
+            db.update_current_epoch_for_validator(validator_index, CURRENT_EPOCH, &mut txn)
+                .unwrap();
+
+            let validator_chunk_index = config.validator_chunk_index(validator_index);
+            let start_epoch = (CURRENT_EPOCH + 1u64).saturating_sub(config.history_length as u64);
+            for epoch in (start_epoch.as_u64()..=CURRENT_EPOCH.as_u64()).map(Epoch::new) {
+                let chunk_index = config.chunk_index(epoch);
+                let mut chunk =
+                    MinTargetChunk::load(db, &mut txn, validator_chunk_index, chunk_index, config)
+                        .unwrap()
+                        .unwrap_or_else(|| MinTargetChunk::empty(config));
+                chunk
+                    .chunk()
+                    .set_raw_distance(
+                        validator_index,
+                        epoch,
+                        distance(validator_index, epoch),
+                        config,
+                    )
+                    .unwrap();
+                chunk
+                    .store(db, &mut txn, validator_chunk_index, chunk_index, config)
+                    .unwrap();
+
+                db.update_attester_max_target(validator_index, None, epoch, &mut txn)
+                    .unwrap();
+                txn.put(
+                    &db.databases.attesters_db,
+                    AttesterKey::new(validator_index, epoch, config),
+                    IndexedAttestationId::new(epoch.as_u64()),
+                )
+                .unwrap();
+            }
+        }
+        txn.commit().unwrap();
+    }
+
+    /// Check that the epochs within the history length of `config` are retained.
+    fn check_resized(db: &SlasherDB<E>, config: &Config) {
+        let mut txn = db.begin_rw_txn().unwrap();
+        assert_eq!(
+            db.load_config::<DiskConfig>(&mut txn).unwrap(),
+            Some(config.disk_config())
+        );
+        assert_eq!(db.load_resize_progress(&mut txn).unwrap(), None);
+
+        for validator_index in VALIDATORS {
+            let validator_chunk_index = config.validator_chunk_index(validator_index);
+            let start_epoch = CURRENT_EPOCH.as_u64() + 1 - config.history_length as u64;
+            for epoch in (start_epoch..=CURRENT_EPOCH.as_u64()).map(Epoch::new) {
+                let mut chunk = MinTargetChunk::load(
+                    db,
+                    &mut txn,
+                    validator_chunk_index,
+                    config.chunk_index(epoch),
+                    config,
+                )
+                .unwrap()
+                .unwrap();
+                assert_eq!(
+                    chunk
+                        .chunk()
+                        .get_raw_distance(validator_index, epoch, config)
+                        .unwrap(),
+                    distance(validator_index, epoch)
+                );
+
+                let record = db
+                    .get_attester_record(&mut txn, validator_index, epoch, Some(CURRENT_EPOCH))
+                    .unwrap()
+                    .unwrap();
+                assert_eq!(
+                    record.indexed_attestation_id,
+                    IndexedAttestationId::new(epoch.as_u64())
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn resize_chunks_and_attester_records() {
+        let (_tempdir, old_config, new_config) = configs();
+        let log = test_logger();
+        let db = SlasherDB::<E>::open(old_config.clone(), chain_spec(), log.clone()).unwrap();
+        populate(&db, &old_config);
+
+        // Use small batches so that each stage takes several transactions.
+        let db = db
+            .resize_in_batches(new_config.clone(), &log, 2, 3)
+            .unwrap();
+        check_resized(&db, &new_config);
+    }
+
+    /// Perform the first few batches of a resize, as if it were interrupted.
+    fn interrupt_resize(db: &SlasherDB<E>, old_config: &Config, new_config: &Config) {
+        let mut progress = ResizeProgress {
+            from: old_config.disk_config(),
+            to: new_config.disk_config(),
+            stage: ResizeStage::CopyChunks {
+                array: TargetArray::Min,
+                next_validator_chunk_index: 0,
+            },
+        };
+        for _ in 0..3 {
+            let mut txn = db.begin_rw_txn().unwrap();
+            progress.stage = db
+                .resize_batch(&mut txn, progress.stage, old_config, new_config, 2, 3)
+                .unwrap()
+                .unwrap();
+            db.store_resize_progress(&progress, &mut txn).unwrap();
+            txn.commit().unwrap();
+        }
+        assert_eq!(
+            progress.stage,
+            ResizeStage::DeleteOldChunks {
+                array: TargetArray::Min
+            }
+        );
+    }
+
+    #[test]
+    fn resume_interrupted_resize() {
+        let (_tempdir, old_config, new_config) = configs();
+        let log = test_logger();
+        let db = SlasherDB::<E>::open(old_config.clone(), chain_spec(), log.clone()).unwrap();
+        populate(&db, &old_config);
+        interrupt_resize(&db, &old_config, &new_config);
+
+        let db = db
+            .resize_in_batches(new_config.clone(), &log, 2, 3)
+            .unwrap();
+        check_resized(&db, &new_config);
+    }
+
+    #[test]
+    fn resume_interrupted_resize_with_other_config() {
+        let (_tempdir, old_config, new_config) = configs();
+        let log = test_logger();
+        let db = SlasherDB::<E>::open(old_config.clone(), chain_spec(), log.clone()).unwrap();
+        populate(&db, &old_config);
+        interrupt_resize(&db, &old_config, &new_config);
+
+        let mut other_config = (*new_config).clone();
+        other_config.history_length = 8;
+        match db.resize(Arc::new(other_config), &log) {
+            Err(Error::ResizeInProgress { to }) => assert_eq!(to, new_config.disk_config()),
+            other => panic!("unexpected result: {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn resize_without_stored_config() {
+        let (tempdir, _, new_config) = configs();
+        let default_config = Arc::new(Config::new(tempdir.path().into()));
+        let log = test_logger();
+
+        // Databases created before the config was stored use the default parameters.
+        let db = SlasherDB::<E>::open_without_config_check(
+            default_config.clone(),
+            chain_spec(),
+            log.clone(),
+        )
+        .unwrap();
+        populate(&db, &default_config);
+
+        let db = db.resize(new_config.clone(), &log).unwrap();
+        check_resized(&db, &new_config);
+    }
+}
@@ -48,9 +48,6 @@ excluded_paths = [
     "tests/.*/electra/ssz_static/LightClientUpdate",
     "tests/.*/electra/ssz_static/LightClientFinalityUpdate",
     "tests/.*/electra/ssz_static/LightClientBootstrap",
-    # TODO(electra) re-enable as DepositRequest when EF tests are updated
-    "tests/.*/electra/operations/deposit_receipt",
-    "tests/.*/electra/ssz_static/DepositReceipt"
 ]
 
 
@@ -60,14 +60,14 @@ type_name_generic!(BeaconState);
 type_name!(BlobIdentifier);
 type_name_generic!(BlobSidecar);
 type_name!(Checkpoint);
-type_name!(Consolidation);
+type_name!(ConsolidationRequest);
 type_name_generic!(ContributionAndProof);
 type_name!(Deposit);
 type_name!(DepositData);
 type_name!(DepositMessage);
 type_name!(DepositRequest);
 type_name!(Eth1Data);
-type_name!(ExecutionLayerWithdrawalRequest);
+type_name!(ExecutionLayerWithdrawalRequest, "WithdrawalRequest");
 type_name_generic!(ExecutionPayload);
 type_name_generic!(ExecutionPayloadBellatrix, "ExecutionPayload");
 type_name_generic!(ExecutionPayloadCapella, "ExecutionPayload");
@@ -139,7 +139,6 @@ type_name_generic!(SignedAggregateAndProofBase, "SignedAggregateAndProof");
 type_name_generic!(SignedAggregateAndProofElectra, "SignedAggregateAndProof");
 type_name_generic!(SignedBeaconBlock);
 type_name!(SignedBeaconBlockHeader);
-type_name!(SignedConsolidation);
 type_name_generic!(SignedContributionAndProof);
 type_name!(SignedVoluntaryExit);
 type_name!(SigningData);
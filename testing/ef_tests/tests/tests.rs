@@ -101,16 +101,14 @@ fn operations_execution_layer_withdrawal_reqeusts() {
 #[test]
 #[cfg(not(feature = "fake_crypto"))]
 fn operations_deposit_requests() {
-    //TODO(electra): re-enable mainnet once they update the name for this
-    // OperationsHandler::<MinimalEthSpec, DepositRequest>::default().run();
-    // OperationsHandler::<MainnetEthSpec, DepositRequest>::default().run();
+    OperationsHandler::<MinimalEthSpec, DepositRequest>::default().run();
+    OperationsHandler::<MainnetEthSpec, DepositRequest>::default().run();
 }
 
 #[test]
-fn operations_consolidations() {
-    OperationsHandler::<MinimalEthSpec, SignedConsolidation>::default().run();
-    //TODO(electra): re-enable mainnet once they make tests for this
-    //OperationsHandler::<MainnetEthSpec, SignedConsolidation>::default().run();
+fn operations_consolidation_requests() {
+    OperationsHandler::<MinimalEthSpec, ConsolidationRequest>::default().run();
+    OperationsHandler::<MainnetEthSpec, ConsolidationRequest>::default().run();
 }
 
 #[test]
@@ -243,9 +241,8 @@ mod ssz_static {
     use types::blob_sidecar::BlobIdentifier;
     use types::historical_summary::HistoricalSummary;
     use types::{
-        AttesterSlashingBase, AttesterSlashingElectra, Consolidation,
-        ExecutionLayerWithdrawalRequest, LightClientBootstrapAltair, PendingBalanceDeposit,
-        PendingPartialWithdrawal, *,
+        AttesterSlashingBase, AttesterSlashingElectra, ExecutionLayerWithdrawalRequest,
+        LightClientBootstrapAltair, PendingBalanceDeposit, PendingPartialWithdrawal, *,
     };
 
     ssz_static_test!(attestation_data, AttestationData);
@@ -630,17 +627,16 @@ mod ssz_static {
     }
 
     #[test]
-    fn consolidation() {
-        SszStaticHandler::<Consolidation, MinimalEthSpec>::electra_and_later().run();
-        SszStaticHandler::<Consolidation, MainnetEthSpec>::electra_and_later().run();
+    fn consolidation_request() {
+        SszStaticHandler::<ConsolidationRequest, MinimalEthSpec>::electra_and_later().run();
+        SszStaticHandler::<ConsolidationRequest, MainnetEthSpec>::electra_and_later().run();
     }
 
-    // TODO(electra) re-enable when EF tests are updated
-    // #[test]
-    // fn deposit_request() {
-    //     SszStaticHandler::<DepositRequest, MinimalEthSpec>::electra_and_later().run();
-    //     SszStaticHandler::<DepositRequest, MainnetEthSpec>::electra_and_later().run();
-    // }
+    #[test]
+    fn deposit_request() {
+        SszStaticHandler::<DepositRequest, MinimalEthSpec>::electra_and_later().run();
+        SszStaticHandler::<DepositRequest, MainnetEthSpec>::electra_and_later().run();
+    }
 
     #[test]
     fn execution_layer_withdrawal_request() {
@@ -667,12 +663,6 @@ mod ssz_static {
         SszStaticHandler::<PendingPartialWithdrawal, MinimalEthSpec>::electra_and_later().run();
         SszStaticHandler::<PendingPartialWithdrawal, MainnetEthSpec>::electra_and_later().run();
     }
-
-    #[test]
-    fn signed_consolidation() {
-        SszStaticHandler::<SignedConsolidation, MinimalEthSpec>::electra_and_later().run();
-        SszStaticHandler::<SignedConsolidation, MainnetEthSpec>::electra_and_later().run();
-    }
 }
 
 #[test]
@@ -0,0 +1,318 @@
+use crate::messages::{craft_block, MessageContext, MessageKind};
+use crate::metrics::peer_report_counts;
+use clap::ArgMatches;
+use eth2::types::BlockId;
+use eth2::{BeaconNodeHttpClient, Timeouts};
+use lighthouse_network::service::Network;
+use lighthouse_network::types::GossipKind;
+use lighthouse_network::{Context, EnrExt, NetworkConfig, NetworkEvent, PubsubMessage};
+use rand::distributions::{Distribution, WeightedIndex};
+use sensitive_url::SensitiveUrl;
+use slog::{info, warn, Logger};
+use std::collections::HashMap;
+use std::net::Ipv4Addr;
+use std::sync::{Arc, Weak};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::runtime::Runtime;
+use types::{ChainSpec, Config, EthSpec, ForkContext, Slot};
+
+const HTTP_TIMEOUT: Duration = Duration::from_secs(10);
+/// How long to wait for the target node to accept our connection.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(30);
+/// How long to keep the network running after the last message, giving the target node time to
+/// receive and process it.
+const FLUSH_DURATION: Duration = Duration::from_secs(5);
+
+pub struct FuzzerConfig {
+    beacon_url: SensitiveUrl,
+    metrics_url: Option<SensitiveUrl>,
+    rate: f64,
+    duration: Duration,
+    weights: Vec<(MessageKind, u32)>,
+}
+
+impl FuzzerConfig {
+    pub fn from_matches(matches: &ArgMatches) -> Result<Self, String> {
+        let parse = |name: &str| -> Result<&String, String> {
+            matches
+                .get_one::<String>(name)
+                .ok_or_else(|| format!("missing {}", name))
+        };
+        let parse_weight = |name: &str| -> Result<u32, String> {
+            parse(name)?
+                .parse()
+                .map_err(|e| format!("invalid {}: {:?}", name, e))
+        };
+
+        let beacon_url = SensitiveUrl::parse(parse("beacon-url")?)
+            .map_err(|e| format!("invalid beacon-url: {:?}", e))?;
+        let metrics_url = matches
+            .get_one::<String>("metrics-url")
+            .map(|url| SensitiveUrl::parse(url))
+            .transpose()
+            .map_err(|e| format!("invalid metrics-url: {:?}", e))?;
+        let rate = parse("rate")?
+            .parse::<f64>()
+            .map_err(|e| format!("invalid rate: {:?}", e))?;
+        if rate.is_nan() || rate <= 0.0 {
+            return Err("rate must be greater than zero".to_string());
+        }
+        let duration = parse("duration")?
+            .parse::<u64>()
+            .map(Duration::from_secs)
+            .map_err(|e| format!("invalid duration: {:?}", e))?;
+
+        let weights = vec![
+            (
+                MessageKind::BadSignature,
+                parse_weight("bad-signature-weight")?,
+            ),
+            (MessageKind::FutureSlot, parse_weight("future-slot-weight")?),
+            (
+                MessageKind::DuplicateProposal,
+                parse_weight("duplicate-proposal-weight")?,
+            ),
+        ];
+
+        Ok(Self {
+            beacon_url,
+            metrics_url,
+            rate,
+            duration,
+            weights,
+        })
+    }
+}
+
+pub async fn run<E: EthSpec>(
+    runtime: Weak<Runtime>,
+    config: FuzzerConfig,
+    log: Logger,
+) -> Result<(), String> {
+    let client =
+        BeaconNodeHttpClient::new(config.beacon_url.clone(), Timeouts::set_all(HTTP_TIMEOUT));
+
+    /*
+     * Learn about the target node and its network.
+     */
+    let spec_config = client
+        .get_config_spec::<Config>()
+        .await
+        .map_err(|e| format!("Unable to get spec: {:?}", e))?
+        .data;
+    let spec = ChainSpec::from_config::<E>(&spec_config)
+        .ok_or("Spec is incompatible with the selected preset")?;
+    let genesis = client
+        .get_beacon_genesis()
+        .await
+        .map_err(|e| format!("Unable to get genesis: {:?}", e))?
+        .data;
+    let target_enr = client
+        .get_node_identity()
+        .await
+        .map_err(|e| format!("Unable to get node identity: {:?}", e))?
+        .data
+        .enr;
+    let target_peer_id = target_enr.peer_id();
+
+    let current_slot = || {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+        Slot::new(now.saturating_sub(genesis.genesis_time) / spec.seconds_per_slot)
+    };
+
+    /*
+     * Start a libp2p service which dials the target node.
+     */
+    let network_dir = tempfile::Builder::new()
+        .prefix("gossip_fuzzer")
+        .tempdir()
+        .map_err(|e| format!("Unable to create network dir: {:?}", e))?;
+    let mut network_config = NetworkConfig::default();
+    network_config.set_ipv4_listening_address(Ipv4Addr::UNSPECIFIED, 0, 0, 0);
+    network_config.network_dir = network_dir.path().to_path_buf();
+    network_config.libp2p_nodes = target_enr.multiaddr_p2p_tcp();
+    network_config.disable_discovery = true;
+    network_config.upnp_enabled = false;
+
+    let (signal, exit) = async_channel::bounded(1);
+    let (shutdown_tx, _) = futures::channel::mpsc::channel(1);
+    let executor = task_executor::TaskExecutor::new(runtime, exit, log.clone(), shutdown_tx);
+    let context = Context {
+        config: &network_config,
+        enr_fork_id: spec.enr_fork_id::<E>(current_slot(), genesis.genesis_validators_root),
+        fork_context: Arc::new(ForkContext::new::<E>(
+            current_slot(),
+            genesis.genesis_validators_root,
+            &spec,
+        )),
+        chain_spec: &spec,
+        libp2p_registry: None,
+    };
+    let (mut network, _) = Network::<E>::new(executor, context, &log)
+        .await
+        .map_err(|e| format!("Unable to start network: {:?}", e))?;
+    network.subscribe_kind(GossipKind::BeaconBlock);
+
+    tokio::time::timeout(CONNECT_TIMEOUT, async {
+        loop {
+            match network.next_event().await {
+                NetworkEvent::PeerConnectedOutgoing(peer_id) if peer_id == target_peer_id => break,
+                _ => continue,
+            }
+        }
+    })
+    .await
+    .map_err(|_| format!("Unable to connect to {}", target_peer_id))?;
+    info!(log, "Connected to target node"; "peer_id" => %target_peer_id);
+
+    /*
+     * Publish invalid messages until the duration elapses.
+     */
+    let report_counts_before = match &config.metrics_url {
+        Some(url) => Some(peer_report_counts(url).await?),
+        None => None,
+    };
+
+    let distribution = WeightedIndex::new(config.weights.iter().map(|(_, weight)| *weight))
+        .map_err(|e| format!("Invalid message weights: {:?}", e))?;
+    let mut sent = HashMap::<MessageKind, u64>::new();
+    let mut connected = true;
+    let mut interval = tokio::time::interval(Duration::from_secs_f64(1.0 / config.rate));
+    let deadline = tokio::time::sleep(config.duration);
+    tokio::pin!(deadline);
+
+    loop {
+        tokio::select! {
+            _ = &mut deadline => break,
+            event = network.next_event() => {
+                connected = handle_event(event, target_peer_id, connected, &log);
+            }
+            _ = interval.tick() => {
+                if !connected {
+                    continue;
+                }
+
+                let head_block = client
+                    .get_beacon_blocks::<E>(BlockId::Head)
+                    .await
+                    .map_err(|e| format!("Unable to get head block: {:?}", e))?
+                    .ok_or("Head block not found")?
+                    .data;
+                let kind = config.weights[distribution.sample(&mut rand::thread_rng())].0;
+                let block = craft_block(
+                    kind,
+                    &MessageContext {
+                        head_block: &head_block,
+                        head_block_root: head_block.canonical_root(),
+                        current_slot: current_slot(),
+                        genesis_validators_root: genesis.genesis_validators_root,
+                        spec: &spec,
+                    },
+                );
+
+                network.publish(vec![PubsubMessage::BeaconBlock(Arc::new(block))]);
+                *sent.entry(kind).or_default() += 1;
+            }
+        }
+    }
+
+    // Keep the network running so the final messages are delivered.
+    let _ = tokio::time::timeout(FLUSH_DURATION, async {
+        loop {
+            network.next_event().await;
+        }
+    })
+    .await;
+
+    for (kind, _) in &config.weights {
+        info!(
+            log,
+            "Published invalid messages";
+            "kind" => ?kind,
+            "count" => sent.get(kind).copied().unwrap_or(0),
+        );
+    }
+
+    /*
+     * Check that the target node flagged every message it received.
+     */
+    let result = match (&config.metrics_url, report_counts_before) {
+        (Some(url), Some(before)) => {
+            let after = peer_report_counts(url).await?;
+            check_peer_reports(&sent, &before, &after, &log)
+        }
+        _ => Ok(()),
+    };
+
+    drop(network);
+    drop(signal);
+    result
+}
+
+/// Tracks the connection to the target node, returning whether we are still connected.
+fn handle_event<E: EthSpec>(
+    event: NetworkEvent<E>,
+    target_peer_id: lighthouse_network::PeerId,
+    connected: bool,
+    log: &Logger,
+) -> bool {
+    match event {
+        NetworkEvent::PeerDisconnected(peer_id) if peer_id == target_peer_id => {
+            warn!(log, "Disconnected from target node, pausing publishing");
+            false
+        }
+        NetworkEvent::PeerConnectedOutgoing(peer_id)
+        | NetworkEvent::PeerConnectedIncoming(peer_id)
+            if peer_id == target_peer_id =>
+        {
+            info!(log, "Reconnected to target node");
+            true
+        }
+        _ => connected,
+    }
+}
+
+/// Checks that the node raised (at least) one peer report for every message that was sent.
+///
+/// Other peers may trigger the same reports, so the counts are treated as a lower bound.
+fn check_peer_reports(
+    sent: &HashMap<MessageKind, u64>,
+    before: &HashMap<String, u64>,
+    after: &HashMap<String, u64>,
+    log: &Logger,
+) -> Result<(), String> {
+    let mut failures = vec![];
+    for (kind, &count) in sent {
+        let msg = kind.peer_report_msg();
+        let reported = after
+            .get(msg)
+            .copied()
+            .unwrap_or(0)
+            .saturating_sub(before.get(msg).copied().unwrap_or(0));
+        info!(
+            log,
+            "Peer reports raised by target node";
+            "kind" => ?kind,
+            "sent" => count,
+            "reported" => reported,
+        );
+        if reported < count {
+            failures.push(format!(
+                "{:?}: sent {}, node reported {}",
+                kind, count, reported
+            ));
+        }
+    }
+
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(format!(
+            "Node did not flag all invalid messages: {}",
+            failures.join("; ")
+        ))
+    }
+}
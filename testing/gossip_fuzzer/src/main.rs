@@ -0,0 +1,172 @@
+//! A libp2p peer which connects to a running beacon node and publishes invalid gossip to it.
+//!
+//! The fuzzer injects three kinds of invalid beacon blocks, each chosen at random according to a
+//! configurable weight:
+//!
+//! - **Bad signature**: a copy of the head block with a modified state root and a random
+//!   signature. The node should *reject* these.
+//! - **Future slot**: a child of the head block from a slot well beyond the current slot. The node
+//!   should *ignore* these.
+//! - **Duplicate proposal**: a copy of the head block with a modified state root, re-signed by the
+//!   proposer. The node should flag these as equivocations. Signing requires the proposer's key,
+//!   so this is only possible on networks using the deterministic interop keys (e.g. the
+//!   simulator). Elsewhere these degrade to bad signatures.
+//!
+//! When the node's metrics endpoint is supplied, the fuzzer compares the peer reports raised by
+//! the node against the number of messages of each kind it sent, and exits with an error if the
+//! node failed to flag any of them.
+//!
+//! Since the node penalizes the fuzzer for every invalid message, high rates will quickly get the
+//! fuzzer banned. Messages published whilst disconnected are not counted.
+//!
+//! ## Example
+//!
+//! ```ignore
+//! gossip_fuzzer \
+//!     --beacon-url http://localhost:5052 \
+//!     --metrics-url http://localhost:5054 \
+//!     --rate 0.5 \
+//!     --duration 120
+//! ```
+mod fuzzer;
+mod messages;
+mod metrics;
+
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use fuzzer::FuzzerConfig;
+use slog::{o, Drain};
+use std::sync::Arc;
+use tokio::runtime::Runtime;
+use types::{EthSpec, MainnetEthSpec, MinimalEthSpec};
+
+fn cli_app() -> Command {
+    Command::new("gossip_fuzzer")
+        .author("Sigma Prime <contact@sigmaprime.io>")
+        .about("Connects to a beacon node and injects invalid gossip at configurable rates.")
+        .arg(
+            Arg::new("beacon-url")
+                .long("beacon-url")
+                .value_name("URL")
+                .action(ArgAction::Set)
+                .required(true)
+                .help("HTTP API of the target node, used to find its ENR, spec and head block."),
+        )
+        .arg(
+            Arg::new("metrics-url")
+                .long("metrics-url")
+                .value_name("URL")
+                .action(ArgAction::Set)
+                .help(
+                    "Metrics endpoint of the target node. If supplied, the node's peer reports \
+                    are checked against the messages sent.",
+                ),
+        )
+        .arg(
+            Arg::new("spec")
+                .long("spec")
+                .value_name("PRESET")
+                .action(ArgAction::Set)
+                .default_value("mainnet")
+                .value_parser(["mainnet", "minimal"])
+                .help("The preset of the target network."),
+        )
+        .arg(
+            Arg::new("rate")
+                .long("rate")
+                .value_name("MESSAGES_PER_SECOND")
+                .action(ArgAction::Set)
+                .default_value("1")
+                .help("Number of invalid messages to publish per second."),
+        )
+        .arg(
+            Arg::new("duration")
+                .long("duration")
+                .value_name("SECONDS")
+                .action(ArgAction::Set)
+                .default_value("60")
+                .help("Number of seconds to publish messages for."),
+        )
+        .arg(
+            Arg::new("bad-signature-weight")
+                .long("bad-signature-weight")
+                .value_name("WEIGHT")
+                .action(ArgAction::Set)
+                .default_value("1")
+                .help("Relative weight of blocks with invalid signatures."),
+        )
+        .arg(
+            Arg::new("future-slot-weight")
+                .long("future-slot-weight")
+                .value_name("WEIGHT")
+                .action(ArgAction::Set)
+                .default_value("1")
+                .help("Relative weight of blocks from future slots."),
+        )
+        .arg(
+            Arg::new("duplicate-proposal-weight")
+                .long("duplicate-proposal-weight")
+                .value_name("WEIGHT")
+                .action(ArgAction::Set)
+                .default_value("0")
+                .help(
+                    "Relative weight of equivocating blocks. Requires the network to use the \
+                    deterministic interop validator keys.",
+                ),
+        )
+        .arg(
+            Arg::new("debug-level")
+                .short('d')
+                .long("debug-level")
+                .action(ArgAction::Set)
+                .default_value("info")
+                .value_parser(["info", "debug", "trace", "warn", "error", "crit"])
+                .help("Set the severity level of the logs."),
+        )
+}
+
+fn main() {
+    let matches = cli_app().get_matches();
+    if let Err(e) = run(&matches) {
+        eprintln!("Gossip fuzzer exited with error: {}", e);
+        std::process::exit(1)
+    }
+}
+
+fn run(matches: &ArgMatches) -> Result<(), String> {
+    let config = FuzzerConfig::from_matches(matches)?;
+    let log = build_log(matches)?;
+
+    let runtime = Arc::new(
+        tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| format!("Unable to build runtime: {:?}", e))?,
+    );
+
+    match matches.get_one::<String>("spec").map(String::as_str) {
+        Some("minimal") => run_with_spec::<MinimalEthSpec>(runtime, config, log),
+        _ => run_with_spec::<MainnetEthSpec>(runtime, config, log),
+    }
+}
+
+fn run_with_spec<E: EthSpec>(
+    runtime: Arc<Runtime>,
+    config: FuzzerConfig,
+    log: slog::Logger,
+) -> Result<(), String> {
+    let weak_runtime = Arc::downgrade(&runtime);
+    runtime.block_on(fuzzer::run::<E>(weak_runtime, config, log))
+}
+
+fn build_log(matches: &ArgMatches) -> Result<slog::Logger, String> {
+    let level = matches
+        .get_one::<String>("debug-level")
+        .ok_or("missing debug-level")?
+        .parse::<slog::Level>()
+        .map_err(|()| "invalid debug-level".to_string())?;
+
+    let decorator = slog_term::TermDecorator::new().build();
+    let drain = slog_term::FullFormat::new(decorator).build().fuse();
+    let drain = slog_async::Async::new(drain).build().fuse();
+    Ok(slog::Logger::root(drain.filter_level(level).fuse(), o!()))
+}
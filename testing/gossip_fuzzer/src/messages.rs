@@ -0,0 +1,62 @@
+use bls::Keypair;
+use types::test_utils::generate_deterministic_keypair;
+use types::{ChainSpec, EthSpec, Hash256, SignedBeaconBlock, Slot};
+
+/// How far beyond the current slot "future slot" blocks are produced. This comfortably exceeds
+/// the maximum gossip clock disparity.
+const FUTURE_SLOT_OFFSET: u64 = 4;
+
+/// A kind of invalid gossip message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MessageKind {
+    BadSignature,
+    FutureSlot,
+    DuplicateProposal,
+}
+
+impl MessageKind {
+    /// The message attached to the peer report the target node raises when it receives a message
+    /// of this kind. These are exposed via the `libp2p_report_peer_msgs_total` metric.
+    pub fn peer_report_msg(self) -> &'static str {
+        match self {
+            MessageKind::BadSignature => "gossip_block_low",
+            MessageKind::FutureSlot => "gossip_block_high",
+            MessageKind::DuplicateProposal => "gossip_block_mid",
+        }
+    }
+}
+
+/// The chain information required to craft invalid messages.
+pub struct MessageContext<'a, E: EthSpec> {
+    pub head_block: &'a SignedBeaconBlock<E>,
+    pub head_block_root: Hash256,
+    pub current_slot: Slot,
+    pub genesis_validators_root: Hash256,
+    pub spec: &'a ChainSpec,
+}
+
+/// Returns an invalid block of the given `kind`.
+pub fn craft_block<E: EthSpec>(kind: MessageKind, ctx: &MessageContext<E>) -> SignedBeaconBlock<E> {
+    let (mut block, _) = ctx.head_block.clone().deconstruct();
+
+    match kind {
+        MessageKind::BadSignature => {
+            // Change the root so the block isn't dismissed as already known.
+            *block.state_root_mut() = Hash256::random();
+            let signature = Keypair::random().sk.sign(Hash256::random());
+            SignedBeaconBlock::from_block(block, signature)
+        }
+        MessageKind::FutureSlot => {
+            *block.slot_mut() = ctx.current_slot + FUTURE_SLOT_OFFSET;
+            *block.parent_root_mut() = ctx.head_block_root;
+            let signature = Keypair::random().sk.sign(Hash256::random());
+            SignedBeaconBlock::from_block(block, signature)
+        }
+        MessageKind::DuplicateProposal => {
+            *block.state_root_mut() = Hash256::random();
+            let proposer = generate_deterministic_keypair(block.proposer_index() as usize);
+            let fork = ctx.spec.fork_at_epoch(block.epoch());
+            block.sign(&proposer.sk, &fork, ctx.genesis_validators_root, ctx.spec)
+        }
+    }
+}
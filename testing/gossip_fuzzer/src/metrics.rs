@@ -0,0 +1,43 @@
+use sensitive_url::SensitiveUrl;
+use std::collections::HashMap;
+
+/// The metric counting the peer reports raised by a node, labelled by `msg`.
+const REPORT_PEER_MSGS_METRIC: &str = "libp2p_report_peer_msgs_total";
+
+/// Scrapes the metrics endpoint at `metrics_url` and returns the number of peer reports the node
+/// has raised, keyed by report message.
+pub async fn peer_report_counts(
+    metrics_url: &SensitiveUrl,
+) -> Result<HashMap<String, u64>, String> {
+    let url = metrics_url
+        .full
+        .join("metrics")
+        .map_err(|e| format!("Invalid metrics URL: {:?}", e))?;
+    let text = reqwest::get(url)
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| format!("Unable to scrape metrics: {:?}", e))?
+        .text()
+        .await
+        .map_err(|e| format!("Unable to read metrics: {:?}", e))?;
+
+    Ok(parse_peer_report_counts(&text))
+}
+
+/// Parses lines of the form `libp2p_report_peer_msgs_total{msg="gossip_block_low"} 3`.
+fn parse_peer_report_counts(text: &str) -> HashMap<String, u64> {
+    text.lines()
+        .filter_map(|line| {
+            let labels = line
+                .strip_prefix(REPORT_PEER_MSGS_METRIC)?
+                .strip_prefix('{')?;
+            let (labels, value) = labels.split_once('}')?;
+            let msg = labels
+                .split(',')
+                .find_map(|label| label.strip_prefix("msg=\""))?
+                .strip_suffix('"')?;
+            let value = value.trim().parse::<f64>().ok()?;
+            Some((msg.to_string(), value as u64))
+        })
+        .collect()
+}
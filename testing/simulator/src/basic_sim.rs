@@ -1,13 +1,13 @@
 use crate::local_network::LocalNetworkParams;
 use crate::local_network::TERMINAL_BLOCK;
-use crate::{checks, LocalNetwork};
+use crate::{checks, LocalNetwork, E};
 use clap::ArgMatches;
 
 use crate::retry::with_retry;
 use futures::prelude::*;
 use node_test_rig::{
-    environment::{EnvironmentBuilder, LoggerConfig},
-    testing_validator_config, ApiTopic, ValidatorFiles,
+    environment::{Environment, EnvironmentBuilder, LoggerConfig, RuntimeContext},
+    testing_validator_config, ApiTopic, ClientConfig, MockExecutionConfig, ValidatorFiles,
 };
 use rayon::prelude::*;
 use std::cmp::max;
@@ -17,7 +17,7 @@ use types::{Epoch, EthSpec, MinimalEthSpec};
 
 const END_EPOCH: u64 = 16;
 const GENESIS_DELAY: u64 = 32;
-const ALTAIR_FORK_EPOCH: u64 = 0;
+pub(crate) const ALTAIR_FORK_EPOCH: u64 = 0;
 const BELLATRIX_FORK_EPOCH: u64 = 0;
 const CAPELLA_FORK_EPOCH: u64 = 1;
 const DENEB_FORK_EPOCH: u64 = 2;
@@ -26,6 +26,23 @@ const DENEB_FORK_EPOCH: u64 = 2;
 const SUGGESTED_FEE_RECIPIENT: [u8; 20] =
     [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1];
 
+/// The shape of the network started by `build_environment` and `start_network`.
+pub(crate) struct BasicSimParams {
+    pub node_count: usize,
+    pub proposer_nodes: usize,
+    /// Beacon nodes which are added by the simulation after the network has started.
+    pub extra_nodes: usize,
+    pub validators_per_node: usize,
+    pub speed_up_factor: u64,
+    pub log_level: String,
+}
+
+impl BasicSimParams {
+    fn total_validator_count(&self) -> usize {
+        self.validators_per_node * self.node_count
+    }
+}
+
 pub fn run_basic_sim(matches: &ArgMatches) -> Result<(), String> {
     let node_count = matches
         .get_one::<String>("nodes")
@@ -63,60 +80,23 @@ pub fn run_basic_sim(matches: &ArgMatches) -> Result<(), String> {
     println!(" speed-up-factor: {}", speed_up_factor);
     println!(" continue-after-checks: {}", continue_after_checks);
 
-    // Generate the directories and keystores required for the validator clients.
-    let validator_files = (0..node_count)
-        .into_par_iter()
-        .map(|i| {
-            println!(
-                "Generating keystores for validator {} of {}",
-                i + 1,
-                node_count
-            );
-
-            let indices =
-                (i * validators_per_node..(i + 1) * validators_per_node).collect::<Vec<_>>();
-            ValidatorFiles::with_keystores(&indices).unwrap()
-        })
-        .collect::<Vec<_>>();
-
-    let mut env = EnvironmentBuilder::minimal()
-        .initialize_logger(LoggerConfig {
-            path: None,
-            debug_level: log_level.clone(),
-            logfile_debug_level: log_level.clone(),
-            log_format: None,
-            logfile_format: None,
-            log_color: false,
-            disable_log_timestamp: false,
-            max_log_size: 0,
-            max_log_number: 0,
-            compression: false,
-            is_restricted: true,
-            sse_logging: false,
-        })?
-        .multi_threaded_tokio_runtime()?
-        .build()?;
-
-    let spec = &mut env.eth2_config.spec;
+    let params = BasicSimParams {
+        node_count,
+        proposer_nodes,
+        extra_nodes,
+        validators_per_node,
+        speed_up_factor,
+        log_level: log_level.clone(),
+    };
+    let (mut env, validator_files) = build_environment(&params)?;
 
-    let total_validator_count = validators_per_node * node_count;
-    let genesis_delay = GENESIS_DELAY;
+    let spec = &env.eth2_config.spec;
 
     // Convenience variables. Update these values when adding a newer fork.
     let latest_fork_version = spec.deneb_fork_version;
     let latest_fork_start_epoch = DENEB_FORK_EPOCH;
 
-    spec.seconds_per_slot /= speed_up_factor;
-    spec.seconds_per_slot = max(1, spec.seconds_per_slot);
-    spec.genesis_delay = genesis_delay;
-    spec.min_genesis_time = 0;
-    spec.min_genesis_active_validator_count = total_validator_count as u64;
-    spec.altair_fork_epoch = Some(Epoch::new(ALTAIR_FORK_EPOCH));
-    spec.bellatrix_fork_epoch = Some(Epoch::new(BELLATRIX_FORK_EPOCH));
-    spec.capella_fork_epoch = Some(Epoch::new(CAPELLA_FORK_EPOCH));
-    spec.deneb_fork_epoch = Some(Epoch::new(DENEB_FORK_EPOCH));
-    //spec.electra_fork_epoch = Some(Epoch::new(ELECTRA_FORK_EPOCH));
-
+    let total_validator_count = params.total_validator_count();
     let slot_duration = Duration::from_secs(spec.seconds_per_slot);
     let slots_per_epoch = MinimalEthSpec::slots_per_epoch();
     let initial_validator_count = spec.min_genesis_active_validator_count as usize;
@@ -124,87 +104,8 @@ pub fn run_basic_sim(matches: &ArgMatches) -> Result<(), String> {
     let context = env.core_context();
 
     let main_future = async {
-        /*
-         * Create a new `LocalNetwork` with one beacon node.
-         */
-        let max_retries = 3;
-        let (network, beacon_config, mock_execution_config) = with_retry(max_retries, || {
-            Box::pin(LocalNetwork::create_local_network(
-                None,
-                None,
-                LocalNetworkParams {
-                    validator_count: total_validator_count,
-                    node_count,
-                    extra_nodes,
-                    proposer_nodes,
-                    genesis_delay,
-                },
-                context.clone(),
-            ))
-        })
-        .await?;
-
-        // Add nodes to the network.
-        for _ in 0..node_count {
-            network
-                .add_beacon_node(beacon_config.clone(), mock_execution_config.clone(), false)
-                .await?;
-        }
-
-        /*
-         * One by one, add proposer nodes to the network.
-         */
-        for _ in 0..proposer_nodes {
-            println!("Adding a proposer node");
-            network
-                .add_beacon_node(beacon_config.clone(), mock_execution_config.clone(), true)
-                .await?;
-        }
-
-        /*
-         * One by one, add validators to the network.
-         */
-
-        let executor = context.executor.clone();
-        for (i, files) in validator_files.into_iter().enumerate() {
-            let network_1 = network.clone();
-            executor.spawn(
-                async move {
-                    let mut validator_config = testing_validator_config();
-                    validator_config.fee_recipient = Some(SUGGESTED_FEE_RECIPIENT.into());
-                    println!("Adding validator client {}", i);
-
-                    // Enable broadcast on every 4th node.
-                    if i % 4 == 0 {
-                        validator_config.broadcast_topics = ApiTopic::all();
-                        let beacon_nodes = vec![i, (i + 1) % node_count];
-                        network_1
-                            .add_validator_client_with_fallbacks(
-                                validator_config,
-                                i,
-                                beacon_nodes,
-                                files,
-                            )
-                            .await
-                    } else {
-                        network_1
-                            .add_validator_client(validator_config, i, files)
-                            .await
-                    }
-                    .expect("should add validator");
-                },
-                "vc",
-            );
-        }
-
-        // Set all payloads as valid. This effectively assumes the EL is infalliable.
-        network.execution_nodes.write().iter().for_each(|node| {
-            node.server.all_payloads_valid();
-        });
-
-        let duration_to_genesis = network.duration_to_genesis().await;
-        println!("Duration to genesis: {}", duration_to_genesis.as_secs());
-        sleep(duration_to_genesis).await;
+        let (network, beacon_config, mock_execution_config) =
+            start_network(&params, validator_files, context).await?;
 
         /*
          * Start the checks that ensure the network performs as expected.
@@ -345,3 +246,155 @@ pub fn run_basic_sim(matches: &ArgMatches) -> Result<(), String> {
 
     Ok(())
 }
+
+/// Generate the keystores for the validator clients, and build the environment with a spec for
+/// the network described by `params`.
+pub(crate) fn build_environment(
+    params: &BasicSimParams,
+) -> Result<(Environment<E>, Vec<ValidatorFiles>), String> {
+    let node_count = params.node_count;
+    let validators_per_node = params.validators_per_node;
+
+    // Generate the directories and keystores required for the validator clients.
+    let validator_files = (0..node_count)
+        .into_par_iter()
+        .map(|i| {
+            println!(
+                "Generating keystores for validator {} of {}",
+                i + 1,
+                node_count
+            );
+
+            let indices =
+                (i * validators_per_node..(i + 1) * validators_per_node).collect::<Vec<_>>();
+            ValidatorFiles::with_keystores(&indices).unwrap()
+        })
+        .collect::<Vec<_>>();
+
+    let mut env = EnvironmentBuilder::minimal()
+        .initialize_logger(LoggerConfig {
+            path: None,
+            debug_level: params.log_level.clone(),
+            logfile_debug_level: params.log_level.clone(),
+            log_format: None,
+            logfile_format: None,
+            log_color: false,
+            disable_log_timestamp: false,
+            max_log_size: 0,
+            max_log_number: 0,
+            compression: false,
+            is_restricted: true,
+            sse_logging: false,
+        })?
+        .multi_threaded_tokio_runtime()?
+        .build()?;
+
+    let spec = &mut env.eth2_config.spec;
+
+    spec.seconds_per_slot /= params.speed_up_factor;
+    spec.seconds_per_slot = max(1, spec.seconds_per_slot);
+    spec.genesis_delay = GENESIS_DELAY;
+    spec.min_genesis_time = 0;
+    spec.min_genesis_active_validator_count = params.total_validator_count() as u64;
+    spec.altair_fork_epoch = Some(Epoch::new(ALTAIR_FORK_EPOCH));
+    spec.bellatrix_fork_epoch = Some(Epoch::new(BELLATRIX_FORK_EPOCH));
+    spec.capella_fork_epoch = Some(Epoch::new(CAPELLA_FORK_EPOCH));
+    spec.deneb_fork_epoch = Some(Epoch::new(DENEB_FORK_EPOCH));
+    //spec.electra_fork_epoch = Some(Epoch::new(ELECTRA_FORK_EPOCH));
+
+    Ok((env, validator_files))
+}
+
+/// Start the beacon nodes and validator clients of the network described by `params`, returning
+/// once genesis has been reached.
+pub(crate) async fn start_network(
+    params: &BasicSimParams,
+    validator_files: Vec<ValidatorFiles>,
+    context: RuntimeContext<E>,
+) -> Result<(LocalNetwork<E>, ClientConfig, MockExecutionConfig), String> {
+    let node_count = params.node_count;
+
+    /*
+     * Create a new `LocalNetwork` with one beacon node.
+     */
+    let max_retries = 3;
+    let (network, beacon_config, mock_execution_config) = with_retry(max_retries, || {
+        Box::pin(LocalNetwork::create_local_network(
+            None,
+            None,
+            LocalNetworkParams {
+                validator_count: params.total_validator_count(),
+                node_count,
+                extra_nodes: params.extra_nodes,
+                proposer_nodes: params.proposer_nodes,
+                genesis_delay: GENESIS_DELAY,
+            },
+            context.clone(),
+        ))
+    })
+    .await?;
+
+    // Add nodes to the network.
+    for _ in 0..node_count {
+        network
+            .add_beacon_node(beacon_config.clone(), mock_execution_config.clone(), false)
+            .await?;
+    }
+
+    /*
+     * One by one, add proposer nodes to the network.
+     */
+    for _ in 0..params.proposer_nodes {
+        println!("Adding a proposer node");
+        network
+            .add_beacon_node(beacon_config.clone(), mock_execution_config.clone(), true)
+            .await?;
+    }
+
+    /*
+     * One by one, add validators to the network.
+     */
+
+    let executor = context.executor.clone();
+    for (i, files) in validator_files.into_iter().enumerate() {
+        let network_1 = network.clone();
+        executor.spawn(
+            async move {
+                let mut validator_config = testing_validator_config();
+                validator_config.fee_recipient = Some(SUGGESTED_FEE_RECIPIENT.into());
+                println!("Adding validator client {}", i);
+
+                // Enable broadcast on every 4th node.
+                if i % 4 == 0 {
+                    validator_config.broadcast_topics = ApiTopic::all();
+                    let beacon_nodes = vec![i, (i + 1) % node_count];
+                    network_1
+                        .add_validator_client_with_fallbacks(
+                            validator_config,
+                            i,
+                            beacon_nodes,
+                            files,
+                        )
+                        .await
+                } else {
+                    network_1
+                        .add_validator_client(validator_config, i, files)
+                        .await
+                }
+                .expect("should add validator");
+            },
+            "vc",
+        );
+    }
+
+    // Set all payloads as valid. This effectively assumes the EL is infalliable.
+    network.execution_nodes.write().iter().for_each(|node| {
+        node.server.all_payloads_valid();
+    });
+
+    let duration_to_genesis = network.duration_to_genesis().await;
+    println!("Duration to genesis: {}", duration_to_genesis.as_secs());
+    sleep(duration_to_genesis).await;
+
+    Ok((network, beacon_config, mock_execution_config))
+}
@@ -1,7 +1,9 @@
 use crate::local_network::LocalNetwork;
 use node_test_rig::eth2::types::{BlockId, FinalityCheckpointsData, StateId};
+use state_processing::per_slot_processing;
+use std::collections::HashMap;
 use std::time::Duration;
-use types::{Epoch, EthSpec, ExecPayload, ExecutionBlockHash, Slot, Unsigned};
+use types::{ChainSpec, Epoch, EthSpec, ExecPayload, ExecutionBlockHash, Slot, Unsigned};
 
 /// Checks that all of the validators have on-boarded by the start of the second eth1 voting
 /// period.
@@ -503,3 +505,74 @@ pub async fn check_attestation_correctness<E: EthSpec>(
 
     Ok(())
 }
+
+/// Verifies that the attestation rewards reported by every node via the standard
+/// `beacon/rewards/attestations` endpoint match the balance changes applied by `state_processing`
+/// for each epoch from `start_epoch` up to (but excluding) `upto_epoch - 1`.
+///
+/// The rewards for epoch `N` are applied during the epoch processing at the end of epoch `N + 1`,
+/// so the expected rewards are computed by applying that epoch processing to the state at the
+/// last slot of epoch `N + 1`. The simulation has no slashings, so every balance change made by
+/// epoch processing should be accounted for by the endpoint.
+pub async fn verify_attestation_rewards<E: EthSpec>(
+    network: LocalNetwork<E>,
+    start_epoch: Epoch,
+    upto_epoch: Epoch,
+    slot_duration: Duration,
+    spec: ChainSpec,
+) -> Result<(), String> {
+    epoch_delay(upto_epoch, slot_duration, E::slots_per_epoch()).await;
+    let remote_nodes = network.remote_nodes()?;
+    let remote_node = remote_nodes.first().unwrap();
+
+    for epoch in (start_epoch.as_u64()..upto_epoch.as_u64().saturating_sub(1)).map(Epoch::new) {
+        let state_slot = (epoch + 1).end_slot(E::slots_per_epoch());
+        let mut state = remote_node
+            .get_debug_beacon_states::<E>(StateId::Slot(state_slot))
+            .await
+            .map_err(|e| format!("Failed to get state at slot {state_slot}: {e:?}"))?
+            .ok_or_else(|| format!("No state available at slot {state_slot}"))?
+            .data;
+        let pre_balances = state.balances().clone();
+        per_slot_processing(&mut state, None, &spec)
+            .map_err(|e| format!("Failed to process epoch {}: {e:?}", epoch + 1))?;
+
+        let expected = pre_balances
+            .iter()
+            .zip(state.balances().iter())
+            .map(|(&pre, &post)| post as i64 - pre as i64)
+            .collect::<Vec<_>>();
+
+        for (node_index, node) in remote_nodes.iter().enumerate() {
+            let reported = node
+                .post_beacon_rewards_attestations(epoch, &[])
+                .await
+                .map_err(|e| format!("Failed to get attestation rewards for {epoch}: {e:?}"))?
+                .data
+                .total_rewards
+                .into_iter()
+                .map(|reward| {
+                    let total = reward.head
+                        + reward.target
+                        + reward.source
+                        + reward.inactivity
+                        + reward.inclusion_delay.map_or(0, |delay| delay.value as i64);
+                    (reward.validator_index as usize, total)
+                })
+                .collect::<HashMap<_, _>>();
+
+            for (validator_index, &expected_reward) in expected.iter().enumerate() {
+                let reported_reward = reported.get(&validator_index).copied().unwrap_or(0);
+                if reported_reward != expected_reward {
+                    return Err(format!(
+                        "Attestation reward mismatch on node {node_index} for validator \
+                         {validator_index} in epoch {epoch}, got: {reported_reward}, \
+                         expected: {expected_reward}"
+                    ));
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
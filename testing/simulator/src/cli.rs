@@ -122,4 +122,62 @@ pub fn cli_app() -> Command {
                         .help("Continue after checks (default false)"),
                 ),
         )
+        .subcommand(
+            Command::new("rewards-sim")
+                .about(
+                    "Runs a Beacon Chain simulation with `n` beacon node and validator clients, \
+                    each with `v` validators, for `e` epochs. \
+                    At the end of the simulation, the attestation rewards reported by each beacon \
+                    node's `beacon/rewards/attestations` endpoint are compared against the balance \
+                    changes computed by `state_processing`. If any validator's rewards diverge, \
+                    the simulation will exit and an error will be reported.",
+                )
+                .arg(
+                    Arg::new("nodes")
+                        .short('n')
+                        .long("nodes")
+                        .action(ArgAction::Set)
+                        .default_value("3")
+                        .help("Number of beacon nodes"),
+                )
+                .arg(
+                    Arg::new("validators-per-node")
+                        .short('v')
+                        .long("validators-per-node")
+                        .action(ArgAction::Set)
+                        .default_value("20")
+                        .help("Number of validators"),
+                )
+                .arg(
+                    Arg::new("epochs")
+                        .short('e')
+                        .long("epochs")
+                        .action(ArgAction::Set)
+                        .default_value("8")
+                        .help("Number of epochs to run before checking rewards."),
+                )
+                .arg(
+                    Arg::new("speed-up-factor")
+                        .short('s')
+                        .long("speed-up-factor")
+                        .action(ArgAction::Set)
+                        .default_value("3")
+                        .help("Speed up factor. Please use a divisor of 12."),
+                )
+                .arg(
+                    Arg::new("debug-level")
+                        .short('d')
+                        .long("debug-level")
+                        .action(ArgAction::Set)
+                        .default_value("debug")
+                        .help("Set the severity level of the logs."),
+                )
+                .arg(
+                    Arg::new("continue-after-checks")
+                        .short('c')
+                        .long("continue_after_checks")
+                        .action(ArgAction::SetTrue)
+                        .help("Continue after checks (default false)"),
+                ),
+        )
 }
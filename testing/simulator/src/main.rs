@@ -16,6 +16,7 @@ mod cli;
 mod fallback_sim;
 mod local_network;
 mod retry;
+mod rewards_sim;
 
 use cli::cli_app;
 use env_logger::{Builder, Env};
@@ -44,6 +45,13 @@ fn main() {
                 std::process::exit(1)
             }
         },
+        Some(("rewards-sim", matches)) => match rewards_sim::run_rewards_sim(matches) {
+            Ok(()) => println!("Simulation exited successfully"),
+            Err(e) => {
+                eprintln!("Simulation exited with error: {}", e);
+                std::process::exit(1)
+            }
+        },
         _ => {
             eprintln!("Invalid subcommand. Use --help to see available options");
             std::process::exit(1)
@@ -0,0 +1,108 @@
+use crate::basic_sim::{build_environment, start_network, BasicSimParams, ALTAIR_FORK_EPOCH};
+use crate::checks;
+use clap::ArgMatches;
+
+use futures::prelude::*;
+use std::time::Duration;
+use types::Epoch;
+
+pub fn run_rewards_sim(matches: &ArgMatches) -> Result<(), String> {
+    let node_count = matches
+        .get_one::<String>("nodes")
+        .expect("missing nodes default")
+        .parse::<usize>()
+        .expect("missing nodes default");
+    let validators_per_node = matches
+        .get_one::<String>("validators-per-node")
+        .expect("missing validators-per-node default")
+        .parse::<usize>()
+        .expect("missing validators-per-node default");
+    let end_epoch = matches
+        .get_one::<String>("epochs")
+        .expect("missing epochs default")
+        .parse::<u64>()
+        .expect("missing epochs default");
+    let speed_up_factor = matches
+        .get_one::<String>("speed-up-factor")
+        .expect("missing speed-up-factor default")
+        .parse::<u64>()
+        .expect("missing speed-up-factor default");
+    let log_level = matches
+        .get_one::<String>("debug-level")
+        .expect("missing debug-level");
+
+    let continue_after_checks = matches.get_flag("continue-after-checks");
+
+    // Rewards for an epoch are only applied at the end of the following epoch.
+    if end_epoch < 3 {
+        return Err("--epochs must be at least 3 to check any rewards".to_string());
+    }
+
+    println!("Rewards Simulator:");
+    println!(" nodes: {}", node_count);
+    println!(" validators-per-node: {}", validators_per_node);
+    println!(" epochs: {}", end_epoch);
+    println!(" speed-up-factor: {}", speed_up_factor);
+    println!(" continue-after-checks: {}", continue_after_checks);
+
+    let params = BasicSimParams {
+        node_count,
+        proposer_nodes: 0,
+        extra_nodes: 0,
+        validators_per_node,
+        speed_up_factor,
+        log_level: log_level.clone(),
+    };
+    let (mut env, validator_files) = build_environment(&params)?;
+
+    let spec = env.eth2_config.spec.clone();
+    let slot_duration = Duration::from_secs(spec.seconds_per_slot);
+
+    let context = env.core_context();
+
+    let main_future = async {
+        let (network, _, _) = start_network(&params, validator_files, context).await?;
+
+        /*
+         * Check that every node reports the attestation rewards applied by `state_processing`.
+         *
+         * The checks of the network itself are left to the basic simulation.
+         */
+        checks::verify_attestation_rewards(
+            network.clone(),
+            Epoch::new(ALTAIR_FORK_EPOCH),
+            Epoch::new(end_epoch),
+            slot_duration,
+            spec,
+        )
+        .await?;
+
+        // The `final_future` either completes immediately or never completes, depending on the value
+        // of `continue_after_checks`.
+
+        if continue_after_checks {
+            future::pending::<()>().await;
+        }
+        /*
+         * End the simulation by dropping the network. This will kill all running beacon nodes and
+         * validator clients.
+         */
+        println!(
+            "Simulation complete. Finished with {} beacon nodes and {} validator clients",
+            network.beacon_node_count(),
+            network.validator_client_count()
+        );
+
+        // Be explicit about dropping the network, as this kills all the nodes. This ensures
+        // all the checks have adequate time to pass.
+        drop(network);
+        Ok::<(), String>(())
+    };
+
+    env.runtime().block_on(main_future).unwrap();
+
+    env.fire_signal();
+    env.shutdown_on_idle();
+
+    Ok(())
+}
@@ -35,7 +35,6 @@ mod tests {
     use std::process::{Child, Command, Stdio};
     use std::sync::Arc;
     use std::time::{Duration, Instant};
-    use task_executor::TaskExecutor;
     use tempfile::{tempdir, TempDir};
     use tokio::sync::OnceCell;
     use tokio::time::sleep;
@@ -46,7 +45,7 @@ mod tests {
             load_pem_certificate, load_pkcs12_identity, InitializedValidators,
         },
         validator_store::{Error as ValidatorStoreError, ValidatorStore},
-        SlashingDatabase, SLASHING_PROTECTION_FILENAME,
+        SigningThreadPool, SlashingDatabase, SLASHING_PROTECTION_FILENAME,
     };
 
     /// If the we are unable to reach the Web3Signer HTTP API within this time out then we will
@@ -340,10 +339,7 @@ mod tests {
                     .build()
                     .unwrap(),
             );
-            let (runtime_shutdown, exit) = async_channel::bounded(1);
-            let (shutdown_tx, _) = futures::channel::mpsc::channel(1);
-            let executor =
-                TaskExecutor::new(Arc::downgrade(&runtime), exit, log.clone(), shutdown_tx);
+            let (runtime_shutdown, _exit) = async_channel::bounded(1);
 
             let slashing_db_path = validator_dir.path().join(SLASHING_PROTECTION_FILENAME);
             let slashing_protection = SlashingDatabase::open_or_create(&slashing_db_path).unwrap();
@@ -366,7 +362,8 @@ mod tests {
                 None,
                 slot_clock,
                 &config,
-                executor,
+                SigningThreadPool::new(config.signing_threads).unwrap(),
+                None,
                 log.clone(),
             );
 
@@ -1,8 +1,14 @@
 #![cfg(test)]
 
-use crate::test_utils::pubkey;
+use crate::interchange::{
+    Interchange, InterchangeData, InterchangeMetadata, SignedAttestation as InterchangeAttestation,
+    SignedBlock as InterchangeBlock,
+};
+use crate::test_utils::{pubkey, DEFAULT_GENESIS_VALIDATORS_ROOT};
 use crate::*;
+use std::collections::HashSet;
 use tempfile::tempdir;
+use types::Slot;
 
 #[test]
 fn export_non_existent_key() {
@@ -73,3 +79,129 @@ fn export_same_key_twice() {
         export_double.minify().unwrap()
     );
 }
+
+fn interchange_for(records: Vec<(usize, Slot)>) -> Interchange {
+    Interchange {
+        metadata: InterchangeMetadata {
+            interchange_format_version: SUPPORTED_INTERCHANGE_FORMAT_VERSION,
+            genesis_validators_root: DEFAULT_GENESIS_VALIDATORS_ROOT,
+        },
+        data: records
+            .into_iter()
+            .map(|(index, slot)| InterchangeData {
+                pubkey: pubkey(index),
+                signed_blocks: vec![InterchangeBlock {
+                    slot,
+                    signing_root: None,
+                }],
+                signed_attestations: vec![InterchangeAttestation {
+                    source_epoch: slot.epoch(32).saturating_sub(1u64),
+                    target_epoch: slot.epoch(32),
+                    signing_root: None,
+                }],
+            })
+            .collect(),
+    }
+}
+
+#[test]
+fn streaming_import_matches_atomic_import() {
+    let dir = tempdir().unwrap();
+    let interchange = interchange_for((0..7).map(|i| (i, Slot::new(100 * i as u64))).collect());
+    let json = serde_json::to_vec(&interchange).unwrap();
+
+    let atomic_db = SlashingDatabase::create(&dir.path().join("atomic.sqlite")).unwrap();
+    atomic_db
+        .import_interchange_info(interchange, DEFAULT_GENESIS_VALIDATORS_ROOT)
+        .unwrap();
+
+    let streaming_db = SlashingDatabase::create(&dir.path().join("streaming.sqlite")).unwrap();
+    let mut progress = vec![];
+    let outcomes = streaming_db
+        .import_interchange_stream(json.as_slice(), DEFAULT_GENESIS_VALIDATORS_ROOT, 3, |p| {
+            progress.push(p)
+        })
+        .unwrap();
+
+    assert_eq!(outcomes.len(), 7);
+    assert_eq!(
+        progress
+            .iter()
+            .map(|p| p.records_imported)
+            .collect::<Vec<_>>(),
+        vec![3, 6, 7]
+    );
+    assert!(streaming_db
+        .export_all_interchange_info(DEFAULT_GENESIS_VALIDATORS_ROOT)
+        .unwrap()
+        .equiv(
+            &atomic_db
+                .export_all_interchange_info(DEFAULT_GENESIS_VALIDATORS_ROOT)
+                .unwrap()
+        ));
+}
+
+#[test]
+fn streaming_import_partial_failure() {
+    let dir = tempdir().unwrap();
+    let slashing_db = SlashingDatabase::create(&dir.path().join("db.sqlite")).unwrap();
+
+    // A slot which can't be stored in the database causes the second record to fail.
+    let interchange = interchange_for(vec![
+        (0, Slot::new(10)),
+        (1, Slot::new(u64::MAX)),
+        (2, Slot::new(30)),
+    ]);
+    let json = serde_json::to_vec(&interchange).unwrap();
+
+    let err = slashing_db
+        .import_interchange_stream(json.as_slice(), DEFAULT_GENESIS_VALIDATORS_ROOT, 2, |_| {})
+        .unwrap_err();
+    let InterchangeError::PartialImport(outcomes) = err else {
+        panic!("unexpected error: {:?}", err);
+    };
+    assert_eq!(
+        outcomes.iter().map(|o| o.failed()).collect::<Vec<_>>(),
+        vec![false, true, false]
+    );
+
+    // The other records from the failed batch should still have been imported.
+    let exported = slashing_db
+        .export_all_interchange_info(DEFAULT_GENESIS_VALIDATORS_ROOT)
+        .unwrap();
+    assert_eq!(
+        exported
+            .data
+            .iter()
+            .map(|d| d.pubkey)
+            .collect::<HashSet<_>>(),
+        HashSet::from([pubkey(0), pubkey(2)])
+    );
+}
+
+#[test]
+fn streaming_import_checks_metadata_first() {
+    let dir = tempdir().unwrap();
+    let slashing_db = SlashingDatabase::create(&dir.path().join("db.sqlite")).unwrap();
+    let json = serde_json::to_vec(&interchange_for(vec![(0, Slot::new(10))])).unwrap();
+
+    let err = slashing_db
+        .import_interchange_stream(json.as_slice(), Hash256::repeat_byte(1), 1, |_| {
+            panic!("no records should be imported")
+        })
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        InterchangeError::GenesisValidatorsMismatch { .. }
+    ));
+
+    // Data which precedes the metadata can't be checked before it is imported.
+    let json = format!(
+        r#"{{"data":[],"metadata":{{"interchange_format_version":"5","genesis_validators_root":"{:?}"}}}}"#,
+        DEFAULT_GENESIS_VALIDATORS_ROOT
+    );
+    let err = slashing_db
+        .import_interchange_stream(json.as_bytes(), DEFAULT_GENESIS_VALIDATORS_ROOT, 1, |_| {})
+        .unwrap_err();
+    assert!(matches!(err, InterchangeError::SerdeJsonError(_)));
+}
@@ -1,8 +1,10 @@
 use crate::InterchangeError;
-use serde::{Deserialize, Serialize};
+use serde::de::{self, DeserializeSeed, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize};
 use std::cmp::max;
 use std::collections::{HashMap, HashSet};
-use std::io;
+use std::fmt;
+use std::io::{self, BufReader};
 use types::{Epoch, Hash256, PublicKeyBytes, Slot};
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
@@ -157,3 +159,151 @@ impl Interchange {
         })
     }
 }
+
+/// Read an interchange file one record at a time, without loading it into memory.
+///
+/// `on_metadata` is called with the metadata before any records are read, and `on_data` is called
+/// with each record in turn. Either may return an error to abort reading. The metadata must
+/// precede the data in the file, as it does in files produced by all known clients.
+pub fn stream_json_reader<M, D>(
+    reader: impl io::Read,
+    on_metadata: M,
+    on_data: D,
+) -> Result<InterchangeMetadata, InterchangeError>
+where
+    M: FnOnce(&InterchangeMetadata) -> Result<(), InterchangeError>,
+    D: FnMut(InterchangeData) -> Result<(), InterchangeError>,
+{
+    let mut stream = InterchangeStream {
+        on_metadata: Some(on_metadata),
+        on_data,
+        error: None,
+    };
+    let mut deserializer = serde_json::Deserializer::from_reader(BufReader::new(reader));
+
+    let result = (&mut stream)
+        .deserialize(&mut deserializer)
+        .and_then(|metadata| deserializer.end().map(|()| metadata));
+
+    // Prefer the error returned by a callback to the error that it was wrapped in.
+    result.map_err(|e| {
+        stream
+            .error
+            .take()
+            .unwrap_or(InterchangeError::SerdeJsonError(e))
+    })
+}
+
+struct InterchangeStream<M, D> {
+    on_metadata: Option<M>,
+    on_data: D,
+    /// The error returned by a callback, if any.
+    error: Option<InterchangeError>,
+}
+
+impl<M, D> InterchangeStream<M, D> {
+    fn abort<E: de::Error>(&mut self, error: InterchangeError) -> E {
+        let message = format!("{:?}", error);
+        self.error = Some(error);
+        E::custom(message)
+    }
+}
+
+impl<'de, M, D> DeserializeSeed<'de> for &mut InterchangeStream<M, D>
+where
+    M: FnOnce(&InterchangeMetadata) -> Result<(), InterchangeError>,
+    D: FnMut(InterchangeData) -> Result<(), InterchangeError>,
+{
+    type Value = InterchangeMetadata;
+
+    fn deserialize<De: Deserializer<'de>>(
+        self,
+        deserializer: De,
+    ) -> Result<Self::Value, De::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de, M, D> Visitor<'de> for &mut InterchangeStream<M, D>
+where
+    M: FnOnce(&InterchangeMetadata) -> Result<(), InterchangeError>,
+    D: FnMut(InterchangeData) -> Result<(), InterchangeError>,
+{
+    type Value = InterchangeMetadata;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an interchange object")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut metadata = None;
+        let mut seen_data = false;
+
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "metadata" => {
+                    if metadata.is_some() {
+                        return Err(de::Error::duplicate_field("metadata"));
+                    }
+                    let value: InterchangeMetadata = map.next_value()?;
+                    if let Some(on_metadata) = self.on_metadata.take() {
+                        on_metadata(&value).map_err(|e| self.abort(e))?;
+                    }
+                    metadata = Some(value);
+                }
+                "data" => {
+                    if seen_data {
+                        return Err(de::Error::duplicate_field("data"));
+                    }
+                    if metadata.is_none() {
+                        return Err(de::Error::custom(
+                            "metadata must precede data in a streamed interchange",
+                        ));
+                    }
+                    map.next_value_seed(DataStream(&mut *self))?;
+                    seen_data = true;
+                }
+                _ => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+
+        if !seen_data {
+            return Err(de::Error::missing_field("data"));
+        }
+        metadata.ok_or_else(|| de::Error::missing_field("metadata"))
+    }
+}
+
+/// Visitor for the `data` array, which passes each record to the callback.
+struct DataStream<'a, M, D>(&'a mut InterchangeStream<M, D>);
+
+impl<'de, M, D> DeserializeSeed<'de> for DataStream<'_, M, D>
+where
+    D: FnMut(InterchangeData) -> Result<(), InterchangeError>,
+{
+    type Value = ();
+
+    fn deserialize<De: Deserializer<'de>>(self, deserializer: De) -> Result<(), De::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de, M, D> Visitor<'de> for DataStream<'_, M, D>
+where
+    D: FnMut(InterchangeData) -> Result<(), InterchangeError>,
+{
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a list of interchange records")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        while let Some(record) = seq.next_element::<InterchangeData>()? {
+            (self.0.on_data)(record).map_err(|e| self.0.abort(e))?;
+        }
+        Ok(())
+    }
+}
@@ -13,8 +13,8 @@ pub mod test_utils;
 pub use crate::signed_attestation::{InvalidAttestation, SignedAttestation};
 pub use crate::signed_block::{InvalidBlock, SignedBlock};
 pub use crate::slashing_database::{
-    InterchangeError, InterchangeImportOutcome, SlashingDatabase,
-    SUPPORTED_INTERCHANGE_FORMAT_VERSION,
+    InterchangeError, InterchangeImportOutcome, InterchangeImportProgress, SlashingDatabase,
+    DEFAULT_INTERCHANGE_IMPORT_BATCH_SIZE, SUPPORTED_INTERCHANGE_FORMAT_VERSION,
 };
 use rusqlite::Error as SQLError;
 use std::fmt::Display;
@@ -1,6 +1,6 @@
 use crate::interchange::{
-    Interchange, InterchangeData, InterchangeMetadata, SignedAttestation as InterchangeAttestation,
-    SignedBlock as InterchangeBlock,
+    self, Interchange, InterchangeData, InterchangeMetadata,
+    SignedAttestation as InterchangeAttestation, SignedBlock as InterchangeBlock,
 };
 use crate::signed_attestation::InvalidAttestation;
 use crate::signed_block::InvalidBlock;
@@ -9,6 +9,7 @@ use filesystem::restrict_file_permissions;
 use r2d2_sqlite::SqliteConnectionManager;
 use rusqlite::{params, OptionalExtension, Transaction, TransactionBehavior};
 use std::fs::File;
+use std::io;
 use std::path::Path;
 use std::time::Duration;
 use types::{AttestationData, BeaconBlockHeader, Epoch, Hash256, PublicKeyBytes, SignedRoot, Slot};
@@ -28,6 +29,9 @@ pub const CONNECTION_TIMEOUT: Duration = Duration::from_secs(1);
 /// Supported version of the interchange format.
 pub const SUPPORTED_INTERCHANGE_FORMAT_VERSION: u64 = 5;
 
+/// Default number of records imported per transaction by `import_interchange_stream`.
+pub const DEFAULT_INTERCHANGE_IMPORT_BATCH_SIZE: usize = 256;
+
 /// Column ID of the `validators.enabled` column.
 pub const VALIDATORS_ENABLED_CID: i64 = 2;
 
@@ -679,17 +683,7 @@ impl SlashingDatabase {
         interchange: Interchange,
         genesis_validators_root: Hash256,
     ) -> Result<Vec<InterchangeImportOutcome>, InterchangeError> {
-        let version = interchange.metadata.interchange_format_version;
-        if version != SUPPORTED_INTERCHANGE_FORMAT_VERSION {
-            return Err(InterchangeError::UnsupportedVersion(version));
-        }
-
-        if genesis_validators_root != interchange.metadata.genesis_validators_root {
-            return Err(InterchangeError::GenesisValidatorsMismatch {
-                client: genesis_validators_root,
-                interchange_file: interchange.metadata.genesis_validators_root,
-            });
-        }
+        check_interchange_metadata(&interchange.metadata, genesis_validators_root)?;
 
         // Create a single transaction for the entire batch, which will only be committed if
         // all records are imported successfully.
@@ -720,6 +714,104 @@ impl SlashingDatabase {
         }
     }
 
+    /// Import slashing protection from an interchange file, reading it one record at a time.
+    ///
+    /// Unlike `import_interchange_info`, this function never holds the whole interchange in
+    /// memory, which makes it suitable for very large files. Records are imported in batches of
+    /// `batch_size`, each in its own transaction, and `on_progress` is called after each batch.
+    ///
+    /// The import is *not* atomic: batches committed before an error remain in the database. This
+    /// is safe because importing a record only ever raises a validator's minimum slot and epochs.
+    /// A batch containing a record which can't be imported is retried one record at a time, and
+    /// if any records fail then `InterchangeError::PartialImport` is returned once the rest of the
+    /// file has been imported.
+    pub fn import_interchange_stream(
+        &self,
+        reader: impl io::Read,
+        genesis_validators_root: Hash256,
+        batch_size: usize,
+        mut on_progress: impl FnMut(InterchangeImportProgress),
+    ) -> Result<Vec<InterchangeImportOutcome>, InterchangeError> {
+        let batch_size = std::cmp::max(batch_size, 1);
+        let mut batch = Vec::with_capacity(batch_size);
+        let mut import_outcomes = vec![];
+        let mut progress = InterchangeImportProgress::default();
+
+        let mut import_batch = |batch: &mut Vec<InterchangeData>,
+                                import_outcomes: &mut Vec<InterchangeImportOutcome>|
+         -> Result<(), InterchangeError> {
+            let outcomes = self.import_interchange_batch(std::mem::take(batch))?;
+            progress.records_imported += outcomes.iter().filter(|o| !o.failed()).count();
+            progress.records_failed += outcomes.iter().filter(|o| o.failed()).count();
+            import_outcomes.extend(outcomes);
+            on_progress(progress);
+            Ok(())
+        };
+
+        interchange::stream_json_reader(
+            reader,
+            |metadata| check_interchange_metadata(metadata, genesis_validators_root),
+            |record| {
+                batch.push(record);
+                if batch.len() >= batch_size {
+                    import_batch(&mut batch, &mut import_outcomes)?;
+                }
+                Ok(())
+            },
+        )?;
+        if !batch.is_empty() {
+            import_batch(&mut batch, &mut import_outcomes)?;
+        }
+
+        if import_outcomes.iter().any(InterchangeImportOutcome::failed) {
+            Err(InterchangeError::PartialImport(import_outcomes))
+        } else {
+            Ok(import_outcomes)
+        }
+    }
+
+    /// Import a batch of records in a single transaction, falling back to one transaction per
+    /// record if any of them fail.
+    fn import_interchange_batch(
+        &self,
+        batch: Vec<InterchangeData>,
+    ) -> Result<Vec<InterchangeImportOutcome>, InterchangeError> {
+        let mut conn = self.conn_pool.get()?;
+
+        let txn = conn.transaction()?;
+        let summaries = batch
+            .iter()
+            .map(|record| self.import_interchange_record(record.clone(), &txn))
+            .collect::<Result<Vec<_>, _>>();
+        if let Ok(summaries) = summaries {
+            txn.commit()?;
+            return Ok(batch
+                .iter()
+                .zip(summaries)
+                .map(|(record, summary)| InterchangeImportOutcome::Success {
+                    pubkey: record.pubkey,
+                    summary,
+                })
+                .collect());
+        }
+        drop(txn);
+
+        batch
+            .into_iter()
+            .map(|record| {
+                let pubkey = record.pubkey;
+                let txn = conn.transaction()?;
+                match self.import_interchange_record(record, &txn) {
+                    Ok(summary) => {
+                        txn.commit()?;
+                        Ok(InterchangeImportOutcome::Success { pubkey, summary })
+                    }
+                    Err(error) => Ok(InterchangeImportOutcome::Failure { pubkey, error }),
+                }
+            })
+            .collect()
+    }
+
     pub fn import_interchange_record(
         &self,
         record: InterchangeData,
@@ -1012,6 +1104,54 @@ impl SlashingDatabase {
         Ok(())
     }
 
+    /// Remove all blocks and attestations for `public_key` except the block with the highest slot
+    /// and the attestation with the highest target epoch.
+    ///
+    /// This is the minimal history described by EIP-3076, which is sufficient to prevent slashing.
+    /// The attestation with the highest target also has the highest source, because we never store
+    /// mutually slashable attestations.
+    fn prune_to_watermarks(
+        &self,
+        public_key: &PublicKeyBytes,
+        txn: &Transaction,
+    ) -> Result<(), NotSafe> {
+        let validator_id = self.get_validator_id_in_txn(txn, public_key)?;
+
+        txn.execute(
+            "DELETE FROM signed_blocks
+             WHERE
+                validator_id = ?1 AND
+                slot < (SELECT MAX(slot)
+                        FROM signed_blocks
+                        WHERE validator_id = ?1)",
+            params![validator_id],
+        )?;
+        txn.execute(
+            "DELETE FROM signed_attestations
+             WHERE
+                validator_id = ?1 AND
+                target_epoch < (SELECT MAX(target_epoch)
+                                FROM signed_attestations
+                                WHERE validator_id = ?1)",
+            params![validator_id],
+        )?;
+
+        Ok(())
+    }
+
+    /// Prune the signed blocks and attestations tables for the given validator keys down to the
+    /// minimal history. See `prune_to_watermarks`.
+    pub fn prune_all_to_watermarks<'a>(
+        &self,
+        mut public_keys: impl Iterator<Item = &'a PublicKeyBytes>,
+    ) -> Result<(), NotSafe> {
+        let mut conn = self.conn_pool.get()?;
+        let txn = conn.transaction()?;
+        public_keys.try_for_each(|pubkey| self.prune_to_watermarks(pubkey, &txn))?;
+        txn.commit()?;
+        Ok(())
+    }
+
     pub fn num_validator_rows(&self) -> Result<u32, NotSafe> {
         let mut conn = self.conn_pool.get()?;
         let txn = conn.transaction()?;
@@ -1104,6 +1244,25 @@ impl ValidatorSummary {
     }
 }
 
+/// Check that an interchange is compatible with this client and network.
+fn check_interchange_metadata(
+    metadata: &InterchangeMetadata,
+    genesis_validators_root: Hash256,
+) -> Result<(), InterchangeError> {
+    let version = metadata.interchange_format_version;
+    if version != SUPPORTED_INTERCHANGE_FORMAT_VERSION {
+        return Err(InterchangeError::UnsupportedVersion(version));
+    }
+
+    if genesis_validators_root != metadata.genesis_validators_root {
+        return Err(InterchangeError::GenesisValidatorsMismatch {
+            client: genesis_validators_root,
+            interchange_file: metadata.genesis_validators_root,
+        });
+    }
+    Ok(())
+}
+
 /// Take the maximum of `opt_x` and `y`, returning `y` if `opt_x` is `None`.
 fn max_or<T: Copy + Ord>(opt_x: Option<T>, y: T) -> T {
     opt_x.map_or(y, |x| std::cmp::max(x, y))
@@ -1137,6 +1296,13 @@ impl InterchangeImportOutcome {
     }
 }
 
+/// The number of records processed so far by a streaming interchange import.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct InterchangeImportProgress {
+    pub records_imported: usize,
+    pub records_failed: usize,
+}
+
 #[derive(Debug)]
 pub enum InterchangeError {
     UnsupportedVersion(u64),
@@ -1152,6 +1318,8 @@ pub enum InterchangeError {
     InvalidPubkey(String),
     NotSafe(NotSafe),
     AtomicBatchAborted(Vec<InterchangeImportOutcome>),
+    /// Some records of a streaming import failed, but the others were imported.
+    PartialImport(Vec<InterchangeImportOutcome>),
 }
 
 impl From<NotSafe> for InterchangeError {
@@ -1250,4 +1418,60 @@ mod tests {
         })
         .unwrap();
     }
+
+    #[test]
+    fn prune_to_watermarks() {
+        let dir = tempdir().unwrap();
+        let db = SlashingDatabase::create(&dir.path().join("db.sqlite")).unwrap();
+        let pubkey = crate::test_utils::pubkey(0);
+        let root = |i| SigningRoot::from(Hash256::from_low_u64_be(i));
+        db.register_validator(pubkey).unwrap();
+
+        for (i, slot) in [1, 5, 10].into_iter().enumerate() {
+            db.check_and_insert_block_signing_root(&pubkey, Slot::new(slot), root(i as u64))
+                .unwrap();
+        }
+        for (i, (source, target)) in [(0, 1), (1, 2), (2, 3)].into_iter().enumerate() {
+            db.check_and_insert_attestation_signing_root(
+                &pubkey,
+                Epoch::new(source),
+                Epoch::new(target),
+                root(i as u64),
+            )
+            .unwrap();
+        }
+
+        db.prune_all_to_watermarks(std::iter::once(&pubkey))
+            .unwrap();
+
+        let interchange = db.export_all_interchange_info(Hash256::zero()).unwrap();
+        assert_eq!(
+            interchange.data[0].signed_blocks,
+            vec![InterchangeBlock {
+                slot: Slot::new(10),
+                signing_root: root(2).to_hash256(),
+            }]
+        );
+        assert_eq!(
+            interchange.data[0].signed_attestations,
+            vec![InterchangeAttestation {
+                source_epoch: Epoch::new(2),
+                target_epoch: Epoch::new(3),
+                signing_root: root(2).to_hash256(),
+            }]
+        );
+
+        // Anything at or below the watermarks is still refused.
+        assert!(db
+            .check_and_insert_block_signing_root(&pubkey, Slot::new(7), root(3))
+            .is_err());
+        assert!(db
+            .check_and_insert_attestation_signing_root(
+                &pubkey,
+                Epoch::new(1),
+                Epoch::new(2),
+                root(3)
+            )
+            .is_err());
+    }
 }
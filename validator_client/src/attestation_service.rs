@@ -23,6 +23,7 @@ pub struct AttestationServiceBuilder<T: SlotClock + 'static, E: EthSpec> {
     slot_clock: Option<T>,
     beacon_nodes: Option<Arc<BeaconNodeFallback<T, E>>>,
     context: Option<RuntimeContext<E>>,
+    attestation_delay: Option<Duration>,
 }
 
 impl<T: SlotClock + 'static, E: EthSpec> AttestationServiceBuilder<T, E> {
@@ -33,6 +34,7 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationServiceBuilder<T, E> {
             slot_clock: None,
             beacon_nodes: None,
             context: None,
+            attestation_delay: None,
         }
     }
 
@@ -61,7 +63,26 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationServiceBuilder<T, E> {
         self
     }
 
+    pub fn attestation_delay(mut self, attestation_delay: Option<Duration>) -> Self {
+        self.attestation_delay = attestation_delay;
+        self
+    }
+
     pub fn build(self) -> Result<AttestationService<T, E>, String> {
+        let context = self
+            .context
+            .ok_or("Cannot build AttestationService without runtime_context")?;
+
+        let attestation_delay = self.attestation_delay.unwrap_or_default();
+        verify_attestation_delay(attestation_delay, &context.eth2_config.spec)?;
+        if !attestation_delay.is_zero() {
+            info!(
+                context.log(),
+                "Delaying attestation production";
+                "delay_ms" => attestation_delay.as_millis(),
+            );
+        }
+
         Ok(AttestationService {
             inner: Arc::new(Inner {
                 duties_service: self
@@ -76,14 +97,34 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationServiceBuilder<T, E> {
                 beacon_nodes: self
                     .beacon_nodes
                     .ok_or("Cannot build AttestationService without beacon_nodes")?,
-                context: self
-                    .context
-                    .ok_or("Cannot build AttestationService without runtime_context")?,
+                context,
+                attestation_delay,
             }),
         })
     }
 }
 
+/// Check that attestations produced `attestation_delay` after 1/3rd of the way through the slot
+/// can still be aggregated, which begins 2/3rds of the way through the slot.
+///
+/// The slot duration is taken from `spec`, so the limit differs between networks.
+pub fn verify_attestation_delay(
+    attestation_delay: Duration,
+    spec: &ChainSpec,
+) -> Result<(), String> {
+    let slot_duration = Duration::from_secs(spec.seconds_per_slot);
+    let max_attestation_delay = slot_duration / 3;
+    if attestation_delay >= max_attestation_delay {
+        return Err(format!(
+            "Attestation delay of {}ms must be less than {}ms, otherwise attestations would be \
+             produced after aggregation begins",
+            attestation_delay.as_millis(),
+            max_attestation_delay.as_millis(),
+        ));
+    }
+    Ok(())
+}
+
 /// Helper to minimise `Arc` usage.
 pub struct Inner<T, E: EthSpec> {
     duties_service: Arc<DutiesService<T, E>>,
@@ -91,9 +132,11 @@ pub struct Inner<T, E: EthSpec> {
     slot_clock: T,
     beacon_nodes: Arc<BeaconNodeFallback<T, E>>,
     context: RuntimeContext<E>,
+    attestation_delay: Duration,
 }
 
-/// Attempts to produce attestations for all known validators 1/3rd of the way through each slot.
+/// Attempts to produce attestations for all known validators 1/3rd of the way through each slot,
+/// or later if an attestation delay is configured.
 ///
 /// If any validators are on the same committee, a single attestation will be downloaded and
 /// returned to the beacon node. This attestation will have a signature from each of the
@@ -140,7 +183,7 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
         let interval_fut = async move {
             loop {
                 if let Some(duration_to_next_slot) = self.slot_clock.duration_to_next_slot() {
-                    sleep(duration_to_next_slot + slot_duration / 3).await;
+                    sleep(duration_to_next_slot + slot_duration / 3 + self.attestation_delay).await;
                     let log = self.context.log();
 
                     if let Err(e) = self.spawn_attestation_tasks(slot_duration) {
@@ -331,6 +374,19 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
             return Ok(None);
         }
 
+        // An optimistic beacon node is unable to produce attestation data. Rather than treating
+        // this as a failure, wait for the execution layer to verify the head.
+        if self.beacon_nodes.is_optimistic().await {
+            debug!(
+                log,
+                "Skipping attestation production";
+                "reason" => "beacon node head is optimistic",
+                "slot" => slot.as_u64(),
+                "committee_index" => committee_index,
+            );
+            return Ok(None);
+        }
+
         let current_epoch = self
             .slot_clock
             .now()
@@ -731,6 +787,20 @@ mod tests {
     use futures::future::FutureExt;
     use parking_lot::RwLock;
 
+    #[test]
+    fn attestation_delay_limit() {
+        let mut spec = ChainSpec::mainnet();
+        spec.seconds_per_slot = 12;
+        assert!(verify_attestation_delay(Duration::ZERO, &spec).is_ok());
+        assert!(verify_attestation_delay(Duration::from_millis(3_999), &spec).is_ok());
+        assert!(verify_attestation_delay(Duration::from_millis(4_000), &spec).is_err());
+
+        // The limit is shorter on networks with shorter slots.
+        spec.seconds_per_slot = 5;
+        assert!(verify_attestation_delay(Duration::from_millis(1_000), &spec).is_ok());
+        assert!(verify_attestation_delay(Duration::from_millis(2_000), &spec).is_err());
+    }
+
     /// This test is to ensure that a `tokio_timer::Sleep` with an instant in the past will still
     /// trigger.
     #[tokio::test]
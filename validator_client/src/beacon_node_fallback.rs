@@ -3,6 +3,7 @@
 //! succeed.
 
 use crate::check_synced::check_synced;
+use crate::event_hooks::{Event, EventHooks};
 use crate::http_metrics::metrics::{inc_counter_vec, ENDPOINT_ERRORS, ENDPOINT_REQUESTS};
 use environment::RuntimeContext;
 use eth2::BeaconNodeHttpClient;
@@ -147,6 +148,8 @@ pub enum CandidateError {
     Offline,
     Incompatible,
     NotSynced,
+    /// The node is synced, but its head has not been verified by the execution layer.
+    Optimistic,
 }
 
 /// Represents a `BeaconNodeHttpClient` inside a `BeaconNodeFallback` that may or may not be used
@@ -169,10 +172,13 @@ impl<E: EthSpec> CandidateBeaconNode<E> {
 
     /// Returns the status of `self`.
     ///
-    /// If `RequiredSynced::No`, any `NotSynced` node will be ignored and mapped to `Ok(())`.
+    /// If `RequiredSynced::No`, any `NotSynced` or `Optimistic` node will be ignored and mapped to
+    /// `Ok(())`.
     pub async fn status(&self, synced: RequireSynced) -> Result<(), CandidateError> {
         match *self.status.read().await {
-            Err(CandidateError::NotSynced) if synced == false => Ok(()),
+            Err(CandidateError::NotSynced | CandidateError::Optimistic) if synced == false => {
+                Ok(())
+            }
             other => other,
         }
     }
@@ -207,11 +213,24 @@ impl<E: EthSpec> CandidateBeaconNode<E> {
         // long time out might over-ride a recent successful response, leading to a falsely-offline
         // status. I deem this edge-case acceptable in return for the concurrency benefits of not
         // holding a write-lock whilst we check the online status of the node.
-        *self.status.write().await = new_status;
+        self.update_status(new_status, log).await;
 
         new_status
     }
 
+    /// Set the status of `self`, logging when a node with an optimistic head becomes ready again.
+    async fn update_status(&self, new_status: Result<(), CandidateError>, log: &Logger) {
+        let mut status = self.status.write().await;
+        if matches!(*status, Err(CandidateError::Optimistic)) && new_status.is_ok() {
+            info!(
+                log,
+                "Beacon node head verified by execution layer";
+                "endpoint" => %self.beacon_node,
+            );
+        }
+        *status = new_status;
+    }
+
     /// Checks if the node is reachable.
     async fn is_online(&self, was_offline: bool, log: &Logger) -> Result<(), CandidateError> {
         let result = self
@@ -348,6 +367,10 @@ pub struct BeaconNodeFallback<T, E> {
     slot_clock: Option<T>,
     broadcast_topics: Vec<ApiTopic>,
     spec: ChainSpec,
+    event_hooks: Option<Arc<EventHooks>>,
+    /// The index of the first synced candidate at the last update, used to detect failovers.
+    /// `None` if no candidate was synced, including before the first update.
+    preferred_candidate: RwLock<Option<usize>>,
     log: Logger,
 }
 
@@ -363,6 +386,8 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
             slot_clock: None,
             broadcast_topics,
             spec,
+            event_hooks: None,
+            preferred_candidate: RwLock::new(None),
             log,
         }
     }
@@ -376,6 +401,11 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
         self.slot_clock = Some(slot_clock);
     }
 
+    /// Set the hooks which are notified when the preferred candidate changes.
+    pub fn set_event_hooks(&mut self, event_hooks: Option<Arc<EventHooks>>) {
+        self.event_hooks = event_hooks;
+    }
+
     /// The count of candidates, regardless of their state.
     pub fn num_total(&self) -> usize {
         self.candidates.len()
@@ -403,6 +433,41 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
         n
     }
 
+    /// The count of synced candidates whose head has not been verified by the execution layer.
+    pub async fn num_optimistic(&self) -> usize {
+        let mut n = 0;
+        for candidate in &self.candidates {
+            if matches!(
+                candidate.status(RequireSynced::Yes).await,
+                Err(CandidateError::Optimistic)
+            ) {
+                n += 1
+            }
+        }
+        n
+    }
+
+    /// Returns `true` if there are no synced candidates and at least one candidate has an
+    /// optimistic head.
+    ///
+    /// Whilst this is the case the beacon nodes are unable to produce attestations, so they should
+    /// not be requested until the execution layer verifies the head.
+    pub async fn is_optimistic(&self) -> bool {
+        self.num_synced().await == 0 && self.num_optimistic().await > 0
+    }
+
+    /// The endpoint and status of each candidate, in order of preference.
+    pub async fn candidate_statuses(&self) -> Vec<(String, Result<(), CandidateError>)> {
+        let mut statuses = Vec::with_capacity(self.candidates.len());
+        for candidate in &self.candidates {
+            statuses.push((
+                candidate.beacon_node.to_string(),
+                candidate.status(RequireSynced::Yes).await,
+            ));
+        }
+        statuses
+    }
+
     /// The count of candidates that are online and compatible, but not necessarily synced.
     pub async fn num_available(&self) -> usize {
         let mut n = 0;
@@ -430,6 +495,34 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
 
         // run all updates concurrently and ignore errors
         let _ = future::join_all(futures).await;
+
+        if let Some(event_hooks) = &self.event_hooks {
+            self.notify_failover(event_hooks).await;
+        }
+    }
+
+    /// Notify `event_hooks` if the first synced candidate has changed since the last update.
+    async fn notify_failover(&self, event_hooks: &Arc<EventHooks>) {
+        let mut preferred = None;
+        for (i, candidate) in self.candidates.iter().enumerate() {
+            if candidate.status(RequireSynced::Yes).await.is_ok() {
+                preferred = Some(i);
+                break;
+            }
+        }
+
+        let previous = std::mem::replace(&mut *self.preferred_candidate.write().await, preferred);
+        if previous != preferred {
+            let endpoint = |index: Option<usize>| {
+                index
+                    .and_then(|i| self.candidates.get(i))
+                    .map(|candidate| candidate.beacon_node.to_string())
+            };
+            event_hooks.fire(Event::BeaconNodeFailover {
+                previous: endpoint(previous),
+                current: endpoint(preferred),
+            });
+        }
     }
 
     /// Concurrently send a request to all candidates (regardless of
@@ -534,7 +627,9 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
         // This ensures that we always choose a synced node if it is available.
         for candidate in &self.candidates {
             match candidate.status(RequireSynced::Yes).await {
-                Err(e @ CandidateError::NotSynced) if require_synced == false => {
+                Err(e @ (CandidateError::NotSynced | CandidateError::Optimistic))
+                    if require_synced == false =>
+                {
                     // This client is unsynced we will try it after trying all synced clients
                     retry_unsynced.push(candidate);
                     errors.push((candidate.beacon_node.to_string(), Error::Unavailable(e)));
@@ -574,7 +669,11 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
 
             match new_status {
                 Ok(()) => try_func!(candidate),
-                Err(CandidateError::NotSynced) if require_synced == false => try_func!(candidate),
+                Err(CandidateError::NotSynced | CandidateError::Optimistic)
+                    if require_synced == false =>
+                {
+                    try_func!(candidate)
+                }
                 Err(e) => {
                     errors.push((candidate.beacon_node.to_string(), Error::Unavailable(e)));
                 }
@@ -640,7 +739,9 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
                 Ok(_) => {
                     first_batch_futures.push(run_on_candidate(candidate));
                 }
-                Err(CandidateError::NotSynced) if require_synced == false => {
+                Err(CandidateError::NotSynced | CandidateError::Optimistic)
+                    if require_synced == false =>
+                {
                     // This client is unsynced we will try it after trying all synced clients
                     retry_unsynced.push(candidate);
                 }
@@ -680,7 +781,9 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
 
             match new_status {
                 Ok(()) => third_batch_futures.push(run_on_candidate(candidate)),
-                Err(CandidateError::NotSynced) if require_synced == false => {
+                Err(CandidateError::NotSynced | CandidateError::Optimistic)
+                    if require_synced == false =>
+                {
                     third_batch_futures.push(run_on_candidate(candidate))
                 }
                 Err(e) => third_batch_results.push(Err((
@@ -749,8 +852,20 @@ impl ApiTopic {
 #[cfg(test)]
 mod test {
     use super::*;
+    use environment::null_logger;
+    use eth2::Timeouts;
+    use sensitive_url::SensitiveUrl;
+    use slot_clock::TestingSlotClock;
     use std::str::FromStr;
     use strum::VariantNames;
+    use types::MainnetEthSpec;
+
+    fn candidate(url: &str) -> CandidateBeaconNode<MainnetEthSpec> {
+        CandidateBeaconNode::new(BeaconNodeHttpClient::new(
+            SensitiveUrl::parse(url).unwrap(),
+            Timeouts::set_all(Duration::from_secs(1)),
+        ))
+    }
 
     #[test]
     fn api_topic_all() {
@@ -761,4 +876,60 @@ mod test {
             .map(|topic| ApiTopic::from_str(topic).unwrap())
             .eq(all.into_iter()));
     }
+
+    #[tokio::test]
+    async fn optimistic_candidate_transitions() {
+        let log = null_logger().unwrap();
+        let candidate = candidate("http://localhost:5052");
+
+        candidate
+            .update_status(Err(CandidateError::Optimistic), &log)
+            .await;
+        assert!(matches!(
+            candidate.status(RequireSynced::Yes).await,
+            Err(CandidateError::Optimistic)
+        ));
+        assert!(candidate.status(RequireSynced::No).await.is_ok());
+
+        // The node resumes as soon as the execution layer verifies its head.
+        candidate.update_status(Ok(()), &log).await;
+        assert!(candidate.status(RequireSynced::Yes).await.is_ok());
+        assert!(candidate.status(RequireSynced::No).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn fallback_is_optimistic_until_a_node_is_synced() {
+        let log = null_logger().unwrap();
+        let fallback = BeaconNodeFallback::<TestingSlotClock, MainnetEthSpec>::new(
+            vec![
+                candidate("http://localhost:5052"),
+                candidate("http://localhost:5053"),
+            ],
+            vec![],
+            MainnetEthSpec::default_spec(),
+            log.clone(),
+        );
+        assert!(!fallback.is_optimistic().await);
+
+        fallback.candidates[0]
+            .update_status(Err(CandidateError::Optimistic), &log)
+            .await;
+        fallback.candidates[1]
+            .update_status(Err(CandidateError::Offline), &log)
+            .await;
+        assert_eq!(fallback.num_optimistic().await, 1);
+        assert_eq!(fallback.num_synced().await, 0);
+        assert_eq!(fallback.num_available().await, 1);
+        assert!(fallback.is_optimistic().await);
+
+        fallback.candidates[1].update_status(Ok(()), &log).await;
+        assert!(!fallback.is_optimistic().await);
+
+        fallback.candidates[1]
+            .update_status(Err(CandidateError::Offline), &log)
+            .await;
+        fallback.candidates[0].update_status(Ok(()), &log).await;
+        assert_eq!(fallback.num_optimistic().await, 0);
+        assert!(!fallback.is_optimistic().await);
+    }
 }
@@ -6,6 +6,7 @@ use crate::{
     OfflineOnFailure,
 };
 use crate::{
+    event_hooks::{Event, EventHooks},
     http_metrics::metrics,
     validator_store::{Error as ValidatorStoreError, ValidatorStore},
 };
@@ -18,12 +19,13 @@ use slot_clock::SlotClock;
 use std::fmt::Debug;
 use std::future::Future;
 use std::ops::Deref;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::mpsc;
 use types::{
-    BlindedBeaconBlock, BlockType, EthSpec, Graffiti, PublicKeyBytes, SignedBlindedBeaconBlock,
-    Slot,
+    Address, BlindedBeaconBlock, BlockType, EthSpec, ExecPayload, Graffiti, PublicKeyBytes,
+    SignedBlindedBeaconBlock, Slot,
 };
 
 #[derive(Debug)]
@@ -33,11 +35,21 @@ pub enum BlockError {
     /// An irrecoverable error has occurred during block proposal and should not be retried, as a
     /// block may have already been signed.
     Irrecoverable(String),
+    /// A beacon node has already seen a different block from this proposer at this slot. The block
+    /// must not be published to any other beacon node, as doing so could get the validator slashed.
+    Equivocation(String),
 }
 
 impl From<Errors<BlockError>> for BlockError {
     fn from(e: Errors<BlockError>) -> Self {
         if e.0.iter().any(|(_, error)| {
+            matches!(
+                error,
+                FallbackError::RequestFailed(BlockError::Equivocation(_))
+            )
+        }) {
+            BlockError::Equivocation(e.to_string())
+        } else if e.0.iter().any(|(_, error)| {
             matches!(
                 error,
                 FallbackError::RequestFailed(BlockError::Irrecoverable(_))
@@ -59,6 +71,7 @@ pub struct BlockServiceBuilder<T, E: EthSpec> {
     context: Option<RuntimeContext<E>>,
     graffiti: Option<Graffiti>,
     graffiti_file: Option<GraffitiFile>,
+    event_hooks: Option<Arc<EventHooks>>,
 }
 
 impl<T: SlotClock + 'static, E: EthSpec> BlockServiceBuilder<T, E> {
@@ -71,6 +84,7 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockServiceBuilder<T, E> {
             context: None,
             graffiti: None,
             graffiti_file: None,
+            event_hooks: None,
         }
     }
 
@@ -109,6 +123,11 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockServiceBuilder<T, E> {
         self
     }
 
+    pub fn event_hooks(mut self, event_hooks: Option<Arc<EventHooks>>) -> Self {
+        self.event_hooks = event_hooks;
+        self
+    }
+
     pub fn build(self) -> Result<BlockService<T, E>, String> {
         Ok(BlockService {
             inner: Arc::new(Inner {
@@ -127,6 +146,7 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockServiceBuilder<T, E> {
                 proposer_nodes: self.proposer_nodes,
                 graffiti: self.graffiti,
                 graffiti_file: self.graffiti_file,
+                event_hooks: self.event_hooks,
             }),
         })
     }
@@ -216,6 +236,7 @@ pub struct Inner<T, E: EthSpec> {
     context: RuntimeContext<E>,
     graffiti: Option<Graffiti>,
     graffiti_file: Option<GraffitiFile>,
+    event_hooks: Option<Arc<EventHooks>>,
 }
 
 /// Attempts to produce attestations for any block producer(s) at the start of the epoch.
@@ -333,9 +354,20 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
                         .publish_block(slot, validator_pubkey, builder_boost_factor)
                         .await;
 
+                    match &result {
+                        Ok(_) => {}
+                        // Slashing protection refused to sign the block, it wasn't a failure.
+                        Err(BlockError::Equivocation(_)) => {
+                            metrics::inc_counter(&metrics::BLOCK_PROPOSALS_REFUSED)
+                        }
+                        Err(_) => metrics::inc_counter(&metrics::BLOCK_PROPOSALS_FAILED),
+                    }
+
                     match result {
                         Ok(_) => {}
-                        Err(BlockError::Recoverable(e)) | Err(BlockError::Irrecoverable(e)) => {
+                        Err(BlockError::Recoverable(e))
+                        | Err(BlockError::Irrecoverable(e))
+                        | Err(BlockError::Equivocation(e)) => {
                             error!(
                                 log,
                                 "Error whilst producing block";
@@ -343,6 +375,13 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
                                 "block_slot" => ?slot,
                                 "info" => "block v3 proposal failed, this error may or may not result in a missed block"
                             );
+                            if let Some(event_hooks) = &service.event_hooks {
+                                event_hooks.fire(Event::MissedProposal {
+                                    slot,
+                                    validator_pubkey: validator_pubkey.to_string(),
+                                    error: format!("{:?}", e),
+                                });
+                            }
                         }
                     }
                 },
@@ -417,17 +456,33 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
         // Try the proposer nodes first, since we've likely gone to efforts to
         // protect them from DoS attacks and they're most likely to successfully
         // publish a block.
+        //
+        // If any beacon node reports that it has already seen a different block for this slot,
+        // stop trying the others so that the equivocation isn't propagated by a different node.
+        let equivocation_detected = AtomicBool::new(false);
         proposer_fallback
             .request_proposers_first(
                 RequireSynced::No,
                 OfflineOnFailure::Yes,
                 |beacon_node| async {
-                    self.publish_signed_block_contents(&signed_block, beacon_node)
-                        .await
+                    if equivocation_detected.load(Ordering::Relaxed) {
+                        return Err(BlockError::Equivocation(
+                            "Not publishing block after a beacon node detected an equivocation"
+                                .to_string(),
+                        ));
+                    }
+                    let result = self
+                        .publish_signed_block_contents(&signed_block, beacon_node)
+                        .await;
+                    if let Err(BlockError::Equivocation(_)) = result {
+                        equivocation_detected.store(true, Ordering::Relaxed);
+                    }
+                    result
                 },
             )
             .await?;
 
+        metrics::inc_counter(&metrics::BLOCK_PROPOSALS_PUBLISHED);
         info!(
             log,
             "Successfully published block";
@@ -511,7 +566,7 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
                         &metrics::BLOCK_SERVICE_TIMES,
                         &[metrics::BEACON_BLOCK_HTTP_GET],
                     );
-                    Self::get_validator_block(
+                    let unsigned_block = Self::get_validator_block(
                         beacon_node,
                         slot,
                         randao_reveal_ref,
@@ -526,7 +581,15 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
                             "Error from beacon node when producing block: {:?}",
                             e
                         ))
-                    })
+                    })?;
+
+                    // The beacon node may have its own fee recipient.
+                    let fee_recipient = self_ref
+                        .validator_store
+                        .get_fee_recipient_for_beacon_node(&validator_pubkey, beacon_node);
+                    check_fee_recipient(&unsigned_block, fee_recipient, beacon_node, log)?;
+
+                    Ok::<_, BlockError>(unsigned_block)
                 },
             )
             .await?;
@@ -609,6 +672,7 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
             log,
             "Received unsigned block";
             "slot" => slot.as_u64(),
+            "beacon_node" => %beacon_node,
         );
         if proposer_index != Some(unsigned_block.proposer_index()) {
             return Err(BlockError::Recoverable(
@@ -662,6 +726,55 @@ impl<E: EthSpec> UnsignedBlock<E> {
             UnsignedBlock::Blinded(block) => block.proposer_index(),
         }
     }
+
+    /// Returns the fee recipient of a locally built execution payload.
+    ///
+    /// Returns `None` for blocks without an execution payload and for blinded blocks, whose
+    /// payloads are built by a builder which pays the proposer in its own way.
+    pub fn local_fee_recipient(&self) -> Option<Address> {
+        match self {
+            UnsignedBlock::Full(block) => block
+                .block()
+                .body()
+                .execution_payload()
+                .ok()
+                .map(|payload| payload.fee_recipient()),
+            UnsignedBlock::Blinded(_) => None,
+        }
+    }
+}
+
+/// Check that the locally built payload of `unsigned_block` pays the `expected` fee recipient.
+fn check_fee_recipient<E: EthSpec>(
+    unsigned_block: &UnsignedBlock<E>,
+    expected: Option<Address>,
+    beacon_node: &BeaconNodeHttpClient,
+    log: &Logger,
+) -> Result<(), BlockError> {
+    let (Some(fee_recipient), Some(expected)) = (unsigned_block.local_fee_recipient(), expected)
+    else {
+        return Ok(());
+    };
+    if fee_recipient != expected {
+        warn!(
+            log,
+            "Block has an unexpected fee recipient";
+            "fee_recipient" => ?fee_recipient,
+            "expected" => ?expected,
+            "beacon_node" => %beacon_node,
+        );
+        return Err(BlockError::Recoverable(format!(
+            "Block fee recipient {:?} does not match the expected fee recipient {:?}",
+            fee_recipient, expected
+        )));
+    }
+    debug!(
+        log,
+        "Block fee recipient verified";
+        "fee_recipient" => ?fee_recipient,
+        "beacon_node" => %beacon_node,
+    );
+    Ok(())
 }
 
 #[derive(Debug)]
@@ -718,6 +831,18 @@ fn handle_block_post_error(err: eth2::Error, slot: Slot, log: &Logger) -> Result
             return Ok(());
         }
     }
+    if err.is_equivocating_block() {
+        crit!(
+            log,
+            "Beacon node refused to publish an equivocating block";
+            "info" => "another block has already been published for this slot, check for \
+                       duplicate validator clients or misconfigured failover",
+            "slot" => slot,
+        );
+        return Err(BlockError::Equivocation(format!(
+            "Beacon node detected an equivocation: {err:?}",
+        )));
+    }
     Err(BlockError::Irrecoverable(format!(
         "Error from beacon node when publishing block: {err:?}",
     )))
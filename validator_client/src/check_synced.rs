@@ -13,6 +13,8 @@ const SYNC_TOLERANCE: u64 = 4;
 ///  `Err(CandidateError::NotSynced)`   if the beacon node indicates that it is syncing **AND**
 ///                                         it is more than `SYNC_TOLERANCE` behind the highest
 ///                                         known slot.
+///  `Err(CandidateError::Optimistic)`  if the beacon node is synced but its head has not yet been
+///                                         verified by the execution layer.
 ///
 ///  The second condition means the even if the beacon node thinks that it's syncing, we'll still
 ///  try to use it if it's close enough to the head.
@@ -72,9 +74,22 @@ pub async fn check_synced<T: SlotClock>(
         }
     }
 
-    if is_synced {
-        Ok(())
-    } else {
-        Err(CandidateError::NotSynced)
+    if !is_synced {
+        return Err(CandidateError::NotSynced);
     }
+
+    if resp.data.is_optimistic {
+        if let Some(log) = log_opt {
+            warn!(
+                log,
+                "Beacon node head is optimistic";
+                "msg" => "waiting for the execution layer to verify the head",
+                "head_slot" => resp.data.head_slot.as_u64(),
+                "endpoint" => %beacon_node,
+            );
+        }
+        return Err(CandidateError::Optimistic);
+    }
+
+    Ok(())
 }
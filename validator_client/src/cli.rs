@@ -118,6 +118,18 @@ pub fn cli_app() -> Command {
             )
             .display_order(0)
         )
+        .arg(
+            Arg::new("lazy-unlock")
+            .long("lazy-unlock")
+            .action(ArgAction::SetTrue)
+            .help_heading(FLAG_HEADER)
+            .help(
+                "If present, start performing duties for validators whose keys are already in the \
+                key cache immediately, whilst the remaining keystores are decrypted in the \
+                background. Validators are enabled in batches as their keystores are decrypted."
+            )
+            .display_order(0)
+        )
         .arg(
             Arg::new("use-long-timeouts")
                 .long("use-long-timeouts")
@@ -167,6 +179,18 @@ pub fn cli_app() -> Command {
                 .action(ArgAction::Set)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("beacon-node-fee-recipients")
+                .long("beacon-node-fee-recipients")
+                .value_name("FEE-RECIPIENTS")
+                .help("Comma-separated fee recipients to use for blocks proposed via each of the \
+                       --beacon-nodes, in the same order. An empty entry uses the \
+                       --suggested-fee-recipient. A fee recipient configured in the validator \
+                       definitions takes priority over these values. These fee recipients only \
+                       apply to locally built payloads, builder registrations are unaffected.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         .arg(
             Arg::new("produce-block-v3")
                 .long("produce-block-v3")
@@ -183,6 +207,18 @@ pub fn cli_app() -> Command {
                 .help_heading(FLAG_HEADER)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("attestation-delay-ms")
+                .long("attestation-delay-ms")
+                .value_name("MILLIS")
+                .help("Delay attestation production by this many milliseconds after the usual \
+                       time of 1/3rd of the way through the slot. This may improve attestation \
+                       accuracy for validators on high-latency links. The delay must finish \
+                       before aggregation begins 2/3rds of the way through the slot, taking \
+                       into account the slot duration of the network.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         /* REST API related arguments */
         .arg(
             Arg::new("http")
@@ -321,6 +357,17 @@ pub fn cli_app() -> Command {
                 .help_heading(FLAG_HEADER)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("enable-reward-metrics")
+                .long("enable-reward-metrics")
+                .help("Query the attestation rewards of all validators once per epoch and \
+                    publish the total expected and actual rewards as metrics. Each query \
+                    requires the beacon node to compute rewards for an epoch, which adds load \
+                    to the beacon node.")
+                .action(ArgAction::SetTrue)
+                .help_heading(FLAG_HEADER)
+                .display_order(0)
+        )
         /*
          * Explorer metrics
          */
@@ -457,6 +504,39 @@ pub fn cli_app() -> Command {
                 .help_heading(FLAG_HEADER)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("minimal-slashing-protection")
+                .long("minimal-slashing-protection")
+                .help("Only keep the highest block slot and the highest source and target epoch \
+                       of each validator in the slashing protection database, as in the minimal \
+                       format of EIP-3076. This greatly reduces the size of the database for \
+                       long-lived validators, at the cost of the signing history that is \
+                       exported.")
+                .action(ArgAction::SetTrue)
+                .help_heading(FLAG_HEADER)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("event-hook-url")
+                .long("event-hook-url")
+                .value_name("URLS")
+                .help("Comma-separated URLs which are sent a JSON payload with an HTTP POST \
+                       request when a notable event occurs: beacon node failover, doppelganger \
+                       detection, a failed block proposal or an unreachable remote signer.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("event-hook-command")
+                .long("event-hook-command")
+                .value_name("PATH")
+                .help("Path to an executable which is run when a notable event occurs (see \
+                       --event-hook-url). The JSON payload is written to its standard input and \
+                       the event name is set in the LIGHTHOUSE_EVENT environment variable. The \
+                       command is killed if it has not exited after 10 seconds.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         /*
          * Experimental/development options.
          */
@@ -470,6 +550,15 @@ pub fn cli_app() -> Command {
                 .action(ArgAction::Set)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("signing-threads")
+                .long("signing-threads")
+                .value_name("COUNT")
+                .help("Number of threads used to produce signatures with local keystores. \
+                       Defaults to one per CPU.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         .arg(
             Arg::new("web3-signer-max-idle-connections")
                 .long("web3-signer-max-idle-connections")
@@ -40,6 +40,9 @@ pub struct Config {
     pub disable_auto_discover: bool,
     /// If true, re-register existing validators in definitions.yml for slashing protection.
     pub init_slashing_protection: bool,
+    /// If true, start performing duties for validators whose keys are in the key cache whilst the
+    /// remaining keystores are decrypted in the background.
+    pub lazy_unlock: bool,
     /// If true, use longer timeouts for requests made to the beacon node.
     pub use_long_timeouts: bool,
     /// Graffiti to be inserted everytime we create a block.
@@ -48,6 +51,9 @@ pub struct Config {
     pub graffiti_file: Option<GraffitiFile>,
     /// Fallback fallback address.
     pub fee_recipient: Option<Address>,
+    /// Fee recipients to use instead of `fee_recipient` for proposals via each of the
+    /// `beacon_nodes`, in the same order. Empty if no beacon node has its own fee recipient.
+    pub beacon_node_fee_recipients: Vec<Option<Address>>,
     /// Configuration for the HTTP REST API.
     pub http_api: http_api::Config,
     /// Configuration for the HTTP REST API.
@@ -62,6 +68,9 @@ pub struct Config {
     /// Note: We publish validator specific metrics for low validator counts without this flag
     /// (<= 64 validators)
     pub enable_high_validator_count_metrics: bool,
+    /// If true, periodically query the attestation rewards of our validators and publish the
+    /// expected and actual rewards as metrics.
+    pub enable_reward_metrics: bool,
     /// Enable use of the blinded block endpoints during proposals.
     pub builder_proposals: bool,
     /// Overrides the timestamp field in builder api ValidatorRegistrationV1
@@ -79,14 +88,25 @@ pub struct Config {
     pub validator_registration_batch_size: usize,
     /// Enable slashing protection even while using web3signer keys.
     pub enable_web3signer_slashing_protection: bool,
+    /// If true, only keep the highest block slot and attestation source/target for each validator
+    /// in the slashing protection database.
+    pub minimal_slashing_protection: bool,
     /// Specifies the boost factor, a percentage multiplier to apply to the builder's payload value.
     pub builder_boost_factor: Option<u64>,
     /// If true, Lighthouse will prefer builder proposals, if available.
     pub prefer_builder_proposals: bool,
     /// Whether we are running with distributed network support.
     pub distributed: bool,
+    /// Delay attestation production by this amount after 1/3rd of the way through the slot.
+    pub attestation_delay: Option<Duration>,
     pub web3_signer_keep_alive_timeout: Option<Duration>,
     pub web3_signer_max_idle_connections: Option<usize>,
+    /// The number of threads used to sign with local keystores, or one per CPU if `None`.
+    pub signing_threads: Option<usize>,
+    /// Endpoints which are sent a JSON payload when a notable event occurs.
+    pub event_hook_urls: Vec<SensitiveUrl>,
+    /// A command which is run with a JSON payload on stdin when a notable event occurs.
+    pub event_hook_command: Option<PathBuf>,
 }
 
 impl Default for Config {
@@ -111,15 +131,18 @@ impl Default for Config {
             allow_unsynced_beacon_node: false,
             disable_auto_discover: false,
             init_slashing_protection: false,
+            lazy_unlock: false,
             use_long_timeouts: false,
             graffiti: None,
             graffiti_file: None,
             fee_recipient: None,
+            beacon_node_fee_recipients: vec![],
             http_api: <_>::default(),
             http_metrics: <_>::default(),
             monitoring_api: None,
             enable_doppelganger_protection: false,
             enable_high_validator_count_metrics: false,
+            enable_reward_metrics: false,
             beacon_nodes_tls_certs: None,
             builder_proposals: false,
             builder_registration_timestamp_override: None,
@@ -128,11 +151,16 @@ impl Default for Config {
             enable_latency_measurement_service: true,
             validator_registration_batch_size: 500,
             enable_web3signer_slashing_protection: true,
+            minimal_slashing_protection: false,
             builder_boost_factor: None,
             prefer_builder_proposals: false,
             distributed: false,
+            attestation_delay: None,
             web3_signer_keep_alive_timeout: DEFAULT_WEB3SIGNER_KEEP_ALIVE,
             web3_signer_max_idle_connections: None,
+            signing_threads: None,
+            event_hook_urls: Vec::new(),
+            event_hook_command: None,
         }
     }
 }
@@ -192,8 +220,18 @@ impl Config {
                 .map_err(|e| format!("Unable to parse proposer node URL: {:?}", e))?;
         }
 
+        if let Some(event_hook_urls) = parse_optional::<String>(cli_args, "event-hook-url")? {
+            config.event_hook_urls = event_hook_urls
+                .split(',')
+                .map(SensitiveUrl::parse)
+                .collect::<Result<_, _>>()
+                .map_err(|e| format!("Unable to parse event hook URL: {:?}", e))?;
+        }
+        config.event_hook_command = parse_optional(cli_args, "event-hook-command")?;
+
         config.disable_auto_discover = cli_args.get_flag("disable-auto-discover");
         config.init_slashing_protection = cli_args.get_flag("init-slashing-protection");
+        config.lazy_unlock = cli_args.get_flag("lazy-unlock");
         config.use_long_timeouts = cli_args.get_flag("use-long-timeouts");
 
         if let Some(graffiti_file_path) = cli_args.get_one::<String>("graffiti-file") {
@@ -230,6 +268,30 @@ impl Config {
             config.fee_recipient = Some(input_fee_recipient);
         }
 
+        if let Some(fee_recipients) =
+            parse_optional::<String>(cli_args, "beacon-node-fee-recipients")?
+        {
+            config.beacon_node_fee_recipients = fee_recipients
+                .split(',')
+                .map(|fee_recipient| {
+                    if fee_recipient.is_empty() {
+                        Ok(None)
+                    } else {
+                        fee_recipient.parse::<Address>().map(Some).map_err(|e| {
+                            format!("Unable to parse fee recipient {}: {:?}", fee_recipient, e)
+                        })
+                    }
+                })
+                .collect::<Result<_, _>>()?;
+            if config.beacon_node_fee_recipients.len() != config.beacon_nodes.len() {
+                return Err(format!(
+                    "--beacon-node-fee-recipients has {} entries but there are {} beacon nodes",
+                    config.beacon_node_fee_recipients.len(),
+                    config.beacon_nodes.len()
+                ));
+            }
+        }
+
         if let Some(tls_certs) = parse_optional::<String>(cli_args, "beacon-nodes-tls-certs")? {
             config.beacon_nodes_tls_certs = Some(tls_certs.split(',').map(PathBuf::from).collect());
         }
@@ -238,6 +300,10 @@ impl Config {
             config.distributed = true;
         }
 
+        if let Some(delay_ms) = parse_optional::<u64>(cli_args, "attestation-delay-ms")? {
+            config.attestation_delay = Some(Duration::from_millis(delay_ms));
+        }
+
         if cli_args.get_flag("disable-run-on-all") {
             warn!(
                 log,
@@ -274,6 +340,13 @@ impl Config {
             config.web3_signer_max_idle_connections = Some(n);
         }
 
+        if let Some(n) = parse_optional::<usize>(cli_args, "signing-threads")? {
+            if n == 0 {
+                return Err("--signing-threads must be at least 1".to_string());
+            }
+            config.signing_threads = Some(n);
+        }
+
         /*
          * Http API server
          */
@@ -330,6 +403,10 @@ impl Config {
             config.enable_high_validator_count_metrics = true;
         }
 
+        if cli_args.get_flag("enable-reward-metrics") {
+            config.enable_reward_metrics = true;
+        }
+
         if let Some(address) = cli_args.get_one::<String>("metrics-address") {
             config.http_metrics.listen_addr = address
                 .parse::<IpAddr>()
@@ -443,6 +520,8 @@ impl Config {
                 true
             };
 
+        config.minimal_slashing_protection = cli_args.get_flag("minimal-slashing-protection");
+
         Ok(config)
     }
 }
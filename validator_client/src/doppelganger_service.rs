@@ -30,6 +30,7 @@
 //! Doppelganger protection is a best-effort, last-line-of-defence mitigation. Do not rely upon it.
 
 use crate::beacon_node_fallback::{BeaconNodeFallback, RequireSynced};
+use crate::event_hooks::{Event, EventHooks};
 use crate::validator_store::ValidatorStore;
 use crate::OfflineOnFailure;
 use environment::RuntimeContext;
@@ -270,13 +271,15 @@ async fn beacon_node_liveness<'a, T: 'static + SlotClock, E: EthSpec>(
 
 pub struct DoppelgangerService {
     doppelganger_states: RwLock<HashMap<PublicKeyBytes, DoppelgangerState>>,
+    event_hooks: Option<Arc<EventHooks>>,
     log: Logger,
 }
 
 impl DoppelgangerService {
-    pub fn new(log: Logger) -> Self {
+    pub fn new(event_hooks: Option<Arc<EventHooks>>, log: Logger) -> Self {
         Self {
             doppelganger_states: <_>::default(),
+            event_hooks,
             log,
         }
     }
@@ -677,6 +680,12 @@ impl DoppelgangerService {
 
         // Attempt to shutdown the validator client if there are any detected duplicate validators.
         if violators_exist {
+            // The hooks are not awaited, so that an unresponsive hook cannot delay the shutdown.
+            if let Some(event_hooks) = &self.event_hooks {
+                let mut validator_indices = violators.into_iter().collect::<Vec<_>>();
+                validator_indices.sort_unstable();
+                event_hooks.fire(Event::DoppelgangerDetected { validator_indices });
+            }
             shutdown_func();
         }
 
@@ -738,7 +747,7 @@ mod test {
                 validators: (0..self.validator_count)
                     .map(|_| PublicKeyBytes::random_for_test(&mut rng))
                     .collect(),
-                doppelganger: DoppelgangerService::new(log),
+                doppelganger: DoppelgangerService::new(None, log),
                 slot_clock,
             }
         }
@@ -332,6 +332,30 @@ impl<T: SlotClock + 'static, E: EthSpec> DutiesService<T, E> {
             .collect()
     }
 
+    /// Returns the first slot at or after `slot` at which the validator with `pubkey` is known to
+    /// attest.
+    pub fn next_attestation_slot(&self, pubkey: &PublicKeyBytes, slot: Slot) -> Option<Slot> {
+        self.attesters
+            .read()
+            .get(pubkey)?
+            .values()
+            .map(|(_, duty_and_proof)| duty_and_proof.duty.slot)
+            .filter(|duty_slot| *duty_slot >= slot)
+            .min()
+    }
+
+    /// Returns the first slot at or after `slot` at which the validator with `pubkey` is known to
+    /// propose.
+    pub fn next_proposal_slot(&self, pubkey: &PublicKeyBytes, slot: Slot) -> Option<Slot> {
+        self.proposers
+            .read()
+            .values()
+            .flat_map(|(_, proposers)| proposers.iter())
+            .filter(|proposer_data| proposer_data.pubkey == *pubkey && proposer_data.slot >= slot)
+            .map(|proposer_data| proposer_data.slot)
+            .min()
+    }
+
     /// Returns `true` if we should collect per validator metrics and `false` otherwise.
     pub fn per_validator_metrics(&self) -> bool {
         self.enable_high_validator_count_metrics
@@ -19,6 +19,13 @@ use types::{ChainSpec, EthSpec, PublicKeyBytes, Slot, SyncDuty, SyncSelectionPro
 pub const AGGREGATION_PRE_COMPUTE_EPOCHS: u64 = 2;
 /// Number of slots in advance to compute selection proofs when in `distributed` mode.
 pub const AGGREGATION_PRE_COMPUTE_SLOTS_DISTRIBUTED: u64 = 1;
+/// Maximum number of slots of selection proofs to compute for a future period each time duties
+/// are polled (i.e. each slot).
+///
+/// Duties for the next period are known half a period before it starts, so even at this rate its
+/// proofs are computed well before they are needed, without a burst of signing for every slot of
+/// the period at once.
+pub const FUTURE_PERIOD_PRE_COMPUTE_SLOTS_PER_POLL: u64 = 8;
 
 /// Top-level data-structure containing sync duty information.
 ///
@@ -107,6 +114,27 @@ impl<E: EthSpec> SyncDutiesMap<E> {
             })
     }
 
+    /// Returns the first sync committee period at or after `committee_period` in which the
+    /// validator with `validator_index` is known to be a member of the sync committee.
+    pub fn next_committee_period(
+        &self,
+        validator_index: u64,
+        committee_period: u64,
+    ) -> Option<u64> {
+        self.committees
+            .read()
+            .iter()
+            .filter(|(period, committee_duties)| {
+                **period >= committee_period
+                    && matches!(
+                        committee_duties.validators.read().get(&validator_index),
+                        Some(Some(_))
+                    )
+            })
+            .map(|(period, _)| *period)
+            .min()
+    }
+
     /// Number of slots in advance to compute selection proofs
     fn aggregation_pre_compute_slots(&self) -> u64 {
         if self.distributed {
@@ -116,56 +144,86 @@ impl<E: EthSpec> SyncDutiesMap<E> {
         }
     }
 
+    /// The slot up to which selection proofs for `committee_period` should eventually be
+    /// pre-computed.
+    ///
+    /// Proofs for a future period are computed for the whole period once its duties are known, so
+    /// that they are ready long before they are needed. In `distributed` mode the usual short
+    /// lookahead is used for every period.
+    fn pre_compute_slot(
+        &self,
+        committee_period: u64,
+        current_slot: Slot,
+        spec: &ChainSpec,
+    ) -> Slot {
+        let last_slot = last_slot_of_period::<E>(committee_period, spec);
+        if !self.distributed && first_slot_of_period::<E>(committee_period, spec) > current_slot {
+            last_slot
+        } else {
+            std::cmp::min(
+                current_slot + self.aggregation_pre_compute_slots(),
+                last_slot,
+            )
+        }
+    }
+
     /// Prepare for pre-computation of selection proofs for `committee_period`.
     ///
     /// Return the slot up to which proofs should be pre-computed, as well as a vec of
-    /// `(previous_pre_compute_slot, sync_duty)` pairs for all validators which need to have proofs
-    /// computed. See `fill_in_aggregation_proofs` for the actual calculation.
+    /// `(start_slot, sync_duty)` pairs for all validators which need to have proofs computed. See
+    /// `fill_in_aggregation_proofs` for the actual calculation.
+    ///
+    /// Proofs for a future period are computed at most `FUTURE_PERIOD_PRE_COMPUTE_SLOTS_PER_POLL`
+    /// slots at a time, starting from the earliest slot which any validator needs.
     fn prepare_for_aggregator_pre_compute(
         &self,
         committee_period: u64,
         current_slot: Slot,
         spec: &ChainSpec,
     ) -> (Slot, Vec<(Slot, SyncDuty)>) {
-        let default_start_slot = std::cmp::max(
-            current_slot,
-            first_slot_of_period::<E>(committee_period, spec),
-        );
-        let pre_compute_lookahead_slots = self.aggregation_pre_compute_slots();
-        let pre_compute_slot = std::cmp::min(
-            current_slot + pre_compute_lookahead_slots,
-            last_slot_of_period::<E>(committee_period, spec),
-        );
+        let first_slot = first_slot_of_period::<E>(committee_period, spec);
+        let default_start_slot = std::cmp::max(current_slot, first_slot);
+        let mut pre_compute_slot = self.pre_compute_slot(committee_period, current_slot, spec);
 
-        let pre_compute_duties = self.committees.read().get(&committee_period).map_or_else(
-            Vec::new,
-            |committee_duties| {
-                let validator_duties = committee_duties.validators.read();
-                validator_duties
-                    .values()
-                    .filter_map(|maybe_duty| {
-                        let duty = maybe_duty.as_ref()?;
-                        let old_pre_compute_slot = duty
-                            .aggregation_duties
-                            .pre_compute_slot
-                            .write()
-                            .replace(pre_compute_slot);
-
-                        match old_pre_compute_slot {
-                            // No proofs pre-computed previously, compute all from the start of
-                            // the period or the current slot (whichever is later).
-                            None => Some((default_start_slot, duty.duty.clone())),
-                            // Proofs computed up to `prev`, start from the subsequent epoch.
-                            Some(prev) if prev < pre_compute_slot => {
-                                Some((prev + 1, duty.duty.clone()))
-                            }
-                            // Proofs already known, no need to compute.
-                            _ => None,
-                        }
-                    })
-                    .collect()
-            },
-        );
+        let committees = self.committees.read();
+        let Some(committee_duties) = committees.get(&committee_period) else {
+            return (pre_compute_slot, vec![]);
+        };
+        let validator_duties = committee_duties.validators.read();
+
+        if first_slot > current_slot {
+            let earliest_start_slot = validator_duties
+                .values()
+                .flatten()
+                .map(|duty| {
+                    pre_compute_start_slot(
+                        *duty.aggregation_duties.pre_compute_slot.read(),
+                        default_start_slot,
+                    )
+                })
+                .min();
+            if let Some(start_slot) = earliest_start_slot {
+                pre_compute_slot = std::cmp::min(
+                    pre_compute_slot,
+                    start_slot + FUTURE_PERIOD_PRE_COMPUTE_SLOTS_PER_POLL - 1,
+                );
+            }
+        }
+
+        let pre_compute_duties = validator_duties
+            .values()
+            .flatten()
+            .filter_map(|duty| {
+                let mut duty_pre_compute_slot = duty.aggregation_duties.pre_compute_slot.write();
+                // Proofs already known, no need to compute.
+                if duty_pre_compute_slot.map_or(false, |prev| prev >= pre_compute_slot) {
+                    return None;
+                }
+                let start_slot = pre_compute_start_slot(*duty_pre_compute_slot, default_start_slot);
+                *duty_pre_compute_slot = Some(pre_compute_slot);
+                Some((start_slot, duty.duty.clone()))
+            })
+            .collect();
         (pre_compute_slot, pre_compute_duties)
     }
 
@@ -275,6 +333,15 @@ impl ValidatorDuties {
     }
 }
 
+/// The first slot for which to compute a validator's selection proofs, given the slot up to which
+/// they have already been computed.
+///
+/// If no proofs have been computed, this is `default_start_slot`, i.e. the start of the period or
+/// the current slot (whichever is later).
+fn pre_compute_start_slot(pre_compute_slot: Option<Slot>, default_start_slot: Slot) -> Slot {
+    pre_compute_slot.map_or(default_start_slot, |prev| prev + 1)
+}
+
 /// Number of epochs to wait from the start of the period before actually fetching duties.
 fn epoch_offset(spec: &ChainSpec) -> u64 {
     spec.epochs_per_sync_committee_period.as_u64() / 2
@@ -382,12 +449,15 @@ pub async fn poll_sync_committee_duties<T: SlotClock + 'static, E: EthSpec>(
         sync_duties.prune(current_sync_committee_period);
     }
 
-    // Pre-compute aggregator selection proofs for the next period.
+    // Pre-compute aggregator selection proofs for the next period. Outside of `distributed` mode
+    // this begins as soon as the duties for the next period are known, and proceeds a few slots
+    // per poll.
     let aggregate_pre_compute_lookahead_slots = sync_duties.aggregation_pre_compute_slots();
-    if (current_slot + aggregate_pre_compute_lookahead_slots)
-        .epoch(E::slots_per_epoch())
-        .sync_committee_period(spec)?
-        == next_sync_committee_period
+    if !sync_duties.distributed
+        || (current_slot + aggregate_pre_compute_lookahead_slots)
+            .epoch(E::slots_per_epoch())
+            .sync_committee_period(spec)?
+            == next_sync_committee_period
     {
         let (pre_compute_slot, new_pre_compute_duties) = sync_duties
             .prepare_for_aggregator_pre_compute(next_sync_committee_period, current_slot, spec);
@@ -528,8 +598,16 @@ pub async fn fill_in_aggregation_proofs<T: SlotClock + 'static, E: EthSpec>(
         "pre_compute_slot" => pre_compute_slot
     );
 
+    // Proofs are never computed for past slots, nor for slots prior to the first slot which any
+    // validator needs, e.g. the start of the next period.
+    let start_slot = pre_compute_duties
+        .iter()
+        .map(|(validator_start_slot, _)| *validator_start_slot)
+        .min()
+        .map_or(current_slot, |slot| std::cmp::max(slot, current_slot));
+
     // Generate selection proofs for each validator at each slot, one slot at a time.
-    for slot in (current_slot.as_u64()..=pre_compute_slot.as_u64()).map(Slot::new) {
+    for slot in (start_slot.as_u64()..=pre_compute_slot.as_u64()).map(Slot::new) {
         let mut validator_proofs = vec![];
         for (validator_start_slot, duty) in pre_compute_duties {
             // Proofs are already known at this slot for this validator.
@@ -560,7 +638,10 @@ pub async fn fill_in_aggregation_proofs<T: SlotClock + 'static, E: EthSpec>(
                     .produce_sync_selection_proof(&duty.pubkey, proof_slot, *subnet_id)
                     .await
                 {
-                    Ok(proof) => proof,
+                    Ok(proof) => {
+                        observe_selection_proof_lead_time(duties_service_ref, proof_slot);
+                        proof
+                    }
                     Err(ValidatorStoreError::UnknownPubkey(pubkey)) => {
                         // A pubkey can be missing when a validator was recently
                         // removed via the API.
@@ -656,3 +737,148 @@ pub async fn fill_in_aggregation_proofs<T: SlotClock + 'static, E: EthSpec>(
         }
     }
 }
+
+/// Record how long before the start of `proof_slot` its selection proof was computed.
+fn observe_selection_proof_lead_time<T: SlotClock + 'static, E: EthSpec>(
+    duties_service: &DutiesService<T, E>,
+    proof_slot: Slot,
+) {
+    let slot_clock = &duties_service.slot_clock;
+    let (Some(slot_start), Some(now)) =
+        (slot_clock.start_of(proof_slot), slot_clock.now_duration())
+    else {
+        return;
+    };
+    match slot_start.checked_sub(now) {
+        Some(lead_time) => {
+            metrics::observe_duration(&metrics::SYNC_SELECTION_PROOF_LEAD_TIMES, lead_time)
+        }
+        None => metrics::inc_counter(&metrics::SYNC_SELECTION_PROOFS_LATE_TOTAL),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::MainnetEthSpec;
+
+    type E = MainnetEthSpec;
+
+    #[test]
+    fn pre_compute_whole_next_period() {
+        let spec = E::default_spec();
+        let current_slot = first_slot_of_period::<E>(1, &spec) + 100;
+
+        let duties = SyncDutiesMap::<E>::new(false);
+        assert_eq!(
+            duties.pre_compute_slot(1, current_slot, &spec),
+            current_slot + E::slots_per_epoch() * AGGREGATION_PRE_COMPUTE_EPOCHS
+        );
+        assert_eq!(
+            duties.pre_compute_slot(2, current_slot, &spec),
+            last_slot_of_period::<E>(2, &spec)
+        );
+
+        // Near the end of the period the lookahead is limited to the period.
+        let end_slot = last_slot_of_period::<E>(1, &spec);
+        assert_eq!(duties.pre_compute_slot(1, end_slot - 1, &spec), end_slot);
+    }
+
+    #[test]
+    fn pre_compute_next_period_a_few_slots_per_poll() {
+        let spec = E::default_spec();
+        let duties = SyncDutiesMap::<E>::new(false);
+        let duty = SyncDuty {
+            pubkey: PublicKeyBytes::empty(),
+            validator_index: 0,
+            validator_sync_committee_indices: vec![0],
+        };
+        duties
+            .committees
+            .write()
+            .entry(2)
+            .or_default()
+            .validators
+            .get_mut()
+            .insert(0, Some(ValidatorDuties::new(duty)));
+
+        let first_slot = first_slot_of_period::<E>(2, &spec);
+        let last_slot = last_slot_of_period::<E>(2, &spec);
+        let mut current_slot = first_slot_of_period::<E>(1, &spec) + 100;
+
+        let (pre_compute_slot, new_duties) =
+            duties.prepare_for_aggregator_pre_compute(2, current_slot, &spec);
+        assert_eq!(
+            pre_compute_slot,
+            first_slot + FUTURE_PERIOD_PRE_COMPUTE_SLOTS_PER_POLL - 1
+        );
+        assert_eq!(new_duties.len(), 1);
+        assert_eq!(new_duties[0].0, first_slot);
+
+        // Each poll continues from where the last one finished, until the whole period is done.
+        let mut polls = 1;
+        loop {
+            current_slot += 1;
+            let (pre_compute_slot, new_duties) =
+                duties.prepare_for_aggregator_pre_compute(2, current_slot, &spec);
+            if new_duties.is_empty() {
+                assert_eq!(pre_compute_slot, last_slot);
+                break;
+            }
+            assert_eq!(
+                new_duties[0].0,
+                first_slot + polls * FUTURE_PERIOD_PRE_COMPUTE_SLOTS_PER_POLL
+            );
+            polls += 1;
+        }
+        assert_eq!(
+            polls * FUTURE_PERIOD_PRE_COMPUTE_SLOTS_PER_POLL,
+            (last_slot - first_slot + 1).as_u64()
+        );
+
+        // The proofs aren't computed again once the period starts.
+        let (_, new_duties) = duties.prepare_for_aggregator_pre_compute(2, first_slot + 10, &spec);
+        assert!(new_duties.is_empty());
+    }
+
+    #[test]
+    fn pre_compute_distributed() {
+        let spec = E::default_spec();
+        let current_slot = last_slot_of_period::<E>(1, &spec) - 10;
+
+        let duties = SyncDutiesMap::<E>::new(true);
+        assert_eq!(
+            duties.pre_compute_slot(1, current_slot, &spec),
+            current_slot + AGGREGATION_PRE_COMPUTE_SLOTS_DISTRIBUTED
+        );
+        assert_eq!(
+            duties.pre_compute_slot(2, current_slot, &spec),
+            current_slot + AGGREGATION_PRE_COMPUTE_SLOTS_DISTRIBUTED
+        );
+    }
+
+    #[test]
+    fn next_committee_period() {
+        let duties = SyncDutiesMap::<E>::new(false);
+        for (period, is_member) in [(1, false), (2, true), (4, true)] {
+            let committee_duties = duties.get_or_create_committee_duties(period, &[0]);
+            if is_member {
+                let duty = SyncDuty {
+                    pubkey: PublicKeyBytes::empty(),
+                    validator_index: 0,
+                    validator_sync_committee_indices: vec![0],
+                };
+                committee_duties
+                    .validators
+                    .write()
+                    .insert(0, Some(ValidatorDuties::new(duty)));
+            }
+        }
+
+        assert_eq!(duties.next_committee_period(0, 0), Some(2));
+        assert_eq!(duties.next_committee_period(0, 2), Some(2));
+        assert_eq!(duties.next_committee_period(0, 3), Some(4));
+        assert_eq!(duties.next_committee_period(0, 5), None);
+        assert_eq!(duties.next_committee_period(1, 0), None);
+    }
+}
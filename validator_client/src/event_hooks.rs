@@ -0,0 +1,335 @@
+//! Notifies external systems (e.g., alerting or paging services) of notable events in the
+//! validator client.
+//!
+//! Each event is serialized to a JSON payload of the form:
+//!
+//! ```json
+//! {"event": "beacon_node_failover", "timestamp": 1700000000, "data": {...}}
+//! ```
+//!
+//! The payload is then `POST`ed to each of the `--event-hook-url` endpoints and written to the
+//! standard input of the `--event-hook-command`, if either are configured.
+
+use crate::config::Config;
+use sensitive_url::SensitiveUrl;
+use serde::Serialize;
+use slog::{debug, warn, Logger};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use task_executor::TaskExecutor;
+use types::Slot;
+
+/// The maximum time to wait for a webhook endpoint to respond.
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The maximum time to wait for the event hook command to exit, after which it is killed.
+const COMMAND_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The interval at which to check whether the event hook command has exited.
+const COMMAND_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// The name of the environment variable containing the event name, set when running the
+/// `--event-hook-command`.
+pub const EVENT_ENV_VAR: &str = "LIGHTHOUSE_EVENT";
+
+/// A notable event which is reported to the event hooks.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "event", content = "data", rename_all = "snake_case")]
+pub enum Event {
+    /// The beacon node used for requests has changed because the previously preferred node is no
+    /// longer synced or reachable.
+    ///
+    /// `previous` is `None` if no synced beacon node was available, including when the validator
+    /// client first finds a synced beacon node after starting. `current` is `None` if there are no
+    /// synced beacon nodes available.
+    BeaconNodeFailover {
+        previous: Option<String>,
+        current: Option<String>,
+    },
+    /// Another instance of one or more local validators was detected on the network. The validator
+    /// client will shut down after this event.
+    DoppelgangerDetected { validator_indices: Vec<u64> },
+    /// A block proposal failed and the slot may have been missed.
+    MissedProposal {
+        slot: Slot,
+        validator_pubkey: String,
+        error: String,
+    },
+    /// A request to a remote signer (e.g., Web3Signer) failed.
+    SignerUnreachable { error: String },
+}
+
+impl Event {
+    /// The name of the event, as it appears in the `event` field of the payload.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Event::BeaconNodeFailover { .. } => "beacon_node_failover",
+            Event::DoppelgangerDetected { .. } => "doppelganger_detected",
+            Event::MissedProposal { .. } => "missed_proposal",
+            Event::SignerUnreachable { .. } => "signer_unreachable",
+        }
+    }
+}
+
+/// The JSON payload sent to each event hook.
+#[derive(Debug, Serialize)]
+pub struct EventPayload<'a> {
+    /// The number of seconds since the UNIX epoch at which the event occurred.
+    pub timestamp: u64,
+    #[serde(flatten)]
+    pub event: &'a Event,
+}
+
+/// Dispatches `Event`s to the configured webhooks and command.
+pub struct EventHooks {
+    urls: Vec<SensitiveUrl>,
+    command: Option<PathBuf>,
+    client: reqwest::Client,
+    executor: TaskExecutor,
+    log: Logger,
+}
+
+impl EventHooks {
+    /// Returns `None` if no event hooks are configured.
+    pub fn from_config(
+        config: &Config,
+        executor: TaskExecutor,
+        log: Logger,
+    ) -> Result<Option<Self>, String> {
+        if config.event_hook_urls.is_empty() && config.event_hook_command.is_none() {
+            return Ok(None);
+        }
+
+        let client = reqwest::Client::builder()
+            .timeout(WEBHOOK_TIMEOUT)
+            .build()
+            .map_err(|e| format!("Unable to build event hook HTTP client: {:?}", e))?;
+
+        Ok(Some(Self {
+            urls: config.event_hook_urls.clone(),
+            command: config.event_hook_command.clone(),
+            client,
+            executor,
+            log,
+        }))
+    }
+
+    /// Notify all hooks of `event` in the background.
+    ///
+    /// The notification is not cancelled by a shutdown of the validator client, so that events
+    /// which precede a shutdown (e.g., `DoppelgangerDetected`) are still delivered. Each hook is
+    /// bounded by a timeout.
+    pub fn fire(self: &Arc<Self>, event: Event) {
+        let hooks = self.clone();
+        self.executor
+            .spawn_without_exit(async move { hooks.notify(event).await }, "event_hooks");
+    }
+
+    /// Notify all hooks of `event`, returning once every hook has completed.
+    ///
+    /// Failures are logged rather than returned, an unreachable hook should never interfere with
+    /// validator duties.
+    pub async fn notify(&self, event: Event) {
+        let payload = EventPayload {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            event: &event,
+        };
+        let body = match serde_json::to_vec(&payload) {
+            Ok(body) => body,
+            Err(e) => {
+                warn!(
+                    self.log,
+                    "Unable to serialize event hook payload";
+                    "event" => event.name(),
+                    "error" => %e,
+                );
+                return;
+            }
+        };
+
+        debug!(
+            self.log,
+            "Notifying event hooks";
+            "event" => event.name(),
+        );
+
+        for url in &self.urls {
+            let result = self
+                .client
+                .post(url.full.clone())
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .body(body.clone())
+                .send()
+                .await
+                .and_then(|response| response.error_for_status());
+            if let Err(e) = result {
+                warn!(
+                    self.log,
+                    "Event webhook failed";
+                    "event" => event.name(),
+                    "url" => %url,
+                    "error" => %e,
+                );
+            }
+        }
+
+        if let Some(command) = self.command.clone() {
+            let event_name = event.name();
+            let result = tokio::task::spawn_blocking(move || {
+                run_command(&command, event_name, &body, COMMAND_TIMEOUT)
+            })
+            .await
+            .map_err(|e| format!("Task failed: {:?}", e))
+            .and_then(|result| result);
+            if let Err(e) = result {
+                warn!(
+                    self.log,
+                    "Event hook command failed";
+                    "event" => event.name(),
+                    "error" => e,
+                );
+            }
+        }
+    }
+}
+
+/// Run `command`, passing the event `name` as an environment variable and writing `body` to its
+/// standard input.
+///
+/// The command is killed if it has not exited within `timeout`.
+fn run_command(command: &Path, name: &str, body: &[u8], timeout: Duration) -> Result<(), String> {
+    let mut child = Command::new(command)
+        .env(EVENT_ENV_VAR, name)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("Unable to run {:?}: {:?}", command, e))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(body)
+            .map_err(|e| format!("Unable to write to stdin of {:?}: {:?}", command, e))?;
+    }
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        let status = child
+            .try_wait()
+            .map_err(|e| format!("Unable to wait for {:?}: {:?}", command, e))?;
+        match status {
+            Some(status) => break status,
+            None if Instant::now() >= deadline => {
+                // Reap the killed process, so that it does not linger as a zombie.
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(format!("{:?} timed out after {:?}", command, timeout));
+            }
+            None => std::thread::sleep(COMMAND_POLL_INTERVAL),
+        }
+    };
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("{:?} exited with {}", command, status))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn payload_format() {
+        let event = Event::BeaconNodeFailover {
+            previous: Some("http://localhost:5052/".into()),
+            current: None,
+        };
+        let payload = EventPayload {
+            timestamp: 1_700_000_000,
+            event: &event,
+        };
+        assert_eq!(
+            serde_json::to_value(&payload).unwrap(),
+            json!({
+                "event": "beacon_node_failover",
+                "timestamp": 1_700_000_000,
+                "data": {
+                    "previous": "http://localhost:5052/",
+                    "current": null,
+                }
+            })
+        );
+
+        let event = Event::MissedProposal {
+            slot: Slot::new(42),
+            validator_pubkey: "0xabcd".into(),
+            error: "timeout".into(),
+        };
+        let value = serde_json::to_value(EventPayload {
+            timestamp: 0,
+            event: &event,
+        })
+        .unwrap();
+        assert_eq!(value["event"], event.name());
+        assert_eq!(value["data"]["slot"], "42");
+    }
+
+    #[cfg(unix)]
+    fn write_script(dir: &Path, name: &str, body: &str) -> PathBuf {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = dir.join(name);
+        std::fs::write(&path, format!("#!/bin/sh\n{}\n", body)).unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        path
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn run_command_passes_event() {
+        let dir = tempfile::tempdir().unwrap();
+        let output = dir.path().join("output");
+        let script = write_script(
+            dir.path(),
+            "hook.sh",
+            &format!(
+                "echo \"${}\" > {:?}; cat >> {:?}",
+                EVENT_ENV_VAR, output, output
+            ),
+        );
+
+        run_command(&script, "missed_proposal", b"{}", COMMAND_TIMEOUT).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&output).unwrap(),
+            "missed_proposal\n{}"
+        );
+
+        let failing = write_script(dir.path(), "failing.sh", "exit 1");
+        assert!(run_command(&failing, "missed_proposal", b"{}", COMMAND_TIMEOUT).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn run_command_times_out() {
+        let dir = tempfile::tempdir().unwrap();
+        let script = write_script(dir.path(), "hang.sh", "exec sleep 60");
+
+        let start = Instant::now();
+        let result = run_command(
+            &script,
+            "missed_proposal",
+            b"{}",
+            Duration::from_millis(200),
+        );
+        assert!(result.unwrap_err().contains("timed out"));
+        assert!(start.elapsed() < Duration::from_secs(10));
+    }
+}
@@ -10,6 +10,8 @@ pub mod test_utils;
 
 use crate::http_api::graffiti::{delete_graffiti, get_graffiti, set_graffiti};
 
+use crate::beacon_node_fallback::{BeaconNodeFallback, CandidateError};
+use crate::duties_service::DutiesService;
 use crate::http_api::create_signed_voluntary_exit::create_signed_voluntary_exit;
 use crate::{determine_graffiti, GraffitiFile, ValidatorStore};
 use account_utils::{
@@ -23,8 +25,8 @@ use create_validator::{
 use eth2::lighthouse_vc::{
     std_types::{AuthResponse, GetFeeRecipientResponse, GetGasLimitResponse},
     types::{
-        self as api_types, GenericResponse, GetGraffitiResponse, Graffiti, PublicKey,
-        PublicKeyBytes, SetGraffitiRequest,
+        self as api_types, BeaconNodeHealth, BeaconNodeStatus, GenericResponse,
+        GetGraffitiResponse, Graffiti, PublicKey, PublicKeyBytes, SetGraffitiRequest,
     },
 };
 use lighthouse_version::version_with_platform;
@@ -73,6 +75,8 @@ pub struct Context<T: SlotClock, E: EthSpec> {
     pub task_executor: TaskExecutor,
     pub api_secret: ApiSecret,
     pub validator_store: Option<Arc<ValidatorStore<T, E>>>,
+    pub beacon_nodes: Option<Arc<BeaconNodeFallback<T, E>>>,
+    pub duties_service: Option<Arc<DutiesService<T, E>>>,
     pub validator_dir: Option<PathBuf>,
     pub secrets_dir: Option<PathBuf>,
     pub graffiti_file: Option<GraffitiFile>,
@@ -180,6 +184,28 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
             })
         });
 
+    let inner_beacon_nodes = ctx.beacon_nodes.clone();
+    let beacon_nodes_filter = warp::any()
+        .map(move || inner_beacon_nodes.clone())
+        .and_then(|beacon_nodes: Option<_>| async move {
+            beacon_nodes.ok_or_else(|| {
+                warp_utils::reject::custom_not_found(
+                    "beacon nodes are not initialized.".to_string(),
+                )
+            })
+        });
+
+    let inner_duties_service = ctx.duties_service.clone();
+    let duties_service_filter = warp::any()
+        .map(move || inner_duties_service.clone())
+        .and_then(|duties_service: Option<_>| async move {
+            duties_service.ok_or_else(|| {
+                warp_utils::reject::custom_not_found(
+                    "duties service is not initialized.".to_string(),
+                )
+            })
+        });
+
     let inner_task_executor = ctx.task_executor.clone();
     let task_executor_filter = warp::any().map(move || inner_task_executor.clone());
 
@@ -347,6 +373,100 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
             },
         );
 
+    // GET lighthouse/validators/{validator_pubkey}/next_duties
+    let get_lighthouse_validators_next_duties = warp::path("lighthouse")
+        .and(warp::path("validators"))
+        .and(warp::path::param::<PublicKey>())
+        .and(warp::path("next_duties"))
+        .and(warp::path::end())
+        .and(validator_store_filter.clone())
+        .and(duties_service_filter)
+        .and(slot_clock_filter.clone())
+        .and(spec_filter.clone())
+        .then(
+            |validator_pubkey: PublicKey,
+             validator_store: Arc<ValidatorStore<T, E>>,
+             duties_service: Arc<DutiesService<T, E>>,
+             slot_clock: T,
+             spec: Arc<ChainSpec>| {
+                blocking_json_task(move || {
+                    let pubkey = PublicKeyBytes::from(&validator_pubkey);
+                    if !validator_store.has_validator(&pubkey) {
+                        return Err(warp_utils::reject::custom_not_found(format!(
+                            "no validator for {:?}",
+                            validator_pubkey
+                        )));
+                    }
+                    let current_slot = slot_clock.now().ok_or_else(|| {
+                        warp_utils::reject::custom_server_error(
+                            "unable to read slot clock".to_string(),
+                        )
+                    })?;
+                    let current_period = current_slot
+                        .epoch(E::slots_per_epoch())
+                        .sync_committee_period(&spec)
+                        .map_err(|e| {
+                            warp_utils::reject::custom_server_error(format!(
+                                "unable to compute sync committee period: {:?}",
+                                e
+                            ))
+                        })?;
+                    let validator_index = validator_store.validator_index(&pubkey);
+
+                    Ok(api_types::GenericResponse::from(api_types::NextDuties {
+                        validator_index,
+                        current_slot,
+                        next_attestation_slot: duties_service
+                            .next_attestation_slot(&pubkey, current_slot),
+                        next_proposal_slot: duties_service
+                            .next_proposal_slot(&pubkey, current_slot),
+                        next_sync_committee_period: validator_index.and_then(|index| {
+                            duties_service
+                                .sync_duties
+                                .next_committee_period(index, current_period)
+                        }),
+                    }))
+                })
+            },
+        );
+
+    // GET lighthouse/validators/{validator_pubkey}/fee_recipients
+    let get_lighthouse_validators_fee_recipients = warp::path("lighthouse")
+        .and(warp::path("validators"))
+        .and(warp::path::param::<PublicKey>())
+        .and(warp::path("fee_recipients"))
+        .and(warp::path::end())
+        .and(validator_store_filter.clone())
+        .then(
+            |validator_pubkey: PublicKey, validator_store: Arc<ValidatorStore<T, E>>| {
+                blocking_json_task(move || {
+                    let pubkey = PublicKeyBytes::from(&validator_pubkey);
+                    if !validator_store.has_validator(&pubkey) {
+                        return Err(warp_utils::reject::custom_not_found(format!(
+                            "no validator for {:?}",
+                            validator_pubkey
+                        )));
+                    }
+                    let fee_recipients = validator_store
+                        .beacon_node_fee_recipients()
+                        .iter()
+                        .map(
+                            |(url, beacon_node_fee_recipient)| api_types::BeaconNodeFeeRecipient {
+                                endpoint: url.to_string(),
+                                fee_recipient: validator_store
+                                    .get_fee_recipient_with_beacon_node_default(
+                                        &pubkey,
+                                        *beacon_node_fee_recipient,
+                                    ),
+                            },
+                        )
+                        .collect::<Vec<_>>();
+
+                    Ok(api_types::GenericResponse::from(fee_recipients))
+                })
+            },
+        );
+
     // GET lighthouse/ui/health
     let get_lighthouse_ui_health = warp::path("lighthouse")
         .and(warp::path("ui"))
@@ -364,6 +484,35 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
             })
         });
 
+    // GET lighthouse/beacon/health
+    let get_lighthouse_beacon_health = warp::path("lighthouse")
+        .and(warp::path("beacon"))
+        .and(warp::path("health"))
+        .and(warp::path::end())
+        .and(beacon_nodes_filter)
+        .and(task_executor_filter.clone())
+        .then(
+            |beacon_nodes: Arc<BeaconNodeFallback<T, E>>, task_executor: TaskExecutor| {
+                blocking_json_task(move || {
+                    if let Some(handle) = task_executor.handle() {
+                        let health = handle
+                            .block_on(beacon_nodes.candidate_statuses())
+                            .into_iter()
+                            .map(|(endpoint, status)| BeaconNodeHealth {
+                                endpoint,
+                                status: beacon_node_status(status),
+                            })
+                            .collect::<Vec<_>>();
+                        Ok(api_types::GenericResponse::from(health))
+                    } else {
+                        Err(warp_utils::reject::custom_server_error(
+                            "Lighthouse shutting down".into(),
+                        ))
+                    }
+                })
+            },
+        );
+
     let get_lighthouse_ui_graffiti = warp::path("lighthouse")
         .and(warp::path("ui"))
         .and(warp::path("graffiti"))
@@ -1251,7 +1400,10 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
                         .or(get_lighthouse_spec)
                         .or(get_lighthouse_validators)
                         .or(get_lighthouse_validators_pubkey)
+                        .or(get_lighthouse_validators_next_duties)
+                        .or(get_lighthouse_validators_fee_recipients)
                         .or(get_lighthouse_ui_health)
+                        .or(get_lighthouse_beacon_health)
                         .or(get_lighthouse_ui_graffiti)
                         .or(get_fee_recipient)
                         .or(get_gas_limit)
@@ -1309,3 +1461,15 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
 
     Ok((listening_socket, server))
 }
+
+/// Convert the status of a beacon node candidate into its HTTP API representation.
+fn beacon_node_status(status: Result<(), CandidateError>) -> BeaconNodeStatus {
+    match status {
+        Ok(()) => BeaconNodeStatus::Synced,
+        Err(CandidateError::Optimistic) => BeaconNodeStatus::Optimistic,
+        Err(CandidateError::NotSynced) => BeaconNodeStatus::NotSynced,
+        Err(CandidateError::Offline) => BeaconNodeStatus::Offline,
+        Err(CandidateError::Incompatible) => BeaconNodeStatus::Incompatible,
+        Err(CandidateError::Uninitialized) => BeaconNodeStatus::Uninitialized,
+    }
+}
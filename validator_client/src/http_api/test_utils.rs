@@ -3,7 +3,7 @@ use crate::key_cache::{KeyCache, CACHE_FILENAME};
 use crate::{
     http_api::{ApiSecret, Config as HttpConfig, Context},
     initialized_validators::{InitializedValidators, OnDecryptFailure},
-    Config, ValidatorDefinitions, ValidatorStore,
+    Config, SigningThreadPool, ValidatorDefinitions, ValidatorStore,
 };
 use account_utils::{
     eth2_wallet::WalletBuilder, mnemonic_from_phrase, random_mnemonic, random_password,
@@ -111,10 +111,11 @@ impl ApiTester {
             slashing_protection,
             Hash256::repeat_byte(42),
             spec,
-            Some(Arc::new(DoppelgangerService::new(log.clone()))),
+            Some(Arc::new(DoppelgangerService::new(None, log.clone()))),
             slot_clock.clone(),
             &config,
-            test_runtime.task_executor.clone(),
+            SigningThreadPool::new(config.signing_threads).unwrap(),
+            None,
             log.clone(),
         ));
 
@@ -130,6 +131,8 @@ impl ApiTester {
             validator_dir: Some(validator_dir.path().into()),
             secrets_dir: Some(secrets_dir.path().into()),
             validator_store: Some(validator_store.clone()),
+            beacon_nodes: None,
+            duties_service: None,
             graffiti_file: None,
             graffiti_flag: Some(Graffiti::default()),
             spec: E::default_spec(),
@@ -3,22 +3,26 @@
 
 mod keystores;
 
+use crate::beacon_node_fallback::{BeaconNodeFallback, CandidateBeaconNode};
 use crate::doppelganger_service::DoppelgangerService;
+use crate::duties_service::{sync::SyncDutiesMap, DutiesService, DutyAndProof};
 use crate::{
     http_api::{ApiSecret, Config as HttpConfig, Context},
     initialized_validators::InitializedValidators,
-    Config, ValidatorDefinitions, ValidatorStore,
+    Config, SigningThreadPool, ValidatorDefinitions, ValidatorStore,
 };
 use account_utils::{
     eth2_wallet::WalletBuilder, mnemonic_from_phrase, random_mnemonic, random_password,
     random_password_string, ZeroizeString,
 };
 use deposit_contract::decode_eth1_tx_data;
+use environment::RuntimeContext;
 use eth2::{
     lighthouse_vc::{http_client::ValidatorClientHttpClient, types::*},
-    types::ErrorMessage as ApiErrorMessage,
-    Error as ApiError,
+    types::{AttesterData, ErrorMessage as ApiErrorMessage, ProposerData},
+    BeaconNodeHttpClient, Error as ApiError, Timeouts,
 };
+use eth2_config::Eth2Config;
 use eth2_keystore::KeystoreBuilder;
 use logging::test_logger;
 use parking_lot::RwLock;
@@ -36,6 +40,8 @@ use tempfile::{tempdir, TempDir};
 use types::graffiti::GraffitiString;
 
 const PASSWORD_BYTES: &[u8] = &[42, 50, 37];
+const BEACON_NODE_URL: &str = "http://localhost:5052/";
+const OTHER_BEACON_NODE_URL: &str = "http://localhost:5053/";
 pub const TEST_DEFAULT_FEE_RECIPIENT: Address = Address::repeat_byte(42);
 
 type E = MainnetEthSpec;
@@ -44,6 +50,7 @@ struct ApiTester {
     client: ValidatorClientHttpClient,
     initialized_validators: Arc<RwLock<InitializedValidators>>,
     validator_store: Arc<ValidatorStore<TestingSlotClock, E>>,
+    duties_service: Arc<DutiesService<TestingSlotClock, E>>,
     url: SensitiveUrl,
     slot_clock: TestingSlotClock,
     _validator_dir: TempDir,
@@ -99,10 +106,11 @@ impl ApiTester {
             slashing_protection,
             Hash256::repeat_byte(42),
             spec.clone(),
-            Some(Arc::new(DoppelgangerService::new(log.clone()))),
+            Some(Arc::new(DoppelgangerService::new(None, log.clone()))),
             slot_clock.clone(),
             &config,
-            test_runtime.task_executor.clone(),
+            SigningThreadPool::new(config.signing_threads).unwrap(),
+            None,
             log.clone(),
         ));
 
@@ -112,12 +120,47 @@ impl ApiTester {
 
         let initialized_validators = validator_store.initialized_validators();
 
+        // The beacon node is never contacted, so it remains uninitialized.
+        let beacon_nodes = BeaconNodeFallback::new(
+            vec![CandidateBeaconNode::new(BeaconNodeHttpClient::new(
+                SensitiveUrl::parse(BEACON_NODE_URL).unwrap(),
+                Timeouts::set_all(Duration::from_secs(1)),
+            ))],
+            vec![],
+            spec.clone(),
+            log.clone(),
+        );
+        let beacon_nodes = Arc::new(beacon_nodes);
+
+        let duties_service = Arc::new(DutiesService {
+            attesters: <_>::default(),
+            proposers: <_>::default(),
+            sync_duties: SyncDutiesMap::new(false),
+            slot_clock: slot_clock.clone(),
+            beacon_nodes: beacon_nodes.clone(),
+            validator_store: validator_store.clone(),
+            unknown_validator_next_poll_slots: <_>::default(),
+            spec: spec.clone(),
+            context: RuntimeContext {
+                executor: test_runtime.task_executor.clone(),
+                eth_spec_instance: E::default(),
+                eth2_config: Eth2Config::mainnet(),
+                eth2_network_config: None,
+                sse_logging_components: None,
+                module_levels: None,
+            },
+            enable_high_validator_count_metrics: false,
+            distributed: false,
+        });
+
         let context = Arc::new(Context {
             task_executor: test_runtime.task_executor.clone(),
             api_secret,
             validator_dir: Some(validator_dir.path().into()),
             secrets_dir: Some(secrets_dir.path().into()),
             validator_store: Some(validator_store.clone()),
+            beacon_nodes: Some(beacon_nodes),
+            duties_service: Some(duties_service.clone()),
             graffiti_file: None,
             graffiti_flag: Some(Graffiti::default()),
             spec: E::default_spec(),
@@ -153,6 +196,7 @@ impl ApiTester {
             client,
             initialized_validators,
             validator_store,
+            duties_service,
             url,
             slot_clock,
             _validator_dir: validator_dir,
@@ -246,6 +290,179 @@ impl ApiTester {
 
         self
     }
+
+    pub async fn test_get_lighthouse_beacon_health(self) -> Self {
+        let result = self
+            .client
+            .get_lighthouse_beacon_health()
+            .await
+            .unwrap()
+            .data;
+        let expected = vec![BeaconNodeHealth {
+            endpoint: BEACON_NODE_URL.to_string(),
+            status: BeaconNodeStatus::Uninitialized,
+        }];
+
+        assert_eq!(result, expected);
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_validators_fee_recipients(
+        self,
+        beacon_node_fee_recipient: Address,
+    ) -> Self {
+        let pubkey = self.client.get_lighthouse_validators().await.unwrap().data[0].voting_pubkey;
+        let fee_recipients = |first: Address, second: Address| {
+            vec![
+                BeaconNodeFeeRecipient {
+                    endpoint: BEACON_NODE_URL.to_string(),
+                    fee_recipient: Some(first),
+                },
+                BeaconNodeFeeRecipient {
+                    endpoint: OTHER_BEACON_NODE_URL.to_string(),
+                    fee_recipient: Some(second),
+                },
+            ]
+        };
+
+        // The beacon node fee recipient takes priority over the process level fee recipient.
+        let result = self
+            .client
+            .get_lighthouse_validators_fee_recipients(&pubkey)
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(
+            result,
+            fee_recipients(TEST_DEFAULT_FEE_RECIPIENT, beacon_node_fee_recipient)
+        );
+
+        // The validator's fee recipient takes priority over both.
+        let validator_fee_recipient = Address::repeat_byte(1);
+        self.client
+            .post_fee_recipient(
+                &pubkey,
+                &UpdateFeeRecipientRequest {
+                    ethaddress: validator_fee_recipient,
+                },
+            )
+            .await
+            .unwrap();
+        let result = self
+            .client
+            .get_lighthouse_validators_fee_recipients(&pubkey)
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(
+            result,
+            fee_recipients(validator_fee_recipient, validator_fee_recipient)
+        );
+
+        // Unknown validators are not found.
+        let result = self
+            .client
+            .get_lighthouse_validators_fee_recipients(&PublicKeyBytes::empty())
+            .await;
+        assert_eq!(result.unwrap_err().status().unwrap(), 404);
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_validators_next_duties(self) -> Self {
+        let pubkey = self.client.get_lighthouse_validators().await.unwrap().data[0].voting_pubkey;
+        let other_pubkey = PublicKeyBytes::empty();
+        let slots_per_epoch = E::slots_per_epoch();
+        self.slot_clock.set_slot(slots_per_epoch + 1);
+        let current_slot = self.slot_clock.now().unwrap();
+
+        // Nothing is known before the duties service has run.
+        let next_duties = self
+            .client
+            .get_lighthouse_validators_next_duties(&pubkey)
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(
+            next_duties,
+            NextDuties {
+                validator_index: None,
+                current_slot,
+                next_attestation_slot: None,
+                next_proposal_slot: None,
+                next_sync_committee_period: None,
+            }
+        );
+
+        // Add a past duty and two upcoming duties for each kind of duty.
+        let attestation_slots = [2, slots_per_epoch + 8, 2 * slots_per_epoch + 3];
+        for slot in attestation_slots.map(Slot::new) {
+            let duty = AttesterData {
+                pubkey,
+                validator_index: 0,
+                committees_at_slot: 1,
+                committee_index: 0,
+                committee_length: 1,
+                validator_committee_index: 0,
+                slot,
+            };
+            self.duties_service
+                .attesters
+                .write()
+                .entry(pubkey)
+                .or_default()
+                .insert(
+                    slot.epoch(slots_per_epoch),
+                    (
+                        Hash256::zero(),
+                        DutyAndProof::new_without_selection_proof(duty, current_slot),
+                    ),
+                );
+        }
+        let proposer = |pubkey, slot| ProposerData {
+            pubkey,
+            validator_index: 0,
+            slot: Slot::new(slot),
+        };
+        self.duties_service.proposers.write().insert(
+            current_slot.epoch(slots_per_epoch),
+            (
+                Hash256::zero(),
+                vec![
+                    proposer(pubkey, slots_per_epoch),
+                    proposer(other_pubkey, slots_per_epoch + 2),
+                    proposer(pubkey, slots_per_epoch + 20),
+                ],
+            ),
+        );
+
+        let next_duties = self
+            .client
+            .get_lighthouse_validators_next_duties(&pubkey)
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(
+            next_duties.next_attestation_slot,
+            Some(Slot::new(slots_per_epoch + 8))
+        );
+        assert_eq!(
+            next_duties.next_proposal_slot,
+            Some(Slot::new(slots_per_epoch + 20))
+        );
+
+        // Unknown validators are not found.
+        let err = self
+            .client
+            .get_lighthouse_validators_next_duties(&other_pubkey)
+            .await
+            .unwrap_err();
+        assert_eq!(err.status().unwrap(), 404);
+
+        self
+    }
+
     pub fn vals_total(&self) -> usize {
         self.initialized_validators.read().num_total()
     }
@@ -846,6 +1063,8 @@ async fn routes_with_invalid_auth() {
         .await
         .test_with_invalid_auth(|client| async move { client.get_lighthouse_health().await })
         .await
+        .test_with_invalid_auth(|client| async move { client.get_lighthouse_beacon_health().await })
+        .await
         .test_with_invalid_auth(|client| async move {
             client.get_lighthouse_spec::<types::Config>().await
         })
@@ -973,6 +1192,48 @@ async fn simple_getters() {
         .test_get_lighthouse_health()
         .await
         .test_get_lighthouse_spec()
+        .await
+        .test_get_lighthouse_beacon_health()
+        .await;
+}
+
+#[tokio::test]
+async fn validator_next_duties() {
+    ApiTester::new()
+        .await
+        .create_hd_validators(HdValidatorScenario {
+            count: 1,
+            specify_mnemonic: false,
+            key_derivation_path_offset: 0,
+            disabled: vec![],
+        })
+        .await
+        .test_get_lighthouse_validators_next_duties()
+        .await;
+}
+
+#[tokio::test]
+async fn validator_fee_recipients() {
+    let beacon_node_fee_recipient = Address::repeat_byte(7);
+    let config = Config {
+        beacon_nodes: vec![
+            SensitiveUrl::parse(BEACON_NODE_URL).unwrap(),
+            SensitiveUrl::parse(OTHER_BEACON_NODE_URL).unwrap(),
+        ],
+        fee_recipient: Some(TEST_DEFAULT_FEE_RECIPIENT),
+        beacon_node_fee_recipients: vec![None, Some(beacon_node_fee_recipient)],
+        ..Config::default()
+    };
+    ApiTester::new_with_config(config)
+        .await
+        .create_hd_validators(HdValidatorScenario {
+            count: 1,
+            specify_mnemonic: false,
+            key_derivation_path_offset: 0,
+            disabled: vec![],
+        })
+        .await
+        .test_get_lighthouse_validators_fee_recipients(beacon_node_fee_recipient)
         .await;
 }
 
@@ -131,6 +131,27 @@ lazy_static::lazy_static! {
         "vc_beacon_block_proposal_changed",
         "A duties update discovered a new block proposer for the current slot",
     );
+    pub static ref BLOCK_PROPOSALS_PUBLISHED: Result<IntCounter> = try_create_int_counter(
+        "vc_beacon_block_proposals_published_total",
+        "Total count of blocks successfully proposed by our validators",
+    );
+    pub static ref BLOCK_PROPOSALS_FAILED: Result<IntCounter> = try_create_int_counter(
+        "vc_beacon_block_proposals_failed_total",
+        "Total count of block proposals by our validators which failed",
+    );
+    pub static ref BLOCK_PROPOSALS_REFUSED: Result<IntCounter> = try_create_int_counter(
+        "vc_beacon_block_proposals_refused_total",
+        "Total count of block proposals by our validators which slashing protection refused to sign",
+    );
+    pub static ref SYNC_SELECTION_PROOF_LEAD_TIMES: Result<Histogram> = try_create_histogram_with_buckets(
+        "vc_sync_selection_proof_lead_times_seconds",
+        "Time between computing a sync selection proof and the start of the slot it is for",
+        exponential_buckets(1.0, 4.0, 10)
+    );
+    pub static ref SYNC_SELECTION_PROOFS_LATE_TOTAL: Result<IntCounter> = try_create_int_counter(
+        "vc_sync_selection_proofs_late_total",
+        "Count of sync selection proofs computed after the start of the slot they are for",
+    );
     /*
      * Endpoint metrics
      */
@@ -156,6 +177,10 @@ lazy_static::lazy_static! {
         "vc_beacon_nodes_synced_count",
         "Number of synced beacon nodes",
     );
+    pub static ref OPTIMISTIC_BEACON_NODES_COUNT: Result<IntGauge> = try_create_int_gauge(
+        "vc_beacon_nodes_optimistic_count",
+        "Number of synced beacon nodes with a head not yet verified by the execution layer",
+    );
     pub static ref TOTAL_BEACON_NODES_COUNT: Result<IntGauge> = try_create_int_gauge(
         "vc_beacon_nodes_total_count",
         "Total number of beacon nodes",
@@ -182,12 +207,40 @@ lazy_static::lazy_static! {
         "vc_block_signing_times_seconds",
         "Duration to obtain a signature for a block",
     );
+    pub static ref SIGNING_LATENCY_TIMES: Result<HistogramVec> = try_create_histogram_vec(
+        "vc_signing_latency_seconds",
+        "Duration to obtain a signature, by signing method and message type",
+        &["type", "message"]
+    );
+    pub static ref SIGNING_QUEUE_TIMES: Result<Histogram> = try_create_histogram(
+        "vc_signing_queue_times_seconds",
+        "Duration a local keystore signature waits for a free thread in the signing pool",
+    );
+    pub static ref SIGNING_DOMAIN_CACHE_HITS: Result<IntCounter> = try_create_int_counter(
+        "vc_signing_domain_cache_hits_total",
+        "Count of signing domains served from the domain cache",
+    );
+    pub static ref SIGNING_DOMAIN_CACHE_MISSES: Result<IntCounter> = try_create_int_counter(
+        "vc_signing_domain_cache_misses_total",
+        "Count of signing domains computed due to a miss in the domain cache",
+    );
 
     pub static ref ATTESTATION_DUTY: Result<IntGaugeVec> = try_create_int_gauge_vec(
         "vc_attestation_duty_slot",
         "Attestation duty slot for all managed validators",
         &["validator"]
     );
+    /*
+     * Rewards
+     */
+    pub static ref VALIDATOR_EXPECTED_REWARD: Result<IntGauge> = try_create_int_gauge(
+        "validator_expected_reward",
+        "Total ideal attestation reward in Gwei of all validators for the last rewarded epoch",
+    );
+    pub static ref VALIDATOR_ACTUAL_REWARD: Result<IntGauge> = try_create_int_gauge(
+        "validator_actual_reward",
+        "Total actual attestation reward in Gwei of all validators for the last rewarded epoch",
+    );
     /*
      * BN latency
      */
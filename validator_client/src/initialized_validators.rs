@@ -15,7 +15,8 @@ use account_utils::{
     },
     ZeroizeString,
 };
-use eth2_keystore::Keystore;
+use eth2_keystore::{Keystore, PlainText};
+use futures::{stream, StreamExt};
 use lighthouse_metrics::set_gauge;
 use lockfile::{Lockfile, LockfileError};
 use parking_lot::{MappedMutexGuard, Mutex, MutexGuard};
@@ -24,6 +25,7 @@ use slog::{debug, error, info, warn, Logger};
 use std::collections::{HashMap, HashSet};
 use std::fs::{self, File};
 use std::io::{self, Read};
+use std::num::NonZeroUsize;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
@@ -45,6 +47,11 @@ const DEFAULT_REMOTE_SIGNER_REQUEST_TIMEOUT: Duration = Duration::from_secs(12);
 // Use TTY instead of stdin to capture passwords from users.
 const USE_STDIN: bool = false;
 
+/// The maximum number of keystores to decrypt at once.
+///
+/// Each decryption occupies a thread and, with the default scrypt parameters, 256MiB of memory.
+const MAX_CONCURRENT_KEYSTORE_DECRYPTIONS: usize = 8;
+
 pub enum OnDecryptFailure {
     /// If the key cache fails to decrypt, create a new cache.
     CreateNew,
@@ -215,7 +222,7 @@ impl InitializedValidator {
             return Err(Error::UnableToInitializeDisabledValidator);
         }
 
-        let signing_method = match def.signing_definition {
+        let signing_method = match def.signing_definition.clone() {
             // Load the keystore, password, decrypt the keypair and create a lockfile for a
             // EIP-2335 keystore on the local filesystem.
             SigningDefinition::LocalKeystore {
@@ -274,25 +281,12 @@ impl InitializedValidator {
                     keypair
                 };
 
-                if voting_keypair.pk != def.voting_public_key {
-                    return Err(Error::VotingPublicKeyMismatch {
-                        definition: Box::new(def.voting_public_key),
-                        keystore: Box::new(voting_keypair.pk),
-                    });
-                }
-
-                // Append a `.lock` suffix to the voting keystore.
-                let lockfile_path = get_lockfile_path(&voting_keystore_path)
-                    .ok_or_else(|| Error::BadVotingKeystorePath(voting_keystore_path.clone()))?;
-
-                let voting_keystore_lockfile = Mutex::new(Some(Lockfile::new(lockfile_path)?));
-
-                SigningMethod::LocalKeystore {
+                Self::local_keystore_signing_method(
+                    &def,
                     voting_keystore_path,
-                    voting_keystore_lockfile,
-                    voting_keystore: voting_keystore.clone(),
-                    voting_keypair: Arc::new(voting_keypair),
-                }
+                    voting_keystore.clone(),
+                    voting_keypair,
+                )?
             }
             SigningDefinition::Web3Signer(web3_signer) => {
                 let signing_url = build_web3_signer_url(&web3_signer.url, &def.voting_public_key)
@@ -340,21 +334,74 @@ impl InitializedValidator {
                 SigningMethod::Web3Signer {
                     signing_url,
                     http_client,
-                    voting_public_key: def.voting_public_key,
+                    voting_public_key: def.voting_public_key.clone(),
                 }
             }
         };
 
-        Ok(Self {
+        Ok(Self::from_signing_method(&def, signing_method))
+    }
+
+    /// Instantiate `self` from a `ValidatorDefinition` for a local keystore at
+    /// `voting_keystore_path`, which has already been decrypted.
+    fn from_unlocked_keystore(
+        def: &ValidatorDefinition,
+        voting_keystore_path: PathBuf,
+        unlocked: UnlockedKeystore,
+    ) -> Result<Self, Error> {
+        if !def.enabled {
+            return Err(Error::UnableToInitializeDisabledValidator);
+        }
+
+        let signing_method = Self::local_keystore_signing_method(
+            def,
+            voting_keystore_path,
+            unlocked.keystore,
+            unlocked.keypair,
+        )?;
+
+        Ok(Self::from_signing_method(def, signing_method))
+    }
+
+    /// Check the decrypted `voting_keypair` against `def` and create a lockfile for the keystore.
+    fn local_keystore_signing_method(
+        def: &ValidatorDefinition,
+        voting_keystore_path: PathBuf,
+        voting_keystore: Keystore,
+        voting_keypair: Keypair,
+    ) -> Result<SigningMethod, Error> {
+        if voting_keypair.pk != def.voting_public_key {
+            return Err(Error::VotingPublicKeyMismatch {
+                definition: Box::new(def.voting_public_key.clone()),
+                keystore: Box::new(voting_keypair.pk),
+            });
+        }
+
+        // Append a `.lock` suffix to the voting keystore.
+        let lockfile_path = get_lockfile_path(&voting_keystore_path)
+            .ok_or_else(|| Error::BadVotingKeystorePath(voting_keystore_path.clone()))?;
+
+        let voting_keystore_lockfile = Mutex::new(Some(Lockfile::new(lockfile_path)?));
+
+        Ok(SigningMethod::LocalKeystore {
+            voting_keystore_path,
+            voting_keystore_lockfile,
+            voting_keystore,
+            voting_keypair: Arc::new(voting_keypair),
+        })
+    }
+
+    fn from_signing_method(def: &ValidatorDefinition, signing_method: SigningMethod) -> Self {
+        Self {
             signing_method: Arc::new(signing_method),
-            graffiti: def.graffiti.map(Into::into),
+            graffiti: def.graffiti.clone().map(Into::into),
             suggested_fee_recipient: def.suggested_fee_recipient,
             gas_limit: def.gas_limit,
             builder_proposals: def.builder_proposals,
             builder_boost_factor: def.builder_boost_factor,
             prefer_builder_proposals: def.prefer_builder_proposals,
             index: None,
-        })
+        }
     }
 
     /// Returns the voting public key for this validator.
@@ -432,6 +479,137 @@ fn build_web3_signer_client(
         .map_err(Error::UnableToBuildWeb3SignerClient)
 }
 
+/// A keystore which must be decrypted before its validator can be initialized.
+pub struct LockedKeystore {
+    voting_public_key: PublicKey,
+    keystore: Keystore,
+    password: PlainText,
+}
+
+impl LockedKeystore {
+    /// Returns the keystore for `def`, if it is a local keystore with a known password.
+    ///
+    /// Keystores without a password are excluded since they require the user to be prompted for
+    /// the password, which can't be done in parallel.
+    fn from_definition(
+        def: &ValidatorDefinition,
+        keystore: Keystore,
+    ) -> Result<Option<Self>, Error> {
+        let password = match &def.signing_definition {
+            SigningDefinition::LocalKeystore {
+                voting_keystore_password: Some(password),
+                ..
+            } => password.as_ref().to_vec().into(),
+            SigningDefinition::LocalKeystore {
+                voting_keystore_password_path: Some(path),
+                ..
+            } => read_password(path).map_err(Error::UnableToReadVotingKeystorePassword)?,
+            SigningDefinition::LocalKeystore { .. } | SigningDefinition::Web3Signer(_) => {
+                return Ok(None)
+            }
+        };
+        Ok(Some(Self {
+            voting_public_key: def.voting_public_key.clone(),
+            keystore,
+            password,
+        }))
+    }
+}
+
+/// A decrypted keystore, ready to be added to the key cache.
+pub struct UnlockedKeystore {
+    keystore: Keystore,
+    keypair: Keypair,
+    password: PlainText,
+}
+
+impl UnlockedKeystore {
+    pub fn voting_public_key(&self) -> &PublicKey {
+        &self.keypair.pk
+    }
+}
+
+/// Decrypt `keystores` in parallel on the blocking thread pool, running at most
+/// `MAX_CONCURRENT_KEYSTORE_DECRYPTIONS` decryptions at once.
+///
+/// Returns the result of decrypting each keystore, alongside its voting public key.
+pub async fn decrypt_keystores(
+    keystores: Vec<LockedKeystore>,
+) -> Vec<(PublicKey, Result<UnlockedKeystore, Error>)> {
+    let max_concurrent = std::thread::available_parallelism()
+        .map_or(1, NonZeroUsize::get)
+        .min(MAX_CONCURRENT_KEYSTORE_DECRYPTIONS);
+
+    stream::iter(keystores)
+        .map(|locked| async move {
+            let voting_public_key = locked.voting_public_key.clone();
+            let result = tokio::task::spawn_blocking(move || {
+                let keypair = locked
+                    .keystore
+                    .decrypt_keypair(locked.password.as_bytes())
+                    .map_err(Error::UnableToDecryptKeystore)?;
+                Ok(UnlockedKeystore {
+                    keystore: locked.keystore,
+                    keypair,
+                    password: locked.password,
+                })
+            })
+            .await
+            .map_err(Error::TokioJoin)
+            .and_then(|result| result);
+            (voting_public_key, result)
+        })
+        .buffer_unordered(max_concurrent)
+        .collect()
+        .await
+}
+
+/// The key cache for keystores decrypted in the background due to `--lazy-unlock`.
+///
+/// This is held apart from `InitializedValidators` so that it can be updated and saved without
+/// holding a lock on the validators.
+pub struct UnlockKeyCache {
+    key_cache: KeyCache,
+    validators_dir: PathBuf,
+}
+
+impl UnlockKeyCache {
+    pub fn add(&mut self, unlocked: &UnlockedKeystore) {
+        self.key_cache.add(
+            unlocked.keypair.clone(),
+            unlocked.keystore.uuid(),
+            unlocked.password.clone(),
+        );
+    }
+
+    /// Save the key cache to disk if it has been modified.
+    ///
+    /// Returns `true` if the key cache was saved.
+    pub async fn save(&mut self) -> Result<bool, Error> {
+        let mut key_cache = std::mem::take(&mut self.key_cache);
+        let validators_dir = self.validators_dir.clone();
+        let (key_cache, result) = tokio::task::spawn_blocking(move || {
+            let result = Self::save_locked(&mut key_cache, &validators_dir);
+            (key_cache, result)
+        })
+        .await
+        .map_err(Error::TokioJoin)?;
+        self.key_cache = key_cache;
+        result
+    }
+
+    /// Save `key_cache` while holding the key cache lockfile, as `update_validators` does.
+    fn save_locked(key_cache: &mut KeyCache, validators_dir: &Path) -> Result<bool, Error> {
+        let key_cache_path = KeyCache::cache_file_path(validators_dir);
+        let cache_lockfile_path =
+            get_lockfile_path(&key_cache_path).ok_or(Error::BadKeyCachePath(key_cache_path))?;
+        let _cache_lockfile = Lockfile::new(cache_lockfile_path)?;
+        key_cache
+            .save(validators_dir)
+            .map_err(Error::UnableToSaveKeyCache)
+    }
+}
+
 /// Try to unlock `keystore` at `keystore_path` by prompting the user via `stdin`.
 fn unlock_keystore_via_stdin_password(
     keystore: &Keystore,
@@ -484,6 +662,12 @@ pub struct InitializedValidators {
     validators: HashMap<PublicKeyBytes, InitializedValidator>,
     /// The clients used for communications with a remote signer.
     web3_signer_client_map: Option<HashMap<Web3SignerDefinition, Client>>,
+    /// Enabled validators whose keystores are yet to be decrypted in the background, due to
+    /// `--lazy-unlock`.
+    pending_unlock: HashSet<PublicKeyBytes>,
+    /// The key cache decrypted at startup, to which the keystores in `pending_unlock` are added
+    /// once decrypted.
+    unlock_key_cache: Option<UnlockKeyCache>,
     /// For logging via `slog`.
     log: Logger,
     config: Config,
@@ -502,10 +686,13 @@ impl InitializedValidators {
             definitions,
             validators: HashMap::default(),
             web3_signer_client_map: None,
+            pending_unlock: HashSet::new(),
+            unlock_key_cache: None,
             config,
             log,
         };
-        this.update_validators().await?;
+        let lazy_unlock = this.config.lazy_unlock;
+        this.update_validators_inner(lazy_unlock).await?;
         Ok(this)
     }
 
@@ -524,6 +711,130 @@ impl InitializedValidators {
         self.validators.keys()
     }
 
+    /// The count of enabled validators which are waiting for their keystores to be decrypted.
+    pub fn num_pending_unlock(&self) -> usize {
+        self.pending_unlock.len()
+    }
+
+    /// Iterate through the voting public keys of validators which are waiting for their keystores
+    /// to be decrypted.
+    pub fn iter_pending_unlock_pubkeys(&self) -> impl Iterator<Item = &PublicKeyBytes> {
+        self.pending_unlock.iter()
+    }
+
+    /// Returns the keystores of the validators which are waiting to be decrypted.
+    ///
+    /// The keystores and passwords are read from disk each time, so that a validator which failed
+    /// to unlock may be fixed by the user. A validator whose keystore or password can't be read is
+    /// returned with an error.
+    pub fn pending_unlock_keystores(&self) -> Vec<(PublicKey, Result<LockedKeystore, Error>)> {
+        let mut keystores = vec![];
+        for def in self.definitions.as_slice() {
+            if !def.enabled
+                || !self
+                    .pending_unlock
+                    .contains(&def.voting_public_key.compress())
+            {
+                continue;
+            }
+            if let SigningDefinition::LocalKeystore {
+                voting_keystore_path,
+                ..
+            } = &def.signing_definition
+            {
+                let result = open_keystore(voting_keystore_path)
+                    .and_then(|keystore| LockedKeystore::from_definition(def, keystore))
+                    .transpose();
+                keystores.extend(result.map(|result| (def.voting_public_key.clone(), result)));
+            }
+        }
+        keystores
+    }
+
+    /// Initialize the validators for keystores which were decrypted in the background.
+    ///
+    /// This doesn't block on I/O or decryption, so that it's cheap to call while holding a lock on
+    /// `self`. The unlocked keystores should be added to the key cache from
+    /// `Self::take_unlock_key_cache`.
+    ///
+    /// Returns the validators which failed to initialize. They remain pending, to be retried.
+    pub fn insert_unlocked_keystores(
+        &mut self,
+        unlocked_keystores: Vec<UnlockedKeystore>,
+    ) -> Vec<(PublicKey, Error)> {
+        let mut failures = vec![];
+        for unlocked in unlocked_keystores {
+            let pubkey_bytes = unlocked.voting_public_key().compress();
+            if !self.pending_unlock.contains(&pubkey_bytes) {
+                // The validator was disabled or deleted while it was being decrypted.
+                continue;
+            }
+
+            let Some((def, voting_keystore_path)) =
+                self.definitions
+                    .as_slice()
+                    .iter()
+                    .find_map(|def| match &def.signing_definition {
+                        SigningDefinition::LocalKeystore {
+                            voting_keystore_path,
+                            ..
+                        } if def.enabled && def.voting_public_key.compress() == pubkey_bytes => {
+                            Some((def, voting_keystore_path.clone()))
+                        }
+                        _ => None,
+                    })
+            else {
+                continue;
+            };
+
+            let voting_public_key = def.voting_public_key.clone();
+            match InitializedValidator::from_unlocked_keystore(def, voting_keystore_path, unlocked)
+            {
+                Ok(init) => {
+                    let existing_lockfile_path = init
+                        .keystore_lockfile()
+                        .as_ref()
+                        .filter(|l| l.file_existed())
+                        .map(|l| l.path().to_owned());
+
+                    self.validators.insert(pubkey_bytes, init);
+                    self.pending_unlock.remove(&pubkey_bytes);
+                    info!(
+                        self.log,
+                        "Enabled validator";
+                        "signing_method" => "local_keystore",
+                        "voting_pubkey" => format!("{:?}", voting_public_key),
+                    );
+
+                    if let Some(lockfile_path) = existing_lockfile_path {
+                        warn!(
+                            self.log,
+                            "Ignored stale lockfile";
+                            "path" => lockfile_path.display(),
+                            "cause" => "Ungraceful shutdown (harmless) OR \
+                                        non-Lighthouse client using this keystore \
+                                        (risky)"
+                        );
+                    }
+                }
+                Err(e) => failures.push((voting_public_key, e)),
+            }
+        }
+
+        set_gauge(
+            &crate::http_metrics::metrics::ENABLED_VALIDATORS_COUNT,
+            self.num_enabled() as i64,
+        );
+        failures
+    }
+
+    /// Take the key cache to which keystores decrypted in the background should be added.
+    ///
+    /// Returns `None` if no validators were left pending at startup, or if it was already taken.
+    pub fn take_unlock_key_cache(&mut self) -> Option<UnlockKeyCache> {
+        self.unlock_key_cache.take()
+    }
+
     /// Returns the voting `Keypair` for a given voting `PublicKey`, if all are true:
     ///
     ///  - The validator is known to `self`.
@@ -1183,6 +1494,39 @@ impl InitializedValidators {
         .map_err(Error::UnableToDecryptKeyCache)
     }
 
+    /// Returns the keystores of enabled validators which are neither initialized nor pending, and
+    /// which are missing from `key_cache`.
+    fn locked_keystores(
+        &self,
+        key_cache: &KeyCache,
+        key_stores: &mut HashMap<PathBuf, Keystore>,
+    ) -> Result<Vec<LockedKeystore>, Error> {
+        let mut keystores = vec![];
+        for def in self.definitions.as_slice().iter().filter(|def| def.enabled) {
+            let pubkey_bytes = def.voting_public_key.compress();
+            if self.validators.contains_key(&pubkey_bytes)
+                || self.pending_unlock.contains(&pubkey_bytes)
+            {
+                continue;
+            }
+            if let SigningDefinition::LocalKeystore {
+                voting_keystore_path,
+                ..
+            } = &def.signing_definition
+            {
+                use std::collections::hash_map::Entry::*;
+                let keystore = match key_stores.entry(voting_keystore_path.clone()) {
+                    Vacant(entry) => entry.insert(open_keystore(voting_keystore_path)?),
+                    Occupied(entry) => entry.into_mut(),
+                };
+                if key_cache.get(keystore.uuid()).is_none() {
+                    keystores.extend(LockedKeystore::from_definition(def, keystore.clone())?);
+                }
+            }
+        }
+        Ok(keystores)
+    }
+
     /// Scans `self.definitions` and attempts to initialize and validators which are not already
     /// initialized.
     ///
@@ -1195,6 +1539,14 @@ impl InitializedValidators {
     /// I.e., if there are two different definitions with the same public key then the second will
     /// be ignored.
     pub(crate) async fn update_validators(&mut self) -> Result<(), Error> {
+        self.update_validators_inner(false).await
+    }
+
+    /// As for `update_validators`.
+    ///
+    /// If `lazy_unlock` is true, validators whose keystores would need to be decrypted are added
+    /// to `self.pending_unlock` rather than being initialized.
+    async fn update_validators_inner(&mut self, lazy_unlock: bool) -> Result<(), Error> {
         //use key cache if available
         let mut key_stores = HashMap::new();
 
@@ -1225,12 +1577,47 @@ impl InitializedValidators {
             KeyCache::new()
         };
 
+        // Decrypt any keystores missing from the key cache in parallel, rather than one at a time
+        // as each validator is initialized below.
+        let locked_keystores = self.locked_keystores(&key_cache, &mut key_stores)?;
+        if lazy_unlock {
+            self.pending_unlock.extend(
+                locked_keystores
+                    .iter()
+                    .map(|locked| locked.voting_public_key.compress()),
+            );
+        } else {
+            for (voting_public_key, result) in decrypt_keystores(locked_keystores).await {
+                match result {
+                    Ok(unlocked) => key_cache.add(
+                        unlocked.keypair,
+                        unlocked.keystore.uuid(),
+                        unlocked.password,
+                    ),
+                    Err(e) => {
+                        error!(
+                            self.log,
+                            "Failed to initialize validator";
+                            "error" => format!("{:?}", e),
+                            "signing_method" => "local_keystore",
+                            "validator" => format!("{:?}", voting_public_key)
+                        );
+
+                        // Exit on an invalid validator.
+                        return Err(e);
+                    }
+                }
+            }
+        }
+
         let mut disabled_uuids = HashSet::new();
         for def in self.definitions.as_slice() {
             if def.enabled {
                 let pubkey_bytes = def.voting_public_key.compress();
 
-                if self.validators.contains_key(&pubkey_bytes) {
+                if self.validators.contains_key(&pubkey_bytes)
+                    || self.pending_unlock.contains(&pubkey_bytes)
+                {
                     continue;
                 }
 
@@ -1331,6 +1718,8 @@ impl InitializedValidators {
                 }
             } else {
                 self.validators.remove(&def.voting_public_key.compress());
+                self.pending_unlock
+                    .remove(&def.voting_public_key.compress());
                 match &def.signing_definition {
                     SigningDefinition::LocalKeystore {
                         voting_keystore_path,
@@ -1358,6 +1747,13 @@ impl InitializedValidators {
             }
         }
 
+        if lazy_unlock && has_local_definitions && !self.pending_unlock.is_empty() {
+            self.unlock_key_cache = Some(UnlockKeyCache {
+                key_cache: key_cache.clone(),
+                validators_dir: self.validators_dir.clone(),
+            });
+        }
+
         let validators_dir = self.validators_dir.clone();
         let log = self.log.clone();
         if has_local_definitions && key_cache.is_modified() {
@@ -4,12 +4,14 @@ mod block_service;
 mod check_synced;
 mod cli;
 mod duties_service;
+mod event_hooks;
 mod graffiti_file;
 mod http_metrics;
 mod key_cache;
 mod latency;
 mod notifier;
 mod preparation_service;
+mod reward_metrics;
 mod signing_method;
 mod sync_committee_service;
 
@@ -26,6 +28,7 @@ use initialized_validators::InitializedValidators;
 use lighthouse_metrics::set_gauge;
 use monitoring_api::{MonitoringHttpClient, ProcessType};
 use sensitive_url::SensitiveUrl;
+pub use signing_method::SigningThreadPool;
 pub use slashing_protection::{SlashingDatabase, SLASHING_PROTECTION_FILENAME};
 
 use crate::beacon_node_fallback::{
@@ -33,6 +36,7 @@ use crate::beacon_node_fallback::{
     RequireSynced,
 };
 use crate::doppelganger_service::DoppelgangerService;
+use crate::event_hooks::EventHooks;
 use crate::graffiti_file::GraffitiFile;
 use crate::initialized_validators::Error::UnableToOpenVotingKeystore;
 use account_utils::validator_definitions::ValidatorDefinitions;
@@ -229,13 +233,19 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
                 }
             })?;
 
-        let voting_pubkeys: Vec<_> = validators.iter_voting_pubkeys().collect();
+        // Validators pending a lazy unlock are registered with slashing protection up-front.
+        let voting_pubkeys: Vec<_> = validators
+            .iter_voting_pubkeys()
+            .chain(validators.iter_pending_unlock_pubkeys())
+            .collect();
 
         info!(
             log,
             "Initialized validators";
-            "disabled" => validators.num_total().saturating_sub(validators.num_enabled()),
+            "disabled" => validators.num_total()
+                .saturating_sub(validators.num_enabled() + validators.num_pending_unlock()),
             "enabled" => validators.num_enabled(),
+            "pending_unlock" => validators.num_pending_unlock(),
         );
 
         if voting_pubkeys.is_empty() {
@@ -392,6 +402,13 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
         // Initialize the number of connected, avaliable beacon nodes to 0.
         set_gauge(&http_metrics::metrics::AVAILABLE_BEACON_NODES_COUNT, 0);
 
+        let event_hooks = EventHooks::from_config(
+            &config,
+            context.executor.clone(),
+            context.service_context("event_hooks".into()).log().clone(),
+        )?
+        .map(Arc::new);
+
         let mut beacon_nodes: BeaconNodeFallback<_, E> = BeaconNodeFallback::new(
             candidates,
             config.broadcast_topics.clone(),
@@ -425,6 +442,8 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
 
         beacon_nodes.set_slot_clock(slot_clock.clone());
         proposer_nodes.set_slot_clock(slot_clock.clone());
+        beacon_nodes.set_event_hooks(event_hooks.clone());
+        proposer_nodes.set_event_hooks(event_hooks.clone());
 
         let beacon_nodes = Arc::new(beacon_nodes);
         start_fallback_updater_service(context.clone(), beacon_nodes.clone())?;
@@ -434,6 +453,7 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
 
         let doppelganger_service = if config.enable_doppelganger_protection {
             Some(Arc::new(DoppelgangerService::new(
+                event_hooks.clone(),
                 context
                     .service_context(DOPPELGANGER_SERVICE_NAME.into())
                     .log()
@@ -451,7 +471,8 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
             doppelganger_service.clone(),
             slot_clock.clone(),
             &config,
-            context.executor.clone(),
+            SigningThreadPool::new(config.signing_threads)?,
+            event_hooks.clone(),
             log.clone(),
         ));
 
@@ -498,7 +519,8 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
             .beacon_nodes(beacon_nodes.clone())
             .runtime_context(context.service_context("block".into()))
             .graffiti(config.graffiti)
-            .graffiti_file(config.graffiti_file.clone());
+            .graffiti_file(config.graffiti_file.clone())
+            .event_hooks(event_hooks);
 
         // If we have proposer nodes, add them to the block service builder.
         if proposer_nodes_num > 0 {
@@ -513,6 +535,7 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
             .validator_store(validator_store.clone())
             .beacon_nodes(beacon_nodes.clone())
             .runtime_context(context.service_context("attestation".into()))
+            .attestation_delay(config.attestation_delay)
             .build()?;
 
         let preparation_service = PreparationServiceBuilder::new()
@@ -564,6 +587,8 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
                 task_executor: self.context.executor.clone(),
                 api_secret,
                 validator_store: Some(self.validator_store.clone()),
+                beacon_nodes: Some(self.beacon_nodes.clone()),
+                duties_service: Some(self.duties_service.clone()),
                 validator_dir: Some(self.config.validator_dir.clone()),
                 secrets_dir: Some(self.config.secrets_dir.clone()),
                 graffiti_file: self.config.graffiti_file.clone(),
@@ -592,6 +617,30 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
             None
         };
 
+        if self
+            .validator_store
+            .initialized_validators()
+            .read()
+            .num_pending_unlock()
+            > 0
+        {
+            let validator_store = self.validator_store.clone();
+            let unlock_log = log.clone();
+            self.context.executor.spawn(
+                async move {
+                    match validator_store.unlock_pending_validators().await {
+                        Ok(unlocked) => {
+                            info!(unlock_log, "Finished unlocking validators"; "unlocked" => unlocked)
+                        }
+                        Err(e) => {
+                            error!(unlock_log, "Failed to unlock validators"; "error" => e)
+                        }
+                    }
+                },
+                "lazy_unlock",
+            );
+        }
+
         // Wait until genesis has occurred.
         wait_for_genesis(&self.beacon_nodes, self.genesis_time, &self.context).await?;
 
@@ -633,6 +682,15 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
 
         spawn_notifier(self).map_err(|e| format!("Failed to start notifier: {}", e))?;
 
+        if self.config.enable_reward_metrics {
+            reward_metrics::start_reward_metrics_service(
+                self.context.service_context("reward_metrics".into()),
+                self.duties_service.slot_clock.clone(),
+                self.duties_service.beacon_nodes.clone(),
+                self.validator_store.clone(),
+            );
+        }
+
         if self.config.enable_latency_measurement_service {
             latency::start_latency_service(
                 self.context.clone(),
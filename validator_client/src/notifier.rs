@@ -1,7 +1,7 @@
 use crate::http_metrics;
 use crate::{DutiesService, ProductionValidatorClient};
 use lighthouse_metrics::set_gauge;
-use slog::{error, info, Logger};
+use slog::{error, info, warn, Logger};
 use slot_clock::SlotClock;
 use tokio::time::{sleep, Duration};
 use types::EthSpec;
@@ -49,6 +49,11 @@ async fn notify<T: SlotClock + 'static, E: EthSpec>(
         &http_metrics::metrics::SYNCED_BEACON_NODES_COUNT,
         num_synced as i64,
     );
+    let num_optimistic = duties_service.beacon_nodes.num_optimistic().await;
+    set_gauge(
+        &http_metrics::metrics::OPTIMISTIC_BEACON_NODES_COUNT,
+        num_optimistic as i64,
+    );
     let num_total = duties_service.beacon_nodes.num_total();
     set_gauge(
         &http_metrics::metrics::TOTAL_BEACON_NODES_COUNT,
@@ -62,6 +67,15 @@ async fn notify<T: SlotClock + 'static, E: EthSpec>(
             "available" => num_available,
             "synced" => num_synced,
         )
+    } else if num_optimistic > 0 {
+        warn!(
+            log,
+            "Waiting for execution layer to verify beacon node head";
+            "msg" => "attestations are paused whilst the head is optimistic",
+            "total" => num_total,
+            "available" => num_available,
+            "optimistic" => num_optimistic,
+        )
     } else {
         error!(
             log,
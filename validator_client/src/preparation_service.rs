@@ -6,7 +6,7 @@ use environment::RuntimeContext;
 use parking_lot::RwLock;
 use slog::{debug, error, info, warn};
 use slot_clock::SlotClock;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::hash::Hash;
 use std::ops::Deref;
 use std::sync::Arc;
@@ -264,17 +264,40 @@ impl<T: SlotClock + 'static, E: EthSpec> PreparationService<T, E> {
 
     /// Prepare proposer preparations and send to beacon node
     async fn prepare_proposers_and_publish(&self, spec: &ChainSpec) -> Result<(), String> {
-        let preparation_data = self.collect_preparation_data(spec);
-        if !preparation_data.is_empty() {
+        let preparation_data = self
+            .beacon_node_fee_recipients()
+            .into_iter()
+            .map(|fee_recipient| {
+                (
+                    fee_recipient,
+                    self.collect_preparation_data(spec, fee_recipient),
+                )
+            })
+            .collect::<HashMap<_, _>>();
+        if preparation_data.values().any(|data| !data.is_empty()) {
             self.publish_preparation_data(preparation_data).await?;
         }
 
         Ok(())
     }
 
-    fn collect_preparation_data(&self, spec: &ChainSpec) -> Vec<ProposerPreparationData> {
+    /// Returns the distinct fee recipients configured for the beacon nodes, with `None` for beacon
+    /// nodes which use the process level fee recipient.
+    fn beacon_node_fee_recipients(&self) -> HashSet<Option<Address>> {
+        self.validator_store
+            .beacon_node_fee_recipients()
+            .iter()
+            .map(|(_, fee_recipient)| *fee_recipient)
+            .collect()
+    }
+
+    fn collect_preparation_data(
+        &self,
+        spec: &ChainSpec,
+        beacon_node_fee_recipient: Option<Address>,
+    ) -> Vec<ProposerPreparationData> {
         let log = self.context.log();
-        self.collect_proposal_data(|pubkey, proposal_data| {
+        self.collect_proposal_data(beacon_node_fee_recipient, |pubkey, proposal_data| {
             if let Some(fee_recipient) = proposal_data.fee_recipient {
                 Some(ProposerPreparationData {
                     // Ignore fee recipients for keys without indices, they are inactive.
@@ -296,7 +319,7 @@ impl<T: SlotClock + 'static, E: EthSpec> PreparationService<T, E> {
     }
 
     fn collect_validator_registration_keys(&self) -> Vec<ValidatorRegistrationKey> {
-        self.collect_proposal_data(|pubkey, proposal_data| {
+        self.collect_proposal_data(None, |pubkey, proposal_data| {
             // Ignore fee recipients for keys without indices, they are inactive.
             proposal_data.validator_index?;
 
@@ -314,7 +337,11 @@ impl<T: SlotClock + 'static, E: EthSpec> PreparationService<T, E> {
         })
     }
 
-    fn collect_proposal_data<G, U>(&self, map_fn: G) -> Vec<U>
+    fn collect_proposal_data<G, U>(
+        &self,
+        beacon_node_fee_recipient: Option<Address>,
+        map_fn: G,
+    ) -> Vec<U>
     where
         G: Fn(PublicKeyBytes, ProposalData) -> Option<U>,
     {
@@ -325,7 +352,9 @@ impl<T: SlotClock + 'static, E: EthSpec> PreparationService<T, E> {
         all_pubkeys
             .into_iter()
             .filter_map(|pubkey| {
-                let proposal_data = self.validator_store.proposal_data(&pubkey)?;
+                let proposal_data = self
+                    .validator_store
+                    .proposal_data(&pubkey, beacon_node_fee_recipient)?;
                 map_fn(pubkey, proposal_data)
             })
             .collect()
@@ -333,13 +362,14 @@ impl<T: SlotClock + 'static, E: EthSpec> PreparationService<T, E> {
 
     async fn publish_preparation_data(
         &self,
-        preparation_data: Vec<ProposerPreparationData>,
+        preparation_data: HashMap<Option<Address>, Vec<ProposerPreparationData>>,
     ) -> Result<(), String> {
         let log = self.context.log();
 
         // Post the proposer preparations to the BN.
-        let preparation_data_len = preparation_data.len();
-        let preparation_entries = preparation_data.as_slice();
+        let preparation_data_len = preparation_data.values().map(Vec::len).max().unwrap_or(0);
+        let preparation_data = &preparation_data;
+        let validator_store = &self.validator_store;
         match self
             .beacon_nodes
             .request(
@@ -347,6 +377,10 @@ impl<T: SlotClock + 'static, E: EthSpec> PreparationService<T, E> {
                 OfflineOnFailure::Yes,
                 ApiTopic::Subscriptions,
                 |beacon_node| async move {
+                    // Each beacon node is sent the fee recipients for its own proposals.
+                    let preparation_entries = preparation_data
+                        .get(&validator_store.beacon_node_fee_recipient(beacon_node))
+                        .map_or(&[][..], Vec::as_slice);
                     beacon_node
                         .post_validator_prepare_beacon_proposer(preparation_entries)
                         .await
@@ -369,6 +403,12 @@ impl<T: SlotClock + 'static, E: EthSpec> PreparationService<T, E> {
     }
 
     /// Register validators with builders, used in the blinded block proposal flow.
+    ///
+    /// Each validator has a single registration, with the fee recipient which ignores
+    /// `--beacon-node-fee-recipients`. Builders share registrations through the relays, so
+    /// registering a different fee recipient via each beacon node would leave the fee recipient of
+    /// builder payloads up to whichever registration a relay saw last. The beacon node fee
+    /// recipients only apply to locally built payloads.
     async fn register_validators(&self) -> Result<(), String> {
         let registration_keys = self.collect_validator_registration_keys();
 
@@ -406,8 +446,45 @@ impl<T: SlotClock + 'static, E: EthSpec> PreparationService<T, E> {
     ) -> Result<(), String> {
         let log = self.context.log();
 
-        let registration_data_len = registration_keys.len();
-        let mut signed = Vec::with_capacity(registration_data_len);
+        let signed = self
+            .sign_validator_registration_data(registration_keys)
+            .await?;
+
+        for batch in signed.chunks(self.validator_registration_batch_size) {
+            match self
+                .beacon_nodes
+                .broadcast(
+                    RequireSynced::No,
+                    OfflineOnFailure::No,
+                    |beacon_node| async move {
+                        beacon_node.post_validator_register_validator(batch).await
+                    },
+                )
+                .await
+            {
+                Ok(()) => info!(
+                    log,
+                    "Published validator registrations to the builder network";
+                    "count" => batch.len(),
+                ),
+                Err(e) => warn!(
+                    log,
+                    "Unable to publish validator registrations to the builder network";
+                    "error" => %e,
+                ),
+            }
+        }
+        Ok(())
+    }
+
+    /// Sign the registration for each key, re-using the signatures of unchanged registrations.
+    async fn sign_validator_registration_data(
+        &self,
+        registration_keys: Vec<ValidatorRegistrationKey>,
+    ) -> Result<Vec<SignedValidatorRegistrationData>, String> {
+        let log = self.context.log();
+
+        let mut signed = Vec::with_capacity(registration_keys.len());
 
         for key in registration_keys {
             let cached_registration_opt =
@@ -473,33 +550,7 @@ impl<T: SlotClock + 'static, E: EthSpec> PreparationService<T, E> {
             signed.push(signed_data);
         }
 
-        if !signed.is_empty() {
-            for batch in signed.chunks(self.validator_registration_batch_size) {
-                match self
-                    .beacon_nodes
-                    .broadcast(
-                        RequireSynced::No,
-                        OfflineOnFailure::No,
-                        |beacon_node| async move {
-                            beacon_node.post_validator_register_validator(batch).await
-                        },
-                    )
-                    .await
-                {
-                    Ok(()) => info!(
-                        log,
-                        "Published validator registrations to the builder network";
-                        "count" => batch.len(),
-                    ),
-                    Err(e) => warn!(
-                        log,
-                        "Unable to publish validator registrations to the builder network";
-                        "error" => %e,
-                    ),
-                }
-            }
-        }
-        Ok(())
+        Ok(signed)
     }
 }
 
@@ -0,0 +1,237 @@
+use crate::beacon_node_fallback::{BeaconNodeFallback, OfflineOnFailure, RequireSynced};
+use crate::http_metrics::metrics;
+use crate::validator_store::{DoppelgangerStatus, ValidatorStore};
+use environment::RuntimeContext;
+use eth2::lighthouse::StandardAttestationRewards;
+use eth2::types::{StateId, ValidatorId};
+use slog::{debug, warn};
+use slot_clock::SlotClock;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::time::sleep;
+use types::{Epoch, EthSpec, Slot};
+
+/// The number of epochs after which the attestation rewards of an epoch are available.
+///
+/// The rewards for an epoch are applied at the end of the following epoch.
+const REWARDS_DELAY_EPOCHS: u64 = 2;
+
+/// Starts a service that queries the attestation rewards of the validators managed by this client
+/// once per epoch, and exports the expected and actual rewards as metrics.
+pub fn start_reward_metrics_service<T: SlotClock + 'static, E: EthSpec>(
+    context: RuntimeContext<E>,
+    slot_clock: T,
+    beacon_nodes: Arc<BeaconNodeFallback<T, E>>,
+    validator_store: Arc<ValidatorStore<T, E>>,
+) {
+    let log = context.log().clone();
+
+    let future = async move {
+        loop {
+            // Run half way through the first slot of each epoch, when the beacon node is likely
+            // to have imported the first block of the epoch.
+            let sleep_time = slot_clock
+                .duration_to_next_epoch(E::slots_per_epoch())
+                .map(|next_epoch| next_epoch + slot_clock.slot_duration() / 2)
+                .unwrap_or_else(|| slot_clock.slot_duration());
+            sleep(sleep_time).await;
+
+            let Some(epoch) = slot_clock
+                .now()
+                .map(|slot| slot.epoch(E::slots_per_epoch()))
+                .and_then(|epoch| epoch.as_u64().checked_sub(REWARDS_DELAY_EPOCHS))
+                .map(Epoch::new)
+            else {
+                continue;
+            };
+
+            let validator_indices: Vec<ValidatorId> = validator_store
+                .voting_pubkeys::<Vec<_>, _>(DoppelgangerStatus::only_safe)
+                .iter()
+                .filter_map(|pubkey| validator_store.validator_index(pubkey))
+                .map(ValidatorId::Index)
+                .collect();
+            if validator_indices.is_empty() {
+                continue;
+            }
+
+            match get_rewards(&beacon_nodes, epoch, &validator_indices).await {
+                Ok((expected, actual)) => {
+                    debug!(
+                        log,
+                        "Updated reward metrics";
+                        "epoch" => epoch,
+                        "expected_reward" => expected,
+                        "actual_reward" => actual,
+                    );
+                    metrics::set_gauge(&metrics::VALIDATOR_EXPECTED_REWARD, expected);
+                    metrics::set_gauge(&metrics::VALIDATOR_ACTUAL_REWARD, actual);
+                }
+                Err(e) => {
+                    warn!(
+                        log,
+                        "Unable to update reward metrics";
+                        "epoch" => epoch,
+                        "error" => e,
+                    );
+                }
+            }
+        }
+    };
+
+    context.executor.spawn(future, "reward_metrics");
+}
+
+/// Returns the total expected and actual attestation rewards of `validator_indices` in `epoch`,
+/// in Gwei.
+async fn get_rewards<T: SlotClock + 'static, E: EthSpec>(
+    beacon_nodes: &BeaconNodeFallback<T, E>,
+    epoch: Epoch,
+    validator_indices: &[ValidatorId],
+) -> Result<(i64, i64), String> {
+    let (rewards, effective_balances) = beacon_nodes
+        .first_success(
+            RequireSynced::Yes,
+            OfflineOnFailure::Yes,
+            |beacon_node| async move {
+                let rewards = beacon_node
+                    .post_beacon_rewards_attestations(epoch, validator_indices)
+                    .await
+                    .map_err(|e| format!("Failed to get attestation rewards: {:?}", e))?
+                    .data;
+                let effective_balances = beacon_node
+                    .get_beacon_states_validators(
+                        StateId::Slot(rewards_state_slot(epoch, E::slots_per_epoch())),
+                        Some(validator_indices),
+                        None,
+                    )
+                    .await
+                    .map_err(|e| format!("Failed to get validators: {:?}", e))?
+                    .ok_or("Rewards state not found")?
+                    .data
+                    .into_iter()
+                    .map(|validator| (validator.index, validator.validator.effective_balance))
+                    .collect::<HashMap<_, _>>();
+                Ok::<_, String>((rewards, effective_balances))
+            },
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(sum_rewards(&rewards, &effective_balances))
+}
+
+/// Returns the slot of the state from which the beacon node computes the attestation rewards of
+/// `epoch`, which is the last slot of the following epoch.
+fn rewards_state_slot(epoch: Epoch, slots_per_epoch: u64) -> Slot {
+    (epoch + 1).end_slot(slots_per_epoch)
+}
+
+/// Sum the expected and actual rewards of all validators in `rewards`.
+///
+/// The expected reward of a validator is the ideal reward for its effective balance in
+/// `effective_balances`, which must be read from the same state as the rewards. Effective balances
+/// from a later state may have changed, and have no matching ideal reward.
+fn sum_rewards(
+    rewards: &StandardAttestationRewards,
+    effective_balances: &HashMap<u64, u64>,
+) -> (i64, i64) {
+    let ideal_rewards = rewards
+        .ideal_rewards
+        .iter()
+        .map(|ideal| (ideal.effective_balance, ideal))
+        .collect::<HashMap<_, _>>();
+
+    let mut expected = 0i64;
+    let mut actual = 0i64;
+    for total in &rewards.total_rewards {
+        let inclusion_delay = total
+            .inclusion_delay
+            .as_ref()
+            .map_or(0, |delay| delay.value as i64);
+        actual = actual.saturating_add(
+            total.head + total.target + total.source + inclusion_delay + total.inactivity,
+        );
+
+        if let Some(ideal) = effective_balances
+            .get(&total.validator_index)
+            .and_then(|effective_balance| ideal_rewards.get(effective_balance))
+        {
+            let inclusion_delay = ideal
+                .inclusion_delay
+                .as_ref()
+                .map_or(0, |delay| delay.value);
+            let ideal_total = ideal.head + ideal.target + ideal.source + inclusion_delay;
+            expected = expected.saturating_add(ideal_total as i64);
+        }
+    }
+
+    (expected, actual)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use eth2::lighthouse::attestation_rewards::{IdealAttestationRewards, TotalAttestationRewards};
+    use types::MainnetEthSpec;
+
+    const GWEI_PER_ETH: u64 = 1_000_000_000;
+
+    fn ideal(effective_balance_eth: u64) -> IdealAttestationRewards {
+        IdealAttestationRewards {
+            effective_balance: effective_balance_eth * GWEI_PER_ETH,
+            head: effective_balance_eth * 3,
+            target: effective_balance_eth * 5,
+            source: effective_balance_eth * 2,
+            inclusion_delay: None,
+            inactivity: 0,
+            total_active_balance: 0,
+            head_participating_balance: 0,
+            target_participating_balance: 0,
+            source_participating_balance: 0,
+            is_in_inactivity_leak: false,
+        }
+    }
+
+    fn total(validator_index: u64, head: i64, target: i64, source: i64) -> TotalAttestationRewards {
+        TotalAttestationRewards {
+            validator_index,
+            head,
+            target,
+            source,
+            inclusion_delay: None,
+            inactivity: 0,
+        }
+    }
+
+    #[test]
+    fn rewards_state_is_the_last_slot_of_the_next_epoch() {
+        let slots_per_epoch = MainnetEthSpec::slots_per_epoch();
+        assert_eq!(
+            rewards_state_slot(Epoch::new(10), slots_per_epoch),
+            Slot::new(12 * slots_per_epoch - 1)
+        );
+    }
+
+    #[test]
+    fn sum_rewards_uses_effective_balances_of_rewards_state() {
+        let rewards = StandardAttestationRewards {
+            ideal_rewards: vec![ideal(31), ideal(32)],
+            total_rewards: vec![total(0, 96, 160, 64), total(1, 0, -155, -62)],
+        };
+
+        // Validator 1 had an effective balance of 31 ETH in the rewards state.
+        let effective_balances = HashMap::from([(0, 32 * GWEI_PER_ETH), (1, 31 * GWEI_PER_ETH)]);
+        assert_eq!(
+            sum_rewards(&rewards, &effective_balances),
+            (32 * 10 + 31 * 10, 320 - 217)
+        );
+
+        // Validators with effective balances without ideal rewards aren't expected any reward.
+        let effective_balances = HashMap::from([(0, 32 * GWEI_PER_ETH), (1, 30 * GWEI_PER_ETH)]);
+        assert_eq!(
+            sum_rewards(&rewards, &effective_balances),
+            (32 * 10, 320 - 217)
+        );
+    }
+}
@@ -10,7 +10,8 @@ use parking_lot::Mutex;
 use reqwest::{header::ACCEPT, Client};
 use std::path::PathBuf;
 use std::sync::Arc;
-use task_executor::TaskExecutor;
+use std::time::Instant;
+use tokio::sync::oneshot;
 use types::*;
 use url::Url;
 use web3signer::{ForkInfo, SigningRequest, SigningResponse};
@@ -27,8 +28,7 @@ pub enum Error {
     },
     Web3SignerRequestFailed(String),
     Web3SignerJsonParsingFailed(String),
-    ShuttingDown,
-    TokioJoin(String),
+    SigningThreadPoolFailed,
     MergeForkNotSupported,
     GenesisForkVersionRequired,
 }
@@ -71,6 +71,22 @@ impl<'a, E: EthSpec, Payload: AbstractExecPayload<E>> SignableMessage<'a, E, Pay
             SignableMessage::VoluntaryExit(exit) => exit.signing_root(domain),
         }
     }
+
+    /// Returns the name of the message type, for use as a metrics label.
+    pub fn metrics_label(&self) -> &'static str {
+        match self {
+            SignableMessage::RandaoReveal(_) => "randao_reveal",
+            SignableMessage::BeaconBlock(_) => "beacon_block",
+            SignableMessage::AttestationData(_) => "attestation",
+            SignableMessage::SignedAggregateAndProof(_) => "aggregate_and_proof",
+            SignableMessage::SelectionProof(_) => "selection_proof",
+            SignableMessage::SyncSelectionProof(_) => "sync_selection_proof",
+            SignableMessage::SyncCommitteeSignature { .. } => "sync_committee_message",
+            SignableMessage::SignedContributionAndProof(_) => "contribution_and_proof",
+            SignableMessage::ValidatorRegistration(_) => "validator_registration",
+            SignableMessage::VoluntaryExit(_) => "voluntary_exit",
+        }
+    }
 }
 
 /// A method used by a validator to sign messages.
@@ -102,17 +118,38 @@ pub struct SigningContext {
     pub epoch: Epoch,
     pub fork: Fork,
     pub genesis_validators_root: Hash256,
+    /// The `Hash256` to be mixed-in with the signature, computed from the other fields.
+    pub domain_hash: Hash256,
+}
+
+/// A pool of threads used to produce signatures with local keystores.
+///
+/// Signing is CPU intensive, so a dedicated pool prevents a large number of validators from
+/// saturating the blocking threads of the tokio runtime.
+pub struct SigningThreadPool {
+    pool: rayon::ThreadPool,
 }
 
-impl SigningContext {
-    /// Returns the `Hash256` to be mixed-in with the signature.
-    pub fn domain_hash(&self, spec: &ChainSpec) -> Hash256 {
-        spec.get_domain(
-            self.epoch,
-            self.domain,
-            &self.fork,
-            self.genesis_validators_root,
-        )
+impl SigningThreadPool {
+    /// Create a pool with `num_threads` threads, or one per CPU if `None`.
+    pub fn new(num_threads: Option<usize>) -> Result<Self, String> {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(num_threads.unwrap_or(0))
+            .thread_name(|i| format!("signing_{i}"))
+            .build()
+            .map_err(|e| format!("Unable to build signing thread pool: {e:?}"))?;
+        Ok(Self { pool })
+    }
+
+    /// Sign `signing_root` with `keypair` on the pool, waiting for a free thread if necessary.
+    async fn sign(&self, keypair: Arc<Keypair>, signing_root: Hash256) -> Result<Signature, Error> {
+        let (tx, rx) = oneshot::channel();
+        let queued_at = Instant::now();
+        self.pool.spawn(move || {
+            metrics::observe_duration(&metrics::SIGNING_QUEUE_TIMES, queued_at.elapsed());
+            let _ = tx.send(keypair.sk.sign(signing_root));
+        });
+        rx.await.map_err(|_| Error::SigningThreadPoolFailed)
     }
 }
 
@@ -136,13 +173,12 @@ impl SigningMethod {
         &self,
         signable_message: SignableMessage<'_, E, Payload>,
         signing_context: SigningContext,
-        spec: &ChainSpec,
-        executor: &TaskExecutor,
+        signing_pool: &SigningThreadPool,
     ) -> Result<Signature, Error> {
-        let domain_hash = signing_context.domain_hash(spec);
         let SigningContext {
             fork,
             genesis_validators_root,
+            domain_hash,
             ..
         } = signing_context;
 
@@ -153,7 +189,7 @@ impl SigningMethod {
             genesis_validators_root,
         });
 
-        self.get_signature_from_root(signable_message, signing_root, executor, fork_info)
+        self.get_signature_from_root(signable_message, signing_root, signing_pool, fork_info)
             .await
     }
 
@@ -161,26 +197,21 @@ impl SigningMethod {
         &self,
         signable_message: SignableMessage<'_, E, Payload>,
         signing_root: Hash256,
-        executor: &TaskExecutor,
+        signing_pool: &SigningThreadPool,
         fork_info: Option<ForkInfo>,
     ) -> Result<Signature, Error> {
         match self {
             SigningMethod::LocalKeystore { voting_keypair, .. } => {
                 let _timer =
                     metrics::start_timer_vec(&metrics::SIGNING_TIMES, &[metrics::LOCAL_KEYSTORE]);
+                let _latency_timer = metrics::start_timer_vec(
+                    &metrics::SIGNING_LATENCY_TIMES,
+                    &[metrics::LOCAL_KEYSTORE, signable_message.metrics_label()],
+                );
 
-                let voting_keypair = voting_keypair.clone();
-                // Spawn a blocking task to produce the signature. This avoids blocking the core
-                // tokio executor.
-                let signature = executor
-                    .spawn_blocking_handle(
-                        move || voting_keypair.sk.sign(signing_root),
-                        "local_keystore_signer",
-                    )
-                    .ok_or(Error::ShuttingDown)?
+                signing_pool
+                    .sign(voting_keypair.clone(), signing_root)
                     .await
-                    .map_err(|e| Error::TokioJoin(e.to_string()))?;
-                Ok(signature)
             }
             SigningMethod::Web3Signer {
                 signing_url,
@@ -189,6 +220,10 @@ impl SigningMethod {
             } => {
                 let _timer =
                     metrics::start_timer_vec(&metrics::SIGNING_TIMES, &[metrics::WEB3SIGNER]);
+                let _latency_timer = metrics::start_timer_vec(
+                    &metrics::SIGNING_LATENCY_TIMES,
+                    &[metrics::WEB3SIGNER, signable_message.metrics_label()],
+                );
 
                 // Map the message into a Web3Signer type.
                 let object = match signable_message {
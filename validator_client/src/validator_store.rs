@@ -1,29 +1,38 @@
 use crate::{
     doppelganger_service::DoppelgangerService,
+    event_hooks::{Event, EventHooks},
     http_metrics::metrics,
-    initialized_validators::InitializedValidators,
-    signing_method::{Error as SigningError, SignableMessage, SigningContext, SigningMethod},
+    initialized_validators::{
+        decrypt_keystores, Error as InitializedValidatorsError, InitializedValidators,
+    },
+    signing_method::{
+        Error as SigningError, SignableMessage, SigningContext, SigningMethod, SigningThreadPool,
+    },
     Config,
 };
 use account_utils::validator_definitions::{PasswordStorage, ValidatorDefinition};
+use eth2::BeaconNodeHttpClient;
 use parking_lot::{Mutex, RwLock};
+use sensitive_url::SensitiveUrl;
 use slashing_protection::{
     interchange::Interchange, InterchangeError, NotSafe, Safe, SlashingDatabase,
 };
-use slog::{crit, error, info, warn, Logger};
+use slog::{crit, debug, error, info, warn, Logger};
 use slot_clock::SlotClock;
+use std::collections::HashMap;
 use std::marker::PhantomData;
 use std::path::Path;
 use std::sync::Arc;
-use task_executor::TaskExecutor;
+use std::time::Duration;
+use tokio::time::sleep;
 use types::{
     attestation::Error as AttestationError, graffiti::GraffitiString, AbstractExecPayload, Address,
     AggregateAndProof, Attestation, BeaconBlock, BlindedPayload, ChainSpec, ContributionAndProof,
-    Domain, Epoch, EthSpec, Fork, ForkName, Graffiti, Hash256, PublicKeyBytes, SelectionProof,
-    Signature, SignedAggregateAndProof, SignedBeaconBlock, SignedContributionAndProof, SignedRoot,
-    SignedValidatorRegistrationData, SignedVoluntaryExit, Slot, SyncAggregatorSelectionData,
-    SyncCommitteeContribution, SyncCommitteeMessage, SyncSelectionProof, SyncSubnetId,
-    ValidatorRegistrationData, VoluntaryExit,
+    Domain, Epoch, EthSpec, Fork, ForkName, Graffiti, Hash256, PublicKey, PublicKeyBytes,
+    SelectionProof, Signature, SignedAggregateAndProof, SignedBeaconBlock,
+    SignedContributionAndProof, SignedRoot, SignedValidatorRegistrationData, SignedVoluntaryExit,
+    Slot, SyncAggregatorSelectionData, SyncCommitteeContribution, SyncCommitteeMessage,
+    SyncSelectionProof, SyncSubnetId, ValidatorRegistrationData, VoluntaryExit,
 };
 
 pub use crate::doppelganger_service::DoppelgangerStatus;
@@ -53,6 +62,20 @@ impl From<SigningError> for Error {
 /// This acts as a maximum safe-guard against clock drift.
 const SLASHING_PROTECTION_HISTORY_EPOCHS: u64 = 512;
 
+/// The number of keystores decrypted in the background before their validators are enabled, when
+/// using `--lazy-unlock`.
+const LAZY_UNLOCK_BATCH_SIZE: usize = 64;
+
+/// The number of times to try unlocking each validator when using `--lazy-unlock`.
+const LAZY_UNLOCK_ATTEMPTS: usize = 5;
+
+/// The time to wait before retrying validators which failed to unlock, giving the user a chance to
+/// fix their keystores or passwords.
+const LAZY_UNLOCK_RETRY_DELAY: Duration = Duration::from_secs(12);
+
+/// The maximum number of `(epoch, domain)` pairs in the signing domain cache.
+const DOMAIN_CACHE_SIZE: usize = 64;
+
 /// Currently used as the default gas limit in execution clients.
 ///
 /// https://github.com/ethereum/builder-specs/issues/17
@@ -68,12 +91,20 @@ pub struct ValidatorStore<T, E: EthSpec> {
     doppelganger_service: Option<Arc<DoppelgangerService>>,
     slot_clock: T,
     fee_recipient_process: Option<Address>,
+    /// The fee recipient configured for each beacon node, which takes priority over
+    /// `fee_recipient_process` for proposals via that beacon node.
+    beacon_node_fee_recipients: Vec<(SensitiveUrl, Option<Address>)>,
     gas_limit: Option<u64>,
     builder_proposals: bool,
     enable_web3signer_slashing_protection: bool,
+    minimal_slashing_protection: bool,
     prefer_builder_proposals: bool,
     builder_boost_factor: Option<u64>,
-    task_executor: TaskExecutor,
+    signing_pool: SigningThreadPool,
+    domain_cache: Mutex<HashMap<(Epoch, Domain), Hash256>>,
+    event_hooks: Option<Arc<EventHooks>>,
+    /// The epoch in which the event hooks were last notified of an unreachable remote signer.
+    signer_unreachable_epoch: Mutex<Option<Epoch>>,
     _phantom: PhantomData<E>,
 }
 
@@ -89,7 +120,8 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         doppelganger_service: Option<Arc<DoppelgangerService>>,
         slot_clock: T,
         config: &Config,
-        task_executor: TaskExecutor,
+        signing_pool: SigningThreadPool,
+        event_hooks: Option<Arc<EventHooks>>,
         log: Logger,
     ) -> Self {
         Self {
@@ -102,12 +134,26 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             doppelganger_service,
             slot_clock,
             fee_recipient_process: config.fee_recipient,
+            beacon_node_fee_recipients: config
+                .beacon_nodes
+                .iter()
+                .cloned()
+                .enumerate()
+                .map(|(i, url)| {
+                    let fee_recipient = config.beacon_node_fee_recipients.get(i).copied();
+                    (url, fee_recipient.flatten())
+                })
+                .collect(),
             gas_limit: config.gas_limit,
             builder_proposals: config.builder_proposals,
             enable_web3signer_slashing_protection: config.enable_web3signer_slashing_protection,
+            minimal_slashing_protection: config.minimal_slashing_protection,
             prefer_builder_proposals: config.prefer_builder_proposals,
             builder_boost_factor: config.builder_boost_factor,
-            task_executor,
+            signing_pool,
+            domain_cache: Mutex::new(HashMap::new()),
+            event_hooks,
+            signer_unreachable_epoch: Mutex::new(None),
             _phantom: PhantomData,
         }
     }
@@ -208,17 +254,154 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         Ok(validator_def)
     }
 
+    /// Decrypt the keystores which were left locked at startup due to `--lazy-unlock`, enabling
+    /// their validators in batches as they're decrypted.
+    ///
+    /// Validators which fail to unlock are retried up to `LAZY_UNLOCK_ATTEMPTS` times, re-reading
+    /// their keystores and passwords each time. Returns the number of validators enabled, or an
+    /// error if some validators remain locked.
+    pub async fn unlock_pending_validators(&self) -> Result<usize, String> {
+        self.unlock_pending_validators_with_retries(LAZY_UNLOCK_ATTEMPTS, LAZY_UNLOCK_RETRY_DELAY)
+            .await
+    }
+
+    async fn unlock_pending_validators_with_retries(
+        &self,
+        attempts: usize,
+        retry_delay: Duration,
+    ) -> Result<usize, String> {
+        let mut key_cache = self.validators.write().take_unlock_key_cache();
+
+        let mut num_unlocked = 0;
+        let mut num_pending = self.validators.read().num_pending_unlock();
+        for attempt in 1..=attempts {
+            if num_pending == 0 {
+                break;
+            }
+            if attempt > 1 {
+                warn!(
+                    self.log,
+                    "Retrying validators which failed to unlock";
+                    "validators" => num_pending,
+                    "attempt" => attempt,
+                );
+                sleep(retry_delay).await;
+            }
+
+            let pending_keystores = self.validators.read().pending_unlock_keystores();
+            let mut pending = vec![];
+            for (voting_public_key, result) in pending_keystores {
+                match result {
+                    Ok(locked) => pending.push(locked),
+                    Err(e) => self.log_unlock_failure(&voting_public_key, &e),
+                }
+            }
+
+            while !pending.is_empty() {
+                let batch = pending
+                    .drain(..pending.len().min(LAZY_UNLOCK_BATCH_SIZE))
+                    .collect();
+
+                // Decrypt without holding the validators lock, since it takes several seconds.
+                let mut unlocked_keystores = vec![];
+                for (voting_public_key, result) in decrypt_keystores(batch).await {
+                    match result {
+                        Ok(unlocked) => unlocked_keystores.push(unlocked),
+                        Err(e) => self.log_unlock_failure(&voting_public_key, &e),
+                    }
+                }
+
+                // Slashing protection registration is checked at startup for pending validators,
+                // but doppelganger protection only applies once they're unlocked.
+                for unlocked in &unlocked_keystores {
+                    if let Some(doppelganger_service) = &self.doppelganger_service {
+                        doppelganger_service.register_new_validator::<E, _>(
+                            unlocked.voting_public_key().compress(),
+                            &self.slot_clock,
+                        )?;
+                    }
+                    if let Some(key_cache) = &mut key_cache {
+                        key_cache.add(unlocked);
+                    }
+                }
+
+                let num_decrypted = unlocked_keystores.len();
+                let failures = self
+                    .validators
+                    .write()
+                    .insert_unlocked_keystores(unlocked_keystores);
+                for (voting_public_key, e) in &failures {
+                    self.log_unlock_failure(voting_public_key, e);
+                }
+                num_unlocked += num_decrypted - failures.len();
+
+                info!(
+                    self.log,
+                    "Unlocked validators";
+                    "unlocked" => num_unlocked,
+                    "remaining" => pending.len(),
+                );
+            }
+
+            if let Some(key_cache) = &mut key_cache {
+                match key_cache.save().await {
+                    Ok(true) => debug!(self.log, "Modified key_cache saved successfully"),
+                    Ok(false) => (),
+                    Err(e) => warn!(
+                        self.log,
+                        "Error during saving of key_cache";
+                        "err" => ?e,
+                    ),
+                }
+            }
+
+            num_pending = self.validators.read().num_pending_unlock();
+        }
+
+        if num_pending == 0 {
+            Ok(num_unlocked)
+        } else {
+            Err(format!(
+                "{} validators remain locked after {} attempts",
+                num_pending, attempts
+            ))
+        }
+    }
+
+    fn log_unlock_failure(
+        &self,
+        voting_public_key: &PublicKey,
+        error: &InitializedValidatorsError,
+    ) {
+        error!(
+            self.log,
+            "Failed to unlock validator";
+            "error" => ?error,
+            "validator" => ?voting_public_key,
+        );
+    }
+
     /// Returns `ProposalData` for the provided `pubkey` if it exists in `InitializedValidators`.
     /// `ProposalData` fields include defaulting logic described in `get_fee_recipient_defaulting`,
     /// `get_gas_limit_defaulting`, and `get_builder_proposals_defaulting`.
-    pub fn proposal_data(&self, pubkey: &PublicKeyBytes) -> Option<ProposalData> {
+    ///
+    /// The `beacon_node_fee_recipient` is used in place of the process level fee recipient, for
+    /// proposals via a beacon node with its own fee recipient.
+    pub fn proposal_data(
+        &self,
+        pubkey: &PublicKeyBytes,
+        beacon_node_fee_recipient: Option<Address>,
+    ) -> Option<ProposalData> {
         self.validators
             .read()
             .validator(pubkey)
             .map(|validator| ProposalData {
                 validator_index: validator.get_index(),
-                fee_recipient: self
-                    .get_fee_recipient_defaulting(validator.get_suggested_fee_recipient()),
+                fee_recipient: self.get_fee_recipient_defaulting(
+                    validator
+                        .get_suggested_fee_recipient()
+                        .or(beacon_node_fee_recipient),
+                ),
                 gas_limit: self.get_gas_limit_defaulting(validator.get_gas_limit()),
                 builder_proposals: self
                     .get_builder_proposals_defaulting(validator.get_builder_proposals()),
@@ -352,36 +535,79 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
     }
 
     fn signing_context(&self, domain: Domain, signing_epoch: Epoch) -> SigningContext {
-        if domain == Domain::VoluntaryExit {
+        let fork = if domain == Domain::VoluntaryExit {
             match self.spec.fork_name_at_epoch(signing_epoch) {
                 ForkName::Base | ForkName::Altair | ForkName::Bellatrix | ForkName::Capella => {
-                    SigningContext {
-                        domain,
-                        epoch: signing_epoch,
-                        fork: self.fork(signing_epoch),
-                        genesis_validators_root: self.genesis_validators_root,
-                    }
+                    self.fork(signing_epoch)
                 }
                 // EIP-7044
-                ForkName::Deneb | ForkName::Electra => SigningContext {
-                    domain,
+                ForkName::Deneb | ForkName::Electra => Fork {
+                    previous_version: self.spec.capella_fork_version,
+                    current_version: self.spec.capella_fork_version,
                     epoch: signing_epoch,
-                    fork: Fork {
-                        previous_version: self.spec.capella_fork_version,
-                        current_version: self.spec.capella_fork_version,
-                        epoch: signing_epoch,
-                    },
-                    genesis_validators_root: self.genesis_validators_root,
                 },
             }
         } else {
-            SigningContext {
-                domain,
-                epoch: signing_epoch,
-                fork: self.fork(signing_epoch),
-                genesis_validators_root: self.genesis_validators_root,
+            self.fork(signing_epoch)
+        };
+
+        SigningContext {
+            domain,
+            epoch: signing_epoch,
+            domain_hash: self.domain_hash(domain, signing_epoch, &fork),
+            fork,
+            genesis_validators_root: self.genesis_validators_root,
+        }
+    }
+
+    /// Returns the domain hash for `domain` at `signing_epoch`, which is derived from the fork.
+    ///
+    /// The fork is a function of the epoch and domain, so the result is cached by `(epoch, domain)`
+    /// to avoid recomputing it for every validator signing the same kind of message.
+    fn domain_hash(&self, domain: Domain, signing_epoch: Epoch, fork: &Fork) -> Hash256 {
+        let mut cache = self.domain_cache.lock();
+        if let Some(domain_hash) = cache.get(&(signing_epoch, domain)) {
+            metrics::inc_counter(&metrics::SIGNING_DOMAIN_CACHE_HITS);
+            return *domain_hash;
+        }
+        metrics::inc_counter(&metrics::SIGNING_DOMAIN_CACHE_MISSES);
+
+        if cache.len() >= DOMAIN_CACHE_SIZE {
+            // Most signatures are for the current epoch, so favour keeping recent epochs.
+            cache.retain(|(epoch, _), _| *epoch + 1 >= signing_epoch);
+            if cache.len() >= DOMAIN_CACHE_SIZE {
+                cache.clear();
             }
         }
+
+        let domain_hash =
+            self.spec
+                .get_domain(signing_epoch, domain, fork, self.genesis_validators_root);
+        cache.insert((signing_epoch, domain), domain_hash);
+        domain_hash
+    }
+
+    /// Convert a `SigningError` into an `Error`, notifying the event hooks if a remote signer could
+    /// not be reached.
+    ///
+    /// The hooks are notified at most once per epoch, rather than once for each failed signature.
+    fn signing_error(&self, e: SigningError) -> Error {
+        if let (SigningError::Web3SignerRequestFailed(error), Some(event_hooks)) =
+            (&e, &self.event_hooks)
+        {
+            let epoch = self
+                .slot_clock
+                .now()
+                .map(|slot| slot.epoch(E::slots_per_epoch()));
+            let mut last_epoch = self.signer_unreachable_epoch.lock();
+            if *last_epoch != epoch {
+                *last_epoch = epoch;
+                event_hooks.fire(Event::SignerUnreachable {
+                    error: error.clone(),
+                });
+            }
+        }
+        Error::UnableToSign(e)
     }
 
     pub async fn randao_reveal(
@@ -396,10 +622,10 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             .get_signature::<E, BlindedPayload<E>>(
                 SignableMessage::RandaoReveal(signing_epoch),
                 signing_context,
-                &self.spec,
-                &self.task_executor,
+                &self.signing_pool,
             )
-            .await?;
+            .await
+            .map_err(|e| self.signing_error(e))?;
 
         Ok(signature)
     }
@@ -423,6 +649,49 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         fee_recipient.or(self.fee_recipient_process)
     }
 
+    /// Returns the fee recipient for proposals by the given public key via `beacon_node`. The
+    /// priority order for fetching the fee recipient is:
+    /// 1. validator_definitions.yml
+    /// 2. beacon node fee recipient
+    /// 3. process level fee recipient
+    pub fn get_fee_recipient_for_beacon_node(
+        &self,
+        validator_pubkey: &PublicKeyBytes,
+        beacon_node: &BeaconNodeHttpClient,
+    ) -> Option<Address> {
+        self.get_fee_recipient_with_beacon_node_default(
+            validator_pubkey,
+            self.beacon_node_fee_recipient(beacon_node),
+        )
+    }
+
+    /// Returns the fee recipient for the given public key, using `beacon_node_fee_recipient` in
+    /// place of the process level fee recipient if it is set.
+    pub fn get_fee_recipient_with_beacon_node_default(
+        &self,
+        validator_pubkey: &PublicKeyBytes,
+        beacon_node_fee_recipient: Option<Address>,
+    ) -> Option<Address> {
+        self.get_fee_recipient_defaulting(
+            self.suggested_fee_recipient(validator_pubkey)
+                .or(beacon_node_fee_recipient),
+        )
+    }
+
+    /// Returns the fee recipient configured for `beacon_node` with `--beacon-node-fee-recipients`,
+    /// if any.
+    pub fn beacon_node_fee_recipient(&self, beacon_node: &BeaconNodeHttpClient) -> Option<Address> {
+        self.beacon_node_fee_recipients
+            .iter()
+            .find(|(url, _)| url.full == beacon_node.server().full)
+            .and_then(|(_, fee_recipient)| *fee_recipient)
+    }
+
+    /// Returns each configured beacon node, along with its fee recipient if any.
+    pub fn beacon_node_fee_recipients(&self) -> &[(SensitiveUrl, Option<Address>)] {
+        &self.beacon_node_fee_recipients
+    }
+
     /// Returns the suggested_fee_recipient from `validator_definitions.yml` if any.
     /// This has been pulled into a private function so the read lock is dropped easily
     fn suggested_fee_recipient(&self, validator_pubkey: &PublicKeyBytes) -> Option<Address> {
@@ -571,7 +840,7 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
 
         let signing_epoch = block.epoch();
         let signing_context = self.signing_context(Domain::BeaconProposer, signing_epoch);
-        let domain_hash = signing_context.domain_hash(&self.spec);
+        let domain_hash = signing_context.domain_hash;
 
         let signing_method = self.doppelganger_checked_signing_method(validator_pubkey)?;
 
@@ -597,10 +866,10 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
                     .get_signature::<E, Payload>(
                         SignableMessage::BeaconBlock(&block),
                         signing_context,
-                        &self.spec,
-                        &self.task_executor,
+                        &self.signing_pool,
                     )
-                    .await?;
+                    .await
+                    .map_err(|e| self.signing_error(e))?;
                 Ok(SignedBeaconBlock::from_block(block, signature))
             }
             Ok(Safe::SameData) => {
@@ -654,7 +923,7 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         // Checking for slashing conditions.
         let signing_epoch = attestation.data().target.epoch;
         let signing_context = self.signing_context(Domain::BeaconAttester, signing_epoch);
-        let domain_hash = signing_context.domain_hash(&self.spec);
+        let domain_hash = signing_context.domain_hash;
         let slashing_status = if signing_method
             .requires_local_slashing_protection(self.enable_web3signer_slashing_protection)
         {
@@ -674,10 +943,10 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
                     .get_signature::<E, BlindedPayload<E>>(
                         SignableMessage::AttestationData(attestation.data()),
                         signing_context,
-                        &self.spec,
-                        &self.task_executor,
+                        &self.signing_pool,
                     )
-                    .await?;
+                    .await
+                    .map_err(|e| self.signing_error(e))?;
                 attestation
                     .add_signature(&signature, validator_committee_position)
                     .map_err(Error::UnableToSignAttestation)?;
@@ -739,10 +1008,10 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             .get_signature::<E, BlindedPayload<E>>(
                 SignableMessage::VoluntaryExit(&voluntary_exit),
                 signing_context,
-                &self.spec,
-                &self.task_executor,
+                &self.signing_pool,
             )
-            .await?;
+            .await
+            .map_err(|e| self.signing_error(e))?;
 
         metrics::inc_counter_vec(&metrics::SIGNED_VOLUNTARY_EXITS_TOTAL, &[metrics::SUCCESS]);
 
@@ -765,10 +1034,11 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             .get_signature_from_root::<E, BlindedPayload<E>>(
                 SignableMessage::ValidatorRegistration(&validator_registration_data),
                 signing_root,
-                &self.task_executor,
+                &self.signing_pool,
                 None,
             )
-            .await?;
+            .await
+            .map_err(|e| self.signing_error(e))?;
 
         metrics::inc_counter_vec(
             &metrics::SIGNED_VALIDATOR_REGISTRATIONS_TOTAL,
@@ -803,10 +1073,10 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             .get_signature::<E, BlindedPayload<E>>(
                 SignableMessage::SignedAggregateAndProof(message.to_ref()),
                 signing_context,
-                &self.spec,
-                &self.task_executor,
+                &self.signing_pool,
             )
-            .await?;
+            .await
+            .map_err(|e| self.signing_error(e))?;
 
         metrics::inc_counter_vec(&metrics::SIGNED_AGGREGATES_TOTAL, &[metrics::SUCCESS]);
 
@@ -838,11 +1108,10 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             .get_signature::<E, BlindedPayload<E>>(
                 SignableMessage::SelectionProof(slot),
                 signing_context,
-                &self.spec,
-                &self.task_executor,
+                &self.signing_pool,
             )
             .await
-            .map_err(Error::UnableToSign)?;
+            .map_err(|e| self.signing_error(e))?;
 
         metrics::inc_counter_vec(&metrics::SIGNED_SELECTION_PROOFS_TOTAL, &[metrics::SUCCESS]);
 
@@ -877,11 +1146,10 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             .get_signature::<E, BlindedPayload<E>>(
                 SignableMessage::SyncSelectionProof(&message),
                 signing_context,
-                &self.spec,
-                &self.task_executor,
+                &self.signing_pool,
             )
             .await
-            .map_err(Error::UnableToSign)?;
+            .map_err(|e| self.signing_error(e))?;
 
         Ok(signature.into())
     }
@@ -906,11 +1174,10 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
                     slot,
                 },
                 signing_context,
-                &self.spec,
-                &self.task_executor,
+                &self.signing_pool,
             )
             .await
-            .map_err(Error::UnableToSign)?;
+            .map_err(|e| self.signing_error(e))?;
 
         metrics::inc_counter_vec(
             &metrics::SIGNED_SYNC_COMMITTEE_MESSAGES_TOTAL,
@@ -948,11 +1215,10 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             .get_signature::<E, BlindedPayload<E>>(
                 SignableMessage::SignedContributionAndProof(&message),
                 signing_context,
-                &self.spec,
-                &self.task_executor,
+                &self.signing_pool,
             )
             .await
-            .map_err(Error::UnableToSign)?;
+            .map_err(|e| self.signing_error(e))?;
 
         metrics::inc_counter_vec(
             &metrics::SIGNED_SYNC_COMMITTEE_CONTRIBUTIONS_TOTAL,
@@ -1011,11 +1277,36 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
     /// runs.
     pub fn prune_slashing_protection_db(&self, current_epoch: Epoch, first_run: bool) {
         // Attempt to prune every SLASHING_PROTECTION_HISTORY_EPOCHs, with a tolerance for
-        // missing the epoch that aligns exactly.
+        // missing the epoch that aligns exactly. With minimal slashing protection, prune every
+        // epoch instead.
         let mut last_prune = self.slashing_protection_last_prune.lock();
-        if current_epoch / SLASHING_PROTECTION_HISTORY_EPOCHS
-            <= *last_prune / SLASHING_PROTECTION_HISTORY_EPOCHS
-        {
+        let prune_interval = if self.minimal_slashing_protection {
+            1
+        } else {
+            SLASHING_PROTECTION_HISTORY_EPOCHS
+        };
+        if current_epoch / prune_interval <= *last_prune / prune_interval {
+            return;
+        }
+
+        if self.minimal_slashing_protection {
+            let _timer = metrics::start_timer(&metrics::SLASHING_PROTECTION_PRUNE_TIMES);
+            let all_pubkeys: Vec<_> = self.voting_pubkeys(DoppelgangerStatus::ignored);
+
+            if let Err(e) = self
+                .slashing_protection
+                .prune_all_to_watermarks(all_pubkeys.iter())
+            {
+                error!(
+                    self.log,
+                    "Error during pruning of slashing protection DB";
+                    "error" => ?e,
+                );
+                return;
+            }
+
+            *last_prune = current_epoch;
+            debug!(self.log, "Pruned slashing protection DB to minimal history"; "epoch" => current_epoch);
             return;
         }
 
@@ -1066,3 +1357,172 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         info!(self.log, "Completed pruning of slashing protection DB");
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use account_utils::validator_definitions::ValidatorDefinitions;
+    use eth2_keystore::{
+        json_keystore::{Kdf, Scrypt},
+        KeystoreBuilder, DKLEN,
+    };
+    use logging::test_logger;
+    use slashing_protection::SLASHING_PROTECTION_FILENAME;
+    use slot_clock::TestingSlotClock;
+    use std::fs::{self, File};
+    use std::path::PathBuf;
+    use tempfile::{tempdir, TempDir};
+    use types::{Keypair, MainnetEthSpec};
+
+    type E = MainnetEthSpec;
+
+    const PASSWORD: &str = "password";
+    const WRONG_PASSWORD: &str = "wrong password";
+    const RETRY_DELAY: Duration = Duration::from_millis(500);
+
+    /// An insecure KDF, so that keystores are cheap to decrypt.
+    fn insecure_kdf() -> Kdf {
+        Kdf::Scrypt(Scrypt {
+            dklen: DKLEN,
+            n: 2,
+            p: 1,
+            r: 8,
+            salt: vec![1; 32].into(),
+        })
+    }
+
+    /// Create `passwords.len()` keystores in `validators_dir`, each with a password file containing
+    /// the respective password. Returns the definitions and the paths of the password files.
+    fn keystore_definitions(
+        validators_dir: &TempDir,
+        passwords: &[&str],
+    ) -> (Vec<ValidatorDefinition>, Vec<PathBuf>) {
+        let mut definitions = vec![];
+        let mut password_paths = vec![];
+        for (i, password) in passwords.iter().enumerate() {
+            let keypair = Keypair::random();
+            let keystore = KeystoreBuilder::new(&keypair, PASSWORD.as_bytes(), String::new())
+                .unwrap()
+                .kdf(insecure_kdf())
+                .build()
+                .unwrap();
+            let keystore_path = validators_dir.path().join(format!("keystore_{}.json", i));
+            keystore
+                .to_json_writer(File::create(&keystore_path).unwrap())
+                .unwrap();
+
+            let password_path = validators_dir.path().join(format!("password_{}", i));
+            fs::write(&password_path, password).unwrap();
+
+            definitions.push(
+                ValidatorDefinition::new_keystore_with_password(
+                    &keystore_path,
+                    PasswordStorage::File(password_path.clone()),
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                )
+                .unwrap(),
+            );
+            password_paths.push(password_path);
+        }
+        (definitions, password_paths)
+    }
+
+    async fn lazy_unlock_validator_store(
+        validators_dir: &TempDir,
+        definitions: Vec<ValidatorDefinition>,
+    ) -> ValidatorStore<TestingSlotClock, E> {
+        let log = test_logger();
+        let config = Config {
+            validator_dir: validators_dir.path().into(),
+            lazy_unlock: true,
+            ..Config::default()
+        };
+
+        let validators = InitializedValidators::from_definitions(
+            ValidatorDefinitions::from(definitions),
+            validators_dir.path().into(),
+            config.clone(),
+            log.clone(),
+        )
+        .await
+        .unwrap();
+
+        let slashing_db_path = validators_dir.path().join(SLASHING_PROTECTION_FILENAME);
+        let slot_clock =
+            TestingSlotClock::new(Slot::new(0), Duration::from_secs(0), Duration::from_secs(1));
+
+        ValidatorStore::new(
+            validators,
+            SlashingDatabase::open_or_create(&slashing_db_path).unwrap(),
+            Hash256::repeat_byte(42),
+            E::default_spec(),
+            None,
+            slot_clock,
+            &config,
+            SigningThreadPool::new(config.signing_threads).unwrap(),
+            None,
+            log,
+        )
+    }
+
+    #[tokio::test]
+    async fn unlock_pending_validators_retries_failures() {
+        let validators_dir = tempdir().unwrap();
+        let (definitions, password_paths) =
+            keystore_definitions(&validators_dir, &[PASSWORD, WRONG_PASSWORD]);
+
+        let validator_store =
+            lazy_unlock_validator_store(&validators_dir, definitions.clone()).await;
+        let validators = validator_store.initialized_validators();
+        assert_eq!(validators.read().num_enabled(), 0);
+        assert_eq!(validators.read().num_pending_unlock(), 2);
+
+        // Both keystores are decrypted in the same batch, so once the first validator is enabled
+        // the second has failed to unlock. Fix its password before it's retried.
+        let fix_password = async {
+            while validators.read().num_enabled() == 0 {
+                sleep(Duration::from_millis(10)).await;
+            }
+            assert_eq!(validators.read().num_pending_unlock(), 1);
+            fs::write(&password_paths[1], PASSWORD).unwrap();
+        };
+
+        let (result, ()) = tokio::join!(
+            validator_store.unlock_pending_validators_with_retries(2, RETRY_DELAY),
+            fix_password
+        );
+        assert_eq!(result, Ok(2));
+        assert_eq!(validators.read().num_enabled(), 2);
+        assert_eq!(validators.read().num_pending_unlock(), 0);
+
+        // The unlocked keystores are saved to the key cache, so they aren't left pending on the
+        // next start up.
+        drop(validators);
+        drop(validator_store);
+        let validator_store = lazy_unlock_validator_store(&validators_dir, definitions).await;
+        let validators = validator_store.initialized_validators();
+        assert_eq!(validators.read().num_enabled(), 2);
+        assert_eq!(validators.read().num_pending_unlock(), 0);
+    }
+
+    #[tokio::test]
+    async fn unlock_pending_validators_gives_up_after_attempts() {
+        let validators_dir = tempdir().unwrap();
+        let (definitions, _) = keystore_definitions(&validators_dir, &[PASSWORD, WRONG_PASSWORD]);
+
+        let validator_store = lazy_unlock_validator_store(&validators_dir, definitions).await;
+        let result = validator_store
+            .unlock_pending_validators_with_retries(2, Duration::from_millis(10))
+            .await;
+        assert!(result.is_err());
+
+        let validators = validator_store.initialized_validators();
+        assert_eq!(validators.read().num_enabled(), 1);
+        assert_eq!(validators.read().num_pending_unlock(), 1);
+    }
+}
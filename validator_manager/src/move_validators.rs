@@ -385,6 +385,7 @@ async fn run<'a>(config: MoveConfig) -> Result<(), String> {
             .unwrap_or(true)
         {
             eprintln!("Skipping read-only validator {:?}", pubkey_to_move);
+            continue;
         }
 
         let request = DeleteKeystoresRequest {
@@ -684,8 +685,10 @@ mod test {
     use super::*;
     use crate::import_validators::tests::TestBuilder as ImportTestBuilder;
     use account_utils::validator_definitions::SigningDefinition;
+    use eth2::lighthouse_vc::types::Web3SignerValidatorRequest;
     use std::fs;
     use tempfile::{tempdir, TempDir};
+    use types::Keypair;
     use validator_client::http_api::{test_utils::ApiTester, Config as HttpConfig};
 
     const SRC_VC_TOKEN_FILE_NAME: &str = "src_vc_token.json";
@@ -696,6 +699,7 @@ mod test {
     struct TestBuilder {
         src_import_builder: Option<ImportTestBuilder>,
         dest_import_builder: Option<ImportTestBuilder>,
+        src_web3signer_validators: usize,
         http_config: HttpConfig,
         duplicates: usize,
         dir: TempDir,
@@ -713,6 +717,7 @@ mod test {
             Self {
                 src_import_builder: None,
                 dest_import_builder: None,
+                src_web3signer_validators: 0,
                 http_config: ApiTester::default_http_config(),
                 duplicates: 0,
                 dir,
@@ -759,6 +764,12 @@ mod test {
             self
         }
 
+        /// Add `count` Web3Signer validators to the src VC, which are reported as read-only.
+        fn with_src_web3signer_validators(mut self, count: usize) -> Self {
+            self.src_web3signer_validators = count;
+            self
+        }
+
         fn register_duplicates(mut self, num_duplicates: usize) -> Self {
             self.duplicates = num_duplicates;
             self
@@ -797,6 +808,11 @@ mod test {
                 .iter()
                 .map(|k| k.validating_pubkey)
                 .collect();
+            let src_vc_readonly_pubkeys: Vec<_> = src_vc_initial_keystores
+                .iter()
+                .filter(|k| k.readonly.unwrap_or(true))
+                .map(|k| k.validating_pubkey)
+                .collect();
             let validators = gen_validators_enum(&src_vc_initial_pubkeys);
 
             let dest_vc_token_path = self.dir.path().join(DEST_VC_TOKEN_FILE_NAME);
@@ -832,17 +848,31 @@ mod test {
 
                 match validators {
                     Validators::All => {
-                        assert!(
-                            src_vc_final_keystores.is_empty(),
-                            "all keystores should be removed from source vc"
+                        assert_eq!(
+                            src_vc_final_keystores
+                                .iter()
+                                .map(|k| k.validating_pubkey)
+                                .collect::<Vec<_>>(),
+                            src_vc_readonly_pubkeys,
+                            "all keystores except read-only ones should be removed from source vc"
                         );
                         assert_eq!(
                             dest_vc_final_keystores.len(),
                             dest_vc_initial_keystores.len() + src_vc_initial_keystores.len()
+                                - src_vc_readonly_pubkeys.len()
                                 - self.duplicates,
                             "the correct count of keystores should have been moved to the dest"
                         );
-                        for initial_keystore in &src_vc_initial_keystores {
+                        assert!(
+                            dest_vc_final_keystores
+                                .iter()
+                                .all(|k| !src_vc_readonly_pubkeys.contains(&k.validating_pubkey)),
+                            "read-only keystores should not be moved to the dest"
+                        );
+                        for initial_keystore in src_vc_initial_keystores
+                            .iter()
+                            .filter(|k| !src_vc_readonly_pubkeys.contains(&k.validating_pubkey))
+                        {
                             assert!(
                                 dest_vc_final_keystores.contains(initial_keystore),
                                 "the source keystore should be present at the dest"
@@ -1006,6 +1036,32 @@ mod test {
                 })
             }
 
+            if self.src_web3signer_validators > 0 {
+                let request: Vec<_> = (0..self.src_web3signer_validators)
+                    .map(|i| Web3SignerValidatorRequest {
+                        enable: true,
+                        description: format!("{}", i),
+                        graffiti: None,
+                        suggested_fee_recipient: None,
+                        gas_limit: None,
+                        builder_proposals: None,
+                        builder_boost_factor: None,
+                        prefer_builder_proposals: None,
+                        voting_public_key: Keypair::random().pk,
+                        url: format!("http://signer_{}.com/", i),
+                        root_certificate_path: None,
+                        request_timeout_ms: None,
+                        client_identity_path: None,
+                        client_identity_password: None,
+                    })
+                    .collect();
+                src_vc
+                    .client
+                    .post_lighthouse_validators_web3signer(&request)
+                    .await
+                    .unwrap();
+            }
+
             let dest_vc = if let Some(import_builder) = self.dest_import_builder.take() {
                 let import_test_result = import_builder.run_test().await;
                 assert!(import_test_result.result.is_ok());
@@ -1308,4 +1364,16 @@ mod test {
             .await
             .assert_ok();
     }
+
+    #[tokio::test]
+    async fn two_validators_move_all_skips_read_only() {
+        TestBuilder::new()
+            .await
+            .with_src_validators(2, 0)
+            .await
+            .with_src_web3signer_validators(1)
+            .run_test(|_| Validators::All)
+            .await
+            .assert_ok();
+    }
 }
@@ -0,0 +1,593 @@
+use super::common::*;
+use crate::import_validators::{VC_TOKEN_FLAG, VC_URL_FLAG};
+use crate::DumpConfig;
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use clap_utils::FLAG_HEADER;
+use eth2::{
+    lighthouse_vc::{
+        http_client::ValidatorClientHttpClient,
+        types::{UpdateFeeRecipientRequest, UpdateGasLimitRequest},
+    },
+    SensitiveUrl,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fmt::Debug;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use types::{Address, PublicKeyBytes};
+
+pub const CMD: &str = "update";
+pub const UPDATES_FILE_FLAG: &str = "updates-file";
+pub const DRY_RUN_FLAG: &str = "dry-run";
+
+/// The columns which may be present in a CSV updates file.
+const CSV_COLUMNS: &[&str] = &[
+    "voting_pubkey",
+    "fee_recipient",
+    "gas_limit",
+    "builder_proposals",
+];
+
+pub fn cli_app() -> Command {
+    Command::new(CMD)
+        .about(
+            "Updates the fee recipient, gas limit and builder proposal settings of validators on \
+                a validator client using the HTTP API. The settings for each validator are \
+                defined in a CSV or YAML file.",
+        )
+        .arg(
+            Arg::new("help")
+                .long("help")
+                .short('h')
+                .help("Prints help information")
+                .action(ArgAction::HelpLong)
+                .display_order(0)
+                .help_heading(FLAG_HEADER),
+        )
+        .arg(
+            Arg::new(UPDATES_FILE_FLAG)
+                .long(UPDATES_FILE_FLAG)
+                .value_name("PATH")
+                .help(
+                    "The path to a CSV (.csv) or YAML (.yaml or .yml) file containing the \
+                    settings to apply to each validator. Each entry must have a \"voting_pubkey\" \
+                    and may have a \"fee_recipient\", \"gas_limit\" and \"builder_proposals\". \
+                    Settings which are omitted, or empty in a CSV file, are left unchanged.",
+                )
+                .required(true)
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(VC_URL_FLAG)
+                .long(VC_URL_FLAG)
+                .value_name("HTTP_ADDRESS")
+                .help("A HTTP(S) address of a validator client using the keymanager-API.")
+                .default_value("http://localhost:5062")
+                .requires(VC_TOKEN_FLAG)
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(VC_TOKEN_FLAG)
+                .long(VC_TOKEN_FLAG)
+                .value_name("PATH")
+                .help("The file containing a token required by the validator client.")
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(DRY_RUN_FLAG)
+                .long(DRY_RUN_FLAG)
+                .help(
+                    "If present, print the changes which would be made to each validator \
+                    without applying them.",
+                )
+                .action(ArgAction::SetTrue)
+                .help_heading(FLAG_HEADER)
+                .display_order(0),
+        )
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct UpdateConfig {
+    pub updates_file_path: PathBuf,
+    pub vc_url: SensitiveUrl,
+    pub vc_token_path: PathBuf,
+    pub dry_run: bool,
+}
+
+impl UpdateConfig {
+    fn from_cli(matches: &ArgMatches) -> Result<Self, String> {
+        Ok(Self {
+            updates_file_path: clap_utils::parse_required(matches, UPDATES_FILE_FLAG)?,
+            vc_url: clap_utils::parse_required(matches, VC_URL_FLAG)?,
+            vc_token_path: clap_utils::parse_required(matches, VC_TOKEN_FLAG)?,
+            dry_run: matches.get_flag(DRY_RUN_FLAG),
+        })
+    }
+}
+
+/// The settings to apply to a single validator, as defined in the updates file.
+///
+/// Settings which are `None` are left unchanged.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ValidatorUpdate {
+    pub voting_pubkey: PublicKeyBytes,
+    #[serde(default)]
+    pub fee_recipient: Option<Address>,
+    #[serde(default)]
+    pub gas_limit: Option<u64>,
+    #[serde(default)]
+    pub builder_proposals: Option<bool>,
+}
+
+pub async fn cli_run(matches: &ArgMatches, dump_config: DumpConfig) -> Result<(), String> {
+    let config = UpdateConfig::from_cli(matches)?;
+    if dump_config.should_exit_early(&config)? {
+        Ok(())
+    } else {
+        run(config).await
+    }
+}
+
+async fn run(config: UpdateConfig) -> Result<(), String> {
+    let UpdateConfig {
+        updates_file_path,
+        vc_url,
+        vc_token_path,
+        dry_run,
+    } = config;
+
+    let updates = read_updates_file(&updates_file_path)?;
+
+    let mut seen = HashSet::new();
+    if let Some(duplicate) = updates
+        .iter()
+        .find(|update| !seen.insert(update.voting_pubkey))
+    {
+        return Err(format!(
+            "{:?} appears more than once in {:?}",
+            duplicate.voting_pubkey, updates_file_path
+        ));
+    }
+
+    let (http_client, keystores) = vc_http_client(vc_url.clone(), &vc_token_path).await?;
+
+    // Check that every validator exists before making any changes.
+    let known_pubkeys: HashSet<_> = keystores.iter().map(|k| k.validating_pubkey).collect();
+    let unknown_pubkeys = updates
+        .iter()
+        .filter(|update| !known_pubkeys.contains(&update.voting_pubkey))
+        .collect::<Vec<_>>();
+    if !unknown_pubkeys.is_empty() {
+        for update in &unknown_pubkeys {
+            eprintln!("{:?} is not present on {:?}", update.voting_pubkey, vc_url);
+        }
+        return Err(format!(
+            "{} validators not found on {:?}",
+            unknown_pubkeys.len(),
+            vc_url
+        ));
+    }
+
+    let mut changes = Vec::with_capacity(updates.len());
+    for update in updates {
+        let change = changed_settings(&http_client, update).await;
+        print_changes(&change);
+        changes.push(change);
+    }
+
+    if dry_run {
+        eprintln!("Dry run, no changes were made to the validator client");
+        return Ok(());
+    }
+
+    let count = changes.len();
+    for (i, change) in changes.into_iter().enumerate() {
+        apply_changes(&http_client, &change).await.map_err(|e| {
+            format!(
+                "Failed to update validator {} of {}. The preceding validators were \
+                    updated, it is safe to run this command again. Error was {}",
+                i + 1,
+                count,
+                e
+            )
+        })?;
+        eprintln!("Updated validator {} of {}", i + 1, count);
+    }
+
+    Ok(())
+}
+
+/// Reads the updates file, using its extension to determine the format.
+pub fn read_updates_file(path: &Path) -> Result<Vec<ValidatorUpdate>, String> {
+    let contents =
+        fs::read_to_string(path).map_err(|e| format!("Unable to read {:?}: {:?}", path, e))?;
+
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some("csv") => parse_csv(&contents),
+        Some("yaml") | Some("yml") => serde_yaml::from_str(&contents)
+            .map_err(|e| format!("Unable to parse YAML in {:?}: {:?}", path, e)),
+        _ => Err(format!(
+            "{:?} must have a .csv, .yaml or .yml extension",
+            path
+        )),
+    }
+}
+
+/// Parses a CSV file with a header row naming some of the `CSV_COLUMNS`.
+fn parse_csv(contents: &str) -> Result<Vec<ValidatorUpdate>, String> {
+    let mut lines = contents
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty());
+
+    let (_, header) = lines.next().ok_or("CSV file is empty")?;
+    let columns = header.split(',').map(str::trim).collect::<Vec<_>>();
+    if let Some(column) = columns.iter().find(|column| !CSV_COLUMNS.contains(column)) {
+        return Err(format!(
+            "Unknown CSV column {:?}, expected one of {:?}",
+            column, CSV_COLUMNS
+        ));
+    }
+
+    lines
+        .map(|(line_number, line)| {
+            let values = line.split(',').map(str::trim).collect::<Vec<_>>();
+            if values.len() != columns.len() {
+                return Err(format!(
+                    "Line {} has {} values, expected {}",
+                    line_number,
+                    values.len(),
+                    columns.len()
+                ));
+            }
+            let value = |column: &str| {
+                columns
+                    .iter()
+                    .position(|c| *c == column)
+                    .map(|i| values[i])
+                    .filter(|value| !value.is_empty())
+            };
+
+            Ok(ValidatorUpdate {
+                voting_pubkey: parse_csv_value(line_number, "voting_pubkey", value)?
+                    .ok_or_else(|| format!("Line {} has no voting_pubkey", line_number))?,
+                fee_recipient: parse_csv_value(line_number, "fee_recipient", value)?,
+                gas_limit: parse_csv_value(line_number, "gas_limit", value)?,
+                builder_proposals: parse_csv_value(line_number, "builder_proposals", value)?,
+            })
+        })
+        .collect()
+}
+
+fn parse_csv_value<'a, T>(
+    line_number: usize,
+    column: &str,
+    value: impl Fn(&str) -> Option<&'a str>,
+) -> Result<Option<T>, String>
+where
+    T: FromStr,
+    T::Err: Debug,
+{
+    value(column)
+        .map(T::from_str)
+        .transpose()
+        .map_err(|e| format!("Invalid {} on line {}: {:?}", column, line_number, e))
+}
+
+/// The settings of a validator which differ from those on the validator client.
+struct ValidatorChanges {
+    voting_pubkey: PublicKeyBytes,
+    fee_recipient: Option<(Option<Address>, Address)>,
+    gas_limit: Option<(Option<u64>, u64)>,
+    /// The current value is not exposed by the validator client, so this is always applied.
+    builder_proposals: Option<bool>,
+}
+
+/// Compares the `update` with the current settings on the validator client.
+///
+/// Settings which cannot be read from the validator client are assumed to have changed.
+async fn changed_settings(
+    http_client: &ValidatorClientHttpClient,
+    update: ValidatorUpdate,
+) -> ValidatorChanges {
+    let pubkey = update.voting_pubkey;
+
+    let fee_recipient = match update.fee_recipient {
+        Some(new) => {
+            let current = http_client
+                .get_fee_recipient(&pubkey)
+                .await
+                .ok()
+                .map(|response| response.ethaddress);
+            (current != Some(new)).then_some((current, new))
+        }
+        None => None,
+    };
+    let gas_limit = match update.gas_limit {
+        Some(new) => {
+            let current = http_client
+                .get_gas_limit(&pubkey)
+                .await
+                .ok()
+                .map(|response| response.gas_limit);
+            (current != Some(new)).then_some((current, new))
+        }
+        None => None,
+    };
+
+    ValidatorChanges {
+        voting_pubkey: pubkey,
+        fee_recipient,
+        gas_limit,
+        builder_proposals: update.builder_proposals,
+    }
+}
+
+fn print_changes(changes: &ValidatorChanges) {
+    let fmt_current = |current: Option<String>| current.unwrap_or_else(|| "unknown".to_string());
+
+    println!("{:?}", changes.voting_pubkey);
+    if let Some((current, new)) = changes.fee_recipient {
+        println!(
+            "  fee_recipient: {} -> {:?}",
+            fmt_current(current.map(|address| format!("{:?}", address))),
+            new
+        );
+    }
+    if let Some((current, new)) = changes.gas_limit {
+        println!(
+            "  gas_limit: {} -> {}",
+            fmt_current(current.map(|gas_limit| gas_limit.to_string())),
+            new
+        );
+    }
+    if let Some(new) = changes.builder_proposals {
+        println!("  builder_proposals: {}", new);
+    }
+    if changes.fee_recipient.is_none()
+        && changes.gas_limit.is_none()
+        && changes.builder_proposals.is_none()
+    {
+        println!("  no changes");
+    }
+}
+
+async fn apply_changes(
+    http_client: &ValidatorClientHttpClient,
+    changes: &ValidatorChanges,
+) -> Result<(), String> {
+    let pubkey = &changes.voting_pubkey;
+
+    if let Some((_, ethaddress)) = changes.fee_recipient {
+        http_client
+            .post_fee_recipient(pubkey, &UpdateFeeRecipientRequest { ethaddress })
+            .await
+            .map_err(|e| format!("failed to set fee recipient: {:?}", e))?;
+    }
+    if let Some((_, gas_limit)) = changes.gas_limit {
+        http_client
+            .post_gas_limit(pubkey, &UpdateGasLimitRequest { gas_limit })
+            .await
+            .map_err(|e| format!("failed to set gas limit: {:?}", e))?;
+    }
+    if let Some(builder_proposals) = changes.builder_proposals {
+        http_client
+            .patch_lighthouse_validators(
+                pubkey,
+                None,
+                None,
+                Some(builder_proposals),
+                None,
+                None,
+                None,
+            )
+            .await
+            .map_err(|e| format!("failed to set builder proposals: {:?}", e))?;
+    }
+
+    Ok(())
+}
+
+// The tests use crypto and are too slow in debug.
+#[cfg(not(debug_assertions))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::import_validators::tests::TestBuilder as ImportTestBuilder;
+    use tempfile::{tempdir, TempDir};
+    use validator_client::http_api::test_utils::ApiTester;
+
+    const VC_TOKEN_FILE_NAME: &str = "vc_token.json";
+    const FEE_RECIPIENT: &str = "0x00000000219ab540356cBB839Cbe05303d7705Fa";
+
+    struct TestBuilder {
+        vc: ApiTester,
+        pubkeys: Vec<PublicKeyBytes>,
+        dir: TempDir,
+    }
+
+    impl TestBuilder {
+        async fn new(count: u32) -> Self {
+            let import_test_result = ImportTestBuilder::new()
+                .await
+                .create_validators(count, 0)
+                .await
+                .run_test()
+                .await;
+            assert!(
+                import_test_result.result.is_ok(),
+                "precondition: validators are imported"
+            );
+            let vc = import_test_result.vc;
+            let pubkeys = vc
+                .client
+                .get_keystores()
+                .await
+                .unwrap()
+                .data
+                .into_iter()
+                .map(|keystore| keystore.validating_pubkey)
+                .collect();
+
+            Self {
+                vc,
+                pubkeys,
+                dir: tempdir().unwrap(),
+            }
+        }
+
+        async fn run(&self, file_name: &str, contents: &str, dry_run: bool) -> Result<(), String> {
+            let vc_token_path = self.dir.path().join(VC_TOKEN_FILE_NAME);
+            fs::write(&vc_token_path, &self.vc.api_token).unwrap();
+            let updates_file_path = self.dir.path().join(file_name);
+            fs::write(&updates_file_path, contents).unwrap();
+
+            run(UpdateConfig {
+                updates_file_path,
+                vc_url: self.vc.url.clone(),
+                vc_token_path,
+                dry_run,
+            })
+            .await
+        }
+
+        async fn fee_recipient(&self, pubkey: &PublicKeyBytes) -> Option<Address> {
+            self.vc
+                .client
+                .get_fee_recipient(pubkey)
+                .await
+                .ok()
+                .map(|response| response.ethaddress)
+        }
+
+        async fn gas_limit(&self, pubkey: &PublicKeyBytes) -> u64 {
+            self.vc
+                .client
+                .get_gas_limit(pubkey)
+                .await
+                .unwrap()
+                .gas_limit
+        }
+    }
+
+    #[test]
+    fn parse_csv_with_empty_values() {
+        let pubkey = PublicKeyBytes::empty();
+        let updates = parse_csv(&format!(
+            "voting_pubkey,gas_limit,fee_recipient\n{},36000000,\n{},,{}\n",
+            pubkey.as_hex_string(),
+            pubkey.as_hex_string(),
+            FEE_RECIPIENT
+        ))
+        .unwrap();
+
+        assert_eq!(
+            updates,
+            vec![
+                ValidatorUpdate {
+                    voting_pubkey: pubkey,
+                    fee_recipient: None,
+                    gas_limit: Some(36_000_000),
+                    builder_proposals: None,
+                },
+                ValidatorUpdate {
+                    voting_pubkey: pubkey,
+                    fee_recipient: Some(Address::from_str(FEE_RECIPIENT).unwrap()),
+                    gas_limit: None,
+                    builder_proposals: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_csv_unknown_column() {
+        parse_csv("voting_pubkey,graffiti\n").unwrap_err();
+    }
+
+    #[tokio::test]
+    async fn update_from_yaml() {
+        let tester = TestBuilder::new(2).await;
+        let (updated, unchanged) = (tester.pubkeys[0], tester.pubkeys[1]);
+        let unchanged_gas_limit = tester.gas_limit(&unchanged).await;
+
+        let yaml = format!(
+            "- voting_pubkey: \"{}\"\n  fee_recipient: \"{}\"\n  gas_limit: 36000000\n  \
+             builder_proposals: true\n",
+            updated.as_hex_string(),
+            FEE_RECIPIENT
+        );
+        tester.run("updates.yaml", &yaml, false).await.unwrap();
+
+        assert_eq!(
+            tester.fee_recipient(&updated).await,
+            Some(Address::from_str(FEE_RECIPIENT).unwrap())
+        );
+        assert_eq!(tester.gas_limit(&updated).await, 36_000_000);
+        assert_eq!(tester.gas_limit(&unchanged).await, unchanged_gas_limit);
+    }
+
+    #[tokio::test]
+    async fn update_from_csv() {
+        let tester = TestBuilder::new(2).await;
+
+        let csv = tester
+            .pubkeys
+            .iter()
+            .map(|pubkey| format!("{},36000000\n", pubkey.as_hex_string()))
+            .collect::<String>();
+        tester
+            .run(
+                "updates.csv",
+                &format!("voting_pubkey,gas_limit\n{}", csv),
+                false,
+            )
+            .await
+            .unwrap();
+
+        for pubkey in &tester.pubkeys {
+            assert_eq!(tester.gas_limit(pubkey).await, 36_000_000);
+        }
+    }
+
+    #[tokio::test]
+    async fn dry_run_makes_no_changes() {
+        let tester = TestBuilder::new(1).await;
+        let pubkey = tester.pubkeys[0];
+        let gas_limit = tester.gas_limit(&pubkey).await;
+
+        let csv = format!(
+            "voting_pubkey,gas_limit\n{},{}\n",
+            pubkey.as_hex_string(),
+            gas_limit + 1
+        );
+        tester.run("updates.csv", &csv, true).await.unwrap();
+
+        assert_eq!(tester.gas_limit(&pubkey).await, gas_limit);
+    }
+
+    #[tokio::test]
+    async fn unknown_validator() {
+        let tester = TestBuilder::new(1).await;
+        let pubkey = tester.pubkeys[0];
+        let gas_limit = tester.gas_limit(&pubkey).await;
+
+        let csv = format!(
+            "voting_pubkey,gas_limit\n{},36000000\n{},36000000\n",
+            pubkey.as_hex_string(),
+            PublicKeyBytes::empty().as_hex_string()
+        );
+        let err = tester.run("updates.csv", &csv, false).await.unwrap_err();
+        assert!(err.contains("validators not found"), "{}", err);
+
+        // No validators should be updated if any are unknown.
+        assert_eq!(tester.gas_limit(&pubkey).await, gas_limit);
+    }
+}